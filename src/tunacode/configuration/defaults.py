@@ -5,7 +5,11 @@ Default configuration values for the TunaCode CLI.
 Provides sensible defaults for user configuration and environment variables.
 """
 
-from tunacode.constants import ENV_OPENAI_BASE_URL, MAX_COMMAND_OUTPUT
+from tunacode.constants import (
+    ENV_OPENAI_BASE_URL,
+    MAX_COMMAND_OUTPUT,
+    MAX_COMMAND_OUTPUT_HISTORY,
+)
 from tunacode.types import UserConfig
 
 DEFAULT_USER_CONFIG: UserConfig = {
@@ -25,15 +29,113 @@ DEFAULT_USER_CONFIG: UserConfig = {
         "max_iterations": 40,
         "request_delay": 0.0,
         "global_request_timeout": 600.0,
+        "turn_deadline": 0.0,
         "tool_strict_validation": False,
+        "recover_partial_tool_calls": False,
+        "safe_mode": False,
         "theme": "dracula",
         "stream_agent_text": False,
+        "show_thoughts": True,
         "max_command_output": MAX_COMMAND_OUTPUT,
+        "max_command_output_history": MAX_COMMAND_OUTPUT_HISTORY,
         "max_tokens": None,
+        "model_limits": {},
+        "output_reserve_fraction": 0.1,
+        "auto_compact": True,
+        "history": {
+            "mode": "compact",
+            "window_turns": 20,
+        },
+        "fallback_providers": [],
+        "fallback_model": None,
+        "base_url_probe_path": "/models",
+        "stream_buffer_max_chars": 262_144,
+        "stream_coalescing": {
+            "window_ms": 0,
+            "max_chars": 0,
+        },
+        "system_prompt_max_tokens": None,
+        "system_prompt": {
+            "override": "",
+            "prefix": "",
+            "suffix": "",
+        },
+        "thinking_budget": None,
+        "task_decomposition": False,
+        "retain_raw_responses": False,
+        "user_message_prefix": "",
+        "user_message_suffix": "",
+        "read_file": {
+            "max_bytes": 102_400,
+            "window_head_lines": 200,
+            "window_tail_lines": 50,
+        },
         "ripgrep": {
             "timeout": 10,
             "max_results": 100,
             "enable_metrics": False,
         },
+        "command_policy": {
+            "read_only": "allow",
+            "write": "allow",
+            "network": "allow",
+            "destructive": "allow",
+        },
+        "shell": {
+            "program": "",
+            "args": [],
+            "max_capture_bytes": 1_048_576,
+            "stream_output": False,
+        },
+        "loop_detection": {
+            "threshold": 3,
+            "action": "nudge",
+        },
+        "code_wrap_mode": "wrap",
+        "terminal": {
+            "color": "auto",
+            "unicode": "auto",
+        },
+        "draft_autosave": {
+            "enabled": True,
+            "debounce_ms": 1000,
+            "max_age_hours": 24,
+        },
+        "watch": {
+            "debounce_seconds": 1.5,
+            "cooldown_seconds": 10.0,
+            "max_runs": 10,
+        },
+        "background_responses": {
+            "enabled": False,
+            "max_reconnects": 3,
+        },
+        "retry_backoff": {
+            "strategy": "full_jitter",
+            "base_delay": 0.5,
+            "max_delay": 8.0,
+        },
+        "provider_http": {},
+        "ollama": {
+            "native_api": False,
+            "keep_alive": "",
+            "options": {},
+        },
+        "prompted_tools": {
+            "models": [],
+            "format": "xml",
+        },
+        "auto_format": {
+            "enabled": False,
+            "formatters": {},
+            "timeout": 30.0,
+        },
+        "secret_redaction": {
+            "enabled": True,
+            "patterns": [],
+            "entropy_threshold": 4.5,
+            "entropy_min_length": 32,
+        },
+        "unknown_slash_commands": "error",
     },
 }
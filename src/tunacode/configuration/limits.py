@@ -10,7 +10,8 @@ This allows:
 from __future__ import annotations
 
 from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
-from tunacode.types import UserSettings
+from tunacode.constants import CommandPolicy, CommandRisk
+from tunacode.types import RipgrepSettings, SecretRedactionSettings, UserSettings
 
 from tunacode.infrastructure.cache.caches import limits_settings as limits_settings_cache
 
@@ -43,6 +44,80 @@ def get_command_limit() -> int:
     return _load_settings()["max_command_output"]
 
 
-def get_max_tokens() -> int | None:
-    """Get max response tokens. Returns None if not set (no limit)."""
-    return _load_settings()["max_tokens"]
+def get_command_history_limit() -> int:
+    """Get max command output length kept in history for the user, beyond what the model sees."""
+    return _load_settings()["max_command_output_history"]
+
+
+def get_read_max_bytes() -> int:
+    """Get the file size above which read_file returns a windowed view."""
+    return _load_settings()["read_file"]["max_bytes"]
+
+
+def get_read_window_lines() -> tuple[int, int]:
+    """Get the (first, last) line counts shown for a windowed read."""
+    read_file = _load_settings()["read_file"]
+    return read_file["window_head_lines"], read_file["window_tail_lines"]
+
+
+def get_ripgrep_settings() -> RipgrepSettings:
+    """Get the grep search timeout, default match cap and metrics switch."""
+    return _load_settings()["ripgrep"]
+
+
+def get_command_shell() -> tuple[str, list[str]]:
+    """Get the configured shell program and flags; empty values mean platform defaults."""
+    shell = _load_settings()["shell"]
+    return shell["program"], shell["args"]
+
+
+def get_command_capture_bytes() -> int:
+    """Get max bytes of each command output stream held in memory (0 = unlimited)."""
+    return _load_settings()["shell"]["max_capture_bytes"]
+
+
+def get_command_stream_output() -> bool:
+    """Get whether bash sends partial output while a command runs."""
+    return _load_settings()["shell"]["stream_output"]
+
+
+def get_max_tokens(model: str | None = None) -> int | None:
+    """Get max response tokens. Returns None if not set (no limit).
+
+    A ``settings.model_limits`` entry for ``model`` takes precedence over
+    ``settings.max_tokens``.
+    """
+    settings = _load_settings()
+    if model is not None:
+        model_limits = settings["model_limits"].get(model)
+        if model_limits is not None and model_limits["max_tokens"] is not None:
+            return model_limits["max_tokens"]
+    return settings["max_tokens"]
+
+
+def get_context_window_override(model: str) -> int | None:
+    """Get the configured context window for a model, or None to use the registry."""
+    model_limits = _load_settings()["model_limits"].get(model)
+    return model_limits["context_window"] if model_limits is not None else None
+
+
+def get_output_reserve_fraction() -> float:
+    """Get the fraction of the context window kept free for the model's response."""
+    return _load_settings()["output_reserve_fraction"]
+
+
+def get_command_policy(risk: CommandRisk) -> CommandPolicy:
+    """Get the configured bash policy for a command risk tier."""
+    policies = _load_settings()["command_policy"]
+    configured = {
+        CommandRisk.READ_ONLY: policies["read_only"],
+        CommandRisk.WRITE: policies["write"],
+        CommandRisk.NETWORK: policies["network"],
+        CommandRisk.DESTRUCTIVE: policies["destructive"],
+    }
+    return CommandPolicy(configured[risk])
+
+
+def get_secret_redaction() -> SecretRedactionSettings:
+    """Get the tool-output secret redaction settings."""
+    return _load_settings()["secret_redaction"]
@@ -72,6 +72,65 @@ def parse_model_string(model_string: str) -> tuple[str, str]:
     return (parts[0], parts[1])
 
 
+# Well-known API hosts, matched exactly or as a parent domain of the base URL host.
+BASE_URL_PROVIDER_HOSTS: dict[str, str] = {
+    "api.openai.com": "openai",
+    "api.anthropic.com": "anthropic",
+    "openrouter.ai": "openrouter",
+    "api.groq.com": "groq",
+    "api.mistral.ai": "mistral",
+    "api.deepseek.com": "deepseek",
+    "api.x.ai": "xai",
+    "generativelanguage.googleapis.com": "google",
+    "api.together.xyz": "togetherai",
+    "api.fireworks.ai": "fireworks-ai",
+    "api.cerebras.ai": "cerebras",
+}
+
+# Default ports of local inference servers, matched only on loopback hosts.
+LOCAL_BASE_URL_PROVIDER_PORTS: dict[int, str] = {
+    11434: "ollama",
+    1234: "lmstudio",
+}
+
+LOOPBACK_HOSTS = frozenset({"localhost", "127.0.0.1", "::1", "0.0.0.0"})
+
+
+def detect_provider_from_base_url(base_url: str | None) -> str | None:
+    """Return the provider id implied by a base URL host, or None if unknown."""
+    from urllib.parse import urlsplit
+
+    if not base_url or not base_url.strip():
+        return None
+    try:
+        parts = urlsplit(base_url.strip())
+        host = (parts.hostname or "").lower()
+        port = parts.port
+    except ValueError:
+        return None
+
+    if host in LOOPBACK_HOSTS:
+        return LOCAL_BASE_URL_PROVIDER_PORTS.get(port) if port is not None else None
+    for known_host, provider_id in BASE_URL_PROVIDER_HOSTS.items():
+        if host == known_host or host.endswith(f".{known_host}"):
+            return provider_id
+    return None
+
+
+def qualify_model_string(model_string: str, base_url: str | None) -> str:
+    """Prefix a bare model id with the provider detected from ``base_url``.
+
+    An explicit ``provider:`` prefix always wins. A bare id whose base URL host
+    is not recognised is returned unchanged.
+    """
+    if ":" in model_string:
+        return model_string
+    provider_id = detect_provider_from_base_url(base_url)
+    if provider_id is None:
+        return model_string
+    return f"{provider_id}:{model_string}"
+
+
 def load_models_registry() -> ModelsRegistryDocument:
     """Load bundled models.dev registry from JSON file.
 
@@ -316,9 +375,16 @@ def get_model_context_window(model_string: str) -> int:
         model_string: Full model identifier (e.g., "openrouter:openai/gpt-4.1")
 
     Returns:
-        Context window size in tokens. Falls back to DEFAULT_CONTEXT_WINDOW
-        if model is invalid, model not found, or limit not specified.
+        Context window size in tokens. A ``settings.model_limits`` override wins;
+        otherwise falls back to DEFAULT_CONTEXT_WINDOW if model is invalid,
+        model not found, or limit not specified.
     """
+    from tunacode.configuration.limits import get_context_window_override
+
+    override = get_context_window_override(model_string)
+    if override is not None:
+        return override
+
     try:
         provider_id, model_id = parse_model_string(model_string)
     except ValueError:
@@ -339,3 +405,55 @@ def get_model_context_window(model_string: str) -> int:
         return DEFAULT_CONTEXT_WINDOW
 
     return context
+
+
+def model_supports_prompt_caching(model_string: str) -> bool:
+    """Return True when the registry prices cached input reads for a model.
+
+    A ``cache_read`` cost entry is the registry's signal that the provider
+    serves repeated prompt prefixes from cache.
+    """
+    try:
+        provider_id, model_id = parse_model_string(model_string)
+    except ValueError:
+        return False
+
+    registry = _get_registry_for_read()
+    provider = _get_provider_entry(registry, provider_id)
+    model = _get_model_entry(provider, model_id)
+    if model is None:
+        return False
+
+    cost = model.get("cost")
+    if cost is None:
+        return False
+
+    return "cache_read" in cost
+
+
+def model_supports_reasoning(model_string: str) -> bool:
+    """Return True when the registry marks a model as reasoning-capable."""
+    try:
+        provider_id, model_id = parse_model_string(model_string)
+    except ValueError:
+        return False
+
+    registry = _get_registry_for_read()
+    provider = _get_provider_entry(registry, provider_id)
+    model = _get_model_entry(provider, model_id)
+    if model is None:
+        return False
+
+    return model.get("reasoning", False)
+
+
+def is_known_model(model_string: str) -> bool:
+    """Return True when ``provider:model`` names a model in the models registry."""
+    try:
+        provider_id, model_id = parse_model_string(model_string)
+    except ValueError:
+        return False
+
+    registry = _get_registry_for_read()
+    provider = _get_provider_entry(registry, provider_id)
+    return _get_model_entry(provider, model_id) is not None
@@ -0,0 +1,175 @@
+"""Validation for the provider-facing ``settings`` sections.
+
+Covers retry backoff, per-provider HTTP pools, Ollama, fallback providers and
+per-model limits.
+"""
+
+from tunacode.configuration.validators import (
+    require_bool,
+    require_choice,
+    require_float,
+    require_int,
+    require_mapping,
+    require_non_negative_int,
+    require_optional_positive_int,
+    require_str,
+    require_text,
+)
+from tunacode.constants import (
+    DEFAULT_PROVIDER_KEEPALIVE_EXPIRY_SECONDS,
+    DEFAULT_PROVIDER_MAX_CONNECTIONS,
+    DEFAULT_PROVIDER_MAX_KEEPALIVE_CONNECTIONS,
+    BackoffStrategy,
+)
+from tunacode.types import (
+    FallbackProviderSettings,
+    ModelLimitSettings,
+    ModelName,
+    OllamaSettings,
+    ProviderHttpSettings,
+    RetryBackoffSettings,
+)
+
+
+def validate_retry_backoff_settings(value: object) -> RetryBackoffSettings:
+    raw_backoff = require_mapping(value, path="settings.retry_backoff")
+    base_delay = require_float(raw_backoff["base_delay"], path="settings.retry_backoff.base_delay")
+    max_delay = require_float(raw_backoff["max_delay"], path="settings.retry_backoff.max_delay")
+    if base_delay < 0:
+        raise ValueError(f"settings.retry_backoff.base_delay must be >= 0, got {base_delay}")
+    if max_delay < base_delay:
+        raise ValueError(
+            "settings.retry_backoff.max_delay must be >= base_delay, "
+            f"got {max_delay} < {base_delay}"
+        )
+    return RetryBackoffSettings(
+        strategy=require_choice(
+            raw_backoff["strategy"],
+            path="settings.retry_backoff.strategy",
+            choices=[member.value for member in BackoffStrategy],
+        ),
+        base_delay=base_delay,
+        max_delay=max_delay,
+    )
+
+
+def _validate_provider_http_entry(value: object, *, path: str) -> ProviderHttpSettings:
+    entry = require_mapping(value, path=path)
+    max_connections = require_int(
+        entry.get("max_connections", DEFAULT_PROVIDER_MAX_CONNECTIONS),
+        path=f"{path}.max_connections",
+    )
+    if max_connections < 1:
+        raise ValueError(f"{path}.max_connections must be >= 1, got {max_connections}")
+    max_keepalive = require_non_negative_int(
+        entry.get("max_keepalive_connections", DEFAULT_PROVIDER_MAX_KEEPALIVE_CONNECTIONS),
+        path=f"{path}.max_keepalive_connections",
+    )
+    if max_keepalive > max_connections:
+        raise ValueError(
+            f"{path}.max_keepalive_connections must be <= max_connections, "
+            f"got {max_keepalive} > {max_connections}"
+        )
+    keepalive_expiry = require_float(
+        entry.get("keepalive_expiry", DEFAULT_PROVIDER_KEEPALIVE_EXPIRY_SECONDS),
+        path=f"{path}.keepalive_expiry",
+    )
+    if keepalive_expiry < 0:
+        raise ValueError(f"{path}.keepalive_expiry must be >= 0, got {keepalive_expiry}")
+    return ProviderHttpSettings(
+        max_connections=max_connections,
+        max_keepalive_connections=max_keepalive,
+        keepalive_expiry=keepalive_expiry,
+        http2=require_bool(entry.get("http2", False), path=f"{path}.http2"),
+    )
+
+
+def validate_provider_http(value: object) -> dict[str, ProviderHttpSettings]:
+    raw_providers = require_mapping(value, path="settings.provider_http")
+    return {
+        provider_id: _validate_provider_http_entry(
+            raw_entry, path=f"settings.provider_http.{provider_id}"
+        )
+        for provider_id, raw_entry in raw_providers.items()
+    }
+
+
+def validate_ollama_settings(value: object) -> OllamaSettings:
+    raw_ollama = require_mapping(value, path="settings.ollama")
+    raw_options = require_mapping(raw_ollama["options"], path="settings.ollama.options")
+    for name, option in raw_options.items():
+        if not isinstance(option, int | float | str | bool):
+            raise TypeError(
+                f"settings.ollama.options.{name} must be a number, string or bool, "
+                f"got {type(option).__name__}"
+            )
+    return OllamaSettings(
+        native_api=require_bool(raw_ollama["native_api"], path="settings.ollama.native_api"),
+        keep_alive=require_text(
+            raw_ollama["keep_alive"], path="settings.ollama.keep_alive"
+        ).strip(),
+        options=dict(raw_options),  # type: ignore[arg-type]
+    )
+
+
+def validate_fallback_providers(value: object) -> list[FallbackProviderSettings]:
+    if not isinstance(value, list):
+        raise TypeError(
+            f"settings.fallback_providers must be a list, got {type(value).__name__}"
+        )
+
+    providers: list[FallbackProviderSettings] = []
+    for index, raw_entry in enumerate(value):
+        path = f"settings.fallback_providers[{index}]"
+        entry = require_mapping(raw_entry, path=path)
+        model = require_str(entry.get("model"), path=f"{path}.model")
+        if ":" not in model:
+            raise ValueError(f"{path}.model must be 'provider:model', got '{model}'")
+        base_url = entry.get("base_url", "")
+        if not isinstance(base_url, str):
+            raise TypeError(f"{path}.base_url must be a string, got {type(base_url).__name__}")
+        providers.append(FallbackProviderSettings(model=model, base_url=base_url.strip()))
+    return providers
+
+
+def require_optional_model(value: object, *, path: str) -> ModelName | None:
+    if value is None:
+        return None
+    model = require_str(value, path=path)
+    if ":" not in model:
+        raise ValueError(f"{path} must be 'provider:model', got '{model}'")
+    return model
+
+
+def validate_model_limits(
+    value: object, *, max_tokens: int | None
+) -> dict[ModelName, ModelLimitSettings]:
+    raw_limits = require_mapping(value, path="settings.model_limits")
+
+    limits: dict[ModelName, ModelLimitSettings] = {}
+    for model, raw_entry in raw_limits.items():
+        path = f"settings.model_limits.{model}"
+        if ":" not in model:
+            raise ValueError(f"settings.model_limits keys must be 'provider:model', got '{model}'")
+        entry = require_mapping(raw_entry, path=path)
+        context_window = require_optional_positive_int(
+            entry.get("context_window"), path=f"{path}.context_window"
+        )
+        model_max_tokens = require_optional_positive_int(
+            entry.get("max_tokens"), path=f"{path}.max_tokens"
+        )
+        effective_max_tokens = model_max_tokens if model_max_tokens is not None else max_tokens
+        if (
+            context_window is not None
+            and effective_max_tokens is not None
+            and effective_max_tokens >= context_window
+        ):
+            raise ValueError(
+                f"{path}: max_tokens ({effective_max_tokens}) must be less than "
+                f"context_window ({context_window})"
+            )
+        limits[model] = ModelLimitSettings(
+            context_window=context_window,
+            max_tokens=model_max_tokens,
+        )
+    return limits
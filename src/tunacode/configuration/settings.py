@@ -25,8 +25,11 @@ class ApplicationSettings:
         self.internal_tools: list[ToolName] = [
             ToolName.BASH,
             ToolName.DISCOVER,
+            ToolName.GREP,
             ToolName.READ_FILE,
             ToolName.HASHLINE_EDIT,
+            ToolName.LIST_DIRECTORY,
             ToolName.WEB_FETCH,
             ToolName.WRITE_FILE,
+            ToolName.TASKS,
         ]
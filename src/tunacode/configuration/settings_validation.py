@@ -0,0 +1,473 @@
+"""Validation of the merged ``settings`` section of the user config.
+
+``validate_settings()`` turns the raw mapping into a typed ``UserSettings``,
+one helper per nested section; provider sections live in
+``provider_settings_validation``.
+"""
+
+import re
+import shlex
+
+from tunacode.configuration.provider_settings_validation import (
+    require_optional_model,
+    validate_fallback_providers,
+    validate_model_limits,
+    validate_ollama_settings,
+    validate_provider_http,
+    validate_retry_backoff_settings,
+)
+from tunacode.configuration.validators import (
+    require_bool,
+    require_choice,
+    require_float,
+    require_fraction,
+    require_int,
+    require_mapping,
+    require_non_negative_float,
+    require_non_negative_int,
+    require_optional_int,
+    require_str,
+    require_text,
+)
+from tunacode.constants import (
+    CodeWrapMode,
+    CommandPolicy,
+    HistoryMode,
+    LoopAction,
+    TerminalColorMode,
+    TerminalUnicodeMode,
+    UnknownCommandMode,
+)
+from tunacode.types import (
+    AutoFormatSettings,
+    BackgroundResponseSettings,
+    CommandPolicySettings,
+    DraftAutosaveSettings,
+    HistorySettings,
+    LoopDetectionSettings,
+    PromptedToolsSettings,
+    ReadFileSettings,
+    RipgrepSettings,
+    SecretRedactionSettings,
+    ShellSettings,
+    StreamCoalescingSettings,
+    SystemPromptSettings,
+    TerminalSettings,
+    UserSettings,
+    WatchSettings,
+)
+
+
+def _validate_ripgrep_settings(value: object) -> RipgrepSettings:
+    raw_ripgrep = require_mapping(value, path="settings.ripgrep")
+    return RipgrepSettings(
+        timeout=require_int(raw_ripgrep["timeout"], path="settings.ripgrep.timeout"),
+        max_results=require_int(
+            raw_ripgrep["max_results"],
+            path="settings.ripgrep.max_results",
+        ),
+        enable_metrics=require_bool(
+            raw_ripgrep["enable_metrics"],
+            path="settings.ripgrep.enable_metrics",
+        ),
+    )
+
+
+def _require_command_policy(value: object, *, path: str) -> str:
+    return require_choice(value, path=path, choices=[member.value for member in CommandPolicy])
+
+
+def _validate_command_policy_settings(value: object) -> CommandPolicySettings:
+    raw_policy = require_mapping(value, path="settings.command_policy")
+    return CommandPolicySettings(
+        read_only=_require_command_policy(
+            raw_policy["read_only"],
+            path="settings.command_policy.read_only",
+        ),
+        write=_require_command_policy(raw_policy["write"], path="settings.command_policy.write"),
+        network=_require_command_policy(
+            raw_policy["network"],
+            path="settings.command_policy.network",
+        ),
+        destructive=_require_command_policy(
+            raw_policy["destructive"],
+            path="settings.command_policy.destructive",
+        ),
+    )
+
+
+def _validate_read_file_settings(value: object) -> ReadFileSettings:
+    raw_read = require_mapping(value, path="settings.read_file")
+    max_bytes = require_int(raw_read["max_bytes"], path="settings.read_file.max_bytes")
+    if max_bytes < 1:
+        raise ValueError(f"settings.read_file.max_bytes must be >= 1, got {max_bytes}")
+    return ReadFileSettings(
+        max_bytes=max_bytes,
+        window_head_lines=require_non_negative_int(
+            raw_read["window_head_lines"], path="settings.read_file.window_head_lines"
+        ),
+        window_tail_lines=require_non_negative_int(
+            raw_read["window_tail_lines"], path="settings.read_file.window_tail_lines"
+        ),
+    )
+
+
+def _validate_shell_args(value: object) -> list[str]:
+    if not isinstance(value, list):
+        raise TypeError(f"settings.shell.args must be a list, got {type(value).__name__}")
+    return [
+        require_text(raw_arg, path=f"settings.shell.args[{index}]")
+        for index, raw_arg in enumerate(value)
+    ]
+
+
+def _validate_shell_settings(value: object) -> ShellSettings:
+    raw_shell = require_mapping(value, path="settings.shell")
+    return ShellSettings(
+        program=require_text(raw_shell["program"], path="settings.shell.program").strip(),
+        args=_validate_shell_args(raw_shell["args"]),
+        max_capture_bytes=require_non_negative_int(
+            raw_shell["max_capture_bytes"],
+            path="settings.shell.max_capture_bytes",
+        ),
+        stream_output=require_bool(
+            raw_shell["stream_output"],
+            path="settings.shell.stream_output",
+        ),
+    )
+
+
+def _validate_history_settings(value: object) -> HistorySettings:
+    raw_history = require_mapping(value, path="settings.history")
+    window_turns = require_int(raw_history["window_turns"], path="settings.history.window_turns")
+    if window_turns < 1:
+        raise ValueError(f"settings.history.window_turns must be >= 1, got {window_turns}")
+    return HistorySettings(
+        mode=require_choice(
+            raw_history["mode"],
+            path="settings.history.mode",
+            choices=[member.value for member in HistoryMode],
+        ),
+        window_turns=window_turns,
+    )
+
+
+def _validate_loop_detection_settings(value: object) -> LoopDetectionSettings:
+    raw_loop = require_mapping(value, path="settings.loop_detection")
+    threshold = require_int(raw_loop["threshold"], path="settings.loop_detection.threshold")
+    if threshold == 1 or threshold < 0:
+        raise ValueError(
+            f"settings.loop_detection.threshold must be 0 (off) or >= 2, got {threshold}"
+        )
+    return LoopDetectionSettings(
+        threshold=threshold,
+        action=require_choice(
+            raw_loop["action"],
+            path="settings.loop_detection.action",
+            choices=[member.value for member in LoopAction],
+        ),
+    )
+
+
+def _validate_stream_coalescing_settings(value: object) -> StreamCoalescingSettings:
+    raw_coalescing = require_mapping(value, path="settings.stream_coalescing")
+    limits: dict[str, int] = {}
+    for key in ("window_ms", "max_chars"):
+        path = f"settings.stream_coalescing.{key}"
+        limit = require_int(raw_coalescing[key], path=path)
+        if limit < 0:
+            raise ValueError(f"{path} must be >= 0, got {limit}")
+        limits[key] = limit
+    return StreamCoalescingSettings(window_ms=limits["window_ms"], max_chars=limits["max_chars"])
+
+
+def _validate_terminal_settings(value: object) -> TerminalSettings:
+    raw_terminal = require_mapping(value, path="settings.terminal")
+    return TerminalSettings(
+        color=require_choice(
+            raw_terminal["color"],
+            path="settings.terminal.color",
+            choices=[member.value for member in TerminalColorMode],
+        ),
+        unicode=require_choice(
+            raw_terminal["unicode"],
+            path="settings.terminal.unicode",
+            choices=[member.value for member in TerminalUnicodeMode],
+        ),
+    )
+
+
+def _validate_draft_autosave_settings(value: object) -> DraftAutosaveSettings:
+    raw_drafts = require_mapping(value, path="settings.draft_autosave")
+    max_age_hours = require_int(
+        raw_drafts["max_age_hours"], path="settings.draft_autosave.max_age_hours"
+    )
+    if max_age_hours < 1:
+        raise ValueError(
+            f"settings.draft_autosave.max_age_hours must be >= 1, got {max_age_hours}"
+        )
+    return DraftAutosaveSettings(
+        enabled=require_bool(raw_drafts["enabled"], path="settings.draft_autosave.enabled"),
+        debounce_ms=require_non_negative_int(
+            raw_drafts["debounce_ms"], path="settings.draft_autosave.debounce_ms"
+        ),
+        max_age_hours=max_age_hours,
+    )
+
+
+def _validate_watch_settings(value: object) -> WatchSettings:
+    raw_watch = require_mapping(value, path="settings.watch")
+    max_runs = require_int(raw_watch["max_runs"], path="settings.watch.max_runs")
+    if max_runs < 1:
+        raise ValueError(f"settings.watch.max_runs must be >= 1, got {max_runs}")
+    return WatchSettings(
+        debounce_seconds=require_non_negative_float(
+            raw_watch["debounce_seconds"], path="settings.watch.debounce_seconds"
+        ),
+        cooldown_seconds=require_non_negative_float(
+            raw_watch["cooldown_seconds"], path="settings.watch.cooldown_seconds"
+        ),
+        max_runs=max_runs,
+    )
+
+
+def _validate_redaction_patterns(value: object) -> list[str]:
+    path = "settings.secret_redaction.patterns"
+    if not isinstance(value, list):
+        raise TypeError(f"{path} must be a list, got {type(value).__name__}")
+    patterns: list[str] = []
+    for index, raw_pattern in enumerate(value):
+        pattern = require_str(raw_pattern, path=f"{path}[{index}]")
+        try:
+            re.compile(pattern)
+        except re.error as exc:
+            raise ValueError(f"{path}[{index}] is not a valid regex: {exc}") from exc
+        patterns.append(pattern)
+    return patterns
+
+
+def _validate_secret_redaction_settings(value: object) -> SecretRedactionSettings:
+    raw_redaction = require_mapping(value, path="settings.secret_redaction")
+    entropy_threshold = require_float(
+        raw_redaction["entropy_threshold"], path="settings.secret_redaction.entropy_threshold"
+    )
+    if entropy_threshold < 0:
+        raise ValueError(
+            "settings.secret_redaction.entropy_threshold must be >= 0 (0 = off), "
+            f"got {entropy_threshold}"
+        )
+    return SecretRedactionSettings(
+        enabled=require_bool(raw_redaction["enabled"], path="settings.secret_redaction.enabled"),
+        patterns=_validate_redaction_patterns(raw_redaction["patterns"]),
+        entropy_threshold=entropy_threshold,
+        entropy_min_length=require_non_negative_int(
+            raw_redaction["entropy_min_length"],
+            path="settings.secret_redaction.entropy_min_length",
+        ),
+    )
+
+
+def _validate_formatters(value: object) -> dict[str, str]:
+    path = "settings.auto_format.formatters"
+    raw_formatters = require_mapping(value, path=path)
+    formatters: dict[str, str] = {}
+    for pattern, raw_command in raw_formatters.items():
+        command = require_str(raw_command, path=f"{path}[{pattern!r}]")
+        try:
+            shlex.split(command)
+        except ValueError as exc:
+            raise ValueError(f"{path}[{pattern!r}] is not a valid command: {exc}") from exc
+        formatters[pattern] = command
+    return formatters
+
+
+def _validate_auto_format_settings(value: object) -> AutoFormatSettings:
+    raw_auto_format = require_mapping(value, path="settings.auto_format")
+    timeout = require_float(raw_auto_format["timeout"], path="settings.auto_format.timeout")
+    if timeout <= 0:
+        raise ValueError(f"settings.auto_format.timeout must be > 0, got {timeout}")
+    return AutoFormatSettings(
+        enabled=require_bool(raw_auto_format["enabled"], path="settings.auto_format.enabled"),
+        formatters=_validate_formatters(raw_auto_format["formatters"]),
+        timeout=timeout,
+    )
+
+
+def _validate_background_response_settings(value: object) -> BackgroundResponseSettings:
+    raw_background = require_mapping(value, path="settings.background_responses")
+    return BackgroundResponseSettings(
+        enabled=require_bool(
+            raw_background["enabled"], path="settings.background_responses.enabled"
+        ),
+        max_reconnects=require_non_negative_int(
+            raw_background["max_reconnects"],
+            path="settings.background_responses.max_reconnects",
+        ),
+    )
+
+
+def _validate_prompted_tools_settings(value: object) -> PromptedToolsSettings:
+    raw_prompted = require_mapping(value, path="settings.prompted_tools")
+    raw_models = raw_prompted["models"]
+    if not isinstance(raw_models, list):
+        raise TypeError(
+            f"settings.prompted_tools.models must be a list, got {type(raw_models).__name__}"
+        )
+    return PromptedToolsSettings(
+        models=[
+            require_str(pattern, path=f"settings.prompted_tools.models[{index}]")
+            for index, pattern in enumerate(raw_models)
+        ],
+        format=require_str(raw_prompted["format"], path="settings.prompted_tools.format"),
+    )
+
+
+def _validate_system_prompt_settings(value: object) -> SystemPromptSettings:
+    raw_prompt = require_mapping(value, path="settings.system_prompt")
+    return SystemPromptSettings(
+        override=require_text(raw_prompt["override"], path="settings.system_prompt.override"),
+        prefix=require_text(raw_prompt["prefix"], path="settings.system_prompt.prefix"),
+        suffix=require_text(raw_prompt["suffix"], path="settings.system_prompt.suffix"),
+    )
+
+
+def _validate_command_output_history(value: object, *, max_command_output: int) -> int:
+    limit = require_int(value, path="settings.max_command_output_history")
+    if limit < max_command_output:
+        raise ValueError(
+            f"settings.max_command_output_history ({limit}) must be >= "
+            f"settings.max_command_output ({max_command_output})"
+        )
+    return limit
+
+
+def _require_probe_path(value: object, *, path: str) -> str:
+    probe_path = require_text(value, path=path).strip()
+    if probe_path and not probe_path.startswith("/"):
+        raise ValueError(f"{path} must be empty or start with '/', got '{probe_path}'")
+    return probe_path
+
+
+def validate_settings(value: object) -> UserSettings:
+    raw_settings = require_mapping(value, path="settings")
+    max_tokens = require_optional_int(raw_settings["max_tokens"], path="settings.max_tokens")
+    max_command_output = require_int(
+        raw_settings["max_command_output"],
+        path="settings.max_command_output",
+    )
+    return UserSettings(
+        max_retries=require_int(raw_settings["max_retries"], path="settings.max_retries"),
+        max_iterations=require_int(
+            raw_settings["max_iterations"],
+            path="settings.max_iterations",
+        ),
+        request_delay=require_float(
+            raw_settings["request_delay"],
+            path="settings.request_delay",
+        ),
+        global_request_timeout=require_float(
+            raw_settings["global_request_timeout"],
+            path="settings.global_request_timeout",
+        ),
+        turn_deadline=require_float(
+            raw_settings["turn_deadline"],
+            path="settings.turn_deadline",
+        ),
+        tool_strict_validation=require_bool(
+            raw_settings["tool_strict_validation"],
+            path="settings.tool_strict_validation",
+        ),
+        recover_partial_tool_calls=require_bool(
+            raw_settings["recover_partial_tool_calls"],
+            path="settings.recover_partial_tool_calls",
+        ),
+        safe_mode=require_bool(raw_settings["safe_mode"], path="settings.safe_mode"),
+        theme=require_str(raw_settings["theme"], path="settings.theme"),
+        stream_agent_text=require_bool(
+            raw_settings["stream_agent_text"],
+            path="settings.stream_agent_text",
+        ),
+        show_thoughts=require_bool(
+            raw_settings["show_thoughts"],
+            path="settings.show_thoughts",
+        ),
+        max_command_output=max_command_output,
+        max_command_output_history=_validate_command_output_history(
+            raw_settings["max_command_output_history"],
+            max_command_output=max_command_output,
+        ),
+        max_tokens=max_tokens,
+        model_limits=validate_model_limits(raw_settings["model_limits"], max_tokens=max_tokens),
+        output_reserve_fraction=require_fraction(
+            raw_settings["output_reserve_fraction"],
+            path="settings.output_reserve_fraction",
+        ),
+        auto_compact=require_bool(raw_settings["auto_compact"], path="settings.auto_compact"),
+        history=_validate_history_settings(raw_settings["history"]),
+        system_prompt_max_tokens=require_optional_int(
+            raw_settings["system_prompt_max_tokens"],
+            path="settings.system_prompt_max_tokens",
+        ),
+        system_prompt=_validate_system_prompt_settings(raw_settings["system_prompt"]),
+        thinking_budget=require_optional_int(
+            raw_settings["thinking_budget"],
+            path="settings.thinking_budget",
+        ),
+        task_decomposition=require_bool(
+            raw_settings["task_decomposition"],
+            path="settings.task_decomposition",
+        ),
+        retain_raw_responses=require_bool(
+            raw_settings["retain_raw_responses"],
+            path="settings.retain_raw_responses",
+        ),
+        user_message_prefix=require_text(
+            raw_settings["user_message_prefix"],
+            path="settings.user_message_prefix",
+        ),
+        user_message_suffix=require_text(
+            raw_settings["user_message_suffix"],
+            path="settings.user_message_suffix",
+        ),
+        read_file=_validate_read_file_settings(raw_settings["read_file"]),
+        ripgrep=_validate_ripgrep_settings(raw_settings["ripgrep"]),
+        command_policy=_validate_command_policy_settings(raw_settings["command_policy"]),
+        shell=_validate_shell_settings(raw_settings["shell"]),
+        loop_detection=_validate_loop_detection_settings(raw_settings["loop_detection"]),
+        fallback_providers=validate_fallback_providers(raw_settings["fallback_providers"]),
+        fallback_model=require_optional_model(
+            raw_settings["fallback_model"],
+            path="settings.fallback_model",
+        ),
+        base_url_probe_path=_require_probe_path(
+            raw_settings["base_url_probe_path"],
+            path="settings.base_url_probe_path",
+        ),
+        stream_coalescing=_validate_stream_coalescing_settings(raw_settings["stream_coalescing"]),
+        stream_buffer_max_chars=require_non_negative_int(
+            raw_settings["stream_buffer_max_chars"],
+            path="settings.stream_buffer_max_chars",
+        ),
+        code_wrap_mode=require_choice(
+            raw_settings["code_wrap_mode"],
+            path="settings.code_wrap_mode",
+            choices=[member.value for member in CodeWrapMode],
+        ),
+        terminal=_validate_terminal_settings(raw_settings["terminal"]),
+        draft_autosave=_validate_draft_autosave_settings(raw_settings["draft_autosave"]),
+        watch=_validate_watch_settings(raw_settings["watch"]),
+        background_responses=_validate_background_response_settings(
+            raw_settings["background_responses"]
+        ),
+        retry_backoff=validate_retry_backoff_settings(raw_settings["retry_backoff"]),
+        provider_http=validate_provider_http(raw_settings["provider_http"]),
+        ollama=validate_ollama_settings(raw_settings["ollama"]),
+        prompted_tools=_validate_prompted_tools_settings(raw_settings["prompted_tools"]),
+        auto_format=_validate_auto_format_settings(raw_settings["auto_format"]),
+        secret_redaction=_validate_secret_redaction_settings(raw_settings["secret_redaction"]),
+        unknown_slash_commands=require_choice(
+            raw_settings["unknown_slash_commands"],
+            path="settings.unknown_slash_commands",
+            choices=[member.value for member in UnknownCommandMode],
+        ),
+    )
@@ -1,6 +1,7 @@
 """User configuration file management.
 
 Handles loading, saving, and updating user preferences including model selection.
+Validation of the ``settings`` section lives in ``settings_validation``.
 """
 
 import copy
@@ -10,15 +11,11 @@ from typing import Protocol
 
 from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
 from tunacode.configuration.settings import ApplicationSettings
+from tunacode.configuration.settings_validation import validate_settings
+from tunacode.configuration.validators import require_mapping, require_str
 from tunacode.constants import MODEL_PICKER_RECENT_LIMIT
 from tunacode.exceptions import ConfigurationError
-from tunacode.types import (
-    EnvConfig,
-    ModelName,
-    RipgrepSettings,
-    UserConfig,
-    UserSettings,
-)
+from tunacode.types import EnvConfig, ModelName, UserConfig
 
 
 class UserConfigSession(Protocol):
@@ -74,49 +71,8 @@ def load_config(default_config: UserConfig | None = None) -> UserConfig | None:
         raise ConfigurationError(f"Failed to load configuration: {err}") from err
 
 
-def _require_mapping(value: object, *, path: str) -> dict[str, object]:
-    if not isinstance(value, dict):
-        raise TypeError(f"{path} must be an object, got {type(value).__name__}")
-    if not all(isinstance(key, str) for key in value):
-        raise TypeError(f"{path} keys must be strings")
-    return value
-
-
-def _require_str(value: object, *, path: str) -> str:
-    if not isinstance(value, str):
-        raise TypeError(f"{path} must be a string, got {type(value).__name__}")
-    stripped = value.strip()
-    if not stripped:
-        raise ValueError(f"{path} must be a non-empty string")
-    return stripped
-
-
-def _require_int(value: object, *, path: str) -> int:
-    if isinstance(value, bool) or not isinstance(value, int):
-        raise TypeError(f"{path} must be an integer, got {type(value).__name__}")
-    return value
-
-
-def _require_optional_int(value: object, *, path: str) -> int | None:
-    if value is None:
-        return None
-    return _require_int(value, path=path)
-
-
-def _require_float(value: object, *, path: str) -> float:
-    if isinstance(value, bool) or not isinstance(value, int | float):
-        raise TypeError(f"{path} must be a float, got {type(value).__name__}")
-    return float(value)
-
-
-def _require_bool(value: object, *, path: str) -> bool:
-    if not isinstance(value, bool):
-        raise TypeError(f"{path} must be a bool, got {type(value).__name__}")
-    return value
-
-
 def _validate_env(value: object) -> EnvConfig:
-    raw_env = _require_mapping(value, path="env")
+    raw_env = require_mapping(value, path="env")
     env: EnvConfig = {}
     for key, raw_item in raw_env.items():
         if not isinstance(raw_item, str):
@@ -131,69 +87,17 @@ def _validate_recent_models(value: object) -> list[ModelName]:
 
     recent_models: list[ModelName] = []
     for index, raw_model in enumerate(value):
-        recent_models.append(_require_str(raw_model, path=f"recent_models[{index}]"))
+        recent_models.append(require_str(raw_model, path=f"recent_models[{index}]"))
     return recent_models
 
 
-def _validate_ripgrep_settings(value: object) -> RipgrepSettings:
-    raw_ripgrep = _require_mapping(value, path="settings.ripgrep")
-    return RipgrepSettings(
-        timeout=_require_int(raw_ripgrep["timeout"], path="settings.ripgrep.timeout"),
-        max_results=_require_int(
-            raw_ripgrep["max_results"],
-            path="settings.ripgrep.max_results",
-        ),
-        enable_metrics=_require_bool(
-            raw_ripgrep["enable_metrics"],
-            path="settings.ripgrep.enable_metrics",
-        ),
-    )
-
-
-def _validate_settings(value: object) -> UserSettings:
-    raw_settings = _require_mapping(value, path="settings")
-    return UserSettings(
-        max_retries=_require_int(raw_settings["max_retries"], path="settings.max_retries"),
-        max_iterations=_require_int(
-            raw_settings["max_iterations"],
-            path="settings.max_iterations",
-        ),
-        request_delay=_require_float(
-            raw_settings["request_delay"],
-            path="settings.request_delay",
-        ),
-        global_request_timeout=_require_float(
-            raw_settings["global_request_timeout"],
-            path="settings.global_request_timeout",
-        ),
-        tool_strict_validation=_require_bool(
-            raw_settings["tool_strict_validation"],
-            path="settings.tool_strict_validation",
-        ),
-        theme=_require_str(raw_settings["theme"], path="settings.theme"),
-        stream_agent_text=_require_bool(
-            raw_settings["stream_agent_text"],
-            path="settings.stream_agent_text",
-        ),
-        max_command_output=_require_int(
-            raw_settings["max_command_output"],
-            path="settings.max_command_output",
-        ),
-        max_tokens=_require_optional_int(
-            raw_settings["max_tokens"],
-            path="settings.max_tokens",
-        ),
-        ripgrep=_validate_ripgrep_settings(raw_settings["ripgrep"]),
-    )
-
-
 def validate_user_config(value: object) -> UserConfig:
-    raw_config = _require_mapping(value, path="user_config")
+    raw_config = require_mapping(value, path="user_config")
     return UserConfig(
-        default_model=_require_str(raw_config["default_model"], path="default_model"),
+        default_model=require_str(raw_config["default_model"], path="default_model"),
         recent_models=_validate_recent_models(raw_config["recent_models"]),
         env=_validate_env(raw_config["env"]),
-        settings=_validate_settings(raw_config["settings"]),
+        settings=validate_settings(raw_config["settings"]),
     )
 
 
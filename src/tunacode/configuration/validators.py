@@ -0,0 +1,88 @@
+"""Type and range checks shared by the user config validators.
+
+Each ``require_*`` helper returns the value narrowed to its type, or raises
+``TypeError``/``ValueError`` naming the offending config ``path``.
+"""
+
+
+def require_mapping(value: object, *, path: str) -> dict[str, object]:
+    if not isinstance(value, dict):
+        raise TypeError(f"{path} must be an object, got {type(value).__name__}")
+    if not all(isinstance(key, str) for key in value):
+        raise TypeError(f"{path} keys must be strings")
+    return value
+
+
+def require_str(value: object, *, path: str) -> str:
+    if not isinstance(value, str):
+        raise TypeError(f"{path} must be a string, got {type(value).__name__}")
+    stripped = value.strip()
+    if not stripped:
+        raise ValueError(f"{path} must be a non-empty string")
+    return stripped
+
+
+def require_text(value: object, *, path: str) -> str:
+    """Like ``require_str`` but keeps the value as written, empty included."""
+    if not isinstance(value, str):
+        raise TypeError(f"{path} must be a string, got {type(value).__name__}")
+    return value
+
+
+def require_int(value: object, *, path: str) -> int:
+    if isinstance(value, bool) or not isinstance(value, int):
+        raise TypeError(f"{path} must be an integer, got {type(value).__name__}")
+    return value
+
+
+def require_non_negative_int(value: object, *, path: str) -> int:
+    number = require_int(value, path=path)
+    if number < 0:
+        raise ValueError(f"{path} must be >= 0, got {number}")
+    return number
+
+
+def require_optional_int(value: object, *, path: str) -> int | None:
+    if value is None:
+        return None
+    return require_int(value, path=path)
+
+
+def require_optional_positive_int(value: object, *, path: str) -> int | None:
+    number = require_optional_int(value, path=path)
+    if number is not None and number <= 0:
+        raise ValueError(f"{path} must be > 0, got {number}")
+    return number
+
+
+def require_float(value: object, *, path: str) -> float:
+    if isinstance(value, bool) or not isinstance(value, int | float):
+        raise TypeError(f"{path} must be a float, got {type(value).__name__}")
+    return float(value)
+
+
+def require_non_negative_float(value: object, *, path: str) -> float:
+    number = require_float(value, path=path)
+    if number < 0:
+        raise ValueError(f"{path} must be >= 0, got {number}")
+    return number
+
+
+def require_fraction(value: object, *, path: str) -> float:
+    fraction = require_float(value, path=path)
+    if not 0.0 <= fraction < 1.0:
+        raise ValueError(f"{path} must be >= 0.0 and < 1.0, got {fraction}")
+    return fraction
+
+
+def require_bool(value: object, *, path: str) -> bool:
+    if not isinstance(value, bool):
+        raise TypeError(f"{path} must be a bool, got {type(value).__name__}")
+    return value
+
+
+def require_choice(value: object, *, path: str, choices: list[str]) -> str:
+    choice = require_str(value, path=path)
+    if choice not in choices:
+        raise ValueError(f"{path} must be one of {choices}, got '{choice}'")
+    return choice
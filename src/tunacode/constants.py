@@ -27,9 +27,15 @@ CONFIG_FILE_NAME = "tunacode.json"
 ENV_OPENAI_BASE_URL = "OPENAI_BASE_URL"
 
 MAX_COMMAND_OUTPUT = 5000
+MAX_COMMAND_OUTPUT_HISTORY = 50_000
+FULL_OUTPUT_DETAIL_KEY = "full_output"
 DEFAULT_CONTEXT_WINDOW = 200000
 
 MAX_CALLBACK_CONTENT = 50_000
+
+DEFAULT_PROVIDER_MAX_CONNECTIONS = 10
+DEFAULT_PROVIDER_MAX_KEEPALIVE_CONNECTIONS = 5
+DEFAULT_PROVIDER_KEEPALIVE_EXPIRY_SECONDS = 30.0
 MAX_PANEL_LINES = 20
 MIN_TOOL_PANEL_LINE_WIDTH = 4
 TOOL_PANEL_HORIZONTAL_INSET = 4  # CSS border (1+1) + padding (1+1)
@@ -57,15 +63,103 @@ class ToolName(StrEnum):
     """Enumeration of tool names."""
 
     DISCOVER = "discover"
+    GREP = "grep"
     READ_FILE = "read_file"
     WRITE_FILE = "write_file"
     HASHLINE_EDIT = "hashline_edit"
+    LIST_DIRECTORY = "list_directory"
     BASH = "bash"
     WEB_FETCH = "web_fetch"
+    TASKS = "tasks"
+
+
+class EditFailureKind(StrEnum):
+    """Why a ``hashline_edit`` call was refused, as reported by ``EditError.kind``."""
+
+    INVALID_REF = "invalid_ref"
+    NOT_READ = "not_read"
+    CONTEXT_MISMATCH = "context_mismatch"
+    OUT_OF_RANGE = "out_of_range"
+    FILE_NOT_FOUND = "file_not_found"
+    PERMISSION_DENIED = "permission_denied"
+
+
+class CommandRisk(StrEnum):
+    """Risk tiers assigned to shell commands before the bash tool runs them."""
+
+    READ_ONLY = "read_only"
+    WRITE = "write"
+    NETWORK = "network"
+    DESTRUCTIVE = "destructive"
+
+
+class CommandPolicy(StrEnum):
+    """What the bash tool does with a command of a given risk tier."""
+
+    ALLOW = "allow"
+    DENY = "deny"
+
+
+class HistoryMode(StrEnum):
+    """How old turns leave the model-facing context."""
+
+    COMPACT = "compact"
+    WINDOW = "window"
+
+
+class LoopAction(StrEnum):
+    """What the agent loop does when it detects repeated tool calls."""
+
+    WARN = "warn"
+    NUDGE = "nudge"
+    HALT = "halt"
+
+
+class CodeWrapMode(StrEnum):
+    """How code blocks handle lines wider than the viewport."""
+
+    WRAP = "wrap"
+    SCROLL = "scroll"
+    TRUNCATE = "truncate"
+
+
+class TerminalColorMode(StrEnum):
+    """Color depth used for rendering; ``auto`` detects it from the environment."""
+
+    AUTO = "auto"
+    TRUECOLOR = "truecolor"
+    COLOR_256 = "256"
+    COLOR_16 = "16"
+    NONE = "none"
+
+
+class TerminalUnicodeMode(StrEnum):
+    """Whether renderers may use unicode glyphs; ``auto`` follows the locale."""
+
+    AUTO = "auto"
+    ON = "on"
+    OFF = "off"
+
+
+class UnknownCommandMode(StrEnum):
+    """What the REPL does with a `/name` that is neither a command nor a custom prompt."""
+
+    ERROR = "error"
+    PASS_THROUGH = "pass_through"
+
+
+class BackoffStrategy(StrEnum):
+    """How retry delays are randomized around the capped exponential backoff."""
+
+    NONE = "none"
+    FULL_JITTER = "full_jitter"
+    EQUAL_JITTER = "equal_jitter"
+    DECORRELATED = "decorrelated"
 
 
 TUNACODE_HOME_DIR = ".tunacode"
 SESSIONS_SUBDIR = "sessions"
+DRAFTS_SUBDIR = "drafts"
 
 UI_COLORS = {
     "background": "#1a1a1a",
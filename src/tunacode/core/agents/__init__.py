@@ -6,3 +6,4 @@ from .main import (  # noqa: F401
     get_agent_tool,
     process_request,
 )
+from .turn_builder import TurnBuilder, TurnRequest  # noqa: F401
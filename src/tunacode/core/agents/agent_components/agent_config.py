@@ -5,21 +5,14 @@ from __future__ import annotations
 import asyncio
 import os
 import time
-from collections.abc import Awaitable, Callable, Mapping, Sequence
+from collections.abc import Awaitable, Callable, Mapping
 from pathlib import Path
-from typing import Protocol, cast
+from typing import cast
 
-import httpx
 from tinyagent.agent import Agent, AgentOptions
 from tinyagent.agent_types import (
     AgentMessage,
-    AgentTool,
-    AgentToolResult,
-    AgentToolUpdateCallback,
-    AssistantMessage,
-    AssistantMessageEvent,
     Context,
-    JsonObject,
     Model,
     SimpleStreamOptions,
     StreamFn,
@@ -46,17 +39,12 @@ from tunacode.skills.registry import list_skill_summaries
 from tunacode.skills.selection import resolve_selected_skills
 from tunacode.types import ModelName
 
-from tunacode.tools.bash import bash
-from tunacode.tools.discover import discover
-from tunacode.tools.hashline_edit import hashline_edit
-from tunacode.tools.read_file import read_file
-from tunacode.tools.web_fetch import web_fetch
-from tunacode.tools.write_file import write_file
-
 from tunacode.infrastructure.cache.caches import agents as agents_cache
 from tunacode.infrastructure.cache.caches import tunacode_context as context_cache
 
 from tunacode.core.compaction.controller import get_or_create_compaction_controller
+from tunacode.core.compaction.history_window import HistoryWindow
+from tunacode.core.compaction.tool_output_budget import fit_request_to_context_window
 from tunacode.core.logging.manager import get_logger
 from tunacode.core.types.state import SessionStateProtocol, StateManagerProtocol
 
@@ -66,10 +54,33 @@ from .agent_session_config import (
     _coerce_global_request_timeout,
     _coerce_max_iterations,
     _coerce_session_config,
+    _coerce_turn_deadline,
     _compute_agent_version,
     _normalize_session_config,
 )
+from .agent_tools import _apply_tool_concurrency_limit, _build_tools, report_command_rules
 from .agent_turn_control import build_should_stop_after_turn as _build_should_stop_after_turn
+from .background_responses import with_background_responses
+from .backoff import BackoffPolicy
+from .http_pool import ProviderHttpPolicies
+from .ollama_native import OllamaConfig, with_ollama_native
+from .prompt_assembly import PromptContext
+from .prompt_caching import apply_prompt_cache_hints
+from .prompted_tools import with_prompted_tools
+from .provider_fallback import (
+    MODEL_FALLBACK_LABEL,
+    is_model_not_found_error,
+    is_retryable_stream_error,
+    with_provider_fallback,
+)
+from .reasoning_budget import with_reasoning_budget
+from .stream_debug import _LifecycleTraceLogger, _TracedStreamResponse, with_raw_response_capture
+from .system_prompt import (
+    build_system_prompt,
+    system_prompt_providers,
+    warn_about_override_tool_gaps,
+)
+from .tool_plugins import registered_tool_plugins
 
 __all__ = [
     "get_or_create_agent",
@@ -82,82 +93,12 @@ __all__ = [
     "_build_tinyagent_model",
     "_coerce_global_request_timeout",
     "_coerce_max_iterations",
+    "_coerce_turn_deadline",
 ]
 
 ENV_OPENAI_API_KEY = "OPENAI_API_KEY"
 OPENAI_CHAT_COMPLETIONS_PATH = "/chat/completions"
 OPENROUTER_PROVIDER_ID = "openrouter"
-MAX_PARALLEL_TOOL_CALLS, MIN_PARALLEL_TOOL_CALLS = 3, 1
-MAX_STREAM_RETRY_DELAY_SECONDS = 8.0
-STREAM_RETRYABLE_STATUS_CODES = frozenset({408, 409, 425, 429})
-STREAM_RAW_EVENT_GAP_WARN_MS = 250.0
-
-ToolExecute = Callable[
-    [str, JsonObject, asyncio.Event | None, AgentToolUpdateCallback],
-    Awaitable[AgentToolResult],
-]
-
-
-class _LifecycleTraceLogger(Protocol):
-    debug_mode: bool
-
-    def lifecycle(self, message: str) -> None: ...
-    def warning(self, message: str, **kwargs: object) -> None: ...
-
-
-class _TracedStreamResponse:
-    """Wrap provider StreamResponse with timing logs for /debug sessions."""
-
-    def __init__(
-        self,
-        response: StreamResponse,
-        *,
-        logger: _LifecycleTraceLogger,
-        opened_at: float,
-        response_ready_at: float,
-    ) -> None:
-        self._response = response
-        self._logger = logger
-        self._opened_at = opened_at
-        self._response_ready_at = response_ready_at
-        self._event_count = 0
-        self._last_event_at = response_ready_at
-
-    def __aiter__(self) -> _TracedStreamResponse:
-        return self
-
-    async def __anext__(self) -> AssistantMessageEvent:
-        event = await self._response.__anext__()
-        now = time.perf_counter()
-        self._event_count += 1
-        event_type = event.type or "unknown"
-
-        if self._event_count == 1:
-            self._logger.lifecycle(
-                "Stream: "
-                f"provider_first_raw type={event_type} "
-                f"since_open={(now - self._opened_at) * 1000.0:.1f}ms "
-                f"since_response={(now - self._response_ready_at) * 1000.0:.1f}ms"
-            )
-        else:
-            gap_ms = (now - self._last_event_at) * 1000.0
-            if gap_ms >= STREAM_RAW_EVENT_GAP_WARN_MS:
-                self._logger.lifecycle(
-                    "Stream: "
-                    f"provider_raw_gap type={event_type} "
-                    f"gap={gap_ms:.1f}ms "
-                    f"count={self._event_count}"
-                )
-
-        self._last_event_at = now
-        return event
-
-    async def result(self) -> AssistantMessage:
-        started_at = time.perf_counter()
-        result = await self._response.result()
-        duration_ms = (time.perf_counter() - started_at) * 1000.0
-        self._logger.lifecycle(f"Stream: provider_result dur={duration_ms:.1f}ms")
-        return result
 
 
 async def _sleep_with_delay(total_delay: float) -> None:
@@ -206,55 +147,6 @@ def load_tunacode_context() -> str:
         raise
 
 
-def _wrap_tool_with_concurrency_limit(tool: AgentTool, *, limiter: asyncio.Semaphore) -> AgentTool:
-    raw_execute_fn = cast(object, tool.execute)
-    if raw_execute_fn is None:
-        raise ValueError(f"Tool '{tool.name}' must define an execute handler")
-    typed_execute_fn = cast(ToolExecute, raw_execute_fn)
-
-    async def _execute_with_limit(
-        tool_call_id: str,
-        args: JsonObject,
-        signal: asyncio.Event | None,
-        on_update: AgentToolUpdateCallback,
-    ) -> AgentToolResult:
-        await limiter.acquire()
-        try:
-            return await typed_execute_fn(tool_call_id, args, signal, on_update)
-        finally:
-            limiter.release()
-
-    tool.execute = _execute_with_limit
-    return tool
-
-
-def _apply_tool_concurrency_limit(
-    tools: Sequence[AgentTool],
-    *,
-    max_parallel_tool_calls: int = MAX_PARALLEL_TOOL_CALLS,
-) -> list[AgentTool]:
-    if max_parallel_tool_calls < MIN_PARALLEL_TOOL_CALLS:
-        raise ValueError(
-            f"max_parallel_tool_calls must be >= {MIN_PARALLEL_TOOL_CALLS}, "
-            f"got {max_parallel_tool_calls}"
-        )
-    limiter = asyncio.Semaphore(max_parallel_tool_calls)
-    return [_wrap_tool_with_concurrency_limit(tool, limiter=limiter) for tool in tools]
-
-
-def _build_tools(*, strict_validation: bool = False) -> list[AgentTool]:
-    _ = strict_validation
-    tools: list[AgentTool] = [
-        bash,
-        discover,
-        read_file,
-        hashline_edit,
-        web_fetch,
-        write_file,
-    ]
-    return _apply_tool_concurrency_limit(tools)
-
-
 def _normalize_chat_completions_url(base_url: str | None) -> str | None:
     if not isinstance(base_url, str):
         return None
@@ -333,24 +225,12 @@ def _merge_stream_options(
     return options.model_copy(update=update_values)
 
 
-def _is_retryable_stream_error(exc: Exception) -> bool:
-    if isinstance(exc, httpx.HTTPStatusError):
-        response = exc.response
-        if response is None:
-            return False
-        return response.status_code in STREAM_RETRYABLE_STATUS_CODES or response.status_code >= 500
-    return isinstance(exc, httpx.RequestError | TimeoutError)
-
-
-def _compute_stream_retry_delay(attempt_number: int) -> float:
-    exponent = attempt_number - 1
-    if exponent < 0:
-        exponent = 0
-    backoff_multiplier = 1 << exponent
-    exponential_delay = 0.5 * float(backoff_multiplier)
-    if exponential_delay > MAX_STREAM_RETRY_DELAY_SECONDS:
-        return MAX_STREAM_RETRY_DELAY_SECONDS
-    return exponential_delay
+async def _open_chat_completions_stream(
+    model: Model,
+    context: Context,
+    options: SimpleStreamOptions,
+) -> StreamResponse:
+    return await stream_alchemy_openai_completions(model, context, options)
 
 
 def _build_stream_fn(
@@ -358,21 +238,39 @@ def _build_stream_fn(
     request_delay: float,
     max_tokens: int | None,
     max_retries: int = 1,
+    background_max_reconnects: int | None = None,
+    backoff: BackoffPolicy | None = None,
+    provider_http: ProviderHttpPolicies = (),
+    ollama: OllamaConfig | None = None,
 ) -> StreamFn:
+    backoff = backoff or BackoffPolicy()
+    open_stream: StreamFn = _open_chat_completions_stream
+    if ollama is not None:
+        open_stream = with_ollama_native(open_stream, ollama, http_policies=dict(provider_http))
+    if background_max_reconnects is not None:
+        open_stream = with_background_responses(
+            open_stream,
+            max_reconnects=background_max_reconnects,
+            backoff=backoff,
+            http_policies=dict(provider_http),
+        )
+
     async def _stream(
         model: Model,
         context: Context,
         options: SimpleStreamOptions,
     ) -> StreamResponse:
         stream_options = _merge_stream_options(options=options, max_tokens=max_tokens)
+        context = apply_prompt_cache_hints(model, context)
         logger = get_logger()
+        retry_delays = backoff.schedule("stream_retry")
 
         for attempt in range(1, max_retries + 1):
             if request_delay > 0:
                 await _sleep_with_delay(request_delay)
             try:
                 opened_at = time.perf_counter()
-                response = await stream_alchemy_openai_completions(model, context, stream_options)
+                response = await open_stream(model, context, stream_options)
                 response_ready_at = time.perf_counter()
                 logger.lifecycle(
                     "Stream: "
@@ -388,13 +286,13 @@ def _build_stream_fn(
                     )
                 return response
             except Exception as exc:  # noqa: BLE001
-                if attempt >= max_retries or not _is_retryable_stream_error(exc):
+                if attempt >= max_retries or not is_retryable_stream_error(exc):
                     raise
                 logger.warning(
                     "Retrying provider stream request after transient error: "
                     f"attempt={attempt}/{max_retries}, error={type(exc).__name__}"
                 )
-                await _sleep_with_delay(_compute_stream_retry_delay(attempt))
+                await _sleep_with_delay(retry_delays.next_delay())
 
         raise RuntimeError("Unreachable stream retry exhaustion")
 
@@ -410,13 +308,23 @@ def _build_transform_context(
     ) -> list[AgentMessage]:
         controller = get_or_create_compaction_controller(state_manager)
         session = state_manager.session
-        compaction_outcome = await controller.check_and_compact(
-            messages,
-            max_tokens=session.conversation.max_tokens,
-            signal=signal,
-            allow_threshold=False,
+        window = HistoryWindow.from_settings(session.user_config["settings"]["history"])
+        if window.enabled:
+            kept_messages = window.apply(messages)
+        else:
+            compaction_outcome = await controller.check_and_compact(
+                messages,
+                max_tokens=session.conversation.max_tokens,
+                signal=signal,
+                allow_threshold=False,
+            )
+            kept_messages = compaction_outcome.messages
+        request_messages = controller.inject_summary_message(kept_messages)
+        return fit_request_to_context_window(
+            request_messages,
+            session.conversation.max_tokens,
+            model=session.current_model,
         )
-        return controller.inject_summary_message(compaction_outcome.messages)
 
     return _transform_context
 
@@ -424,12 +332,15 @@ def _build_transform_context(
 def _build_tinyagent_model(
     model: ModelName,
     config: SessionConfig | SessionStateProtocol,
+    *,
+    base_url_override: str | None = None,
 ) -> OpenAICompatModel:
     session_config = _coerce_session_config(config)
     provider_id, model_id = parse_model_string(model)
     base_url = _require_provider_base_url(
         provider_id,
-        _resolve_base_url(session_config, provider_id),
+        _normalize_chat_completions_url(base_url_override)
+        or _resolve_base_url(session_config, provider_id),
     )
     alchemy_api = get_provider_alchemy_api(provider_id)
     if base_url is not None and alchemy_api is not None:
@@ -446,6 +357,18 @@ def _build_tinyagent_model(
     return OpenAICompatModel(provider=provider_id, id=model_id)
 
 
+def _build_fallback_models(config: SessionConfig) -> list[OpenAICompatModel]:
+    return [
+        _build_tinyagent_model(model, config, base_url_override=base_url)
+        for model, base_url in config.settings.fallback_providers
+    ]
+
+
+def _build_model_fallback(config: SessionConfig) -> list[OpenAICompatModel]:
+    fallback_model = config.settings.fallback_model
+    return [] if fallback_model is None else [_build_tinyagent_model(fallback_model, config)]
+
+
 def _build_skills_prompt_state(session: SessionStateProtocol) -> SkillsPromptState:
     logger = get_logger()
     available_skills_started_at = time.perf_counter()
@@ -499,14 +422,44 @@ def _build_agent_options(
     config: SessionConfig,
     max_tokens: int | None,
 ) -> AgentOptions:
-    return AgentOptions(
-        stream_fn=_build_stream_fn(
+    resolve_api_key = _build_api_key_resolver(config.env)
+    stream_fn = with_reasoning_budget(
+        _build_stream_fn(
             request_delay=config.settings.request_delay,
             max_tokens=max_tokens,
             max_retries=config.settings.max_retries,
+            background_max_reconnects=config.settings.background_max_reconnects,
+            backoff=config.settings.retry_backoff,
+            provider_http=config.settings.provider_http,
+            ollama=config.settings.ollama,
+        ),
+        config.settings.thinking_budget,
+    )
+    if config.settings.retain_raw_responses:
+        stream_fn = with_raw_response_capture(
+            stream_fn,
+            lambda: session.raw_responses,
+            request_id_fn=lambda: session.runtime.request_id,
+        )
+    if config.settings.prompted_tools is not None:
+        stream_fn = with_prompted_tools(stream_fn, config.settings.prompted_tools)
+    stream_fn = with_provider_fallback(
+        stream_fn,
+        _build_model_fallback(config),
+        resolve_api_key=resolve_api_key,
+        should_fail_over=is_model_not_found_error,
+        label=MODEL_FALLBACK_LABEL,
+    )
+    return AgentOptions(
+        stream_fn=with_provider_fallback(
+            stream_fn,
+            _build_fallback_models(config),
+            resolve_api_key=resolve_api_key,
+            should_fail_over=is_retryable_stream_error,
+            backoff=config.settings.retry_backoff,
         ),
         session_id=session.session_id,
-        get_api_key=_build_api_key_resolver(config.env),
+        get_api_key=resolve_api_key,
         transform_context=_build_transform_context(state_manager),
         should_stop_after_turn=_build_should_stop_after_turn(session),
     )
@@ -539,11 +492,21 @@ def get_or_create_agent(model: ModelName, state_manager: StateManagerProtocol) -
         f"selected={len(skills_state.selected_skills)}"
     )
 
-    max_tokens = get_max_tokens()
+    max_tokens = get_max_tokens(model)
+    providers = system_prompt_providers(
+        config.settings.system_prompt,
+        task_decomposition=config.settings.task_decomposition,
+        load_base_prompt=load_system_prompt,
+        load_project_guide=load_tunacode_context,
+    )
     agent_version = _compute_agent_version(
         config.settings,
         max_tokens=max_tokens,
         skills_prompt_fingerprint=skills_state.fingerprint,
+        context_provider_labels=tuple(provider.label for provider in providers),
+        tool_plugin_keys=tuple(
+            (entry.source, entry.name, id(entry.plugin)) for entry in registered_tool_plugins()
+        ),
     )
 
     session_agent = _get_session_cached_agent(session, model)
@@ -557,16 +520,22 @@ def get_or_create_agent(model: ModelName, state_manager: StateManagerProtocol) -
         session.agent_versions.pop(model, None)
 
     base_path = Path(__file__).parent.parent.parent.parent
-    system_prompt_content = load_system_prompt(base_path, model=model)
-    tunacode_context_content = load_tunacode_context()
-    system_prompt = (
-        system_prompt_content
-        + tunacode_context_content
-        + skills_state.selected_block
-        + skills_state.available_block
+    system_prompt = build_system_prompt(
+        providers,
+        PromptContext(model=model, base_path=base_path, skills_state=skills_state),
+        budget_tokens=config.settings.system_prompt_max_tokens,
     )
 
-    tools = _build_tools(strict_validation=config.settings.tool_strict_validation)
+    tools = _build_tools(
+        strict_validation=config.settings.tool_strict_validation,
+        safe_mode=config.settings.safe_mode,
+        session_id_fn=lambda: session.session_id,
+        task_store_fn=lambda: session.tasks,
+    )
+    report_command_rules()
+    warn_about_override_tool_gaps(config.settings.system_prompt, [tool.name for tool in tools])
+    if config.settings.safe_mode:
+        logger.lifecycle(f"Init: safe_mode=on tools={','.join(tool.name for tool in tools)}")
 
     agent_build_started_at = time.perf_counter()
     agent = Agent(
@@ -578,6 +547,7 @@ def get_or_create_agent(model: ModelName, state_manager: StateManagerProtocol) -
         )
     )
     agent.set_system_prompt(system_prompt)
+    session.system_prompt = system_prompt
     agent.set_model(_build_tinyagent_model(model, config))
     agent.set_tools(tools)
 
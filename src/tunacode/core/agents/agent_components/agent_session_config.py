@@ -9,14 +9,42 @@ from dataclasses import dataclass
 from tunacode.skills.models import SelectedSkill
 from tunacode.core.types.state import SessionStateProtocol
 
+from .backoff import BackoffPolicy
+from .http_pool import ProviderHttpPolicies, provider_http_policies
+from .ollama_native import OllamaConfig
+from .prompted_tools import PromptedToolsConfig
+from .system_prompt_layers import SystemPromptLayers
+
+# Anthropic rejects thinking budgets below this; effort tiers start here too.
+MIN_THINKING_BUDGET_TOKENS = 1024
+
 
 @dataclass(frozen=True, slots=True)
 class AgentSettings:
     request_delay: float
     global_request_timeout: float | None
+    turn_deadline: float | None
     max_retries: int
     tool_strict_validation: bool
     max_iterations: int
+    recover_partial_tool_calls: bool
+    safe_mode: bool
+    loop_detection_threshold: int
+    loop_detection_action: str
+    system_prompt_max_tokens: int | None
+    fallback_providers: tuple[tuple[str, str], ...]
+    fallback_model: str | None
+    stream_coalesce_window_ms: int
+    stream_coalesce_max_chars: int
+    thinking_budget: int | None
+    task_decomposition: bool
+    retain_raw_responses: bool
+    background_max_reconnects: int | None
+    retry_backoff: BackoffPolicy
+    provider_http: ProviderHttpPolicies = ()
+    ollama: OllamaConfig | None = None
+    system_prompt: SystemPromptLayers = SystemPromptLayers()
+    prompted_tools: PromptedToolsConfig | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -44,19 +72,56 @@ def _normalize_session_config(session: SessionStateProtocol) -> SessionConfig:
     if timeout < 0.0:
         raise ValueError(f"global_request_timeout must be >= 0.0 seconds, got {timeout}")
 
+    turn_deadline = raw_settings["turn_deadline"]
+    if turn_deadline < 0.0:
+        raise ValueError(f"turn_deadline must be >= 0.0 seconds, got {turn_deadline}")
+
     env_config = {key: value.strip() for key, value in user_config["env"].items() if value.strip()}
 
     settings = AgentSettings(
         request_delay=request_delay,
         global_request_timeout=None if timeout == 0.0 else timeout,
+        turn_deadline=None if turn_deadline == 0.0 else turn_deadline,
         max_retries=raw_settings["max_retries"],
         tool_strict_validation=raw_settings["tool_strict_validation"],
         max_iterations=raw_settings["max_iterations"],
+        recover_partial_tool_calls=raw_settings["recover_partial_tool_calls"],
+        safe_mode=raw_settings["safe_mode"],
+        loop_detection_threshold=raw_settings["loop_detection"]["threshold"],
+        loop_detection_action=raw_settings["loop_detection"]["action"],
+        system_prompt_max_tokens=raw_settings["system_prompt_max_tokens"],
+        fallback_providers=tuple(
+            (entry["model"], entry["base_url"]) for entry in raw_settings["fallback_providers"]
+        ),
+        fallback_model=raw_settings["fallback_model"],
+        stream_coalesce_window_ms=raw_settings["stream_coalescing"]["window_ms"],
+        stream_coalesce_max_chars=raw_settings["stream_coalescing"]["max_chars"],
+        thinking_budget=raw_settings["thinking_budget"],
+        task_decomposition=raw_settings["task_decomposition"],
+        retain_raw_responses=raw_settings["retain_raw_responses"],
+        background_max_reconnects=(
+            raw_settings["background_responses"]["max_reconnects"]
+            if raw_settings["background_responses"]["enabled"]
+            else None
+        ),
+        retry_backoff=BackoffPolicy.from_settings(raw_settings["retry_backoff"]),
+        provider_http=provider_http_policies(raw_settings["provider_http"]),
+        ollama=OllamaConfig.from_settings(raw_settings["ollama"]),
+        system_prompt=SystemPromptLayers.from_settings(raw_settings["system_prompt"]),
+        prompted_tools=PromptedToolsConfig.from_settings(raw_settings["prompted_tools"]),
     )
     if settings.max_retries < 1:
         raise ValueError(f"max_retries must be >= 1, got {settings.max_retries}")
     if settings.max_iterations < 1:
         raise ValueError(f"max_iterations must be >= 1, got {settings.max_iterations}")
+    prompt_budget = settings.system_prompt_max_tokens
+    if prompt_budget is not None and prompt_budget < 1:
+        raise ValueError(f"system_prompt_max_tokens must be >= 1, got {prompt_budget}")
+    thinking_budget = settings.thinking_budget
+    if thinking_budget is not None and thinking_budget < MIN_THINKING_BUDGET_TOKENS:
+        raise ValueError(
+            f"thinking_budget must be >= {MIN_THINKING_BUDGET_TOKENS}, got {thinking_budget}"
+        )
     return SessionConfig(settings=settings, env=env_config)
 
 
@@ -74,24 +139,51 @@ def _coerce_global_request_timeout(session: SessionStateProtocol) -> float | Non
     return _normalize_session_config(session).settings.global_request_timeout
 
 
+def _coerce_turn_deadline(session: SessionStateProtocol) -> float | None:
+    return _normalize_session_config(session).settings.turn_deadline
+
+
 def _coerce_max_iterations(session: SessionStateProtocol) -> int:
     return _normalize_session_config(session).settings.max_iterations
 
 
+def _coerce_recover_partial_tool_calls(session: SessionStateProtocol) -> bool:
+    return _normalize_session_config(session).settings.recover_partial_tool_calls
+
+
 def _compute_agent_version(
     settings: AgentSettings,
     *,
     max_tokens: int | None,
     skills_prompt_fingerprint: str,
+    context_provider_labels: tuple[str, ...] = (),
+    tool_plugin_keys: tuple[tuple[str, str, int], ...] = (),
 ) -> int:
     return hash(
         (
             settings.max_retries,
             settings.tool_strict_validation,
+            settings.safe_mode,
             settings.request_delay,
             settings.global_request_timeout,
+            settings.loop_detection_threshold,
+            settings.loop_detection_action,
+            settings.system_prompt_max_tokens,
+            settings.fallback_providers,
+            settings.fallback_model,
+            settings.thinking_budget,
+            settings.task_decomposition,
+            settings.retain_raw_responses,
+            settings.background_max_reconnects,
+            settings.retry_backoff,
+            settings.provider_http,
+            settings.ollama,
+            settings.system_prompt,
+            settings.prompted_tools,
             max_tokens,
             3,
             skills_prompt_fingerprint,
+            context_provider_labels,
+            tool_plugin_keys,
         )
     )
@@ -3,6 +3,7 @@
 from __future__ import annotations
 
 import asyncio
+import copy
 import threading
 import time
 from typing import TYPE_CHECKING
@@ -26,7 +27,13 @@ from tinyagent.agent_types import (
     is_turn_end_event,
 )
 
-from tunacode.exceptions import AgentError
+from tunacode.types.callbacks import (
+    STEP_COMPLETED,
+    STEP_FAILED,
+    STEP_STARTED,
+    ToolProgress,
+    UsageUpdate,
+)
 from tunacode.utils.messaging import estimate_message_tokens, estimate_messages_tokens
 
 from tunacode.core.debug.usage_trace import log_usage_update
@@ -38,18 +45,60 @@ from ..helpers import (
     is_context_overflow_error,
     parse_canonical_usage,
 )
+from .agent_session_config import _coerce_recover_partial_tool_calls, _normalize_session_config
+from .delta_coalescer import build_text_coalescer
+from .partial_recovery import (
+    RECOVERY_CONTINUE_PROMPT,
+    RECOVERY_NOTICE_TEMPLATE,
+    execute_salvaged_tool_calls,
+    salvage_tool_calls,
+    trailing_error_message,
+)
+from .provider_error_hints import build_agent_error, provider_from_model
+from .reasoning_budget import reasoning_tokens_used
+from .usage_stream import interim_usage, session_total_with
 
 if TYPE_CHECKING:
     from tunacode.types import (
         ModelName,
+        NoticeCallback,
         StreamingCallback,
+        ToolProgressCallback,
         ToolResultCallback,
         ToolStartCallback,
+        UsageCallback,
     )
 
     from tunacode.core.types.state import StateManagerProtocol
 
+    from .turn_deadline import TurnDeadline
+
 _MS_PER_S = 1000
+# Starts a new reasoning segment when thinking resumes after answer text.
+THINKING_SEGMENT_SEPARATOR = "\n\n"
+# Argument shown next to the tool name in progress steps, first match wins.
+PROGRESS_LABEL_ARG_KEYS = ("command", "filepath", "file_path", "path", "pattern", "url")
+PROGRESS_LABEL_MAX_CHARS = 60
+
+
+def _progress_label(tool_name: str, args: object) -> str:
+    if not isinstance(args, dict):
+        return tool_name
+    for key in PROGRESS_LABEL_ARG_KEYS:
+        value = args.get(key)
+        if isinstance(value, str) and value.strip():
+            detail = " ".join(value.split())
+            if len(detail) > PROGRESS_LABEL_MAX_CHARS:
+                detail = detail[: PROGRESS_LABEL_MAX_CHARS - 1] + "…"
+            return f"{tool_name}: {detail}"
+    return tool_name
+
+
+def _is_text_delta_event(event: AgentEvent) -> bool:
+    if not isinstance(event, MessageUpdateEvent):
+        return False
+    assistant_event = event.assistant_message_event
+    return assistant_event is not None and assistant_event.type == "text_delta"
 
 
 class AgentStreamMixin:
@@ -63,7 +112,11 @@ class AgentStreamMixin:
         thinking_callback: StreamingCallback | None
         tool_result_callback: ToolResultCallback | None
         tool_start_callback: ToolStartCallback | None
+        tool_progress_callback: ToolProgressCallback | None
+        usage_callback: UsageCallback | None
+        notice_callback: NoticeCallback | None
         _active_stream_state: _TinyAgentStreamState | None
+        _turn_deadline: TurnDeadline
 
         def _agent_error_text(self, agent: Agent) -> str: ...
 
@@ -96,6 +149,24 @@ class AgentStreamMixin:
         conversation.messages = [*list(agent.state.messages), *external_messages]
         conversation.total_tokens = estimate_messages_tokens(conversation.messages)
 
+    async def _checkpoint_turn(self, agent: Agent, state: _TinyAgentStreamState) -> None:
+        """Save the in-flight history so a crash mid-turn leaves a resumable session.
+
+        Runs after each assistant message and again at turn end, where it only
+        saves when tool results arrived since, so a tool batch costs one write.
+        The conversation itself is only replaced at agent end; the checkpoint
+        writes what ``_persist_agent_messages`` would produce without applying it.
+        """
+        session = self.state_manager.session
+        if not session.project_id:
+            return
+        agent_messages = list(agent.state.messages)
+        if len(agent_messages) == state.checkpointed_message_count:
+            return
+        state.checkpointed_message_count = len(agent_messages)
+        external_messages = list(session.conversation.messages[state.baseline_message_count :])
+        await self.state_manager.save_session([*agent_messages, *external_messages])
+
     def _append_interrupted_partial_message(self) -> None:
         from tinyagent.agent_types import AssistantMessage
 
@@ -150,7 +221,8 @@ class AgentStreamMixin:
         state: _TinyAgentStreamState,
         baseline_message_count: int,
     ) -> bool:
-        _ = (event_obj, agent, state, baseline_message_count)
+        _ = (event_obj, baseline_message_count)
+        await self._checkpoint_turn(agent, state)
         return False
 
     async def _handle_stream_message_update(
@@ -161,10 +233,28 @@ class AgentStreamMixin:
         state: _TinyAgentStreamState,
         baseline_message_count: int,
     ) -> bool:
-        _ = (agent, state, baseline_message_count)
-        await self._handle_message_update(event_obj)
+        _ = (agent, baseline_message_count)
+        self._report_interim_usage(event_obj, state)
+        await self._handle_message_update(event_obj, state=state)
         return False
 
+    def _report_interim_usage(
+        self, event_obj: MessageUpdateEvent, state: _TinyAgentStreamState
+    ) -> None:
+        if self.usage_callback is None:
+            return
+        assistant_event = event_obj.assistant_message_event
+        partial = assistant_event.partial if assistant_event is not None else None
+        raw_usage = partial.usage if partial is not None else None
+        usage = interim_usage(raw_usage, state.running_usage)
+        if usage is None:
+            return
+        state.running_usage = usage
+        session_total = session_total_with(
+            self.state_manager.session.usage.session_total_usage, usage
+        )
+        self.usage_callback(UsageUpdate(call_usage=usage, session_total=session_total, final=False))
+
     async def _handle_stream_message_end(
         self,
         event_obj: MessageEndEvent,
@@ -173,12 +263,19 @@ class AgentStreamMixin:
         state: _TinyAgentStreamState,
         baseline_message_count: int,
     ) -> bool:
-        _ = (agent, baseline_message_count)
+        _ = baseline_message_count
         if not isinstance(event_obj.message, AssistantMessage):
             return False
+        await self._checkpoint_turn(agent, state)
         state.last_assistant_message = event_obj.message
         usage = parse_canonical_usage(event_obj.message.usage)
+        usage.reasoning = reasoning_tokens_used(event_obj.message)
         session = self.state_manager.session
+        thinking_budget = _normalize_session_config(session).settings.thinking_budget
+        if thinking_budget is not None and usage.reasoning > thinking_budget:
+            get_logger().warning(
+                f"Reasoning tokens over budget: used={usage.reasoning} budget={thinking_budget}"
+            )
         session.usage.last_call_usage = usage
         session.usage.session_total_usage.add(usage)
         log_usage_update(
@@ -188,6 +285,12 @@ class AgentStreamMixin:
             last_call_usage=session.usage.last_call_usage,
             session_total_usage=session.usage.session_total_usage,
         )
+        state.running_usage = None
+        if self.usage_callback is not None:
+            session_total = copy.deepcopy(session.usage.session_total_usage)
+            self.usage_callback(
+                UsageUpdate(call_usage=usage, session_total=session_total, final=True)
+            )
         return False
 
     async def _handle_stream_tool_execution_start(
@@ -203,8 +306,13 @@ class AgentStreamMixin:
         tool_name = event_obj.tool_name
         state.tool_start_times[tool_call_id] = time.perf_counter()
         self._mark_tool_start_batch_state(state, tool_call_id=tool_call_id)
+        label = _progress_label(tool_name, event_obj.args)
+        self._turn_deadline.tool_started(tool_call_id, label)
         if self.tool_start_callback is not None:
             self.tool_start_callback(tool_name)
+        if self.tool_progress_callback is not None:
+            state.tool_progress_labels[tool_call_id] = label
+            self.tool_progress_callback(ToolProgress(tool_call_id, label, STEP_STARTED))
         return False
 
     async def _handle_stream_tool_execution_end(
@@ -223,6 +331,12 @@ class AgentStreamMixin:
 
         state.active_tool_call_ids.discard(tool_call_id)
         self._clear_tool_batch_state_if_idle(state)
+        self._turn_deadline.tool_finished(tool_call_id)
+
+        if self.tool_progress_callback is not None:
+            label = state.tool_progress_labels.pop(tool_call_id, tool_name)
+            step_status = STEP_FAILED if event_obj.is_error else STEP_COMPLETED
+            self.tool_progress_callback(ToolProgress(tool_call_id, label, step_status))
 
         if self.tool_result_callback is None:
             return False
@@ -277,6 +391,8 @@ class AgentStreamMixin:
         state: _TinyAgentStreamState,
         baseline_message_count: int,
     ) -> bool:
+        if state.text_coalescer is not None and not _is_text_delta_event(event):
+            await state.text_coalescer.flush()
         if is_turn_end_event(event):
             return await self._handle_stream_turn_end(
                 event,
@@ -333,6 +449,8 @@ class AgentStreamMixin:
         *,
         agent: Agent,
         baseline_message_count: int,
+        prompt: str | None = None,
+        allow_partial_recovery: bool = True,
     ) -> Agent:
         """Run ``agent.stream``; on success, persistence is via AgentEnd; on failure, via cleanup.
 
@@ -341,15 +459,23 @@ class AgentStreamMixin:
         Interrupt (``CancelledError``, ``Exception``, including user abort):
         ``_handle_interrupted_stream_cleanup`` runs first and clears ``_active_stream_state``;
         the ``finally`` block here does not clear again.
+        A provider error after complete tool calls is salvaged once when
+        ``settings.recover_partial_tool_calls`` is on; see ``_recover_partial_tool_calls``.
         """
         logger = get_logger()
-        runtime = self.state_manager.session.runtime
+        session = self.state_manager.session
+        settings = _normalize_session_config(session).settings
         state = _TinyAgentStreamState(
-            runtime=runtime,
+            runtime=session.runtime,
             baseline_message_count=baseline_message_count,
             tool_start_times={},
             active_tool_call_ids=set(),
             batch_tool_call_ids=set(),
+            text_coalescer=build_text_coalescer(
+                self.streaming_callback,
+                window_ms=settings.stream_coalesce_window_ms,
+                max_chars=settings.stream_coalesce_max_chars,
+            ),
         )
         self._active_stream_state = state
         started_at = time.perf_counter()
@@ -359,7 +485,7 @@ class AgentStreamMixin:
         logger.lifecycle(f"Stream: start thread={stream_thread_id}")
         stream_completed = False
         try:
-            async for event in agent.stream(self.message):
+            async for event in agent.stream(self.message if prompt is None else prompt):
                 event_count += 1
                 if first_event_ms is None:
                     first_event_ms = (time.perf_counter() - started_at) * _MS_PER_S
@@ -371,6 +497,8 @@ class AgentStreamMixin:
                 )
                 if should_stop:
                     break
+            if state.text_coalescer is not None:
+                await state.text_coalescer.flush()
             stream_completed = True
         except (asyncio.CancelledError, Exception):
             self._handle_interrupted_stream_cleanup(
@@ -392,12 +520,55 @@ class AgentStreamMixin:
         logger.lifecycle(f"Request complete ({elapsed_ms:.0f}ms)")
 
         error_text = self._agent_error_text(agent)
-        if error_text and not is_context_overflow_error(error_text):
-            raise AgentError(error_text)
+        provider = provider_from_model(self.model)
+        if error_text and not is_context_overflow_error(error_text, provider):
+            if allow_partial_recovery and await self._recover_partial_tool_calls(agent):
+                return agent
+            raise build_agent_error(error_text, provider)
 
         return agent
 
-    async def _handle_message_update(self, event: MessageUpdateEvent) -> None:
+    async def _recover_partial_tool_calls(self, agent: Agent) -> bool:
+        """Run the complete tool calls of an errored turn, then regenerate its text once.
+
+        Partially streamed tool calls are discarded. Returns False, leaving the
+        error to the caller, when recovery is off or nothing can be salvaged.
+        """
+        session = self.state_manager.session
+        if not _coerce_recover_partial_tool_calls(session):
+            return False
+
+        messages = list(agent.state.messages)
+        errored_message = trailing_error_message(messages)
+        if errored_message is None:
+            return False
+        salvaged = salvage_tool_calls(errored_message)
+        if salvaged is None:
+            return False
+
+        tool_results = await execute_salvaged_tool_calls(salvaged, agent.state.tools)
+        notice = RECOVERY_NOTICE_TEMPLATE.format(count=len(tool_results))
+        get_logger().warning(notice)
+        if self.notice_callback is not None:
+            self.notice_callback(notice)
+
+        agent.replace_messages([*messages[:-1], salvaged, *tool_results])
+        self._persist_agent_messages(agent, len(session.conversation.messages))
+        session._debug_raw_stream_accum = ""
+        await self._run_stream(
+            agent=agent,
+            baseline_message_count=len(session.conversation.messages),
+            prompt=RECOVERY_CONTINUE_PROMPT,
+            allow_partial_recovery=False,
+        )
+        return True
+
+    async def _handle_message_update(
+        self,
+        event: MessageUpdateEvent,
+        *,
+        state: _TinyAgentStreamState | None = None,
+    ) -> None:
         assistant_event = event.assistant_message_event
         if (
             assistant_event is None
@@ -406,11 +577,23 @@ class AgentStreamMixin:
         ):
             return
 
+        coalescer = None
+        previous_delta_type = ""
+        if state is not None:
+            coalescer = state.text_coalescer
+            previous_delta_type = state.last_delta_type
+            state.last_delta_type = assistant_event.type
+
         if assistant_event.type == "text_delta":
             self.state_manager.session._debug_raw_stream_accum += assistant_event.delta
-            if self.streaming_callback is not None:
+            if coalescer is not None:
+                await coalescer.push(assistant_event.delta)
+            elif self.streaming_callback is not None:
                 await self.streaming_callback(assistant_event.delta)
             return
 
         if assistant_event.type == "thinking_delta" and self.thinking_callback is not None:
-            await self.thinking_callback(assistant_event.delta)
+            delta = assistant_event.delta
+            if previous_delta_type == "text_delta":
+                delta = THINKING_SEGMENT_SEPARATOR + delta
+            await self.thinking_callback(delta)
@@ -0,0 +1,238 @@
+"""Native tool wiring for tinyagent agents.
+
+In safe mode (``settings.safe_mode``) the agent is offered only tools whose
+risk tier can be read-only, and every call is re-classified when it is
+dispatched: anything not ``read_only`` -- a write tool, or a bash command such
+as ``git push`` -- is refused before the tool runs. The check wraps each tool
+outermost, after the concurrency limiter, and does not consult
+``settings.command_policy``, so no policy or wrapper can relax it.
+
+Outside safe mode, a bash command above ``read_only`` that the command policy
+allows is written to the command audit log (``core/session/audit.py``) before
+it runs.
+
+With ``settings.secret_redaction.enabled`` every tool's result passes through
+``SecretRedactor`` (``secret_redaction.py``) before tinyagent adds it to the
+history.
+
+Tools registered through ``tool_plugins.register_tool_plugin`` are merged in
+after the built-ins and wrapped like them. With ``TUNACODE_TEST_TOOL_MOCKS=1``,
+calls first check the test-only mock registry (``tool_mocks.py``).
+"""
+
+from __future__ import annotations
+
+import asyncio
+from collections.abc import Awaitable, Callable, Sequence
+from typing import cast
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+)
+
+from tunacode.configuration.limits import get_command_policy, get_secret_redaction
+from tunacode.constants import CommandPolicy, CommandRisk, ToolName
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools.bash import bash
+from tunacode.tools.cache_accessors.command_rules_cache import get_command_rules
+from tunacode.tools.discover import discover
+from tunacode.tools.grep import grep
+from tunacode.tools.hashline_edit import hashline_edit
+from tunacode.tools.list_directory import list_directory
+from tunacode.tools.read_file import read_file
+from tunacode.tools.utils.command_risk import classify_tool_call
+from tunacode.tools.utils.command_rules import CommandRuleError
+from tunacode.tools.web_fetch import web_fetch
+from tunacode.tools.write_file import write_file
+
+from tunacode.core.logging.manager import get_logger
+from tunacode.core.session.audit import record_command_override
+from tunacode.core.session.tasks import TaskStore
+
+from .secret_redaction import SecretRedactor
+from .task_tool import build_task_tool
+from .tool_mocks import apply_tool_mocks
+from .tool_plugins import merge_plugin_tools
+
+MAX_PARALLEL_TOOL_CALLS, MIN_PARALLEL_TOOL_CALLS = 3, 1
+
+BUILTIN_TOOLS: tuple[AgentTool, ...] = (
+    bash,
+    discover,
+    grep,
+    read_file,
+    hashline_edit,
+    list_directory,
+    web_fetch,
+    write_file,
+)
+
+ToolExecute = Callable[
+    [str, JsonObject, asyncio.Event | None, AgentToolUpdateCallback],
+    Awaitable[AgentToolResult],
+]
+
+
+def _wrap_tool_with_concurrency_limit(tool: AgentTool, *, limiter: asyncio.Semaphore) -> AgentTool:
+    raw_execute_fn = cast(object, tool.execute)
+    if raw_execute_fn is None:
+        raise ValueError(f"Tool '{tool.name}' must define an execute handler")
+    typed_execute_fn = cast(ToolExecute, raw_execute_fn)
+
+    async def _execute_with_limit(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        await limiter.acquire()
+        try:
+            return await typed_execute_fn(tool_call_id, args, signal, on_update)
+        finally:
+            limiter.release()
+
+    tool.execute = _execute_with_limit
+    return tool
+
+
+def _apply_tool_concurrency_limit(
+    tools: Sequence[AgentTool],
+    *,
+    max_parallel_tool_calls: int = MAX_PARALLEL_TOOL_CALLS,
+) -> list[AgentTool]:
+    if max_parallel_tool_calls < MIN_PARALLEL_TOOL_CALLS:
+        raise ValueError(
+            f"max_parallel_tool_calls must be >= {MIN_PARALLEL_TOOL_CALLS}, "
+            f"got {max_parallel_tool_calls}"
+        )
+    limiter = asyncio.Semaphore(max_parallel_tool_calls)
+    return [_wrap_tool_with_concurrency_limit(tool, limiter=limiter) for tool in tools]
+
+
+def _classify_or_refuse(tool_name: str, args: JsonObject) -> CommandRisk:
+    try:
+        return classify_tool_call(tool_name, args)
+    except CommandRuleError as exc:
+        raise ToolRetryError(
+            f"{tool_name} call blocked: command rules could not be loaded: {exc}\n"
+            "Do not retry it; ask the user to fix the rule file."
+        ) from exc
+
+
+def report_command_rules() -> None:
+    """Log rule-file warnings and errors once per agent build."""
+    logger = get_logger()
+    try:
+        rule_set = get_command_rules()
+    except CommandRuleError as exc:
+        logger.error(f"Command rules not loaded; bash commands will be blocked: {exc}")
+        return
+    for warning in rule_set.warnings:
+        logger.warning(f"Command rules: {warning}")
+
+
+def _wrap_tool_with_safe_mode(tool: AgentTool) -> AgentTool:
+    typed_execute_fn = cast(ToolExecute, tool.execute)
+
+    async def _execute_read_only(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        risk = _classify_or_refuse(tool.name, args)
+        if risk is not CommandRisk.READ_ONLY:
+            raise ToolRetryError(
+                f"Safe mode: {tool.name} call blocked ({risk.value}). "
+                "Only read-only tools and commands are allowed in this session."
+            )
+        return await typed_execute_fn(tool_call_id, args, signal, on_update)
+
+    return tool.model_copy(update={"execute": _execute_read_only})
+
+
+def _wrap_tool_with_override_audit(
+    tool: AgentTool,
+    *,
+    session_id_fn: Callable[[], str],
+) -> AgentTool:
+    typed_execute_fn = cast(ToolExecute, tool.execute)
+
+    async def _execute_audited(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        command = args.get("command")
+        risk = _classify_or_refuse(tool.name, args)
+        allowed = get_command_policy(risk) is CommandPolicy.ALLOW
+        if isinstance(command, str) and risk is not CommandRisk.READ_ONLY and allowed:
+            record_command_override(session_id=session_id_fn(), command=command, risk=risk)
+        return await typed_execute_fn(tool_call_id, args, signal, on_update)
+
+    return tool.model_copy(update={"execute": _execute_audited})
+
+
+def _wrap_tool_with_secret_redaction(tool: AgentTool, *, redactor: SecretRedactor) -> AgentTool:
+    typed_execute_fn = cast(ToolExecute, tool.execute)
+
+    async def _execute_redacted(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        result = await typed_execute_fn(tool_call_id, args, signal, on_update)
+        redacted, count = redactor.redact_result(result)
+        if count:
+            get_logger().info(f"Redacted {count} secret value(s) from {tool.name} output")
+        return redacted
+
+    return tool.model_copy(update={"execute": _execute_redacted})
+
+
+def _apply_secret_redaction(tools: list[AgentTool]) -> list[AgentTool]:
+    settings = get_secret_redaction()
+    if not settings["enabled"]:
+        return tools
+    redactor = SecretRedactor.from_settings(settings)
+    return [_wrap_tool_with_secret_redaction(tool, redactor=redactor) for tool in tools]
+
+
+def _is_read_only_capable(tool: AgentTool) -> bool:
+    return tool.name == ToolName.BASH or classify_tool_call(tool.name, {}) is CommandRisk.READ_ONLY
+
+
+def _build_tools(
+    *,
+    strict_validation: bool = False,
+    safe_mode: bool = False,
+    session_id_fn: Callable[[], str] | None = None,
+    task_store_fn: Callable[[], TaskStore] | None = None,
+) -> list[AgentTool]:
+    _ = strict_validation
+    builtin = list(BUILTIN_TOOLS)
+    if task_store_fn is not None:
+        builtin.append(build_task_tool(task_store_fn))
+    builtin = apply_tool_mocks(merge_plugin_tools(builtin))
+    if not safe_mode:
+        tools = _apply_tool_concurrency_limit(builtin)
+        if session_id_fn is None:
+            return _apply_secret_redaction(tools)
+        return _apply_secret_redaction(
+            [
+                _wrap_tool_with_override_audit(tool, session_id_fn=session_id_fn)
+                if tool.name == ToolName.BASH
+                else tool
+                for tool in tools
+            ]
+        )
+    offered = [tool for tool in builtin if _is_read_only_capable(tool)]
+    return _apply_secret_redaction(
+        [_wrap_tool_with_safe_mode(tool) for tool in _apply_tool_concurrency_limit(offered)]
+    )
@@ -4,12 +4,21 @@ from __future__ import annotations
 
 from collections.abc import Callable
 
-from tinyagent.agent_types import AgentContext, AgentMessage, AssistantMessage, ToolResultMessage
+from tinyagent.agent_types import (
+    AgentContext,
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolResultMessage,
+)
+
+from tunacode.constants import LoopAction
 
 from tunacode.core.logging.manager import get_logger
 from tunacode.core.types.state import SessionStateProtocol
 
-from .agent_session_config import _coerce_max_iterations
+from .agent_session_config import _coerce_max_iterations, _normalize_session_config
+from .loop_detection import LOOP_NUDGE_TEMPLATE, LoopDetector
 
 
 def build_should_stop_after_turn(
@@ -18,20 +27,38 @@ def build_should_stop_after_turn(
     [AssistantMessage, list[ToolResultMessage], AgentContext, list[AgentMessage]],
     bool,
 ]:
+    settings = _normalize_session_config(session).settings
+    loop_detector = LoopDetector(threshold=settings.loop_detection_threshold)
+    loop_action = LoopAction(settings.loop_detection_action)
+
     def _should_stop_after_turn(
         message: AssistantMessage,
         tool_results: list[ToolResultMessage],
         context: AgentContext,
         new_messages: list[AgentMessage],
     ) -> bool:
-        _ = (message, tool_results, context, new_messages)
+        _ = (context, new_messages)
         runtime = session.runtime
         runtime.iteration_count += 1
         runtime.current_iteration = runtime.iteration_count
+        if runtime.iteration_count == 1:
+            loop_detector.reset()
+
+        loop_description = loop_detector.observe(message, tool_results)
+        if loop_description is not None:
+            get_logger().warning(f"Possible loop detected: {loop_description}")
+            runtime.loop_detection = loop_description
+            if loop_action is LoopAction.HALT:
+                return True
+            if loop_action is LoopAction.NUDGE and tool_results:
+                nudge = LOOP_NUDGE_TEMPLATE.format(description=loop_description)
+                tool_results[-1].content.append(TextContent(text=f"\n\n{nudge}"))
+
         max_iterations = _coerce_max_iterations(session)
         if runtime.iteration_count <= max_iterations:
             return False
         get_logger().warning(f"Max iterations exceeded ({max_iterations}); ending agent loop")
+        runtime.step_limit_reached = True
         return True
 
     return _should_stop_after_turn
@@ -0,0 +1,126 @@
+"""Run configured formatters on the files an agent turn edited.
+
+With ``settings.auto_format.enabled`` the request orchestrator calls
+``format_turn_edits`` once the model is done. ``formatters`` maps a path
+pattern (matched with ``PurePath.match``, so ``*.py`` matches at any depth) to
+a command line; each command runs once on the turn's files that match it, in
+a process group registered with ``AGENT_TURN_SCOPE`` like a ``bash`` tool
+command. A file is formatted by the first pattern it matches, and only files
+in the edit journal's current turn (``tools/edit_journal.py``) inside the
+working directory are considered.
+
+Whatever a formatter rewrites is recorded back into the journal, so the
+turn's diff and ``/undo`` cover the formatting too, and the file's hashline
+cache is dropped. A formatter that is missing, fails or times out produces a
+warning; the turn itself still succeeds.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import shlex
+from dataclasses import dataclass, field
+from pathlib import Path, PurePath
+
+from tunacode.types import AutoFormatSettings
+from tunacode.utils.system.process_group import (
+    AGENT_TURN_SCOPE,
+    kill_process_group,
+    new_process_group_kwargs,
+)
+
+from tunacode.tools import edit_journal, line_cache
+
+MAX_STDERR_CHARS = 500
+FORMATTED_NOTICE_TEMPLATE = "Auto-formatted {count} file(s): {paths}"
+FORMATTER_FAILED_TEMPLATE = "Auto-format: `{command}` {problem}"
+
+
+@dataclass(slots=True)
+class FormatReport:
+    formatted: list[Path] = field(default_factory=list)
+    warnings: list[str] = field(default_factory=list)
+
+
+def _relative(path: Path, root: Path) -> PurePath | None:
+    try:
+        return path.relative_to(root)
+    except ValueError:
+        return None
+
+
+def select_formatter_files(
+    paths: list[Path], formatters: dict[str, str], *, root: Path
+) -> dict[str, list[Path]]:
+    """Group ``paths`` by the command of the first pattern each one matches."""
+    selected: dict[str, list[Path]] = {}
+    for path in paths:
+        relative = _relative(path, root)
+        if relative is None or not path.is_file():
+            continue
+        for pattern, command in formatters.items():
+            if relative.match(pattern):
+                selected.setdefault(command, []).append(path)
+                break
+    return selected
+
+
+async def _run_formatter(command: str, files: list[Path], *, root: Path, timeout: float) -> str:
+    """Run ``command`` on ``files``; returns a problem description, or "" on success."""
+    argv = [*shlex.split(command), *(str(path) for path in files)]
+    try:
+        process = await asyncio.create_subprocess_exec(
+            *argv,
+            cwd=root,
+            stdin=asyncio.subprocess.DEVNULL,
+            stdout=asyncio.subprocess.DEVNULL,
+            stderr=asyncio.subprocess.PIPE,
+            **new_process_group_kwargs(),
+        )
+    except OSError as exc:
+        return f"could not start: {exc.strerror or exc}"
+    AGENT_TURN_SCOPE.register(process)
+    try:
+        _, stderr = await asyncio.wait_for(process.communicate(), timeout=timeout)
+    except TimeoutError:
+        kill_process_group(process)
+        await process.wait()
+        return f"timed out after {timeout:g}s"
+    if process.returncode != 0:
+        detail = stderr.decode(errors="replace").strip()[:MAX_STDERR_CHARS]
+        return f"exited with {process.returncode}" + (f": {detail}" if detail else "")
+    return ""
+
+
+def _invalidate_line_cache(path: Path) -> None:
+    # The cache is keyed by the path string the tools were given, not the resolved path.
+    for cached_path in line_cache.cached_files():
+        if Path(cached_path).resolve() == path:
+            line_cache.invalidate(cached_path)
+
+
+def _read(path: Path) -> bytes | None:
+    try:
+        return path.read_bytes()
+    except OSError:
+        return None
+
+
+async def format_turn_edits(settings: AutoFormatSettings, *, root: Path) -> FormatReport:
+    """Format this turn's edited files and fold the changes into the turn's journal."""
+    report = FormatReport()
+    selected = select_formatter_files(edit_journal.turn_paths(), settings["formatters"], root=root)
+    for command, files in selected.items():
+        before = {path: _read(path) for path in files}
+        problem = await _run_formatter(command, files, root=root, timeout=settings["timeout"])
+        if problem:
+            warning = FORMATTER_FAILED_TEMPLATE.format(command=command, problem=problem)
+            report.warnings.append(warning)
+        for path in files:
+            after = _read(path)
+            if after is None or after == before[path]:
+                continue
+            edit_journal.record(path, before[path], after)
+            _invalidate_line_cache(path)
+            report.formatted.append(path)
+    return report
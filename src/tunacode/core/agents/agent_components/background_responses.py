@@ -0,0 +1,569 @@
+"""Resumable provider streams using the Responses API background mode.
+
+With ``settings.background_responses.enabled``, a request to a provider that
+supports it (the OpenAI API) is created as a background response
+(``POST /responses`` with ``background`` and ``stream`` set). The provider
+keeps generating even if the connection drops, and each streamed event
+carries a ``sequence_number``. When the stream breaks -- a network error, a
+read timeout, or the connection closing before a terminal event -- the stream
+reconnects with ``GET /responses/{id}?stream=true&starting_after=<n>``, where
+``n`` is the last sequence number handed on, up to ``max_reconnects`` times,
+waiting between attempts as ``settings.retry_backoff`` says (``backoff.py``).
+Events at or below that number are dropped, so nothing is duplicated or lost
+across a reconnect.
+
+Background requests go through the provider's pooled client (``http_pool.py``),
+so reconnects and later turns reuse its keep-alive connections.
+
+Providers without background mode use the normal chat-completions stream,
+and so does a provider that rejects the background request (400, 404, 405 or
+501); that base URL is then not tried again for the rest of the process.
+A background response abandoned mid-stream (user abort, turn deadline) is
+cancelled on the provider so it stops generating.
+
+Background responses are stored, so the next request continues from the last
+completed one with ``previous_response_id`` and sends only the messages added
+since (``incremental_context.py``). When the history no longer starts with
+what that response covered -- compaction, a fork, a model or system prompt
+change -- the full history is sent instead. A provider that rejects the
+continuation gets the full history in a new request.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import time
+from collections.abc import AsyncIterator, Callable, Mapping, Sequence
+from typing import Any
+
+import httpx
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+    TextContent,
+    ThinkingContent,
+    ToolCallContent,
+)
+
+from tunacode.types import UsageMetrics
+from tunacode.utils.messaging import to_canonical
+
+from tunacode.core.logging.manager import get_logger
+
+from .backoff import BackoffPolicy
+from .http_pool import ProviderHttpPolicy, provider_client
+from .incremental_context import ProviderResponseTracker
+
+CHAT_COMPLETIONS_PATH = "/chat/completions"
+RESPONSES_PATH = "/responses"
+BACKGROUND_RESPONSE_HOSTS = ("api.openai.com",)
+UNSUPPORTED_STATUS_CODES = frozenset({400, 404, 405, 501})
+TERMINAL_EVENT_TYPES = frozenset(
+    {"response.completed", "response.incomplete", "response.failed", "response.cancelled", "error"}
+)
+READ_TIMEOUT_SECONDS = 60.0
+CONNECT_TIMEOUT_SECONDS = 10.0
+RECONNECT_DELAY_SECONDS = 0.5
+CANCEL_TIMEOUT_SECONDS = 5.0
+SSE_DATA_PREFIX = "data:"
+SSE_DONE_MARKER = "[DONE]"
+_MS_PER_S = 1000
+
+_REQUEST_TIMEOUT = httpx.Timeout(READ_TIMEOUT_SECONDS, connect=CONNECT_TIMEOUT_SECONDS)
+
+ResponseCompleted = Callable[[str, AssistantMessage], None]
+
+_unsupported_base_urls: set[str] = set()
+_pending_cancels: set[asyncio.Task[None]] = set()
+
+
+class BackgroundResponseError(RuntimeError):
+    """The background response failed, or its stream could not be resumed."""
+
+
+def responses_url(base_url: str) -> str:
+    """Responses endpoint next to a chat-completions ``base_url``."""
+    root = base_url.removesuffix(CHAT_COMPLETIONS_PATH).rstrip("/")
+    return f"{root}{RESPONSES_PATH}"
+
+
+def supports_background_responses(model: Model) -> bool:
+    base_url = model.base_url
+    if not base_url or base_url in _unsupported_base_urls:
+        return False
+    host = httpx.URL(base_url).host
+    return host in BACKGROUND_RESPONSE_HOSTS
+
+
+def _input_content(item: dict[str, Any]) -> dict[str, Any] | None:
+    if item.get("type") == "text":
+        return {"type": "input_text", "text": item.get("text") or ""}
+    if item.get("type") == "image" and item.get("url"):
+        return {"type": "input_image", "image_url": item["url"]}
+    return None
+
+
+def _assistant_items(message: dict[str, Any]) -> list[dict[str, Any]]:
+    items: list[dict[str, Any]] = []
+    for item in message.get("content") or []:
+        if item.get("type") == "text" and item.get("text"):
+            items.append(
+                {
+                    "role": "assistant",
+                    "content": [{"type": "output_text", "text": item["text"]}],
+                }
+            )
+        elif item.get("type") == "tool_call":
+            items.append(
+                {
+                    "type": "function_call",
+                    "call_id": item.get("id"),
+                    "name": item.get("name"),
+                    "arguments": json.dumps(item.get("arguments") or {}),
+                }
+            )
+    return items
+
+
+def _tool_output_text(message: dict[str, Any]) -> str:
+    return "".join(
+        item.get("text") or ""
+        for item in message.get("content") or []
+        if item.get("type") == "text"
+    )
+
+
+def build_request_input(context: Context) -> list[dict[str, Any]]:
+    """Convert the conversation into Responses API input items."""
+    return request_items(context.messages)
+
+
+def request_items(messages: Sequence[AgentMessage]) -> list[dict[str, Any]]:
+    """Convert ``messages`` into Responses API input items."""
+    items: list[dict[str, Any]] = []
+    for message in messages:
+        payload = to_canonical(message)
+        role = payload.get("role")
+        if role == "user":
+            content = [
+                converted
+                for converted in map(_input_content, payload.get("content") or [])
+                if converted is not None
+            ]
+            items.append({"role": "user", "content": content})
+        elif role == "assistant":
+            items.extend(_assistant_items(payload))
+        elif role == "tool_result":
+            items.append(
+                {
+                    "type": "function_call_output",
+                    "call_id": payload.get("tool_call_id"),
+                    "output": _tool_output_text(payload),
+                }
+            )
+    return items
+
+
+def build_request_body(
+    model: Model,
+    context: Context,
+    options: SimpleStreamOptions,
+    *,
+    previous_response_id: str | None = None,
+) -> dict[str, Any]:
+    body: dict[str, Any] = {
+        "model": model.id,
+        "input": build_request_input(context),
+        "background": True,
+        "stream": True,
+        "store": True,
+    }
+    if previous_response_id is not None:
+        body["previous_response_id"] = previous_response_id
+    if context.system_prompt:
+        body["instructions"] = context.system_prompt
+    if context.tools:
+        body["tools"] = [
+            {
+                "type": "function",
+                "name": tool.name,
+                "description": tool.description,
+                "parameters": tool.parameters,
+            }
+            for tool in context.tools
+        ]
+    if options.max_tokens is not None:
+        body["max_output_tokens"] = options.max_tokens
+    return body
+
+
+async def iter_sse_payloads(lines: AsyncIterator[str]) -> AsyncIterator[dict[str, Any]]:
+    """Yield the JSON payload of each ``data:`` line of a server-sent event stream."""
+    async for line in lines:
+        if not line.startswith(SSE_DATA_PREFIX):
+            continue
+        data = line[len(SSE_DATA_PREFIX) :].strip()
+        if not data or data == SSE_DONE_MARKER:
+            continue
+        payload = json.loads(data)
+        if isinstance(payload, dict):
+            yield payload
+
+
+def _usage_payload(raw_usage: object) -> dict[str, Any]:
+    usage = raw_usage if isinstance(raw_usage, dict) else {}
+    input_details = usage.get("input_tokens_details") or {}
+    output_details = usage.get("output_tokens_details") or {}
+    cached = int(input_details.get("cached_tokens") or 0)
+    input_tokens = int(usage.get("input_tokens") or 0)
+    output_tokens = int(usage.get("output_tokens") or 0)
+    return UsageMetrics(
+        input=input_tokens - cached,
+        output=output_tokens,
+        cache_read=cached,
+        total_tokens=int(usage.get("total_tokens") or input_tokens + output_tokens),
+        reasoning=int(output_details.get("reasoning_tokens") or 0),
+    ).to_dict()
+
+
+class BackgroundResponseStream:
+    """``StreamResponse`` over a background response that resumes after a disconnect."""
+
+    def __init__(
+        self,
+        client: httpx.AsyncClient,
+        response: httpx.Response,
+        *,
+        model: Model,
+        url: str,
+        headers: dict[str, str],
+        max_reconnects: int,
+        backoff: BackoffPolicy | None = None,
+        owns_client: bool = True,
+        on_completed: ResponseCompleted | None = None,
+    ) -> None:
+        self._client = client
+        self._owns_client = owns_client
+        self._response: httpx.Response | None = response
+        self._model = model
+        self._url = url
+        self._headers = headers
+        self._max_reconnects = max_reconnects
+        policy = backoff or BackoffPolicy(base_delay=RECONNECT_DELAY_SECONDS)
+        self._reconnect_delays = policy.schedule("background_reconnect")
+        self.response_id: str | None = None
+        self.last_sequence = -1
+        self.reconnects = 0
+        self._text = ""
+        self._thinking = ""
+        self._tool_calls: list[ToolCallContent] = []
+        self._usage: dict[str, Any] = UsageMetrics().to_dict()
+        self._stop_reason = "stop"
+        self._finished = False
+        self._final_message: AssistantMessage | None = None
+        self._on_completed = on_completed
+        self._events = self._stream_events()
+
+    def __aiter__(self) -> BackgroundResponseStream:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        try:
+            return await self._events.__anext__()
+        except asyncio.CancelledError:
+            self._cancel_remote_response()
+            raise
+
+    async def result(self) -> AssistantMessage:
+        async for _event in self:
+            pass
+        return self._final_message or self._message()
+
+    async def _stream_events(self) -> AsyncIterator[AssistantMessageEvent]:
+        try:
+            while not self._finished:
+                response = self._response or await self._reconnect()
+                self._response = None
+                if response is None:
+                    continue
+                error: httpx.TransportError | None = None
+                try:
+                    async for payload in iter_sse_payloads(response.aiter_lines()):
+                        event = self._handle_payload(payload)
+                        if event is not None:
+                            yield event
+                        if self._finished:
+                            return
+                except httpx.TransportError as exc:
+                    error = exc
+                finally:
+                    await response.aclose()
+                await self._wait_before_reconnect(error)
+        finally:
+            if self._owns_client:
+                await self._client.aclose()
+
+    async def _reconnect(self) -> httpx.Response | None:
+        """Reopen the stream after ``last_sequence``; None when the attempt itself failed."""
+        url = f"{self._url}/{self.response_id}"
+        params = {"stream": "true", "starting_after": str(self.last_sequence)}
+        request = self._client.build_request(
+            "GET", url, params=params, headers=self._headers, timeout=_REQUEST_TIMEOUT
+        )
+        try:
+            response = await self._client.send(request, stream=True)
+        except httpx.TransportError as exc:
+            await self._wait_before_reconnect(exc)
+            return None
+        if response.is_error:
+            await response.aread()
+            response.raise_for_status()
+        return response
+
+    async def _wait_before_reconnect(self, exc: Exception | None) -> None:
+        reason = type(exc).__name__ if exc is not None else "stream closed early"
+        if self.response_id is None:
+            raise BackgroundResponseError(
+                f"Background response stream lost before it started ({reason})"
+            ) from exc
+        if self.reconnects >= self._max_reconnects:
+            raise BackgroundResponseError(
+                f"Background response {self.response_id} stream lost after "
+                f"{self.reconnects} reconnect(s) ({reason})"
+            ) from exc
+        self.reconnects += 1
+        get_logger().warning(
+            f"Background response stream lost ({reason}); reconnecting "
+            f"id={self.response_id} after={self.last_sequence} "
+            f"attempt={self.reconnects}/{self._max_reconnects}"
+        )
+        await asyncio.sleep(self._reconnect_delays.next_delay())
+
+    def _handle_payload(self, payload: dict[str, Any]) -> AssistantMessageEvent | None:
+        sequence = payload.get("sequence_number")
+        if isinstance(sequence, int):
+            if sequence <= self.last_sequence:
+                return None
+            self.last_sequence = sequence
+        event_type = payload.get("type")
+        response = payload.get("response")
+        if isinstance(response, dict) and isinstance(response.get("id"), str):
+            self.response_id = response["id"]
+        if event_type in TERMINAL_EVENT_TYPES:
+            return self._finish(event_type, payload)
+        if event_type == "response.output_text.delta":
+            delta = payload.get("delta") or ""
+            self._text += delta
+            return AssistantMessageEvent(type="text_delta", delta=delta, partial=self._message())
+        if event_type in ("response.reasoning_summary_text.delta", "response.reasoning_text.delta"):
+            delta = payload.get("delta") or ""
+            self._thinking += delta
+            return AssistantMessageEvent(
+                type="thinking_delta", delta=delta, partial=self._message()
+            )
+        if event_type == "response.output_item.done":
+            self._add_tool_call(payload.get("item"))
+        return None
+
+    def _add_tool_call(self, item: object) -> None:
+        if not isinstance(item, dict) or item.get("type") != "function_call":
+            return
+        try:
+            arguments = json.loads(item.get("arguments") or "{}")
+        except ValueError:
+            arguments = {}
+        self._tool_calls.append(
+            ToolCallContent(
+                id=item.get("call_id") or "",
+                name=item.get("name") or "",
+                arguments=arguments if isinstance(arguments, dict) else {},
+            )
+        )
+
+    def _finish(self, event_type: str, payload: dict[str, Any]) -> AssistantMessageEvent:
+        self._finished = True
+        response = payload.get("response") if isinstance(payload.get("response"), dict) else {}
+        if event_type in ("response.failed", "response.cancelled", "error"):
+            error = response.get("error") or payload
+            message = error.get("message") if isinstance(error, dict) else None
+            raise BackgroundResponseError(
+                f"Background response {self.response_id} {event_type}: "
+                f"{message or 'no error message'}"
+            )
+        self._usage = _usage_payload(response.get("usage"))
+        if self._tool_calls:
+            self._stop_reason = "tool_calls"
+        elif event_type == "response.incomplete":
+            self._stop_reason = "length"
+        message = self._message()
+        self._final_message = message
+        if self._on_completed is not None and self.response_id is not None:
+            self._on_completed(self.response_id, message)
+        return AssistantMessageEvent(type="done", partial=message)
+
+    def _message(self) -> AssistantMessage:
+        content: list[object] = []
+        if self._thinking:
+            content.append(ThinkingContent(thinking=self._thinking))
+        if self._text:
+            content.append(TextContent(text=self._text))
+        content.extend(self._tool_calls)
+        return AssistantMessage(
+            content=content,
+            stop_reason=self._stop_reason,
+            usage=self._usage,
+            provider=self._model.provider,
+            model=self._model.id,
+            timestamp=int(time.time() * _MS_PER_S),
+        )
+
+    def _cancel_remote_response(self) -> None:
+        if self.response_id is None or self._finished:
+            return
+        task = asyncio.get_running_loop().create_task(self._post_cancel(self.response_id))
+        _pending_cancels.add(task)
+        task.add_done_callback(_pending_cancels.discard)
+
+    async def _post_cancel(self, response_id: str) -> None:
+        try:
+            async with httpx.AsyncClient(timeout=CANCEL_TIMEOUT_SECONDS) as client:
+                await client.post(f"{self._url}/{response_id}/cancel", headers=self._headers)
+        except httpx.HTTPError as exc:
+            get_logger().warning(f"Could not cancel background response {response_id}: {exc}")
+
+
+async def open_background_response(
+    model: Model,
+    context: Context,
+    options: SimpleStreamOptions,
+    *,
+    max_reconnects: int,
+    transport: httpx.AsyncBaseTransport | None = None,
+    backoff: BackoffPolicy | None = None,
+    http_policy: ProviderHttpPolicy | None = None,
+    previous_response_id: str | None = None,
+    on_completed: ResponseCompleted | None = None,
+) -> BackgroundResponseStream | None:
+    """Start a background response; None when the provider rejects the request.
+
+    A rejected ``previous_response_id`` continuation returns None without
+    marking the provider unsupported, so the caller can resend in full. A
+    ``transport`` (tests) gets a dedicated client closed with the stream;
+    otherwise the request uses the provider's pooled client.
+    """
+    base_url = model.base_url
+    url = responses_url(base_url)
+    headers = {"Authorization": f"Bearer {options.api_key or ''}"}
+    owns_client = transport is not None
+    client = (
+        httpx.AsyncClient(transport=transport)
+        if owns_client
+        else provider_client(model.provider, http_policy or ProviderHttpPolicy())
+    )
+
+    async def _release_client() -> None:
+        if owns_client:
+            await client.aclose()
+
+    try:
+        request = client.build_request(
+            "POST",
+            url,
+            json=build_request_body(
+                model, context, options, previous_response_id=previous_response_id
+            ),
+            headers=headers,
+            timeout=_REQUEST_TIMEOUT,
+        )
+        response = await client.send(request, stream=True)
+        if response.status_code in UNSUPPORTED_STATUS_CODES:
+            await response.aclose()
+            await _release_client()
+            if previous_response_id is not None:
+                get_logger().warning(
+                    f"Could not continue background response {previous_response_id} "
+                    f"(HTTP {response.status_code}); resending the full history"
+                )
+                return None
+            _unsupported_base_urls.add(base_url)
+            get_logger().warning(
+                f"Background responses unsupported by {base_url} "
+                f"(HTTP {response.status_code}); using normal streaming"
+            )
+            return None
+        if response.is_error:
+            await response.aread()
+            response.raise_for_status()
+    except BaseException:
+        await _release_client()
+        raise
+    return BackgroundResponseStream(
+        client,
+        response,
+        model=model,
+        url=url,
+        headers=headers,
+        max_reconnects=max_reconnects,
+        backoff=backoff,
+        owns_client=owns_client,
+        on_completed=on_completed,
+    )
+
+
+def with_background_responses(
+    stream_fn: StreamFn,
+    *,
+    max_reconnects: int,
+    transport: httpx.AsyncBaseTransport | None = None,
+    backoff: BackoffPolicy | None = None,
+    http_policies: Mapping[str, ProviderHttpPolicy] | None = None,
+) -> StreamFn:
+    """Wrap ``stream_fn`` to use a resumable background response where supported."""
+    tracker = ProviderResponseTracker(request_items=request_items)
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        delta = tracker.delta_for(model, context)
+        previous_response_id = tracker.response_id
+        tracker.reset()
+        if not supports_background_responses(model):
+            return await stream_fn(model, context, options)
+
+        async def _open(
+            sent: Context, continued_from: str | None
+        ) -> BackgroundResponseStream | None:
+            return await open_background_response(
+                model,
+                sent,
+                options,
+                max_reconnects=max_reconnects,
+                transport=transport,
+                backoff=backoff,
+                http_policy=(http_policies or {}).get(model.provider),
+                previous_response_id=continued_from,
+                on_completed=lambda response_id, answer: tracker.record(
+                    response_id, model, context, answer
+                ),
+            )
+
+        stream: BackgroundResponseStream | None = None
+        if delta is not None:
+            continued = context.model_copy(update={"messages": delta})
+            stream = await _open(continued, previous_response_id)
+        if stream is None:
+            stream = await _open(context, None)
+        if stream is not None:
+            return stream  # type: ignore[return-value]
+        return await stream_fn(model, context, options)
+
+    return _stream
@@ -0,0 +1,84 @@
+"""Retry delays shared by every provider retry path.
+
+``settings.retry_backoff`` picks how the delay before each retry is drawn.
+Attempt ``n`` has a ceiling of ``min(max_delay, base_delay * 2 ** (n - 1))``:
+
+- ``none``: wait exactly the ceiling.
+- ``full_jitter`` (default): a uniform delay in ``[0, ceiling]``.
+- ``equal_jitter``: half the ceiling plus a uniform delay in ``[0, ceiling / 2]``.
+- ``decorrelated``: a uniform delay in ``[base_delay, 3 * previous delay]``,
+  capped at ``max_delay``.
+
+Jitter spreads the retries of many agents hitting the same provider so they
+do not arrive in lockstep. The stream retry (including 429 responses),
+provider failover and background-response reconnects each draw their delays
+from a fresh ``BackoffSchedule``, and every computed delay is written to the
+debug lifecycle log.
+"""
+
+from __future__ import annotations
+
+import random
+from dataclasses import dataclass
+
+from tunacode.constants import BackoffStrategy
+from tunacode.types import RetryBackoffSettings
+
+from tunacode.core.logging.manager import get_logger
+
+DECORRELATED_GROWTH = 3.0
+
+
+@dataclass(frozen=True, slots=True)
+class BackoffPolicy:
+    strategy: BackoffStrategy = BackoffStrategy.FULL_JITTER
+    base_delay: float = 0.5
+    max_delay: float = 8.0
+
+    @classmethod
+    def from_settings(cls, settings: RetryBackoffSettings) -> BackoffPolicy:
+        return cls(
+            strategy=BackoffStrategy(settings["strategy"]),
+            base_delay=settings["base_delay"],
+            max_delay=settings["max_delay"],
+        )
+
+    def schedule(self, label: str, *, rng: random.Random | None = None) -> BackoffSchedule:
+        return BackoffSchedule(self, label=label, rng=rng or random.Random())
+
+
+class BackoffSchedule:
+    """Delays for one sequence of retries; ``next_delay()`` is called once per retry."""
+
+    def __init__(self, policy: BackoffPolicy, *, label: str, rng: random.Random) -> None:
+        self._policy = policy
+        self._label = label
+        self._rng = rng
+        self._attempt = 0
+        self._previous = policy.base_delay
+
+    def _ceiling(self) -> float:
+        policy = self._policy
+        return min(policy.max_delay, policy.base_delay * float(1 << (self._attempt - 1)))
+
+    def _draw(self) -> float:
+        policy = self._policy
+        if policy.strategy is BackoffStrategy.NONE:
+            return self._ceiling()
+        if policy.strategy is BackoffStrategy.FULL_JITTER:
+            return self._rng.uniform(0.0, self._ceiling())
+        if policy.strategy is BackoffStrategy.EQUAL_JITTER:
+            half = self._ceiling() / 2
+            return half + self._rng.uniform(0.0, half)
+        upper = max(policy.base_delay, self._previous * DECORRELATED_GROWTH)
+        return min(policy.max_delay, self._rng.uniform(policy.base_delay, upper))
+
+    def next_delay(self) -> float:
+        self._attempt += 1
+        delay = self._draw()
+        self._previous = delay
+        get_logger().lifecycle(
+            f"Backoff: {self._label} retry={self._attempt} "
+            f"strategy={self._policy.strategy.value} delay={delay:.3f}s"
+        )
+        return delay
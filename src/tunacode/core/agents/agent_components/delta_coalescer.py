@@ -0,0 +1,72 @@
+"""Batch answer-text deltas before they reach the UI streaming callback.
+
+Enabled by ``settings.stream_coalescing``: a batch is emitted once it holds
+``max_chars`` characters or its first delta is ``window_ms`` old, whichever
+comes first (``0`` disables that trigger; both ``0`` turns coalescing off).
+There is no timer -- the window is checked as deltas arrive -- so the stream
+loop flushes before every other event. Reasoning deltas and tool events are
+therefore never held back, and text keeps its order relative to them.
+
+Only the callback sees batches. The concatenated text is the same string the
+callback would have received delta by delta, and the assistant message tinyagent
+assembles is untouched.
+"""
+
+from __future__ import annotations
+
+import time
+from collections.abc import Callable
+
+from tunacode.types import StreamingCallback
+
+_MS_PER_S = 1000.0
+
+
+class TextDeltaCoalescer:
+    def __init__(
+        self,
+        emit: StreamingCallback,
+        *,
+        window_ms: int,
+        max_chars: int,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        self._emit = emit
+        self._window_ms = window_ms
+        self._max_chars = max_chars
+        self._clock = clock
+        self._chunks: list[str] = []
+        self._char_count = 0
+        self._first_buffered_at = 0.0
+
+    async def push(self, delta: str) -> None:
+        if not self._chunks:
+            self._first_buffered_at = self._clock()
+        self._chunks.append(delta)
+        self._char_count += len(delta)
+        if self._max_chars and self._char_count >= self._max_chars:
+            await self.flush()
+            return
+        age_ms = (self._clock() - self._first_buffered_at) * _MS_PER_S
+        if self._window_ms and age_ms >= self._window_ms:
+            await self.flush()
+
+    async def flush(self) -> None:
+        if not self._chunks:
+            return
+        text = "".join(self._chunks)
+        self._chunks = []
+        self._char_count = 0
+        await self._emit(text)
+
+
+def build_text_coalescer(
+    emit: StreamingCallback | None,
+    *,
+    window_ms: int,
+    max_chars: int,
+) -> TextDeltaCoalescer | None:
+    """Return a coalescer for ``emit``, or None when coalescing is off."""
+    if emit is None or (window_ms == 0 and max_chars == 0):
+        return None
+    return TextDeltaCoalescer(emit, window_ms=window_ms, max_chars=max_chars)
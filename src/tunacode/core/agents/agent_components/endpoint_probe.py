@@ -0,0 +1,119 @@
+"""Reachability probe for a user-supplied OpenAI-compatible base URL.
+
+``--baseurl`` usually points at a self-hosted server (Ollama, LM Studio, vLLM).
+A wrong host or port otherwise only shows up as a failed first turn, so startup
+sends one GET to ``<base_url><settings.base_url_probe_path>`` (``/models`` by
+default) and fails early when nothing answers. Servers that do not implement
+the probe path still start: any HTTP response proves the endpoint is reachable.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+import httpx
+
+from tunacode.configuration.models import detect_provider_from_base_url, parse_model_string
+from tunacode.exceptions import ConfigurationError
+
+from tunacode.core.types.state import StateManagerProtocol
+
+OPENAI_CHAT_COMPLETIONS_PATH = "/chat/completions"
+PROBE_TIMEOUT_SECONDS = 5.0
+
+
+@dataclass(frozen=True, slots=True)
+class EndpointProbe:
+    """What a base URL answered to the probe request."""
+
+    base_url: str
+    provider_id: str | None
+    status_code: int
+    model_ids: tuple[str, ...]
+
+    @property
+    def lists_models(self) -> bool:
+        return self.status_code < 400 and bool(self.model_ids)
+
+
+def _probe_url(base_url: str, probe_path: str) -> str:
+    root = base_url.strip().rstrip("/")
+    if root.endswith(OPENAI_CHAT_COMPLETIONS_PATH):
+        root = root[: -len(OPENAI_CHAT_COMPLETIONS_PATH)]
+    return f"{root}{probe_path}"
+
+
+def _model_ids(payload: object) -> tuple[str, ...]:
+    """Read ids from an OpenAI ``{"data": [...]}`` or Ollama ``{"models": [...]}`` body."""
+    if not isinstance(payload, dict):
+        return ()
+    entries = payload.get("data", payload.get("models"))
+    if not isinstance(entries, list):
+        return ()
+    ids: list[str] = []
+    for entry in entries:
+        if not isinstance(entry, dict):
+            continue
+        model_id = entry.get("id", entry.get("name"))
+        if isinstance(model_id, str) and model_id:
+            ids.append(model_id)
+    return tuple(ids)
+
+
+def _json_or_none(response: httpx.Response) -> object:
+    try:
+        return response.json()
+    except ValueError:
+        return None
+
+
+async def probe_base_url(
+    base_url: str,
+    *,
+    probe_path: str,
+    api_key: str | None = None,
+    timeout: float = PROBE_TIMEOUT_SECONDS,
+    transport: httpx.AsyncBaseTransport | None = None,
+) -> EndpointProbe:
+    """Probe ``base_url`` and return the provider and model ids it serves.
+
+    Raises ``ConfigurationError`` when the endpoint cannot be reached at all.
+    """
+    url = _probe_url(base_url, probe_path)
+    headers = {"Authorization": f"Bearer {api_key}"} if api_key else {}
+    try:
+        async with httpx.AsyncClient(
+            timeout=httpx.Timeout(timeout), transport=transport
+        ) as client:
+            response = await client.get(url, headers=headers)
+    except httpx.RequestError as exc:
+        raise ConfigurationError(
+            f"Cannot reach base URL {base_url}: {type(exc).__name__}: {exc}",
+            suggested_fix="Check the host and port in --baseurl, or start the server first.",
+        ) from exc
+
+    model_ids = _model_ids(_json_or_none(response)) if response.status_code < 400 else ()
+    return EndpointProbe(
+        base_url=base_url,
+        provider_id=detect_provider_from_base_url(base_url),
+        status_code=response.status_code,
+        model_ids=model_ids,
+    )
+
+
+async def probe_session_base_url(
+    state_manager: StateManagerProtocol, base_url: str
+) -> EndpointProbe | None:
+    """Probe ``base_url`` with the session's API key; None when probing is disabled."""
+    from .agent_config import _build_api_key_resolver
+
+    session = state_manager.session
+    probe_path = session.user_config["settings"]["base_url_probe_path"]
+    if not probe_path:
+        return None
+    try:
+        provider_id, _model_id = parse_model_string(session.current_model)
+    except ValueError:
+        provider_id = detect_provider_from_base_url(base_url) or ""
+    api_key = _build_api_key_resolver(session)(provider_id) if provider_id else None
+    return await probe_base_url(base_url, probe_path=probe_path, api_key=api_key)
@@ -0,0 +1,123 @@
+"""Pooled HTTP clients for the provider requests tunacode sends itself.
+
+Background responses (``background_responses.py``) talk to the provider over
+httpx rather than through the alchemy binding. Those requests share one
+``httpx.AsyncClient`` per provider id for the life of the process, so a
+reconnect or the next turn reuses a warm keep-alive connection instead of
+paying for a new TCP and TLS handshake.
+
+``settings.provider_http`` tunes that client per provider id::
+
+    "provider_http": {
+        "openai": {"max_connections": 32, "max_keepalive_connections": 16, "http2": true}
+    }
+
+A provider without an entry gets the defaults: 10 connections, 5 kept alive
+for 30 seconds, HTTP/1.1 -- the safe choice for OSS servers with shaky HTTP/2
+support. ``http2`` asks for HTTP/2; the server can still negotiate HTTP/1.1,
+and without the ``h2`` package installed the client stays on HTTP/1.1 and
+logs a warning. Changing a provider's settings replaces its
+client; the old one is closed with the rest in ``close_provider_clients()``
+once the app exits, so streams still reading from it are not cut off.
+"""
+
+from __future__ import annotations
+
+import importlib.util
+from collections.abc import Mapping
+from dataclasses import dataclass, field
+
+import httpx
+
+from tunacode.constants import (
+    DEFAULT_PROVIDER_KEEPALIVE_EXPIRY_SECONDS,
+    DEFAULT_PROVIDER_MAX_CONNECTIONS,
+    DEFAULT_PROVIDER_MAX_KEEPALIVE_CONNECTIONS,
+)
+from tunacode.types import ProviderHttpSettings
+
+from tunacode.core.logging.manager import get_logger
+
+HTTP2_PACKAGE = "h2"
+
+
+@dataclass(frozen=True, slots=True)
+class ProviderHttpPolicy:
+    max_connections: int = DEFAULT_PROVIDER_MAX_CONNECTIONS
+    max_keepalive_connections: int = DEFAULT_PROVIDER_MAX_KEEPALIVE_CONNECTIONS
+    keepalive_expiry: float = DEFAULT_PROVIDER_KEEPALIVE_EXPIRY_SECONDS
+    http2: bool = False
+
+    @classmethod
+    def from_settings(cls, settings: ProviderHttpSettings) -> ProviderHttpPolicy:
+        return cls(
+            max_connections=settings["max_connections"],
+            max_keepalive_connections=settings["max_keepalive_connections"],
+            keepalive_expiry=settings["keepalive_expiry"],
+            http2=settings["http2"],
+        )
+
+    def limits(self) -> httpx.Limits:
+        return httpx.Limits(
+            max_connections=self.max_connections,
+            max_keepalive_connections=self.max_keepalive_connections,
+            keepalive_expiry=self.keepalive_expiry,
+        )
+
+
+ProviderHttpPolicies = tuple[tuple[str, ProviderHttpPolicy], ...]
+
+
+def provider_http_policies(settings: Mapping[str, ProviderHttpSettings]) -> ProviderHttpPolicies:
+    """Hashable per-provider policies, sorted by provider id."""
+    return tuple(
+        (provider_id, ProviderHttpPolicy.from_settings(entry))
+        for provider_id, entry in sorted(settings.items())
+    )
+
+
+def _http2_available() -> bool:
+    return importlib.util.find_spec(HTTP2_PACKAGE) is not None
+
+
+@dataclass
+class _ClientPool:
+    clients: dict[str, tuple[ProviderHttpPolicy, httpx.AsyncClient]] = field(default_factory=dict)
+    retired: list[httpx.AsyncClient] = field(default_factory=list)
+
+    def _create(self, provider_id: str, policy: ProviderHttpPolicy) -> httpx.AsyncClient:
+        http2 = policy.http2
+        if http2 and not _http2_available():
+            get_logger().warning(
+                f"provider_http.{provider_id}.http2 needs the '{HTTP2_PACKAGE}' package; "
+                "using HTTP/1.1"
+            )
+            http2 = False
+        return httpx.AsyncClient(limits=policy.limits(), http2=http2)
+
+    def client_for(self, provider_id: str, policy: ProviderHttpPolicy) -> httpx.AsyncClient:
+        entry = self.clients.get(provider_id)
+        if entry is not None:
+            current_policy, client = entry
+            if current_policy == policy and not client.is_closed:
+                return client
+            self.retired.append(client)
+        client = self._create(provider_id, policy)
+        self.clients[provider_id] = (policy, client)
+        return client
+
+
+_pool = _ClientPool()
+
+
+def provider_client(provider_id: str, policy: ProviderHttpPolicy) -> httpx.AsyncClient:
+    """The shared client for ``provider_id``; callers must not close it."""
+    return _pool.client_for(provider_id, policy)
+
+
+async def close_provider_clients() -> None:
+    clients = [client for _policy, client in _pool.clients.values()] + _pool.retired
+    _pool.clients.clear()
+    _pool.retired.clear()
+    for client in clients:
+        await client.aclose()
@@ -0,0 +1,79 @@
+"""Track the provider response a Responses API request can continue from.
+
+A stored Responses API response can be continued with ``previous_response_id``:
+the provider already holds the conversation that response ended, so the
+client only needs to send what happened since. ``ProviderResponseTracker``
+remembers the last response id together with a fingerprint of everything it
+covered -- the model, the system prompt, and the request items of every
+message sent plus the answer. ``delta_for()`` returns just the messages after
+that prefix when the history still starts with it.
+
+Anything that rewrites history (compaction, tool-output pruning, forking, a
+model switch, a new system prompt) changes the fingerprint, so the request
+falls back to a full resend and tracking restarts from its response.
+``background_responses`` owns the tracker and is the only caller.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+from collections.abc import Callable, Sequence
+from dataclasses import dataclass
+from typing import Any
+
+from tinyagent.agent_types import AgentMessage, Context, Model
+
+RequestItems = Callable[[Sequence[AgentMessage]], list[dict[str, Any]]]
+
+
+@dataclass(slots=True)
+class ProviderResponseTracker:
+    """The last provider response and the history prefix it already holds.
+
+    ``request_items`` converts messages to what the provider stored, so only
+    changes the provider can see break the prefix.
+    """
+
+    request_items: RequestItems
+    response_id: str | None = None
+    message_count: int = 0
+    fingerprint: str = ""
+
+    def reset(self) -> None:
+        self.response_id = None
+        self.message_count = 0
+        self.fingerprint = ""
+
+    def delta_for(self, model: Model, context: Context) -> list[AgentMessage] | None:
+        """Return the messages after the tracked prefix, or None to resend everything."""
+        messages = list(context.messages)
+        if self.response_id is None or len(messages) <= self.message_count:
+            return None
+        prefix = messages[: self.message_count]
+        if self._fingerprint(model, context.system_prompt, prefix) != self.fingerprint:
+            return None
+        return messages[self.message_count :]
+
+    def record(
+        self,
+        response_id: str,
+        model: Model,
+        context: Context,
+        answer: AgentMessage,
+    ) -> None:
+        """Remember ``response_id`` as covering ``context``'s messages and ``answer``."""
+        covered = [*context.messages, answer]
+        self.response_id = response_id
+        self.message_count = len(covered)
+        self.fingerprint = self._fingerprint(model, context.system_prompt, covered)
+
+    def _fingerprint(
+        self, model: Model, system_prompt: str, messages: Sequence[AgentMessage]
+    ) -> str:
+        digest = hashlib.sha256()
+        digest.update(f"{model.provider}:{model.id}\n{system_prompt}\n".encode())
+        for item in self.request_items(messages):
+            digest.update(json.dumps(item, sort_keys=True).encode())
+            digest.update(b"\n")
+        return digest.hexdigest()
@@ -0,0 +1,88 @@
+"""Detect unproductive tool-call loops in the agent turn loop.
+
+Every turn that calls tools is reduced to a signature: each call's name, its
+sorted JSON arguments, and a digest of its result. Including the result keeps
+legitimate repetition -- re-reading a file that is changing, re-running tests
+after an edit -- from looking like a loop. A loop is reported when the last
+``threshold`` turns repeat one signature, or alternate between two.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+from collections import deque
+from dataclasses import dataclass, field
+
+from tinyagent.agent_types import AssistantMessage, ToolCallContent, ToolResultMessage
+
+from tunacode.utils.messaging import get_content
+
+LOOP_PERIODS = (1, 2)
+RESULT_DIGEST_CHARS = 12
+LOOP_NUDGE_TEMPLATE = (
+    "[loop detector] {description}. Repeating these calls will not make progress: "
+    "change approach, or stop and tell the user what is blocking you."
+)
+
+
+def _result_digest(result: ToolResultMessage) -> str:
+    text = get_content(result)
+    payload = f"{result.is_error}:{text}".encode()
+    return hashlib.sha256(payload).hexdigest()[:RESULT_DIGEST_CHARS]
+
+
+def turn_signature(message: AssistantMessage, tool_results: list[ToolResultMessage]) -> str:
+    """Signature of one turn's tool calls and results; empty when it called no tools."""
+    results_by_id = {result.tool_call_id: result for result in tool_results}
+    call_signatures: list[str] = []
+    for item in message.content:
+        if not isinstance(item, ToolCallContent):
+            continue
+        arguments = json.dumps(item.arguments, sort_keys=True, default=str)
+        result = results_by_id.get(item.id)
+        digest = _result_digest(result) if result is not None else "-"
+        call_signatures.append(f"{item.name}({arguments})={digest}")
+    return "|".join(sorted(call_signatures))
+
+
+@dataclass(slots=True)
+class LoopDetector:
+    """Track recent turn signatures and report repeating patterns."""
+
+    threshold: int
+    _history: deque[str] = field(init=False)
+
+    def __post_init__(self) -> None:
+        self._history = deque(maxlen=max(LOOP_PERIODS) * max(self.threshold, 1))
+
+    def reset(self) -> None:
+        self._history.clear()
+
+    def observe(
+        self,
+        message: AssistantMessage,
+        tool_results: list[ToolResultMessage],
+    ) -> str | None:
+        """Record a turn; return a description when it completes a loop."""
+        if self.threshold < 2:
+            return None
+        signature = turn_signature(message, tool_results)
+        if not signature:
+            self._history.clear()
+            return None
+
+        self._history.append(signature)
+        history = list(self._history)
+        for period in LOOP_PERIODS:
+            span = period * self.threshold
+            if len(history) < span:
+                continue
+            recent = history[-span:]
+            if len(set(recent)) != period or recent != recent[-period:] * self.threshold:
+                continue
+            self._history.clear()
+            if period == 1:
+                return f"The same tool calls returned the same results {self.threshold} times"
+            return f"Tool calls alternated between two identical states {self.threshold} times"
+        return None
@@ -0,0 +1,429 @@
+"""Streams from Ollama's native ``/api/chat`` instead of its OpenAI-compatible shim.
+
+With ``settings.ollama.native_api``, requests for ``ollama:`` models go to
+``<root>/api/chat``, where ``<root>`` is the model's base URL without the
+``/v1/chat/completions`` suffix (``http://localhost:11434`` when none is set).
+The native API takes what the shim drops: ``settings.ollama.keep_alive``
+(how long the model stays loaded, e.g. ``"30m"``; empty for the server
+default) and ``settings.ollama.options``, passed through as Ollama's
+``options`` (``num_ctx``, ``temperature`` and so on). ``max_tokens`` becomes
+``num_predict`` unless the options set it.
+
+Before the first request for a model, ``/api/show`` is asked for its
+capabilities. A model Ollama does not have raises ``OllamaModelNotPulledError``
+naming the ``ollama pull`` command; tools are sent only to a model that lists
+the ``tools`` capability (or to an older server that reports none). The answer
+is remembered per root and model for the rest of the process.
+
+The newline-delimited JSON stream is mapped to the same events the other
+streams produce: ``message.content`` to ``text_delta``, ``message.thinking``
+to ``thinking_delta``, and ``message.tool_calls`` to tool calls with
+``stop_reason`` ``tool_calls``. The final chunk's ``prompt_eval_count`` and
+``eval_count`` become the usage, and an ``error`` chunk raises ``OllamaError``.
+"""
+
+from __future__ import annotations
+
+import json
+import time
+from collections.abc import AsyncIterator, Mapping
+from dataclasses import dataclass
+from typing import Any
+
+import httpx
+from tinyagent.agent_types import (
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+    TextContent,
+    ThinkingContent,
+    ToolCallContent,
+)
+
+from tunacode.types import OllamaSettings, UsageMetrics
+from tunacode.utils.messaging import to_canonical
+
+from tunacode.core.logging.manager import get_logger
+
+from .http_pool import ProviderHttpPolicy, provider_client
+
+OLLAMA_PROVIDER_ID = "ollama"
+DEFAULT_OLLAMA_ROOT = "http://localhost:11434"
+OPENAI_COMPAT_SUFFIXES = ("/chat/completions", "/v1")
+CHAT_PATH = "/api/chat"
+SHOW_PATH = "/api/show"
+TOOLS_CAPABILITY = "tools"
+NOT_FOUND_STATUS = 404
+DATA_URL_SEPARATOR = ";base64,"
+# Loading a large local model can take minutes before the first chunk arrives.
+READ_TIMEOUT_SECONDS = 300.0
+CONNECT_TIMEOUT_SECONDS = 10.0
+_MS_PER_S = 1000
+
+OptionValue = int | float | str | bool
+
+_REQUEST_TIMEOUT = httpx.Timeout(READ_TIMEOUT_SECONDS, connect=CONNECT_TIMEOUT_SECONDS)
+
+# (root, model id) -> capabilities from /api/show; None when the server lists none.
+_capabilities: dict[tuple[str, str], frozenset[str] | None] = {}
+
+
+class OllamaError(RuntimeError):
+    """Ollama answered with an error."""
+
+
+class OllamaModelNotPulledError(OllamaError):
+    """The requested model is not available locally."""
+
+    def __init__(self, model_id: str, detail: str) -> None:
+        super().__init__(
+            f"Ollama model '{model_id}' is not pulled ({detail}); run `ollama pull {model_id}`"
+        )
+        self.model_id = model_id
+
+
+@dataclass(frozen=True, slots=True)
+class OllamaConfig:
+    keep_alive: str = ""
+    options: tuple[tuple[str, OptionValue], ...] = ()
+
+    @classmethod
+    def from_settings(cls, settings: OllamaSettings) -> OllamaConfig | None:
+        """The native-API config, or None when ``native_api`` is off."""
+        if not settings["native_api"]:
+            return None
+        return cls(
+            keep_alive=settings["keep_alive"],
+            options=tuple(sorted(settings["options"].items())),
+        )
+
+
+def ollama_root(base_url: str | None) -> str:
+    """Server root for a base URL that may point at the OpenAI-compatible shim."""
+    root = (base_url or DEFAULT_OLLAMA_ROOT).rstrip("/")
+    for suffix in OPENAI_COMPAT_SUFFIXES:
+        root = root.removesuffix(suffix).rstrip("/")
+    return root
+
+
+def uses_ollama_native(model: Model) -> bool:
+    return model.provider == OLLAMA_PROVIDER_ID
+
+
+def _text(payload: dict[str, Any]) -> str:
+    return "".join(
+        item.get("text") or ""
+        for item in payload.get("content") or []
+        if item.get("type") == "text"
+    )
+
+
+def _images(payload: dict[str, Any]) -> list[str]:
+    images: list[str] = []
+    for item in payload.get("content") or []:
+        url = item.get("url") if item.get("type") == "image" else None
+        if isinstance(url, str) and DATA_URL_SEPARATOR in url:
+            images.append(url.split(DATA_URL_SEPARATOR, 1)[1])
+    return images
+
+
+def _assistant_message(payload: dict[str, Any]) -> dict[str, Any]:
+    message: dict[str, Any] = {"role": "assistant", "content": _text(payload)}
+    tool_calls = [
+        {"function": {"name": item.get("name"), "arguments": item.get("arguments") or {}}}
+        for item in payload.get("content") or []
+        if item.get("type") == "tool_call"
+    ]
+    if tool_calls:
+        message["tool_calls"] = tool_calls
+    return message
+
+
+def build_chat_messages(context: Context) -> list[dict[str, Any]]:
+    """Convert the conversation into ``/api/chat`` messages."""
+    messages: list[dict[str, Any]] = []
+    if context.system_prompt:
+        messages.append({"role": "system", "content": context.system_prompt})
+    for message in context.messages:
+        payload = to_canonical(message)
+        role = payload.get("role")
+        if role == "user":
+            user_message: dict[str, Any] = {"role": "user", "content": _text(payload)}
+            images = _images(payload)
+            if images:
+                user_message["images"] = images
+            messages.append(user_message)
+        elif role == "assistant":
+            messages.append(_assistant_message(payload))
+        elif role == "tool_result":
+            messages.append(
+                {"role": "tool", "content": _text(payload), "tool_name": payload.get("tool_name")}
+            )
+    return messages
+
+
+def build_chat_body(
+    model: Model,
+    context: Context,
+    options: SimpleStreamOptions,
+    config: OllamaConfig,
+    *,
+    include_tools: bool,
+) -> dict[str, Any]:
+    request_options: dict[str, OptionValue] = {}
+    if options.max_tokens is not None:
+        request_options["num_predict"] = options.max_tokens
+    if options.temperature is not None:
+        request_options["temperature"] = options.temperature
+    request_options.update(config.options)
+    body: dict[str, Any] = {
+        "model": model.id,
+        "messages": build_chat_messages(context),
+        "stream": True,
+    }
+    if request_options:
+        body["options"] = request_options
+    if config.keep_alive:
+        body["keep_alive"] = config.keep_alive
+    if include_tools and context.tools:
+        body["tools"] = [
+            {
+                "type": "function",
+                "function": {
+                    "name": tool.name,
+                    "description": tool.description,
+                    "parameters": tool.parameters,
+                },
+            }
+            for tool in context.tools
+        ]
+    return body
+
+
+def _error_detail(response: httpx.Response) -> str:
+    try:
+        payload = response.json()
+    except ValueError:
+        return response.text or f"HTTP {response.status_code}"
+    error = payload.get("error") if isinstance(payload, dict) else None
+    return error if isinstance(error, str) else f"HTTP {response.status_code}"
+
+
+async def _model_capabilities(
+    client: httpx.AsyncClient, root: str, model_id: str
+) -> frozenset[str] | None:
+    key = (root, model_id)
+    if key in _capabilities:
+        return _capabilities[key]
+    response = await client.post(
+        f"{root}{SHOW_PATH}", json={"model": model_id}, timeout=_REQUEST_TIMEOUT
+    )
+    if response.status_code == NOT_FOUND_STATUS:
+        raise OllamaModelNotPulledError(model_id, _error_detail(response))
+    if response.is_error:
+        raise OllamaError(f"Ollama {SHOW_PATH} for '{model_id}' failed: {_error_detail(response)}")
+    payload = response.json()
+    raw = payload.get("capabilities") if isinstance(payload, dict) else None
+    capabilities = frozenset(str(item) for item in raw) if isinstance(raw, list) else None
+    _capabilities[key] = capabilities
+    return capabilities
+
+
+class OllamaChatStream:
+    """``StreamResponse`` over one streamed ``/api/chat`` response."""
+
+    def __init__(
+        self,
+        client: httpx.AsyncClient,
+        response: httpx.Response,
+        *,
+        model: Model,
+        owns_client: bool,
+    ) -> None:
+        self._client = client
+        self._response = response
+        self._model = model
+        self._owns_client = owns_client
+        self._text = ""
+        self._thinking = ""
+        self._tool_calls: list[ToolCallContent] = []
+        self._usage: dict[str, Any] = UsageMetrics().to_dict()
+        self._stop_reason = "stop"
+        self._events = self._stream_events()
+
+    def __aiter__(self) -> OllamaChatStream:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        return await self._events.__anext__()
+
+    async def result(self) -> AssistantMessage:
+        async for _event in self:
+            pass
+        return self._message()
+
+    async def _stream_events(self) -> AsyncIterator[AssistantMessageEvent]:
+        try:
+            async for line in self._response.aiter_lines():
+                if not line.strip():
+                    continue
+                chunk = json.loads(line)
+                if not isinstance(chunk, dict):
+                    continue
+                for event in self._handle_chunk(chunk):
+                    yield event
+        finally:
+            await self._response.aclose()
+            if self._owns_client:
+                await self._client.aclose()
+
+    def _handle_chunk(self, chunk: dict[str, Any]) -> list[AssistantMessageEvent]:
+        error = chunk.get("error")
+        if error:
+            raise OllamaError(f"Ollama stream failed: {error}")
+        message = chunk.get("message") if isinstance(chunk.get("message"), dict) else {}
+        events: list[AssistantMessageEvent] = []
+        thinking = message.get("thinking") or ""
+        if thinking:
+            self._thinking += thinking
+            events.append(
+                AssistantMessageEvent(
+                    type="thinking_delta", delta=thinking, partial=self._message()
+                )
+            )
+        text = message.get("content") or ""
+        if text:
+            self._text += text
+            events.append(
+                AssistantMessageEvent(type="text_delta", delta=text, partial=self._message())
+            )
+        for tool_call in message.get("tool_calls") or []:
+            self._add_tool_call(tool_call)
+        if chunk.get("done"):
+            events.append(self._finish(chunk))
+        return events
+
+    def _add_tool_call(self, tool_call: object) -> None:
+        function = tool_call.get("function") if isinstance(tool_call, dict) else None
+        if not isinstance(function, dict):
+            return
+        arguments = function.get("arguments") or {}
+        if isinstance(arguments, str):
+            try:
+                arguments = json.loads(arguments)
+            except ValueError:
+                arguments = {}
+        call_id = tool_call.get("id") or f"ollama_call_{len(self._tool_calls)}"
+        self._tool_calls.append(
+            ToolCallContent(
+                id=call_id,
+                name=function.get("name") or "",
+                arguments=arguments if isinstance(arguments, dict) else {},
+            )
+        )
+
+    def _finish(self, chunk: dict[str, Any]) -> AssistantMessageEvent:
+        input_tokens = int(chunk.get("prompt_eval_count") or 0)
+        output_tokens = int(chunk.get("eval_count") or 0)
+        self._usage = UsageMetrics(
+            input=input_tokens, output=output_tokens, total_tokens=input_tokens + output_tokens
+        ).to_dict()
+        if self._tool_calls:
+            self._stop_reason = "tool_calls"
+        elif chunk.get("done_reason") == "length":
+            self._stop_reason = "length"
+        return AssistantMessageEvent(type="done", partial=self._message())
+
+    def _message(self) -> AssistantMessage:
+        content: list[object] = []
+        if self._thinking:
+            content.append(ThinkingContent(thinking=self._thinking))
+        if self._text:
+            content.append(TextContent(text=self._text))
+        content.extend(self._tool_calls)
+        return AssistantMessage(
+            content=content,
+            stop_reason=self._stop_reason,
+            usage=self._usage,
+            provider=self._model.provider,
+            model=self._model.id,
+            timestamp=int(time.time() * _MS_PER_S),
+        )
+
+
+async def open_ollama_chat(
+    model: Model,
+    context: Context,
+    options: SimpleStreamOptions,
+    *,
+    config: OllamaConfig,
+    transport: httpx.AsyncBaseTransport | None = None,
+    http_policy: ProviderHttpPolicy | None = None,
+) -> OllamaChatStream:
+    """Start a native chat stream; a ``transport`` (tests) gets a dedicated client."""
+    root = ollama_root(model.base_url)
+    owns_client = transport is not None
+    client = (
+        httpx.AsyncClient(transport=transport)
+        if owns_client
+        else provider_client(model.provider, http_policy or ProviderHttpPolicy())
+    )
+    try:
+        capabilities = await _model_capabilities(client, root, model.id)
+        include_tools = capabilities is None or TOOLS_CAPABILITY in capabilities
+        if context.tools and not include_tools:
+            get_logger().warning(
+                f"Ollama model '{model.id}' does not advertise tool support; sending no tools"
+            )
+        request = client.build_request(
+            "POST",
+            f"{root}{CHAT_PATH}",
+            json=build_chat_body(model, context, options, config, include_tools=include_tools),
+            timeout=_REQUEST_TIMEOUT,
+        )
+        response = await client.send(request, stream=True)
+        if response.is_error:
+            await response.aread()
+            await response.aclose()
+            detail = _error_detail(response)
+            if response.status_code == NOT_FOUND_STATUS:
+                raise OllamaModelNotPulledError(model.id, detail)
+            raise OllamaError(f"Ollama {CHAT_PATH} failed: {detail}")
+    except BaseException:
+        if owns_client:
+            await client.aclose()
+        raise
+    return OllamaChatStream(client, response, model=model, owns_client=owns_client)
+
+
+def with_ollama_native(
+    stream_fn: StreamFn,
+    config: OllamaConfig,
+    *,
+    transport: httpx.AsyncBaseTransport | None = None,
+    http_policies: Mapping[str, ProviderHttpPolicy] | None = None,
+) -> StreamFn:
+    """Wrap ``stream_fn`` so ``ollama:`` models use the native chat API."""
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        if not uses_ollama_native(model):
+            return await stream_fn(model, context, options)
+        stream = await open_ollama_chat(
+            model,
+            context,
+            options,
+            config=config,
+            transport=transport,
+            http_policy=(http_policies or {}).get(model.provider),
+        )
+        return stream  # type: ignore[return-value]
+
+    return _stream
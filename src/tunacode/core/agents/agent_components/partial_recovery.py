@@ -0,0 +1,143 @@
+"""Salvage completed tool calls from an assistant turn cut off by a stream error.
+
+When a provider stream fails after the model already emitted whole tool calls,
+those calls are still worth running: the model chose them and their arguments
+are complete. A tool call counts as complete when it has an id, a name, and
+object arguments whose raw JSON (if any) parses; anything still mid-stream is
+dropped. The salvaged turn is rewritten as a normal ``tool_calls`` turn so the
+follow-up request only has to regenerate the text that was lost.
+"""
+
+from __future__ import annotations
+
+import json
+import time
+from collections.abc import Sequence
+from typing import cast
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    AgentTool,
+    AgentToolResult,
+    AssistantMessage,
+    JsonObject,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+)
+
+RECOVERY_CONTINUE_PROMPT = (
+    "Your previous response was cut off by a stream error after its tool calls "
+    "were emitted. Those tool calls have been run and their results are above. "
+    "Continue from those results."
+)
+RECOVERY_NOTICE_TEMPLATE = (
+    "Stream failed after {count} complete tool call(s); ran them and retrying the response..."
+)
+SALVAGED_STOP_REASON = "tool_calls"
+_MS_PER_S = 1000
+
+
+def is_complete_tool_call(item: object) -> bool:
+    """Return True when ``item`` is a tool call that finished streaming."""
+    if not isinstance(item, ToolCallContent):
+        return False
+    if not isinstance(item.id, str) or not item.id:
+        return False
+    if not isinstance(item.name, str) or not item.name:
+        return False
+    if not isinstance(item.arguments, dict):
+        return False
+
+    if not item.partial_json:
+        return True
+    try:
+        return isinstance(json.loads(item.partial_json), dict)
+    except (TypeError, ValueError):
+        return False
+
+
+def trailing_error_message(messages: Sequence[AgentMessage]) -> AssistantMessage | None:
+    """Return the last message when it is an assistant turn that ended in an error."""
+    if not messages:
+        return None
+    message = messages[-1]
+    if isinstance(message, AssistantMessage) and message.stop_reason == "error":
+        return message
+    return None
+
+
+def salvage_tool_calls(message: AssistantMessage) -> AssistantMessage | None:
+    """Return ``message`` trimmed to its complete tool calls, or None if it has none.
+
+    Text and thinking blocks are kept; partially streamed tool calls are removed.
+    """
+    kept: list[object] = []
+    salvaged_count = 0
+    for item in message.content:
+        if isinstance(item, ToolCallContent):
+            if not is_complete_tool_call(item):
+                continue
+            salvaged_count += 1
+        kept.append(item)
+
+    if salvaged_count == 0:
+        return None
+    return message.model_copy(update={"content": kept, "stop_reason": SALVAGED_STOP_REASON})
+
+
+def _error_result(tool_call: ToolCallContent, text: str) -> ToolResultMessage:
+    return ToolResultMessage(
+        tool_call_id=tool_call.id,
+        tool_name=tool_call.name,
+        content=[TextContent(text=text)],
+        details={},
+        is_error=True,
+        timestamp=int(time.time() * _MS_PER_S),
+    )
+
+
+async def _execute_tool_call(
+    tool_call: ToolCallContent,
+    tools_by_name: dict[str, AgentTool],
+) -> ToolResultMessage:
+    tool = tools_by_name.get(tool_call.name)
+    if tool is None or tool.execute is None:
+        return _error_result(tool_call, f"Tool not found: {tool_call.name}")
+
+    try:
+        result: AgentToolResult = await tool.execute(
+            tool_call.id,
+            cast(JsonObject, tool_call.arguments),
+            None,
+            lambda _update: None,
+        )
+    except Exception as exc:
+        return _error_result(tool_call, str(exc))
+
+    return ToolResultMessage(
+        tool_call_id=tool_call.id,
+        tool_name=tool_call.name,
+        content=list(result.content),
+        details=result.details,
+        is_error=False,
+        timestamp=int(time.time() * _MS_PER_S),
+    )
+
+
+async def execute_tool_calls(
+    tool_calls: Sequence[ToolCallContent],
+    tools: Sequence[AgentTool],
+) -> list[ToolResultMessage]:
+    """Run ``tool_calls`` in order and return their result messages."""
+    tools_by_name = {tool.name: tool for tool in tools}
+    return [await _execute_tool_call(tool_call, tools_by_name) for tool_call in tool_calls]
+
+
+async def execute_salvaged_tool_calls(
+    message: AssistantMessage,
+    tools: Sequence[AgentTool],
+) -> list[ToolResultMessage]:
+    """Run each tool call in ``message`` in order and return their result messages."""
+    tool_calls = [item for item in message.content if isinstance(item, ToolCallContent)]
+    return await execute_tool_calls(tool_calls, tools)
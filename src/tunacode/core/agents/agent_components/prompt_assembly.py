@@ -0,0 +1,150 @@
+"""Pluggable system prompt assembly from ordered context providers.
+
+A ``ContextProvider`` contributes one labeled section of the system prompt.
+The built-in providers (base system prompt, project ``AGENTS.md`` guide,
+selected skills, available skills) run first, followed by any provider an
+embedder registered with ``register_context_provider()``, in registration
+order. Sections are concatenated as-is; labels are metadata for budgeting and
+diagnostics, not rendered text.
+
+When ``settings.system_prompt_max_tokens`` is set, sections are trimmed from
+the lowest priority up -- truncated if that is enough, dropped otherwise --
+until the prompt fits. Providers run when an agent is built, so a registered
+provider takes effect for the next agent created.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Callable, Sequence
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Protocol
+
+from tunacode.utils.messaging import estimate_tokens
+from tunacode.utils.messaging.token_counter import CHARS_PER_TOKEN
+
+from .agent_session_config import SkillsPromptState
+
+DEFAULT_CONTEXT_PRIORITY = 50
+TRUNCATED_SECTION_MARKER = "\n[... section truncated to fit the system prompt budget ...]\n"
+
+
+@dataclass(frozen=True, slots=True)
+class PromptContext:
+    """Inputs available to every provider while a prompt is assembled."""
+
+    model: str
+    base_path: Path
+    skills_state: SkillsPromptState
+
+
+class ContextProvider(Protocol):
+    """Contributes one labeled section to the system prompt.
+
+    Higher ``priority`` sections are kept longest when the budget is tight.
+    """
+
+    @property
+    def label(self) -> str: ...
+
+    @property
+    def priority(self) -> int: ...
+
+    def render(self, context: PromptContext) -> str: ...
+
+
+@dataclass(frozen=True, slots=True)
+class FunctionContextProvider:
+    """``ContextProvider`` backed by a plain function."""
+
+    label: str
+    render_fn: Callable[[PromptContext], str]
+    priority: int = DEFAULT_CONTEXT_PRIORITY
+
+    def render(self, context: PromptContext) -> str:
+        return self.render_fn(context)
+
+
+@dataclass(frozen=True, slots=True)
+class PromptSection:
+    label: str
+    priority: int
+    text: str
+    truncated: bool = False
+
+
+@dataclass(slots=True)
+class AssembledPrompt:
+    sections: list[PromptSection]
+    dropped: list[str] = field(default_factory=list)
+
+    @property
+    def text(self) -> str:
+        return "".join(section.text for section in self.sections)
+
+
+@dataclass(slots=True)
+class _ProviderRegistry:
+    providers: list[ContextProvider] = field(default_factory=list)
+
+
+_registry = _ProviderRegistry()
+
+
+def register_context_provider(provider: ContextProvider) -> None:
+    """Add ``provider`` after the built-ins; a provider with the same label is replaced."""
+    unregister_context_provider(provider.label)
+    _registry.providers.append(provider)
+
+
+def unregister_context_provider(label: str) -> None:
+    _registry.providers = [p for p in _registry.providers if p.label != label]
+
+
+def registered_context_providers() -> list[ContextProvider]:
+    return list(_registry.providers)
+
+
+def _fit_to_budget(sections: list[PromptSection], budget_tokens: int) -> AssembledPrompt:
+    excess_chars = (
+        estimate_tokens("".join(section.text for section in sections)) - budget_tokens
+    ) * CHARS_PER_TOKEN
+    fitted = list(sections)
+    dropped: list[str] = []
+    trim_order = sorted(range(len(sections)), key=lambda index: sections[index].priority)
+    for index in trim_order:
+        if excess_chars <= 0:
+            break
+        section = sections[index]
+        keep_chars = len(section.text) - excess_chars - len(TRUNCATED_SECTION_MARKER)
+        if keep_chars > 0:
+            fitted[index] = PromptSection(
+                label=section.label,
+                priority=section.priority,
+                text=section.text[:keep_chars] + TRUNCATED_SECTION_MARKER,
+                truncated=True,
+            )
+            excess_chars = 0
+            break
+        excess_chars -= len(section.text)
+        dropped.append(section.label)
+
+    kept = [section for section in fitted if section.label not in dropped]
+    return AssembledPrompt(sections=kept, dropped=dropped)
+
+
+def assemble_prompt(
+    providers: Sequence[ContextProvider],
+    context: PromptContext,
+    *,
+    budget_tokens: int | None = None,
+) -> AssembledPrompt:
+    """Render every provider in order and enforce ``budget_tokens`` when given."""
+    sections = [
+        PromptSection(label=provider.label, priority=provider.priority, text=text)
+        for provider in providers
+        if (text := provider.render(context))
+    ]
+    if budget_tokens is None:
+        return AssembledPrompt(sections=sections)
+    return _fit_to_budget(sections, budget_tokens)
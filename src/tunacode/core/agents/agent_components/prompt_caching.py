@@ -0,0 +1,81 @@
+"""Prompt caching hints for providers that cache repeated prompt prefixes."""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    Context,
+    Model,
+    TextContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.configuration.models import model_supports_prompt_caching
+
+PROMPT_CACHE_MODE_NONE = "none"
+PROMPT_CACHE_MODE_AUTOMATIC = "automatic"
+PROMPT_CACHE_MODE_EXPLICIT = "explicit"
+
+ANTHROPIC_PROVIDER_ID = "anthropic"
+CLAUDE_MODEL_MARKER = "claude"
+
+CACHE_CONTROL_KEY = "cache_control"
+EPHEMERAL_CACHE_CONTROL = {"type": "ephemeral"}
+
+
+def resolve_prompt_cache_mode(provider_id: str, model_id: str) -> str:
+    """Return how the provider expects prompt caching to be requested.
+
+    - ``explicit``: Anthropic-family models need ``cache_control`` breakpoints.
+    - ``automatic``: the provider caches prefixes on its own (OpenAI style).
+    - ``none``: the registry does not price cached reads for this model.
+    """
+
+    if not model_supports_prompt_caching(f"{provider_id}:{model_id}"):
+        return PROMPT_CACHE_MODE_NONE
+
+    if provider_id == ANTHROPIC_PROVIDER_ID or CLAUDE_MODEL_MARKER in model_id.lower():
+        return PROMPT_CACHE_MODE_EXPLICIT
+
+    return PROMPT_CACHE_MODE_AUTOMATIC
+
+
+def apply_prompt_cache_hints(model: Model, context: Context) -> Context:
+    """Mark cache breakpoints on the request context when the model needs them.
+
+    Breakpoints cover the whole prefix before them, so marking the first
+    message caches tools + system prompt + stable early history, and marking
+    the last message lets the next turn reuse everything sent so far.
+    """
+
+    mode = resolve_prompt_cache_mode(model.provider, model.id)
+    if mode != PROMPT_CACHE_MODE_EXPLICIT or not context.messages:
+        return context
+
+    messages = list(context.messages)
+    breakpoint_indexes = sorted({0, len(messages) - 1})
+    for index in breakpoint_indexes:
+        messages[index] = _mark_cache_breakpoint(messages[index])
+
+    return Context(
+        system_prompt=context.system_prompt,
+        messages=messages,
+        tools=context.tools,
+    )
+
+
+def _mark_cache_breakpoint(message: AgentMessage) -> AgentMessage:
+    if not isinstance(message, UserMessage | ToolResultMessage):
+        return message
+
+    content = list(message.content)
+    for index in range(len(content) - 1, -1, -1):
+        item = content[index]
+        if not isinstance(item, TextContent):
+            continue
+        cache_control = dict(EPHEMERAL_CACHE_CONTROL)
+        content[index] = item.model_copy(update={CACHE_CONTROL_KEY: cache_control})
+        return message.model_copy(update={"content": content})
+
+    return message
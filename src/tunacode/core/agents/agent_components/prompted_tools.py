@@ -0,0 +1,410 @@
+"""Tool use for models without native function calling.
+
+``settings.prompted_tools`` lists ``provider:model`` patterns (``fnmatch``
+style, e.g. ``"ollama:hermes*"``) whose requests are sent without native tool
+definitions. Instead the tools are described at the end of the system prompt,
+and the model is told to call one by writing a block in the configured
+``format``:
+
+- ``xml``: ``<tool_call>{"name": ..., "arguments": {...}}</tool_call>``
+- ``json``: a fenced block opened with ````` ```tool_call ````` holding the
+  same JSON object.
+
+The streamed text is scanned for those blocks as it arrives. Text that could
+be the start of a block is held back until it is clear, text inside a block is
+never shown, and each closed block becomes a ``ToolCallContent`` in the
+response, which then ends with ``stop_reason`` ``tool_calls`` like a native
+tool call. A block left open when the stream ends is still used if its JSON is
+complete; a block whose JSON does not parse is kept as plain text and logged.
+
+History is rewritten the same way on every request: earlier tool calls become
+blocks in the assistant text, and tool results are sent as user messages
+wrapped in the format's result markers.
+
+Further formats plug in through ``register_tool_call_format()``; a format
+renders tools, calls and results and supplies an incremental parser.
+"""
+
+from __future__ import annotations
+
+import json
+import uuid
+from collections.abc import AsyncIterator, Sequence
+from dataclasses import dataclass, field
+from fnmatch import fnmatchcase
+from typing import Any, Protocol
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    JsonObject,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.types import PromptedToolsSettings
+
+from tunacode.core.logging.manager import get_logger
+
+TOOL_CALLS_STOP_REASON = "tool_calls"
+# Provider stop reasons a parsed tool call does not override.
+_TERMINAL_STOP_REASONS = frozenset({"error", "aborted"})
+CALL_ID_PREFIX = "prompted_call_"
+
+
+@dataclass(frozen=True, slots=True)
+class ParsedChunk:
+    """Visible text and completed ``(name, arguments)`` calls from one parser step."""
+
+    text: str = ""
+    calls: list[tuple[str, JsonObject]] = field(default_factory=list)
+
+
+class ToolCallParser(Protocol):
+    def feed(self, text: str) -> ParsedChunk: ...
+
+    def finish(self) -> ParsedChunk: ...
+
+
+class ToolCallFormat(Protocol):
+    """How tools, tool calls and tool results look in prompted tool calling."""
+
+    @property
+    def name(self) -> str: ...
+
+    def describe_tools(self, tools: Sequence[AgentTool]) -> str: ...
+
+    def render_call(self, name: str, arguments: JsonObject) -> str: ...
+
+    def render_result(self, tool_name: str, text: str, *, is_error: bool) -> str: ...
+
+    def new_parser(self) -> ToolCallParser: ...
+
+
+def parse_tool_call_payload(payload: str) -> tuple[str, JsonObject] | None:
+    """``(name, arguments)`` from a block's JSON, or None when it is not a tool call."""
+    try:
+        data = json.loads(payload)
+    except ValueError:
+        return None
+    if not isinstance(data, dict):
+        return None
+    name = data.get("name")
+    arguments = data.get("arguments", data.get("parameters", {}))
+    if isinstance(arguments, str):
+        try:
+            arguments = json.loads(arguments)
+        except ValueError:
+            return None
+    if not isinstance(name, str) or not name or not isinstance(arguments, dict):
+        return None
+    return name, arguments
+
+
+def _held_prefix_length(text: str, marker: str) -> int:
+    """Length of the longest suffix of ``text`` that starts ``marker``."""
+    for length in range(min(len(text), len(marker) - 1), 0, -1):
+        if marker.startswith(text[-length:]):
+            return length
+    return 0
+
+
+class DelimitedBlockParser:
+    """Incremental parser for JSON tool calls between an open and a close marker."""
+
+    def __init__(self, open_marker: str, close_marker: str) -> None:
+        self._open = open_marker
+        self._close = close_marker
+        self._buffer = ""
+        self._inside = False
+
+    def feed(self, text: str) -> ParsedChunk:
+        self._buffer += text
+        visible: list[str] = []
+        calls: list[tuple[str, JsonObject]] = []
+        progressed = True
+        while progressed:
+            if self._inside:
+                progressed = self._take_block(visible, calls)
+            else:
+                progressed = self._take_text(visible)
+        return ParsedChunk(text="".join(visible), calls=calls)
+
+    def finish(self) -> ParsedChunk:
+        remainder, self._buffer = self._buffer, ""
+        if not self._inside:
+            return ParsedChunk(text=remainder)
+        self._inside = False
+        call = parse_tool_call_payload(remainder)
+        if call is None:
+            _log_unparsed_block(remainder)
+            return ParsedChunk(text=f"{self._open}{remainder}")
+        return ParsedChunk(calls=[call])
+
+    def _take_text(self, visible: list[str]) -> bool:
+        start = self._buffer.find(self._open)
+        if start < 0:
+            held = _held_prefix_length(self._buffer, self._open)
+            visible.append(self._buffer[: len(self._buffer) - held])
+            self._buffer = self._buffer[len(self._buffer) - held :]
+            return False
+        visible.append(self._buffer[:start])
+        self._buffer = self._buffer[start + len(self._open) :]
+        self._inside = True
+        return True
+
+    def _take_block(self, visible: list[str], calls: list[tuple[str, JsonObject]]) -> bool:
+        end = self._buffer.find(self._close)
+        if end < 0:
+            return False
+        payload = self._buffer[:end]
+        self._buffer = self._buffer[end + len(self._close) :]
+        self._inside = False
+        call = parse_tool_call_payload(payload)
+        if call is None:
+            _log_unparsed_block(payload)
+            visible.append(f"{self._open}{payload}{self._close}")
+        else:
+            calls.append(call)
+        return True
+
+
+def _log_unparsed_block(payload: str) -> None:
+    get_logger().warning(f"Prompted tool call block is not a valid tool call: {payload[:200]!r}")
+
+
+@dataclass(frozen=True, slots=True)
+class DelimitedToolCallFormat:
+    """A format whose calls are JSON objects between fixed markers."""
+
+    name: str
+    call_open: str
+    call_close: str
+    result_open: str
+    result_close: str
+
+    def describe_tools(self, tools: Sequence[AgentTool]) -> str:
+        example = self.render_call("<tool name>", {"<argument>": "<value>"})
+        lines = [
+            "# Tools",
+            "",
+            "To call a tool, write a block exactly like this, with the arguments as JSON:",
+            "",
+            example,
+            "",
+            "You may write several blocks in one reply. After your tool calls, stop and "
+            "wait for the results; each one comes back as",
+            f"{self.result_open.format(name='<tool name>')} ... {self.result_close}.",
+            "",
+            "Available tools:",
+        ]
+        for tool in tools:
+            lines += [
+                "",
+                f"## {tool.name}",
+                tool.description,
+                f"Parameters (JSON schema): {json.dumps(tool.parameters, sort_keys=True)}",
+            ]
+        return "\n".join(lines)
+
+    def render_call(self, name: str, arguments: JsonObject) -> str:
+        payload = json.dumps({"name": name, "arguments": arguments})
+        return f"{self.call_open}\n{payload}\n{self.call_close}"
+
+    def render_result(self, tool_name: str, text: str, *, is_error: bool) -> str:
+        body = f"Error: {text}" if is_error else text
+        return f"{self.result_open.format(name=tool_name)}\n{body}\n{self.result_close}"
+
+    def new_parser(self) -> ToolCallParser:
+        return DelimitedBlockParser(self.call_open, self.call_close)
+
+
+XML_TOOL_CALL_FORMAT = DelimitedToolCallFormat(
+    name="xml",
+    call_open="<tool_call>",
+    call_close="</tool_call>",
+    result_open='<tool_result name="{name}">',
+    result_close="</tool_result>",
+)
+JSON_TOOL_CALL_FORMAT = DelimitedToolCallFormat(
+    name="json",
+    call_open="```tool_call",
+    call_close="```",
+    result_open="```tool_result {name}",
+    result_close="```",
+)
+
+
+@dataclass
+class _FormatRegistry:
+    formats: dict[str, ToolCallFormat] = field(
+        default_factory=lambda: {
+            XML_TOOL_CALL_FORMAT.name: XML_TOOL_CALL_FORMAT,
+            JSON_TOOL_CALL_FORMAT.name: JSON_TOOL_CALL_FORMAT,
+        }
+    )
+
+
+_registry = _FormatRegistry()
+
+
+def register_tool_call_format(call_format: ToolCallFormat) -> None:
+    """Make ``call_format`` selectable by name; a format with the same name is replaced."""
+    _registry.formats[call_format.name] = call_format
+
+
+def unregister_tool_call_format(name: str) -> None:
+    _registry.formats.pop(name, None)
+
+
+def tool_call_format(name: str) -> ToolCallFormat:
+    call_format = _registry.formats.get(name)
+    if call_format is None:
+        known = ", ".join(sorted(_registry.formats))
+        raise ValueError(f"Unknown settings.prompted_tools.format '{name}' (known: {known})")
+    return call_format
+
+
+@dataclass(frozen=True, slots=True)
+class PromptedToolsConfig:
+    models: tuple[str, ...]
+    format: str
+
+    @classmethod
+    def from_settings(cls, settings: PromptedToolsSettings) -> PromptedToolsConfig | None:
+        """The config, or None when no model uses prompted tool calling."""
+        if not settings["models"]:
+            return None
+        tool_call_format(settings["format"])
+        return cls(models=tuple(settings["models"]), format=settings["format"])
+
+    def applies_to(self, model: Model) -> bool:
+        model_key = f"{model.provider}:{model.id}"
+        return any(fnmatchcase(model_key, pattern) for pattern in self.models)
+
+
+def _text(content: Sequence[object]) -> str:
+    return "".join(item.text for item in content if isinstance(item, TextContent))
+
+
+def _prompted_message(message: Any, call_format: ToolCallFormat) -> Any:
+    if isinstance(message, ToolResultMessage):
+        text = call_format.render_result(
+            message.tool_name, _text(message.content), is_error=message.is_error
+        )
+        return UserMessage(content=[TextContent(text=text)], timestamp=message.timestamp)
+    if not isinstance(message, AssistantMessage):
+        return message
+    calls = [item for item in message.content if isinstance(item, ToolCallContent)]
+    if not calls:
+        return message
+    blocks = [call_format.render_call(call.name, call.arguments) for call in calls]
+    text = "\n\n".join(part for part in (_text(message.content), *blocks) if part)
+    content = [
+        item
+        for item in message.content
+        if not isinstance(item, TextContent | ToolCallContent)
+    ]
+    return message.model_copy(update={"content": [*content, TextContent(text=text)]})
+
+
+def build_prompted_context(context: Context, call_format: ToolCallFormat) -> Context:
+    """``context`` with tools moved into the system prompt and tool turns rewritten as text."""
+    system_prompt = context.system_prompt
+    if context.tools:
+        description = call_format.describe_tools(context.tools)
+        system_prompt = f"{system_prompt}\n\n{description}" if system_prompt else description
+    return Context(
+        system_prompt=system_prompt,
+        messages=[_prompted_message(message, call_format) for message in context.messages],
+        tools=None,
+    )
+
+
+class PromptedToolStream:
+    """``StreamResponse`` that turns tool-call blocks in the streamed text into tool calls."""
+
+    def __init__(self, response: StreamResponse, parser: ToolCallParser) -> None:
+        self._response = response
+        self._parser = parser
+        self._text = ""
+        self._calls: list[ToolCallContent] = []
+        self._events = self._stream_events()
+
+    def __aiter__(self) -> PromptedToolStream:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        return await self._events.__anext__()
+
+    async def result(self) -> AssistantMessage:
+        async for _event in self:
+            pass
+        return self._rewrite(await self._response.result())
+
+    def _accept(self, chunk: ParsedChunk) -> str:
+        self._text += chunk.text
+        for name, arguments in chunk.calls:
+            call_id = f"{CALL_ID_PREFIX}{uuid.uuid4().hex[:12]}"
+            self._calls.append(ToolCallContent(id=call_id, name=name, arguments=arguments))
+        return chunk.text
+
+    async def _stream_events(self) -> AsyncIterator[AssistantMessageEvent]:
+        async for event in self._response:
+            if event.type == "text_delta":
+                visible = self._accept(self._parser.feed(event.delta or ""))
+                if visible:
+                    yield self._with_partial(event, delta=visible)
+                continue
+            if event.type == "done":
+                leftover = self._accept(self._parser.finish())
+                if leftover:
+                    yield self._with_partial(
+                        AssistantMessageEvent(type="text_delta", partial=event.partial),
+                        delta=leftover,
+                    )
+            yield self._with_partial(event, delta=event.delta)
+
+    def _with_partial(
+        self, event: AssistantMessageEvent, *, delta: str | None
+    ) -> AssistantMessageEvent:
+        partial = event.partial
+        update: dict[str, Any] = {"delta": delta}
+        if isinstance(partial, AssistantMessage):
+            update["partial"] = self._rewrite(partial)
+        return event.model_copy(update=update)
+
+    def _rewrite(self, message: AssistantMessage) -> AssistantMessage:
+        content = [item for item in message.content if not isinstance(item, TextContent)]
+        if self._text.strip():
+            content.append(TextContent(text=self._text))
+        content.extend(self._calls)
+        update: dict[str, Any] = {"content": content}
+        if self._calls and message.stop_reason not in _TERMINAL_STOP_REASONS:
+            update["stop_reason"] = TOOL_CALLS_STOP_REASON
+        return message.model_copy(update=update)
+
+
+def with_prompted_tools(stream_fn: StreamFn, config: PromptedToolsConfig) -> StreamFn:
+    """Wrap ``stream_fn`` so matching models call tools through prompted text blocks."""
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        if not config.applies_to(model):
+            return await stream_fn(model, context, options)
+        call_format = tool_call_format(config.format)
+        response = await stream_fn(model, build_prompted_context(context, call_format), options)
+        return PromptedToolStream(response, call_format.new_parser())  # type: ignore[return-value]
+
+    return _stream
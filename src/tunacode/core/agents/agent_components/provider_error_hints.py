@@ -0,0 +1,148 @@
+"""Turn common provider request errors into actionable hints.
+
+A 400 such as ``max_tokens: 64000 > 32000`` or ``model does not support
+tools`` used to surface verbatim. ``match_provider_error()`` checks the error
+text against the patterns registered for the provider (``PROVIDER_ERROR_PATTERNS``)
+and then against the patterns every provider shares, returning a
+``ProviderErrorHint`` with a typed ``kind``, a suggested fix and the raw
+message unchanged. ``build_agent_error()`` raises that as an ``AgentError``
+whose ``raw_message`` still holds the provider's own text; unmatched errors
+keep their plain message.
+"""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from enum import StrEnum
+
+from tunacode.exceptions import AgentError
+
+
+class ProviderErrorKind(StrEnum):
+    MAX_TOKENS_TOO_LARGE = "max_tokens_too_large"
+    TOOLS_UNSUPPORTED = "tools_unsupported"
+    IMAGES_UNSUPPORTED = "images_unsupported"
+    PARAMETER_UNSUPPORTED = "parameter_unsupported"
+    MODEL_NOT_PULLED = "model_not_pulled"
+
+
+HINT_SUGGESTED_FIXES: dict[ProviderErrorKind, str] = {
+    ProviderErrorKind.MAX_TOKENS_TOO_LARGE: (
+        "Reduce settings.max_tokens (or the model's entry in settings.model_limits) "
+        "to the model's maximum output"
+    ),
+    ProviderErrorKind.TOOLS_UNSUPPORTED: (
+        "This model lacks tool support; switch to a tool-capable model with /model"
+    ),
+    ProviderErrorKind.IMAGES_UNSUPPORTED: (
+        "This model cannot read images; remove the image or switch models with /model"
+    ),
+    ProviderErrorKind.PARAMETER_UNSUPPORTED: (
+        "This model rejects a request parameter; unset settings.thinking_budget "
+        "or settings.max_tokens, or switch models with /model"
+    ),
+    ProviderErrorKind.MODEL_NOT_PULLED: (
+        "Ollama does not have this model yet; run `ollama pull <model>` and retry"
+    ),
+}
+
+_FLAGS = re.IGNORECASE | re.DOTALL
+
+# Checked for every provider, after that provider's own patterns.
+COMMON_ERROR_PATTERNS: tuple[tuple[ProviderErrorKind, re.Pattern[str]], ...] = (
+    (
+        ProviderErrorKind.TOOLS_UNSUPPORTED,
+        re.compile(
+            r"(?:does not|doesn't) support (?:tools|tool use|tool calling|function calling)"
+            r"|tool(?:s| use| calling)? (?:is |are )?not supported",
+            _FLAGS,
+        ),
+    ),
+    (
+        ProviderErrorKind.MAX_TOKENS_TOO_LARGE,
+        re.compile(
+            r"max_(?:completion_)?tokens\b.{0,80}\b(?:too large|exceeds?|greater than|maximum)",
+            _FLAGS,
+        ),
+    ),
+    (
+        ProviderErrorKind.IMAGES_UNSUPPORTED,
+        re.compile(
+            r"(?:does not|doesn't) support (?:image|vision)|image input is not supported",
+            _FLAGS,
+        ),
+    ),
+    (
+        ProviderErrorKind.PARAMETER_UNSUPPORTED,
+        re.compile(r"unsupported parameter|(?:parameter|argument) is not supported", _FLAGS),
+    ),
+)
+
+# Provider-specific wording, keyed by the provider id of ``provider:model``.
+PROVIDER_ERROR_PATTERNS: dict[str, tuple[tuple[ProviderErrorKind, re.Pattern[str]], ...]] = {
+    "anthropic": (
+        (
+            ProviderErrorKind.MAX_TOKENS_TOO_LARGE,
+            re.compile(r"max_tokens: \d+ > \d+", _FLAGS),
+        ),
+    ),
+    "ollama": (
+        (
+            ProviderErrorKind.MODEL_NOT_PULLED,
+            re.compile(r"not found, try pulling it first|is not pulled", _FLAGS),
+        ),
+    ),
+    "openai": (
+        (
+            ProviderErrorKind.PARAMETER_UNSUPPORTED,
+            re.compile(r"'max_tokens' is not supported with this model", _FLAGS),
+        ),
+    ),
+    "openrouter": (
+        (
+            ProviderErrorKind.TOOLS_UNSUPPORTED,
+            re.compile(r"no endpoints found that support tool use", _FLAGS),
+        ),
+        (
+            ProviderErrorKind.IMAGES_UNSUPPORTED,
+            re.compile(r"no endpoints found that support image input", _FLAGS),
+        ),
+    ),
+}
+
+
+@dataclass(frozen=True, slots=True)
+class ProviderErrorHint:
+    kind: ProviderErrorKind
+    provider: str
+    suggested_fix: str
+    raw_message: str
+
+
+def provider_from_model(model: str) -> str:
+    """Return the provider id of ``provider:model`` (empty when there is none)."""
+    provider, separator, _model_id = model.partition(":")
+    return provider if separator else ""
+
+
+def match_provider_error(error_text: str, provider: str) -> ProviderErrorHint | None:
+    if not error_text:
+        return None
+    patterns = (*PROVIDER_ERROR_PATTERNS.get(provider, ()), *COMMON_ERROR_PATTERNS)
+    for kind, pattern in patterns:
+        if pattern.search(error_text):
+            return ProviderErrorHint(
+                kind=kind,
+                provider=provider,
+                suggested_fix=HINT_SUGGESTED_FIXES[kind],
+                raw_message=error_text,
+            )
+    return None
+
+
+def build_agent_error(error_text: str, provider: str) -> AgentError:
+    hint = match_provider_error(error_text, provider)
+    if hint is None:
+        return AgentError(error_text)
+    return AgentError(error_text, suggested_fix=hint.suggested_fix, kind=hint.kind)
@@ -0,0 +1,133 @@
+"""Fail over to secondary providers when the primary cannot serve a turn.
+
+``settings.fallback_providers`` is an ordered chain of ``{"model", "base_url"}``
+entries, each naming the model to use on that provider. When opening the
+primary stream fails with a retryable error -- 5xx, 429 or a network error,
+after the per-provider retries are exhausted -- the same request is sent to the
+next entry. Other errors (400, 401, ...) are raised without failover.
+
+``settings.fallback_model`` is a separate, opt-in safety net for a model name
+the provider does not recognize (a typo or a retired model): when opening the
+stream fails with a model-not-found error, the request is retried once with
+that model on the same chain. Both substitutions are logged as warnings.
+When given a ``backoff`` policy, each failover waits one of its delays first,
+so a fleet of agents moving to the same secondary provider is spread out.
+
+The served model is recorded on the assistant message itself: tinyagent stamps
+``provider`` and ``model`` from the model that produced the response, so the
+session file shows which provider and model answered each turn.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import re
+from collections.abc import Callable, Sequence
+
+import httpx
+from tinyagent.agent_types import (
+    Context,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+)
+
+from tunacode.core.logging.manager import get_logger
+
+from .backoff import BackoffPolicy
+
+STREAM_RETRYABLE_STATUS_CODES = frozenset({408, 409, 425, 429})
+MODEL_NOT_FOUND_STATUS_CODES = frozenset({400, 404, 422})
+MODEL_NOT_FOUND_PATTERN = re.compile(
+    r"model[_ ]not[_ ]found|no such model|unknown model|invalid model"
+    r"|model\b.{0,80}\b(?:does not exist|not found|is not available|not supported)",
+    re.IGNORECASE | re.DOTALL,
+)
+PROVIDER_FAILOVER_LABEL = "Provider failover"
+MODEL_FALLBACK_LABEL = "Model fallback"
+
+
+def is_retryable_stream_error(exc: Exception) -> bool:
+    """True for errors worth retrying or failing over: 408/409/425/429, 5xx, network."""
+    if isinstance(exc, httpx.HTTPStatusError):
+        response = exc.response
+        if response is None:
+            return False
+        return response.status_code in STREAM_RETRYABLE_STATUS_CODES or response.status_code >= 500
+    return isinstance(exc, httpx.RequestError | TimeoutError)
+
+
+def _response_text(response: httpx.Response) -> str:
+    try:
+        return response.text
+    except httpx.ResponseNotRead:
+        return ""
+
+
+def is_model_not_found_error(exc: Exception) -> bool:
+    """True for a 400/404/422 whose error says the requested model does not exist."""
+    if not isinstance(exc, httpx.HTTPStatusError):
+        return False
+    response = exc.response
+    if response is None or response.status_code not in MODEL_NOT_FOUND_STATUS_CODES:
+        return False
+    return MODEL_NOT_FOUND_PATTERN.search(f"{exc} {_response_text(response)}") is not None
+
+
+def _model_label(model: Model) -> str:
+    return f"{model.provider}:{model.id}"
+
+
+def with_provider_fallback(
+    stream_fn: StreamFn,
+    fallback_models: Sequence[Model],
+    *,
+    resolve_api_key: Callable[[str], str | None],
+    should_fail_over: Callable[[Exception], bool],
+    label: str = PROVIDER_FAILOVER_LABEL,
+    backoff: BackoffPolicy | None = None,
+) -> StreamFn:
+    """Wrap ``stream_fn`` so matching failures move on to ``fallback_models`` in order.
+
+    A fallback naming the model that just failed is skipped.
+    """
+    if not fallback_models:
+        return stream_fn
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        try:
+            return await stream_fn(model, context, options)
+        except Exception as exc:  # noqa: BLE001
+            if not should_fail_over(exc):
+                raise
+            failed_model, last_error = model, exc
+
+        logger = get_logger()
+        delays = backoff.schedule("provider_failover") if backoff is not None else None
+        for fallback in fallback_models:
+            if _model_label(fallback) == _model_label(failed_model):
+                continue
+            logger.warning(
+                f"{label}: "
+                f"{_model_label(failed_model)} -> {_model_label(fallback)} "
+                f"after {type(last_error).__name__}"
+            )
+            if delays is not None:
+                await asyncio.sleep(delays.next_delay())
+            fallback_options = options.model_copy(
+                update={"api_key": resolve_api_key(fallback.provider)}
+            )
+            try:
+                return await stream_fn(fallback, context, fallback_options)
+            except Exception as exc:  # noqa: BLE001
+                if not should_fail_over(exc):
+                    raise
+                failed_model, last_error = fallback, exc
+        raise last_error
+
+    return _stream
@@ -0,0 +1,102 @@
+"""Cap the reasoning tokens a reasoning-capable model may spend per call.
+
+``settings.thinking_budget`` is a token count. Anthropic-family models take it
+verbatim as ``thinking.budget_tokens``; other providers only expose a coarse
+``reasoning_effort`` knob, so the budget maps to the largest effort tier whose
+nominal budget fits under it. Models the registry does not mark as reasoning
+models are left alone.
+
+Actual usage is read back from the assistant message: the provider's
+``reasoning`` usage count when it reports one, otherwise an estimate from the
+thinking content the model streamed.
+"""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AssistantMessage,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+)
+
+from tunacode.configuration.models import model_supports_reasoning
+from tunacode.utils.messaging import estimate_tokens, to_canonical
+
+from .prompt_caching import ANTHROPIC_PROVIDER_ID, CLAUDE_MODEL_MARKER
+
+THINKING_OPTION_KEY = "thinking"
+REASONING_EFFORT_OPTION_KEY = "reasoning_effort"
+USAGE_REASONING_KEY = "reasoning"
+
+# Nominal reasoning tokens per effort tier, smallest first.
+EFFORT_TIER_BUDGETS: tuple[tuple[str, int], ...] = (
+    ("minimal", 1_024),
+    ("low", 4_096),
+    ("medium", 16_384),
+    ("high", 32_768),
+)
+
+
+def effort_for_budget(budget_tokens: int) -> str:
+    """Return the largest effort tier whose nominal budget does not exceed ``budget_tokens``."""
+    effort = EFFORT_TIER_BUDGETS[0][0]
+    for tier, tier_budget in EFFORT_TIER_BUDGETS:
+        if tier_budget > budget_tokens:
+            break
+        effort = tier
+    return effort
+
+
+def _uses_explicit_budget(model: Model) -> bool:
+    return model.provider == ANTHROPIC_PROVIDER_ID or CLAUDE_MODEL_MARKER in model.id.lower()
+
+
+def apply_reasoning_budget(
+    model: Model,
+    options: SimpleStreamOptions,
+    budget_tokens: int,
+) -> SimpleStreamOptions:
+    """Return ``options`` carrying the budget in the form ``model``'s provider accepts."""
+    if not model_supports_reasoning(f"{model.provider}:{model.id}"):
+        return options
+    if _uses_explicit_budget(model):
+        thinking = {"type": "enabled", "budget_tokens": budget_tokens}
+        return options.model_copy(update={THINKING_OPTION_KEY: thinking})
+    effort = effort_for_budget(budget_tokens)
+    return options.model_copy(update={REASONING_EFFORT_OPTION_KEY: effort})
+
+
+def with_reasoning_budget(stream_fn: StreamFn, budget_tokens: int | None) -> StreamFn:
+    """Wrap ``stream_fn`` so every call is sent with the configured reasoning budget."""
+    if budget_tokens is None:
+        return stream_fn
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        budgeted = apply_reasoning_budget(model, options, budget_tokens)
+        return await stream_fn(model, context, budgeted)
+
+    return _stream
+
+
+def reasoning_tokens_used(message: AssistantMessage) -> int:
+    """Return the reasoning tokens behind ``message``, reported or estimated."""
+    usage = message.usage
+    if isinstance(usage, dict):
+        reported = usage.get(USAGE_REASONING_KEY)
+        if isinstance(reported, int) and not isinstance(reported, bool):
+            return reported
+
+    payload = to_canonical(message)
+    thinking_parts = [
+        item.get("thinking", "")
+        for item in payload.get("content") or []
+        if isinstance(item, dict) and item.get("type") == "thinking"
+    ]
+    return estimate_tokens("".join(part for part in thinking_parts if isinstance(part, str)))
@@ -0,0 +1,72 @@
+"""Pre-flight token estimate of the next request.
+
+``estimate_next_request()`` assembles what the next turn would send for
+``message`` -- the agent's system prompt and tools, the history as the request
+context sees it (windowed in ``settings.history`` window mode, with any
+compaction summary in front) and the message wrapped in the configured
+affixes -- and counts it with ``utils/messaging/request_tokens.py`` for the
+wire API the model is reached through. Nothing is sent.
+"""
+
+from __future__ import annotations
+
+from tunacode.configuration.models import get_provider_alchemy_api
+from tunacode.types import ModelName, UserSettings
+from tunacode.utils.messaging.request_tokens import (
+    DEFAULT_WIRE_API,
+    RequestTokenEstimate,
+    TokenCounter,
+    estimate_request_tokens,
+)
+from tunacode.utils.messaging.token_counter import estimate_tokens
+
+from tunacode.core.compaction.controller import get_or_create_compaction_controller
+from tunacode.core.compaction.history_window import HistoryWindow
+from tunacode.core.types.state import StateManagerProtocol
+
+from ..helpers import apply_user_message_affixes
+from .agent_config import get_or_create_agent
+from .provider_error_hints import provider_from_model
+
+OLLAMA_NATIVE_WIRE_API = "ollama-chat"
+
+
+def wire_api_for(model: ModelName, settings: UserSettings) -> str:
+    """Name of the wire API the model's requests use, for its formatting overhead."""
+    provider = provider_from_model(model)
+    if provider == "ollama" and settings["ollama"]["native_api"]:
+        return OLLAMA_NATIVE_WIRE_API
+    return get_provider_alchemy_api(provider) or DEFAULT_WIRE_API
+
+
+def estimate_next_request(
+    model: ModelName,
+    state_manager: StateManagerProtocol,
+    message: str = "",
+    *,
+    count_tokens: TokenCounter = estimate_tokens,
+) -> RequestTokenEstimate:
+    """Estimate the input tokens of sending ``message`` as the next turn."""
+    agent = get_or_create_agent(model, state_manager)
+    session = state_manager.session
+    settings = session.user_config["settings"]
+    history = list(session.conversation.messages)
+    window = HistoryWindow.from_settings(settings["history"])
+    if window.enabled:
+        history = window.apply(history)
+    history = get_or_create_compaction_controller(state_manager).inject_summary_message(history)
+    next_message = ""
+    if message:
+        next_message = apply_user_message_affixes(
+            message,
+            prefix=settings["user_message_prefix"],
+            suffix=settings["user_message_suffix"],
+        )
+    return estimate_request_tokens(
+        system_prompt=session.system_prompt,
+        messages=history,
+        tools=agent.state.tools,
+        next_message=next_message,
+        wire_api=wire_api_for(model, settings),
+        count_tokens=count_tokens,
+    )
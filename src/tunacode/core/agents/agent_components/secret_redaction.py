@@ -0,0 +1,162 @@
+"""Mask secrets in tool output before it enters the conversation history.
+
+Output from ``env``, ``cat .env`` or a config dump can carry credentials that
+would otherwise be sent to the provider and saved in the session file. Every
+text block of a tool result is passed through ``SecretRedactor`` first:
+
+- the command audit patterns (``KEY=...`` assignments, ``--password``
+  flags, ``Authorization`` headers, URL credentials, well-known token
+  prefixes) plus a few output-only ones (private key blocks, ``key: value``
+  and JSON-style secret fields);
+- ``settings.secret_redaction.patterns``, extra regexes that mask the whole
+  match, or only the named group ``secret`` when the pattern has one;
+- an entropy pass: a token of at least ``entropy_min_length`` characters
+  mixing letters and digits whose Shannon entropy is at least
+  ``entropy_threshold`` bits per character (0 turns the pass off). Hex
+  digests top out at 4 bits per character, so commit hashes are kept.
+
+The fuller output a tool keeps in ``details`` for the user (``full_output``)
+is masked the same way. The number of masked values is added to the result's
+``details`` under ``redactions`` and logged. The original values are not kept anywhere, so a
+redaction cannot be undone.
+"""
+
+from __future__ import annotations
+
+import math
+import re
+from collections import Counter
+from collections.abc import Sequence
+from dataclasses import dataclass
+
+from tinyagent.agent_types import AgentToolResult, TextContent
+
+from tunacode.constants import FULL_OUTPUT_DETAIL_KEY
+from tunacode.types import SecretRedactionSettings
+
+from tunacode.core.session.audit import REDACTED, SECRET_PATTERNS
+
+REDACTIONS_DETAIL_KEY = "redactions"
+SECRET_GROUP = "secret"
+
+OUTPUT_SECRET_PATTERNS: tuple[tuple[re.Pattern[str], str], ...] = (
+    (
+        re.compile(
+            r"-----BEGIN [A-Z ]*PRIVATE KEY-----.*?-----END [A-Z ]*PRIVATE KEY-----",
+            re.DOTALL,
+        ),
+        REDACTED,
+    ),
+    (
+        re.compile(
+            r"(?i)([\"']?[a-z0-9_\-]*(?:api[_-]?key|token|secret|password|passwd)"
+            r"[a-z0-9_\-]*[\"']?\s*[:=]\s*[\"']?)(?!\[REDACTED\])[^\s\"',]{4,}"
+        ),
+        rf"\1{REDACTED}",
+    ),
+)
+
+_ENTROPY_TOKEN = re.compile(r"[A-Za-z0-9+_\-=]+")
+_HAS_LETTER = re.compile(r"[A-Za-z]")
+_HAS_DIGIT = re.compile(r"[0-9]")
+
+
+def shannon_entropy(text: str) -> float:
+    """Bits of entropy per character of ``text``."""
+    if not text:
+        return 0.0
+    length = len(text)
+    return -sum(count / length * math.log2(count / length) for count in Counter(text).values())
+
+
+@dataclass(frozen=True, slots=True)
+class RedactionResult:
+    text: str
+    count: int
+
+
+class SecretRedactor:
+    """Pattern- and entropy-based masking of secrets in text."""
+
+    def __init__(
+        self,
+        *,
+        extra_patterns: Sequence[str] = (),
+        entropy_threshold: float = 0.0,
+        entropy_min_length: int = 0,
+    ) -> None:
+        self._patterns = (*SECRET_PATTERNS, *OUTPUT_SECRET_PATTERNS)
+        self._extra_patterns = tuple(re.compile(pattern) for pattern in extra_patterns)
+        self._entropy_threshold = entropy_threshold
+        self._entropy_min_length = entropy_min_length
+
+    @classmethod
+    def from_settings(cls, settings: SecretRedactionSettings) -> SecretRedactor:
+        return cls(
+            extra_patterns=settings["patterns"],
+            entropy_threshold=settings["entropy_threshold"],
+            entropy_min_length=settings["entropy_min_length"],
+        )
+
+    def redact(self, text: str) -> RedactionResult:
+        count = 0
+        for pattern, replacement in self._patterns:
+            text, replaced = pattern.subn(replacement, text)
+            count += replaced
+        for pattern in self._extra_patterns:
+            text, replaced = pattern.subn(_mask_match, text)
+            count += replaced
+        if self._entropy_threshold > 0:
+            text, replaced = self._redact_high_entropy(text)
+            count += replaced
+        return RedactionResult(text=text, count=count)
+
+    def _redact_high_entropy(self, text: str) -> tuple[str, int]:
+        parts: list[str] = []
+        count = 0
+        position = 0
+        for match in _ENTROPY_TOKEN.finditer(text):
+            if not self._is_high_entropy(match.group(0)):
+                continue
+            parts.extend((text[position : match.start()], REDACTED))
+            position = match.end()
+            count += 1
+        parts.append(text[position:])
+        return "".join(parts), count
+
+    def _is_high_entropy(self, token: str) -> bool:
+        if len(token) < self._entropy_min_length:
+            return False
+        if not (_HAS_LETTER.search(token) and _HAS_DIGIT.search(token)):
+            return False
+        return shannon_entropy(token) >= self._entropy_threshold
+
+    def redact_result(self, result: AgentToolResult) -> tuple[AgentToolResult, int]:
+        """Return ``result`` with its text blocks redacted, and the number masked."""
+        total = 0
+        content: list[object] = []
+        for item in result.content:
+            if isinstance(item, TextContent) and isinstance(item.text, str):
+                redacted = self.redact(item.text)
+                total += redacted.count
+                item = item.model_copy(update={"text": redacted.text})
+            content.append(item)
+        details = dict(result.details or {})
+        full_output = details.get(FULL_OUTPUT_DETAIL_KEY)
+        if isinstance(full_output, str):
+            redacted = self.redact(full_output)
+            total += redacted.count
+            details[FULL_OUTPUT_DETAIL_KEY] = redacted.text
+        if total == 0:
+            return result, 0
+        details[REDACTIONS_DETAIL_KEY] = total
+        return result.model_copy(update={"content": content, "details": details}), total
+
+
+def _mask_match(match: re.Match[str]) -> str:
+    if SECRET_GROUP not in match.re.groupindex or match.group(SECRET_GROUP) is None:
+        return REDACTED
+    start, end = match.span(SECRET_GROUP)
+    offset = match.start()
+    whole = match.group(0)
+    return whole[: start - offset] + REDACTED + whole[end - offset :]
@@ -0,0 +1,150 @@
+"""Debug-only wrappers around the provider stream.
+
+``_TracedStreamResponse`` logs provider timing (first raw event, long gaps
+between events, result latency) while ``/debug`` is on.
+``with_raw_response_capture()`` records each call's events and final message
+in the session's ``RawResponseLog`` when ``settings.retain_raw_responses`` is
+set; it sees only what the provider returns, never the request options.
+"""
+
+from __future__ import annotations
+
+import time
+from collections.abc import Callable
+from typing import Any, Protocol
+
+from tinyagent.agent_types import (
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    StreamFn,
+    StreamResponse,
+)
+
+from tunacode.core.debug.raw_responses import RawResponseLog, event_payload
+
+STREAM_RAW_EVENT_GAP_WARN_MS = 250.0
+
+
+class _LifecycleTraceLogger(Protocol):
+    debug_mode: bool
+
+    def lifecycle(self, message: str) -> None: ...
+    def warning(self, message: str, **kwargs: object) -> None: ...
+
+
+class _TracedStreamResponse:
+    """Wrap provider StreamResponse with timing logs for /debug sessions."""
+
+    def __init__(
+        self,
+        response: StreamResponse,
+        *,
+        logger: _LifecycleTraceLogger,
+        opened_at: float,
+        response_ready_at: float,
+    ) -> None:
+        self._response = response
+        self._logger = logger
+        self._opened_at = opened_at
+        self._response_ready_at = response_ready_at
+        self._event_count = 0
+        self._last_event_at = response_ready_at
+
+    def __aiter__(self) -> _TracedStreamResponse:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        event = await self._response.__anext__()
+        now = time.perf_counter()
+        self._event_count += 1
+        event_type = event.type or "unknown"
+
+        if self._event_count == 1:
+            self._logger.lifecycle(
+                "Stream: "
+                f"provider_first_raw type={event_type} "
+                f"since_open={(now - self._opened_at) * 1000.0:.1f}ms "
+                f"since_response={(now - self._response_ready_at) * 1000.0:.1f}ms"
+            )
+        else:
+            gap_ms = (now - self._last_event_at) * 1000.0
+            if gap_ms >= STREAM_RAW_EVENT_GAP_WARN_MS:
+                self._logger.lifecycle(
+                    "Stream: "
+                    f"provider_raw_gap type={event_type} "
+                    f"gap={gap_ms:.1f}ms "
+                    f"count={self._event_count}"
+                )
+
+        self._last_event_at = now
+        return event
+
+    async def result(self) -> AssistantMessage:
+        started_at = time.perf_counter()
+        result = await self._response.result()
+        duration_ms = (time.perf_counter() - started_at) * 1000.0
+        self._logger.lifecycle(f"Stream: provider_result dur={duration_ms:.1f}ms")
+        return result
+
+
+class _CapturingStreamResponse:
+    """Pass events through and log them with the final message on ``result()``."""
+
+    def __init__(
+        self,
+        response: StreamResponse,
+        *,
+        log: RawResponseLog,
+        request_id: str,
+        model: str,
+    ) -> None:
+        self._response = response
+        self._log = log
+        self._request_id = request_id
+        self._model = model
+        self._events: list[dict[str, Any]] = []
+
+    def __aiter__(self) -> _CapturingStreamResponse:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        event = await self._response.__anext__()
+        self._events.append(event_payload(event))
+        return event
+
+    async def result(self) -> AssistantMessage:
+        answer = await self._response.result()
+        self._log.record(
+            request_id=self._request_id,
+            model=self._model,
+            events=self._events,
+            response=answer.model_dump(mode="json", exclude_none=True),
+        )
+        return answer
+
+
+def with_raw_response_capture(
+    stream_fn: StreamFn,
+    log_fn: Callable[[], RawResponseLog],
+    *,
+    request_id_fn: Callable[[], str],
+) -> StreamFn:
+    """Wrap ``stream_fn`` to record every provider response in ``log_fn()``."""
+
+    async def _stream(
+        model: Model,
+        context: Context,
+        options: SimpleStreamOptions,
+    ) -> StreamResponse:
+        response = await stream_fn(model, context, options)
+        return _CapturingStreamResponse(  # type: ignore[return-value]
+            response,
+            log=log_fn(),
+            request_id=request_id_fn(),
+            model=f"{model.provider}:{model.id}",
+        )
+
+    return _stream
@@ -0,0 +1,157 @@
+"""Fork one agent event stream into reasoning, answer and tool-call streams.
+
+``fork_response_stream(agent.stream(prompt))`` returns ``ResponseForks``:
+``reasoning`` and ``answer`` iterate the thinking and text deltas as strings,
+and ``tool_calls`` iterates the ``ToolExecutionStartEvent`` of each tool call.
+All three are fed by one pump task reading the single underlying stream, so
+they can be piped to different UI surfaces without a second request.
+
+Each fork buffers on its own: a consumer that stops reading, or ``close()``s
+its fork, never stalls the others, and a closed fork simply stops buffering.
+When every fork is closed the pump stops pulling from the source. When the
+source ends, every open fork finishes after yielding what it buffered; when
+the source raises, every open fork re-raises that error after its buffer.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import contextlib
+from collections.abc import AsyncGenerator, AsyncIterator
+from typing import Generic, TypeVar
+
+from tinyagent.agent_types import AgentEvent, MessageUpdateEvent, ToolExecutionStartEvent
+
+T = TypeVar("T")
+
+REASONING_DELTA_TYPE = "thinking_delta"
+ANSWER_DELTA_TYPE = "text_delta"
+
+_END = object()
+
+
+class StreamFork(Generic[T]):
+    """One branch of a forked stream; an async iterator that ends exactly once."""
+
+    def __init__(self, name: str) -> None:
+        self.name = name
+        self._queue: asyncio.Queue[object] = asyncio.Queue()
+        self._error: BaseException | None = None
+        self._closed = False
+        self._finished = False
+
+    @property
+    def closed(self) -> bool:
+        return self._closed
+
+    @property
+    def finished(self) -> bool:
+        """True once iteration has ended, normally or with the source's error."""
+        return self._finished
+
+    def close(self) -> None:
+        """Stop consuming this fork; buffered items are dropped and iteration ends."""
+        if self._closed:
+            return
+        self._closed = True
+        while not self._queue.empty():
+            self._queue.get_nowait()
+        # Wakes a consumer already waiting in ``__anext__``.
+        self._queue.put_nowait(_END)
+
+    def _put(self, item: T) -> None:
+        if not self._closed:
+            self._queue.put_nowait(item)
+
+    def _finish(self, error: BaseException | None) -> None:
+        if self._closed:
+            return
+        self._error = error
+        self._queue.put_nowait(_END)
+
+    def __aiter__(self) -> StreamFork[T]:
+        return self
+
+    async def __anext__(self) -> T:
+        if self._closed or self._finished:
+            raise StopAsyncIteration
+        item = await self._queue.get()
+        if item is _END:
+            self._finished = True
+            if self._error is not None and not self._closed:
+                raise self._error
+            raise StopAsyncIteration
+        return item  # type: ignore[return-value]
+
+
+class ResponseForks:
+    """Reasoning, answer and tool-call forks over a single agent event stream."""
+
+    def __init__(self, events: AsyncIterator[AgentEvent]) -> None:
+        self.reasoning: StreamFork[str] = StreamFork("reasoning")
+        self.answer: StreamFork[str] = StreamFork("answer")
+        self.tool_calls: StreamFork[ToolExecutionStartEvent] = StreamFork("tool_calls")
+        self._pump_task = asyncio.create_task(self._pump(events), name="response-stream-forks")
+
+    @property
+    def forks(self) -> tuple[StreamFork[str], StreamFork[str], StreamFork[ToolExecutionStartEvent]]:
+        return (self.reasoning, self.answer, self.tool_calls)
+
+    def _all_closed(self) -> bool:
+        return all(fork.closed for fork in self.forks)
+
+    def _route(self, event: AgentEvent) -> None:
+        if isinstance(event, ToolExecutionStartEvent):
+            self.tool_calls._put(event)
+            return
+        if not isinstance(event, MessageUpdateEvent):
+            return
+        assistant_event = event.assistant_message_event
+        if assistant_event is None or not isinstance(assistant_event.delta, str):
+            return
+        if not assistant_event.delta:
+            return
+        if assistant_event.type == REASONING_DELTA_TYPE:
+            self.reasoning._put(assistant_event.delta)
+        elif assistant_event.type == ANSWER_DELTA_TYPE:
+            self.answer._put(assistant_event.delta)
+
+    async def _pump(self, events: AsyncIterator[AgentEvent]) -> None:
+        error: BaseException | None = None
+        try:
+            async for event in events:
+                self._route(event)
+                if self._all_closed():
+                    break
+        except asyncio.CancelledError:
+            raise
+        except Exception as exc:
+            error = exc
+        finally:
+            for fork in self.forks:
+                fork._finish(error)
+            if isinstance(events, AsyncGenerator):
+                with contextlib.suppress(Exception):
+                    await events.aclose()
+
+    async def wait_closed(self) -> None:
+        """Wait until the source stream has been fully read (or failed)."""
+        await asyncio.wait({self._pump_task})
+
+    async def aclose(self) -> None:
+        """Close every fork and stop reading the source."""
+        for fork in self.forks:
+            fork.close()
+        self._pump_task.cancel()
+        await self.wait_closed()
+
+    async def __aenter__(self) -> ResponseForks:
+        return self
+
+    async def __aexit__(self, *_exc_info: object) -> None:
+        await self.aclose()
+
+
+def fork_response_stream(events: AsyncIterator[AgentEvent]) -> ResponseForks:
+    """Fork ``events``; must be called with a running event loop."""
+    return ResponseForks(events)
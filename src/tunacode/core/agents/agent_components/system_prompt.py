@@ -0,0 +1,119 @@
+"""Layered system prompt assembly for the agent.
+
+``system_prompt_providers()`` lists the prompt sections in order: the
+configured prefix, the base prompt (or its override), the project guide,
+selected and available skills, the task-decomposition section, registered
+context providers, and the configured suffix. ``build_system_prompt()``
+renders them within ``settings.system_prompt_max_tokens`` and logs what the
+budget dropped or truncated.
+
+The base prompt and project guide loaders are passed in by ``agent_config``,
+which owns reading them from disk.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Callable, Sequence
+from pathlib import Path
+
+from tunacode.core.logging.manager import get_logger
+
+from .prompt_assembly import (
+    ContextProvider,
+    FunctionContextProvider,
+    PromptContext,
+    assemble_prompt,
+    registered_context_providers,
+)
+from .system_prompt_layers import (
+    PREFIX_LABEL,
+    SUFFIX_LABEL,
+    SystemPromptLayers,
+    tools_missing_from_override,
+)
+from .task_tool import task_decomposition_providers
+
+BasePromptLoader = Callable[[Path, str | None], str]
+ProjectGuideLoader = Callable[[], str]
+
+
+def system_prompt_providers(
+    layers: SystemPromptLayers,
+    *,
+    task_decomposition: bool,
+    load_base_prompt: BasePromptLoader,
+    load_project_guide: ProjectGuideLoader,
+) -> list[ContextProvider]:
+    """All system prompt sections for one agent, in prompt order."""
+    return [
+        *_builtin_providers(layers, load_base_prompt, load_project_guide),
+        *task_decomposition_providers(task_decomposition),
+        *registered_context_providers(),
+        FunctionContextProvider(
+            label=SUFFIX_LABEL,
+            render_fn=lambda _context: f"\n\n{layers.suffix}\n" if layers.suffix else "",
+            priority=100,
+        ),
+    ]
+
+
+def build_system_prompt(
+    providers: Sequence[ContextProvider],
+    context: PromptContext,
+    *,
+    budget_tokens: int | None,
+) -> str:
+    """Render ``providers`` within the token budget, warning about what was cut."""
+    prompt = assemble_prompt(providers, context, budget_tokens=budget_tokens)
+    truncated = [section.label for section in prompt.sections if section.truncated]
+    if prompt.dropped or truncated:
+        get_logger().warning(
+            "System prompt over budget: "
+            f"dropped={','.join(prompt.dropped) or '-'} truncated={','.join(truncated) or '-'}"
+        )
+    return prompt.text
+
+
+def warn_about_override_tool_gaps(layers: SystemPromptLayers, tool_names: Sequence[str]) -> None:
+    """Log the tools a base-prompt override never mentions."""
+    missing_tools = tools_missing_from_override(layers, tool_names)
+    if missing_tools:
+        get_logger().warning(
+            "settings.system_prompt.override replaces the built-in tool-usage instructions "
+            f"and never mentions: {', '.join(missing_tools)}"
+        )
+
+
+def _builtin_providers(
+    layers: SystemPromptLayers,
+    load_base_prompt: BasePromptLoader,
+    load_project_guide: ProjectGuideLoader,
+) -> list[ContextProvider]:
+    return [
+        FunctionContextProvider(
+            label=PREFIX_LABEL,
+            render_fn=lambda _context: f"{layers.prefix}\n\n" if layers.prefix else "",
+            priority=100,
+        ),
+        FunctionContextProvider(
+            label="system_prompt",
+            render_fn=lambda context: (
+                layers.override or load_base_prompt(context.base_path, context.model)
+            ),
+            priority=100,
+        ),
+        FunctionContextProvider(
+            label="project_guide",
+            render_fn=lambda _context: load_project_guide(),
+        ),
+        FunctionContextProvider(
+            label="selected_skills",
+            render_fn=lambda context: context.skills_state.selected_block,
+            priority=80,
+        ),
+        FunctionContextProvider(
+            label="available_skills",
+            render_fn=lambda context: context.skills_state.available_block,
+            priority=20,
+        ),
+    ]
@@ -0,0 +1,51 @@
+"""Config layers over the assembled system prompt.
+
+``settings.system_prompt`` turns tunacode into a specialized agent without
+editing ``prompts/system_prompt.md``::
+
+    "system_prompt": {"override": "", "prefix": "", "suffix": ""}
+
+- ``override`` replaces the built-in base prompt. The project ``AGENTS.md``
+  guide, skills and registered context providers still follow it.
+- ``prefix`` becomes the first section and ``suffix`` the last, around either
+  prompt.
+
+All three default to empty, which keeps the built-in prompt unchanged. The
+built-in prompt carries the tool-usage rules (read a file before
+``hashline_edit``, ``discover`` before ``read_file``); an override that never
+names some of the agent's tools logs a warning listing them. The effective
+prompt of the last agent built is kept on ``session.system_prompt``, saved
+with the session and shown by ``/debug prompt``.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+from dataclasses import dataclass
+
+from tunacode.types import SystemPromptSettings
+
+PREFIX_LABEL = "system_prompt_prefix"
+SUFFIX_LABEL = "system_prompt_suffix"
+
+
+@dataclass(frozen=True, slots=True)
+class SystemPromptLayers:
+    override: str = ""
+    prefix: str = ""
+    suffix: str = ""
+
+    @classmethod
+    def from_settings(cls, settings: SystemPromptSettings) -> SystemPromptLayers:
+        return cls(
+            override=settings["override"].strip(),
+            prefix=settings["prefix"].strip(),
+            suffix=settings["suffix"].strip(),
+        )
+
+
+def tools_missing_from_override(layers: SystemPromptLayers, tool_names: Sequence[str]) -> list[str]:
+    """Tools an override never mentions; empty when there is no override."""
+    if not layers.override:
+        return []
+    return [name for name in tool_names if name not in layers.override]
@@ -0,0 +1,162 @@
+"""The ``tasks`` tool: the agent's handle on the session task store.
+
+The tool is built per agent around a callable that returns the current
+session's ``TaskStore`` (``core/session/tasks.py``), so it always edits the
+live list, including after ``/resume`` swaps the session. Every call answers
+with the full list, which keeps the model's picture of the plan current.
+Invalid transitions come back as ``ToolRetryError`` so the model can correct
+itself.
+
+With ``settings.task_decomposition`` on, ``task_decomposition_providers()``
+adds ``TASK_DECOMPOSITION_PROMPT`` to the system prompt: before acting on a
+multi-step request the model records its plan with ``tasks`` and then works
+through it. The model judges what counts as trivial and skips the step for
+those. The plan is the task list itself, so it is saved with the session and
+shown by ``/tasks``.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import json
+from collections.abc import Callable
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+    TextContent,
+)
+
+from tunacode.constants import ToolName
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.core.session.tasks import TASK_STATUSES, TaskStore, TaskTransitionError
+
+from .prompt_assembly import ContextProvider, FunctionContextProvider
+
+TASK_ACTIONS = ("create", "update", "list")
+TASK_DECOMPOSITION_LABEL = "task_decomposition"
+TASK_DECOMPOSITION_PRIORITY = 60
+
+TASK_DECOMPOSITION_PROMPT = """\
+## Task decomposition
+
+Before acting on a request that needs several distinct steps (touches more than
+one file, or mixes investigation, changes and verification), first break it
+into concrete tasks with `tasks` action=create, then work through them in
+order: mark each in_progress when you start it and done when it is finished.
+Call `tasks` action=list first if the session may already hold a plan.
+
+Skip this for trivial requests: a question, a single lookup, or a one-line
+change. Do not create a plan with only one task.
+"""
+
+_TASKS_DESCRIPTION = """Track the steps of a multi-step request in the session task list.
+
+Actions:
+- create: add tasks from `titles` (each starts as pending).
+- update: change `status` and/or `title` of the task `id`. Statuses move
+  pending -> in_progress -> done; in_progress may go back to pending.
+- list: show the tasks, optionally filtered by `status`.
+
+The list survives across turns and session resume. Mark a task in_progress
+before working on it and done when it is finished.
+"""
+
+_TASKS_PARAMETERS: JsonObject = {
+    "type": "object",
+    "additionalProperties": False,
+    "properties": {
+        "action": {"type": "string", "enum": list(TASK_ACTIONS)},
+        "titles": {
+            "type": "array",
+            "items": {"type": "string"},
+            "description": "Task titles to create (action=create).",
+        },
+        "id": {"type": "string", "description": "Task id to update (action=update)."},
+        "status": {"type": "string", "enum": list(TASK_STATUSES)},
+        "title": {"type": "string", "description": "New title (action=update)."},
+    },
+    "required": ["action"],
+}
+
+
+def _invalid(message: str) -> ToolRetryError:
+    return ToolRetryError(f"Invalid arguments for tool 'tasks': {message}")
+
+
+def _optional_str(args: JsonObject, key: str) -> str | None:
+    value = args.get(key)
+    if value is None or isinstance(value, str):
+        return value
+    raise _invalid(f"'{key}' must be a string.")
+
+
+def _apply_action(store: TaskStore, args: JsonObject) -> None:
+    action = args.get("action")
+    if action == "create":
+        titles = args.get("titles")
+        if not isinstance(titles, list) or not titles:
+            raise _invalid("'titles' must be a non-empty list of strings.")
+        if not all(isinstance(title, str) for title in titles):
+            raise _invalid("'titles' must be a non-empty list of strings.")
+        for title in titles:
+            store.create(title)
+        return
+    if action == "update":
+        task_id = _optional_str(args, "id")
+        if task_id is None:
+            raise _invalid("'id' is required for action=update.")
+        store.update(
+            task_id, status=_optional_str(args, "status"), title=_optional_str(args, "title")
+        )
+        return
+    if action != "list":
+        raise _invalid(f"'action' must be one of: {', '.join(TASK_ACTIONS)}.")
+
+
+def _render(store: TaskStore, status: str | None) -> str:
+    tasks = [task.to_dict() for task in store.list(status=status)]
+    return json.dumps({"tasks": tasks}, indent=2)
+
+
+def build_task_tool(store_fn: Callable[[], TaskStore]) -> AgentTool:
+    """Return the ``tasks`` tool bound to the store ``store_fn`` returns."""
+
+    async def _execute_tasks(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        _ = (tool_call_id, signal, on_update)
+        store = store_fn()
+        try:
+            _apply_action(store, args)
+        except TaskTransitionError as exc:
+            raise ToolRetryError(str(exc)) from exc
+        status = _optional_str(args, "status") if args.get("action") == "list" else None
+        return AgentToolResult(content=[TextContent(text=_render(store, status))], details={})
+
+    return AgentTool(
+        name=ToolName.TASKS,
+        label=ToolName.TASKS,
+        description=_TASKS_DESCRIPTION,
+        parameters=_TASKS_PARAMETERS,
+        execute=_execute_tasks,
+    )
+
+
+def task_decomposition_providers(enabled: bool) -> list[ContextProvider]:
+    """Return the decomposition prompt section when ``enabled``, else nothing."""
+    if not enabled:
+        return []
+    return [
+        FunctionContextProvider(
+            label=TASK_DECOMPOSITION_LABEL,
+            render_fn=lambda _context: TASK_DECOMPOSITION_PROMPT,
+            priority=TASK_DECOMPOSITION_PRIORITY,
+        )
+    ]
@@ -0,0 +1,134 @@
+"""Scripted tool results for tests of agent behaviour.
+
+A test that wants to see what the agent does when, say, ``bash`` reports a
+failing test run registers a mock instead of running the tool::
+
+    mock = register_tool_mock("bash", ["3 failed, 12 passed"], args={"command": "pytest"})
+
+Mocks are a test-only feature: they are consulted only when the
+``TUNACODE_TEST_TOOL_MOCKS`` environment variable is ``1`` at the time the agent
+is built. Then ``_build_tools`` wraps every tool so a call first looks for a
+mock with the same tool name whose ``args`` are a subset of the call's
+arguments, most recently registered first. A matching mock returns its next
+scripted result -- a string, an ``AgentToolResult``, or an exception to raise
+(``ToolRetryError`` to simulate a tool error) -- and records the call's
+arguments in ``calls``. Once its script is used up it stops matching; a call
+no mock matches runs the real tool. The mock sits inside the concurrency,
+safe-mode, audit and redaction wrappers, so those still apply to mocked calls.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import os
+from collections.abc import Mapping, Sequence
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, cast
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+    TextContent,
+)
+
+if TYPE_CHECKING:
+    from .agent_tools import ToolExecute
+
+TOOL_MOCKS_ENV_VAR = "TUNACODE_TEST_TOOL_MOCKS"
+TOOL_MOCKS_ENABLED_VALUE = "1"
+
+ScriptedResult = str | AgentToolResult | BaseException
+
+
+@dataclass(slots=True)
+class ToolMock:
+    """Scripted results for one tool, optionally only for matching arguments."""
+
+    tool_name: str
+    results: list[ScriptedResult]
+    args: dict[str, object] | None = None
+    calls: list[JsonObject] = field(default_factory=list)
+
+    @property
+    def exhausted(self) -> bool:
+        return len(self.calls) >= len(self.results)
+
+    def matches(self, tool_name: str, args: JsonObject) -> bool:
+        if tool_name != self.tool_name or self.exhausted:
+            return False
+        expected = self.args or {}
+        return all(key in args and args[key] == value for key, value in expected.items())
+
+
+@dataclass
+class _MockRegistry:
+    mocks: list[ToolMock] = field(default_factory=list)
+
+
+_registry = _MockRegistry()
+
+
+def tool_mocks_enabled() -> bool:
+    return os.environ.get(TOOL_MOCKS_ENV_VAR) == TOOL_MOCKS_ENABLED_VALUE
+
+
+def register_tool_mock(
+    tool_name: str,
+    results: ScriptedResult | Sequence[ScriptedResult],
+    *,
+    args: Mapping[str, object] | None = None,
+) -> ToolMock:
+    """Script ``results`` for calls to ``tool_name``; one result is used per matching call."""
+    script = [results] if isinstance(results, ScriptedResult) else list(results)
+    if not script:
+        raise ValueError(f"Tool mock for {tool_name!r} needs at least one result")
+    mock = ToolMock(tool_name=tool_name, results=script, args=dict(args) if args else None)
+    _registry.mocks.append(mock)
+    return mock
+
+
+def clear_tool_mocks() -> None:
+    _registry.mocks.clear()
+
+
+def find_tool_mock(tool_name: str, args: JsonObject) -> ToolMock | None:
+    for mock in reversed(_registry.mocks):
+        if mock.matches(tool_name, args):
+            return mock
+    return None
+
+
+def _play(mock: ToolMock, args: JsonObject) -> AgentToolResult:
+    result = mock.results[len(mock.calls)]
+    mock.calls.append(dict(args))
+    if isinstance(result, BaseException):
+        raise result
+    if isinstance(result, str):
+        return AgentToolResult(content=[TextContent(text=result)], details={"mocked": True})
+    return result
+
+
+def _wrap_tool_with_mocks(tool: AgentTool) -> AgentTool:
+    typed_execute_fn = cast("ToolExecute", tool.execute)
+
+    async def _execute_or_mock(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        mock = find_tool_mock(tool.name, args)
+        if mock is not None:
+            return _play(mock, args)
+        return await typed_execute_fn(tool_call_id, args, signal, on_update)
+
+    return tool.model_copy(update={"execute": _execute_or_mock})
+
+
+def apply_tool_mocks(tools: Sequence[AgentTool]) -> list[AgentTool]:
+    """Route ``tools`` through the mock registry when mocks are enabled."""
+    if not tool_mocks_enabled():
+        return list(tools)
+    return [_wrap_tool_with_mocks(tool) for tool in tools]
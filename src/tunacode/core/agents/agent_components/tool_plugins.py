@@ -0,0 +1,247 @@
+"""In-process tool plugins registered at runtime.
+
+An embedder extends the agent with its own native tools by implementing
+``ToolPlugin`` -- ``name()``, ``schema()`` and an async ``invoke(args)`` -- and
+calling ``register_tool_plugin``. The next agent build picks the plugin up:
+``_build_tools`` merges plugin tools with the built-ins, and they go through
+the same concurrency limit, safe-mode check, secret redaction and command
+audit as any built-in tool.
+
+Before ``invoke`` runs, the arguments are checked against the top level of
+``schema()`` (required keys, unknown keys when ``additionalProperties`` is
+false, and property types), and every argument named in ``path_arguments`` is
+held inside the working directory. Both failures come back to the model as
+``ToolRetryError``. The plugin's ``risk`` tier is what safe mode and the
+command policy see; it defaults to ``write``, so a plugin is only offered in
+safe mode when it declares itself ``read_only``.
+
+Names follow the collision rule of ``tool_catalog.merge_tool_sources``:
+built-in tools keep their bare names, and a plugin whose name collides with
+any other tool is offered as ``<source>__<name>``.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import re
+from collections import Counter
+from collections.abc import Mapping, Sequence
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Protocol
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+    TextContent,
+)
+
+from tunacode.constants import CommandRisk
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools.utils.command_risk import set_extension_tool_risks
+from tunacode.tools.utils.workspace import jail_workspace_path
+
+BUILTIN_TOOL_SOURCE = "builtin"
+PLUGIN_TOOL_SOURCE = "plugin"
+NAMESPACE_SEPARATOR = "__"
+
+_TOOL_NAME_PATTERN = re.compile(r"^[A-Za-z0-9_-]{1,64}$")
+_JSON_TYPES: dict[str, tuple[type, ...]] = {
+    "string": (str,),
+    "integer": (int,),
+    "number": (int, float),
+    "boolean": (bool,),
+    "array": (list,),
+    "object": (dict,),
+    "null": (type(None),),
+}
+
+
+class ToolPlugin(Protocol):
+    """A native tool supplied by the embedding application."""
+
+    def name(self) -> str: ...
+
+    def schema(self) -> JsonObject:
+        """JSON Schema for the arguments; its ``description`` is shown to the model."""
+        ...
+
+    async def invoke(self, args: JsonObject) -> AgentToolResult | str: ...
+
+
+@dataclass(frozen=True, slots=True)
+class RegisteredToolPlugin:
+    plugin: ToolPlugin
+    source: str
+    risk: CommandRisk
+    path_arguments: tuple[str, ...]
+
+    @property
+    def name(self) -> str:
+        return self.plugin.name()
+
+
+@dataclass(frozen=True, slots=True)
+class NamedTool:
+    """A tool with the source it came from and the name the model sees."""
+
+    name: str
+    source: str
+    tool: AgentTool
+
+
+@dataclass
+class _PluginRegistry:
+    plugins: dict[tuple[str, str], RegisteredToolPlugin] = field(default_factory=dict)
+
+
+_registry = _PluginRegistry()
+
+
+def _check_tool_name(name: str, *, what: str) -> None:
+    if not _TOOL_NAME_PATTERN.fullmatch(name):
+        raise ValueError(f"Tool plugin {what} must match [A-Za-z0-9_-]{{1,64}}, got {name!r}")
+
+
+def register_tool_plugin(
+    plugin: ToolPlugin,
+    *,
+    source: str = PLUGIN_TOOL_SOURCE,
+    risk: CommandRisk = CommandRisk.WRITE,
+    path_arguments: Sequence[str] = (),
+) -> None:
+    """Offer ``plugin`` to agents built from now on; re-registering replaces it."""
+    name = plugin.name()
+    _check_tool_name(name, what="name")
+    _check_tool_name(source, what="source")
+    if source == BUILTIN_TOOL_SOURCE:
+        raise ValueError(f"Tool plugin source {BUILTIN_TOOL_SOURCE!r} is reserved")
+    if plugin.schema().get("type") != "object":
+        raise ValueError(f"Tool plugin {name!r} schema must have type 'object'")
+    _registry.plugins[(source, name)] = RegisteredToolPlugin(
+        plugin=plugin, source=source, risk=risk, path_arguments=tuple(path_arguments)
+    )
+
+
+def unregister_tool_plugin(name: str, *, source: str = PLUGIN_TOOL_SOURCE) -> bool:
+    """Remove a registered plugin; returns whether one was removed."""
+    return _registry.plugins.pop((source, name), None) is not None
+
+
+def registered_tool_plugins() -> list[RegisteredToolPlugin]:
+    return list(_registry.plugins.values())
+
+
+def namespaced_tool_name(source: str, tool_name: str) -> str:
+    return f"{source}{NAMESPACE_SEPARATOR}{tool_name}"
+
+
+def resolve_tool_names(sources: Mapping[str, Sequence[AgentTool]]) -> list[NamedTool]:
+    """Name every tool uniquely, prefixing colliding non-built-in tools with their source."""
+    name_counts = Counter(tool.name for tools in sources.values() for tool in tools)
+    named: list[NamedTool] = []
+    for source, tools in sources.items():
+        for tool in tools:
+            collides = name_counts[tool.name] > 1 and source != BUILTIN_TOOL_SOURCE
+            name = namespaced_tool_name(source, tool.name) if collides else tool.name
+            named.append(NamedTool(name=name, source=source, tool=tool))
+    return named
+
+
+def _type_error(value: object, expected: object) -> str | None:
+    names = expected if isinstance(expected, list) else [expected]
+    allowed = [_JSON_TYPES[name] for name in names if isinstance(name, str) and name in _JSON_TYPES]
+    if not allowed:
+        return None
+    is_bool = isinstance(value, bool)
+    for types in allowed:
+        if isinstance(value, types) and (bool in types or not is_bool):
+            return None
+    return f"must be of type {' or '.join(str(name) for name in names)}"
+
+
+def validate_tool_arguments(tool_name: str, schema: JsonObject, args: JsonObject) -> None:
+    """Check ``args`` against the top level of ``schema``; nested schemas are not checked."""
+    problems: list[str] = []
+    properties = schema.get("properties")
+    properties = properties if isinstance(properties, dict) else {}
+    required = schema.get("required")
+    for key in required if isinstance(required, list) else []:
+        if key not in args:
+            problems.append(f"'{key}' is required")
+    for key, value in args.items():
+        spec = properties.get(key)
+        if spec is None:
+            if schema.get("additionalProperties") is False:
+                problems.append(f"'{key}' is not a known argument")
+            continue
+        error = _type_error(value, spec.get("type")) if isinstance(spec, dict) else None
+        if error is not None:
+            problems.append(f"'{key}' {error}")
+    if problems:
+        raise ToolRetryError(f"Invalid arguments for tool '{tool_name}': {'; '.join(problems)}.")
+
+
+def _jail_path_arguments(entry: RegisteredToolPlugin, args: JsonObject) -> None:
+    root = Path.cwd().resolve()
+    for key in entry.path_arguments:
+        value = args.get(key)
+        if isinstance(value, str):
+            jail_workspace_path(value, root)
+
+
+def _plugin_agent_tool(entry: RegisteredToolPlugin) -> AgentTool:
+    schema = dict(entry.plugin.schema())
+    name = entry.name
+
+    async def _execute_plugin(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        _ = (tool_call_id, signal, on_update)
+        validate_tool_arguments(name, schema, args)
+        _jail_path_arguments(entry, args)
+        result = await entry.plugin.invoke(args)
+        if isinstance(result, str):
+            return AgentToolResult(content=[TextContent(text=result)], details={})
+        return result
+
+    description = schema.get("description")
+    return AgentTool(
+        name=name,
+        label=name,
+        description=description if isinstance(description, str) else "",
+        parameters=schema,
+        execute=_execute_plugin,
+    )
+
+
+def plugin_tool_sources() -> dict[str, list[AgentTool]]:
+    """Group the registered plugins' tools by source, in registration order."""
+    sources: dict[str, list[AgentTool]] = {}
+    for entry in _registry.plugins.values():
+        sources.setdefault(entry.source, []).append(_plugin_agent_tool(entry))
+    return sources
+
+
+def merge_plugin_tools(builtin: Sequence[AgentTool]) -> list[AgentTool]:
+    """Return ``builtin`` followed by the plugin tools, named so none collide.
+
+    Also publishes each plugin's risk tier under the name the model sees.
+    """
+    risks = {(entry.source, entry.name): entry.risk for entry in _registry.plugins.values()}
+    named = resolve_tool_names({BUILTIN_TOOL_SOURCE: builtin, **plugin_tool_sources()})
+    set_extension_tool_risks(
+        {item.name: risks[(item.source, item.tool.name)] for item in named[len(builtin) :]}
+    )
+    return [
+        item.tool
+        if item.name == item.tool.name
+        else item.tool.model_copy(update={"name": item.name})
+        for item in named
+    ]
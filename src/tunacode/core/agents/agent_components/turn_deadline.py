@@ -0,0 +1,77 @@
+"""Wall-clock deadline for one whole request turn.
+
+``settings.turn_deadline`` bounds everything a turn does: model requests,
+tool execution and the retries in between. It is separate from per-request
+provider timeouts and from ``settings.global_request_timeout``; whichever
+fires first ends the turn.
+
+The deadline is an ``asyncio.timeout_at`` around the turn's task, so expiry
+cancels whatever is awaiting at that moment (a streaming model request or a
+running tool). The orchestrator then reaps the turn's process groups, so a
+cancelled ``bash`` command leaves no orphaned processes. ``TurnDeadline``
+records what was in flight so the notice and error can name it.
+"""
+
+from __future__ import annotations
+
+import asyncio
+from collections.abc import AsyncIterator
+from contextlib import asynccontextmanager
+
+MODEL_REQUEST_ACTIVITY = "model request"
+TURN_DEADLINE_NOTICE_TEMPLATE = (
+    "Turn deadline exceeded after {seconds:g}s while running: {running}."
+)
+
+
+class TurnDeadline:
+    """Arms one deadline per turn and tracks the tool calls running under it."""
+
+    def __init__(self, seconds: float | None) -> None:
+        self.seconds = seconds
+        self._running: dict[str, str] = {}
+        self._timeout: asyncio.Timeout | None = None
+        self._expired = False
+
+    @property
+    def expired(self) -> bool:
+        """Whether the last scope ended because the deadline fired."""
+        return self._expired
+
+    def reset(self) -> None:
+        """Restart the clock of the active scope, giving the turn a full deadline again."""
+        if self._timeout is not None:
+            self._timeout.reschedule(self._deadline_at())
+
+    @asynccontextmanager
+    async def scope(self) -> AsyncIterator[TurnDeadline]:
+        self._running.clear()
+        self._expired = False
+        async with asyncio.timeout_at(self._deadline_at()) as timeout:
+            self._timeout = timeout
+            try:
+                yield self
+            finally:
+                self._expired = timeout.expired()
+                self._timeout = None
+
+    def tool_started(self, tool_call_id: str, label: str) -> None:
+        self._running[tool_call_id] = label
+
+    def tool_finished(self, tool_call_id: str) -> None:
+        self._running.pop(tool_call_id, None)
+
+    def running(self) -> list[str]:
+        """Labels of the running tool calls, or the model request when none are."""
+        return list(self._running.values()) or [MODEL_REQUEST_ACTIVITY]
+
+    def notice(self) -> str:
+        return TURN_DEADLINE_NOTICE_TEMPLATE.format(
+            seconds=self.seconds or 0.0,
+            running=", ".join(self.running()),
+        )
+
+    def _deadline_at(self) -> float | None:
+        if self.seconds is None:
+            return None
+        return asyncio.get_running_loop().time() + self.seconds
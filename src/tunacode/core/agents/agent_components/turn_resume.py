@@ -0,0 +1,60 @@
+"""Finish the interrupted last turn of a resumed session.
+
+``detect_interrupted_turn()`` (``core/agents/resume/interrupted.py``) reports
+what the previous process left unfinished; ``prepare_turn_resume()`` brings
+the history to a point a new request can pick up from and returns the message
+to send:
+
+- ``no_response``: the trailing user message is removed and its text (without
+  the configured prefix/suffix, which the new request adds again) is returned,
+  so the prompt runs again as if it had just been sent.
+- ``pending_tool_calls``: only the tool calls without a result are run; results
+  that were already recorded are kept, so no tool runs twice. The new results
+  are appended and ``RESUME_CONTINUE_PROMPT`` asks the model to continue.
+- ``unanswered_tool_results``: nothing needs to run; the model is asked to
+  continue from the results already in the history.
+"""
+
+from __future__ import annotations
+
+from tunacode.types import ModelName
+from tunacode.utils.messaging import estimate_messages_tokens
+
+from tunacode.core.agents.resume.interrupted import InterruptedTurnKind, detect_interrupted_turn
+from tunacode.core.logging.manager import get_logger
+from tunacode.core.types.state import StateManagerProtocol
+
+from ..helpers import strip_user_message_affixes
+from .agent_config import get_or_create_agent
+from .partial_recovery import execute_tool_calls
+
+RESUME_CONTINUE_PROMPT = (
+    "The previous session ended before this turn finished. The tool calls it left "
+    "without results have been run and all results are above. Continue from those results."
+)
+
+
+async def prepare_turn_resume(model: ModelName, state_manager: StateManagerProtocol) -> str | None:
+    """Repair the interrupted last turn and return the message to send, or None."""
+    conversation = state_manager.session.conversation
+    turn = detect_interrupted_turn(conversation.messages)
+    if turn is None:
+        return None
+
+    get_logger().lifecycle(f"Resume: interrupted turn kind={turn.kind.value}")
+    if turn.kind is InterruptedTurnKind.NO_RESPONSE:
+        conversation.messages.pop()
+        conversation.total_tokens = estimate_messages_tokens(conversation.messages)
+        settings = state_manager.session.user_config["settings"]
+        return strip_user_message_affixes(
+            turn.prompt,
+            prefix=settings["user_message_prefix"],
+            suffix=settings["user_message_suffix"],
+        )
+
+    if turn.missing_tool_calls:
+        tools = get_or_create_agent(model, state_manager).state.tools
+        results = await execute_tool_calls(turn.missing_tool_calls, tools)
+        conversation.messages.extend(results)
+        conversation.total_tokens = estimate_messages_tokens(conversation.messages)
+    return RESUME_CONTINUE_PROMPT
@@ -0,0 +1,53 @@
+"""Live token-usage updates while a model call streams.
+
+Some providers report running usage before the call ends -- periodically, or
+in the last chunk ahead of the close. ``interim_usage`` reads it from the
+partial assistant message of a stream update and keeps only snapshots that
+grew, so a live display never moves backwards within a call. A call whose
+provider reports nothing until the end produces no interim updates.
+
+Interim snapshots describe the current call only and are never added to the
+session total: ``session.usage.session_total_usage`` still grows solely by the
+authoritative usage of each finished call, which is also sent as the last,
+``final`` update of the call.
+"""
+
+from __future__ import annotations
+
+import copy
+
+from tunacode.types import UsageMetrics
+
+
+def _grew(previous: UsageMetrics, current: UsageMetrics) -> bool:
+    shrank = (
+        current.input < previous.input
+        or current.output < previous.output
+        or current.cache_read < previous.cache_read
+        or current.cache_write < previous.cache_write
+        or current.total_tokens < previous.total_tokens
+        or current.cost.total < previous.cost.total
+    )
+    return not shrank and current != previous
+
+
+def interim_usage(raw_usage: object, previous: UsageMetrics | None) -> UsageMetrics | None:
+    """The running usage in ``raw_usage`` when it is reported and larger than ``previous``."""
+    if not isinstance(raw_usage, dict):
+        return None
+    try:
+        usage = UsageMetrics.from_dict(raw_usage)
+    except (TypeError, ValueError):
+        return None
+    if usage.total_tokens <= 0 and usage.cost.total <= 0:
+        return None
+    if previous is not None and not _grew(previous, usage):
+        return None
+    return usage
+
+
+def session_total_with(session_total: UsageMetrics, call_usage: UsageMetrics) -> UsageMetrics:
+    """A copy of ``session_total`` with the in-flight call's usage added."""
+    combined = copy.deepcopy(session_total)
+    combined.add(call_usage)
+    return combined
@@ -2,7 +2,8 @@
 
 from __future__ import annotations
 
-from dataclasses import dataclass
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING
 
 from tinyagent.agent_types import (
     AgentToolResult,
@@ -14,14 +15,35 @@ from tunacode.types import UsageMetrics
 
 from tunacode.core.types.state_structures import RuntimeState
 
+if TYPE_CHECKING:
+    from .agent_components.delta_coalescer import TextDeltaCoalescer
+
 CONTEXT_OVERFLOW_PATTERNS: tuple[str, ...] = (
     "context_length_exceeded",
     "maximum context length",
 )
+# Provider-specific wording, keyed by the provider id of ``provider:model`` and
+# checked before the shared patterns above.
+CONTEXT_OVERFLOW_PROVIDER_PATTERNS: dict[str, tuple[str, ...]] = {
+    "anthropic": ("prompt is too long",),
+    "gemini": ("exceeds the maximum number of tokens allowed",),
+    "google": ("exceeds the maximum number of tokens allowed",),
+    "groq": ("please reduce the length of the messages",),
+    "mistral": ("too large for model with",),
+    "ollama": ("exceeds the context window", "exceeds the available context size"),
+}
 CONTEXT_OVERFLOW_RETRY_NOTICE = "Context overflow detected. Compacting and retrying once..."
 CONTEXT_OVERFLOW_FAILURE_NOTICE = (
     "Context is still too large after compaction. Use /compact or /clear and retry."
 )
+CONTEXT_OVERFLOW_AUTO_COMPACT_OFF_NOTICE = (
+    "Context overflow: settings.auto_compact is off. Use /compact or /clear and retry."
+)
+LOOP_DETECTED_NOTICE_TEMPLATE = "Possible loop detected: {description}."
+STEP_LIMIT_NOTICE_TEMPLATE = (
+    "Step limit reached: stopped after {count} model turns "
+    "(settings.max_iterations = {limit}). Send a follow-up message to continue."
+)
 
 
 @dataclass(slots=True)
@@ -32,6 +54,27 @@ class _TinyAgentStreamState:
     active_tool_call_ids: set[str]
     batch_tool_call_ids: set[str]
     last_assistant_message: AssistantMessage | None = None
+    text_coalescer: TextDeltaCoalescer | None = None
+    last_delta_type: str = ""
+    tool_progress_labels: dict[str, str] = field(default_factory=dict)
+    running_usage: UsageMetrics | None = None
+    checkpointed_message_count: int = 0
+
+
+def apply_user_message_affixes(message: str, *, prefix: str, suffix: str) -> str:
+    """Wrap ``message`` in the configured prefix/suffix, each on its own paragraph."""
+    parts = [part for part in (prefix.strip(), message, suffix.strip()) if part]
+    return "\n\n".join(parts)
+
+
+def strip_user_message_affixes(message: str, *, prefix: str, suffix: str) -> str:
+    """Undo ``apply_user_message_affixes`` for a message read back from history."""
+    prefix, suffix = prefix.strip(), suffix.strip()
+    if prefix:
+        message = message.removeprefix(f"{prefix}\n\n")
+    if suffix:
+        message = message.removesuffix(f"\n\n{suffix}")
+    return message
 
 
 def coerce_error_text(value: object) -> str:
@@ -40,11 +83,12 @@ def coerce_error_text(value: object) -> str:
     return ""
 
 
-def is_context_overflow_error(error_text: str) -> bool:
+def is_context_overflow_error(error_text: str, provider: str = "") -> bool:
     if not error_text:
         return False
     normalized_error = error_text.lower()
-    return any(pattern in normalized_error for pattern in CONTEXT_OVERFLOW_PATTERNS)
+    patterns = (*CONTEXT_OVERFLOW_PROVIDER_PATTERNS.get(provider, ()), *CONTEXT_OVERFLOW_PATTERNS)
+    return any(pattern in normalized_error for pattern in patterns)
 
 
 def parse_canonical_usage(raw_usage: object) -> UsageMetrics:
@@ -5,22 +5,34 @@ from __future__ import annotations
 import asyncio
 import time
 import uuid
-from typing import cast
+from pathlib import Path
+from typing import NoReturn, cast
 
 from tinyagent.agent import Agent
 from tinyagent.agent_types import AgentMessage, AgentTool
 
+from tunacode.configuration.models import is_known_model
 from tunacode.constants import DEFAULT_CONTEXT_WINDOW
-from tunacode.exceptions import ContextOverflowError, GlobalRequestTimeoutError
+from tunacode.exceptions import (
+    ContextOverflowError,
+    GlobalRequestTimeoutError,
+    ModelConfigurationError,
+    TurnDeadlineExceededError,
+)
 from tunacode.types import (
     ModelName,
     NoticeCallback,
     StreamingCallback,
+    ToolProgressCallback,
     ToolResultCallback,
     ToolStartCallback,
+    UsageCallback,
     UsageMetrics,
 )
 from tunacode.utils.messaging import estimate_messages_tokens
+from tunacode.utils.system.process_group import AGENT_TURN_SCOPE
+
+from tunacode.tools import edit_journal
 
 from tunacode.core.compaction.controller import (
     CompactionStatusCallback,
@@ -28,17 +40,29 @@ from tunacode.core.compaction.controller import (
     build_compaction_notice,
     get_or_create_compaction_controller,
 )
+from tunacode.core.compaction.history_window import HistoryWindow
 from tunacode.core.compaction.types import CompactionOutcome
 from tunacode.core.logging.manager import get_logger
 from tunacode.core.types.state import StateManagerProtocol
 
 from . import agent_components as ac
-from .agent_components.agent_config import _coerce_global_request_timeout
+from .agent_components.agent_config import (
+    _coerce_global_request_timeout,
+    _coerce_max_iterations,
+    _coerce_turn_deadline,
+)
 from .agent_components.agent_streaming import AgentStreamMixin
+from .agent_components.auto_format import FORMATTED_NOTICE_TEMPLATE, format_turn_edits
+from .agent_components.provider_error_hints import provider_from_model
+from .agent_components.turn_deadline import TurnDeadline
 from .helpers import (
+    CONTEXT_OVERFLOW_AUTO_COMPACT_OFF_NOTICE,
     CONTEXT_OVERFLOW_FAILURE_NOTICE,
     CONTEXT_OVERFLOW_RETRY_NOTICE,
+    LOOP_DETECTED_NOTICE_TEMPLATE,
+    STEP_LIMIT_NOTICE_TEMPLATE,
     _TinyAgentStreamState,
+    apply_user_message_affixes,
     coerce_error_text,
     is_context_overflow_error,
 )
@@ -61,6 +85,8 @@ class RequestOrchestrator(AgentStreamMixin):
         tool_start_callback: ToolStartCallback | None = None,
         notice_callback: NoticeCallback | None = None,
         compaction_status_callback: CompactionStatusCallback | None = None,
+        tool_progress_callback: ToolProgressCallback | None = None,
+        usage_callback: UsageCallback | None = None,
     ) -> None:
         self.message = message
         self.model = model
@@ -71,19 +97,52 @@ class RequestOrchestrator(AgentStreamMixin):
         self.tool_start_callback = tool_start_callback
         self.notice_callback = notice_callback
         self.compaction_status_callback = compaction_status_callback
+        self.tool_progress_callback = tool_progress_callback
+        self.usage_callback = usage_callback
         self.compaction_controller = get_or_create_compaction_controller(state_manager)
         self._active_stream_state: _TinyAgentStreamState | None = None
+        self._turn_deadline = TurnDeadline(_coerce_turn_deadline(state_manager.session))
 
     async def run(self) -> Agent:
+        try:
+            return await self._run_with_timeout()
+        finally:
+            self._kill_turn_processes()
+
+    async def _run_with_timeout(self) -> Agent:
         timeout = _coerce_global_request_timeout(self.state_manager.session)
         if timeout is None:
-            return await self._run_impl()
+            return await self._run_with_turn_deadline()
         try:
-            return await asyncio.wait_for(self._run_impl(), timeout=timeout)
+            return await asyncio.wait_for(self._run_with_turn_deadline(), timeout=timeout)
         except TimeoutError as exc:
             self._invalidate_agent_cache_after_timeout(timeout)
             raise GlobalRequestTimeoutError(timeout) from exc
 
+    async def _run_with_turn_deadline(self) -> Agent:
+        deadline = self._turn_deadline
+        try:
+            async with deadline.scope():
+                return await self._run_impl()
+        except TimeoutError as exc:
+            if not deadline.expired or deadline.seconds is None:
+                raise
+            running = deadline.running()
+            get_logger().warning(f"Turn deadline exceeded; cancelled: {', '.join(running)}")
+            self._invalidate_agent_cache_after_timeout(deadline.seconds)
+            if self.notice_callback is not None:
+                self.notice_callback(deadline.notice())
+            raise TurnDeadlineExceededError(deadline.seconds, running) from exc
+
+    def reset_turn_deadline(self) -> None:
+        """Give the running turn a fresh ``settings.turn_deadline`` from now."""
+        self._turn_deadline.reset()
+
+    def _kill_turn_processes(self) -> None:
+        killed = AGENT_TURN_SCOPE.kill_all()
+        if killed:
+            get_logger().lifecycle(f"Turn end: reaped process_groups={killed}")
+
     def _invalidate_agent_cache_after_timeout(self, timeout: float) -> None:
         _ = timeout
         logger = get_logger()
@@ -145,6 +204,9 @@ class RequestOrchestrator(AgentStreamMixin):
             agent=agent,
             pre_request_history=pre_request_history,
         )
+        await self._auto_format_turn_edits()
+        self._maybe_emit_loop_notice()
+        self._maybe_emit_step_limit_notice()
         return agent
 
     def _initialize_request(self) -> None:
@@ -155,7 +217,10 @@ class RequestOrchestrator(AgentStreamMixin):
         runtime.current_iteration = 0
         runtime.iteration_count = 0
         runtime.batch_counter = 0
+        runtime.step_limit_reached = False
+        runtime.loop_detection = ""
         session.usage.last_call_usage = UsageMetrics()
+        edit_journal.begin_turn()
         if not session.task.original_query:
             session.task.original_query = self.message
 
@@ -166,8 +231,48 @@ class RequestOrchestrator(AgentStreamMixin):
         if notice is not None:
             self.notice_callback(notice)
 
+    async def _auto_format_turn_edits(self) -> None:
+        settings = self.state_manager.session.user_config["settings"]["auto_format"]
+        if not settings["enabled"] or not settings["formatters"]:
+            return
+        report = await format_turn_edits(settings, root=Path.cwd().resolve())
+        logger = get_logger()
+        for warning in report.warnings:
+            logger.warning(warning)
+        notices = list(report.warnings)
+        if report.formatted:
+            notices.append(
+                FORMATTED_NOTICE_TEMPLATE.format(
+                    count=len(report.formatted),
+                    paths=", ".join(path.name for path in report.formatted),
+                )
+            )
+        if self.notice_callback is not None:
+            for notice in notices:
+                self.notice_callback(notice)
+
+    def _maybe_emit_loop_notice(self) -> None:
+        loop_detection = self.state_manager.session.runtime.loop_detection
+        if not loop_detection or self.notice_callback is None:
+            return
+        self.notice_callback(LOOP_DETECTED_NOTICE_TEMPLATE.format(description=loop_detection))
+
+    def _maybe_emit_step_limit_notice(self) -> None:
+        session = self.state_manager.session
+        if not session.runtime.step_limit_reached or self.notice_callback is None:
+            return
+        self.notice_callback(
+            STEP_LIMIT_NOTICE_TEMPLATE.format(
+                count=session.runtime.iteration_count,
+                limit=_coerce_max_iterations(session),
+            )
+        )
+
     async def _compact_history_for_request(self, history: list[AgentMessage]) -> list[AgentMessage]:
         self.compaction_controller.reset_request_state()
+        settings = self.state_manager.session.user_config["settings"]
+        if HistoryWindow.from_settings(settings["history"]).enabled:
+            return history
         outcome = await self.compaction_controller.check_and_compact(
             history,
             max_tokens=self.state_manager.session.conversation.max_tokens,
@@ -192,11 +297,16 @@ class RequestOrchestrator(AgentStreamMixin):
         agent: Agent,
         pre_request_history: list[AgentMessage],
     ) -> None:
+        provider = provider_from_model(self.model)
         error_text = self._agent_error_text(agent)
-        if not is_context_overflow_error(error_text):
+        if not is_context_overflow_error(error_text, provider):
             return
 
         logger = get_logger()
+        if not self.compaction_controller.auto_compact:
+            logger.warning("Context overflow detected; settings.auto_compact is off")
+            self._raise_context_overflow(CONTEXT_OVERFLOW_AUTO_COMPACT_OFF_NOTICE)
+
         logger.warning("Context overflow detected; forcing compaction and retrying")
         if self.notice_callback is not None:
             self.notice_callback(CONTEXT_OVERFLOW_RETRY_NOTICE)
@@ -204,6 +314,11 @@ class RequestOrchestrator(AgentStreamMixin):
         conversation = self.state_manager.session.conversation
         apply_compaction_messages(self.state_manager, pre_request_history)
         forced_history = await self._force_compact_history(pre_request_history)
+        self.compaction_controller.record_overflow_retry()
+        logger.lifecycle(
+            f"Context overflow retry: {len(pre_request_history)} -> "
+            f"{len(forced_history)} messages"
+        )
         agent.replace_messages(forced_history)
         self.state_manager.session._debug_raw_stream_accum = ""
         await self._run_stream(
@@ -212,11 +327,15 @@ class RequestOrchestrator(AgentStreamMixin):
         )
 
         retry_error_text = self._agent_error_text(agent)
-        if not is_context_overflow_error(retry_error_text):
+        if not is_context_overflow_error(retry_error_text, provider):
             return
 
+        self._raise_context_overflow(CONTEXT_OVERFLOW_FAILURE_NOTICE)
+
+    def _raise_context_overflow(self, notice: str) -> NoReturn:
         if self.notice_callback is not None:
-            self.notice_callback(CONTEXT_OVERFLOW_FAILURE_NOTICE)
+            self.notice_callback(notice)
+        conversation = self.state_manager.session.conversation
         estimated_tokens = conversation.total_tokens
         if estimated_tokens == 0 and conversation.messages:
             estimated_tokens = estimate_messages_tokens(conversation.messages)
@@ -237,6 +356,29 @@ def get_agent_tool() -> tuple[type[Agent], type[object]]:
     return Agent, cast(type[object], AgentTool)
 
 
+def resolve_turn_model(
+    model: ModelName,
+    model_override: ModelName | None,
+    state_manager: StateManagerProtocol,
+) -> ModelName:
+    """Return the model for one request: ``model_override`` when given, else ``model``.
+
+    An override must be a configured model (the default, a recent model, or the
+    session default passed in) or one listed in the models registry.
+    """
+    if model_override is None:
+        return model
+    user_config = state_manager.session.user_config
+    configured = {model, user_config["default_model"], *user_config["recent_models"]}
+    if model_override in configured or is_known_model(model_override):
+        return model_override
+    raise ModelConfigurationError(
+        model_override,
+        "per-turn model override is not a configured or registry model",
+        valid_models=sorted(configured),
+    )
+
+
 async def process_request(
     message: str,
     model: ModelName,
@@ -247,10 +389,37 @@ async def process_request(
     tool_start_callback: ToolStartCallback | None = None,
     notice_callback: NoticeCallback | None = None,
     compaction_status_callback: CompactionStatusCallback | None = None,
+    model_override: ModelName | None = None,
+    tool_progress_callback: ToolProgressCallback | None = None,
+    apply_affixes: bool = True,
+    usage_callback: UsageCallback | None = None,
 ) -> Agent:
+    """Run one request; ``model_override`` selects a different model for this turn only.
+
+    The override never changes ``session.current_model``. Each assistant message
+    records the ``provider`` and ``model`` that produced it, so the session file
+    shows which model served every turn.
+
+    Unless ``apply_affixes`` is False, the message is wrapped in
+    ``settings.user_message_prefix``/``user_message_suffix`` first, and the
+    wrapped text is what the history records.
+
+    ``usage_callback`` receives any running usage the provider reports while a
+    call streams, then the call's final authoritative usage.
+    """
+    if apply_affixes:
+        settings = state_manager.session.user_config["settings"]
+        message = apply_user_message_affixes(
+            message,
+            prefix=settings["user_message_prefix"],
+            suffix=settings["user_message_suffix"],
+        )
+    turn_model = resolve_turn_model(model, model_override, state_manager)
+    if turn_model != model:
+        get_logger().lifecycle(f"Init: turn model override={turn_model} default={model}")
     orchestrator = RequestOrchestrator(
         message,
-        model,
+        turn_model,
         state_manager,
         streaming_callback,
         thinking_callback,
@@ -258,5 +427,7 @@ async def process_request(
         tool_start_callback,
         notice_callback,
         compaction_status_callback,
+        tool_progress_callback,
+        usage_callback,
     )
     return await orchestrator.run()
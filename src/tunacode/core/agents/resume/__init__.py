@@ -1,6 +1,7 @@
 """Session resume helpers (tinyagent-only).
 
-This package contains utilities for cleaning up conversation history between runs.
+This package contains utilities for cleaning up conversation history between runs
+and for detecting a last turn the previous run never finished.
 
 TunaCode persists history as tinyagent-style dict messages:
 
@@ -9,6 +10,11 @@ TunaCode persists history as tinyagent-style dict messages:
 Legacy pydantic-ai message formats are intentionally not supported.
 """
 
+from tunacode.core.agents.resume.interrupted import (  # noqa: F401
+    InterruptedTurn,
+    InterruptedTurnKind,
+    detect_interrupted_turn,
+)
 from tunacode.core.agents.resume.sanitize import (  # noqa: F401
     find_dangling_tool_call_ids,
     remove_consecutive_requests,
@@ -0,0 +1,102 @@
+"""Detect a turn the previous process never finished.
+
+A running request checkpoints the session after each assistant message and
+each finished tool batch (``RequestOrchestrator._checkpoint_turn``), so a
+crash mid-turn leaves the unfinished turn at the end of the saved history.
+``detect_interrupted_turn()`` reads that tail:
+
+- ``no_response``: a user message with nothing after it; the model never
+  answered.
+- ``pending_tool_calls``: the last assistant message has tool calls without
+  results. Some results may already follow it; ``missing_tool_calls`` lists
+  only the calls that have none.
+- ``unanswered_tool_results``: every tool call has its result, but no
+  assistant message follows them.
+
+A turn the user aborted (``stop_reason`` ``aborted``) and an assistant reply
+without tool calls, the normal end of a turn, are not reported.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+from dataclasses import dataclass
+from enum import StrEnum
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+ABORTED_STOP_REASON = "aborted"
+
+
+class InterruptedTurnKind(StrEnum):
+    NO_RESPONSE = "no_response"
+    PENDING_TOOL_CALLS = "pending_tool_calls"
+    UNANSWERED_TOOL_RESULTS = "unanswered_tool_results"
+
+
+@dataclass(frozen=True, slots=True)
+class InterruptedTurn:
+    kind: InterruptedTurnKind
+    prompt: str = ""
+    missing_tool_calls: tuple[ToolCallContent, ...] = ()
+    completed_tool_call_ids: tuple[str, ...] = ()
+
+    def describe(self) -> str:
+        if self.kind is InterruptedTurnKind.NO_RESPONSE:
+            return "the last message never got a response"
+        if self.kind is InterruptedTurnKind.PENDING_TOOL_CALLS:
+            total = len(self.missing_tool_calls) + len(self.completed_tool_call_ids)
+            return f"{len(self.missing_tool_calls)} of {total} tool call(s) never finished"
+        return "the model never answered the last tool results"
+
+
+def _user_text(message: UserMessage) -> str:
+    return "".join(item.text for item in message.content if isinstance(item, TextContent))
+
+
+def _last_tool_turn(
+    messages: Sequence[AgentMessage],
+) -> tuple[AssistantMessage, list[ToolResultMessage]] | None:
+    """The last assistant message and the tool results after it, if only results follow."""
+    results: list[ToolResultMessage] = []
+    for message in reversed(messages):
+        if isinstance(message, AssistantMessage):
+            return message, results
+        if not isinstance(message, ToolResultMessage):
+            return None
+        results.append(message)
+    return None
+
+
+def detect_interrupted_turn(messages: Sequence[AgentMessage]) -> InterruptedTurn | None:
+    """Return the unfinished last turn of ``messages``, or None when it completed."""
+    if not messages:
+        return None
+    last = messages[-1]
+    if isinstance(last, UserMessage):
+        return InterruptedTurn(kind=InterruptedTurnKind.NO_RESPONSE, prompt=_user_text(last))
+
+    tool_turn = _last_tool_turn(messages)
+    if tool_turn is None:
+        return None
+    assistant, results = tool_turn
+    tool_calls = [item for item in assistant.content if isinstance(item, ToolCallContent)]
+    if assistant.stop_reason == ABORTED_STOP_REASON or not tool_calls:
+        return None
+
+    answered = {result.tool_call_id for result in results}
+    missing = tuple(call for call in tool_calls if call.id not in answered)
+    completed = tuple(call.id for call in tool_calls if call.id in answered)
+    kind = (
+        InterruptedTurnKind.PENDING_TOOL_CALLS
+        if missing
+        else InterruptedTurnKind.UNANSWERED_TOOL_RESULTS
+    )
+    return InterruptedTurn(kind=kind, missing_tool_calls=missing, completed_tool_call_ids=completed)
@@ -0,0 +1,68 @@
+"""Introspection over the tools offered to the model.
+
+Tools are grouped by source: ``builtin`` for TunaCode's native tools, or the
+name of whatever extension supplied them (``plugin`` for tool plugins, see
+``agent_components/tool_plugins.py``). Names must be unique in the merged
+list, so a collision is resolved by prefixing the tool with its source as
+``<source>__<tool>``. Built-in tools always keep their bare names; every other
+tool involved in a collision is prefixed, including the first one seen.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Mapping, Sequence
+from dataclasses import dataclass
+
+from tinyagent.agent_types import AgentTool, JsonObject
+
+from .agent_components.agent_tools import BUILTIN_TOOLS
+from .agent_components.tool_plugins import (
+    BUILTIN_TOOL_SOURCE,
+    NAMESPACE_SEPARATOR,
+    namespaced_tool_name,
+    plugin_tool_sources,
+    resolve_tool_names,
+)
+
+__all__ = [
+    "BUILTIN_TOOL_SOURCE",
+    "NAMESPACE_SEPARATOR",
+    "ToolInfo",
+    "list_tools",
+    "merge_tool_sources",
+    "namespaced_tool_name",
+]
+
+
+@dataclass(frozen=True, slots=True)
+class ToolInfo:
+    """One tool as the model sees it."""
+
+    name: str
+    tool_name: str
+    source: str
+    description: str
+    parameters: JsonObject
+
+    @property
+    def namespaced(self) -> bool:
+        return self.name != self.tool_name
+
+
+def merge_tool_sources(sources: Mapping[str, Sequence[AgentTool]]) -> list[ToolInfo]:
+    """Merge tools from every source into one list with unique names."""
+    return [
+        ToolInfo(
+            name=item.name,
+            tool_name=item.tool.name,
+            source=item.source,
+            description=item.tool.description or "",
+            parameters=dict(item.tool.parameters or {}),
+        )
+        for item in resolve_tool_names(sources)
+    ]
+
+
+def list_tools() -> list[ToolInfo]:
+    """Return every tool available to the agent with its description and schema."""
+    return merge_tool_sources({BUILTIN_TOOL_SOURCE: BUILTIN_TOOLS, **plugin_tool_sources()})
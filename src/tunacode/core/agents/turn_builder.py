@@ -0,0 +1,140 @@
+"""Fluent builder for submitting one user turn.
+
+``process_request()`` takes the message, the model and seven optional
+callbacks positionally or by keyword. ``TurnBuilder`` assembles the same call
+step by step and validates it before anything runs::
+
+    agent = await (
+        TurnBuilder(state_manager, model)
+        .text("explain this diff")
+        .model_override("openai:gpt-4.1-mini")
+        .on_text(streaming_callback)
+        .submit()
+    )
+
+``build()`` returns the validated ``TurnRequest`` without running it, and
+``process_request()`` stays available for callers that already have every
+argument at hand.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass, replace
+from typing import TYPE_CHECKING
+
+from tunacode.exceptions import ValidationError
+
+from tunacode.core.types.state import StateManagerProtocol
+
+from .main import process_request, resolve_turn_model
+
+if TYPE_CHECKING:
+    from tinyagent.agent import Agent
+
+    from tunacode.types import (
+        CompactionStatusCallback,
+        ModelName,
+        NoticeCallback,
+        StreamingCallback,
+        ToolProgressCallback,
+        ToolResultCallback,
+        ToolStartCallback,
+    )
+
+
+@dataclass(frozen=True, slots=True)
+class TurnRequest:
+    """A validated user turn: the arguments for one ``process_request()`` call."""
+
+    message: str
+    model: ModelName
+    model_override: ModelName | None = None
+    apply_affixes: bool = True
+    streaming_callback: StreamingCallback | None = None
+    thinking_callback: StreamingCallback | None = None
+    tool_result_callback: ToolResultCallback | None = None
+    tool_start_callback: ToolStartCallback | None = None
+    notice_callback: NoticeCallback | None = None
+    compaction_status_callback: CompactionStatusCallback | None = None
+    tool_progress_callback: ToolProgressCallback | None = None
+
+
+class TurnBuilder:
+    """Build and submit a user turn; every setter returns the builder."""
+
+    def __init__(self, state_manager: StateManagerProtocol, model: ModelName) -> None:
+        self._state_manager = state_manager
+        self._request = TurnRequest(message="", model=model)
+
+    def _set(self, **changes: object) -> TurnBuilder:
+        self._request = replace(self._request, **changes)  # type: ignore[arg-type]
+        return self
+
+    def text(self, message: str) -> TurnBuilder:
+        """Append ``message`` to the turn text; repeated calls join with a newline."""
+        if self._request.message:
+            message = f"{self._request.message}\n{message}"
+        return self._set(message=message)
+
+    def model_override(self, model: ModelName) -> TurnBuilder:
+        """Use ``model`` for this turn only; the session model is unchanged."""
+        return self._set(model_override=model)
+
+    def raw(self) -> TurnBuilder:
+        """Send the text as written, without the configured message prefix/suffix."""
+        return self._set(apply_affixes=False)
+
+    def on_text(self, callback: StreamingCallback) -> TurnBuilder:
+        return self._set(streaming_callback=callback)
+
+    def on_thinking(self, callback: StreamingCallback) -> TurnBuilder:
+        return self._set(thinking_callback=callback)
+
+    def on_tool_start(self, callback: ToolStartCallback) -> TurnBuilder:
+        return self._set(tool_start_callback=callback)
+
+    def on_tool_result(self, callback: ToolResultCallback) -> TurnBuilder:
+        return self._set(tool_result_callback=callback)
+
+    def on_tool_progress(self, callback: ToolProgressCallback) -> TurnBuilder:
+        return self._set(tool_progress_callback=callback)
+
+    def on_notice(self, callback: NoticeCallback) -> TurnBuilder:
+        return self._set(notice_callback=callback)
+
+    def on_compaction_status(self, callback: CompactionStatusCallback) -> TurnBuilder:
+        return self._set(compaction_status_callback=callback)
+
+    def build(self) -> TurnRequest:
+        """Validate and return the turn.
+
+        Raises ``ValidationError`` for an empty message and
+        ``ModelConfigurationError`` for an override that is neither configured
+        nor in the models registry.
+        """
+        request = self._request
+        if not request.message.strip():
+            raise ValidationError(
+                "A turn needs message text",
+                suggested_fix="Call .text(...) with a non-empty message before submitting",
+            )
+        resolve_turn_model(request.model, request.model_override, self._state_manager)
+        return request
+
+    async def submit(self) -> Agent:
+        """Validate the turn, then run it through ``process_request()``."""
+        request = self.build()
+        return await process_request(
+            request.message,
+            request.model,
+            self._state_manager,
+            streaming_callback=request.streaming_callback,
+            thinking_callback=request.thinking_callback,
+            tool_result_callback=request.tool_result_callback,
+            tool_start_callback=request.tool_start_callback,
+            notice_callback=request.notice_callback,
+            compaction_status_callback=request.compaction_status_callback,
+            model_override=request.model_override,
+            tool_progress_callback=request.tool_progress_callback,
+            apply_affixes=request.apply_affixes,
+        )
@@ -329,6 +329,7 @@ class CompactionController:
         previous_record = self._state_manager.session.compaction
         previous_summary = None if previous_record is None else previous_record.summary
         previous_count = 0 if previous_record is None else previous_record.compaction_count
+        overflow_retries = 0 if previous_record is None else previous_record.overflow_retry_count
 
         tokens_before = self._estimated_tokens(all_messages)
         retained_tokens = estimate_messages_tokens(retained_messages)
@@ -343,8 +344,16 @@ class CompactionController:
             compaction_count=previous_count + 1,
             previous_summary=previous_summary,
             last_compacted_at=datetime.now(UTC).isoformat(),
+            overflow_retry_count=overflow_retries,
         )
 
+    def record_overflow_retry(self) -> None:
+        """Count a compaction forced by a provider context-overflow error on the record."""
+
+        record = self._state_manager.session.compaction
+        if record is not None:
+            record.overflow_retry_count += 1
+
     async def _generate_summary(self, prompt: str, signal: asyncio.Event | None) -> str:
         model = self._build_model()
         api_key = self._resolve_api_key(model.provider)
@@ -359,7 +368,7 @@ class CompactionController:
             api_key=api_key,
             signal=signal,
             temperature=None,
-            max_tokens=get_max_tokens(),
+            max_tokens=get_max_tokens(self._state_manager.session.current_model),
         )
 
         response = await stream_alchemy_openai_completions(model, context, options)
@@ -464,11 +473,13 @@ def get_or_create_compaction_controller(
     """Return the session-scoped CompactionController instance."""
 
     session = state_manager.session
+    auto_compact = session.user_config["settings"]["auto_compact"]
     existing = session._compaction_controller
     if isinstance(existing, CompactionController):
+        existing.auto_compact = auto_compact
         return existing
 
-    controller = CompactionController(state_manager=state_manager)
+    controller = CompactionController(state_manager=state_manager, auto_compact=auto_compact)
     session._compaction_controller = controller
     return controller
 
@@ -0,0 +1,50 @@
+"""Sliding-window history, an alternative to summarization-based compaction.
+
+With ``settings.history.mode`` set to ``window`` the request context keeps only
+the last ``window_turns`` user turns and drops older ones without summarizing
+them. A turn starts at a user message and runs through the assistant replies
+and tool results that follow, so a window never splits a tool call from its
+result. The summary of an earlier ``/compact`` stays pinned in front of the
+window.
+
+Only the model-facing context is windowed: ``conversation.messages`` and the
+saved session keep every turn. Threshold compaction is off in this mode;
+``/compact`` and the context-overflow retry still summarize on request.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+from tinyagent.agent_types import AgentMessage, UserMessage
+
+from tunacode.constants import HistoryMode
+from tunacode.types import HistorySettings
+
+
+@dataclass(frozen=True, slots=True)
+class HistoryWindow:
+    mode: HistoryMode = HistoryMode.COMPACT
+    window_turns: int = 20
+
+    @classmethod
+    def from_settings(cls, settings: HistorySettings) -> HistoryWindow:
+        return cls(mode=HistoryMode(settings["mode"]), window_turns=settings["window_turns"])
+
+    @property
+    def enabled(self) -> bool:
+        return self.mode is HistoryMode.WINDOW
+
+    def apply(self, messages: list[AgentMessage]) -> list[AgentMessage]:
+        """Return the messages of the last ``window_turns`` turns."""
+        return window_messages(messages, self.window_turns)
+
+
+def window_messages(messages: list[AgentMessage], turns: int) -> list[AgentMessage]:
+    """Drop every turn before the last ``turns`` user turns."""
+    turn_starts = [
+        index for index, message in enumerate(messages) if isinstance(message, UserMessage)
+    ]
+    if len(turn_starts) <= turns:
+        return list(messages)
+    return list(messages[turn_starts[-turns] :])
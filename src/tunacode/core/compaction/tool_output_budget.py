@@ -0,0 +1,97 @@
+"""Keep request history inside the input budget left after reserving output room.
+
+The input budget is ``context_window - reserved_output - max_tokens``, where
+``reserved_output`` is ``settings.output_reserve_fraction`` of the window and
+``max_tokens`` is the configured response limit. Compaction summarizes old
+turns; this pass runs after it on every request context built by the agent's
+``transform_context`` hook and trims tool results, which are usually what
+overflows a single request; persisted history keeps the full outputs. Every
+tool result is capped at the same length and the cap is halved until the
+history fits, so the largest outputs shrink first and small ones stay whole.
+"""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import AgentMessage, TextContent, ToolResultMessage
+
+from tunacode.configuration.limits import get_max_tokens, get_output_reserve_fraction
+from tunacode.utils.messaging import estimate_messages_tokens, get_content
+
+MIN_TOOL_RESULT_CHARS = 512
+TRIM_MARKER_TEMPLATE = "\n\n[... {dropped} characters trimmed to fit the context window ...]"
+
+
+def max_input_tokens(
+    context_window: int,
+    *,
+    reserve_fraction: float,
+    max_output_tokens: int | None,
+) -> int:
+    """Tokens available for request input once output room is reserved."""
+    reserved_output = int(context_window * reserve_fraction)
+    return max(0, context_window - reserved_output - (max_output_tokens or 0))
+
+
+def _trim_text(text: str, cap: int) -> str:
+    if len(text) <= cap:
+        return text
+    return text[:cap] + TRIM_MARKER_TEMPLATE.format(dropped=len(text) - cap)
+
+
+def _cap_tool_results(messages: list[AgentMessage], cap: int) -> list[AgentMessage]:
+    capped: list[AgentMessage] = []
+    for message in messages:
+        if isinstance(message, ToolResultMessage):
+            text = get_content(message)
+            if len(text) > cap:
+                message = message.model_copy(
+                    update={"content": [TextContent(text=_trim_text(text, cap))]}
+                )
+        capped.append(message)
+    return capped
+
+
+def fit_tool_results_to_budget(
+    messages: list[AgentMessage],
+    input_budget: int,
+) -> list[AgentMessage]:
+    """Return ``messages`` with tool results trimmed until they fit ``input_budget``.
+
+    Returns the input list unchanged when it already fits. Trimming stops at
+    ``MIN_TOOL_RESULT_CHARS`` per result, so the history can still exceed the
+    budget when the overflow is not in tool output.
+    """
+    if input_budget <= 0 or estimate_messages_tokens(messages) <= input_budget:
+        return messages
+
+    lengths = [
+        len(get_content(message)) for message in messages if isinstance(message, ToolResultMessage)
+    ]
+    if not lengths:
+        return messages
+
+    cap = max(lengths)
+    fitted = messages
+    while cap > MIN_TOOL_RESULT_CHARS:
+        cap = max(MIN_TOOL_RESULT_CHARS, cap // 2)
+        fitted = _cap_tool_results(messages, cap)
+        if estimate_messages_tokens(fitted) <= input_budget:
+            break
+    return fitted
+
+
+def fit_request_to_context_window(
+    messages: list[AgentMessage],
+    context_window: int,
+    *,
+    model: str | None = None,
+) -> list[AgentMessage]:
+    """Trim tool results in a request context to the configured input budget."""
+    if context_window <= 0:
+        return messages
+    budget = max_input_tokens(
+        context_window,
+        reserve_fraction=get_output_reserve_fraction(),
+        max_output_tokens=get_max_tokens(model),
+    )
+    return fit_tool_results_to_budget(messages, budget)
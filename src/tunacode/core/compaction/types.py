@@ -14,6 +14,7 @@ KEY_TOKENS_AFTER = "tokens_after"
 KEY_COMPACTION_COUNT = "compaction_count"
 KEY_PREVIOUS_SUMMARY = "previous_summary"
 KEY_LAST_COMPACTED_AT = "last_compacted_at"
+KEY_OVERFLOW_RETRY_COUNT = "overflow_retry_count"
 
 CompactionStatus: TypeAlias = Literal[
     "compacted",
@@ -71,6 +72,7 @@ class CompactionRecord:
     compaction_count: int
     previous_summary: str | None
     last_compacted_at: str
+    overflow_retry_count: int = 0
 
     def to_dict(self) -> dict[str, Any]:
         """Serialize the record to a JSON-friendly dictionary."""
@@ -83,6 +85,7 @@ class CompactionRecord:
             KEY_COMPACTION_COUNT: self.compaction_count,
             KEY_PREVIOUS_SUMMARY: self.previous_summary,
             KEY_LAST_COMPACTED_AT: self.last_compacted_at,
+            KEY_OVERFLOW_RETRY_COUNT: self.overflow_retry_count,
         }
 
     @classmethod
@@ -121,6 +124,11 @@ class CompactionRecord:
             field_name=KEY_LAST_COMPACTED_AT,
         )
 
+        overflow_retry_count = _coerce_non_negative_int(
+            data.get(KEY_OVERFLOW_RETRY_COUNT, 0),
+            field_name=KEY_OVERFLOW_RETRY_COUNT,
+        )
+
         return cls(
             summary=summary,
             compacted_message_count=compacted_message_count,
@@ -129,6 +137,7 @@ class CompactionRecord:
             compaction_count=compaction_count,
             previous_summary=previous_summary,
             last_compacted_at=last_compacted_at,
+            overflow_retry_count=overflow_retry_count,
         )
 
 
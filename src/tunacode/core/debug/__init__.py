@@ -1,5 +1,9 @@
 """Debug helpers for runtime instrumentation."""
 
+from tunacode.core.debug.raw_responses import (  # noqa: F401
+    RawResponseLog,
+    RawResponseRecord,
+)
 from tunacode.core.debug.usage_trace import (  # noqa: F401
     build_resource_bar_lifecycle_message,
     build_usage_lifecycle_message,
@@ -0,0 +1,89 @@
+"""Raw provider responses retained for debugging a turn.
+
+With ``settings.retain_raw_responses`` on, every provider call records the
+stream events it produced and the final assistant message as JSON-ready
+dicts. Records are numbered per session in call order and only the newest
+``RAW_RESPONSE_LOG_LIMIT`` are kept, so a long session does not grow without
+bound. ``/debug raw [index]`` shows a record.
+
+Only what the provider sent back is recorded: request options, headers and
+API keys never reach the log. Event ``partial`` snapshots are dropped because
+they repeat the accumulated message on every delta.
+"""
+
+from __future__ import annotations
+
+from collections import deque
+from dataclasses import dataclass, field
+from typing import Any
+
+RAW_RESPONSE_LOG_LIMIT = 20
+DROPPED_EVENT_KEYS = frozenset({"partial"})
+
+
+@dataclass(frozen=True, slots=True)
+class RawResponseRecord:
+    index: int
+    request_id: str
+    model: str
+    events: list[dict[str, Any]]
+    response: dict[str, Any]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "index": self.index,
+            "request_id": self.request_id,
+            "model": self.model,
+            "events": self.events,
+            "response": self.response,
+        }
+
+
+def event_payload(event: Any) -> dict[str, Any]:
+    """Return ``event`` as a dict without its bulky ``partial`` snapshot."""
+    payload = event.model_dump(mode="json", exclude_none=True)
+    return {key: value for key, value in payload.items() if key not in DROPPED_EVENT_KEYS}
+
+
+@dataclass(slots=True)
+class RawResponseLog:
+    """Bounded, call-ordered log of raw provider responses."""
+
+    limit: int = RAW_RESPONSE_LOG_LIMIT
+    next_index: int = 1
+    _records: deque[RawResponseRecord] = field(default_factory=deque)
+
+    def record(
+        self,
+        *,
+        request_id: str,
+        model: str,
+        events: list[dict[str, Any]],
+        response: dict[str, Any],
+    ) -> RawResponseRecord:
+        entry = RawResponseRecord(
+            index=self.next_index,
+            request_id=request_id,
+            model=model,
+            events=events,
+            response=response,
+        )
+        self.next_index += 1
+        self._records.append(entry)
+        while len(self._records) > self.limit:
+            self._records.popleft()
+        return entry
+
+    def get(self, index: int | None = None) -> RawResponseRecord | None:
+        """Return the record numbered ``index``, or the newest when omitted."""
+        if not self._records:
+            return None
+        if index is None:
+            return self._records[-1]
+        for entry in self._records:
+            if entry.index == index:
+                return entry
+        return None
+
+    def entries(self) -> list[RawResponseRecord]:
+        return list(self._records)
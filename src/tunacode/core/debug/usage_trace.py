@@ -34,6 +34,7 @@ def build_usage_lifecycle_message(
         f"cache_read={last_call_usage.cache_read},"
         f"cache_write={last_call_usage.cache_write},"
         f"total={last_call_usage.total_tokens},"
+        f"reasoning={last_call_usage.reasoning},"
         f"cost={last_call_usage.cost.total:.{COST_PRECISION}f}"
         ") "
         "session_total("
@@ -42,6 +43,7 @@ def build_usage_lifecycle_message(
         f"cache_read={session_total_usage.cache_read},"
         f"cache_write={session_total_usage.cache_write},"
         f"total={session_total_usage.total_tokens},"
+        f"reasoning={session_total_usage.reasoning},"
         f"cost={session_total_usage.cost.total:.{COST_PRECISION}f}"
         ")"
     )
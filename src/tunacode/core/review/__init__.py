@@ -0,0 +1 @@
+"""Agent code review of git changes."""
@@ -0,0 +1,97 @@
+"""Review exactly what is about to be committed.
+
+``run_staged_review()`` reads ``git diff --cached``, parses it, and sends the
+agent ``REVIEW_PROMPT`` plus the staged hunks rendered with ``path:line``
+prefixes, so findings can cite new-file line numbers. The run is forced into
+safe mode: a review reads code, it never edits it. ``review_format()`` frames
+the answer with the reviewed files. An empty staging area returns
+``EMPTY_STAGING_MESSAGE`` without calling the model.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from pathlib import Path
+
+from tinyagent.agent_types import AssistantMessage, TextContent
+
+from tunacode.types import ModelName
+from tunacode.utils.system.git_diff import (
+    FileDiff,
+    format_numbered_diff,
+    parse_git_diff,
+    read_git_diff,
+)
+
+from tunacode.core.agents.main import process_request
+from tunacode.core.types.state import StateManagerProtocol
+
+EMPTY_STAGING_MESSAGE = "Nothing is staged to review. Stage changes with `git add` and try again."
+
+REVIEW_PROMPT = """\
+Review the staged changes below before they are committed.
+
+Report only problems introduced or exposed by these changes: bugs, missing error
+handling, security issues, broken tests, and unclear code. For every finding,
+cite the location as `path:line` using the new-file line numbers shown, give a
+severity (high, medium, low), and suggest a fix. Do not edit files. If the
+changes look correct, say so briefly.
+
+Staged changes:
+"""
+
+
+@dataclass(frozen=True, slots=True)
+class ReviewTarget:
+    files: list[FileDiff]
+    prompt: str
+
+
+def build_review_prompt(files: list[FileDiff]) -> str:
+    """Return ``REVIEW_PROMPT`` followed by the numbered hunks of ``files``."""
+    return f"{REVIEW_PROMPT}\n{format_numbered_diff(files)}\n"
+
+
+def collect_staged_review(*, cwd: Path | None = None) -> ReviewTarget | None:
+    """Return the staged review target, or None when nothing is staged."""
+    files = parse_git_diff(read_git_diff(staged=True, cwd=cwd))
+    if not files:
+        return None
+    return ReviewTarget(files=files, prompt=build_review_prompt(files))
+
+
+def review_format(review_text: str, files: list[FileDiff]) -> str:
+    """Frame ``review_text`` with the files and line counts it covers."""
+    summary = [
+        f"- {file_diff.path} ({file_diff.status}, "
+        f"+{len(file_diff.added_lines())}/-{len(file_diff.removed_lines())})"
+        for file_diff in files
+    ]
+    header = f"Review of {len(files)} staged file(s):"
+    return "\n".join([header, *summary, "", review_text.strip()])
+
+
+def _latest_answer_text(state_manager: StateManagerProtocol) -> str:
+    for message in reversed(state_manager.session.conversation.messages):
+        if isinstance(message, AssistantMessage):
+            return "".join(
+                item.text
+                for item in message.content
+                if isinstance(item, TextContent) and isinstance(item.text, str)
+            )
+    return ""
+
+
+async def run_staged_review(
+    state_manager: StateManagerProtocol,
+    model: ModelName,
+    *,
+    cwd: Path | None = None,
+) -> str:
+    """Review the staged diff with the agent and return the formatted review."""
+    target = collect_staged_review(cwd=cwd)
+    if target is None:
+        return EMPTY_STAGING_MESSAGE
+    state_manager.session.user_config["settings"]["safe_mode"] = True
+    await process_request(target.prompt, model, state_manager)
+    return review_format(_latest_answer_text(state_manager), target.files)
@@ -0,0 +1,142 @@
+"""Append-only audit log of risky shell commands the command policy let through.
+
+Every bash call classified above ``read_only`` that ``settings.command_policy``
+allows is one recorded decision: timestamp, session id, risk tier and the
+command, with secrets redacted before anything touches disk. Entries live in
+``~/.tunacode/audit/command_overrides.jsonl``, one JSON object per line.
+
+Each entry stores the hash of the previous entry and a SHA-256 over its own
+fields plus that hash, so editing, deleting or reordering lines breaks the
+chain from that point on. ``verify_audit_log()`` reports the first break. This
+is tamper-evident, not tamper-proof: someone who rewrites the whole file can
+rebuild the chain.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import re
+from dataclasses import asdict, dataclass
+from datetime import UTC, datetime
+from pathlib import Path
+
+from tunacode.configuration.paths import get_tunacode_home
+from tunacode.constants import CommandRisk
+
+AUDIT_SUBDIR = "audit"
+AUDIT_LOG_FILENAME = "command_overrides.jsonl"
+GENESIS_HASH = "0" * 64
+REDACTED = "[REDACTED]"
+
+SECRET_PATTERNS: tuple[tuple[re.Pattern[str], str], ...] = (
+    (
+        re.compile(r"\b([A-Za-z0-9_]*(?:KEY|TOKEN|SECRET|PASSWORD|PASSWD)[A-Za-z0-9_]*)=\S+"),
+        rf"\1={REDACTED}",
+    ),
+    (
+        re.compile(r"(?i)(--?(?:password|passwd|token|api-key|secret)[= ])\S+"),
+        rf"\1{REDACTED}",
+    ),
+    (re.compile(r"(?i)(authorization:\s*(?:bearer|basic|token)\s+)[^\s'\"]+"), rf"\1{REDACTED}"),
+    (re.compile(r"(://[^/\s:@]+:)[^@\s/]+@"), rf"\1{REDACTED}@"),
+    (re.compile(r"\b(?:sk-|ghp_|gho_|github_pat_|xox[abp]-|AKIA)[A-Za-z0-9_\-]{8,}"), REDACTED),
+)
+
+
+@dataclass(frozen=True, slots=True)
+class AuditEntry:
+    timestamp: str
+    session_id: str
+    risk: str
+    command: str
+    prev_hash: str
+    entry_hash: str
+
+
+def redact_secrets(text: str) -> str:
+    """Replace credentials that commonly appear in shell commands with a marker."""
+    for pattern, replacement in SECRET_PATTERNS:
+        text = pattern.sub(replacement, text)
+    return text
+
+
+def get_audit_log_path() -> Path:
+    """Return the audit log path under the TunaCode home directory."""
+    audit_dir = get_tunacode_home() / AUDIT_SUBDIR
+    audit_dir.mkdir(mode=0o700, exist_ok=True)
+    return audit_dir / AUDIT_LOG_FILENAME
+
+
+def _entry_digest(
+    *,
+    prev_hash: str,
+    timestamp: str,
+    session_id: str,
+    risk: str,
+    command: str,
+) -> str:
+    payload = json.dumps([prev_hash, timestamp, session_id, risk, command], ensure_ascii=False)
+    return hashlib.sha256(payload.encode("utf-8")).hexdigest()
+
+
+def _read_entries(path: Path) -> list[AuditEntry]:
+    if not path.exists():
+        return []
+    lines = path.read_text(encoding="utf-8").splitlines()
+    return [AuditEntry(**json.loads(line)) for line in lines if line.strip()]
+
+
+def record_command_override(
+    *,
+    session_id: str,
+    command: str,
+    risk: CommandRisk,
+    path: Path | None = None,
+) -> AuditEntry:
+    """Append one approval decision to the audit log and return the stored entry."""
+    log_path = path or get_audit_log_path()
+    entries = _read_entries(log_path)
+    prev_hash = entries[-1].entry_hash if entries else GENESIS_HASH
+    timestamp = datetime.now(UTC).isoformat()
+    safe_command = redact_secrets(command)
+    entry = AuditEntry(
+        timestamp=timestamp,
+        session_id=session_id,
+        risk=risk.value,
+        command=safe_command,
+        prev_hash=prev_hash,
+        entry_hash=_entry_digest(
+            prev_hash=prev_hash,
+            timestamp=timestamp,
+            session_id=session_id,
+            risk=risk.value,
+            command=safe_command,
+        ),
+    )
+    with log_path.open("a", encoding="utf-8") as handle:
+        handle.write(json.dumps(asdict(entry), ensure_ascii=False) + "\n")
+    return entry
+
+
+def list_audit_entries(session_id: str, *, path: Path | None = None) -> list[AuditEntry]:
+    """Return the audit entries recorded for ``session_id``, oldest first."""
+    entries = _read_entries(path or get_audit_log_path())
+    return [entry for entry in entries if entry.session_id == session_id]
+
+
+def verify_audit_log(*, path: Path | None = None) -> int | None:
+    """Return the index of the first entry whose hash chain is broken, or None if intact."""
+    prev_hash = GENESIS_HASH
+    for index, entry in enumerate(_read_entries(path or get_audit_log_path())):
+        expected = _entry_digest(
+            prev_hash=prev_hash,
+            timestamp=entry.timestamp,
+            session_id=entry.session_id,
+            risk=entry.risk,
+            command=entry.command,
+        )
+        if entry.prev_hash != prev_hash or entry.entry_hash != expected:
+            return index
+        prev_hash = entry.entry_hash
+    return None
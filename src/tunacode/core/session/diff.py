@@ -0,0 +1,142 @@
+"""Structured diff between two persisted sessions.
+
+Each session is reduced to a sequence of semantic events -- user prompts,
+tool calls (name + arguments), tool results (name, error flag, output), and
+assistant answers. Timestamps, usage, and provider metadata are ignored, so two
+runs of the same prompt compare equal unless their behavior differs. The event
+sequences are aligned with ``difflib.SequenceMatcher``; the first non-matching
+block is the divergence point.
+"""
+
+from __future__ import annotations
+
+import difflib
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Literal
+
+from tunacode.core.session.migrations import migrate_session_file
+
+EventKind = Literal["user", "tool_call", "tool_result", "answer"]
+
+
+@dataclass(frozen=True, slots=True)
+class SessionEvent:
+    """One comparable step of a session."""
+
+    kind: EventKind
+    summary: str
+
+
+@dataclass(frozen=True, slots=True)
+class EventDifference:
+    """A run of events that differs between the sessions, with its start indexes.
+
+    ``tag`` is a ``SequenceMatcher`` opcode: ``replace``, ``delete``, or ``insert``.
+    """
+
+    tag: str
+    index_a: int
+    index_b: int
+    events_a: list[SessionEvent]
+    events_b: list[SessionEvent]
+
+
+@dataclass(slots=True)
+class SessionDiff:
+    events_a: list[SessionEvent]
+    events_b: list[SessionEvent]
+    differences: list[EventDifference] = field(default_factory=list)
+
+    @property
+    def first_divergence(self) -> EventDifference | None:
+        return self.differences[0] if self.differences else None
+
+    @property
+    def final_answer_a(self) -> str | None:
+        return _final_answer(self.events_a)
+
+    @property
+    def final_answer_b(self) -> str | None:
+        return _final_answer(self.events_b)
+
+
+def _final_answer(events: list[SessionEvent]) -> str | None:
+    for event in reversed(events):
+        if event.kind == "answer":
+            return event.summary
+    return None
+
+
+def _content_text(content: Any) -> str:
+    if isinstance(content, str):
+        return content.strip()
+    if not isinstance(content, list):
+        return ""
+    parts: list[str] = []
+    for item in content:
+        if isinstance(item, dict) and item.get("type") == "text":
+            text = item.get("text")
+            if isinstance(text, str):
+                parts.append(text)
+    return "".join(parts).strip()
+
+
+def _message_events(message: Any) -> list[SessionEvent]:
+    if not isinstance(message, dict):
+        return []
+    role = message.get("role")
+    content = message.get("content")
+    if role == "user":
+        return [SessionEvent("user", _content_text(content))]
+    if role == "tool_result":
+        status = "error" if message.get("is_error") else "ok"
+        output = _content_text(content)
+        return [SessionEvent("tool_result", f"{message.get('tool_name')} [{status}] {output}")]
+    if role != "assistant":
+        return []
+
+    events: list[SessionEvent] = []
+    text = _content_text(content)
+    if text:
+        events.append(SessionEvent("answer", text))
+    for item in content if isinstance(content, list) else []:
+        if isinstance(item, dict) and item.get("type") == "tool_call":
+            arguments = json.dumps(item.get("arguments", {}), sort_keys=True)
+            events.append(SessionEvent("tool_call", f"{item.get('name')}({arguments})"))
+    return events
+
+
+def session_events(data: dict[str, Any]) -> list[SessionEvent]:
+    """Reduce persisted session data to its comparable event sequence."""
+    messages = data.get("messages") or []
+    return [event for message in messages for event in _message_events(message)]
+
+
+def diff_session_data(data_a: dict[str, Any], data_b: dict[str, Any]) -> SessionDiff:
+    events_a = session_events(data_a)
+    events_b = session_events(data_b)
+    result = SessionDiff(events_a=events_a, events_b=events_b)
+    matcher = difflib.SequenceMatcher(a=events_a, b=events_b, autojunk=False)
+    for tag, a_start, a_end, b_start, b_end in matcher.get_opcodes():
+        if tag == "equal":
+            continue
+        result.differences.append(
+            EventDifference(
+                tag=tag,
+                index_a=a_start,
+                index_b=b_start,
+                events_a=events_a[a_start:a_end],
+                events_b=events_b[b_start:b_end],
+            )
+        )
+    return result
+
+
+def diff_session_files(session_file_a: Path, session_file_b: Path) -> SessionDiff:
+    """Diff two session files, migrating either to the current schema first."""
+    return diff_session_data(
+        migrate_session_file(session_file_a),
+        migrate_session_file(session_file_b),
+    )
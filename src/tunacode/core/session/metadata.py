@@ -0,0 +1,128 @@
+"""User-defined metadata and tags for organizing saved sessions.
+
+A session carries free-form ``key=value`` metadata (``project=foo``,
+``ticket=JIRA-123``) and a set of tags. Both live on ``SessionState`` and are
+saved in the session file under ``"metadata"``; a file written before this
+existed has no such key and loads, and lists, with empty metadata.
+``SessionFilter`` selects sessions by tag and metadata for
+``StateManager.list_sessions()``.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Iterable, Mapping, Sequence
+from dataclasses import dataclass, field
+from typing import Any
+
+VALUES_KEY = "values"
+TAGS_KEY = "tags"
+TAG_FILTER_PREFIX = "tag:"
+HAS_KEY_FILTER_PREFIX = "has:"
+
+
+class SessionMetadataError(ValueError):
+    """Raised for an empty or whitespace-containing metadata key or tag."""
+
+
+def _check_token(token: str, *, what: str) -> str:
+    if not token or any(character.isspace() for character in token):
+        raise SessionMetadataError(
+            f"Session {what} must be non-empty without whitespace, got {token!r}."
+        )
+    return token
+
+
+@dataclass(slots=True)
+class SessionMetadata:
+    """Key-value metadata and tags, kept in insertion order."""
+
+    values: dict[str, str] = field(default_factory=dict)
+    tags: list[str] = field(default_factory=list)
+
+    def update(
+        self,
+        values: Mapping[str, str | None] | None = None,
+        *,
+        add_tags: Iterable[str] = (),
+        remove_tags: Iterable[str] = (),
+    ) -> SessionMetadata:
+        """Set metadata (a ``None`` value removes the key) and add or remove tags.
+
+        Every key and tag is validated before anything changes, so a rejected
+        update leaves the metadata as it was.
+        """
+        updates = dict(values or {})
+        added = [_check_token(tag, what="tag") for tag in add_tags]
+        removed = set(remove_tags)
+        for key, value in updates.items():
+            _check_token(key, what="metadata key")
+            if value is not None and not isinstance(value, str):
+                raise SessionMetadataError(f"Session metadata '{key}' must be a string.")
+
+        for key, value in updates.items():
+            if value is None:
+                self.values.pop(key, None)
+            else:
+                self.values[key] = value
+        self.tags = [tag for tag in self.tags if tag not in removed]
+        self.tags.extend(tag for tag in dict.fromkeys(added) if tag not in self.tags)
+        return self
+
+    def is_empty(self) -> bool:
+        return not self.values and not self.tags
+
+    def to_dict(self) -> dict[str, Any]:
+        return {VALUES_KEY: dict(self.values), TAGS_KEY: list(self.tags)}
+
+    @classmethod
+    def from_dict(cls, data: Any) -> SessionMetadata:
+        if data is None:
+            return cls()
+        if not isinstance(data, dict):
+            raise TypeError(f"Session 'metadata' must be an object, got {type(data).__name__}")
+        values = data.get(VALUES_KEY) or {}
+        tags = data.get(TAGS_KEY) or []
+        if not isinstance(values, dict) or not all(
+            isinstance(value, str) for value in values.values()
+        ):
+            raise TypeError("Session metadata values must be an object of strings")
+        if not isinstance(tags, list) or not all(isinstance(tag, str) for tag in tags):
+            raise TypeError("Session metadata tags must be a list of strings")
+        return cls(values={str(key): value for key, value in values.items()}, tags=tags)
+
+
+@dataclass(frozen=True, slots=True)
+class SessionFilter:
+    """Sessions that carry every tag, every key, and every ``key=value`` given."""
+
+    tags: tuple[str, ...] = ()
+    has_keys: tuple[str, ...] = ()
+    values: tuple[tuple[str, str], ...] = ()
+
+    def matches(self, metadata: SessionMetadata) -> bool:
+        return (
+            all(tag in metadata.tags for tag in self.tags)
+            and all(key in metadata.values for key in self.has_keys)
+            and all(metadata.values.get(key) == value for key, value in self.values)
+        )
+
+
+def parse_session_filter(terms: Sequence[str]) -> SessionFilter:
+    """Build a filter from ``tag:<tag>``, ``has:<key>`` and ``<key>=<value>`` terms."""
+    tags: list[str] = []
+    has_keys: list[str] = []
+    values: list[tuple[str, str]] = []
+    for term in terms:
+        if term.startswith(TAG_FILTER_PREFIX):
+            tags.append(_check_token(term.removeprefix(TAG_FILTER_PREFIX), what="tag"))
+        elif term.startswith(HAS_KEY_FILTER_PREFIX):
+            key = term.removeprefix(HAS_KEY_FILTER_PREFIX)
+            has_keys.append(_check_token(key, what="metadata key"))
+        elif "=" in term:
+            key, value = term.split("=", 1)
+            values.append((_check_token(key, what="metadata key"), value))
+        else:
+            raise SessionMetadataError(
+                f"Unknown session filter {term!r}; use tag:<tag>, has:<key> or <key>=<value>."
+            )
+    return SessionFilter(tags=tuple(tags), has_keys=tuple(has_keys), values=tuple(values))
@@ -0,0 +1,122 @@
+"""Session file schema migrations.
+
+Each entry in ``SESSION_MIGRATIONS`` upgrades a session dict from one version
+to the next, so a file at any older version is brought current by chaining
+steps (v0 -> v1 -> v2). Files written before the ``version`` key existed are
+treated as version 0. Migrating a current file is a no-op, and a file is only
+rewritten after every step succeeded, so a failure leaves it untouched.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import secrets
+from collections.abc import Callable
+from pathlib import Path
+from typing import Any
+
+from tunacode.exceptions import SessionMigrationError
+
+SessionData = dict[str, Any]
+SessionMigration = Callable[[SessionData], SessionData]
+
+LEGACY_SESSION_VERSION = 0
+VERSION_KEY = "version"
+BACKUP_SUFFIX_TEMPLATE = ".v{version}.bak"
+TEMP_SUFFIX = ".migrating"
+
+
+def _stamp_version(data: SessionData) -> SessionData:
+    """v0 -> v1: pre-version files share the v1 layout; only the version is added."""
+    return data
+
+
+def _move_thought_entries(data: SessionData) -> SessionData:
+    """v1 -> v2: move legacy ``{"thought": ...}`` entries out of ``messages``."""
+    messages = data.get("messages") or []
+    if not isinstance(messages, list):
+        raise TypeError(f"Session 'messages' must be a list, got {type(messages).__name__}")
+
+    thoughts = list(data.get("thoughts") or [])
+    cleaned_messages: list[Any] = []
+    for message in messages:
+        if isinstance(message, dict) and "thought" in message:
+            thought_value = message.get("thought")
+            if thought_value is not None:
+                thoughts.append(str(thought_value))
+            continue
+        cleaned_messages.append(message)
+
+    data["messages"] = cleaned_messages
+    data["thoughts"] = thoughts
+    return data
+
+
+SESSION_MIGRATIONS: dict[int, SessionMigration] = {
+    0: _stamp_version,
+    1: _move_thought_entries,
+}
+CURRENT_SESSION_VERSION = max(SESSION_MIGRATIONS) + 1
+
+
+def session_version(data: SessionData) -> int:
+    version = data.get(VERSION_KEY, LEGACY_SESSION_VERSION)
+    if isinstance(version, bool) or not isinstance(version, int):
+        raise SessionMigrationError(f"version must be an integer, got {version!r}")
+    return version
+
+
+def migrate_session_data(data: SessionData) -> SessionData:
+    """Return ``data`` upgraded to ``CURRENT_SESSION_VERSION``; the input is not mutated."""
+    version = session_version(data)
+    if version > CURRENT_SESSION_VERSION:
+        raise SessionMigrationError(
+            f"version {version} is newer than supported version {CURRENT_SESSION_VERSION}"
+        )
+
+    migrated = json.loads(json.dumps(data))
+    while version < CURRENT_SESSION_VERSION:
+        step = SESSION_MIGRATIONS.get(version)
+        if step is None:
+            raise SessionMigrationError(f"no migration registered from version {version}")
+        try:
+            migrated = step(migrated)
+        except (KeyError, TypeError, ValueError) as exc:
+            raise SessionMigrationError(f"migration from version {version} failed: {exc}") from exc
+        version += 1
+        migrated[VERSION_KEY] = version
+    return migrated
+
+
+def migrate_session_file(session_file: Path) -> SessionData:
+    """Load ``session_file`` at the current version, upgrading it on disk if older.
+
+    The original bytes are kept next to the file as ``<name>.v<old>.bak`` before
+    the upgraded session atomically replaces it.
+    """
+    original = session_file.read_bytes()
+    data = json.loads(original)
+    if not isinstance(data, dict):
+        raise SessionMigrationError(
+            f"session file must contain an object, got {type(data).__name__}"
+        )
+
+    version = session_version(data)
+    migrated = migrate_session_data(data)
+    if version == CURRENT_SESSION_VERSION:
+        return migrated
+
+    backup = session_file.with_name(
+        session_file.name + BACKUP_SUFFIX_TEMPLATE.format(version=version)
+    )
+    if not backup.exists():
+        backup.write_bytes(original)
+
+    temp_file = session_file.with_name(f".{session_file.name}.{secrets.token_hex(4)}{TEMP_SUFFIX}")
+    try:
+        temp_file.write_text(json.dumps(migrated, indent=2), encoding="utf-8")
+        os.replace(temp_file, session_file)
+    finally:
+        temp_file.unlink(missing_ok=True)
+    return migrated
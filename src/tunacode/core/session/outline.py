@@ -0,0 +1,172 @@
+"""Condensed, one-line-per-turn outline of the conversation.
+
+A turn starts at each user message and runs until the next one. Its
+``TurnSummary`` is derived mechanically from the messages -- no model call --
+and holds the request snippet, the tools called, the files written
+successfully, and the outcome. ``first_message``/``end_message`` are the
+turn's slice of ``conversation.messages`` and ``turn_id`` is its position, so
+an outline entry links back to the full events; both stay stable while
+history only grows (compaction and ``/clear`` renumber the turns).
+
+``ConversationOutline.update()`` is cheap to call after every turn: finished
+turns are kept and only the last turn, which may still be running, is
+summarized again from the messages appended since.
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+from dataclasses import dataclass, field
+from typing import Literal
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.constants import ToolName
+
+TurnOutcome = Literal["answered", "error", "aborted", "pending"]
+
+FILE_WRITING_TOOLS = frozenset({ToolName.WRITE_FILE, ToolName.HASHLINE_EDIT})
+FILEPATH_ARGUMENT = "filepath"
+SNIPPET_MAX_CHARS = 80
+SNIPPET_ELLIPSIS = "..."
+
+
+@dataclass(frozen=True, slots=True)
+class TurnSummary:
+    turn_id: int
+    first_message: int
+    end_message: int
+    request: str
+    tools: tuple[str, ...]
+    files_changed: tuple[str, ...]
+    tool_errors: int
+    outcome: TurnOutcome
+
+    def render_line(self) -> str:
+        parts = [f"#{self.turn_id + 1} {self.request}"]
+        if self.tools:
+            parts.append(f"tools: {', '.join(self.tools)}")
+        if self.files_changed:
+            parts.append(f"files: {', '.join(self.files_changed)}")
+        parts.append(self.outcome)
+        return " | ".join(parts)
+
+
+def _text(message: UserMessage | AssistantMessage) -> str:
+    return "".join(item.text for item in message.content if isinstance(item, TextContent))
+
+
+def request_snippet(text: str, max_chars: int = SNIPPET_MAX_CHARS) -> str:
+    first_line = next((line.strip() for line in text.splitlines() if line.strip()), "")
+    if len(first_line) <= max_chars:
+        return first_line
+    return first_line[: max_chars - len(SNIPPET_ELLIPSIS)].rstrip() + SNIPPET_ELLIPSIS
+
+
+def _outcome(messages: Sequence[AgentMessage]) -> TurnOutcome:
+    last = messages[-1] if messages else None
+    if not isinstance(last, AssistantMessage):
+        return "pending"
+    if last.stop_reason == "error":
+        return "error"
+    if last.stop_reason == "aborted":
+        return "aborted"
+    has_tool_calls = any(isinstance(item, ToolCallContent) for item in last.content)
+    if has_tool_calls or not _text(last).strip():
+        return "pending"
+    return "answered"
+
+
+def summarize_turn(
+    turn_id: int, first_message: int, messages: Sequence[AgentMessage]
+) -> TurnSummary:
+    """Summarize one turn; ``messages[0]`` is its user message."""
+    request = messages[0]
+    tools: list[str] = []
+    written: dict[str, str] = {}
+    failed_call_ids: set[str] = set()
+    for message in messages:
+        if isinstance(message, ToolResultMessage) and message.is_error:
+            failed_call_ids.add(message.tool_call_id)
+        if not isinstance(message, AssistantMessage):
+            continue
+        for item in message.content:
+            if not isinstance(item, ToolCallContent):
+                continue
+            if item.name not in tools:
+                tools.append(item.name)
+            path = item.arguments.get(FILEPATH_ARGUMENT)
+            if item.name in FILE_WRITING_TOOLS and isinstance(path, str):
+                written[item.id] = path
+    files = [path for call_id, path in written.items() if call_id not in failed_call_ids]
+    return TurnSummary(
+        turn_id=turn_id,
+        first_message=first_message,
+        end_message=first_message + len(messages),
+        request=request_snippet(_text(request)) if isinstance(request, UserMessage) else "",
+        tools=tuple(tools),
+        files_changed=tuple(dict.fromkeys(files)),
+        tool_errors=len(failed_call_ids),
+        outcome=_outcome(messages),
+    )
+
+
+def _turn_starts(messages: Sequence[AgentMessage], start: int) -> list[int]:
+    return [
+        index
+        for index in range(start, len(messages))
+        if isinstance(messages[index], UserMessage)
+    ]
+
+
+def summarize_turns(messages: Sequence[AgentMessage]) -> list[TurnSummary]:
+    """Summarize every turn; messages before the first user message are skipped."""
+    return _summarize_from(messages, _turn_starts(messages, 0), first_turn_id=0)
+
+
+def _summarize_from(
+    messages: Sequence[AgentMessage], starts: list[int], *, first_turn_id: int
+) -> list[TurnSummary]:
+    ends = [*starts[1:], len(messages)]
+    return [
+        summarize_turn(first_turn_id + offset, start, messages[start:end])
+        for offset, (start, end) in enumerate(zip(starts, ends, strict=True))
+    ]
+
+
+@dataclass(slots=True)
+class ConversationOutline:
+    """Turn summaries kept up to date incrementally as messages are appended."""
+
+    _turns: list[TurnSummary] = field(default_factory=list)
+    _last_turn_head: AgentMessage | None = None
+
+    @property
+    def turns(self) -> list[TurnSummary]:
+        return list(self._turns)
+
+    def update(self, messages: Sequence[AgentMessage]) -> list[TurnSummary]:
+        """Resummarize from the last known turn on, or everything if history was rewritten."""
+        if not self._history_extends(messages):
+            self._turns = summarize_turns(messages)
+        else:
+            last = self._turns.pop()
+            starts = _turn_starts(messages, last.first_message)
+            self._turns.extend(_summarize_from(messages, starts, first_turn_id=last.turn_id))
+        self._last_turn_head = messages[self._turns[-1].first_message] if self._turns else None
+        return self.turns
+
+    def _history_extends(self, messages: Sequence[AgentMessage]) -> bool:
+        if not self._turns:
+            return False
+        head_index = self._turns[-1].first_message
+        if head_index >= len(messages) or len(messages) < self._turns[-1].end_message:
+            return False
+        return messages[head_index] is self._last_turn_head
@@ -20,9 +20,14 @@ from typing import TYPE_CHECKING, Any
 from pydantic import ValidationError
 
 from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.constants import ENV_OPENAI_BASE_URL
 from tunacode.types import InputSessions, ModelName, SessionId, UsageMetrics, UserConfig
 from tunacode.utils.messaging import estimate_messages_tokens
 
+from tunacode.core.debug.raw_responses import RawResponseLog
+from tunacode.core.session.metadata import SessionFilter, SessionMetadata
+from tunacode.core.session.migrations import CURRENT_SESSION_VERSION, migrate_session_file
+from tunacode.core.session.tasks import TaskStore
 from tunacode.core.types import ConversationState, RuntimeState, TaskState, UsageState
 
 if TYPE_CHECKING:
@@ -60,6 +65,8 @@ class SessionState:
     last_modified: str = ""
     working_directory: str = ""
     selected_skill_names: list[str] = field(default_factory=list)
+    tasks: TaskStore = field(default_factory=TaskStore)
+    metadata: SessionMetadata = field(default_factory=SessionMetadata)
     # Recursive execution tracking
     current_recursion_depth: int = 0
     max_recursion_depth: int = 5
@@ -72,6 +79,12 @@ class SessionState:
     # Streaming debug instrumentation (see core/agents/agent_components/streaming.py)
     _debug_events: list[str] = field(default_factory=list)
     _debug_raw_stream_accum: str = ""
+    # Raw provider responses, kept only with settings.retain_raw_responses
+    raw_responses: RawResponseLog = field(default_factory=RawResponseLog)
+    # Effective system prompt of the last agent built, saved for inspection
+    system_prompt: str = ""
+    # Scratch directory of this session (core/session/temp_dir.py); not persisted
+    temp_dir: str = ""
 
 
 class StateManager:
@@ -84,7 +97,7 @@ class StateManager:
     def _load_user_configuration(self) -> None:
         """Load validated user configuration from file or default config."""
         from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
-        from tunacode.configuration.models import get_model_context_window
+        from tunacode.configuration.models import get_model_context_window, qualify_model_string
         from tunacode.configuration.user_config import load_config_with_defaults
 
         # No config file - setup will be shown and replace this reference
@@ -92,7 +105,11 @@ class StateManager:
         merged_user_config = load_config_with_defaults(default_user_config)
         self._session.user_config = merged_user_config
 
-        self._session.current_model = self._session.user_config["default_model"]
+        self._session.current_model = qualify_model_string(
+            self._session.user_config["default_model"],
+            self._session.user_config["env"].get(ENV_OPENAI_BASE_URL),
+        )
+        self._session.show_thoughts = self._session.user_config["settings"]["show_thoughts"]
 
         # Initialize max_tokens from model's registry context window
         self._session.conversation.max_tokens = get_model_context_window(
@@ -166,10 +183,11 @@ class StateManager:
         storage_dir = get_session_storage_dir()
         return storage_dir / f"{self._session.project_id}_{self._session.session_id}.json"
 
-    def _serialize_messages(self) -> list[dict[str, Any]]:
+    def _serialize_messages(self, messages: list[Any] | None = None) -> list[dict[str, Any]]:
         """Serialize in-memory tinyagent message models to JSON dictionaries."""
 
-        messages = self._session.conversation.messages
+        if messages is None:
+            messages = self._session.conversation.messages
         return [message.model_dump(exclude_none=True) for message in messages]
 
     def _deserialize_message(self, raw_message: Any, *, index: int) -> AgentMessage:
@@ -236,32 +254,6 @@ class StateManager:
 
         return CompactionRecord.from_dict(data)
 
-    def _split_thought_messages(
-        self,
-        messages: list[Any],
-    ) -> tuple[list[str], list[dict[str, Any]]]:
-        """Separate legacy thought entries from persisted message history."""
-
-        thoughts: list[str] = []
-        cleaned_messages: list[dict[str, Any]] = []
-
-        for index, message in enumerate(messages):
-            if not isinstance(message, dict):
-                raise TypeError(
-                    "Session messages must be tinyagent JSON objects; "
-                    f"found {type(message).__name__} at index {index}"
-                )
-
-            if "thought" in message:
-                thought_value = message.get("thought")
-                if thought_value is not None:
-                    thoughts.append(str(thought_value))
-                continue
-
-            cleaned_messages.append(message)
-
-        return thoughts, cleaned_messages
-
     def _deserialize_thoughts(self, raw_thoughts: Any) -> list[str]:
         if raw_thoughts is None:
             return []
@@ -284,10 +276,6 @@ class StateManager:
         with open(session_file, "w") as f:
             json.dump(session_data, f, indent=2)
 
-    def _read_session_data(self, session_file: Path) -> dict[str, Any]:
-        with open(session_file) as f:
-            return json.load(f)
-
     def _coerce_str_value(self, value: Any, default: str) -> str:
         if value is None:
             return default
@@ -315,15 +303,20 @@ class StateManager:
 
         return selected_skill_names
 
-    async def save_session(self) -> bool:
-        """Save current session to disk."""
+    async def save_session(self, messages: list[Any] | None = None) -> bool:
+        """Save current session to disk.
+
+        ``messages`` is written in place of the conversation without changing
+        it; a running request passes its in-flight history to checkpoint the
+        unfinished turn.
+        """
         if not self._session.project_id:
             return False
 
         self._session.last_modified = datetime.now(UTC).isoformat()
 
         session_data = {
-            "version": 1,
+            "version": CURRENT_SESSION_VERSION,
             "session_id": self._session.session_id,
             "project_id": self._session.project_id,
             "created_at": self._session.created_at,
@@ -333,8 +326,11 @@ class StateManager:
             "current_model": self._session.current_model,
             "session_total_usage": self._session.usage.session_total_usage.to_dict(),
             "thoughts": self._session.conversation.thoughts,
-            "messages": self._serialize_messages(),
+            "messages": self._serialize_messages(messages),
             "compaction": self._serialize_compaction(),
+            "tasks": self._session.tasks.to_list(),
+            "metadata": self._session.metadata.to_dict(),
+            "system_prompt": self._session.system_prompt,
         }
 
         try:
@@ -360,7 +356,7 @@ class StateManager:
             return False
 
         try:
-            data = await asyncio.to_thread(self._read_session_data, session_file)
+            data = await asyncio.to_thread(migrate_session_file, session_file)
 
             session_id_value = self._coerce_str_value(data.get("session_id"), session_id)
             project_id_value = self._coerce_str_value(data.get("project_id"), "")
@@ -376,19 +372,13 @@ class StateManager:
             max_tokens_value = get_model_context_window(current_model_value)
             session_total_usage = UsageMetrics.from_dict(data.get("session_total_usage", {}))
 
-            raw_messages = data.get("messages", [])
-            if not isinstance(raw_messages, list):
-                raise TypeError(
-                    f"Session 'messages' must be a list, got {type(raw_messages).__name__}"
-                )
-
-            extracted_thoughts, cleaned_messages = self._split_thought_messages(raw_messages)
-            loaded_messages = self._deserialize_messages(cleaned_messages)
-            stored_thoughts = self._deserialize_thoughts(data.get("thoughts"))
-
-            conversation_thoughts = [*stored_thoughts, *extracted_thoughts]
+            loaded_messages = self._deserialize_messages(data.get("messages"))
+            conversation_thoughts = self._deserialize_thoughts(data.get("thoughts"))
             conversation_total_tokens = estimate_messages_tokens(loaded_messages)
             session_compaction = self._deserialize_compaction(data.get("compaction"))
+            session_tasks = TaskStore.from_list(data.get("tasks"))
+            session_metadata = SessionMetadata.from_dict(data.get("metadata"))
+            system_prompt_value = self._coerce_str_value(data.get("system_prompt"), "")
 
             session = self._session
             session.session_id = session_id_value
@@ -404,6 +394,9 @@ class StateManager:
             session.conversation.messages = loaded_messages
             session.conversation.total_tokens = conversation_total_tokens
             session.compaction = session_compaction
+            session.tasks = session_tasks
+            session.metadata = session_metadata
+            session.system_prompt = system_prompt_value
 
             return True
         except json.JSONDecodeError:
@@ -411,8 +404,29 @@ class StateManager:
         except Exception:
             return False
 
-    def list_sessions(self) -> list[dict]:
-        """List available sessions for current project."""
+    def migrate_sessions(self) -> tuple[int, int]:
+        """Upgrade every stored session file to the current schema version.
+
+        Returns ``(migrated, failed)`` counts; failed files are left untouched.
+        """
+        from tunacode.configuration.paths import get_session_storage_dir
+
+        migrated = 0
+        failed = 0
+        for file in get_session_storage_dir().glob("*.json"):
+            try:
+                with open(file) as f:
+                    version = json.load(f).get("version")
+                if version == CURRENT_SESSION_VERSION:
+                    continue
+                migrate_session_file(file)
+                migrated += 1
+            except Exception:
+                failed += 1
+        return migrated, failed
+
+    def list_sessions(self, session_filter: SessionFilter | None = None) -> list[dict]:
+        """List available sessions for current project, optionally only those matching."""
         from tunacode.configuration.paths import get_session_storage_dir
 
         storage_dir = get_session_storage_dir()
@@ -425,6 +439,9 @@ class StateManager:
             try:
                 with open(file) as f:
                     data = json.load(f)
+                metadata = SessionMetadata.from_dict(data.get("metadata"))
+                if session_filter is not None and not session_filter.matches(metadata):
+                    continue
                 sessions.append(
                     {
                         "session_id": data.get("session_id", ""),
@@ -432,6 +449,8 @@ class StateManager:
                         "last_modified": data.get("last_modified", ""),
                         "message_count": len(data.get("messages", [])),
                         "current_model": data.get("current_model", ""),
+                        "metadata": dict(metadata.values),
+                        "tags": list(metadata.tags),
                         "file_path": str(file),
                     }
                 )
@@ -0,0 +1,142 @@
+"""Per-session task list the agent keeps across turns.
+
+Tasks move ``pending -> in_progress -> done``; an in-progress task may also go
+back to ``pending`` when work on it is paused. Any other transition, including
+reopening a finished task, is rejected with ``TaskTransitionError`` so the
+list cannot silently contradict itself. The store lives on ``SessionState``
+and is saved in the session file under ``"tasks"``, so a resumed session
+continues with the same list.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from datetime import UTC, datetime
+from typing import Any
+
+TASK_PENDING = "pending"
+TASK_IN_PROGRESS = "in_progress"
+TASK_DONE = "done"
+
+TASK_STATUSES: tuple[str, ...] = (TASK_PENDING, TASK_IN_PROGRESS, TASK_DONE)
+ALLOWED_TASK_TRANSITIONS: dict[str, frozenset[str]] = {
+    TASK_PENDING: frozenset({TASK_IN_PROGRESS}),
+    TASK_IN_PROGRESS: frozenset({TASK_DONE, TASK_PENDING}),
+    TASK_DONE: frozenset(),
+}
+
+
+class TaskTransitionError(ValueError):
+    """Raised for unknown task ids, unknown statuses, or disallowed transitions."""
+
+
+@dataclass(slots=True)
+class TaskItem:
+    task_id: str
+    title: str
+    status: str = TASK_PENDING
+    created_at: str = ""
+    updated_at: str = ""
+
+    def to_dict(self) -> dict[str, str]:
+        return {
+            "id": self.task_id,
+            "title": self.title,
+            "status": self.status,
+            "created_at": self.created_at,
+            "updated_at": self.updated_at,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Any) -> TaskItem:
+        if not isinstance(data, dict):
+            raise TypeError(f"Task must be an object, got {type(data).__name__}")
+        values = {key: data.get(key) for key in ("id", "title", "status")}
+        for key, value in values.items():
+            if not isinstance(value, str):
+                raise TypeError(f"Task '{key}' must be a string, got {type(value).__name__}")
+        if values["status"] not in TASK_STATUSES:
+            raise TypeError(f"Task status must be one of {TASK_STATUSES}, got {values['status']}")
+        return cls(
+            task_id=values["id"],
+            title=values["title"],
+            status=values["status"],
+            created_at=str(data.get("created_at") or ""),
+            updated_at=str(data.get("updated_at") or ""),
+        )
+
+
+def _now() -> str:
+    return datetime.now(UTC).isoformat()
+
+
+@dataclass(slots=True)
+class TaskStore:
+    """Ordered task list with validated status transitions."""
+
+    tasks: list[TaskItem] = field(default_factory=list)
+    next_id: int = 1
+
+    def create(self, title: str) -> TaskItem:
+        title = title.strip()
+        if not title:
+            raise TaskTransitionError("Task title must not be empty.")
+        timestamp = _now()
+        task = TaskItem(
+            task_id=str(self.next_id),
+            title=title,
+            created_at=timestamp,
+            updated_at=timestamp,
+        )
+        self.next_id += 1
+        self.tasks.append(task)
+        return task
+
+    def get(self, task_id: str) -> TaskItem:
+        for task in self.tasks:
+            if task.task_id == task_id:
+                return task
+        raise TaskTransitionError(f"Unknown task id '{task_id}'.")
+
+    def update(
+        self,
+        task_id: str,
+        *,
+        status: str | None = None,
+        title: str | None = None,
+    ) -> TaskItem:
+        task = self.get(task_id)
+        if status is not None and status != task.status:
+            if status not in TASK_STATUSES:
+                raise TaskTransitionError(
+                    f"Unknown task status '{status}'. Use one of: {', '.join(TASK_STATUSES)}."
+                )
+            if status not in ALLOWED_TASK_TRANSITIONS[task.status]:
+                raise TaskTransitionError(
+                    f"Task {task_id} cannot move from {task.status} to {status}."
+                )
+            task.status = status
+        if title is not None:
+            if not title.strip():
+                raise TaskTransitionError("Task title must not be empty.")
+            task.title = title.strip()
+        task.updated_at = _now()
+        return task
+
+    def list(self, *, status: str | None = None) -> list[TaskItem]:
+        if status is None:
+            return list(self.tasks)
+        return [task for task in self.tasks if task.status == status]
+
+    def to_list(self) -> list[dict[str, str]]:
+        return [task.to_dict() for task in self.tasks]
+
+    @classmethod
+    def from_list(cls, data: Any) -> TaskStore:
+        if data is None:
+            return cls()
+        if not isinstance(data, list):
+            raise TypeError(f"Session 'tasks' must be a list, got {type(data).__name__}")
+        tasks = [TaskItem.from_dict(item) for item in data]
+        numeric_ids = [int(task.task_id) for task in tasks if task.task_id.isdigit()]
+        return cls(tasks=tasks, next_id=max(numeric_ids, default=0) + 1)
@@ -0,0 +1,99 @@
+"""Per-session scratch directory for tool intermediates.
+
+Tools that need scratch space write under ``<system temp>/tunacode-<session_id>``
+instead of the project directory. The path depends only on the session id, so
+it stays the same for the whole session, including across app restarts of a
+resumed session.
+
+``open_session_temp_dir()`` creates it when a session starts (app mount or
+``/resume``), records the owning pid, exports it to tool subprocesses as
+``$TUNACODE_SESSION_TMP`` and returns it for ``session.temp_dir``.
+``close_session_temp_dir()`` removes it when the session ends. Cleanup is
+best-effort past that:
+
+- an ``atexit`` hook removes the open directory when the process exits without
+  unmounting the app (an unhandled exception, ``sys.exit``);
+- a process killed outright leaves its directory behind, so every start runs
+  ``sweep_stale_session_temp_dirs()``, which removes directories whose owner pid
+  no longer runs.
+"""
+
+from __future__ import annotations
+
+import atexit
+import os
+import shutil
+import tempfile
+from dataclasses import dataclass
+from pathlib import Path
+
+from tunacode.utils.system.process_group import pid_is_running
+
+SESSION_TEMP_ENV_VAR = "TUNACODE_SESSION_TMP"
+SESSION_TEMP_PREFIX = "tunacode-"
+OWNER_PID_FILE = ".owner_pid"
+
+
+@dataclass(slots=True)
+class _OpenDir:
+    path: Path | None = None
+    exit_hook_registered: bool = False
+
+
+_open = _OpenDir()
+
+
+def session_temp_path(session_id: str, *, root: Path | None = None) -> Path:
+    """Return the scratch directory path of ``session_id`` without creating it."""
+    base = Path(tempfile.gettempdir()) if root is None else root
+    return base / f"{SESSION_TEMP_PREFIX}{session_id}"
+
+
+def open_session_temp_dir(session_id: str, *, root: Path | None = None) -> Path:
+    """Create the scratch directory of ``session_id``, closing any other open one."""
+    path = session_temp_path(session_id, root=root)
+    if _open.path is not None and _open.path != path:
+        close_session_temp_dir(_open.path)
+    path.mkdir(mode=0o700, parents=True, exist_ok=True)
+    (path / OWNER_PID_FILE).write_text(str(os.getpid()), encoding="utf-8")
+    os.environ[SESSION_TEMP_ENV_VAR] = str(path)
+    _open.path = path
+    if not _open.exit_hook_registered:
+        atexit.register(_close_open_dir)
+        _open.exit_hook_registered = True
+    return path
+
+
+def close_session_temp_dir(path: Path) -> None:
+    """Remove a session scratch directory and stop exporting it."""
+    shutil.rmtree(path, ignore_errors=True)
+    if os.environ.get(SESSION_TEMP_ENV_VAR) == str(path):
+        del os.environ[SESSION_TEMP_ENV_VAR]
+    if _open.path == path:
+        _open.path = None
+
+
+def sweep_stale_session_temp_dirs(*, root: Path | None = None) -> list[Path]:
+    """Remove scratch directories left by processes that no longer run."""
+    base = Path(tempfile.gettempdir()) if root is None else root
+    removed: list[Path] = []
+    for candidate in base.glob(f"{SESSION_TEMP_PREFIX}*"):
+        owner_pid = _read_owner_pid(candidate)
+        if owner_pid is None or pid_is_running(owner_pid):
+            continue
+        shutil.rmtree(candidate, ignore_errors=True)
+        removed.append(candidate)
+    return removed
+
+
+def _close_open_dir() -> None:
+    if _open.path is not None:
+        close_session_temp_dir(_open.path)
+
+
+def _read_owner_pid(path: Path) -> int | None:
+    """Owner pid of a scratch directory; None for anything tunacode did not create."""
+    try:
+        return int((path / OWNER_PID_FILE).read_text(encoding="utf-8").strip())
+    except (OSError, ValueError):
+        return None
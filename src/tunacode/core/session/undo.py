@@ -0,0 +1,13 @@
+"""Undo facade over the tool-layer edit journal for the UI."""
+
+from __future__ import annotations
+
+from tunacode.tools import edit_journal
+
+
+def undo_last_turn() -> list[str]:
+    """Restore files edited by the most recent turn; return their paths.
+
+    Raises ``UndoConflictError`` when a file changed after the turn wrote it.
+    """
+    return [str(path) for path in edit_journal.undo_last_turn()]
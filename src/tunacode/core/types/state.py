@@ -10,6 +10,8 @@ from typing import TYPE_CHECKING, Any, Protocol
 
 if TYPE_CHECKING:
     from tunacode.core.compaction.types import CompactionRecord
+    from tunacode.core.debug.raw_responses import RawResponseLog
+    from tunacode.core.session.tasks import TaskStore
 
 from tunacode.types import UserConfig
 
@@ -38,12 +40,16 @@ class SessionStateProtocol(Protocol):
     _compaction_controller: Any | None
     _debug_events: list[str]
     _debug_raw_stream_accum: str
+    raw_responses: RawResponseLog
+    system_prompt: str
+    temp_dir: str
     # Persistence fields
     session_id: str
     project_id: str
     created_at: str
     working_directory: str
     selected_skill_names: list[str]
+    tasks: TaskStore
 
 
 class StateManagerProtocol(Protocol):
@@ -96,8 +102,8 @@ class StateManagerProtocol(Protocol):
         ...
 
     # Session persistence methods
-    async def save_session(self) -> bool:
-        """Save current session to disk."""
+    async def save_session(self, messages: list[Any] | None = None) -> bool:
+        """Save current session to disk, optionally with ``messages`` as the history."""
         ...
 
     async def load_session(self, session_id: str) -> bool:
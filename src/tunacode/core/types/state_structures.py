@@ -55,6 +55,8 @@ class RuntimeState:
     consecutive_empty_responses: int = DEFAULT_CONSECUTIVE_EMPTY_RESPONSES
     batch_counter: int = DEFAULT_BATCH_COUNTER
     operation_cancelled: bool = False
+    step_limit_reached: bool = False
+    loop_detection: str = ""
     is_streaming_active: bool = False
     streaming_panel: Any | None = None
 
@@ -0,0 +1 @@
+"""File watching for watch mode."""
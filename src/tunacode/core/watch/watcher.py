@@ -0,0 +1,158 @@
+"""Polling file watcher that turns file changes into watch-mode prompts.
+
+The watcher snapshots ``mtime``/size for every file matching its globs and is
+polled by the UI. A batch of changes fires only after the files have been
+quiet for ``debounce_seconds``, at most once per ``cooldown_seconds``, and at
+most ``max_runs`` times. Callers ``rebaseline()`` while a turn is running so
+the agent's own edits never trigger a run.
+
+Scans walk the tree through the discovery tools' ignore manager, so
+``.gitignore``'d and default-ignored directories are never entered, and skip
+directories no glob can match below. ``scan()`` may run off the event loop;
+pass its result to ``poll()``/``rebaseline()``.
+"""
+
+from __future__ import annotations
+
+import os
+from dataclasses import dataclass, field
+from fnmatch import fnmatch
+from pathlib import Path
+
+from tunacode.tools.ignore import get_ignore_manager
+
+DEFAULT_DEBOUNCE_SECONDS = 1.5
+DEFAULT_COOLDOWN_SECONDS = 10.0
+DEFAULT_MAX_RUNS = 10
+DEFAULT_WATCH_PROMPT = (
+    "These watched files changed: {files}. Review the changes and make the tests pass."
+)
+CHANGED_FILES_PLACEHOLDER = "{files}"
+CHANGED_FILES_SEPARATOR = ", "
+
+RECURSIVE_GLOB_SEGMENT = "**"
+
+FileSignature = tuple[int, int]
+Snapshot = dict[str, FileSignature]
+
+
+def render_watch_prompt(template: str, changed_files: list[str]) -> str:
+    """Fill ``{files}`` in ``template``, or append the files when it is absent."""
+    files = CHANGED_FILES_SEPARATOR.join(changed_files)
+    if CHANGED_FILES_PLACEHOLDER in template:
+        return template.replace(CHANGED_FILES_PLACEHOLDER, files)
+    return f"{template}\n\nChanged files: {files}"
+
+
+def _glob_matches(pattern: tuple[str, ...], parts: tuple[str, ...]) -> bool:
+    """Match path ``parts`` against glob segments, with ``**`` spanning directories."""
+    if not pattern:
+        return not parts
+    head, rest = pattern[0], pattern[1:]
+    if head == RECURSIVE_GLOB_SEGMENT:
+        return any(_glob_matches(rest, parts[index:]) for index in range(len(parts) + 1))
+    return bool(parts) and fnmatch(parts[0], head) and _glob_matches(rest, parts[1:])
+
+
+def _glob_may_match_below(pattern: tuple[str, ...], parts: tuple[str, ...]) -> bool:
+    """Whether a file below the directory ``parts`` could match the glob segments."""
+    if not parts:
+        return bool(pattern)
+    if not pattern:
+        return False
+    head = pattern[0]
+    if head == RECURSIVE_GLOB_SEGMENT:
+        return True
+    return fnmatch(parts[0], head) and _glob_may_match_below(pattern[1:], parts[1:])
+
+
+def _split_glob(pattern: str) -> tuple[str, ...]:
+    return tuple(segment for segment in pattern.split("/") if segment not in ("", "."))
+
+
+@dataclass(slots=True)
+class FileWatcher:
+    """Debounced, rate-limited change detection over a set of globs."""
+
+    root: Path
+    globs: list[str]
+    prompt_template: str = DEFAULT_WATCH_PROMPT
+    debounce_seconds: float = DEFAULT_DEBOUNCE_SECONDS
+    cooldown_seconds: float = DEFAULT_COOLDOWN_SECONDS
+    max_runs: int = DEFAULT_MAX_RUNS
+    runs: int = 0
+    _snapshot: Snapshot = field(default_factory=dict)
+    _pending: set[str] = field(default_factory=set)
+    _last_change_at: float = 0.0
+    _last_run_at: float | None = None
+
+    def __post_init__(self) -> None:
+        self._snapshot = self.scan()
+
+    @property
+    def exhausted(self) -> bool:
+        return self.runs >= self.max_runs
+
+    def scan(self) -> Snapshot:
+        """Stat every non-ignored file matching the globs; blocking, thread-safe."""
+        patterns = [_split_glob(pattern) for pattern in self.globs]
+        ignore_manager = get_ignore_manager(self.root)
+        snapshot: Snapshot = {}
+        for dirpath, dirnames, filenames in os.walk(self.root):
+            current = Path(dirpath)
+            prefix = current.relative_to(self.root).parts
+            dirnames[:] = [
+                name
+                for name in dirnames
+                if any(_glob_may_match_below(pattern, (*prefix, name)) for pattern in patterns)
+                and not ignore_manager.should_ignore_dir(current / name)
+            ]
+            for name in filenames:
+                parts = (*prefix, name)
+                if not any(_glob_matches(pattern, parts) for pattern in patterns):
+                    continue
+                path = current / name
+                if ignore_manager.should_ignore(path):
+                    continue
+                try:
+                    stat_result = path.stat()
+                except OSError:
+                    continue
+                snapshot["/".join(parts)] = (stat_result.st_mtime_ns, stat_result.st_size)
+        return snapshot
+
+    def rebaseline(self, snapshot: Snapshot | None = None) -> None:
+        """Accept the current file state without reporting it as a change."""
+        self._snapshot = self.scan() if snapshot is None else snapshot
+        self._pending.clear()
+
+    def poll(self, now: float, snapshot: Snapshot | None = None) -> str | None:
+        """Return a prompt when a debounced batch of changes is ready to run.
+
+        ``snapshot`` is a fresh ``scan()`` result; the watcher scans itself when omitted.
+        """
+        if self.exhausted:
+            return None
+
+        current = self.scan() if snapshot is None else snapshot
+        changed = {
+            path
+            for path in current.keys() | self._snapshot.keys()
+            if current.get(path) != self._snapshot.get(path)
+        }
+        self._snapshot = current
+        if changed:
+            self._pending |= changed
+            self._last_change_at = now
+            return None
+
+        if not self._pending or now - self._last_change_at < self.debounce_seconds:
+            return None
+        if self._last_run_at is not None and now - self._last_run_at < self.cooldown_seconds:
+            return None
+
+        changed_files = sorted(self._pending)
+        self._pending.clear()
+        self._last_run_at = now
+        self.runs += 1
+        return render_watch_prompt(self.prompt_template, changed_files)
@@ -5,6 +5,7 @@ This module defines all custom exceptions used throughout the TunaCode CLI.
 All exceptions inherit from TunaCodeError for easy catching of any TunaCode-specific error.
 """
 
+from tunacode.constants import EditFailureKind
 from tunacode.types import ErrorMessage, FilePath, OriginalError, ToolName
 
 SECTION_SEPARATOR = "\n\n"
@@ -168,9 +169,12 @@ class AgentError(TunaCodeError):
         message: str,
         suggested_fix: str | None = None,
         troubleshooting_steps: list | None = None,
+        kind: str | None = None,
     ):
         self.suggested_fix = suggested_fix
         self.troubleshooting_steps = troubleshooting_steps or []
+        self.raw_message = message
+        self.kind = kind
 
         base_message = f"{AGENT_ERROR_PREFIX}{message}"
         full_message = _build_error_message(
@@ -272,6 +276,20 @@ class GlobalRequestTimeoutError(TunaCodeError):
         )
 
 
+class TurnDeadlineExceededError(TunaCodeError):
+    """Raised when a turn runs past ``settings.turn_deadline``."""
+
+    def __init__(self, deadline_seconds: float, running: list[str]):
+        self.deadline_seconds = deadline_seconds
+        self.running = running
+        super().__init__(
+            f"Turn deadline of {deadline_seconds:g}s exceeded while running: "
+            f"{', '.join(running)}. The in-flight work was cancelled. "
+            f"Try increasing settings.turn_deadline in tunacode.json "
+            f"or split the task into smaller requests."
+        )
+
+
 class ContextOverflowError(TunaCodeError):
     """Raised when model context length exceeds the configured window."""
 
@@ -331,3 +349,62 @@ class ToolRetryError(TunaCodeError):
     def __init__(self, message: str, original_error: OriginalError = None):
         super().__init__(message)
         self.original_error = original_error
+
+
+class EditError(ToolRetryError):
+    """A refused file edit, with machine-readable details next to the message.
+
+    ``kind`` tells a caller what to do next: ``context_mismatch``, ``not_read``
+    and ``out_of_range`` are fixed by re-reading the file, ``invalid_ref`` by
+    correcting the call, and ``file_not_found`` or ``permission_denied`` will
+    not succeed on retry. ``expected``/``actual`` hold the line hash the edit
+    referenced and the one on record; ``actual_line`` is the line on disk.
+    """
+
+    def __init__(
+        self,
+        kind: EditFailureKind,
+        message: str,
+        *,
+        filepath: FilePath,
+        line: int | None = None,
+        expected: str | None = None,
+        actual: str | None = None,
+        actual_line: str | None = None,
+        original_error: OriginalError = None,
+    ):
+        self.kind = kind
+        self.filepath = filepath
+        self.line = line
+        self.expected = expected
+        self.actual = actual
+        self.actual_line = actual_line
+        super().__init__(message, original_error=original_error)
+
+    def diagnostic(self) -> dict[str, object]:
+        """JSON-ready details of the failure, omitting fields that do not apply."""
+        fields: dict[str, object | None] = {
+            "kind": self.kind.value,
+            "filepath": str(self.filepath),
+            "line": self.line,
+            "expected": self.expected,
+            "actual": self.actual,
+            "actual_line": self.actual_line,
+        }
+        return {key: value for key, value in fields.items() if value is not None}
+
+
+class UndoConflictError(TunaCodeError):
+    """Raised when undo is refused because edited files changed after the turn."""
+
+    def __init__(self, modified_paths: list[FilePath]):
+        self.modified_paths = modified_paths
+        listed = LINE_SEPARATOR.join(f"{BULLET_PREFIX}{path}" for path in modified_paths)
+        super().__init__(
+            "Cannot undo the last turn; these files were modified after it:"
+            f"{LINE_SEPARATOR}{listed}"
+        )
+
+
+class SessionMigrationError(TunaCodeError):
+    """Raised when a session file cannot be upgraded to the current schema version."""
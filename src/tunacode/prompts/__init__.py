@@ -0,0 +1 @@
+"""Built-in system prompt and user-defined custom prompts."""
@@ -0,0 +1,154 @@
+"""User-defined prompt templates invoked as ``/name args`` from the editor.
+
+A custom prompt is a Markdown file named ``<name>.md`` in the project's
+``.tunacode/prompts/`` directory or the user's ``~/.tunacode/prompts/``; the
+project file wins when both exist. Built-in slash commands always take
+precedence over a prompt with the same name.
+
+Arguments are split shell-style. ``key=value`` tokens are named arguments and
+everything else is positional. The prompt body may reference:
+
+- ``$1`` .. ``$9``: a positional argument (empty when not given),
+- ``$@``: all positional arguments joined by spaces,
+- ``{{name}}``: a named argument (empty when not given).
+
+Bodies may also use conditionals and includes (``prompt_template.py``). A
+prompt may declare its arguments in frontmatter (``prompt_schema.py``); those
+are validated before anything is submitted and shown by ``/help name``.
+"""
+
+from __future__ import annotations
+
+import re
+import shlex
+from dataclasses import dataclass
+from pathlib import Path
+
+from tunacode.constants import TUNACODE_HOME_DIR
+from tunacode.prompts.prompt_schema import (
+    PromptArguments,
+    PromptSchema,
+    PromptSchemaError,
+    bind_arguments,
+    parse_prompt_schema,
+    split_frontmatter,
+)
+from tunacode.prompts.prompt_template import PromptTemplateError, render_template
+
+CUSTOM_PROMPT_SUFFIX = ".md"
+CUSTOM_PROMPTS_DIR_NAME = "prompts"
+CUSTOM_PROMPT_NAME_PATTERN = re.compile(r"^[A-Za-z0-9][A-Za-z0-9_-]*$")
+NAMED_ARGUMENT_PATTERN = re.compile(r"^([A-Za-z_][A-Za-z0-9_]*)=(.*)$", re.DOTALL)
+
+
+class CustomPromptError(ValueError):
+    """Raised when a custom prompt cannot be read or its arguments are invalid."""
+
+
+@dataclass(frozen=True, slots=True)
+class CustomPrompt:
+    name: str
+    path: Path
+    body: str
+    schema: PromptSchema = PromptSchema()
+
+
+def resolve_prompt_roots(
+    *,
+    project_root: Path | None = None,
+    home_directory: Path | None = None,
+) -> tuple[Path, Path]:
+    """Return the project-local and user-global prompt directories, local first."""
+    resolved_project_root = Path.cwd() if project_root is None else project_root
+    resolved_home_directory = Path.home() if home_directory is None else home_directory
+    return (
+        resolved_project_root / TUNACODE_HOME_DIR / CUSTOM_PROMPTS_DIR_NAME,
+        resolved_home_directory / TUNACODE_HOME_DIR / CUSTOM_PROMPTS_DIR_NAME,
+    )
+
+
+def _prompt_files(root: Path) -> dict[str, Path]:
+    if not root.is_dir():
+        return {}
+    files: dict[str, Path] = {}
+    for path in sorted(root.glob(f"*{CUSTOM_PROMPT_SUFFIX}")):
+        if path.is_file() and CUSTOM_PROMPT_NAME_PATTERN.match(path.stem):
+            files.setdefault(path.stem.casefold(), path)
+    return files
+
+
+def _read_prompt(name: str, path: Path) -> CustomPrompt:
+    try:
+        body = path.read_text(encoding="utf-8")
+    except (OSError, UnicodeDecodeError) as exc:
+        raise CustomPromptError(f"Cannot read custom prompt /{name} ({path}): {exc}") from exc
+    try:
+        frontmatter, body = split_frontmatter(body)
+        schema = parse_prompt_schema(frontmatter)
+    except PromptSchemaError as exc:
+        raise CustomPromptError(f"Invalid custom prompt /{name} ({path}): {exc}") from exc
+    return CustomPrompt(name=name, path=path, body=body, schema=schema)
+
+
+def find_custom_prompt(name: str, *, roots: tuple[Path, ...] | None = None) -> CustomPrompt | None:
+    """Return the prompt called ``name`` (case-insensitive), or None."""
+    key = name.casefold()
+    for root in roots if roots is not None else resolve_prompt_roots():
+        path = _prompt_files(root).get(key)
+        if path is not None:
+            return _read_prompt(path.stem, path)
+    return None
+
+
+def list_custom_prompts(*, roots: tuple[Path, ...] | None = None) -> list[CustomPrompt]:
+    """Return every visible prompt, sorted by name, earlier roots shadowing later ones."""
+    merged: dict[str, Path] = {}
+    for root in roots if roots is not None else resolve_prompt_roots():
+        for key, path in _prompt_files(root).items():
+            merged.setdefault(key, path)
+    return [_read_prompt(merged[key].stem, merged[key]) for key in sorted(merged)]
+
+
+def parse_prompt_arguments(raw_args: str) -> PromptArguments:
+    try:
+        tokens = shlex.split(raw_args)
+    except ValueError as exc:
+        raise CustomPromptError(f"Cannot parse arguments: {exc}") from exc
+    arguments = PromptArguments()
+    for token in tokens:
+        named = NAMED_ARGUMENT_PATTERN.match(token)
+        if named is None:
+            arguments.positional.append(token)
+        else:
+            arguments.named[named.group(1)] = named.group(2)
+    return arguments
+
+
+def render_custom_prompt(
+    prompt: CustomPrompt,
+    raw_args: str,
+    *,
+    roots: tuple[Path, ...] | None = None,
+) -> str:
+    """Return the rendered prompt body for ``raw_args``, ready to submit.
+
+    Includes resolve against ``roots`` (the default prompt roots when None).
+    """
+
+    def _include_body(name: str) -> str | None:
+        included = find_custom_prompt(name, roots=roots)
+        return None if included is None else included.body
+
+    try:
+        arguments = bind_arguments(
+            prompt.schema, parse_prompt_arguments(raw_args), prompt_name=prompt.name
+        )
+    except PromptSchemaError as exc:
+        raise CustomPromptError(str(exc)) from exc
+    try:
+        rendered = render_template(
+            prompt.body, arguments, prompt_name=prompt.name, resolve_include=_include_body
+        )
+    except PromptTemplateError as exc:
+        raise CustomPromptError(f"Cannot render custom prompt /{prompt.name}: {exc}") from exc
+    return rendered.strip()
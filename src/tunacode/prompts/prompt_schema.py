@@ -0,0 +1,134 @@
+"""Frontmatter argument schemas for custom prompts.
+
+A prompt file may open with a ``---`` delimited block of ``key: value`` lines:
+
+    ---
+    description: Fix a failing test
+    arg.test: required; The failing test id
+    arg.file: The file to look at first
+    ---
+
+``arg.<name>`` declares an argument; a leading ``required;`` marks it as
+mandatory and the rest is its description. Declared arguments are filled from
+``name=value`` tokens first, then from positional arguments in declaration
+order, so ``/fix test_login`` and ``/fix test=test_login`` bind the same way.
+A prompt without declared arguments accepts anything.
+"""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+
+FRONTMATTER_DELIMITER = "---"
+ARGUMENT_KEY_PREFIX = "arg."
+REQUIRED_MARKER = "required"
+REQUIRED_SEPARATOR = ";"
+ARGUMENT_NAME_PATTERN = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*$")
+
+
+class PromptSchemaError(ValueError):
+    """Raised for malformed frontmatter or arguments that do not fit the schema."""
+
+
+@dataclass(frozen=True, slots=True)
+class PromptArgumentSpec:
+    name: str
+    required: bool = False
+    description: str = ""
+
+
+@dataclass(frozen=True, slots=True)
+class PromptSchema:
+    description: str = ""
+    arguments: tuple[PromptArgumentSpec, ...] = ()
+
+    def usage(self, prompt_name: str) -> str:
+        """Return a one-line usage string such as ``/fix <test> [file]``."""
+        parts = [f"/{prompt_name}"]
+        for spec in self.arguments:
+            parts.append(f"<{spec.name}>" if spec.required else f"[{spec.name}]")
+        return " ".join(parts)
+
+
+@dataclass(frozen=True, slots=True)
+class PromptArguments:
+    positional: list[str] = field(default_factory=list)
+    named: dict[str, str] = field(default_factory=dict)
+
+
+def split_frontmatter(content: str) -> tuple[dict[str, str], str]:
+    """Return the frontmatter mapping and the body; no frontmatter gives ``{}``."""
+    lines = content.splitlines()
+    if not lines or lines[0].strip() != FRONTMATTER_DELIMITER:
+        return {}, content
+    for closing_index, line in enumerate(lines[1:], start=1):
+        if line.strip() == FRONTMATTER_DELIMITER:
+            break
+    else:
+        raise PromptSchemaError("Frontmatter is missing its closing '---'")
+
+    frontmatter: dict[str, str] = {}
+    for raw_line in lines[1:closing_index]:
+        if not raw_line.strip():
+            continue
+        key, separator, value = raw_line.partition(":")
+        if not separator or not key.strip():
+            raise PromptSchemaError(f"Malformed frontmatter line: {raw_line!r}")
+        frontmatter[key.strip()] = value.strip()
+    return frontmatter, "\n".join(lines[closing_index + 1 :])
+
+
+def _parse_argument_spec(name: str, value: str) -> PromptArgumentSpec:
+    if not ARGUMENT_NAME_PATTERN.match(name):
+        raise PromptSchemaError(f"Invalid argument name '{name}' in frontmatter")
+    marker, separator, rest = value.partition(REQUIRED_SEPARATOR)
+    if separator and marker.strip().lower() == REQUIRED_MARKER:
+        return PromptArgumentSpec(name=name, required=True, description=rest.strip())
+    if value.strip().lower() == REQUIRED_MARKER:
+        return PromptArgumentSpec(name=name, required=True)
+    return PromptArgumentSpec(name=name, description=value.strip())
+
+
+def parse_prompt_schema(frontmatter: dict[str, str]) -> PromptSchema:
+    arguments = [
+        _parse_argument_spec(key[len(ARGUMENT_KEY_PREFIX) :], value)
+        for key, value in frontmatter.items()
+        if key.startswith(ARGUMENT_KEY_PREFIX)
+    ]
+    return PromptSchema(description=frontmatter.get("description", ""), arguments=tuple(arguments))
+
+
+def bind_arguments(
+    schema: PromptSchema, arguments: PromptArguments, *, prompt_name: str
+) -> PromptArguments:
+    """Validate ``arguments`` against ``schema`` and fill declared names.
+
+    Raises ``PromptSchemaError`` for unknown named arguments or missing
+    required ones. Positional arguments are kept as given for ``$1``/``$@``.
+    """
+    if not schema.arguments:
+        return arguments
+    declared = [spec.name for spec in schema.arguments]
+    unknown = sorted(set(arguments.named) - set(declared))
+    if unknown:
+        raise PromptSchemaError(
+            f"Unknown argument(s) for /{prompt_name}: {', '.join(unknown)}. "
+            f"Usage: {schema.usage(prompt_name)}"
+        )
+
+    named = dict(arguments.named)
+    remaining = iter(arguments.positional)
+    for name in declared:
+        if name not in named:
+            value = next(remaining, None)
+            if value is not None:
+                named[name] = value
+
+    missing = [spec.name for spec in schema.arguments if spec.required and not named.get(spec.name)]
+    if missing:
+        raise PromptSchemaError(
+            f"Missing required argument(s) for /{prompt_name}: {', '.join(missing)}. "
+            f"Usage: {schema.usage(prompt_name)}"
+        )
+    return PromptArguments(positional=list(arguments.positional), named=named)
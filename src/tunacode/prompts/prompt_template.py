@@ -0,0 +1,180 @@
+"""Minimal template language for custom prompt bodies.
+
+Supported syntax, all evaluated without running any user code:
+
+- ``$1`` .. ``$9`` and ``$@``: positional arguments, as before.
+- ``{{name}}``: a named argument (empty when missing).
+- ``{{#if name}}...{{else}}...{{/if}}``: keep the first branch when ``name``
+  is a non-empty named argument (or, for ``1``..``9``, a given positional
+  one), otherwise the ``{{else}}`` branch if present. Blocks nest.
+- ``{{> other}}``: include the body of the custom prompt ``other``, rendered
+  with the same arguments. Include cycles are rejected and nesting stops at
+  ``MAX_INCLUDE_DEPTH``.
+- ``\\{{`` and ``\\$``: a literal ``{{`` or ``$``.
+
+Any other text, including ``{{`` forms not listed above, renders unchanged,
+so a prompt without template syntax comes out exactly as written.
+"""
+
+from __future__ import annotations
+
+import re
+from collections.abc import Callable
+from dataclasses import dataclass, field
+from typing import TypeAlias
+
+from tunacode.prompts.prompt_schema import PromptArguments
+
+MAX_INCLUDE_DEPTH = 8
+
+TOKEN_PATTERN = re.compile(
+    r"\\(?P<escaped>\{\{|\$)"
+    r"|\{\{\s*(?:"
+    r"#if\s+(?P<if>[A-Za-z_][A-Za-z0-9_]*|[1-9])"
+    r"|(?P<else>else)"
+    r"|(?P<endif>/if)"
+    r"|>\s*(?P<include>[A-Za-z0-9][A-Za-z0-9_-]*)"
+    r"|(?P<var>[A-Za-z_][A-Za-z0-9_]*)"
+    r")\s*\}\}"
+)
+POSITIONAL_PATTERN = re.compile(r"\$(@|[1-9])")
+
+IncludeResolver = Callable[[str], str | None]
+
+
+class PromptTemplateError(ValueError):
+    """Raised for unbalanced blocks, missing includes, cycles, or deep nesting."""
+
+
+@dataclass(slots=True)
+class _Text:
+    text: str
+    literal: bool = False
+
+
+@dataclass(slots=True)
+class _Variable:
+    name: str
+
+
+@dataclass(slots=True)
+class _Include:
+    name: str
+
+
+@dataclass(slots=True)
+class _Conditional:
+    name: str
+    then_nodes: list[TemplateNode] = field(default_factory=list)
+    else_nodes: list[TemplateNode] = field(default_factory=list)
+
+
+TemplateNode: TypeAlias = _Text | _Variable | _Include | _Conditional
+
+
+def _tag_node(match: re.Match[str]) -> TemplateNode:
+    if match.group("escaped") is not None:
+        return _Text(match.group("escaped"), literal=True)
+    if match.group("include") is not None:
+        return _Include(match.group("include"))
+    return _Variable(match.group("var"))
+
+
+def parse_template(source: str) -> list[TemplateNode]:
+    """Parse ``source`` into nodes; raises ``PromptTemplateError`` on bad blocks."""
+    root: list[TemplateNode] = []
+    # Each open block is (conditional, the branch currently being filled).
+    stack: list[tuple[_Conditional, list[TemplateNode]]] = []
+    position = 0
+    for match in TOKEN_PATTERN.finditer(source):
+        target = stack[-1][1] if stack else root
+        if match.start() > position:
+            target.append(_Text(source[position : match.start()]))
+        position = match.end()
+        if match.group("if") is not None:
+            block = _Conditional(match.group("if"))
+            target.append(block)
+            stack.append((block, block.then_nodes))
+        elif match.group("else") is not None:
+            if not stack or stack[-1][1] is stack[-1][0].else_nodes:
+                raise PromptTemplateError("'{{else}}' without a matching '{{#if}}'")
+            stack[-1] = (stack[-1][0], stack[-1][0].else_nodes)
+        elif match.group("endif") is not None:
+            if not stack:
+                raise PromptTemplateError("'{{/if}}' without a matching '{{#if}}'")
+            stack.pop()
+        else:
+            target.append(_tag_node(match))
+    if stack:
+        raise PromptTemplateError(f"'{{{{#if {stack[-1][0].name}}}}}' is never closed")
+    if position < len(source):
+        root.append(_Text(source[position:]))
+    return root
+
+
+def substitute_positional(text: str, arguments: PromptArguments) -> str:
+    def _replace(match: re.Match[str]) -> str:
+        if match.group(1) == "@":
+            return " ".join(arguments.positional)
+        index = int(match.group(1)) - 1
+        return arguments.positional[index] if index < len(arguments.positional) else ""
+
+    return POSITIONAL_PATTERN.sub(_replace, text)
+
+
+def _is_truthy(name: str, arguments: PromptArguments) -> bool:
+    if name.isdigit():
+        return int(name) <= len(arguments.positional)
+    return bool(arguments.named.get(name))
+
+
+@dataclass(slots=True)
+class _Renderer:
+    arguments: PromptArguments
+    resolve_include: IncludeResolver
+    include_stack: list[str]
+
+    def render(self, nodes: list[TemplateNode]) -> str:
+        return "".join(self._render_node(node) for node in nodes)
+
+    def _render_node(self, node: TemplateNode) -> str:
+        if isinstance(node, _Text):
+            return node.text if node.literal else substitute_positional(node.text, self.arguments)
+        if isinstance(node, _Variable):
+            return self.arguments.named.get(node.name, "")
+        if isinstance(node, _Conditional):
+            branch = node.then_nodes if _is_truthy(node.name, self.arguments) else node.else_nodes
+            return self.render(branch)
+        return self._render_include(node.name)
+
+    def _render_include(self, name: str) -> str:
+        key = name.casefold()
+        if key in self.include_stack:
+            cycle = " -> ".join([*self.include_stack, key])
+            raise PromptTemplateError(f"Include cycle: {cycle}")
+        if len(self.include_stack) > MAX_INCLUDE_DEPTH:
+            raise PromptTemplateError(f"Includes nest deeper than {MAX_INCLUDE_DEPTH} levels")
+        source = self.resolve_include(name)
+        if source is None:
+            raise PromptTemplateError(f"Included prompt '{name}' was not found")
+        self.include_stack.append(key)
+        try:
+            return self.render(parse_template(source))
+        finally:
+            self.include_stack.pop()
+
+
+def render_template(
+    source: str,
+    arguments: PromptArguments,
+    *,
+    prompt_name: str,
+    resolve_include: IncludeResolver,
+) -> str:
+    """Render ``source`` for the prompt ``prompt_name`` with ``arguments``."""
+    renderer = _Renderer(
+        arguments=arguments,
+        resolve_include=resolve_include,
+        include_stack=[prompt_name.casefold()],
+    )
+    return renderer.render(parse_template(source))
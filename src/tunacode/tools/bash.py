@@ -4,8 +4,11 @@ from __future__ import annotations
 
 import asyncio
 import os
+import signal
 import subprocess
+import time
 from asyncio.subprocess import Process
+from collections.abc import Callable
 
 from tinyagent.agent_types import (
     AgentTool,
@@ -15,16 +18,39 @@ from tinyagent.agent_types import (
     TextContent,
 )
 
-from tunacode.configuration.limits import get_command_limit
+from tunacode.configuration.limits import (
+    get_command_capture_bytes,
+    get_command_history_limit,
+    get_command_limit,
+    get_command_policy,
+    get_command_shell,
+    get_command_stream_output,
+)
+from tunacode.constants import FULL_OUTPUT_DETAIL_KEY, CommandPolicy
 from tunacode.exceptions import ToolExecutionError, ToolRetryError, UserAbortError
+from tunacode.utils.system.process_group import (
+    AGENT_TURN_SCOPE,
+    kill_process_group,
+    new_process_group_kwargs,
+    signal_process_group,
+)
+from tunacode.utils.system.shell_program import resolve_shell
+
+from tunacode.tools.utils.command_risk import explain_command
+from tunacode.tools.utils.command_rules import CommandRuleError
+from tunacode.tools.utils.output_capture import BoundedCapture
 
 COMMAND_OUTPUT_THRESHOLD = 3500
 COMMAND_OUTPUT_START_INDEX = 2500
 COMMAND_OUTPUT_END_SIZE = 1000
 CMD_OUTPUT_TRUNCATED = "\n...\n[truncated]\n...\n"
+HISTORY_OUTPUT_TAIL_DIVISOR = 4
 MIN_TIMEOUT_SECONDS = 1
 MAX_TIMEOUT_SECONDS = 600
 DEFAULT_TIMEOUT_SECONDS = 120
+STREAM_READ_CHUNK_BYTES = 64 * 1024
+STREAM_UPDATE_INTERVAL_SECONDS = 0.25
+STREAM_UPDATE_TAIL_BYTES = 4096
 
 _BASH_DESCRIPTION = """Execute a bash command with enhanced features.
 
@@ -69,8 +95,8 @@ _BASH_PARAMETERS: JsonObject = {
 }
 
 
-def _text_result(text: str) -> AgentToolResult:
-    return AgentToolResult(content=[TextContent(text=text)], details={})
+def _text_result(text: str, details: JsonObject | None = None) -> AgentToolResult:
+    return AgentToolResult(content=[TextContent(text=text)], details=details or {})
 
 
 def _require_string_arg(args: JsonObject, key: str) -> str:
@@ -121,51 +147,121 @@ def _optional_env_arg(args: JsonObject) -> dict[str, str] | None:
     return env
 
 
+class _ProgressReporter:
+    """Send the tail of stdout through ``on_update``, at most once per interval."""
+
+    def __init__(self, on_update: AgentToolUpdateCallback, stdout: BoundedCapture) -> None:
+        self._on_update = on_update
+        self._stdout = stdout
+        self._last_update = 0.0
+
+    def __call__(self) -> None:
+        now = time.monotonic()
+        if now - self._last_update < STREAM_UPDATE_INTERVAL_SECONDS:
+            return
+        self._last_update = now
+        partial = _text_result(
+            self._stdout.tail_text(STREAM_UPDATE_TAIL_BYTES),
+            {"stdout_bytes": self._stdout.total_bytes},
+        )
+        self._on_update(partial)
+
+
+async def _drain_stream(
+    stream: asyncio.StreamReader | None,
+    capture: BoundedCapture,
+    on_chunk: Callable[[], None] | None,
+) -> None:
+    if stream is None:
+        return
+    while chunk := await stream.read(STREAM_READ_CHUNK_BYTES):
+        capture.feed(chunk)
+        if on_chunk is not None:
+            on_chunk()
+
+
+async def _collect_output(
+    process: Process,
+    on_update: AgentToolUpdateCallback | None,
+) -> tuple[BoundedCapture, BoundedCapture]:
+    """Read both pipes into bounded captures while the process runs."""
+    max_capture_bytes = get_command_capture_bytes()
+    stdout = BoundedCapture(max_capture_bytes)
+    stderr = BoundedCapture(max_capture_bytes)
+    reporter = _ProgressReporter(on_update, stdout) if on_update is not None else None
+    await asyncio.gather(
+        _drain_stream(process.stdout, stdout, reporter),
+        _drain_stream(process.stderr, stderr, None),
+        process.wait(),
+    )
+    return stdout, stderr
+
+
 async def _run_bash(
     command: str,
     cwd: str | None = None,
     env: dict[str, str] | None = None,
     timeout: int | None = DEFAULT_TIMEOUT_SECONDS,
     capture_output: bool = True,
-) -> str:
+    on_update: AgentToolUpdateCallback | None = None,
+) -> AgentToolResult:
     _validate_inputs(command, cwd, timeout)
+    _enforce_command_policy(command)
 
     exec_env = os.environ.copy()
     if env:
         exec_env.update(env)
 
     exec_cwd = cwd or os.getcwd()
+    shell = resolve_shell(*get_command_shell())
     process: Process | None = None
+    started_at = time.perf_counter()
     try:
-        process = await asyncio.create_subprocess_shell(
-            command,
+        process = await asyncio.create_subprocess_exec(
+            *shell.argv(command),
             stdout=subprocess.PIPE if capture_output else None,
             stderr=subprocess.PIPE if capture_output else None,
             cwd=exec_cwd,
             env=exec_env,
+            **new_process_group_kwargs(),
         )
+        AGENT_TURN_SCOPE.register(process)
 
         try:
-            stdout, stderr = await asyncio.wait_for(process.communicate(), timeout=timeout)
+            stdout, stderr = await asyncio.wait_for(
+                _collect_output(process, on_update), timeout=timeout
+            )
         except TimeoutError as err:
-            process.kill()
+            kill_process_group(process)
             await process.wait()
             raise ToolRetryError(
                 f"Command timed out after {timeout} seconds: {command}\n"
                 "Consider using a longer timeout or breaking the command into smaller parts."
             ) from err
 
-        stdout_text = stdout.decode("utf-8", errors="replace").strip() if stdout else ""
-        stderr_text = stderr.decode("utf-8", errors="replace").strip() if stderr else ""
+        stdout_text = stdout.text().strip()
+        stderr_text = stderr.text().strip()
 
         return_code = process.returncode
         assert return_code is not None
         _check_common_errors(command, return_code, stderr_text)
-        return _format_output(command, return_code, stdout_text, stderr_text, exec_cwd)
+        full_output = _render_output(command, return_code, stdout_text, stderr_text, exec_cwd)
+        output, truncated = _truncate_for_model(full_output)
+        details: JsonObject = {
+            "exit_code": return_code,
+            "duration_ms": round((time.perf_counter() - started_at) * 1000.0, 1),
+            "cwd": exec_cwd,
+            "truncated": truncated or stdout.truncated or stderr.truncated,
+            "stdout_bytes": stdout.total_bytes,
+            "stderr_bytes": stderr.total_bytes,
+        }
+        if truncated:
+            details[FULL_OUTPUT_DETAIL_KEY] = _truncate_for_history(full_output)
+        return _text_result(output, details)
     except FileNotFoundError as err:
         raise ToolRetryError(
-            f"Shell not found. Cannot execute command: {command}\n"
-            "This typically indicates a system configuration issue."
+            f"Shell '{shell.program}' not found. Cannot execute command: {command}\n"
+            "Install it or change settings.shell.program."
         ) from err
     finally:
         await _cleanup_process(process)
@@ -177,25 +273,24 @@ async def _execute_bash(
     signal: asyncio.Event | None,
     on_update: AgentToolUpdateCallback,
 ) -> AgentToolResult:
-    _ = (tool_call_id, on_update)
+    _ = tool_call_id
     if signal is not None and signal.is_set():
         raise UserAbortError("Tool execution aborted: bash")
 
     try:
-        result = await _run_bash(
+        return await _run_bash(
             command=_require_string_arg(args, "command"),
             cwd=_optional_string_arg(args, "cwd"),
             env=_optional_env_arg(args),
             timeout=_optional_int_arg(args, "timeout", DEFAULT_TIMEOUT_SECONDS),
             capture_output=_optional_bool_arg(args, "capture_output", True),
+            on_update=on_update if get_command_stream_output() else None,
         )
     except (ToolRetryError, ToolExecutionError):
         raise
     except Exception as exc:  # noqa: BLE001
         raise ToolExecutionError(tool_name="bash", message=str(exc), original_error=exc) from exc
 
-    return _text_result(result)
-
 
 bash = AgentTool(
     name="bash",
@@ -223,6 +318,24 @@ def _validate_inputs(command: str, cwd: str | None, timeout: int | None) -> None
         )
 
 
+def _enforce_command_policy(command: str) -> None:
+    try:
+        explanation = explain_command(command)
+    except CommandRuleError as exc:
+        raise ToolRetryError(
+            f"Command blocked: command rules could not be loaded: {exc}\n"
+            "Do not retry it; ask the user to fix the rule file."
+        ) from exc
+    risk = explanation.risk
+    if get_command_policy(risk) is CommandPolicy.DENY:
+        raise ToolRetryError(
+            f"Command blocked: {risk.value} commands are denied by "
+            f"settings.command_policy.{risk.value}. Command: {command}\n"
+            f"Matched rule: {explanation.reason}\n"
+            "Do not retry it; use another approach or ask the user to run it."
+        )
+
+
 def _check_common_errors(command: str, returncode: int, stderr: str) -> None:
     _ = command
     if returncode == 0 or not stderr:
@@ -235,16 +348,16 @@ async def _cleanup_process(process: Process | None) -> None:
 
     try:
         try:
-            process.terminate()
+            signal_process_group(process, signal.SIGTERM)
             await asyncio.wait_for(process.wait(), timeout=5.0)
         except TimeoutError:
-            process.kill()
+            kill_process_group(process)
             await asyncio.wait_for(process.wait(), timeout=1.0)
     except Exception:
         pass
 
 
-def _format_output(command: str, exit_code: int, stdout: str, stderr: str, cwd: str) -> str:
+def _render_output(command: str, exit_code: int, stdout: str, stderr: str, cwd: str) -> str:
     lines = [
         f"Command: {command}",
         f"Exit Code: {exit_code}",
@@ -257,9 +370,13 @@ def _format_output(command: str, exit_code: int, stdout: str, stderr: str, cwd:
         stderr or "(no errors)",
     ]
 
-    result = "\n".join(lines)
-    max_output = get_command_limit()
-    if len(result) > max_output:
+    return "\n".join(lines)
+
+
+def _truncate_for_model(result: str) -> tuple[str, bool]:
+    """Return the output the model sees and whether it was truncated."""
+    truncated = len(result) > get_command_limit()
+    if truncated:
         start_part = result[:COMMAND_OUTPUT_START_INDEX]
         end_part = (
             result[-COMMAND_OUTPUT_END_SIZE:]
@@ -268,4 +385,13 @@ def _format_output(command: str, exit_code: int, stdout: str, stderr: str, cwd:
         )
         result = start_part + CMD_OUTPUT_TRUNCATED + end_part
 
-    return result
+    return result, truncated
+
+
+def _truncate_for_history(result: str) -> str:
+    """Return the output kept in history for the user, head and tail within the limit."""
+    limit = get_command_history_limit()
+    if len(result) <= limit:
+        return result
+    tail_size = limit // HISTORY_OUTPUT_TAIL_DIVISOR
+    return result[: limit - tail_size] + CMD_OUTPUT_TRUNCATED + result[len(result) - tail_size :]
@@ -0,0 +1,52 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tunacode.tools.utils.command_rules import CommandRuleSet, build_rule_set, command_rule_paths
+
+from tunacode.infrastructure.cache import ManualStrategy, get_cache, register_cache
+
+COMMAND_RULES_CACHE_NAME = "tunacode.command_rules"
+
+register_cache(COMMAND_RULES_CACHE_NAME, ManualStrategy())
+
+
+def _mtime_ns(path: Path) -> int | None:
+    try:
+        return path.stat().st_mtime_ns
+    except OSError:
+        return None
+
+
+def get_command_rules(
+    *,
+    project_root: Path | None = None,
+    home_directory: Path | None = None,
+) -> CommandRuleSet:
+    """Return the merged command rules for the current user and project.
+
+    The merged set is rebuilt when either rule file appears, changes or is
+    removed. Raises ``CommandRuleError`` while a rule file does not parse.
+    """
+
+    user_file, project_file = command_rule_paths(
+        project_root=project_root, home_directory=home_directory
+    )
+    key = (str(user_file), _mtime_ns(user_file), str(project_file), _mtime_ns(project_file))
+
+    cache = get_cache(COMMAND_RULES_CACHE_NAME)
+    cached = cache.get(key)
+    if cached is not None:
+        if not isinstance(cached, CommandRuleSet):
+            raise TypeError(
+                f"Command rules cache value must be CommandRuleSet, got {type(cached).__name__}"
+            )
+        return cached
+
+    rule_set = build_rule_set(user_file=user_file, project_file=project_file)
+    cache.set(key, rule_set)
+    return rule_set
+
+
+def clear_command_rules_cache() -> None:
+    get_cache(COMMAND_RULES_CACHE_NAME).clear()
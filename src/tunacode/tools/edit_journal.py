@@ -0,0 +1,192 @@
+"""Per-turn journal of file edits, used to undo the last agent turn.
+
+Every write committed through ``FileTransaction`` is recorded against the
+current turn as ``{path: (before_digest, after_digest)}``. File contents are
+stored once, content-addressed by sha256, under
+``~/.tunacode/edit-journal/<pid>``. Only the last ``MAX_JOURNAL_TURNS`` turns
+are kept, in memory, so undo history lives for the current session.
+
+The blob store stays bounded by the retained turns: blobs no retained turn
+references are deleted when a turn starts (an older turn may have fallen off
+the journal) and after an undo, and ``clear()`` and process exit remove the
+process's directory. Directories of processes that no longer run are swept
+the first time a process stores a blob.
+"""
+
+from __future__ import annotations
+
+import atexit
+import hashlib
+import os
+import shutil
+from collections import deque
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from tunacode.configuration.paths import get_tunacode_home
+from tunacode.exceptions import UndoConflictError
+from tunacode.utils.system.process_group import pid_is_running
+
+MAX_JOURNAL_TURNS = 20
+JOURNAL_SUBDIR = "edit-journal"
+
+
+@dataclass(frozen=True, slots=True)
+class JournalEntry:
+    """Content of a file before and after a turn; ``before`` is None for new files."""
+
+    before_digest: str | None
+    after_digest: str
+
+
+@dataclass(slots=True)
+class _Turn:
+    entries: dict[Path, JournalEntry] = field(default_factory=dict)
+
+
+@dataclass(slots=True)
+class _BlobStore:
+    path: Path | None = None
+    exit_hook_registered: bool = False
+
+
+# Module-level singleton journal: oldest turn first, current turn last.
+_turns: deque[_Turn] = deque([_Turn()], maxlen=MAX_JOURNAL_TURNS)
+_store = _BlobStore()
+
+
+def _blob_dir() -> Path:
+    journal_dir = get_tunacode_home() / JOURNAL_SUBDIR
+    blob_dir = journal_dir / str(os.getpid())
+    if _store.path != blob_dir:
+        _sweep_stale_blob_dirs(journal_dir)
+        _store.path = blob_dir
+    blob_dir.mkdir(parents=True, exist_ok=True)
+    if not _store.exit_hook_registered:
+        atexit.register(_remove_blob_dir)
+        _store.exit_hook_registered = True
+    return blob_dir
+
+
+def _sweep_stale_blob_dirs(journal_dir: Path) -> None:
+    if not journal_dir.is_dir():
+        return
+    for candidate in journal_dir.iterdir():
+        if candidate.is_dir() and candidate.name.isdigit() and not pid_is_running(
+            int(candidate.name)
+        ):
+            shutil.rmtree(candidate, ignore_errors=True)
+
+
+def _remove_blob_dir() -> None:
+    if _store.path is not None:
+        shutil.rmtree(_store.path, ignore_errors=True)
+        _store.path = None
+
+
+def _prune_blobs() -> None:
+    """Delete blobs that no retained turn references."""
+    if _store.path is None or not _store.path.is_dir():
+        return
+    referenced = {
+        digest
+        for turn in _turns
+        for entry in turn.entries.values()
+        for digest in (entry.before_digest, entry.after_digest)
+        if digest is not None
+    }
+    for blob_path in _store.path.iterdir():
+        if blob_path.name not in referenced:
+            blob_path.unlink(missing_ok=True)
+
+
+def _digest(content: bytes) -> str:
+    return hashlib.sha256(content).hexdigest()
+
+
+def _store_blob(content: bytes) -> str:
+    digest = _digest(content)
+    blob_path = _blob_dir() / digest
+    if not blob_path.exists():
+        blob_path.write_bytes(content)
+    return digest
+
+
+def _load_blob(digest: str) -> bytes:
+    return (_blob_dir() / digest).read_bytes()
+
+
+def begin_turn() -> None:
+    """Start a new turn; edits recorded after this belong to it."""
+    if _turns[-1].entries:
+        _turns.append(_Turn())
+        _prune_blobs()
+
+
+def record(filepath: Path, before: bytes | None, after: bytes) -> None:
+    """Record a committed write. A file's first ``before`` in a turn is kept."""
+    path = filepath.resolve()
+    entries = _turns[-1].entries
+    previous = entries.get(path)
+    if previous is not None:
+        before_digest = previous.before_digest
+    else:
+        before_digest = None if before is None else _store_blob(before)
+    entries[path] = JournalEntry(before_digest=before_digest, after_digest=_store_blob(after))
+
+
+def turn_paths() -> list[Path]:
+    """Files written during the current turn, in the order they were first written."""
+    return list(_turns[-1].entries)
+
+
+def _current_digest(path: Path) -> str | None:
+    if not path.exists():
+        return None
+    return _digest(path.read_bytes())
+
+
+def undo_last_turn() -> list[Path]:
+    """Restore every file edited in the most recent turn that made edits.
+
+    Raises ``UndoConflictError`` without touching any file when one of them
+    changed on disk after the turn wrote it. Returns the restored paths, or an
+    empty list when there is nothing to undo.
+    """
+    # Imported here: file_transaction records into this module on commit.
+    from tunacode.tools.utils.file_transaction import FileTransaction
+
+    while _turns and not _turns[-1].entries:
+        _turns.pop()
+    if not _turns:
+        _turns.append(_Turn())
+        return []
+
+    turn = _turns[-1]
+    modified = [
+        str(path)
+        for path, entry in turn.entries.items()
+        if _current_digest(path) != entry.after_digest
+    ]
+    if modified:
+        raise UndoConflictError(modified)
+
+    transaction = FileTransaction(journal=False)
+    for path, entry in turn.entries.items():
+        if entry.before_digest is None:
+            transaction.stage_delete(str(path))
+        else:
+            transaction.stage_bytes(str(path), _load_blob(entry.before_digest))
+    transaction.commit()
+
+    _turns.pop()
+    _turns.append(_Turn())
+    _prune_blobs()
+    return sorted(turn.entries)
+
+
+def clear() -> None:
+    """Drop all recorded turns and their blobs."""
+    _turns.clear()
+    _turns.append(_Turn())
+    _remove_blob_dir()
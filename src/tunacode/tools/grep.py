@@ -0,0 +1,353 @@
+"""Native tinyagent grep tool backed by ripgrep, with an in-process regex fallback.
+
+The search runs through ``tools/utils/ripgrep_search.py`` when a ripgrep
+binary is available. Without one, or when ripgrep rejects a pattern that
+Python's ``re`` accepts, a thread pool searches a gitignore-pruned walk of the
+working directory instead. ``settings.ripgrep`` applies to both: ``timeout``
+bounds the search, ``max_results`` is the default ``max_matches`` and
+``enable_metrics`` records each search in ``tools/utils/ripgrep.metrics``.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import os
+import re
+import time
+from collections import deque
+from collections.abc import Callable, Iterable, Iterator
+from concurrent.futures import Future, ThreadPoolExecutor
+from contextlib import closing
+from dataclasses import dataclass, field, replace
+from fnmatch import fnmatch
+from pathlib import Path
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+    TextContent,
+)
+
+from tunacode.configuration.limits import get_ripgrep_settings
+from tunacode.exceptions import ToolRetryError, UserAbortError
+
+from tunacode.tools.ignore import get_ignore_manager
+from tunacode.tools.ignore_manager import IgnoreManager
+from tunacode.tools.utils.binary_detection import SNIFF_BYTES, sniff_bytes
+from tunacode.tools.utils.file_errors import translate_file_tool_errors
+from tunacode.tools.utils.ripgrep import metrics
+from tunacode.tools.utils.ripgrep_search import (
+    SearchMatch,
+    SearchOutcome,
+    SearchQuery,
+    search_with_ripgrep,
+)
+from tunacode.tools.utils.workspace import resolve_workspace_path
+
+MAX_MATCHES_LIMIT = 1000
+MAX_CONTEXT_LINES = 10
+MAX_MATCH_LINE_LENGTH = 500
+MAX_SEARCH_FILE_BYTES = 10 * 1024 * 1024
+SEARCH_WORKER_COUNT = min(8, os.cpu_count() or 1)
+# Files queued ahead of the one being collected; the walk is consumed lazily.
+MAX_PENDING_SEARCHES = SEARCH_WORKER_COUNT * 2
+TRUNCATION_SUFFIX = "..."
+
+_GREP_DESCRIPTION = """Search file contents for a regular expression and return structured matches.
+
+Returns JSON with one entry per matching line: file (relative to the working
+directory), line (1-based), text, and before/after context lines when
+context_lines is set. Respects .gitignore, skips binary files, and stops after
+max_matches (truncated is true when more matches exist). Use include globs such
+as "*.py" or "src/**/*.ts" to narrow the search.
+"""
+
+_GREP_PARAMETERS: JsonObject = {
+    "type": "object",
+    "additionalProperties": False,
+    "properties": {
+        "pattern": {"type": "string", "description": "Python regular expression to search for."},
+        "path": {
+            "type": "string",
+            "description": "File or directory to search. Defaults to the working directory.",
+        },
+        "include": {
+            "type": "array",
+            "items": {"type": "string"},
+            "description": "Glob filters; a file is searched when it matches any of them.",
+        },
+        "case_insensitive": {"type": "boolean", "description": "Ignore case when matching."},
+        "context_lines": {
+            "type": "integer",
+            "description": f"Context lines around each match (max {MAX_CONTEXT_LINES}).",
+        },
+        "max_matches": {
+            "type": "integer",
+            "description": "Maximum matches to return (default settings.ripgrep.max_results).",
+        },
+    },
+    "required": ["pattern"],
+}
+
+
+@dataclass(slots=True)
+class _FileSearchResult:
+    matches: list[SearchMatch] = field(default_factory=list)
+    is_binary: bool = False
+
+
+def _text_result(text: str) -> AgentToolResult:
+    return AgentToolResult(content=[TextContent(text=text)], details={})
+
+
+def _truncate_line(line_text: str) -> str:
+    if len(line_text) > MAX_MATCH_LINE_LENGTH:
+        return line_text[:MAX_MATCH_LINE_LENGTH] + TRUNCATION_SUFFIX
+    return line_text
+
+
+def _matches_include(relative_path: str, include: tuple[str, ...]) -> bool:
+    if not include:
+        return True
+    name = relative_path.rsplit("/", 1)[-1]
+    for glob_pattern in include:
+        target = relative_path if "/" in glob_pattern else name
+        if fnmatch(target, glob_pattern):
+            return True
+    return False
+
+
+def _walk_files(
+    search_root: Path,
+    root: Path,
+    ignore_manager: IgnoreManager,
+    include: tuple[str, ...],
+) -> Iterator[Path]:
+    if search_root.is_file():
+        yield search_root
+        return
+
+    for dirpath, dirnames, filenames in os.walk(search_root):
+        current = Path(dirpath)
+        dirnames[:] = sorted(
+            name for name in dirnames if not ignore_manager.should_ignore_dir(current / name)
+        )
+        for name in sorted(filenames):
+            file_path = current / name
+            if file_path.is_symlink() or ignore_manager.should_ignore(file_path):
+                continue
+            if _matches_include(file_path.relative_to(root).as_posix(), include):
+                yield file_path
+
+
+def _search_file(
+    file_path: Path,
+    root: Path,
+    regex: re.Pattern[str],
+    context: int,
+) -> _FileSearchResult:
+    result = _FileSearchResult()
+    try:
+        if file_path.stat().st_size > MAX_SEARCH_FILE_BYTES:
+            return result
+        raw = file_path.read_bytes()
+    except OSError:
+        return result
+
+    sniff = sniff_bytes(raw[:SNIFF_BYTES])
+    if sniff.is_binary:
+        result.is_binary = True
+        return result
+
+    lines = raw.decode(sniff.encoding, errors="replace").splitlines()
+    relative_path = file_path.relative_to(root).as_posix()
+    for index, line_text in enumerate(lines):
+        if regex.search(line_text) is None:
+            continue
+        result.matches.append(
+            SearchMatch(
+                file=relative_path,
+                line=index + 1,
+                text=line_text,
+                before=tuple(lines[max(0, index - context) : index]),
+                after=tuple(lines[index + 1 : index + 1 + context]),
+            )
+        )
+    return result
+
+
+def _search_in_order(
+    files: Iterable[Path],
+    search: Callable[[Path], _FileSearchResult],
+) -> Iterator[_FileSearchResult]:
+    """Search files on the pool, yielding results in walk order with bounded submissions."""
+    with ThreadPoolExecutor(max_workers=SEARCH_WORKER_COUNT) as executor:
+        pending: deque[Future[_FileSearchResult]] = deque()
+        try:
+            for file_path in files:
+                pending.append(executor.submit(search, file_path))
+                if len(pending) >= MAX_PENDING_SEARCHES:
+                    yield pending.popleft().result()
+            while pending:
+                yield pending.popleft().result()
+        finally:
+            for future in pending:
+                future.cancel()
+
+
+def _search_in_process(
+    query: SearchQuery,
+    regex: re.Pattern[str],
+    search_root: Path,
+    root: Path,
+) -> SearchOutcome:
+    outcome = SearchOutcome()
+    deadline = None if query.timeout is None else time.monotonic() + query.timeout
+    files = _walk_files(search_root, root, get_ignore_manager(root), query.include)
+    results = _search_in_order(
+        files, lambda file_path: _search_file(file_path, root, regex, query.context_lines)
+    )
+    with closing(results):
+        for file_result in results:
+            outcome.files_searched += 1
+            outcome.binary_files_skipped += int(file_result.is_binary)
+            remaining = query.max_matches - len(outcome.matches)
+            if len(file_result.matches) > remaining:
+                outcome.matches.extend(file_result.matches[:remaining])
+                outcome.truncated = True
+                break
+            outcome.matches.extend(file_result.matches)
+            if deadline is not None and time.monotonic() > deadline:
+                outcome.truncated = True
+                break
+    return outcome
+
+
+def _match_payload(match: SearchMatch, context: int) -> JsonObject:
+    payload: JsonObject = {
+        "file": match.file,
+        "line": match.line,
+        "text": _truncate_line(match.text),
+    }
+    if context > 0:
+        payload["before"] = [_truncate_line(text) for text in match.before]
+        payload["after"] = [_truncate_line(text) for text in match.after]
+    return payload
+
+
+def _compile_pattern(pattern: str, case_insensitive: bool) -> re.Pattern[str]:
+    flags = re.IGNORECASE if case_insensitive else 0
+    try:
+        return re.compile(pattern, flags)
+    except re.error as exc:
+        raise ToolRetryError(f"Invalid regular expression for tool 'grep': {exc}.") from exc
+
+
+async def _grep(query: SearchQuery, root: Path, *, record_metrics: bool) -> str:
+    regex = _compile_pattern(query.pattern, query.case_insensitive)
+    search_root = resolve_workspace_path(query.path, root)
+    relative_path = search_root.relative_to(root).as_posix()
+    started_at = time.perf_counter()
+
+    outcome = await search_with_ripgrep(replace(query, path=relative_path), root)
+    used_fallback = outcome is None
+    if outcome is None:
+        outcome = await asyncio.to_thread(_search_in_process, query, regex, search_root, root)
+    if record_metrics:
+        metrics.record_search(time.perf_counter() - started_at, used_fallback=used_fallback)
+
+    payload = {
+        "pattern": query.pattern,
+        "files_searched": outcome.files_searched,
+        "binary_files_skipped": outcome.binary_files_skipped,
+        "truncated": outcome.truncated,
+        "matches": [_match_payload(match, query.context_lines) for match in outcome.matches],
+    }
+    return json.dumps(payload, indent=2)
+
+
+def _require_int_arg(
+    args: JsonObject,
+    key: str,
+    *,
+    default: int,
+    minimum: int,
+    maximum: int,
+) -> int:
+    value = args.get(key, default)
+    if not isinstance(value, int) or isinstance(value, bool) or not minimum <= value <= maximum:
+        raise ToolRetryError(
+            f"Invalid arguments for tool 'grep': '{key}' must be an integer "
+            f"between {minimum} and {maximum}."
+        )
+    return value
+
+
+async def _execute_grep(
+    tool_call_id: str,
+    args: JsonObject,
+    signal: asyncio.Event | None,
+    on_update: AgentToolUpdateCallback,
+) -> AgentToolResult:
+    _ = (tool_call_id, on_update)
+    if signal is not None and signal.is_set():
+        raise UserAbortError("Tool execution aborted: grep")
+
+    pattern = args.get("pattern")
+    path = args.get("path", ".")
+    include = args.get("include", [])
+    case_insensitive = args.get("case_insensitive", False)
+    if not isinstance(pattern, str) or not pattern:
+        raise ToolRetryError(
+            "Invalid arguments for tool 'grep': 'pattern' must be a non-empty string."
+        )
+    if not isinstance(path, str):
+        raise ToolRetryError("Invalid arguments for tool 'grep': 'path' must be a string.")
+    if not isinstance(include, list) or not all(isinstance(item, str) for item in include):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'grep': 'include' must be a list of strings."
+        )
+    if not isinstance(case_insensitive, bool):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'grep': 'case_insensitive' must be a boolean."
+        )
+    context_lines = _require_int_arg(
+        args, "context_lines", default=0, minimum=0, maximum=MAX_CONTEXT_LINES
+    )
+    ripgrep_settings = get_ripgrep_settings()
+    default_max_matches = min(max(ripgrep_settings["max_results"], 1), MAX_MATCHES_LIMIT)
+    max_matches = _require_int_arg(
+        args, "max_matches", default=default_max_matches, minimum=1, maximum=MAX_MATCHES_LIMIT
+    )
+    query = SearchQuery(
+        pattern=pattern,
+        path=path,
+        include=tuple(include),
+        case_insensitive=case_insensitive,
+        context_lines=context_lines,
+        max_matches=max_matches,
+        timeout=ripgrep_settings["timeout"] or None,
+    )
+
+    result = await translate_file_tool_errors(
+        tool_name="grep",
+        filepath=path,
+        operation=_grep(
+            query,
+            Path.cwd().resolve(),
+            record_metrics=ripgrep_settings["enable_metrics"],
+        ),
+    )
+    return _text_result(result)
+
+
+grep = AgentTool(
+    name="grep",
+    label="grep",
+    description=_GREP_DESCRIPTION,
+    parameters=_GREP_PARAMETERS,
+    execute=_execute_grep,
+)
@@ -1,10 +1,18 @@
-"""Native tinyagent hashline_edit tool."""
+"""Native tinyagent hashline_edit tool.
+
+A refused edit raises ``EditError`` (a ``ToolRetryError``): the message tells
+the model what went wrong, prefixed with the failure kind, and the exception
+carries the same facts as fields (``kind``, ``filepath``, ``line``, the
+expected and recorded hashes, the line on disk) for callers that decide
+programmatically whether to re-read, correct the call or give up.
+"""
 
 from __future__ import annotations
 
 import asyncio
 import difflib
 from collections.abc import Callable
+from dataclasses import dataclass
 from pathlib import Path
 
 from tinyagent.agent_types import (
@@ -15,16 +23,26 @@ from tinyagent.agent_types import (
     TextContent,
 )
 
+from tunacode.constants import EditFailureKind
 from tunacode.exceptions import (
+    EditError,
     ToolRetryError,
     UserAbortError,
 )
 
-from tunacode.tools.hashline import parse_line_ref
+from tunacode.tools.hashline import HashedLine, content_hash, format_hashline, parse_line_ref
 from tunacode.tools.line_cache import get as _cache_get
 from tunacode.tools.line_cache import replace_range as _cache_replace_range
+from tunacode.tools.line_cache import store as _cache_store
 from tunacode.tools.line_cache import update_lines as _cache_update_lines
 from tunacode.tools.utils.file_errors import translate_file_tool_errors
+from tunacode.tools.utils.file_transaction import FileTransaction
+from tunacode.tools.utils.line_endings import (
+    carry_line_endings,
+    dominant_ending,
+    join_lines,
+    split_lines,
+)
 
 STALE_REF_MESSAGE = (
     "File has changed since last read — line {line} hash mismatch "
@@ -37,10 +55,22 @@ LINE_NOT_CACHED_MESSAGE = (
     "Re-read the file to include this line."
 )
 FILE_NOT_FOUND_MESSAGE = "File '{filepath}' not found. Cannot edit."
+PERMISSION_DENIED_MESSAGE = "Permission denied for '{filepath}': {error}. Do not retry this edit."
+EDIT_ERROR_PREFIX = "hashline_edit failed ({kind}): "
+TRAILING_NEWLINE_ADDED = " Added a newline at end of file."
+TRAILING_NEWLINE_REMOVED = " Removed the newline at end of file."
 DEFAULT_ENCODING = "utf-8"
 INVALID_REF_MESSAGE = (
     "Invalid line reference '{ref}': {error}. Use '<line>:<hash>' from read_file output."
 )
+CONFLICT_REGION_RADIUS = 10
+CONFLICT_REGION_HEADER = (
+    "Current on-disk content of '{filepath}' around line {line} "
+    "(lines {start}-{end} of {total}). The cache now holds exactly these lines, "
+    "so you can retry the edit with these references without calling read_file:"
+)
+FILE_TAG_OPEN = "<file>"
+FILE_TAG_CLOSE = "</file>"
 
 _HASHLINE_EDIT_DESCRIPTION = """Edit a file using hash-validated line references."""
 
@@ -59,6 +89,12 @@ _HASHLINE_EDIT_PARAMETERS: JsonObject = {
         "end": {"type": "string", "description": "End line reference for replace_range."},
         "after": {"type": "string", "description": "Line reference for insert_after."},
         "new": {"type": "string", "description": "Replacement or inserted text."},
+        "trailing_newline": {
+            "type": "boolean",
+            "description": (
+                "Whether the file should end with a newline. Omit to keep the file's current state."
+            ),
+        },
     },
     "required": ["filepath", "operation"],
 }
@@ -79,44 +115,147 @@ def _optional_string_arg(args: JsonObject, key: str) -> str | None:
     return value
 
 
+def _current_region(filepath: str, line_number: int) -> tuple[str, str | None]:
+    """Render the on-disk lines around ``line_number`` and cache them as a fresh read.
+
+    Also returns the on-disk text of ``line_number`` itself, when it exists.
+    """
+    try:
+        lines, _ = _read_file_lines(filepath)
+    except (OSError, UnicodeDecodeError):
+        return "", None
+    if not lines:
+        return "", None
+
+    center = min(max(line_number, 1), len(lines))
+    start = max(1, center - CONFLICT_REGION_RADIUS)
+    end = min(len(lines), center + CONFLICT_REGION_RADIUS)
+    region = [
+        HashedLine(line_number=number, hash=content_hash(text), content=text)
+        for number, text in enumerate(lines[start - 1 : end], start=start)
+    ]
+    _cache_store(filepath, region)
+
+    header = CONFLICT_REGION_HEADER.format(
+        filepath=filepath,
+        line=line_number,
+        start=start,
+        end=end,
+        total=len(lines),
+    )
+    body = "\n".join(format_hashline(hashed_line) for hashed_line in region)
+    on_disk_line = lines[line_number - 1] if 1 <= line_number <= len(lines) else None
+    return f"\n\n{header}\n{FILE_TAG_OPEN}\n{body}\n{FILE_TAG_CLOSE}", on_disk_line
+
+
+def _edit_error(
+    kind: EditFailureKind,
+    filepath: str,
+    message: str,
+    *,
+    line: int | None = None,
+    expected: str | None = None,
+    actual: str | None = None,
+    actual_line: str | None = None,
+    original_error: Exception | None = None,
+) -> EditError:
+    return EditError(
+        kind,
+        EDIT_ERROR_PREFIX.format(kind=kind.value) + message,
+        filepath=filepath,
+        line=line,
+        expected=expected,
+        actual=actual,
+        actual_line=actual_line,
+        original_error=original_error,
+    )
+
+
+def _conflict_error(
+    kind: EditFailureKind,
+    filepath: str,
+    line_number: int,
+    message: str,
+    *,
+    expected: str | None = None,
+    actual: str | None = None,
+) -> EditError:
+    """An ``EditError`` that carries the current on-disk region for a retry."""
+    region, on_disk_line = _current_region(filepath, line_number)
+    return _edit_error(
+        kind,
+        filepath,
+        message + region,
+        line=line_number,
+        expected=expected,
+        actual=actual,
+        actual_line=on_disk_line,
+    )
+
+
 def _validate_ref(filepath: str, ref: str) -> int:
     try:
         line_number, expected_hash = parse_line_ref(ref)
     except ValueError as exc:
-        raise ToolRetryError(INVALID_REF_MESSAGE.format(ref=ref, error=str(exc))) from exc
+        raise _edit_error(
+            EditFailureKind.INVALID_REF,
+            filepath,
+            INVALID_REF_MESSAGE.format(ref=ref, error=str(exc)),
+        ) from exc
 
     cached = _cache_get(filepath)
     if cached is None:
-        raise ToolRetryError(UNCACHED_FILE_MESSAGE.format(filepath=filepath))
+        raise _conflict_error(
+            EditFailureKind.NOT_READ,
+            filepath,
+            line_number,
+            UNCACHED_FILE_MESSAGE.format(filepath=filepath),
+            expected=expected_hash,
+        )
 
     cached_line = cached.get(line_number)
     if cached_line is None:
-        raise ToolRetryError(LINE_NOT_CACHED_MESSAGE.format(line=line_number, filepath=filepath))
+        raise _conflict_error(
+            EditFailureKind.NOT_READ,
+            filepath,
+            line_number,
+            LINE_NOT_CACHED_MESSAGE.format(line=line_number, filepath=filepath),
+            expected=expected_hash,
+        )
 
     if cached_line.hash != expected_hash:
-        raise ToolRetryError(
+        raise _conflict_error(
+            EditFailureKind.CONTEXT_MISMATCH,
+            filepath,
+            line_number,
             STALE_REF_MESSAGE.format(
                 line=line_number,
                 expected=expected_hash,
                 actual=cached_line.hash,
-            )
+            ),
+            expected=expected_hash,
+            actual=cached_line.hash,
         )
 
     return line_number
 
 
-def _read_file_lines(filepath: str) -> tuple[list[str], bool]:
-    with open(filepath, encoding=DEFAULT_ENCODING) as file_obj:
+def _read_file_lines(filepath: str) -> tuple[list[str], list[str]]:
+    """Return the file's lines and, separately, each line's original ending."""
+    with open(filepath, encoding=DEFAULT_ENCODING, newline="") as file_obj:
         content = file_obj.read()
-    return content.splitlines(), content.endswith("\n")
+    return split_lines(content)
+
 
+def _write_file_lines(filepath: str, lines: list[str], endings: list[str]) -> None:
+    transaction = FileTransaction(encoding=DEFAULT_ENCODING)
+    transaction.stage(filepath, join_lines(lines, endings))
+    transaction.commit()
 
-def _write_file_lines(filepath: str, lines: list[str], preserve_trailing_newline: bool) -> None:
-    serialized = "\n".join(lines)
-    if preserve_trailing_newline:
-        serialized += "\n"
-    with open(filepath, "w", encoding=DEFAULT_ENCODING) as file_obj:
-        file_obj.write(serialized)
+
+def _content_lines(new_content: str) -> list[str]:
+    lines, _ = split_lines(new_content)
+    return lines
 
 
 def _make_diff(filepath: str, original_lines: list[str], new_lines: list[str]) -> str:
@@ -140,13 +279,22 @@ def _apply_replace(
     line_number = _validate_ref(filepath, line_ref)
     index = line_number - 1
     if index < 0 or index >= len(lines):
-        raise ToolRetryError(f"Line {line_number} is out of range (file has {len(lines)} lines).")
+        raise _conflict_error(
+            EditFailureKind.OUT_OF_RANGE,
+            filepath,
+            line_number,
+            f"Line {line_number} is out of range (file has {len(lines)} lines).",
+        )
 
-    new_lines = list(lines)
-    new_lines[index] = new_content
+    # A trailing line break in ``new`` ends the line; it does not add an empty one.
+    replacement_lines = _content_lines(new_content) or [""]
+    new_lines = lines[:index] + replacement_lines + lines[index + 1 :]
 
     def cache_mutation() -> None:
-        _cache_update_lines(filepath, {line_number: new_content})
+        if len(replacement_lines) == 1:
+            _cache_update_lines(filepath, {line_number: replacement_lines[0]})
+        else:
+            _cache_replace_range(filepath, line_number, line_number, replacement_lines)
 
     return new_lines, f"File '{filepath}' updated: replaced line {line_number}.", cache_mutation
 
@@ -173,11 +321,14 @@ def _apply_replace_range(
     start_index = start_line - 1
     end_index = end_line
     if start_index < 0 or end_index > len(lines):
-        raise ToolRetryError(
-            f"Line range {start_line}-{end_line} is out of bounds (file has {len(lines)} lines)."
+        raise _conflict_error(
+            EditFailureKind.OUT_OF_RANGE,
+            filepath,
+            end_line,
+            f"Line range {start_line}-{end_line} is out of bounds (file has {len(lines)} lines).",
         )
 
-    replacement_lines = new_content.splitlines() if new_content else []
+    replacement_lines = _content_lines(new_content)
     new_lines = lines[:start_index] + replacement_lines + lines[end_index:]
 
     def cache_mutation() -> None:
@@ -201,9 +352,14 @@ def _apply_insert_after(
     after_line = _validate_ref(filepath, after_ref)
     insert_index = after_line
     if insert_index > len(lines):
-        raise ToolRetryError(f"Line {after_line} is out of range (file has {len(lines)} lines).")
+        raise _conflict_error(
+            EditFailureKind.OUT_OF_RANGE,
+            filepath,
+            after_line,
+            f"Line {after_line} is out of range (file has {len(lines)} lines).",
+        )
 
-    insertion_lines = new_content.splitlines() if new_content else []
+    insertion_lines = _content_lines(new_content)
     new_lines = lines[:insert_index] + insertion_lines + lines[insert_index:]
 
     def cache_mutation() -> None:
@@ -219,50 +375,103 @@ def _apply_insert_after(
     )
 
 
-async def _run_hashline_edit(
-    filepath: str,
-    operation: str,
-    line: str | None = None,
-    start: str | None = None,
-    end: str | None = None,
-    after: str | None = None,
-    new: str = "",
-) -> str:
+@dataclass(frozen=True, slots=True)
+class _EditRequest:
+    operation: str
+    line: str | None = None
+    start: str | None = None
+    end: str | None = None
+    after: str | None = None
+    new: str = ""
+    # None preserves whether the file ends with a newline.
+    trailing_newline: bool | None = None
+
+
+async def _run_hashline_edit(filepath: str, request: _EditRequest) -> str:
     path = Path(filepath)
     if not await asyncio.to_thread(path.exists):
-        raise ToolRetryError(FILE_NOT_FOUND_MESSAGE.format(filepath=filepath))
-
-    original_lines, had_trailing_newline = await asyncio.to_thread(_read_file_lines, filepath)
-    if operation == "replace":
-        new_lines, description, cache_mutation = _apply_replace(filepath, original_lines, line, new)
-    elif operation == "replace_range":
-        new_lines, description, cache_mutation = _apply_replace_range(
+        raise _edit_error(
+            EditFailureKind.FILE_NOT_FOUND,
             filepath,
-            original_lines,
-            start,
-            end,
-            new,
+            FILE_NOT_FOUND_MESSAGE.format(filepath=filepath),
         )
-    elif operation == "insert_after":
-        new_lines, description, cache_mutation = _apply_insert_after(
+
+    try:
+        return await _edit_existing_file(filepath, request)
+    except PermissionError as exc:
+        raise _edit_error(
+            EditFailureKind.PERMISSION_DENIED,
             filepath,
-            original_lines,
-            after,
-            new,
-        )
-    else:
-        raise ToolRetryError(
-            f"Unknown operation '{operation}'. Use 'replace', 'replace_range', or 'insert_after'."
-        )
+            PERMISSION_DENIED_MESSAGE.format(filepath=filepath, error=exc.strerror or exc),
+            original_error=exc,
+        ) from exc
+
+
+def _apply_operation(
+    filepath: str, lines: list[str], request: _EditRequest
+) -> tuple[list[str], str, Callable[[], None]]:
+    if request.operation == "replace":
+        return _apply_replace(filepath, lines, request.line, request.new)
+    if request.operation == "replace_range":
+        return _apply_replace_range(filepath, lines, request.start, request.end, request.new)
+    if request.operation == "insert_after":
+        return _apply_insert_after(filepath, lines, request.after, request.new)
+    raise ToolRetryError(
+        f"Unknown operation '{request.operation}'. "
+        "Use 'replace', 'replace_range', or 'insert_after'."
+    )
+
 
-    await asyncio.to_thread(_write_file_lines, filepath, new_lines, had_trailing_newline)
+def _set_trailing_newline(endings: list[str], trailing_newline: bool, fallback: str) -> str:
+    """Apply an explicit final-newline choice; returns a note when it changed the file."""
+    if not endings or bool(endings[-1]) == trailing_newline:
+        return ""
+    endings[-1] = fallback if trailing_newline else ""
+    return TRAILING_NEWLINE_ADDED if trailing_newline else TRAILING_NEWLINE_REMOVED
+
+
+async def _edit_existing_file(filepath: str, request: _EditRequest) -> str:
+    original_lines, original_endings = await asyncio.to_thread(_read_file_lines, filepath)
+    new_lines, description, cache_mutation = _apply_operation(filepath, original_lines, request)
+
+    new_endings = carry_line_endings(original_lines, original_endings, new_lines)
+    if request.trailing_newline is not None:
+        description += _set_trailing_newline(
+            new_endings, request.trailing_newline, dominant_ending(original_endings)
+        )
+    await asyncio.to_thread(_write_file_lines, filepath, new_lines, new_endings)
     cache_mutation()
     diff_text = _make_diff(filepath, original_lines, new_lines)
     output = f"{description}\n\n{diff_text}"
     return output
 
 
-async def _execute_hashline_edit(  # noqa: C901
+def _parse_edit_request(args: JsonObject) -> _EditRequest:
+    operation = args.get("operation")
+    new = args.get("new", "")
+    trailing_newline = args.get("trailing_newline")
+    if not isinstance(operation, str):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'hashline_edit': 'operation' must be a string."
+        )
+    if not isinstance(new, str):
+        raise ToolRetryError("Invalid arguments for tool 'hashline_edit': 'new' must be a string.")
+    if trailing_newline is not None and not isinstance(trailing_newline, bool):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'hashline_edit': 'trailing_newline' must be a boolean."
+        )
+    return _EditRequest(
+        operation=operation,
+        line=_optional_string_arg(args, "line"),
+        start=_optional_string_arg(args, "start"),
+        end=_optional_string_arg(args, "end"),
+        after=_optional_string_arg(args, "after"),
+        new=new,
+        trailing_newline=trailing_newline,
+    )
+
+
+async def _execute_hashline_edit(
     tool_call_id: str,
     args: JsonObject,
     signal: asyncio.Event | None,
@@ -273,36 +482,16 @@ async def _execute_hashline_edit(  # noqa: C901
         raise UserAbortError("Tool execution aborted: hashline_edit")
 
     filepath = args.get("filepath")
-    operation = args.get("operation")
-    line = _optional_string_arg(args, "line")
-    start = _optional_string_arg(args, "start")
-    end = _optional_string_arg(args, "end")
-    after = _optional_string_arg(args, "after")
-    new = args.get("new", "")
-
     if not isinstance(filepath, str):
         raise ToolRetryError(
             "Invalid arguments for tool 'hashline_edit': 'filepath' must be a string."
         )
-    if not isinstance(operation, str):
-        raise ToolRetryError(
-            "Invalid arguments for tool 'hashline_edit': 'operation' must be a string."
-        )
-    if not isinstance(new, str):
-        raise ToolRetryError("Invalid arguments for tool 'hashline_edit': 'new' must be a string.")
+    request = _parse_edit_request(args)
 
     result = await translate_file_tool_errors(
         tool_name="hashline_edit",
         filepath=filepath,
-        operation=_run_hashline_edit(
-            filepath=filepath,
-            operation=operation,
-            line=line,
-            start=start,
-            end=end,
-            after=after,
-            new=new,
-        ),
+        operation=_run_hashline_edit(filepath, request),
     )
 
     return _text_result(result)
@@ -0,0 +1,186 @@
+"""Native tinyagent list_directory tool."""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import os
+import stat
+from datetime import UTC, datetime
+from pathlib import Path
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    JsonObject,
+    TextContent,
+)
+
+from tunacode.exceptions import ToolRetryError, UserAbortError
+
+from tunacode.tools.ignore import get_ignore_manager
+from tunacode.tools.utils.file_errors import translate_file_tool_errors
+from tunacode.tools.utils.workspace import resolve_workspace_path
+
+DEFAULT_LIST_LIMIT = 500
+MAX_LIST_LIMIT = 2000
+
+ENTRY_TYPE_FILE = "file"
+ENTRY_TYPE_DIRECTORY = "directory"
+ENTRY_TYPE_SYMLINK = "symlink"
+ENTRY_TYPE_OTHER = "other"
+
+ERROR_NOT_A_DIRECTORY = "Path '{path}' is not a directory. Use read_file for files."
+
+_LIST_DIRECTORY_DESCRIPTION = """List the entries of a directory as structured JSON.
+
+Each entry reports name, type (file, directory, symlink, other), size in bytes,
+mtime (UTC ISO-8601), is_symlink, and symlink_target for symlinks. Symlinks are
+reported but never followed. Entries are sorted by name so output is stable.
+Use offset/limit to page through large directories; next_offset is null on the
+last page. Gitignored entries are skipped unless respect_gitignore is false.
+"""
+
+_LIST_DIRECTORY_PARAMETERS: JsonObject = {
+    "type": "object",
+    "additionalProperties": False,
+    "properties": {
+        "path": {
+            "type": "string",
+            "description": "Directory to list. Defaults to the working directory.",
+        },
+        "offset": {"type": "integer", "description": "0-based entry offset to start from."},
+        "limit": {"type": "integer", "description": "Maximum number of entries to return."},
+        "respect_gitignore": {
+            "type": "boolean",
+            "description": "Skip gitignored entries. Defaults to true.",
+        },
+    },
+}
+
+
+def _text_result(text: str) -> AgentToolResult:
+    return AgentToolResult(content=[TextContent(text=text)], details={})
+
+
+def _resolve_directory(path: str, root: Path) -> Path:
+    resolved = resolve_workspace_path(path, root)
+    if not resolved.is_dir():
+        raise ToolRetryError(ERROR_NOT_A_DIRECTORY.format(path=path))
+    return resolved
+
+
+def _entry_type(mode: int) -> str:
+    if stat.S_ISLNK(mode):
+        return ENTRY_TYPE_SYMLINK
+    if stat.S_ISDIR(mode):
+        return ENTRY_TYPE_DIRECTORY
+    if stat.S_ISREG(mode):
+        return ENTRY_TYPE_FILE
+    return ENTRY_TYPE_OTHER
+
+
+def _describe_entry(entry: os.DirEntry[str]) -> JsonObject:
+    entry_stat = entry.stat(follow_symlinks=False)
+    is_symlink = stat.S_ISLNK(entry_stat.st_mode)
+    mtime = datetime.fromtimestamp(entry_stat.st_mtime, tz=UTC)
+    return {
+        "name": entry.name,
+        "type": _entry_type(entry_stat.st_mode),
+        "size": entry_stat.st_size,
+        "mtime": mtime.isoformat(),
+        "is_symlink": is_symlink,
+        "symlink_target": os.readlink(entry.path) if is_symlink else None,
+    }
+
+
+def _is_ignored(entry: os.DirEntry[str], root: Path) -> bool:
+    ignore_manager = get_ignore_manager(root)
+    entry_path = Path(entry.path)
+    if entry.is_dir(follow_symlinks=False):
+        return ignore_manager.should_ignore_dir(entry_path)
+    return ignore_manager.should_ignore(entry_path)
+
+
+def _list_directory_sync(
+    path: str,
+    root: Path,
+    offset: int,
+    limit: int,
+    respect_gitignore: bool,
+) -> str:
+    directory = _resolve_directory(path, root)
+    with os.scandir(directory) as scanner:
+        entries = [
+            entry for entry in scanner if not (respect_gitignore and _is_ignored(entry, root))
+        ]
+    entries.sort(key=lambda entry: entry.name)
+
+    page = entries[offset : offset + limit]
+    next_offset = offset + len(page)
+    payload = {
+        "path": str(directory),
+        "total": len(entries),
+        "offset": offset,
+        "next_offset": next_offset if next_offset < len(entries) else None,
+        "entries": [_describe_entry(entry) for entry in page],
+    }
+    return json.dumps(payload, indent=2)
+
+
+async def _execute_list_directory(
+    tool_call_id: str,
+    args: JsonObject,
+    signal: asyncio.Event | None,
+    on_update: AgentToolUpdateCallback,
+) -> AgentToolResult:
+    _ = (tool_call_id, on_update)
+    if signal is not None and signal.is_set():
+        raise UserAbortError("Tool execution aborted: list_directory")
+
+    path = args.get("path", ".")
+    offset = args.get("offset", 0)
+    limit = args.get("limit", DEFAULT_LIST_LIMIT)
+    respect_gitignore = args.get("respect_gitignore", True)
+    if not isinstance(path, str):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'list_directory': 'path' must be a string."
+        )
+    if not isinstance(offset, int) or isinstance(offset, bool) or offset < 0:
+        raise ToolRetryError(
+            "Invalid arguments for tool 'list_directory': 'offset' must be a non-negative integer."
+        )
+    if not isinstance(limit, int) or isinstance(limit, bool) or not 1 <= limit <= MAX_LIST_LIMIT:
+        raise ToolRetryError(
+            "Invalid arguments for tool 'list_directory': "
+            f"'limit' must be an integer between 1 and {MAX_LIST_LIMIT}."
+        )
+    if not isinstance(respect_gitignore, bool):
+        raise ToolRetryError(
+            "Invalid arguments for tool 'list_directory': 'respect_gitignore' must be a boolean."
+        )
+
+    root = Path.cwd().resolve()
+    result = await translate_file_tool_errors(
+        tool_name="list_directory",
+        filepath=path,
+        operation=asyncio.to_thread(
+            _list_directory_sync,
+            path,
+            root,
+            offset,
+            limit,
+            respect_gitignore,
+        ),
+    )
+    return _text_result(result)
+
+
+list_directory = AgentTool(
+    name="list_directory",
+    label="list_directory",
+    description=_LIST_DIRECTORY_DESCRIPTION,
+    parameters=_LIST_DIRECTORY_PARAMETERS,
+    execute=_execute_list_directory,
+)
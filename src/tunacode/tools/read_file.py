@@ -3,7 +3,12 @@
 from __future__ import annotations
 
 import asyncio
+import base64
 import os
+from collections import deque
+from collections.abc import Iterable
+from dataclasses import dataclass
+from typing import TextIO
 
 from tinyagent.agent_types import (
     AgentTool,
@@ -13,21 +18,14 @@ from tinyagent.agent_types import (
     TextContent,
 )
 
-from tunacode.exceptions import (
-    ToolExecutionError,
-    ToolRetryError,
-    UserAbortError,
-)
+from tunacode.configuration.limits import get_read_max_bytes, get_read_window_lines
+from tunacode.exceptions import ToolRetryError, UserAbortError
 
 from tunacode.tools.hashline import HashedLine, content_hash, format_hashline
 from tunacode.tools.line_cache import store as _cache_store
+from tunacode.tools.utils.binary_detection import FileSniff, sniff_file
 from tunacode.tools.utils.file_errors import translate_file_tool_errors
 
-KB_BYTES = 1024
-MAX_FILE_SIZE_KB = 100
-MAX_FILE_SIZE = MAX_FILE_SIZE_KB * KB_BYTES
-ERROR_FILE_TOO_LARGE = f"Error: File '{{filepath}}' is too large (> {MAX_FILE_SIZE_KB}KB)."
-MSG_FILE_SIZE_LIMIT = " Please specify a smaller file or use other tools to process it."
 DEFAULT_READ_LIMIT = 2000
 MAX_LINE_LENGTH = 2000
 DEFAULT_FILE_ENCODING = "utf-8"
@@ -36,12 +34,33 @@ FILE_TAG_OPEN = "<file>"
 FILE_TAG_CLOSE = "</file>"
 MORE_LINES_MESSAGE = "(File has more lines. Use 'offset' to read beyond line {last_line})"
 END_OF_FILE_MESSAGE = "(End of file - total {total_lines} lines)"
+NO_TRAILING_NEWLINE_MESSAGE = "(End of file - total {total_lines} lines, no newline at end)"
+BINARY_FILE_MESSAGE = (
+    "Binary file '{filepath}': {total_bytes} bytes, detected type: {detected_type}. "
+    "Not shown as text; pass as_base64=true to read its bytes as base64."
+)
+BASE64_ENCODING = "base64"
+BASE64_TAG_OPEN = "<file_base64>"
+BASE64_TAG_CLOSE = "</file_base64>"
+BASE64_STATUS_MESSAGE = "(Returned {returned_bytes} of {total_bytes} bytes as base64)"
+INVALID_ARGUMENTS_PREFIX = "Invalid arguments for tool 'read_file': "
+OMITTED_LINES_MARKER = "... (lines {first}-{last} omitted) ..."
+WINDOWED_MESSAGE = (
+    "(File windowed: {total_bytes} bytes exceeds the {max_bytes}-byte read limit; "
+    "showing lines 1-{head_end} and {tail_first}-{total_lines} "
+    "of {total_lines} lines. Use 'start_line'/'end_line' to read lines "
+    "{omitted_first}-{omitted_last})"
+)
 
 _READ_FILE_DESCRIPTION = """Read the contents of a file with line limiting and truncation.
 
 Each call replaces the cache for filepath with only the lines returned by that read.
 hashline_edit can only edit lines present in the current cache and raises ToolRetryError
 with an offset hint when a requested line is missing from cache.
+
+A file larger than the read limit is returned windowed (its first and last lines) unless
+a line range is given; page through it with start_line/end_line or offset/limit.
+Binary files are summarized (size and detected type) unless as_base64 is true.
 """
 
 _READ_FILE_PARAMETERS: JsonObject = {
@@ -51,13 +70,57 @@ _READ_FILE_PARAMETERS: JsonObject = {
         "filepath": {"type": "string", "description": "Absolute path to the file to read."},
         "offset": {"type": "integer", "description": "0-based line offset to start from."},
         "limit": {"type": "integer", "description": "Maximum number of lines to read."},
+        "start_line": {
+            "type": "integer",
+            "description": "1-based first line to read (instead of offset).",
+        },
+        "end_line": {
+            "type": "integer",
+            "description": "1-based last line to read, inclusive (instead of limit).",
+        },
+        "max_bytes": {
+            "type": "integer",
+            "description": "Maximum bytes of file content to return; capped by the read limit.",
+        },
+        "as_base64": {
+            "type": "boolean",
+            "description": "Return a binary file's bytes as base64 instead of a summary.",
+        },
     },
     "required": ["filepath"],
 }
 
 
-def _text_result(text: str) -> AgentToolResult:
-    return AgentToolResult(content=[TextContent(text=text)], details={})
+@dataclass(frozen=True, slots=True)
+class ReadRange:
+    """Lines to read: ``offset`` is 0-based; ``explicit`` when the caller chose them."""
+
+    offset: int
+    limit: int
+    explicit: bool
+
+
+@dataclass(slots=True)
+class _ReadOutput:
+    text: str
+    hashed_lines: list[HashedLine]
+    total_lines: int | None
+    windowed: bool = False
+    # Known only when the read reached the end of the file.
+    ends_with_newline: bool | None = None
+
+    def ranges(self) -> list[list[int]]:
+        ranges: list[list[int]] = []
+        for hashed_line in self.hashed_lines:
+            if ranges and ranges[-1][1] == hashed_line.line_number - 1:
+                ranges[-1][1] = hashed_line.line_number
+            else:
+                ranges.append([hashed_line.line_number, hashed_line.line_number])
+        return ranges
+
+
+def _text_result(text: str, details: JsonObject) -> AgentToolResult:
+    return AgentToolResult(content=[TextContent(text=text)], details=details)
 
 
 def _truncate_line(line_text: str, line_limit: int) -> str:
@@ -66,76 +129,286 @@ def _truncate_line(line_text: str, line_limit: int) -> str:
     return line_text
 
 
-async def _run_read_file(filepath: str, offset: int = 0, limit: int | None = None) -> str:
-    if os.path.getsize(filepath) > MAX_FILE_SIZE:
-        raise ToolExecutionError(
-            tool_name="read_file",
-            message=ERROR_FILE_TOO_LARGE.format(filepath=filepath) + MSG_FILE_SIZE_LIMIT,
+def _tag_line(line_number: int, line_text: str) -> tuple[HashedLine, str]:
+    line_hash = content_hash(line_text)
+    hashed_line = HashedLine(line_number=line_number, hash=line_hash, content=line_text)
+    display_line = HashedLine(
+        line_number=line_number,
+        hash=line_hash,
+        content=_truncate_line(line_text, MAX_LINE_LENGTH),
+    )
+    return hashed_line, format_hashline(display_line)
+
+
+def _wrap_file_body(display_lines: list[str], status: str) -> str:
+    return f"{FILE_TAG_OPEN}\n" + "\n".join(display_lines) + f"\n\n{status}\n{FILE_TAG_CLOSE}"
+
+
+def _end_of_file_status(total_lines: int, ends_with_newline: bool) -> str:
+    # An empty file has no last line to end with a newline.
+    if ends_with_newline or not total_lines:
+        return END_OF_FILE_MESSAGE.format(total_lines=total_lines)
+    return NO_TRAILING_NEWLINE_MESSAGE.format(total_lines=total_lines)
+
+
+def _open_text(path: str, encoding: str) -> TextIO:
+    # Text mode decodes whole lines, so no read boundary can split a UTF-8 sequence;
+    # stray invalid bytes become U+FFFD rather than failing the read.
+    return open(path, encoding=encoding, errors="replace")
+
+
+def _read_range_sync(
+    path: str, read_range: ReadRange, max_bytes: int, *, encoding: str
+) -> _ReadOutput:
+    display_lines: list[str] = []
+    hashed_lines: list[HashedLine] = []
+    skipped_lines = 0
+    used_bytes = 0
+    with _open_text(path, encoding) as file_obj:
+        for _ in range(read_range.offset):
+            if not file_obj.readline():
+                break
+            skipped_lines += 1
+        unread_line = ""
+        last_line_read = ""
+        while len(hashed_lines) < read_range.limit:
+            line = file_obj.readline()
+            if not line:
+                break
+            last_line_read = line
+            line_text = line.rstrip("\n")
+            used_bytes += len(line_text.encode(DEFAULT_FILE_ENCODING))
+            if hashed_lines and used_bytes > max_bytes:
+                unread_line = line
+                break
+            hashed_line, display_line = _tag_line(skipped_lines + len(hashed_lines) + 1, line_text)
+            hashed_lines.append(hashed_line)
+            display_lines.append(display_line)
+        has_more_lines = bool(unread_line) or bool(file_obj.readline())
+
+    if read_range.offset and not hashed_lines:
+        raise ToolRetryError(
+            f"Line range starts past the end of '{path}' ({skipped_lines} lines). "
+            f"Use start_line <= {skipped_lines}."
+        )
+
+    last_line = skipped_lines + len(hashed_lines)
+    if has_more_lines:
+        text = _wrap_file_body(display_lines, MORE_LINES_MESSAGE.format(last_line=last_line))
+        return _ReadOutput(text=text, hashed_lines=hashed_lines, total_lines=None)
+    ends_with_newline = last_line_read.endswith("\n")
+    text = _wrap_file_body(display_lines, _end_of_file_status(last_line, ends_with_newline))
+    return _ReadOutput(
+        text=text,
+        hashed_lines=hashed_lines,
+        total_lines=last_line,
+        ends_with_newline=ends_with_newline,
+    )
+
+
+def _tag_entries(
+    entries: Iterable[tuple[int, str]],
+    hashed_lines: list[HashedLine],
+    display_lines: list[str],
+) -> None:
+    for line_number, line_text in entries:
+        hashed_line, display_line = _tag_line(line_number, line_text)
+        hashed_lines.append(hashed_line)
+        display_lines.append(display_line)
+
+
+def _read_window_sync(
+    path: str,
+    *,
+    total_bytes: int,
+    max_bytes: int,
+    head_lines: int,
+    tail_lines: int,
+    encoding: str,
+) -> _ReadOutput:
+    head: list[tuple[int, str]] = []
+    tail: deque[tuple[int, str]] = deque(maxlen=tail_lines)
+    total_lines = 0
+    ends_with_newline = False
+    with _open_text(path, encoding) as file_obj:
+        for line in file_obj:
+            total_lines += 1
+            ends_with_newline = line.endswith("\n")
+            entry = (total_lines, line.rstrip("\n"))
+            if len(head) < head_lines:
+                head.append(entry)
+            elif tail_lines:
+                tail.append(entry)
+
+    hashed_lines: list[HashedLine] = []
+    display_lines: list[str] = []
+    _tag_entries(head, hashed_lines, display_lines)
+    tail_first = tail[0][0] if tail else total_lines + 1
+    head_end = head[-1][0] if head else 0
+    if tail_first - head_end <= 1:
+        _tag_entries(tail, hashed_lines, display_lines)
+        text = _wrap_file_body(display_lines, _end_of_file_status(total_lines, ends_with_newline))
+        return _ReadOutput(
+            text=text,
+            hashed_lines=hashed_lines,
+            total_lines=total_lines,
+            ends_with_newline=ends_with_newline,
         )
 
-    effective_limit = limit if limit is not None else DEFAULT_READ_LIMIT
-
-    def _read_sync(
-        path: str,
-        line_limit: int,
-        line_offset: int,
-        line_count: int,
-    ) -> tuple[str, list[HashedLine]]:
-        display_lines: list[str] = []
-        hashed_lines: list[HashedLine] = []
-        skipped_lines = 0
-
-        with open(path, encoding=DEFAULT_FILE_ENCODING) as file_obj:
-            for _ in range(line_offset):
-                line = file_obj.readline()
-                if not line:
-                    break
-                skipped_lines += 1
-
-            for line_index in range(line_count):
-                line = file_obj.readline()
-                if not line:
-                    break
-                line_text = line.rstrip("\n")
-                line_number = skipped_lines + line_index + 1
-
-                line_hash = content_hash(line_text)
-                hashed_line = HashedLine(line_number=line_number, hash=line_hash, content=line_text)
-                hashed_lines.append(hashed_line)
-
-                display_line = HashedLine(
-                    line_number=line_number,
-                    hash=line_hash,
-                    content=_truncate_line(line_text, line_limit),
-                )
-                display_lines.append(format_hashline(display_line))
-
-            extra_line = file_obj.readline()
-            has_more_lines = bool(extra_line)
-
-        last_line = skipped_lines + len(display_lines)
-        output = f"{FILE_TAG_OPEN}\n" + "\n".join(display_lines)
-        if has_more_lines:
-            output += f"\n\n{MORE_LINES_MESSAGE.format(last_line=last_line)}"
-            output += f"\n{FILE_TAG_CLOSE}"
-            return output, hashed_lines
-
-        output += f"\n\n{END_OF_FILE_MESSAGE.format(total_lines=last_line)}"
-        output += f"\n{FILE_TAG_CLOSE}"
-        return output, hashed_lines
-
-    result, hashed = await asyncio.to_thread(
-        _read_sync,
-        filepath,
-        MAX_LINE_LENGTH,
-        offset,
-        effective_limit,
+    display_lines.append(OMITTED_LINES_MARKER.format(first=head_end + 1, last=tail_first - 1))
+    _tag_entries(tail, hashed_lines, display_lines)
+    status = WINDOWED_MESSAGE.format(
+        total_bytes=total_bytes,
+        max_bytes=max_bytes,
+        head_end=head_end,
+        tail_first=tail_first,
+        total_lines=total_lines,
+        omitted_first=head_end + 1,
+        omitted_last=tail_first - 1,
+    )
+    return _ReadOutput(
+        text=_wrap_file_body(display_lines, status),
+        hashed_lines=hashed_lines,
+        total_lines=total_lines,
+        windowed=True,
+        ends_with_newline=ends_with_newline,
     )
-    _cache_store(filepath, hashed)
-    return result
 
 
-async def _execute_read_file(  # noqa: C901
+def _binary_summary(filepath: str, total_bytes: int, sniff: FileSniff) -> tuple[str, JsonObject]:
+    text = BINARY_FILE_MESSAGE.format(
+        filepath=filepath, total_bytes=total_bytes, detected_type=sniff.detected_type
+    )
+    details: JsonObject = {
+        "total_bytes": total_bytes,
+        "binary": True,
+        "detected_type": sniff.detected_type,
+    }
+    return text, details
+
+
+def _read_base64_sync(
+    filepath: str, total_bytes: int, max_bytes: int, sniff: FileSniff
+) -> tuple[str, JsonObject]:
+    with open(filepath, "rb") as file_obj:
+        data = file_obj.read(max_bytes)
+    status = BASE64_STATUS_MESSAGE.format(returned_bytes=len(data), total_bytes=total_bytes)
+    text = (
+        f"{BASE64_TAG_OPEN}\n{base64.b64encode(data).decode('ascii')}\n{BASE64_TAG_CLOSE}"
+        f"\n{status}"
+    )
+    details: JsonObject = {
+        "total_bytes": total_bytes,
+        "binary": True,
+        "detected_type": sniff.detected_type,
+        "encoding": BASE64_ENCODING,
+        "returned_bytes": len(data),
+    }
+    return text, details
+
+
+async def _read_text(
+    filepath: str,
+    read_range: ReadRange,
+    *,
+    total_bytes: int,
+    max_bytes: int,
+    encoding: str,
+) -> tuple[str, JsonObject]:
+    if total_bytes > max_bytes and not read_range.explicit:
+        head_lines, tail_lines = get_read_window_lines()
+        output = await asyncio.to_thread(
+            _read_window_sync,
+            filepath,
+            total_bytes=total_bytes,
+            max_bytes=max_bytes,
+            head_lines=head_lines,
+            tail_lines=tail_lines,
+            encoding=encoding,
+        )
+    else:
+        output = await asyncio.to_thread(
+            _read_range_sync, filepath, read_range, max_bytes, encoding=encoding
+        )
+    _cache_store(filepath, output.hashed_lines)
+    details: JsonObject = {
+        "total_bytes": total_bytes,
+        "total_lines": output.total_lines,
+        "ranges": output.ranges(),
+        "windowed": output.windowed,
+        "encoding": encoding,
+        "ends_with_newline": output.ends_with_newline,
+    }
+    return output.text, details
+
+
+async def _run_read_file(
+    filepath: str,
+    read_range: ReadRange,
+    max_bytes: int | None = None,
+    *,
+    as_base64: bool = False,
+) -> tuple[str, JsonObject]:
+    read_limit = get_read_max_bytes()
+    effective_max_bytes = read_limit if max_bytes is None else min(max_bytes, read_limit)
+    total_bytes = os.path.getsize(filepath)
+    sniff = await asyncio.to_thread(sniff_file, filepath)
+    if not sniff.is_binary:
+        return await _read_text(
+            filepath,
+            read_range,
+            total_bytes=total_bytes,
+            max_bytes=effective_max_bytes,
+            encoding=sniff.encoding,
+        )
+    _cache_store(filepath, [])
+    if as_base64:
+        return await asyncio.to_thread(
+            _read_base64_sync, filepath, total_bytes, effective_max_bytes, sniff
+        )
+    return _binary_summary(filepath, total_bytes, sniff)
+
+
+def _optional_int(args: JsonObject, name: str, *, minimum: int) -> int | None:
+    value = args.get(name)
+    if value is None:
+        return None
+    if not isinstance(value, int) or isinstance(value, bool):
+        raise ToolRetryError(f"{INVALID_ARGUMENTS_PREFIX}'{name}' must be an integer.")
+    if value < minimum:
+        raise ToolRetryError(
+            f"{INVALID_ARGUMENTS_PREFIX}'{name}' must be >= {minimum}, got {value}."
+        )
+    return value
+
+
+def parse_read_range(args: JsonObject) -> ReadRange:
+    """Validate offset/limit or start_line/end_line; the two forms cannot be mixed."""
+    offset = _optional_int(args, "offset", minimum=0)
+    limit = _optional_int(args, "limit", minimum=1)
+    start_line = _optional_int(args, "start_line", minimum=1)
+    end_line = _optional_int(args, "end_line", minimum=1)
+    uses_lines = start_line is not None or end_line is not None
+    if uses_lines and (offset is not None or limit is not None):
+        raise ToolRetryError(
+            f"{INVALID_ARGUMENTS_PREFIX}use either offset/limit or start_line/end_line, not both."
+        )
+    if not uses_lines:
+        return ReadRange(
+            offset=offset or 0,
+            limit=limit if limit is not None else DEFAULT_READ_LIMIT,
+            explicit=offset is not None or limit is not None,
+        )
+    first = start_line if start_line is not None else 1
+    if end_line is not None and end_line < first:
+        raise ToolRetryError(
+            f"{INVALID_ARGUMENTS_PREFIX}end_line ({end_line}) is before start_line ({first})."
+        )
+    count = end_line - first + 1 if end_line is not None else DEFAULT_READ_LIMIT
+    return ReadRange(offset=first - 1, limit=count, explicit=True)
+
+
+async def _execute_read_file(
     tool_call_id: str,
     args: JsonObject,
     signal: asyncio.Event | None,
@@ -146,22 +419,21 @@ async def _execute_read_file(  # noqa: C901
         raise UserAbortError("Tool execution aborted: read_file")
 
     filepath = args.get("filepath")
-    offset = args.get("offset", 0)
-    limit = args.get("limit")
     if not isinstance(filepath, str):
-        raise ToolRetryError("Invalid arguments for tool 'read_file': 'filepath' must be a string.")
-    if not isinstance(offset, int) or isinstance(offset, bool):
-        raise ToolRetryError("Invalid arguments for tool 'read_file': 'offset' must be an integer.")
-    if limit is not None and (not isinstance(limit, int) or isinstance(limit, bool)):
-        raise ToolRetryError("Invalid arguments for tool 'read_file': 'limit' must be an integer.")
-
-    result = await translate_file_tool_errors(
+        raise ToolRetryError(f"{INVALID_ARGUMENTS_PREFIX}'filepath' must be a string.")
+    read_range = parse_read_range(args)
+    max_bytes = _optional_int(args, "max_bytes", minimum=1)
+    as_base64 = args.get("as_base64", False)
+    if not isinstance(as_base64, bool):
+        raise ToolRetryError(f"{INVALID_ARGUMENTS_PREFIX}'as_base64' must be a boolean.")
+
+    text, details = await translate_file_tool_errors(
         tool_name="read_file",
         filepath=filepath,
-        operation=_run_read_file(filepath=filepath, offset=offset, limit=limit),
+        operation=_run_read_file(filepath, read_range, max_bytes, as_base64=as_base64),
     )
 
-    return _text_result(result)
+    return _text_result(text, details)
 
 
 read_file = AgentTool(
@@ -0,0 +1,97 @@
+"""Tell text files from binary ones by sampling their first bytes.
+
+Only the first ``SNIFF_BYTES`` are examined, so the check costs one small read
+however large the file is. In order:
+
+1. A byte-order mark means text in that encoding (UTF-8, UTF-16, UTF-32).
+2. A known magic number (PNG, PDF, ZIP, ELF, ...) means binary of that type.
+3. NUL bytes mean binary, unless they sit on every other byte the way
+   BOM-less UTF-16 encodes ASCII text.
+4. Mostly control characters means binary; anything else is UTF-8 text.
+"""
+
+from __future__ import annotations
+
+import codecs
+from dataclasses import dataclass
+from pathlib import Path
+
+SNIFF_BYTES = 8192
+UTF8_ENCODING = "utf-8"
+GENERIC_BINARY_TYPE = "binary data"
+MAX_CONTROL_CHAR_RATIO = 0.3
+MIN_UTF16_NUL_RATIO = 0.9
+TEXT_CONTROL_BYTES = frozenset(b"\t\n\r\f\b\x1b")
+
+# Longest BOMs first: the UTF-32-LE mark starts with the UTF-16-LE one.
+_BYTE_ORDER_MARKS: tuple[tuple[bytes, str], ...] = (
+    (codecs.BOM_UTF32_LE, "utf-32"),
+    (codecs.BOM_UTF32_BE, "utf-32"),
+    (codecs.BOM_UTF8, "utf-8-sig"),
+    (codecs.BOM_UTF16_LE, "utf-16"),
+    (codecs.BOM_UTF16_BE, "utf-16"),
+)
+
+_MAGIC_NUMBERS: tuple[tuple[bytes, str], ...] = (
+    (b"\x89PNG\r\n\x1a\n", "PNG image"),
+    (b"\xff\xd8\xff", "JPEG image"),
+    (b"GIF87a", "GIF image"),
+    (b"GIF89a", "GIF image"),
+    (b"%PDF-", "PDF document"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x1f\x8b", "gzip archive"),
+    (b"BZh", "bzip2 archive"),
+    (b"\xfd7zXZ\x00", "xz archive"),
+    (b"7z\xbc\xaf\x27\x1c", "7-Zip archive"),
+    (b"\x7fELF", "ELF executable"),
+    (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+    (b"\xca\xfe\xba\xbe", "Mach-O universal binary or Java class"),
+    (b"\x00asm", "WebAssembly module"),
+    (b"SQLite format 3\x00", "SQLite database"),
+)
+
+
+@dataclass(frozen=True, slots=True)
+class FileSniff:
+    """Outcome of sniffing a file's head: binary with a type, or text in an encoding."""
+
+    is_binary: bool
+    encoding: str = UTF8_ENCODING
+    detected_type: str | None = None
+
+
+def _utf16_encoding(sample: bytes) -> str | None:
+    even = sample[0::2]
+    odd = sample[1::2]
+    if not odd:
+        return None
+    if odd.count(0) / len(odd) >= MIN_UTF16_NUL_RATIO and 0 not in even:
+        return "utf-16-le"
+    if even.count(0) / len(even) >= MIN_UTF16_NUL_RATIO and 0 not in odd:
+        return "utf-16-be"
+    return None
+
+
+def sniff_bytes(sample: bytes) -> FileSniff:
+    for mark, encoding in _BYTE_ORDER_MARKS:
+        if sample.startswith(mark):
+            return FileSniff(is_binary=False, encoding=encoding)
+    for magic, detected_type in _MAGIC_NUMBERS:
+        if sample.startswith(magic):
+            return FileSniff(is_binary=True, detected_type=detected_type)
+    if b"\x00" in sample:
+        utf16_encoding = _utf16_encoding(sample)
+        if utf16_encoding is not None:
+            return FileSniff(is_binary=False, encoding=utf16_encoding)
+        return FileSniff(is_binary=True, detected_type=GENERIC_BINARY_TYPE)
+    if sample:
+        control_bytes = sum(1 for byte in sample if byte < 0x20 and byte not in TEXT_CONTROL_BYTES)
+        if control_bytes / len(sample) > MAX_CONTROL_CHAR_RATIO:
+            return FileSniff(is_binary=True, detected_type=GENERIC_BINARY_TYPE)
+    return FileSniff(is_binary=False)
+
+
+def sniff_file(path: str | Path, sample_bytes: int = SNIFF_BYTES) -> FileSniff:
+    """Sniff the first ``sample_bytes`` of ``path``; OSError propagates."""
+    with open(path, "rb") as file_obj:
+        return sniff_bytes(file_obj.read(sample_bytes))
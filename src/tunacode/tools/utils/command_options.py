@@ -0,0 +1,440 @@
+"""Option checks for programs the command rules mark read-only.
+
+A rule names a program, not what its options do: ``sort`` only reads, but
+``sort -o out`` writes and ``rg --pre cmd`` runs ``cmd`` on every file. Each
+``OptionPolicy`` below lists the options a read-only program (or ``git``
+subcommand) is known to accept without writing or running anything. Any other
+option -- including an abbreviation of a safe long option -- makes the command
+``write``; options that run another program make it ``destructive``. Operands
+are checked where they can write: ``uniq in out`` writes ``out`` and
+``date 0101`` sets the clock.
+
+Short options use ``getopt`` spec strings: ``"cn:"`` accepts ``-c`` and
+``-n VALUE`` (or ``-nVALUE``); ``"M::"`` takes an optional value that must be
+attached (``-M50%``). Long options ending in ``=`` take a value that may be
+the next word. ``find`` primaries are single-dash words and use the long
+option set.
+
+Programs whose options only change what they print (``echo``, ``printf``,
+``pwd``, ``true``...) and ``ps``, whose BSD-style options need no dash, have
+no policy and are left to their rule.
+"""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+
+from tunacode.constants import CommandRisk
+
+from tunacode.tools.utils.command_rules import RISK_ORDER
+
+UNSAFE_OPTION_RULE = "option not known to be read-only"
+EXEC_OPTION_RULE = "option runs another program"
+UNSAFE_OPERAND_RULE = "operand not known to be read-only"
+
+# ``less +cmd`` runs ``cmd`` at startup; ``!`` and ``|`` hand text to a shell.
+_SHELL_ESCAPE_CHARACTERS = frozenset("!|")
+# Startup commands that only move, search or follow: ``+100``, ``+G``, ``+/error``.
+_PAGER_COMMAND_PATTERN = re.compile(r"\++(?:\d*[gGF]?|[/?].*)", re.DOTALL)
+_FIND_COMMAND_TERMINATORS = frozenset({";", "+"})
+
+
+@dataclass(frozen=True, slots=True)
+class OptionVerdict:
+    """The option or operand that raised a read-only command's tier."""
+
+    risk: CommandRisk
+    rule: str
+    word: str
+
+
+@dataclass(frozen=True, slots=True)
+class OptionPolicy:
+    """The options and operands one read-only program is known to accept."""
+
+    flags: frozenset[str]
+    value_flags: frozenset[str]
+    optional_value_flags: frozenset[str]
+    long_options: frozenset[str]
+    long_value_options: frozenset[str]
+    exec_options: frozenset[str] = frozenset()
+    # Primaries are whole single-dash words (``find -name``), not flag clusters.
+    word_options: bool = False
+    # Primaries followed by a command line up to ``;`` or ``+``; classified elsewhere.
+    command_options: frozenset[str] = frozenset()
+    # ``+cmd`` startup commands, for pagers.
+    pager_commands: bool = False
+    max_operands: int | None = None
+    operand_pattern: re.Pattern[str] | None = None
+
+
+def _policy(
+    short: str = "",
+    long: str = "",
+    *,
+    exec_options: str = "",
+    word_options: bool = False,
+    command_options: str = "",
+    pager_commands: bool = False,
+    max_operands: int | None = None,
+    operand_pattern: str | None = None,
+) -> OptionPolicy:
+    flags: set[str] = set()
+    value_flags: set[str] = set()
+    optional_value_flags: set[str] = set()
+    for match in re.finditer(r"(.)(:{0,2})", short):
+        letter, colons = match.groups()
+        {"": flags, ":": value_flags, "::": optional_value_flags}[colons].add(letter)
+    long_names = long.split()
+    return OptionPolicy(
+        flags=frozenset(flags),
+        value_flags=frozenset(value_flags),
+        optional_value_flags=frozenset(optional_value_flags),
+        long_options=frozenset(name.rstrip("=") for name in long_names),
+        long_value_options=frozenset(name[:-1] for name in long_names if name.endswith("=")),
+        exec_options=frozenset(exec_options.split()),
+        word_options=word_options,
+        command_options=frozenset(command_options.split()),
+        pager_commands=pager_commands,
+        max_operands=max_operands,
+        operand_pattern=re.compile(operand_pattern) if operand_pattern is not None else None,
+    )
+
+
+_GIT_DIFF_SHORT = "abBpsuRwWzM::C::D::U:l:S:G:O:"
+_GIT_DIFF_LONG = (
+    "abbrev binary cached check color color-moved color-words compact-summary diff-algorithm= "
+    "diff-filter= dirstat dst-prefix= exit-code find-copies find-renames full-index "
+    "function-context histogram ignore-all-space ignore-blank-lines ignore-cr-at-eol "
+    "ignore-space-at-eol ignore-space-change ignore-submodules inter-hunk-context= merge-base "
+    "minimal name-only name-status no-color no-ext-diff no-patch no-prefix no-renames no-textconv "
+    "numstat patch patience quiet raw relative shortstat src-prefix= staged stat submodule summary "
+    "text unified= word-diff word-diff-regex="
+)
+_GIT_LOG_LONG = _GIT_DIFF_LONG + (
+    " all author= branches committer= date= decorate first-parent follow format= graph grep= "
+    "max-count= merges no-merges oneline pretty remotes reverse since= skip= tags until="
+)
+
+OPTION_POLICIES: dict[tuple[str, str], OptionPolicy] = {
+    ("cat", ""): _policy(
+        "AbeEnstTuv",
+        "number number-nonblank show-all show-ends show-nonprinting show-tabs squeeze-blank",
+    ),
+    ("column", ""): _policy(
+        "txc:N:o:s:",
+        "columns= fillrows output-separator= separator= table table-columns=",
+    ),
+    ("cut", ""): _policy(
+        "nszb:c:d:f:",
+        (
+            "bytes= characters= complement delimiter= fields= only-delimited output-delimiter= "
+            "zero-terminated"
+        ),
+    ),
+    ("date", ""): _policy(
+        "uRd:f:r:I::",
+        "date= file= iso-8601 reference= rfc-3339= rfc-email universal utc",
+        max_operands=1,
+        operand_pattern=r"\+.*",
+    ),
+    ("df", ""): _policy(
+        "ahHiklPTB:t:x:",
+        (
+            "all block-size= exclude-type= human-readable inodes local output portability "
+            "print-type si total type="
+        ),
+    ),
+    ("diff", ""): _policy(
+        "aBbcdEeilNnpqrsTtuwyZC:D:F:I:L:S:U:W:x:X:",
+        (
+            "brief color context exclude= exclude-from= expand-tabs from-file= ignore-all-space "
+            "ignore-blank-lines ignore-case ignore-matching-lines= ignore-space-change "
+            "ignore-trailing-space initial-tab label= minimal new-file no-dereference normal "
+            "recursive report-identical-files show-function-line= side-by-side speed-large-files "
+            "strip-trailing-cr suppress-common-lines text to-file= unidirectional-new-file unified "
+            "width="
+        ),
+    ),
+    ("dirname", ""): _policy("z", "zero"),
+    ("du", ""): _policy(
+        "0aAbcDhHklLmPsSxB:d:t:X:",
+        (
+            "all apparent-size block-size= bytes count-links dereference exclude= exclude-from= "
+            "files0-from= human-readable inodes max-depth= null one-file-system separate-dirs si "
+            "summarize threshold= time total"
+        ),
+    ),
+    ("file", ""): _policy(
+        "0bcdEhiIkLlNnprsSvzZe:F:f:m:P:",
+        (
+            "brief dereference exclude= extension files-from= keep-going magic-file= mime "
+            "mime-encoding mime-type no-buffer no-dereference no-pad no-sandbox preserve-date "
+            "print0 raw separator= special-files uncompress uncompress-noreport"
+        ),
+    ),
+    ("find", ""): _policy(
+        "",
+        (
+            "H L P a amin= and anewer= atime= cmin= cnewer= ctime= daystart depth empty executable "
+            "false follow fstype= gid= group= ilname= iname= inum= ipath= iregex= iwholename= "
+            "links= lname= ls maxdepth= mindepth= mmin= mount mtime= name= newer= newerat= "
+            "newerct= newermt= nogroup noleaf not nouser o or path= perm= print print0 printf= "
+            "prune quit readable regex= regextype= samefile= size= true type= uid= used= user= "
+            "wholename= writable xdev xtype="
+        ),
+        word_options=True,
+        command_options="-exec -execdir -ok -okdir",
+    ),
+    ("grep", ""): _policy(
+        "abcEFGHhIiLlnoPqRrsTUvwxyZz0123456789A:B:C:D:d:e:f:m:X:",
+        (
+            "after-context= basic-regexp before-context= binary-files= byte-offset color colour "
+            "context= count dereference-recursive devices= directories= exclude= exclude-dir= "
+            "exclude-from= extended-regexp file= files-with-matches files-without-match "
+            "fixed-strings ignore-case include= initial-tab invert-match label= line-buffered "
+            "line-number line-regexp max-count= no-filename no-ignore-case no-messages null "
+            "null-data only-matching perl-regexp quiet recursive regexp= silent text with-filename "
+            "word-regexp"
+        ),
+    ),
+    ("head", ""): _policy(
+        "qvz0123456789c:n:",
+        "bytes= lines= quiet silent verbose zero-terminated",
+    ),
+    ("hostname", ""): _policy(
+        "aAdfiIsy",
+        "alias all-fqdns all-ip-addresses domain fqdn ip-address long nis short yp",
+        max_operands=0,
+    ),
+    ("id", ""): _policy("aGgnruzZ", "context group groups name real user zero"),
+    ("less", ""): _policy(
+        "aABcCdeEfFgGiIJKLmMnNqQrRsSuUVwWXz::b:h:j:k:p:P:t:T:x:y:D:#:",
+        (
+            "chop-long-lines ignore-case IGNORE-CASE line-numbers LINE-NUMBERS LONG-PROMPT mouse "
+            "no-init pattern= quit-at-eof QUIT-AT-EOF quit-if-one-screen raw-control-chars "
+            "RAW-CONTROL-CHARS squeeze-blank-lines tabs="
+        ),
+        pager_commands=True,
+    ),
+    ("ls", ""): _policy(
+        "aAbBcCdDfFgGhHiklLmnNopqQrRsStuUvxXZ1I:T:w:",
+        (
+            "all almost-all author block-size= classify color context dereference "
+            "dereference-command-line dereference-command-line-symlink-to-dir directory escape "
+            "file-type format= full-time group-directories-first hide= hide-control-chars "
+            "human-readable hyperlink ignore= ignore-backups indicator-style= inode kibibytes "
+            "literal no-group numeric-uid-gid quote-name quoting-style= recursive reverse "
+            "show-control-chars si size sort= tabsize= time= time-style= width= zero"
+        ),
+    ),
+    ("md5sum", ""): _policy(
+        "bctwz",
+        "binary check ignore-missing quiet status strict tag text warn",
+    ),
+    ("more", ""): _policy(
+        "cdflpsun:",
+        "clean-print lines= logical no-pause plain print-over silent squeeze",
+        pager_commands=True,
+    ),
+    ("readlink", ""): _policy(
+        "efmnqsvz",
+        (
+            "canonicalize canonicalize-existing canonicalize-missing no-newline quiet silent "
+            "verbose zero"
+        ),
+    ),
+    ("realpath", ""): _policy(
+        "eLmPqsz",
+        (
+            "canonicalize-existing canonicalize-missing logical no-symlinks physical quiet "
+            "relative-base= relative-to= strip zero"
+        ),
+    ),
+    ("rg", ""): _policy(
+        "abcFHhiIlLNnopqSsuUvVwxz0A:B:C:E:e:f:g:j:M:m:r:T:t:",
+        (
+            "after-context= before-context= binary byte-offset case-sensitive color= colors= "
+            "column context= context-separator= count count-matches crlf encoding= engine= "
+            "field-context-separator= field-match-separator= files files-with-matches "
+            "files-without-match fixed-strings follow glob= glob-case-insensitive heading hidden "
+            "iglob= ignore-case ignore-file= include-zero invert-match json line-number "
+            "line-regexp max-columns= max-columns-preview max-count= max-depth= max-filesize= "
+            "maxdepth= multiline multiline-dotall no-config no-filename no-heading no-ignore "
+            "no-ignore-dot no-ignore-global no-ignore-parent no-ignore-vcs no-line-number "
+            "no-messages null null-data only-matching passthru path-separator= pcre2 pretty quiet "
+            "regexp= replace= search-zip smart-case sort= sortr= stats text threads= trim type= "
+            "type-add= type-clear= type-list type-not= unrestricted vimgrep with-filename "
+            "word-regexp"
+        ),
+        exec_options="pre pre-glob",
+    ),
+    ("sha256sum", ""): _policy(
+        "bctwz",
+        "binary check ignore-missing quiet status strict tag text warn",
+    ),
+    ("sort", ""): _policy(
+        "bcCdfghiMmnRrsuVzk:S:t:T:",
+        (
+            "batch-size= buffer-size= check debug dictionary-order field-separator= files0-from= "
+            "general-numeric-sort human-numeric-sort ignore-case ignore-leading-blanks "
+            "ignore-nonprinting key= merge month-sort numeric-sort parallel= random-sort "
+            "random-source= reverse sort= stable temporary-directory= unique version-sort "
+            "zero-terminated"
+        ),
+        exec_options="compress-program",
+    ),
+    ("stat", ""): _policy(
+        "fLtc:",
+        "cached= dereference file-system format= printf= terse",
+    ),
+    ("tail", ""): _policy(
+        "fFqrvz0123456789c:n:s:",
+        (
+            "bytes= follow lines= max-unchanged-stats= pid= quiet retry silent sleep-interval= "
+            "verbose zero-terminated"
+        ),
+    ),
+    ("tree", ""): _policy(
+        "aACdDfFghilnNpqQrsStuvxH:I:L:P:T:",
+        (
+            "charset= dirsfirst du filelimit= filesfirst gitignore ignore-case inodes matchdirs "
+            "noreport prune si sort= timefmt="
+        ),
+    ),
+    ("uname", ""): _policy(
+        "aimnoprsv",
+        (
+            "all hardware-platform kernel-name kernel-release kernel-version machine nodename "
+            "operating-system processor"
+        ),
+    ),
+    ("uniq", ""): _policy(
+        "cdiuzf:s:w:D::",
+        (
+            "all-repeated check-chars= count group ignore-case repeated skip-chars= skip-fields= "
+            "unique zero-terminated"
+        ),
+        max_operands=1,
+    ),
+    ("wc", ""): _policy("clLmw", "bytes chars files0-from= lines max-line-length total= words"),
+    ("which", ""): _policy("as"),
+    ("whoami", ""): _policy(),
+    ("git", "blame"): _policy(
+        "bcefklnpstwC::L:M::S:",
+        "color-by-age color-lines date= incremental porcelain line-porcelain",
+    ),
+    ("git", "diff"): _policy(_GIT_DIFF_SHORT, _GIT_DIFF_LONG),
+    ("git", "log"): _policy(f"{_GIT_DIFF_SHORT}0123456789n:", _GIT_LOG_LONG),
+    ("git", "show"): _policy(_GIT_DIFF_SHORT, _GIT_LOG_LONG),
+    ("git", "status"): _policy(
+        "bsvzu::",
+        (
+            "ahead-behind branch ignored long no-ahead-behind porcelain short show-stash "
+            "untracked-files verbose"
+        ),
+    ),
+}
+
+
+class _OptionScan:
+    """Walks one command's arguments and keeps the riskiest verdict."""
+
+    def __init__(self, policy: OptionPolicy) -> None:
+        self.policy = policy
+        self.operands: list[str] = []
+        self.verdict: OptionVerdict | None = None
+
+    def flag(self, risk: CommandRisk, rule: str, word: str) -> None:
+        if self.verdict is None or RISK_ORDER.index(risk) > RISK_ORDER.index(self.verdict.risk):
+            self.verdict = OptionVerdict(risk, rule, word)
+
+    def long_option(self, word: str, name: str, *, has_value: bool) -> bool:
+        """Check one long option; return whether the next word is its value."""
+        policy = self.policy
+        # Abbreviations of an option that runs a program run it too.
+        if any(option.startswith(name) for option in policy.exec_options):
+            self.flag(CommandRisk.DESTRUCTIVE, EXEC_OPTION_RULE, word)
+            return False
+        if name not in policy.long_options:
+            self.flag(CommandRisk.WRITE, UNSAFE_OPTION_RULE, word)
+            return False
+        return name in policy.long_value_options and not has_value
+
+    def flag_cluster(self, word: str) -> bool:
+        """Check ``-abc``; return whether the next word is the last flag's value."""
+        letters = word[1:]
+        for position, letter in enumerate(letters):
+            if letter in self.policy.value_flags:
+                return position == len(letters) - 1
+            if letter in self.policy.optional_value_flags:
+                return False
+            if letter not in self.policy.flags:
+                self.flag(CommandRisk.WRITE, UNSAFE_OPTION_RULE, word)
+                return False
+        return False
+
+    def pager_command(self, word: str) -> None:
+        if _SHELL_ESCAPE_CHARACTERS.intersection(word):
+            self.flag(CommandRisk.DESTRUCTIVE, EXEC_OPTION_RULE, word)
+        elif _PAGER_COMMAND_PATTERN.fullmatch(word) is None:
+            self.flag(CommandRisk.WRITE, UNSAFE_OPTION_RULE, word)
+
+    def word(self, word: str) -> bool:
+        """Check one argument; return whether the next word is its value."""
+        policy = self.policy
+        if policy.pager_commands and word.startswith("+"):
+            self.pager_command(word)
+            return False
+        if word.startswith("--"):
+            name, separator, _value = word[2:].partition("=")
+            return self.long_option(word, name, has_value=bool(separator))
+        if not word.startswith("-") or word == "-":
+            self.operands.append(word)
+            return False
+        if policy.word_options:
+            return self.long_option(word, word[1:], has_value=False)
+        return self.flag_cluster(word)
+
+    def operand_limits(self) -> None:
+        policy = self.policy
+        if policy.max_operands is not None and len(self.operands) > policy.max_operands:
+            self.flag(CommandRisk.WRITE, UNSAFE_OPERAND_RULE, self.operands[policy.max_operands])
+        if policy.operand_pattern is not None:
+            for operand in self.operands:
+                if policy.operand_pattern.fullmatch(operand) is None:
+                    self.flag(CommandRisk.WRITE, UNSAFE_OPERAND_RULE, operand)
+
+
+def check_options(program: str, args: list[str]) -> OptionVerdict | None:
+    """Return why ``args`` make a read-only ``program`` riskier, or ``None``.
+
+    ``args`` are the words after the program; a policy keyed by a subcommand
+    (``git log``) checks the words after it.
+    """
+    policy = OPTION_POLICIES.get((program, args[0])) if args else None
+    if policy is not None:
+        args = args[1:]
+    else:
+        policy = OPTION_POLICIES.get((program, ""))
+    if policy is None:
+        return None
+    scan = _OptionScan(policy)
+    index = 0
+    while index < len(args):
+        word = args[index]
+        index += 1
+        if word == "--":
+            scan.operands.extend(args[index:])
+            break
+        if word in policy.command_options:
+            # ``find -exec cmd ;`` is classified as the nested command it runs.
+            while index < len(args) and args[index] not in _FIND_COMMAND_TERMINATORS:
+                index += 1
+            index += 1
+            continue
+        if scan.word(word):
+            index += 1
+    scan.operand_limits()
+    return scan.verdict
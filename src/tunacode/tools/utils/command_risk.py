@@ -0,0 +1,410 @@
+"""Heuristic risk classification for shell commands run by the bash tool.
+
+A command line is split into simple commands on shell control operators and
+each program name is looked up in the command rules (``command_rules``: the
+built-in rules plus the user and project rule files). A read-only verdict is
+then checked against the program's known-safe options (``command_options``),
+so ``sort -o out`` is ``write`` and ``rg --pre cmd`` is ``destructive``. The
+riskiest segment wins, so ``ls && curl example.com`` is a network command.
+Unknown programs default to ``write``: they are not known to be read-only.
+
+Leading ``VAR=value`` assignments and wrappers such as ``env`` or ``sudo``
+(with their options) are stripped to find the real program. Assignments to
+variables that change what a program loads or runs (``LD_PRELOAD``, ``PATH``,
+``DYLD_*``...), inline or via ``export``, make the segment destructive.
+
+Compound commands are classified by the commands inside them: keywords such
+as ``do``/``then``, subshell parentheses and function headers are skipped, and
+the command lines run by ``bash -c``, ``eval``, ``xargs`` and ``find -exec``
+are classified in place. What cannot be analyzed -- a program named by a
+variable, or nesting deeper than ``MAX_NESTED_COMMAND_DEPTH`` -- is
+destructive, never read-only.
+
+``classify_tool_call`` extends the same tiers to every agent tool: built-in
+tools have a fixed tier and ``bash`` is classified by its command. Tool
+plugins declare theirs through ``set_extension_tool_risks``; any other tool is
+``write``.
+
+``explain_command`` returns the same verdict as ``classify_command`` together
+with the segment, rule and words that decided it, for block messages and
+policy debugging.
+"""
+
+from __future__ import annotations
+
+import re
+import shlex
+from collections.abc import Mapping
+from dataclasses import dataclass, replace
+
+from tunacode.constants import CommandRisk, ToolName
+
+from tunacode.tools.cache_accessors.command_rules_cache import get_command_rules
+from tunacode.tools.utils.command_options import check_options
+from tunacode.tools.utils.command_rules import (
+    BUILTIN_RULE_SOURCE,
+    RISK_ORDER,
+    UNMATCHED_RISK,
+    CommandRule,
+    CommandRuleSet,
+)
+
+_CONTROL_OPERATOR_PATTERN = re.compile(r"\|\||&&|[;|&\n]|[$<>]\(|`")
+_ENV_ASSIGNMENT_PATTERN = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*=")
+_OUTPUT_REDIRECT_PATTERN = re.compile(r"(?<![<>&0-9])\d?>>?(?!&)")
+
+_COMMAND_WRAPPERS = frozenset({"sudo", "env", "nohup", "time", "nice", "command", "exec"})
+
+# Wrapper options that take a separate value, so the value is not mistaken
+# for the wrapped program (``sudo -u root rm``, ``env -u HOME rm``).
+_WRAPPER_VALUE_OPTIONS: dict[str, frozenset[str]] = {
+    "env": frozenset({"-u", "--unset", "-C", "--chdir"}),
+    "sudo": frozenset({"-u", "--user", "-g", "--group", "-C", "-D", "-h", "-p", "-r", "-t", "-U"}),
+    "nice": frozenset({"-n", "--adjustment"}),
+    "time": frozenset({"-f", "--format", "-o", "--output"}),
+    "exec": frozenset({"-a"}),
+}
+
+# Variables that change what an otherwise harmless program loads or runs, so
+# ``LD_PRELOAD=evil.so ls`` is not read-only.
+_DANGEROUS_ENV_VARS = frozenset(
+    (
+        "BASH_ENV ENV IFS LD_AUDIT LD_LIBRARY_PATH LD_PRELOAD NODE_OPTIONS PATH PERL5OPT "
+        "PROMPT_COMMAND PYTHONPATH PYTHONSTARTUP RUBYOPT"
+    ).split()
+)
+_DANGEROUS_ENV_PREFIXES = ("DYLD_",)
+
+# Builtins whose assignment arguments persist for the commands that follow.
+_ASSIGNMENT_BUILTINS = frozenset({"declare", "export", "local", "readonly", "typeset"})
+
+# Words that open or close a compound command; the command after them is
+# classified as if it stood alone (``do rm $f`` is ``rm``). ``case`` headers and
+# ``pattern)`` arm labels are skipped the same way.
+_SHELL_KEYWORDS = frozenset(
+    {"!", "{", "}", "do", "done", "elif", "else", "esac", "fi", "if", "then", "until", "while"}
+)
+# ``for f in *`` and friends only bind a variable; their bodies are separate segments.
+_LOOP_HEADERS = frozenset({"for", "select"})
+
+# Programs that run another command line given as arguments, which is then
+# classified in their place.
+_SHELL_PROGRAMS = frozenset({"bash", "dash", "ksh", "sh", "zsh"})
+_XARGS_VALUE_OPTIONS = frozenset(
+    {"-a", "-d", "-E", "-I", "-L", "-n", "-P", "-s", "--arg-file", "--delimiter", "--max-args"}
+)
+_FIND_EXEC_ACTIONS = frozenset({"-exec", "-execdir", "-ok", "-okdir"})
+_FIND_EXEC_TERMINATORS = frozenset({";", "+"})
+_FIND_DESTRUCTIVE_ACTIONS = frozenset({"-delete"})
+
+# Deeper ``bash -c "eval ..."`` chains are not analyzed and count as destructive.
+MAX_NESTED_COMMAND_DEPTH = 3
+
+_TOOL_RISKS: dict[str, CommandRisk] = {
+    ToolName.DISCOVER: CommandRisk.READ_ONLY,
+    ToolName.GREP: CommandRisk.READ_ONLY,
+    ToolName.LIST_DIRECTORY: CommandRisk.READ_ONLY,
+    ToolName.READ_FILE: CommandRisk.READ_ONLY,
+    ToolName.HASHLINE_EDIT: CommandRisk.WRITE,
+    ToolName.WRITE_FILE: CommandRisk.WRITE,
+    ToolName.WEB_FETCH: CommandRisk.NETWORK,
+    # Only edits the session task list, never the workspace.
+    ToolName.TASKS: CommandRisk.READ_ONLY,
+}
+# Tiers declared by tool plugins, keyed by the name the model sees.
+_extension_tool_risks: dict[str, CommandRisk] = {}
+
+
+@dataclass(frozen=True, slots=True)
+class RiskExplanation:
+    """Why a command got its tier: the deciding segment, rule and words."""
+
+    risk: CommandRisk
+    segment: str
+    rule: str
+    matched: str
+    risky_args: tuple[str, ...] = ()
+
+    @property
+    def reason(self) -> str:
+        match = f" '{self.matched}'" if self.matched else ""
+        return f"{self.rule}{match} in `{self.segment}` -> {self.risk.value}"
+
+
+@dataclass(frozen=True, slots=True)
+class _SimpleCommand:
+    """A segment's program words and the ``VAR=value`` assignments around them."""
+
+    words: list[str]
+    assignments: tuple[str, ...]
+
+
+def _skip_options(words: list[str], value_options: frozenset[str]) -> list[str]:
+    index = 0
+    while index < len(words) and words[index].startswith("-"):
+        option = words[index]
+        index += 1
+        if option == "--":
+            break
+        if option in value_options:
+            index += 1
+    return words[index:]
+
+
+def _compound_prefix_length(words: list[str]) -> int:
+    """Return how many leading words are compound-command syntax, not the program."""
+    word = words[0]
+    if word == "function":
+        return 2
+    if word == "case":
+        # ``case $x in pat) cmd`` runs ``cmd``; the header itself runs nothing.
+        return words.index("in") + 1 if "in" in words else len(words)
+    if word.endswith("()"):
+        return 1
+    if word.endswith(")") and not word.startswith("(") and len(words) > 1:
+        return 1
+    head = word.strip("()")
+    return 1 if not head or head in _SHELL_KEYWORDS else 0
+
+
+def _parse_simple_command(segment: str) -> _SimpleCommand:
+    try:
+        words = shlex.split(segment)
+    except ValueError:
+        words = segment.split()
+    assignments: list[str] = []
+    while words:
+        prefix_length = _compound_prefix_length(words)
+        if prefix_length:
+            words = words[prefix_length:]
+            continue
+        # Subshell parentheses cling to the first and last words: ``(cd x``, ``rm y)``.
+        word = words[0].strip("()")
+        if _ENV_ASSIGNMENT_PATTERN.match(word):
+            assignments.append(word)
+            words = words[1:]
+        elif word in _COMMAND_WRAPPERS:
+            wrapped = _skip_options(words[1:], _WRAPPER_VALUE_OPTIONS.get(word, frozenset()))
+            if not wrapped:
+                # A wrapper with nothing to wrap (``sudo -s``) is itself the program.
+                words = [word]
+                break
+            words = wrapped
+        else:
+            words = [word, *words[1:]]
+            break
+    if words and words[0] in _ASSIGNMENT_BUILTINS:
+        assignments.extend(word for word in words[1:] if _ENV_ASSIGNMENT_PATTERN.match(word))
+    return _SimpleCommand(words, tuple(assignments))
+
+
+def _shell_command_string(args: list[str]) -> str | None:
+    for index, arg in enumerate(args):
+        if arg == "--" or not arg.startswith("-"):
+            return None
+        if not arg.startswith("--") and "c" in arg[1:]:
+            return args[index + 1] if index + 1 < len(args) else None
+    return None
+
+
+def _find_exec_commands(args: list[str]) -> str | None:
+    commands: list[str] = []
+    for index, arg in enumerate(args):
+        if arg not in _FIND_EXEC_ACTIONS:
+            continue
+        rest = args[index + 1 :]
+        end = next((i for i, word in enumerate(rest) if word in _FIND_EXEC_TERMINATORS), len(rest))
+        commands.append(shlex.join(rest[:end]))
+    return "; ".join(commands) if commands else None
+
+
+def _nested_command(program: str, args: list[str]) -> str | None:
+    """Return the command line ``program`` would run, for programs that run one."""
+    if program in _SHELL_PROGRAMS:
+        return _shell_command_string(args)
+    if program == "eval":
+        return " ".join(args) or None
+    if program == "xargs":
+        # Bare ``xargs`` echoes its input.
+        return shlex.join(_skip_options(args, _XARGS_VALUE_OPTIONS)) or "echo"
+    if program == "find":
+        return _find_exec_commands(args)
+    return None
+
+
+def _is_dangerous_assignment(assignment: str) -> bool:
+    name = assignment.split("=", 1)[0]
+    return name in _DANGEROUS_ENV_VARS or name.startswith(_DANGEROUS_ENV_PREFIXES)
+
+
+def _rule_explanation(rule: CommandRule, *, segment: str, words: list[str]) -> RiskExplanation:
+    kind = "subcommand" if rule.subcommand else "program"
+    label = f"{rule.risk.value} {kind}"
+    if rule.source != BUILTIN_RULE_SOURCE:
+        label = f"{label} rule {rule.location}"
+    if rule.matcher is None:
+        return RiskExplanation(rule.risk, segment, label, rule.program, (words[0],))
+    matched = f"{rule.program} {words[1]}"
+    return RiskExplanation(rule.risk, segment, label, matched, (words[0], words[1]))
+
+
+def _unmatched_explanation(
+    rules: CommandRuleSet, *, segment: str, words: list[str], program: str
+) -> RiskExplanation:
+    candidates = rules.subcommand_rules(program)
+    if len(words) < 2 or not candidates:
+        return RiskExplanation(
+            UNMATCHED_RISK, segment, "program not known to be read-only", program, (words[0],)
+        )
+    # Name the argument that no subcommand rule accepted, and what would have.
+    allowed = " ".join(rule.subcommand for rule in reversed(candidates))
+    return RiskExplanation(
+        UNMATCHED_RISK,
+        segment,
+        f"{program} subcommand not matched by {allowed}",
+        words[1],
+        (words[0], words[1]),
+    )
+
+
+def _redirect_explanation(segment: str) -> RiskExplanation | None:
+    redirect = _OUTPUT_REDIRECT_PATTERN.search(segment)
+    if redirect is None:
+        return None
+    target = segment[redirect.end() :].split()
+    return RiskExplanation(
+        CommandRisk.WRITE, segment, "output redirect", redirect.group(0), tuple(target[:1])
+    )
+
+
+def _option_explanation(segment: str, program: str, args: list[str]) -> RiskExplanation | None:
+    verdict = check_options(program, args)
+    if verdict is None:
+        return None
+    return RiskExplanation(verdict.risk, segment, verdict.rule, verdict.word, (verdict.word,))
+
+
+def _explain_program(segment: str, words: list[str], rules: CommandRuleSet) -> RiskExplanation:
+    program = words[0].rsplit("/", 1)[-1]
+    rule = rules.lookup(program, words[1] if len(words) > 1 else "")
+    if rule is not None and rule.risk is not CommandRisk.READ_ONLY:
+        return _rule_explanation(rule, segment=segment, words=words)
+    redirect = _redirect_explanation(segment)
+    if rule is not None:
+        # A read-only program can still write or run something through its options.
+        option = _option_explanation(segment, program, words[1:])
+        if option is not None:
+            return _riskier(option, redirect)
+    if redirect is not None:
+        return redirect
+    if rule is not None:
+        return _rule_explanation(rule, segment=segment, words=words)
+    return _unmatched_explanation(rules, segment=segment, words=words, program=program)
+
+
+def _riskier(first: RiskExplanation, second: RiskExplanation | None) -> RiskExplanation:
+    if second is None or RISK_ORDER.index(second.risk) <= RISK_ORDER.index(first.risk):
+        return first
+    return second
+
+
+def _explain_nested(
+    segment: str, words: list[str], rules: CommandRuleSet, depth: int
+) -> RiskExplanation | None:
+    program = words[0].rsplit("/", 1)[-1]
+    nested = _nested_command(program, words[1:])
+    if nested is None:
+        return None
+    if depth >= MAX_NESTED_COMMAND_DEPTH:
+        return RiskExplanation(
+            CommandRisk.DESTRUCTIVE, segment, "commands nested too deeply to analyze", program
+        )
+    inner = _explain_command(nested, rules, depth + 1)
+    inner = replace(inner, rule=f"{inner.rule} via {program}")
+    # ``find`` keeps its own tier (``find -exec grep`` is read-only); shells,
+    # ``eval`` and ``xargs`` are transparent apart from redirects.
+    if program == "find":
+        return _riskier(_explain_program(segment, words, rules), inner)
+    return _riskier(inner, _redirect_explanation(segment))
+
+
+def _explain_unanalyzable(segment: str, words: list[str]) -> RiskExplanation | None:
+    program = words[0]
+    if program in _LOOP_HEADERS:
+        return RiskExplanation(CommandRisk.READ_ONLY, segment, "loop header", program)
+    if program.startswith("$") or "`" in program:
+        return RiskExplanation(
+            CommandRisk.DESTRUCTIVE, segment, "dynamic program name", program, (program,)
+        )
+    if program.rsplit("/", 1)[-1] == "find":
+        actions = tuple(word for word in words[1:] if word in _FIND_DESTRUCTIVE_ACTIONS)
+        if actions:
+            return RiskExplanation(
+                CommandRisk.DESTRUCTIVE, segment, "destructive find action", actions[0], actions
+            )
+    return None
+
+
+def _explain_segment(segment: str, rules: CommandRuleSet, depth: int) -> RiskExplanation:
+    segment = segment.strip()
+    command = _parse_simple_command(segment)
+    dangerous = tuple(item for item in command.assignments if _is_dangerous_assignment(item))
+    if dangerous:
+        name = dangerous[0].split("=", 1)[0]
+        return RiskExplanation(
+            CommandRisk.DESTRUCTIVE, segment, "dangerous environment assignment", name, dangerous
+        )
+    words = command.words
+    if not words:
+        rule = "assignment only" if command.assignments else "empty segment"
+        return RiskExplanation(CommandRisk.READ_ONLY, segment, rule, "")
+    special = _explain_unanalyzable(segment, words) or _explain_nested(
+        segment, words, rules, depth
+    )
+    return special if special is not None else _explain_program(segment, words, rules)
+
+
+def _explain_command(command: str, rules: CommandRuleSet, depth: int) -> RiskExplanation:
+    segments = _CONTROL_OPERATOR_PATTERN.split(command)
+    explanations = [_explain_segment(segment, rules, depth) for segment in segments]
+    riskiest = explanations[0]
+    for explanation in explanations[1:]:
+        riskiest = _riskier(riskiest, explanation)
+    return riskiest
+
+
+def explain_command(command: str, *, rules: CommandRuleSet | None = None) -> RiskExplanation:
+    """Return the explanation of the riskiest simple command in ``command``.
+
+    Ties keep the first segment, so the explanation names the earliest
+    command that reached the final tier. ``rules`` defaults to the merged
+    built-in, user and project rules.
+    """
+
+    active_rules = get_command_rules() if rules is None else rules
+    return _explain_command(command, active_rules, 0)
+
+
+def classify_command(command: str, *, rules: CommandRuleSet | None = None) -> CommandRisk:
+    """Return the riskiest tier among the simple commands in ``command``."""
+
+    return explain_command(command, rules=rules).risk
+
+
+def classify_tool_call(tool_name: str, arguments: Mapping[str, object]) -> CommandRisk:
+    """Return the risk tier of one tool call; unknown tools are ``write``."""
+
+    if tool_name == ToolName.BASH:
+        command = arguments.get("command")
+        return classify_command(command) if isinstance(command, str) else CommandRisk.WRITE
+    builtin_risk = _TOOL_RISKS.get(tool_name)
+    if builtin_risk is not None:
+        return builtin_risk
+    return _extension_tool_risks.get(tool_name, CommandRisk.WRITE)
+
+
+def set_extension_tool_risks(risks: Mapping[str, CommandRisk]) -> None:
+    """Replace the declared tiers of non-built-in tools; built-in tiers always win."""
+
+    _extension_tool_risks.clear()
+    _extension_tool_risks.update(risks)
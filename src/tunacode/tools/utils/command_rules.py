@@ -0,0 +1,382 @@
+"""Rules that map shell programs to command risk tiers.
+
+One rule per line, ``<tier> <program> [<subcommand>]``::
+
+    read_only   git {status,diff,log}
+    network     curl
+    destructive make clean
+    write       npm run-*
+    network     gh /(pr|issue)-.+/
+
+``tier`` is a ``CommandRisk`` value. The optional subcommand is matched
+against the program's first argument and is one of:
+
+- a literal word (``clean``);
+- a set of words in braces (``{status,diff,log}``);
+- a glob (``run-*``, ``v[0-9]*``);
+- a regular expression between slashes (``/(pr|issue)-.+/``), matched
+  against the whole argument. It may not contain spaces.
+
+A matching subcommand rule takes precedence over a rule for the bare
+program; among subcommand rules the one loaded last wins. When a program has
+subcommand rules but none matches, the command is ``write`` and the
+explanation names the argument that did not match. ``#`` at the start of a
+line or after whitespace starts a comment.
+
+The built-in rules below use the same format. User rules from
+``~/.tunacode/command_rules`` are loaded next and replace a built-in rule for
+the same program and subcommand, with a warning. Project rules from
+``.tunacode/command_rules`` are loaded last and may only raise a tier: a
+checked-out repository must not be able to mark ``rm`` read-only, so a
+project rule that would lower the tier is ignored with a warning. A project
+glob or regex must be at least as risky as every rule it could shadow.
+
+A rule only names a program; whether its options keep a ``read_only``
+program read-only is checked separately by ``command_options``.
+
+Syntax errors raise ``CommandRuleError`` naming the file and line.
+"""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+from enum import StrEnum
+from fnmatch import fnmatchcase
+from pathlib import Path
+
+from tunacode.constants import TUNACODE_HOME_DIR, CommandRisk
+
+COMMAND_RULES_FILE_NAME = "command_rules"
+BUILTIN_RULE_SOURCE = "built-in"
+RULE_SYNTAX = "<tier> <program> [<subcommand>]"
+RULE_WORD_PATTERN = re.compile(r"[A-Za-z0-9_.+@:-]+")
+RULE_GLOB_PATTERN = re.compile(r"[A-Za-z0-9_.+@:*?!\[\]-]+")
+RULE_COMMENT_PATTERN = re.compile(r"(?:^|\s)#.*$")
+GLOB_CHARACTERS = frozenset("*?[")
+
+# Tier ordering used to decide whether a project rule lowers a tier.
+RISK_ORDER = (
+    CommandRisk.READ_ONLY,
+    CommandRisk.WRITE,
+    CommandRisk.NETWORK,
+    CommandRisk.DESTRUCTIVE,
+)
+
+# Programs without a rule are ``write``: not known to be read-only.
+UNMATCHED_RISK = CommandRisk.WRITE
+
+DEFAULT_COMMAND_RULES = """\
+# Inspect files and the system without changing them.
+read_only   cat
+read_only   cd
+read_only   column
+read_only   cut
+read_only   date
+read_only   df
+read_only   diff
+read_only   dirname
+read_only   du
+read_only   echo
+read_only   file
+read_only   find
+read_only   grep
+read_only   head
+read_only   hostname
+read_only   id
+read_only   less
+read_only   ls
+read_only   md5sum
+read_only   more
+read_only   printf
+read_only   ps
+read_only   pwd
+read_only   readlink
+read_only   realpath
+read_only   rg
+read_only   sha256sum
+read_only   sort
+read_only   stat
+read_only   tail
+read_only   tree
+read_only   true
+read_only   type
+read_only   uname
+read_only   uniq
+read_only   wc
+read_only   which
+read_only   whoami
+read_only   git {blame,diff,log,show,status}
+
+# Reach the network.
+network     curl
+network     ftp
+network     nc
+network     ncat
+network     ping
+network     rsync
+network     scp
+network     sftp
+network     ssh
+network     telnet
+network     wget
+
+# Subcommands that reach the network for otherwise local tools.
+network     git {clone,fetch,pull,push,ls-remote,submodule}
+network     pip {download,install}
+network     uv {add,pip,sync,lock}
+network     npm {ci,install,publish}
+network     cargo {fetch,install,publish}
+
+# Delete data or take the machine down.
+destructive dd
+destructive mkfs
+destructive reboot
+destructive rm
+destructive rmdir
+destructive shred
+destructive shutdown
+destructive truncate
+destructive wipefs
+destructive git {clean,reset}
+"""
+
+
+class CommandRuleError(ValueError):
+    """Raised for a rule line that does not parse; names the file and line."""
+
+    def __init__(self, source: str, line: int, message: str) -> None:
+        self.source = source
+        self.line = line
+        super().__init__(f"{source}:{line}: {message}")
+
+
+class ArgMatchKind(StrEnum):
+    LITERAL = "literal"
+    SET = "set"
+    GLOB = "glob"
+    REGEX = "regex"
+
+
+@dataclass(frozen=True, slots=True)
+class ArgMatcher:
+    """Matches a program's first argument; ``pattern`` is the rule text."""
+
+    kind: ArgMatchKind
+    pattern: str
+    # Every accepted word for literal and set matchers; empty otherwise.
+    values: frozenset[str] = frozenset()
+
+    @property
+    def is_enumerable(self) -> bool:
+        return self.kind in (ArgMatchKind.LITERAL, ArgMatchKind.SET)
+
+    def matches(self, argument: str) -> bool:
+        if self.kind is ArgMatchKind.GLOB:
+            return fnmatchcase(argument, self.pattern)
+        if self.kind is ArgMatchKind.REGEX:
+            return re.fullmatch(self.pattern[1:-1], argument) is not None
+        return argument in self.values
+
+
+def parse_arg_matcher(text: str, *, source: str, line: int) -> ArgMatcher:
+    """Parse one subcommand field; raises ``CommandRuleError`` when invalid."""
+    if len(text) > 2 and text.startswith("/") and text.endswith("/"):
+        try:
+            re.compile(text[1:-1])
+        except re.error as exc:
+            raise CommandRuleError(source, line, f"invalid regex {text}: {exc}") from None
+        return ArgMatcher(ArgMatchKind.REGEX, text)
+    if text.startswith("{") and text.endswith("}"):
+        members = text[1:-1].split(",")
+        for member in members:
+            if RULE_WORD_PATTERN.fullmatch(member) is None:
+                raise CommandRuleError(source, line, f"invalid subcommand '{member}' in {text}")
+        return ArgMatcher(ArgMatchKind.SET, text, frozenset(members))
+    if GLOB_CHARACTERS.intersection(text) and RULE_GLOB_PATTERN.fullmatch(text) is not None:
+        return ArgMatcher(ArgMatchKind.GLOB, text)
+    if RULE_WORD_PATTERN.fullmatch(text) is None:
+        raise CommandRuleError(source, line, f"invalid subcommand '{text}'")
+    return ArgMatcher(ArgMatchKind.LITERAL, text, frozenset({text}))
+
+
+@dataclass(frozen=True, slots=True)
+class CommandRule:
+    risk: CommandRisk
+    program: str
+    matcher: ArgMatcher | None
+    source: str
+    line: int
+
+    @property
+    def subcommand(self) -> str:
+        return self.matcher.pattern if self.matcher is not None else ""
+
+    @property
+    def key(self) -> tuple[str, str]:
+        return (self.program, self.subcommand)
+
+    @property
+    def text(self) -> str:
+        return " ".join(word for word in (self.risk.value, self.program, self.subcommand) if word)
+
+    @property
+    def location(self) -> str:
+        return f"{self.source}:{self.line}"
+
+
+def _parse_rule_line(fields: list[str], *, source: str, line: int) -> CommandRule:
+    if len(fields) not in (2, 3):
+        raise CommandRuleError(source, line, f"expected '{RULE_SYNTAX}'")
+    tier, program, *subcommand = fields
+    try:
+        risk = CommandRisk(tier)
+    except ValueError:
+        expected = ", ".join(risk.value for risk in RISK_ORDER)
+        raise CommandRuleError(
+            source, line, f"unknown tier '{tier}' (expected one of {expected})"
+        ) from None
+    if RULE_WORD_PATTERN.fullmatch(program) is None:
+        raise CommandRuleError(source, line, f"invalid program '{program}'")
+    matcher = parse_arg_matcher(subcommand[0], source=source, line=line) if subcommand else None
+    return CommandRule(risk, program, matcher, source, line)
+
+
+def parse_command_rules(text: str, *, source: str) -> list[CommandRule]:
+    """Parse rule ``text``; raises ``CommandRuleError`` on the first bad line."""
+    rules: list[CommandRule] = []
+    for line_number, raw_line in enumerate(text.splitlines(), start=1):
+        fields = RULE_COMMENT_PATTERN.sub("", raw_line).split()
+        if fields:
+            rules.append(_parse_rule_line(fields, source=source, line=line_number))
+    return rules
+
+
+def _riskiest(risks: list[CommandRisk]) -> CommandRisk:
+    return max(risks, key=RISK_ORDER.index)
+
+
+def _overlaps(first: ArgMatcher, second: ArgMatcher) -> bool:
+    if first.pattern == second.pattern:
+        return True
+    if first.is_enumerable:
+        return any(second.matches(value) for value in first.values)
+    if second.is_enumerable:
+        return any(first.matches(value) for value in second.values)
+    return False
+
+
+@dataclass(slots=True)
+class CommandRuleSet:
+    """The merged rules consulted by command classification."""
+
+    rules: dict[tuple[str, str], CommandRule] = field(default_factory=dict)
+    warnings: list[str] = field(default_factory=list)
+
+    def subcommand_rules(self, program: str) -> list[CommandRule]:
+        """Return ``program``'s subcommand rules, most recently loaded first."""
+        return [
+            rule
+            for rule in reversed(self.rules.values())
+            if rule.program == program and rule.matcher is not None
+        ]
+
+    def lookup(self, program: str, subcommand: str) -> CommandRule | None:
+        """Return the matching subcommand rule for ``program``, else its program rule."""
+        if subcommand:
+            for rule in self.subcommand_rules(program):
+                assert rule.matcher is not None
+                if rule.matcher.matches(subcommand):
+                    return rule
+        return self.rules.get((program, ""))
+
+    def _shadowed(self, rule: CommandRule) -> list[CommandRule]:
+        if rule.matcher is None:
+            existing = self.rules.get(rule.key)
+            return [existing] if existing is not None else []
+        return [
+            other
+            for other in self.subcommand_rules(rule.program)
+            if other.matcher is not None and _overlaps(rule.matcher, other.matcher)
+        ]
+
+    def override(self, rule: CommandRule) -> None:
+        """Add ``rule`` ahead of every rule it overlaps, warning about changed tiers."""
+        for existing in self._shadowed(rule):
+            if existing.risk is not rule.risk:
+                self.warnings.append(
+                    f"{rule.location}: '{rule.text}' overrides '{existing.text}' "
+                    f"from {existing.location}"
+                )
+        # Re-inserting moves the rule to the end, where lookups find it first.
+        self.rules.pop(rule.key, None)
+        self.rules[rule.key] = rule
+
+    def _current_risk(self, rule: CommandRule) -> CommandRisk:
+        program_rule = self.rules.get((rule.program, ""))
+        fallback = program_rule.risk if program_rule is not None else UNMATCHED_RISK
+        if rule.matcher is None:
+            return fallback
+        if rule.matcher.is_enumerable:
+            return _riskiest(
+                [self._lookup_risk(rule.program, value) for value in rule.matcher.values]
+            )
+        return _riskiest([fallback, *(other.risk for other in self.subcommand_rules(rule.program))])
+
+    def _lookup_risk(self, program: str, subcommand: str) -> CommandRisk:
+        rule = self.lookup(program, subcommand)
+        return rule.risk if rule is not None else UNMATCHED_RISK
+
+    def restrict(self, rule: CommandRule) -> None:
+        """Add ``rule`` only if it does not lower the tier of any command it matches."""
+        current = self._current_risk(rule)
+        if RISK_ORDER.index(rule.risk) < RISK_ORDER.index(current):
+            self.warnings.append(
+                f"{rule.location}: ignored '{rule.text}'; project rules cannot lower "
+                f"a command below {current.value}"
+            )
+            return
+        self.override(rule)
+
+
+BUILTIN_COMMAND_RULES = tuple(
+    parse_command_rules(DEFAULT_COMMAND_RULES, source=BUILTIN_RULE_SOURCE)
+)
+
+
+def command_rule_paths(
+    *,
+    project_root: Path | None = None,
+    home_directory: Path | None = None,
+) -> tuple[Path, Path]:
+    """Return the user-global and project rule files, in load order."""
+    resolved_project_root = Path.cwd() if project_root is None else project_root
+    resolved_home_directory = Path.home() if home_directory is None else home_directory
+    return (
+        resolved_home_directory / TUNACODE_HOME_DIR / COMMAND_RULES_FILE_NAME,
+        resolved_project_root / TUNACODE_HOME_DIR / COMMAND_RULES_FILE_NAME,
+    )
+
+
+def _read_rule_file(path: Path) -> list[CommandRule]:
+    if not path.is_file():
+        return []
+    try:
+        text = path.read_text(encoding="utf-8")
+    except (OSError, UnicodeDecodeError) as exc:
+        raise CommandRuleError(str(path), 0, f"cannot read rules: {exc}") from exc
+    return parse_command_rules(text, source=str(path))
+
+
+def build_rule_set(*, user_file: Path | None, project_file: Path | None) -> CommandRuleSet:
+    """Merge the built-in rules with the user and project rule files."""
+    rule_set = CommandRuleSet()
+    for rule in BUILTIN_COMMAND_RULES:
+        rule_set.override(rule)
+    if user_file is not None:
+        for rule in _read_rule_file(user_file):
+            rule_set.override(rule)
+    if project_file is not None:
+        for rule in _read_rule_file(project_file):
+            rule_set.restrict(rule)
+    return rule_set
@@ -0,0 +1,125 @@
+"""All-or-nothing file writes shared by file-mutating tools."""
+
+from __future__ import annotations
+
+import os
+import secrets
+import shutil
+from pathlib import Path
+
+from tunacode.exceptions import FileOperationError
+
+from tunacode.tools import edit_journal
+
+DEFAULT_ENCODING = "utf-8"
+TEMP_FILE_SUFFIX = ".tunacode-tmp"
+NOTHING_APPLIED_MESSAGE = "Nothing was applied; every file was left with its previous content."
+
+
+class FileTransaction:
+    """Stage whole-file writes and commit them all or none.
+
+    Staged content is written to temp files next to each target, so a failure
+    while staging never touches the targets. Commit swaps the temp files in
+    with ``os.replace``; if a swap fails, the targets already swapped are
+    restored from snapshots taken just before the swap.
+
+    Writes and deletes go through symlinks: the target is resolved first, so
+    the link stays and the file it points to changes, and one file staged by
+    two different paths is still a single entry. A file with other hard links is
+    overwritten in place instead of swapped, so every link sees the new
+    content.
+
+    Committed writes are recorded in the edit journal so the turn can be
+    undone; the undo itself commits with ``journal=False``.
+    """
+
+    def __init__(self, encoding: str = DEFAULT_ENCODING, *, journal: bool = True) -> None:
+        self._encoding = encoding
+        self._journal = journal
+        self._staged: dict[Path, bytes | None] = {}
+
+    def stage(self, filepath: str, content: str) -> None:
+        """Queue ``content`` to replace ``filepath`` on commit."""
+
+        self._staged[_resolve_target(filepath)] = content.encode(self._encoding)
+
+    def stage_bytes(self, filepath: str, content: bytes) -> None:
+        """Queue raw ``content`` to replace ``filepath`` on commit."""
+
+        self._staged[_resolve_target(filepath)] = content
+
+    def stage_delete(self, filepath: str) -> None:
+        """Queue ``filepath`` for removal on commit."""
+
+        self._staged[_resolve_target(filepath)] = None
+
+    def commit(self) -> None:
+        """Write every staged file, or raise with no file changed."""
+
+        temp_paths: dict[Path, Path] = {}
+        snapshots: dict[Path, bytes | None] = {}
+        current_path: Path | None = None
+        try:
+            for target, content in self._staged.items():
+                current_path = target
+                if content is not None:
+                    temp_paths[target] = self._write_temp(target, content)
+            for target, content in self._staged.items():
+                current_path = target
+                snapshots[target] = target.read_bytes() if target.exists() else None
+                if content is None:
+                    target.unlink(missing_ok=True)
+                elif _has_other_links(target):
+                    target.write_bytes(content)
+                else:
+                    os.replace(temp_paths[target], target)
+        except OSError as exc:
+            self._rollback(snapshots)
+            failed_path = current_path if current_path is not None else Path()
+            raise FileOperationError(
+                operation="write",
+                path=str(failed_path),
+                message=f"{exc}. {NOTHING_APPLIED_MESSAGE}",
+                original_error=exc,
+            ) from exc
+        finally:
+            for temp_path in temp_paths.values():
+                temp_path.unlink(missing_ok=True)
+        if self._journal:
+            for target, content in self._staged.items():
+                if content is not None:
+                    edit_journal.record(target, snapshots[target], content)
+        self._staged.clear()
+
+    def _write_temp(self, target: Path, content: bytes) -> Path:
+        # Exclusive create instead of mkstemp so new files get umask-default permissions.
+        temp_path = target.with_name(f".{target.name}.{secrets.token_hex(4)}{TEMP_FILE_SUFFIX}")
+        file_obj = open(temp_path, "xb")  # noqa: SIM115
+        try:
+            with file_obj:
+                file_obj.write(content)
+            if target.exists():
+                shutil.copymode(target, temp_path)
+        except BaseException:
+            temp_path.unlink(missing_ok=True)
+            raise
+        return temp_path
+
+    def _rollback(self, snapshots: dict[Path, bytes | None]) -> None:
+        for target, original in snapshots.items():
+            if original is None:
+                target.unlink(missing_ok=True)
+                continue
+            target.write_bytes(original)
+
+
+def _resolve_target(filepath: str) -> Path:
+    return Path(os.path.realpath(filepath))
+
+
+def _has_other_links(target: Path) -> bool:
+    try:
+        return target.stat().st_nlink > 1
+    except FileNotFoundError:
+        return False
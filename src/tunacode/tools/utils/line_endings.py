@@ -0,0 +1,85 @@
+"""Line-ending detection and preservation for line-based edits.
+
+Lines split on ``\\r\\n``, ``\\r`` and ``\\n`` -- the same breaks ``read_file``'s
+text-mode reads use -- so line numbers and hashes agree with what the model
+was shown. Each line keeps its own ending, which lets an edit write back a
+CRLF or mixed-ending file without touching the endings it did not change.
+"""
+
+from __future__ import annotations
+
+import re
+from collections import Counter
+from collections.abc import Sequence
+
+LF = "\n"
+_LINE_BREAK_PATTERN = re.compile(r"\r\n|\r|\n")
+
+
+def split_lines(text: str) -> tuple[list[str], list[str]]:
+    """Split ``text`` into lines and their endings; the last ending is ``""`` when absent."""
+    lines: list[str] = []
+    endings: list[str] = []
+    start = 0
+    for match in _LINE_BREAK_PATTERN.finditer(text):
+        lines.append(text[start : match.start()])
+        endings.append(match.group())
+        start = match.end()
+    if start < len(text):
+        lines.append(text[start:])
+        endings.append("")
+    return lines, endings
+
+
+def dominant_ending(endings: Sequence[str]) -> str:
+    """The most common line ending, first seen on a tie; ``\\n`` when there is none."""
+    counts = Counter(ending for ending in endings if ending)
+    if not counts:
+        return LF
+    return counts.most_common(1)[0][0]
+
+
+def join_lines(lines: Sequence[str], endings: Sequence[str]) -> str:
+    return "".join(line + ending for line, ending in zip(lines, endings, strict=True))
+
+
+def _common_prefix(first: Sequence[str], second: Sequence[str]) -> int:
+    length = 0
+    for left, right in zip(first, second, strict=False):
+        if left != right:
+            break
+        length += 1
+    return length
+
+
+def carry_line_endings(
+    original_lines: Sequence[str],
+    original_endings: Sequence[str],
+    new_lines: Sequence[str],
+) -> list[str]:
+    """Endings for ``new_lines`` after a contiguous edit of ``original_lines``.
+
+    Lines outside the edited span keep their ending. Edited lines take the
+    ending of the original line at the same position in the span, and lines
+    the edit added take the file's dominant ending. Only the last line may
+    lack an ending, and it does exactly when the original file did.
+    """
+    prefix = _common_prefix(original_lines, new_lines)
+    limit = min(len(original_lines), len(new_lines)) - prefix
+    suffix = min(_common_prefix(original_lines[::-1], new_lines[::-1]), limit)
+    fallback = dominant_ending(original_endings)
+    old_span = original_endings[prefix : len(original_endings) - suffix]
+    new_span_length = len(new_lines) - prefix - suffix
+    span = [
+        old_span[index] if index < len(old_span) else fallback
+        for index in range(new_span_length)
+    ]
+    endings = [
+        *original_endings[:prefix],
+        *span,
+        *original_endings[len(original_endings) - suffix :],
+    ]
+    endings = [ending or fallback for ending in endings]
+    if endings and original_endings and original_endings[-1] == "":
+        endings[-1] = ""
+    return endings
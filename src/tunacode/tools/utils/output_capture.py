@@ -0,0 +1,102 @@
+"""Bounded capture of a command's output stream.
+
+A command that prints gigabytes (an accidental ``cat`` of a large file) must
+not be held in memory. ``BoundedCapture`` keeps the first and the last half of
+``max_bytes`` and only counts the bytes in between, so memory stays bounded
+however much the command writes. The kept halves are cut on UTF-8 character
+boundaries before decoding, so truncation never splits a character.
+"""
+
+from __future__ import annotations
+
+UTF8_CONTINUATION_MASK = 0xC0
+UTF8_CONTINUATION_BITS = 0x80
+MAX_UTF8_SEQUENCE_BYTES = 4
+
+
+def truncation_marker(kept_bytes: int, total_bytes: int) -> str:
+    return f"\n...\n[output truncated ({kept_bytes} of {total_bytes} bytes)]\n...\n"
+
+
+def _is_continuation(byte: int) -> bool:
+    return byte & UTF8_CONTINUATION_MASK == UTF8_CONTINUATION_BITS
+
+
+def _sequence_length(lead: int) -> int:
+    if lead >= 0xF0:
+        return 4
+    if lead >= 0xE0:
+        return 3
+    if lead >= 0xC0:
+        return 2
+    return 1
+
+
+def trim_incomplete_utf8_end(data: bytes) -> bytes:
+    """Drop a multi-byte character cut off at the end of ``data``."""
+    for back in range(1, min(MAX_UTF8_SEQUENCE_BYTES, len(data)) + 1):
+        byte = data[-back]
+        if _is_continuation(byte):
+            continue
+        if _sequence_length(byte) > back:
+            return data[:-back]
+        return data
+    return data
+
+
+def trim_incomplete_utf8_start(data: bytes) -> bytes:
+    """Drop continuation bytes of a character cut off at the start of ``data``."""
+    skip = 0
+    while skip < min(MAX_UTF8_SEQUENCE_BYTES - 1, len(data)) and _is_continuation(data[skip]):
+        skip += 1
+    return data[skip:]
+
+
+class BoundedCapture:
+    """Keep the head and tail of a byte stream within ``max_bytes``; 0 keeps everything."""
+
+    def __init__(self, max_bytes: int) -> None:
+        self._max_bytes = max_bytes
+        self._tail_limit = max_bytes // 2
+        self._head_limit = max_bytes - self._tail_limit
+        self._head = bytearray()
+        self._tail = bytearray()
+        self.total_bytes = 0
+
+    @property
+    def truncated(self) -> bool:
+        return self._max_bytes > 0 and self.total_bytes > self._max_bytes
+
+    def feed(self, chunk: bytes) -> None:
+        self.total_bytes += len(chunk)
+        if self._max_bytes <= 0:
+            self._head += chunk
+            return
+        room = self._head_limit - len(self._head)
+        if room > 0:
+            self._head += chunk[:room]
+            chunk = chunk[room:]
+        if not chunk or self._tail_limit == 0:
+            return
+        self._tail += chunk
+        overflow = len(self._tail) - self._tail_limit
+        if overflow > 0:
+            del self._tail[:overflow]
+
+    def text(self, encoding: str = "utf-8") -> str:
+        """Decode the kept bytes, with a marker where the middle was dropped."""
+        if not self.truncated:
+            return (bytes(self._head) + bytes(self._tail)).decode(encoding, errors="replace")
+        head = trim_incomplete_utf8_end(bytes(self._head))
+        tail = trim_incomplete_utf8_start(bytes(self._tail))
+        marker = truncation_marker(len(head) + len(tail), self.total_bytes)
+        return (
+            head.decode(encoding, errors="replace")
+            + marker
+            + tail.decode(encoding, errors="replace")
+        )
+
+    def tail_text(self, max_bytes: int, encoding: str = "utf-8") -> str:
+        """Decode up to the last ``max_bytes`` kept bytes, for live progress."""
+        kept = bytes(self._tail) if self.truncated else bytes(self._head) + bytes(self._tail)
+        return trim_incomplete_utf8_start(kept[-max_bytes:]).decode(encoding, errors="replace")
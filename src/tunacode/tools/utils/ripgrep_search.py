@@ -0,0 +1,201 @@
+"""Structured content search through the ripgrep binary.
+
+``search_with_ripgrep()`` runs ``rg --json`` from the working directory and
+turns its event stream into per-line matches with before/after context. It
+applies the same filters as the in-process walk of ``tools/grep.py``: the
+default ignore patterns, ``.gitignore`` (also outside a git repository),
+hidden files included, symlinks and files over 10MB skipped. Files ripgrep
+stops reading at a NUL byte count as binary and contribute no matches; binary
+files it skips without matching anything are not reported by ripgrep.
+
+It returns ``None`` when no ripgrep binary is available or ripgrep fails before
+reporting anything (for example a pattern only Python's ``re`` accepts), so
+the caller falls back to the in-process search.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import base64
+import json
+from collections.abc import AsyncIterable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from tunacode.configuration.ignore_patterns import DEFAULT_IGNORE_PATTERNS
+
+from tunacode.tools.utils.ripgrep import RIPGREP_SUCCESS_EXIT_CODES, get_ripgrep_binary_path
+
+RIPGREP_MAX_FILESIZE = "10M"
+# rg --json writes one event per line; a long matched line makes a long event.
+RIPGREP_EVENT_LINE_LIMIT = 64 * 1024 * 1024
+
+
+@dataclass(frozen=True, slots=True)
+class SearchQuery:
+    pattern: str
+    path: str
+    include: tuple[str, ...] = ()
+    case_insensitive: bool = False
+    context_lines: int = 0
+    max_matches: int = 100
+    timeout: float | None = None
+
+
+@dataclass(frozen=True, slots=True)
+class SearchMatch:
+    file: str
+    line: int
+    text: str
+    before: tuple[str, ...] = ()
+    after: tuple[str, ...] = ()
+
+
+@dataclass(slots=True)
+class SearchOutcome:
+    matches: list[SearchMatch] = field(default_factory=list)
+    files_searched: int = 0
+    binary_files_skipped: int = 0
+    truncated: bool = False
+
+
+def build_ripgrep_command(binary: Path, query: SearchQuery) -> list[str]:
+    """Return the ``rg`` argument list for ``query``, run from the working directory."""
+    command = [
+        str(binary),
+        "--json",
+        "--no-config",
+        "--hidden",
+        "--no-require-git",
+        "--no-messages",
+        "--max-filesize",
+        RIPGREP_MAX_FILESIZE,
+    ]
+    if query.case_insensitive:
+        command.append("--ignore-case")
+    if query.context_lines > 0:
+        command.extend(["--context", str(query.context_lines)])
+    # Later globs win in ripgrep, so the ignore defaults come after the includes.
+    for include in query.include:
+        command.extend(["--glob", include])
+    for ignored in DEFAULT_IGNORE_PATTERNS:
+        command.extend(["--glob", f"!{ignored}"])
+    command.extend(["--regexp", query.pattern, "--"])
+    if query.path not in ("", "."):
+        command.append(query.path)
+    return command
+
+
+class RipgrepEventCollector:
+    """Fold ``rg --json`` events into a ``SearchOutcome``, stopping at ``max_matches``."""
+
+    def __init__(self, *, context_lines: int, max_matches: int) -> None:
+        self.outcome = SearchOutcome()
+        self.saw_events = False
+        self._context_lines = context_lines
+        self._max_matches = max_matches
+        self._files_seen = 0
+        self._lines: dict[int, str] = {}
+        self._match_lines: list[int] = []
+
+    def feed(self, event: dict[str, Any]) -> bool:
+        """Apply one event; return False once the match cap is exceeded."""
+        self.saw_events = True
+        kind = event.get("type")
+        data = event.get("data") or {}
+        if kind == "begin":
+            self._files_seen += 1
+            self._lines, self._match_lines = {}, []
+        elif kind in ("match", "context"):
+            line_number = data["line_number"]
+            self._lines[line_number] = _line_text(data["lines"])
+            if kind == "match":
+                self._match_lines.append(line_number)
+        elif kind == "end":
+            return self._finish_file(_event_text(data["path"]), data.get("binary_offset"))
+        elif kind == "summary":
+            searched = data.get("stats", {}).get("searches", 0)
+            self.outcome.files_searched = max(searched, self._files_seen)
+        return True
+
+    def finish(self) -> SearchOutcome:
+        self.outcome.files_searched = max(self.outcome.files_searched, self._files_seen)
+        return self.outcome
+
+    def _finish_file(self, file: str, binary_offset: object) -> bool:
+        if binary_offset is not None:
+            self.outcome.binary_files_skipped += 1
+            return True
+        context = self._context_lines
+        for line_number in self._match_lines:
+            if len(self.outcome.matches) >= self._max_matches:
+                self.outcome.truncated = True
+                return False
+            self.outcome.matches.append(
+                SearchMatch(
+                    file=file.removeprefix("./"),
+                    line=line_number,
+                    text=self._lines[line_number],
+                    before=self._window(line_number - context, line_number),
+                    after=self._window(line_number + 1, line_number + 1 + context),
+                )
+            )
+        return True
+
+    def _window(self, start: int, stop: int) -> tuple[str, ...]:
+        return tuple(self._lines[number] for number in range(start, stop) if number in self._lines)
+
+
+async def search_with_ripgrep(query: SearchQuery, root: Path) -> SearchOutcome | None:
+    """Run ``query`` through ripgrep, or return ``None`` to request the fallback search."""
+    binary = get_ripgrep_binary_path()
+    if binary is None:
+        return None
+    try:
+        process = await asyncio.create_subprocess_exec(
+            *build_ripgrep_command(binary, query),
+            cwd=root,
+            stdout=asyncio.subprocess.PIPE,
+            stderr=asyncio.subprocess.DEVNULL,
+            limit=RIPGREP_EVENT_LINE_LIMIT,
+        )
+    except OSError:
+        return None
+
+    assert process.stdout is not None
+    collector = RipgrepEventCollector(
+        context_lines=query.context_lines, max_matches=query.max_matches
+    )
+    try:
+        async with asyncio.timeout(query.timeout):
+            await _collect_events(process.stdout, collector)
+    except TimeoutError:
+        collector.outcome.truncated = True
+    finally:
+        if process.returncode is None:
+            process.kill()
+        await process.wait()
+
+    failed = process.returncode not in RIPGREP_SUCCESS_EXIT_CODES
+    if failed and not collector.saw_events and not collector.outcome.truncated:
+        return None
+    return collector.finish()
+
+
+async def _collect_events(stream: AsyncIterable[bytes], collector: RipgrepEventCollector) -> None:
+    async for raw_line in stream:
+        if not raw_line.strip():
+            continue
+        if not collector.feed(json.loads(raw_line)):
+            return
+
+
+def _event_text(value: dict[str, str]) -> str:
+    if "text" in value:
+        return value["text"]
+    return base64.b64decode(value.get("bytes", "")).decode("utf-8", errors="replace")
+
+
+def _line_text(value: dict[str, str]) -> str:
+    return _event_text(value).removesuffix("\n").removesuffix("\r")
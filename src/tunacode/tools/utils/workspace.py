@@ -0,0 +1,33 @@
+"""Working-directory jail shared by path-taking tools."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+from tunacode.exceptions import ToolRetryError
+
+ERROR_OUTSIDE_WORKSPACE = (
+    "Path '{path}' is outside the working directory '{root}'. "
+    "Use a path inside the project instead."
+)
+
+
+def jail_workspace_path(path: str, root: Path) -> Path:
+    """Resolve ``path`` against ``root``; the path need not exist yet."""
+
+    candidate = Path(path)
+    if not candidate.is_absolute():
+        candidate = root / candidate
+    resolved = candidate.resolve()
+    if resolved != root and root not in resolved.parents:
+        raise ToolRetryError(ERROR_OUTSIDE_WORKSPACE.format(path=path, root=root))
+    return resolved
+
+
+def resolve_workspace_path(path: str, root: Path) -> Path:
+    """Resolve ``path`` against ``root`` and reject anything that escapes it."""
+
+    resolved = jail_workspace_path(path, root)
+    if not resolved.exists():
+        raise FileNotFoundError(path)
+    return resolved
@@ -19,6 +19,7 @@ from tunacode.exceptions import (
 )
 
 from tunacode.tools.utils.file_errors import translate_file_tool_errors
+from tunacode.tools.utils.file_transaction import FileTransaction
 
 _WRITE_FILE_DESCRIPTION = """Write content to a new file. Fails if the file already exists."""
 
@@ -48,8 +49,9 @@ async def _run_write_file(filepath: str, content: str) -> str:
     if dirpath and not os.path.exists(dirpath):
         os.makedirs(dirpath, exist_ok=True)
 
-    with open(filepath, "w", encoding="utf-8") as file_obj:
-        file_obj.write(content)
+    transaction = FileTransaction()
+    transaction.stage(filepath, content)
+    transaction.commit()
 
     result = f"Successfully wrote to new file: {filepath}"
     return result
@@ -13,14 +13,19 @@ from typing import Any
 from tunacode.types.base import (  # noqa: F401
     AgentConfig,
     AgentName,
+    AutoFormatSettings,
+    BackgroundResponseSettings,
     CommandArgs,
+    CommandPolicySettings,
     CommandResult,
     ConfigFile,
     ConfigPath,
     CostAmount,
     DiffHunk,
     DiffLine,
+    DraftAutosaveSettings,
     EnvConfig,
+    FallbackProviderSettings,
     ErrorContext,
     ErrorMessage,
     FileContent,
@@ -28,12 +33,25 @@ from tunacode.types.base import (  # noqa: F401
     FileEncoding,
     FilePath,
     FileSize,
+    HistorySettings,
     InputSessions,
     LineNumber,
+    LoopDetectionSettings,
+    ModelLimitSettings,
     ModelName,
+    OllamaSettings,
     OriginalError,
+    PromptedToolsSettings,
+    ProviderHttpSettings,
+    ReadFileSettings,
+    RetryBackoffSettings,
     RipgrepSettings,
+    SecretRedactionSettings,
     SessionId,
+    ShellSettings,
+    StreamCoalescingSettings,
+    SystemPromptSettings,
+    TerminalSettings,
     TokenCount,
     ToolArgs,
     ToolCallId,
@@ -44,6 +62,7 @@ from tunacode.types.base import (  # noqa: F401
     UserSettings,
     ValidationResult,
     Validator,
+    WatchSettings,
 )
 
 # Callback types
@@ -56,10 +75,14 @@ from tunacode.types.callbacks import (  # noqa: F401
     StreamResultProtocol,
     ToolCallback,
     ToolCallPartProtocol,
+    ToolProgress,
+    ToolProgressCallback,
     ToolResultCallback,
     ToolStartCallback,
     UICallback,
     UIInputCallback,
+    UsageCallback,
+    UsageUpdate,
 )
 from tunacode.types.dataclasses import (  # noqa: F401
     CostBreakdown,
@@ -138,6 +161,7 @@ class UsageMetrics:
     cache_read: int = 0
     cache_write: int = 0
     total_tokens: int = 0
+    reasoning: int = 0
     cost: UsageCost = field(default_factory=UsageCost)
 
     def add(self, other: "UsageMetrics") -> None:
@@ -147,6 +171,7 @@ class UsageMetrics:
         self.cache_read += other.cache_read
         self.cache_write += other.cache_write
         self.total_tokens += other.total_tokens
+        self.reasoning += other.reasoning
         self.cost.add(other.cost)
 
     @classmethod
@@ -170,6 +195,7 @@ class UsageMetrics:
             cache_read=int(data["cache_read"]),
             cache_write=int(data["cache_write"]),
             total_tokens=int(data["total_tokens"]),
+            reasoning=int(data.get("reasoning", 0)),
             cost=UsageCost.from_dict(cost_raw),
         )
 
@@ -181,5 +207,6 @@ class UsageMetrics:
             "cache_read": self.cache_read,
             "cache_write": self.cache_write,
             "total_tokens": self.total_tokens,
+            "reasoning": self.reasoning,
             "cost": self.cost.to_dict(),
         }
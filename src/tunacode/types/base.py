@@ -32,17 +32,164 @@ class RipgrepSettings(TypedDict):
     enable_metrics: bool
 
 
+class CommandPolicySettings(TypedDict):
+    read_only: str
+    write: str
+    network: str
+    destructive: str
+
+
+class FallbackProviderSettings(TypedDict):
+    model: str
+    base_url: str
+
+
+class ModelLimitSettings(TypedDict):
+    context_window: int | None
+    max_tokens: int | None
+
+
+class StreamCoalescingSettings(TypedDict):
+    window_ms: int
+    max_chars: int
+
+
+class HistorySettings(TypedDict):
+    mode: str
+    window_turns: int
+
+
+class LoopDetectionSettings(TypedDict):
+    threshold: int
+    action: str
+
+
+class ShellSettings(TypedDict):
+    program: str
+    args: list[str]
+    max_capture_bytes: int
+    stream_output: bool
+
+
+class TerminalSettings(TypedDict):
+    color: str
+    unicode: str
+
+
+class DraftAutosaveSettings(TypedDict):
+    enabled: bool
+    debounce_ms: int
+    max_age_hours: int
+
+
+class WatchSettings(TypedDict):
+    debounce_seconds: float
+    cooldown_seconds: float
+    max_runs: int
+
+
+class ReadFileSettings(TypedDict):
+    max_bytes: int
+    window_head_lines: int
+    window_tail_lines: int
+
+
+class BackgroundResponseSettings(TypedDict):
+    enabled: bool
+    max_reconnects: int
+
+
+class RetryBackoffSettings(TypedDict):
+    strategy: str
+    base_delay: float
+    max_delay: float
+
+
+class ProviderHttpSettings(TypedDict):
+    max_connections: int
+    max_keepalive_connections: int
+    keepalive_expiry: float
+    http2: bool
+
+
+class SystemPromptSettings(TypedDict):
+    override: str
+    prefix: str
+    suffix: str
+
+
+class OllamaSettings(TypedDict):
+    native_api: bool
+    keep_alive: str
+    options: dict[str, int | float | str | bool]
+
+
+class PromptedToolsSettings(TypedDict):
+    models: list[str]
+    format: str
+
+
+class AutoFormatSettings(TypedDict):
+    enabled: bool
+    formatters: dict[str, str]
+    timeout: float
+
+
+class SecretRedactionSettings(TypedDict):
+    enabled: bool
+    patterns: list[str]
+    entropy_threshold: float
+    entropy_min_length: int
+
+
 class UserSettings(TypedDict):
     max_retries: int
     max_iterations: int
     request_delay: float
     global_request_timeout: float
+    turn_deadline: float
     tool_strict_validation: bool
+    recover_partial_tool_calls: bool
+    safe_mode: bool
     theme: str
     stream_agent_text: bool
+    show_thoughts: bool
     max_command_output: int
+    max_command_output_history: int
     max_tokens: int | None
+    model_limits: dict[ModelName, ModelLimitSettings]
+    output_reserve_fraction: float
+    auto_compact: bool
+    history: HistorySettings
+    system_prompt_max_tokens: int | None
+    system_prompt: SystemPromptSettings
+    thinking_budget: int | None
+    task_decomposition: bool
+    retain_raw_responses: bool
+    user_message_prefix: str
+    user_message_suffix: str
+    read_file: ReadFileSettings
     ripgrep: RipgrepSettings
+    command_policy: CommandPolicySettings
+    shell: ShellSettings
+    loop_detection: LoopDetectionSettings
+    stream_coalescing: StreamCoalescingSettings
+    stream_buffer_max_chars: int
+    fallback_providers: list[FallbackProviderSettings]
+    fallback_model: ModelName | None
+    base_url_probe_path: str
+    code_wrap_mode: str
+    terminal: TerminalSettings
+    draft_autosave: DraftAutosaveSettings
+    watch: WatchSettings
+    background_responses: BackgroundResponseSettings
+    retry_backoff: RetryBackoffSettings
+    provider_http: dict[str, ProviderHttpSettings]
+    ollama: OllamaSettings
+    prompted_tools: PromptedToolsSettings
+    auto_format: AutoFormatSettings
+    secret_redaction: SecretRedactionSettings
+    unknown_slash_commands: str
 
 
 EnvConfig = dict[str, str]
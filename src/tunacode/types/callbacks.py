@@ -9,13 +9,23 @@ Callback contracts (preconditions/postconditions):
   should not raise.
 - StreamingCallback: Preconditions: chunk is ordered text delta. Postconditions:
   enqueue or render the chunk without raising.
+- ToolProgressCallback: Preconditions: every step reports STEP_STARTED once, then
+  exactly one of STEP_COMPLETED or STEP_FAILED under the same step_id.
+  Postconditions: update the step in place without raising.
+- UsageCallback: Preconditions: zero or more interim updates per model call, each
+  larger than the last, then exactly one ``final`` update with the authoritative
+  usage. Postconditions: side effects only (UI/logging) and should not raise.
 """
 
 from collections.abc import Awaitable, Callable
-from typing import Any, Protocol, TypeAlias, runtime_checkable
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Any, Protocol, TypeAlias, runtime_checkable
 
 from tunacode.types.base import ToolArgs, ToolName, ToolResult
 
+if TYPE_CHECKING:
+    from tunacode.types import UsageMetrics
+
 # =============================================================================
 # Protocol Types (Framework-Agnostic)
 # =============================================================================
@@ -66,6 +76,39 @@ ToolResultCallback: TypeAlias = Callable[
 ]
 NoticeCallback: TypeAlias = Callable[[str], None]
 
+STEP_STARTED = "started"
+STEP_COMPLETED = "completed"
+STEP_FAILED = "failed"
+
+
+@dataclass(frozen=True, slots=True)
+class ToolProgress:
+    """One step of live progress; ``step_id`` is stable across its updates."""
+
+    step_id: str
+    label: str
+    status: str
+
+
+ToolProgressCallback: TypeAlias = Callable[[ToolProgress], None]
+
+
+@dataclass(frozen=True, slots=True)
+class UsageUpdate:
+    """Usage of the model call in flight, and the session total including it.
+
+    Interim updates (``final`` False) are running snapshots reported by the
+    provider mid-stream; the ``final`` update carries the call's authoritative
+    usage, already added to the session total.
+    """
+
+    call_usage: "UsageMetrics"
+    session_total: "UsageMetrics"
+    final: bool
+
+
+UsageCallback: TypeAlias = Callable[[UsageUpdate], None]
+
 # UI callbacks
 StreamingCallback: TypeAlias = Callable[[str], Awaitable[None]]
 UICallback: TypeAlias = Callable[[str], Awaitable[None]]
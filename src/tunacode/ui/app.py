@@ -22,8 +22,11 @@ if TYPE_CHECKING:
     from textual.theme import Theme
 
     from tunacode.core.session import StateManager
+    from tunacode.types import ToolProgress
+    from tunacode.ui.drafts import DraftAutosave
     from tunacode.ui.lifecycle import AppLifecycle
     from tunacode.ui.shell_runner import ShellRunner
+    from tunacode.utils.system.shell_program import ShellInvocation
 
 from tunacode.constants import (
     MIN_TOOL_PANEL_LINE_WIDTH,
@@ -40,6 +43,7 @@ from tunacode.ui.context_panel import (
     build_context_gauge,
     build_context_panel_widgets,
     build_files_field,
+    build_progress_field,
     build_skills_field,
     is_widget_within_field,
     token_color,
@@ -65,20 +69,30 @@ from tunacode.ui.slopgotchi import (
 )
 from tunacode.ui.streaming import StreamingHandler
 from tunacode.ui.styles import STYLE_PRIMARY, STYLE_SUCCESS, STYLE_WARNING
+from tunacode.ui.terminal import (
+    TerminalCapabilities,
+    resolve_terminal_capabilities,
+    set_terminal_capabilities,
+    textual_environment,
+)
 from tunacode.ui.thinking_state import ThinkingState
+from tunacode.ui.watch_mode import WatchMode
 
 from tunacode.ui.widgets import (
     ChatContainer,
     CommandAutoComplete,
     CompactionStatusChanged,
     Editor,
+    EditorDraftChanged,
     EditorSubmitRequested,
     FileAutoComplete,
     ResourceBar,
     SkillsAutoComplete,
     SystemNoticeDisplay,
+    ToolProgressChanged,
     ToolResultDisplay,
     TuiLogDisplay,
+    UsageChanged,
 )
 
 
@@ -118,7 +132,13 @@ class TextualReplApp(App[None]):
     CONTEXT_PANEL_MIN_TERMINAL_WIDTH: int = 80
 
     def __init__(self, *, state_manager: StateManager, show_setup: bool = False) -> None:
-        super().__init__()
+        capabilities = resolve_terminal_capabilities(
+            state_manager.session.user_config["settings"]["terminal"]
+        )
+        set_terminal_capabilities(capabilities)
+        with textual_environment(capabilities):
+            super().__init__()
+        self.terminal_capabilities: TerminalCapabilities = capabilities
         self.register_theme(build_tunacode_theme())
         self.register_theme(build_nextstep_theme())
         for theme in wrap_builtin_themes(self.available_themes):
@@ -133,6 +153,7 @@ class TextualReplApp(App[None]):
         self._request_start_time: float = 0.0
         self._esc_handler: EscHandler = EscHandler()
         self._shell_runner: ShellRunner | None = None
+        self._draft_autosave: DraftAutosave | None = None
         self.chat_container: ChatContainer
         self.editor: Editor
         self.resource_bar: ResourceBar
@@ -144,6 +165,8 @@ class TextualReplApp(App[None]):
         self._field_context: Static | None = None
         self._field_cost: Static | None = None
         self._field_files: Static | None = None
+        self._field_progress: Static | None = None
+        self._progress_steps: dict[str, ToolProgress] = {}
         self._field_skills: Static | None = None
         self._slopgotchi_state: SlopgotchiPanelState = SlopgotchiPanelState()
         self._field_slopgotchi: Static | None = None
@@ -155,6 +178,7 @@ class TextualReplApp(App[None]):
 
         self._thinking_state = ThinkingState(self)
         self._request_debug = RequestDebugTracer(self)
+        self.watch_mode = WatchMode(self)
 
     def compose(self) -> ComposeResult:
         self.resource_bar = ResourceBar()
@@ -185,6 +209,7 @@ class TextualReplApp(App[None]):
                 self._field_context = context_panel_widgets.field_context
                 self._field_cost = context_panel_widgets.field_cost
                 self._field_files = context_panel_widgets.field_files
+                self._field_progress = context_panel_widgets.field_progress
                 self._field_skills = context_panel_widgets.field_skills
                 yield from context_panel_widgets.widgets
         yield self.editor
@@ -200,6 +225,11 @@ class TextualReplApp(App[None]):
             if theme_name in self.available_themes
         }
 
+    @property
+    def request_in_flight(self) -> bool:
+        """Whether an agent request is running right now."""
+        return self._current_request_task is not None
+
     @property
     def shell_runner(self) -> ShellRunner:
         shell_runner = self._shell_runner
@@ -328,7 +358,12 @@ class TextualReplApp(App[None]):
             self._request_debug.loading_shown(reason="request_start")
         self._show_loading_indicator()
         self._thinking_state.clear()
-        bridge = RequestUiBridge(self)
+        self._progress_steps.clear()
+        self._refresh_progress_field()
+        bridge = RequestUiBridge(
+            self,
+            max_buffered_chars=session.user_config["settings"]["stream_buffer_max_chars"],
+        )
         self._request_bridge = bridge
         self._start_delta_flush_timer()
         try:
@@ -352,6 +387,8 @@ class TextualReplApp(App[None]):
                     tool_start_callback=None,
                     notice_callback=bridge.notice_callback,
                     compaction_status_callback=bridge.compaction_status_callback,
+                    tool_progress_callback=bridge.tool_progress_callback,
+                    usage_callback=bridge.usage_callback,
                 ),
                 exit_on_error=False,
                 name="process_request",
@@ -392,6 +429,7 @@ class TextualReplApp(App[None]):
                     time.monotonic() - final_flush_started_at
                 ) * self.MILLISECONDS_PER_SECOND
                 self._stop_delta_flush_timer()
+                bridge.close()
                 self._request_bridge = None
                 self._current_request_task = None
             if self._loading_indicator_shown:
@@ -481,11 +519,21 @@ class TextualReplApp(App[None]):
     async def on_editor_submit_requested(self, message: EditorSubmitRequested) -> None:
         from tunacode.ui.commands import handle_command
 
+        if self._draft_autosave is not None:
+            self._draft_autosave.discard()
         if await handle_command(self, message.text):
             return
-        normalized_message = normalize_agent_message_text(message.text)
+        self.submit_user_message(message.text)
+
+    def on_editor_draft_changed(self, message: EditorDraftChanged) -> None:
+        if self._draft_autosave is not None:
+            self._draft_autosave.schedule(message.text)
+
+    def submit_user_message(self, text: str) -> None:
+        """Echo ``text`` as the user's message and queue it as an agent request."""
+        normalized_message = normalize_agent_message_text(text)
         submission_trace = self._request_debug.submit_received(
-            raw_text=message.text,
+            raw_text=text,
             normalized_text=normalized_message,
         )
         if not self._loading_indicator_shown:
@@ -496,7 +544,7 @@ class TextualReplApp(App[None]):
         timestamp = datetime.now().strftime("%I:%M %p").lstrip("0")
         self.chat_container.write("")
         render_width = max(1, self.chat_container.size.width - 2)
-        user_block = format_user_message(message.text, STYLE_PRIMARY, width=render_width)
+        user_block = format_user_message(text, STYLE_PRIMARY, width=render_width)
         user_block.append(f"│ you {timestamp}", style=f"dim {STYLE_PRIMARY}")
         self.chat_container.write(user_block).add_class("user-message")
         self._queue_request_after_refresh(normalized_message, submission_trace)
@@ -510,6 +558,24 @@ class TextualReplApp(App[None]):
     def on_compaction_status_changed(self, message: CompactionStatusChanged) -> None:
         self._update_compaction_status(message.active)
 
+    def on_tool_progress_changed(self, message: ToolProgressChanged) -> None:
+        progress = message.progress
+        self._progress_steps[progress.step_id] = progress
+        self._refresh_progress_field()
+
+    def on_usage_changed(self, message: UsageChanged) -> None:
+        self.resource_bar.update_stats(session_cost=message.update.session_total.cost.total)
+
+    def _refresh_progress_field(self) -> None:
+        field_progress = self._field_progress
+        if field_progress is None:
+            return
+        progress_title, progress_content = build_progress_field(
+            list(self._progress_steps.values())
+        )
+        field_progress.border_title = progress_title
+        field_progress.update(progress_content)
+
     def on_tool_result_display(self, message: ToolResultDisplay) -> None:
         from tunacode.ui.renderers.panels import tool_panel_smart
 
@@ -537,6 +603,12 @@ class TextualReplApp(App[None]):
         self._edited_files.add(filepath)
         self._refresh_context_panel()
 
+    def shell_invocation(self) -> ShellInvocation:
+        from tunacode.utils.system.shell_program import resolve_shell
+
+        shell = self.state_manager.session.user_config["settings"]["shell"]
+        return resolve_shell(shell["program"], shell["args"])
+
     def tool_panel_max_width(self) -> int:
         viewport = self.query_one("#viewport")
         width_candidates = [
@@ -558,11 +630,12 @@ class TextualReplApp(App[None]):
 
     def _replay_session_messages(self) -> None:
         """Render loaded session messages to ChatContainer."""
-        from rich.markdown import Markdown
         from tinyagent.agent_types import AssistantMessage, UserMessage
 
         from tunacode.utils.messaging import get_content
 
+        from tunacode.ui.renderers.code_wrap import WrappedMarkdown
+
         conversation = self.state_manager.session.conversation
         for message in conversation.messages:
             if isinstance(message, UserMessage):
@@ -584,7 +657,7 @@ class TextualReplApp(App[None]):
                 continue
 
             self.chat_container.write(Text("agent:", style="accent"))
-            self.chat_container.write(Markdown(content))
+            self.chat_container.write(WrappedMarkdown(content))
 
     def _context_panel_supported_for_width(self, width: int) -> bool:
         return width >= self.CONTEXT_PANEL_MIN_TERMINAL_WIDTH
@@ -675,12 +748,14 @@ class TextualReplApp(App[None]):
                 max_tokens=max_tokens,
                 remaining_pct=remaining_pct,
                 token_style=current_token_style,
+                unicode=self.terminal_capabilities.unicode,
             )
         )
         field_cost.update(Text(cost_display, style=f"bold {STYLE_SUCCESS}"))
         files_title, files_content = build_files_field(self._edited_files)
         field_files.border_title = files_title
         field_files.update(files_content)
+        self._refresh_progress_field()
 
         skill_entries = self._build_skill_entries()
         skills_title, skills_content = build_skills_field(skill_entries)
@@ -29,16 +29,26 @@ _COMMAND_SPECS: dict[str, CommandSpec] = {
     ),
     "debug": CommandSpec("debug", "DebugCommand", "Toggle debug mode"),
     "exit": CommandSpec("exit", "ExitCommand", "Exit TunaCode"),
+    "meta": CommandSpec("meta", "MetaCommand", "Show or set session metadata and tags"),
     "model": CommandSpec("model", "ModelCommand", "Change or show current model"),
     "resume": CommandSpec("resume", "ResumeCommand", "Resume a previous session"),
     "skills": CommandSpec("skills", "SkillsCommand", "Browse, search, and load session skills"),
+    "tasks": CommandSpec("tasks", "TasksCommand", "Show the session task list"),
     "theme": CommandSpec("theme", "ThemeCommand", "Change the active theme"),
     "thoughts": CommandSpec(
         "thoughts",
         "ThoughtsCommand",
         "Toggle streaming of agent thought text",
     ),
+    "tools": CommandSpec("tools", "ToolsCommand", "List available tools and their parameters"),
+    "undo": CommandSpec("undo", "UndoCommand", "Undo file edits from the last agent turn"),
     "update": CommandSpec("update", "UpdateCommand", "Update tunacode to latest version"),
+    "watch": CommandSpec("watch", "WatchCommand", "Re-run a prompt when watched files change"),
+    "wrap": CommandSpec(
+        "wrap",
+        "WrapCommand",
+        "Set code block wrapping: wrap, scroll, or truncate",
+    ),
 }
 
 COMMAND_DESCRIPTIONS: dict[str, str] = {
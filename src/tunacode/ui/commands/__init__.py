@@ -4,6 +4,13 @@ from __future__ import annotations
 
 from typing import TYPE_CHECKING
 
+from tunacode.constants import UnknownCommandMode
+from tunacode.prompts.custom_prompts import (
+    CustomPromptError,
+    find_custom_prompt,
+    render_custom_prompt,
+)
+
 from tunacode.ui.command_registry import COMMANDS
 
 if TYPE_CHECKING:
@@ -13,6 +20,11 @@ if TYPE_CHECKING:
 async def handle_command(app: TextualReplApp, text: str) -> bool:
     """Handle a command if text starts with / or !.
 
+    A ``/name`` that is not a built-in command is expanded from the custom
+    prompt of that name and submitted. Other unknown names are rejected, or
+    sent to the agent unchanged with ``settings.unknown_slash_commands`` set to
+    ``pass_through``.
+
     Returns True if command was handled, False otherwise.
     """
 
@@ -29,6 +41,13 @@ async def handle_command(app: TextualReplApp, text: str) -> bool:
             await COMMANDS[cmd_name].execute(app, cmd_args)
             return True
 
+        if cmd_name and _submit_custom_prompt(app, cmd_name, cmd_args):
+            return True
+
+        settings = app.state_manager.session.user_config["settings"]
+        if settings["unknown_slash_commands"] == UnknownCommandMode.PASS_THROUGH:
+            return False
+
         app.notify(f"Unknown command: /{cmd_name}", severity="warning")
         return True
 
@@ -37,3 +56,20 @@ async def handle_command(app: TextualReplApp, text: str) -> bool:
         return True
 
     return False
+
+
+def _submit_custom_prompt(app: TextualReplApp, name: str, args: str) -> bool:
+    """Expand and submit the custom prompt ``name``; False when there is none."""
+    try:
+        prompt = find_custom_prompt(name)
+        if prompt is None:
+            return False
+        expanded = render_custom_prompt(prompt, args)
+    except CustomPromptError as exc:
+        app.notify(str(exc), severity="error")
+        return True
+    if not expanded:
+        app.notify(f"Custom prompt /{name} is empty", severity="warning")
+        return True
+    app.submit_user_message(expanded)
+    return True
@@ -30,6 +30,8 @@ if TYPE_CHECKING:
 
 COMPACT_USAGE_HINT = "Usage: /compact"
 COMPACT_EMPTY_HISTORY_NOTICE = "Nothing to compact."
+COMPACT_BUSY_NOTICE = "Cannot compact while a request is running. Wait for it or cancel it first."
+COMPACT_SUMMARY_PREVIEW_CHARS = 600
 COMPACT_COMPLETE_TEMPLATE = "Compaction complete: {removed} messages, ~{tokens} tokens reclaimed"
 _AGENT_MESSAGE_TYPES = UserMessage, AssistantMessage, ToolResultMessage, CustomAgentMessage
 
@@ -46,6 +48,10 @@ class CompactCommand(Command):
             app.notify(COMPACT_USAGE_HINT, severity="warning")
             return
 
+        if app.request_in_flight:
+            app.notify(COMPACT_BUSY_NOTICE, severity="warning")
+            return
+
         session = app.state_manager.session
         conversation = session.conversation
 
@@ -121,6 +127,9 @@ class CompactCommand(Command):
                 tokens=reclaimed_tokens,
             )
         )
+        record = session.compaction
+        if record is not None and record.summary:
+            app.chat_container.write(_summary_preview(record.summary))
 
 
 def _coerce_history(messages: list[AgentMessage]) -> list[AgentMessage]:
@@ -128,3 +137,10 @@ def _coerce_history(messages: list[AgentMessage]) -> list[AgentMessage]:
         return list(messages)
 
     raise TypeError("Session history must contain tinyagent message models")
+
+
+def _summary_preview(summary: str) -> str:
+    text = summary.strip()
+    if len(text) > COMPACT_SUMMARY_PREVIEW_CHARS:
+        text = text[: COMPACT_SUMMARY_PREVIEW_CHARS - 1].rstrip() + "…"
+    return f"Summary:\n{text}"
@@ -1,7 +1,9 @@
-"""Debug command for toggling UI debug logging."""
+"""Debug command for toggling UI debug logging and showing raw responses, the prompt and
+the next request's token estimate."""
 
 from __future__ import annotations
 
+import json
 from typing import TYPE_CHECKING
 
 from tunacode.ui.commands.base import Command
@@ -9,14 +11,39 @@ from tunacode.ui.commands.base import Command
 if TYPE_CHECKING:
     from tunacode.ui.app import TextualReplApp
 
+RAW_SUBCOMMAND = "raw"
+PROMPT_SUBCOMMAND = "prompt"
+TOKENS_SUBCOMMAND = "tokens"
+TOKENS_TEMPLATE = (
+    "Next request: ~{total:,} tokens (system {system:,}, history {history:,}, "
+    "tools {tools:,}, message {next_message:,}, formatting {overhead:,})"
+)
+PROMPT_EMPTY_NOTICE = "No system prompt yet; it is built with the agent on the first request."
+RAW_DISABLED_NOTICE = "Raw responses are not retained. Set settings.retain_raw_responses to true."
+RAW_USAGE = "Usage: /debug raw [index]"
+
 
 class DebugCommand(Command):
-    """Toggle debug logging to the screen and configured file logger."""
+    """Toggle debug logging, or show a retained raw provider response or the system prompt."""
 
     name = "debug"
-    description = "Toggle debug logging to screen"
+    description = (
+        "Toggle debug logging to screen (/debug raw [index] for responses, "
+        "/debug prompt for the system prompt, /debug tokens [message] for a request estimate)"
+    )
 
     async def execute(self, app: TextualReplApp, args: str) -> None:
+        parts = args.split()
+        if parts and parts[0] == RAW_SUBCOMMAND:
+            _show_raw_response(app, parts[1:])
+            return
+        if parts and parts[0] == PROMPT_SUBCOMMAND:
+            _show_system_prompt(app)
+            return
+        if parts and parts[0] == TOKENS_SUBCOMMAND:
+            _show_request_estimate(app, args.strip().removeprefix(TOKENS_SUBCOMMAND).strip())
+            return
+
         from tunacode.core.debug import log_usage_update
         from tunacode.core.logging import get_logger
 
@@ -60,3 +87,56 @@ class DebugCommand(Command):
                 last_call_usage=session.usage.last_call_usage,
                 session_total_usage=session.usage.session_total_usage,
             )
+
+
+def _show_raw_response(app: TextualReplApp, args: list[str]) -> None:
+    session = app.state_manager.session
+    if not session.user_config["settings"]["retain_raw_responses"]:
+        app.notify(RAW_DISABLED_NOTICE, severity="warning")
+        return
+    if len(args) > 1 or (args and not args[0].isdigit()):
+        app.notify(RAW_USAGE, severity="warning")
+        return
+
+    index = int(args[0]) if args else None
+    record = session.raw_responses.get(index)
+    if record is None:
+        app.notify("No raw response recorded" + (f" with index {index}" if args else ""))
+        return
+
+    from rich.syntax import Syntax
+
+    payload = json.dumps(record.to_dict(), indent=2, default=str)
+    app.chat_container.write(Syntax(payload, "json", word_wrap=True))
+
+
+def _show_system_prompt(app: TextualReplApp) -> None:
+    system_prompt = app.state_manager.session.system_prompt
+    if not system_prompt:
+        app.notify(PROMPT_EMPTY_NOTICE)
+        return
+
+    from rich.text import Text
+
+    app.chat_container.write(Text(system_prompt))
+
+
+def _show_request_estimate(app: TextualReplApp, message: str) -> None:
+    from rich.text import Text
+
+    from tunacode.core.agents.agent_components.request_estimate import estimate_next_request
+
+    session = app.state_manager.session
+    estimate = estimate_next_request(session.current_model, app.state_manager, message)
+    app.chat_container.write(
+        Text(
+            TOKENS_TEMPLATE.format(
+                total=estimate.total,
+                system=estimate.system,
+                history=estimate.history,
+                tools=estimate.tools,
+                next_message=estimate.next_message,
+                overhead=estimate.overhead,
+            )
+        )
+    )
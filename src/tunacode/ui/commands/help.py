@@ -4,32 +4,99 @@ from __future__ import annotations
 
 from typing import TYPE_CHECKING
 
+from tunacode.prompts.custom_prompts import (
+    CustomPrompt,
+    CustomPromptError,
+    find_custom_prompt,
+    list_custom_prompts,
+)
+
 from tunacode.ui.commands.base import Command
-from tunacode.ui.styles import STYLE_PRIMARY
+from tunacode.ui.styles import STYLE_MUTED, STYLE_PRIMARY
 
 if TYPE_CHECKING:
     from tunacode.ui.app import TextualReplApp
 
+CUSTOM_PROMPT_FALLBACK_DESCRIPTION = "Custom prompt"
+
 
 class HelpCommand(Command):
-    """Display available commands in a table."""
+    """Display available commands in a table, or details for one command."""
 
     name = "help"
     description = "Show available commands"
+    usage = "/help [command]"
 
-    async def execute(self, app: TextualReplApp, _args: str) -> None:
+    async def execute(self, app: TextualReplApp, args: str) -> None:
         from rich.table import Table
 
         from tunacode.ui.command_registry import COMMAND_DESCRIPTIONS
 
+        name = args.strip().lstrip("/").lower()
+        if name:
+            _show_command_help(app, name)
+            return
+
         table = Table(title="Commands", show_header=True)
         table.add_column("Command", style=STYLE_PRIMARY)
         table.add_column("Description")
 
-        for name, description in COMMAND_DESCRIPTIONS.items():
-            table.add_row(f"/{name}", description)
+        for command_name, description in COMMAND_DESCRIPTIONS.items():
+            table.add_row(f"/{command_name}", description)
+
+        for prompt in _visible_custom_prompts(app):
+            if prompt.name.casefold() in COMMAND_DESCRIPTIONS:
+                continue
+            description = prompt.schema.description or CUSTOM_PROMPT_FALLBACK_DESCRIPTION
+            table.add_row(prompt.schema.usage(prompt.name), description)
 
         table.add_row("!<cmd>", "Run shell command")
         table.add_row("exit", "Exit TunaCode (legacy bare command)")
 
         app.chat_container.write(table)
+
+
+def _visible_custom_prompts(app: TextualReplApp) -> list[CustomPrompt]:
+    try:
+        return list_custom_prompts()
+    except CustomPromptError as exc:
+        app.notify(str(exc), severity="warning")
+        return []
+
+
+def _show_command_help(app: TextualReplApp, name: str) -> None:
+    from tunacode.ui.command_registry import COMMAND_DESCRIPTIONS, COMMANDS
+
+    if name in COMMAND_DESCRIPTIONS:
+        usage = COMMANDS[name].usage or f"/{name}"
+        app.chat_container.write(f"[{STYLE_PRIMARY}]{usage}[/] - {COMMAND_DESCRIPTIONS[name]}")
+        return
+
+    try:
+        prompt = find_custom_prompt(name)
+    except CustomPromptError as exc:
+        app.notify(str(exc), severity="error")
+        return
+    if prompt is None:
+        app.notify(f"Unknown command: /{name}", severity="warning")
+        return
+    _show_custom_prompt_help(app, prompt)
+
+
+def _show_custom_prompt_help(app: TextualReplApp, prompt: CustomPrompt) -> None:
+    from rich.table import Table
+
+    schema = prompt.schema
+    description = schema.description or CUSTOM_PROMPT_FALLBACK_DESCRIPTION
+    app.chat_container.write(f"[{STYLE_PRIMARY}]{schema.usage(prompt.name)}[/] - {description}")
+    app.chat_container.write(f"[{STYLE_MUTED}]{prompt.path}[/]")
+    if not schema.arguments:
+        return
+
+    table = Table(show_header=True)
+    table.add_column("Argument", style=STYLE_PRIMARY)
+    table.add_column("Required")
+    table.add_column("Description")
+    for spec in schema.arguments:
+        table.add_row(spec.name, "yes" if spec.required else "no", spec.description)
+    app.chat_container.write(table)
@@ -0,0 +1,65 @@
+"""Meta command for showing and editing the session's metadata and tags."""
+
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+from tunacode.core.session.metadata import SessionMetadataError
+
+from tunacode.ui.commands.base import Command
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+ADD_TAG_PREFIX = "+"
+REMOVE_TAG_PREFIX = "-"
+
+
+def _parse_meta_terms(
+    terms: list[str],
+) -> tuple[dict[str, str | None], list[str], list[str]]:
+    """Split terms into ``key=value`` updates (``key=`` removes) and tag additions/removals."""
+    values: dict[str, str | None] = {}
+    add_tags: list[str] = []
+    remove_tags: list[str] = []
+    for term in terms:
+        if term.startswith(ADD_TAG_PREFIX):
+            add_tags.append(term.removeprefix(ADD_TAG_PREFIX))
+        elif term.startswith(REMOVE_TAG_PREFIX):
+            remove_tags.append(term.removeprefix(REMOVE_TAG_PREFIX))
+        elif "=" in term:
+            key, value = term.split("=", 1)
+            values[key] = value or None
+        else:
+            raise SessionMetadataError(
+                f"Unknown term {term!r}; use <key>=<value>, <key>=, +<tag> or -<tag>."
+            )
+    return values, add_tags, remove_tags
+
+
+class MetaCommand(Command):
+    """Show or update the metadata and tags of the current session."""
+
+    name = "meta"
+    description = "Show or set session metadata and tags"
+    usage = "/meta [<key>=<value>|<key>=|+<tag>|-<tag>...]"
+
+    async def execute(self, app: TextualReplApp, args: str) -> None:
+        metadata = app.state_manager.session.metadata
+        terms = args.split()
+        if terms:
+            try:
+                values, add_tags, remove_tags = _parse_meta_terms(terms)
+                metadata.update(values, add_tags=add_tags, remove_tags=remove_tags)
+            except SessionMetadataError as exc:
+                app.notify(str(exc), severity="warning")
+                return
+            await app.state_manager.save_session()
+
+        if metadata.is_empty():
+            app.notify("No metadata or tags on this session")
+            return
+        pairs = [f"{key}={value}" for key, value in metadata.values.items()]
+        tags = [f"#{tag}" for tag in metadata.tags]
+        app.notify(" ".join([*pairs, *tags]))
+
@@ -1,4 +1,4 @@
-"""Resume command for listing, loading, and deleting sessions."""
+"""Resume command for listing, loading, deleting, and comparing sessions."""
 
 from __future__ import annotations
 
@@ -8,15 +8,24 @@ from typing import TYPE_CHECKING
 from tunacode.ui.commands.base import Command
 
 if TYPE_CHECKING:
+    from rich.text import Text
+
+    from tunacode.core.session.diff import EventDifference, SessionDiff, SessionEvent
+
     from tunacode.ui.app import TextualReplApp
 
+DIFF_EVENT_PREVIEW_CHARS = 120
+
 
 class ResumeCommand(Command):
     """Manage previous session restore and deletion."""
 
     name = "resume"
     description = "Resume a previous session"
-    usage = "/resume [load <id>|delete <id>]"
+    usage = (
+        "/resume [list [tag:<tag>|has:<key>|<key>=<value>...]|load <id>|delete <id>"
+        "|diff <id> <id>|migrate|continue]"
+    )
 
     async def execute(self, app: TextualReplApp, args: str) -> None:
         parts = args.split(maxsplit=1) if args else []
@@ -27,6 +36,9 @@ class ResumeCommand(Command):
             "list": self._handle_list,
             "load": self._handle_load,
             "delete": self._handle_delete,
+            "diff": self._handle_diff,
+            "migrate": self._handle_migrate,
+            "continue": self._handle_continue,
         }.get(subcommand)
 
         if handler is None:
@@ -36,12 +48,22 @@ class ResumeCommand(Command):
         await handler(app, parts)
 
     async def _handle_list(self, app: TextualReplApp, parts: list[str]) -> None:
-        """Open the session picker UI."""
+        """Open the session picker UI, limited to sessions matching any filter terms."""
+        from tunacode.core.session.metadata import SessionMetadataError, parse_session_filter
+
         from tunacode.ui.screens import SessionPickerScreen
 
-        sessions = app.state_manager.list_sessions()
+        terms = parts[1].split() if len(parts) > 1 else []
+        try:
+            session_filter = parse_session_filter(terms) if terms else None
+        except SessionMetadataError as exc:
+            app.notify(str(exc), severity="warning")
+            return
+
+        sessions = app.state_manager.list_sessions(session_filter)
         if not sessions:
-            app.notify("No saved sessions found")
+            message = "No saved sessions match the filter" if terms else "No saved sessions found"
+            app.notify(message)
             return
 
         current_session_id = app.state_manager.session.session_id
@@ -71,8 +93,18 @@ class ResumeCommand(Command):
             app.notify(f"Usage: /resume {usage_hint}", severity="warning")
             return None
 
-        session_id_prefix = parts[1].strip()
         sessions = app.state_manager.list_sessions()
+        matched = self._match_session(app, sessions, parts[1].strip())
+        if matched is None:
+            return None
+        return sessions, matched
+
+    def _match_session(
+        self,
+        app: TextualReplApp,
+        sessions: list[dict],
+        session_id_prefix: str,
+    ) -> dict | None:
         matching = [s for s in sessions if s["session_id"].startswith(session_id_prefix)]
 
         if not matching:
@@ -82,7 +114,7 @@ class ResumeCommand(Command):
             app.notify("Multiple sessions match, be more specific", severity="warning")
             return None
 
-        return sessions, matching[0]
+        return matching[0]
 
     async def _handle_load(self, app: TextualReplApp, parts: list[str]) -> None:
         """Load a session by prefix."""
@@ -111,6 +143,71 @@ class ResumeCommand(Command):
         else:
             app.notify("Failed to delete session", severity="error")
 
+    async def _handle_diff(self, app: TextualReplApp, parts: list[str]) -> None:
+        """Compare the tool calls and answers of two sessions."""
+        from pathlib import Path
+
+        from tunacode.exceptions import SessionMigrationError
+
+        from tunacode.core.session.diff import diff_session_files
+
+        prefixes = parts[1].split() if len(parts) > 1 else []
+        if len(prefixes) != 2:
+            app.notify("Usage: /resume diff <session-id> <session-id>", severity="warning")
+            return
+
+        sessions = app.state_manager.list_sessions()
+        session_a = self._match_session(app, sessions, prefixes[0])
+        session_b = self._match_session(app, sessions, prefixes[1])
+        if session_a is None or session_b is None:
+            return
+
+        try:
+            diff = await asyncio.to_thread(
+                diff_session_files,
+                Path(session_a["file_path"]),
+                Path(session_b["file_path"]),
+            )
+        except (OSError, ValueError, SessionMigrationError) as exc:
+            app.notify(f"Failed to diff sessions: {exc}", severity="error")
+            return
+
+        label_a = session_a["session_id"][:8]
+        label_b = session_b["session_id"][:8]
+        first = diff.first_divergence
+        if first is None:
+            app.notify(f"Sessions {label_a} and {label_b} match ({len(diff.events_a)} events)")
+            return
+        app.chat_container.write(_render_session_diff(diff, first, label_a, label_b))
+
+    async def _handle_migrate(self, app: TextualReplApp, parts: list[str]) -> None:
+        """Upgrade every stored session file to the current schema version."""
+        _ = parts
+        migrated, failed = await asyncio.to_thread(app.state_manager.migrate_sessions)
+        if failed:
+            app.notify(
+                f"Migrated {migrated} session(s); {failed} could not be migrated "
+                "and were left as-is",
+                severity="warning",
+            )
+            return
+        app.notify(f"Migrated {migrated} session(s)")
+
+    async def _handle_continue(self, app: TextualReplApp, parts: list[str]) -> None:
+        """Finish the interrupted last turn of the loaded session."""
+        from tunacode.types import ModelName
+
+        from tunacode.core.agents.agent_components.turn_resume import prepare_turn_resume
+
+        _ = parts
+        session = app.state_manager.session
+        model_name = session.current_model or "openai/gpt-4o"
+        prompt = await prepare_turn_resume(ModelName(model_name), app.state_manager)
+        if prompt is None:
+            app.notify("Last turn completed; nothing to resume")
+            return
+        app.submit_user_message(prompt)
+
     async def _load_session(
         self,
         app: TextualReplApp,
@@ -120,6 +217,9 @@ class ResumeCommand(Command):
         """Load a session by ID."""
         from rich.text import Text
 
+        from tunacode.core.agents.resume import detect_interrupted_turn
+        from tunacode.core.session.temp_dir import open_session_temp_dir
+
         target = next((s for s in sessions if s["session_id"] == session_id), None)
         if not target:
             app.notify("Session not found", severity="error")
@@ -128,6 +228,8 @@ class ResumeCommand(Command):
         await app.state_manager.save_session()
 
         if await app.state_manager.load_session(session_id):
+            session = app.state_manager.session
+            session.temp_dir = str(open_session_temp_dir(session.session_id))
             app.chat_container.clear()
             app._replay_session_messages()
             app._update_resource_bar()
@@ -137,7 +239,50 @@ class ResumeCommand(Command):
                 f"Loaded session {session_id[:8]} ({target['message_count']} messages)\n",
                 style="green",
             )
+            interrupted = detect_interrupted_turn(app.state_manager.session.conversation.messages)
+            if interrupted is not None:
+                loaded_msg.append(
+                    f"Last turn was interrupted: {interrupted.describe()}. "
+                    "Run /resume continue to finish it.\n",
+                    style="yellow",
+                )
             app.chat_container.write(loaded_msg)
             app.notify("Session loaded")
         else:
             app.notify("Failed to load session", severity="error")
+
+
+def _event_line(event: SessionEvent) -> str:
+    summary = " ".join(event.summary.split())
+    if len(summary) > DIFF_EVENT_PREVIEW_CHARS:
+        summary = summary[: DIFF_EVENT_PREVIEW_CHARS - 3] + "..."
+    return f"{event.kind}: {summary}"
+
+
+def _render_session_diff(
+    diff: SessionDiff,
+    first: EventDifference,
+    label_a: str,
+    label_b: str,
+) -> Text:
+    from rich.text import Text
+
+    content = Text()
+    content.append(f"Session diff {label_a} (-) vs {label_b} (+)\n", style="bold")
+    content.append(
+        f"{len(diff.differences)} difference(s); first divergence at event "
+        f"{first.index_a + 1} of {label_a}\n"
+    )
+    for difference in diff.differences:
+        content.append(
+            f"\n@@ {label_a} #{difference.index_a + 1}, {label_b} #{difference.index_b + 1}\n",
+            style="cyan",
+        )
+        for event in difference.events_a:
+            content.append(f"- {_event_line(event)}\n", style="red")
+        for event in difference.events_b:
+            content.append(f"+ {_event_line(event)}\n", style="green")
+
+    if diff.final_answer_a != diff.final_answer_b:
+        content.append("\nFinal answers differ.\n", style="bold yellow")
+    return content
@@ -0,0 +1,41 @@
+"""Tasks command for showing the session task list."""
+
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+from tunacode.core.session.tasks import TASK_DONE, TASK_IN_PROGRESS
+
+from tunacode.ui.commands.base import Command
+from tunacode.ui.styles import STYLE_MUTED, STYLE_PRIMARY, STYLE_SUCCESS, STYLE_WARNING
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+_STATUS_STYLES = {TASK_DONE: STYLE_SUCCESS, TASK_IN_PROGRESS: STYLE_WARNING}
+
+
+class TasksCommand(Command):
+    """Show the tasks the agent has recorded for this session."""
+
+    name = "tasks"
+    description = "Show the session task list"
+
+    async def execute(self, app: TextualReplApp, args: str) -> None:
+        _ = args
+        from rich.table import Table
+
+        tasks = app.state_manager.session.tasks.list()
+        if not tasks:
+            app.notify("No tasks recorded in this session")
+            return
+
+        done = sum(1 for task in tasks if task.status == TASK_DONE)
+        table = Table(title=f"Tasks [{done}/{len(tasks)} done]", show_header=True)
+        table.add_column("#", style=STYLE_MUTED)
+        table.add_column("Status")
+        table.add_column("Task", style=STYLE_PRIMARY)
+        for task in tasks:
+            style = _STATUS_STYLES.get(task.status, STYLE_MUTED)
+            table.add_row(task.task_id, f"[{style}]{task.status}[/]", task.title)
+        app.chat_container.write(table)
@@ -0,0 +1,54 @@
+"""Tools command for listing the tools offered to the model."""
+
+from __future__ import annotations
+
+import json
+from typing import TYPE_CHECKING
+
+from tunacode.core.agents.tool_catalog import ToolInfo, list_tools
+
+from tunacode.ui.commands.base import Command
+from tunacode.ui.styles import STYLE_PRIMARY
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+
+def _parameter_summary(tool: ToolInfo) -> str:
+    properties = tool.parameters.get("properties")
+    if not isinstance(properties, dict) or not properties:
+        return "-"
+    required = tool.parameters.get("required")
+    required_names = set(required) if isinstance(required, list) else set()
+    return ", ".join(f"{name}*" if name in required_names else str(name) for name in properties)
+
+
+class ToolsCommand(Command):
+    """List every available tool, or show one tool's full parameter schema."""
+
+    name = "tools"
+    description = "List available tools and their parameters"
+    usage = "/tools [tool-name]"
+
+    async def execute(self, app: TextualReplApp, args: str) -> None:
+        from rich.syntax import Syntax
+        from rich.table import Table
+
+        tools = list_tools()
+        requested_name = args.strip()
+        if requested_name:
+            tool = next((tool for tool in tools if tool.name == requested_name), None)
+            if tool is None:
+                app.notify(f"Unknown tool: {requested_name}", severity="error")
+                return
+            app.chat_container.write(f"{tool.name} ({tool.source})\n\n{tool.description}")
+            app.chat_container.write(Syntax(json.dumps(tool.parameters, indent=2), "json"))
+            return
+
+        table = Table(title="Tools", show_header=True)
+        table.add_column("Tool", style=STYLE_PRIMARY)
+        table.add_column("Source")
+        table.add_column("Parameters (* required)")
+        for tool in tools:
+            table.add_row(tool.name, tool.source, _parameter_summary(tool))
+        app.chat_container.write(table)
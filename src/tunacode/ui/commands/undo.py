@@ -0,0 +1,39 @@
+"""Undo command for reverting the file edits of the last agent turn."""
+
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+from tunacode.exceptions import FileOperationError, UndoConflictError
+
+from tunacode.core.session.undo import undo_last_turn
+
+from tunacode.ui.commands.base import Command
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+UNDO_BUSY_NOTICE = "Cannot undo while a request is running. Wait for it or cancel it first."
+
+
+class UndoCommand(Command):
+    """Restore every file the last agent turn edited."""
+
+    name = "undo"
+    description = "Undo file edits from the last agent turn"
+
+    async def execute(self, app: TextualReplApp, _args: str) -> None:
+        if app.request_in_flight:
+            app.notify(UNDO_BUSY_NOTICE, severity="warning")
+            return
+
+        try:
+            restored = undo_last_turn()
+        except (UndoConflictError, FileOperationError) as exc:
+            app.notify(str(exc), severity="error")
+            return
+
+        if not restored:
+            app.notify("Nothing to undo")
+            return
+        app.notify(f"Restored {len(restored)} file(s): " + ", ".join(restored))
@@ -0,0 +1,63 @@
+"""Watch command for re-running the agent when watched files change."""
+
+from __future__ import annotations
+
+import asyncio
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tunacode.core.watch.watcher import DEFAULT_WATCH_PROMPT, FileWatcher
+
+from tunacode.ui.commands.base import Command
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+PROMPT_SEPARATOR = " -- "
+STOP_ARG = "stop"
+USAGE = "Usage: /watch <glob> [<glob> ...] [-- <prompt with {files}>] | /watch stop"
+
+
+class WatchCommand(Command):
+    """Start, stop, or show watch mode; limits come from ``settings.watch``."""
+
+    name = "watch"
+    description = "Re-run a prompt when watched files change"
+
+    async def execute(self, app: TextualReplApp, args: str) -> None:
+        watch_mode = app.watch_mode
+        globs_text, _, prompt = f" {args.strip()} ".partition(PROMPT_SEPARATOR)
+        globs = globs_text.split()
+
+        if not globs:
+            watcher = watch_mode.watcher
+            if watcher is None:
+                app.notify(USAGE)
+                return
+            app.notify(
+                f"Watching {' '.join(watcher.globs)} "
+                f"({watcher.runs}/{watcher.max_runs} runs used)"
+            )
+            return
+
+        if globs == [STOP_ARG]:
+            if watch_mode.watcher is None:
+                app.notify("Watch mode is not running")
+                return
+            watch_mode.stop()
+            app.notify("Watch mode stopped")
+            return
+
+        settings = app.state_manager.session.user_config["settings"]["watch"]
+        # The initial scan walks the tree; keep it off the event loop.
+        watcher = await asyncio.to_thread(
+            FileWatcher,
+            root=Path.cwd(),
+            globs=globs,
+            prompt_template=prompt.strip() or DEFAULT_WATCH_PROMPT,
+            debounce_seconds=settings["debounce_seconds"],
+            cooldown_seconds=settings["cooldown_seconds"],
+            max_runs=settings["max_runs"],
+        )
+        watch_mode.start(watcher)
+        app.notify(f"Watching {' '.join(globs)} (max {watcher.max_runs} runs)")
@@ -0,0 +1,37 @@
+"""Wrap command for switching how code blocks handle long lines."""
+
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+from tunacode.constants import CodeWrapMode
+
+from tunacode.ui.commands.base import Command
+from tunacode.ui.renderers.code_wrap import get_code_wrap_mode, set_code_wrap_mode
+
+if TYPE_CHECKING:
+    from tunacode.ui.app import TextualReplApp
+
+WRAP_MODE_ORDER = list(CodeWrapMode)
+
+
+class WrapCommand(Command):
+    """Set or cycle the code block wrap mode for this session."""
+
+    name = "wrap"
+    description = "Set code block wrapping: wrap, scroll, or truncate"
+
+    async def execute(self, app: TextualReplApp, args: str) -> None:
+        requested = args.strip().lower()
+        if not requested:
+            current_index = WRAP_MODE_ORDER.index(get_code_wrap_mode())
+            mode = WRAP_MODE_ORDER[(current_index + 1) % len(WRAP_MODE_ORDER)]
+        elif requested in {member.value for member in CodeWrapMode}:
+            mode = CodeWrapMode(requested)
+        else:
+            choices = ", ".join(member.value for member in CodeWrapMode)
+            app.notify(f"Unknown wrap mode: {requested} (choose {choices})", severity="error")
+            return
+
+        set_code_wrap_mode(mode)
+        app.notify(f"Code block wrapping: {mode.value} (applies to new output)")
@@ -8,6 +8,8 @@ from rich.text import Text
 from textual.dom import DOMNode
 from textual.widgets import Static
 
+from tunacode.types.callbacks import STEP_COMPLETED, STEP_FAILED, ToolProgress
+
 from tunacode.ui.slopgotchi import SLOPGOTCHI_ART_STATES, SLOPGOTCHI_NAME
 from tunacode.ui.styles import (
     STYLE_ACCENT,
@@ -19,6 +21,13 @@ from tunacode.ui.styles import (
 )
 
 CONTEXT_GAUGE_WIDTH: int = 24
+PROGRESS_MARKER_RUNNING = "◌ "
+PROGRESS_MARKER_COMPLETED = "✓ "
+PROGRESS_MARKER_FAILED = "✗ "
+GAUGE_FILLED_UNICODE = "█"
+GAUGE_EMPTY_UNICODE = "░"
+GAUGE_FILLED_ASCII = "#"
+GAUGE_EMPTY_ASCII = "-"
 
 
 class InspectorField(Static):
@@ -35,6 +44,7 @@ class ContextPanelWidgets:
     field_context: InspectorField
     field_cost: InspectorField
     field_files: InspectorField
+    field_progress: InspectorField
     field_skills: InspectorField
 
 
@@ -74,6 +84,13 @@ def build_context_panel_widgets() -> ContextPanelWidgets:
     )
     field_files.border_title = "Files"
 
+    field_progress = InspectorField(
+        "",
+        id="field-progress",
+        classes="inspector-field",
+    )
+    field_progress.border_title = "Progress"
+
     field_skills = InspectorField(
         "",
         id="field-skills",
@@ -87,6 +104,7 @@ def build_context_panel_widgets() -> ContextPanelWidgets:
         field_context,
         field_cost,
         field_files,
+        field_progress,
         field_skills,
     )
 
@@ -97,6 +115,7 @@ def build_context_panel_widgets() -> ContextPanelWidgets:
         field_context=field_context,
         field_cost=field_cost,
         field_files=field_files,
+        field_progress=field_progress,
         field_skills=field_skills,
     )
 
@@ -123,14 +142,17 @@ def build_context_gauge(
     max_tokens: int,
     remaining_pct: float,
     token_style: str,
+    unicode: bool = True,
 ) -> Text:
     used_pct = 100.0 - remaining_pct
     filled = round(CONTEXT_GAUGE_WIDTH * used_pct / 100)
     empty = CONTEXT_GAUGE_WIDTH - filled
+    filled_glyph = GAUGE_FILLED_UNICODE if unicode else GAUGE_FILLED_ASCII
+    empty_glyph = GAUGE_EMPTY_UNICODE if unicode else GAUGE_EMPTY_ASCII
 
     gauge = Text()
-    gauge.append("█" * filled, style=token_style)
-    gauge.append("░" * empty, style=STYLE_MUTED)
+    gauge.append(filled_glyph * filled, style=token_style)
+    gauge.append(empty_glyph * empty, style=STYLE_MUTED)
     gauge.append(f" {remaining_pct:.0f}%\n", style=f"bold {token_style}")
     gauge.append(f"{tokens:,}", style=token_style)
     gauge.append(f" / {max_tokens:,}", style=STYLE_MUTED)
@@ -155,6 +177,30 @@ def build_files_field(edited_files: set[str]) -> tuple[str, Text]:
     return border_title, content
 
 
+def build_progress_field(steps: list[ToolProgress]) -> tuple[str, Text]:
+    if not steps:
+        return "Progress", Text("(idle)", style=f"dim {STYLE_MUTED}")
+
+    finished = sum(1 for step in steps if step.status in (STEP_COMPLETED, STEP_FAILED))
+    border_title = f"Progress [{finished}/{len(steps)}]"
+
+    content = Text()
+    for index, step in enumerate(steps):
+        if step.status == STEP_COMPLETED:
+            content.append(PROGRESS_MARKER_COMPLETED, style=STYLE_SUCCESS)
+            content.append(step.label, style=f"dim {STYLE_PRIMARY}")
+        elif step.status == STEP_FAILED:
+            content.append(PROGRESS_MARKER_FAILED, style=STYLE_ERROR)
+            content.append(step.label, style=STYLE_ERROR)
+        else:
+            content.append(PROGRESS_MARKER_RUNNING, style=STYLE_WARNING)
+            content.append(step.label, style=f"bold {STYLE_PRIMARY}")
+        if index < len(steps) - 1:
+            content.append("\n")
+
+    return border_title, content
+
+
 def build_skills_field(skill_entries: list[tuple[str, str]]) -> tuple[str, Text]:
     skill_count = len(skill_entries)
     border_title = f"Loaded Skills [{skill_count}]"
@@ -0,0 +1,150 @@
+"""Composer draft autosave and recovery.
+
+The editor's unsent text is written, debounced by
+``settings.draft_autosave.debounce_ms``, to
+``~/.tunacode/drafts/<project_id>.json`` so a crash or an accidental exit does
+not lose it. The next launch in the same project offers to recover or discard
+it. Drafts may hold sensitive text, so the file is owner-only, it is removed
+as soon as the message is submitted, and drafts older than
+``settings.draft_autosave.max_age_hours`` are deleted instead of offered.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import time
+from collections.abc import Callable
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Protocol
+
+from tunacode.constants import DRAFTS_SUBDIR
+
+DRAFT_FILE_SUFFIX = ".json"
+DRAFT_FILE_MODE = 0o600
+SECONDS_PER_HOUR = 3600
+
+
+@dataclass(frozen=True, slots=True)
+class Draft:
+    text: str
+    saved_at: float
+
+
+class DraftStore:
+    """One project's draft file, with age-based expiry."""
+
+    def __init__(self, path: Path, *, max_age_seconds: float) -> None:
+        self._path = path
+        self._max_age_seconds = max_age_seconds
+
+    @classmethod
+    def for_project(
+        cls, tunacode_home: Path, project_id: str, *, max_age_hours: int
+    ) -> DraftStore:
+        path = tunacode_home / DRAFTS_SUBDIR / f"{project_id}{DRAFT_FILE_SUFFIX}"
+        return cls(path, max_age_seconds=max_age_hours * SECONDS_PER_HOUR)
+
+    @property
+    def path(self) -> Path:
+        return self._path
+
+    def save(self, text: str, *, now: float | None = None) -> None:
+        if not text.strip():
+            self.clear()
+            return
+        saved_at = time.time() if now is None else now
+        payload = json.dumps({"text": text, "saved_at": saved_at})
+        self._path.parent.mkdir(parents=True, exist_ok=True)
+        temp_path = self._path.with_suffix(".tmp")
+        fd = os.open(temp_path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, DRAFT_FILE_MODE)
+        with os.fdopen(fd, "w", encoding="utf-8") as handle:
+            handle.write(payload)
+        os.replace(temp_path, self._path)
+
+    def load(self, *, now: float | None = None) -> Draft | None:
+        """Return the saved draft, deleting it when expired or unreadable."""
+        try:
+            raw = json.loads(self._path.read_text(encoding="utf-8"))
+        except FileNotFoundError:
+            return None
+        except (OSError, ValueError):
+            self.clear()
+            return None
+        text = raw.get("text") if isinstance(raw, dict) else None
+        saved_at = raw.get("saved_at") if isinstance(raw, dict) else None
+        if not isinstance(text, str) or not isinstance(saved_at, int | float):
+            self.clear()
+            return None
+        current = time.time() if now is None else now
+        if current - saved_at > self._max_age_seconds:
+            self.clear()
+            return None
+        return Draft(text=text, saved_at=float(saved_at))
+
+    def clear(self) -> None:
+        self._path.unlink(missing_ok=True)
+
+
+def prune_expired_drafts(
+    drafts_dir: Path, *, max_age_hours: int, now: float | None = None
+) -> int:
+    """Delete every project's draft older than the retention; returns how many."""
+    if not drafts_dir.is_dir():
+        return 0
+    cutoff = (time.time() if now is None else now) - max_age_hours * SECONDS_PER_HOUR
+    removed = 0
+    for draft_path in drafts_dir.glob(f"*{DRAFT_FILE_SUFFIX}"):
+        try:
+            if draft_path.stat().st_mtime < cutoff:
+                draft_path.unlink()
+                removed += 1
+        except OSError:
+            continue
+    return removed
+
+
+class _Timer(Protocol):
+    def stop(self) -> None: ...
+
+
+class DraftAutosave:
+    """Debounce editor changes into ``DraftStore.save``."""
+
+    def __init__(
+        self,
+        store: DraftStore,
+        *,
+        debounce_seconds: float,
+        set_timer: Callable[[float, Callable[[], None]], _Timer],
+    ) -> None:
+        self._store = store
+        self._debounce_seconds = debounce_seconds
+        self._set_timer = set_timer
+        self._timer: _Timer | None = None
+        self._pending: str | None = None
+
+    def schedule(self, text: str) -> None:
+        self._pending = text
+        if self._timer is not None:
+            self._timer.stop()
+        self._timer = self._set_timer(self._debounce_seconds, self.flush)
+
+    def flush(self) -> None:
+        """Write a pending change now; called on the debounce timer and at exit."""
+        self._stop_timer()
+        pending, self._pending = self._pending, None
+        if pending is not None:
+            self._store.save(pending)
+
+    def discard(self) -> None:
+        """Forget pending changes and delete the saved draft, e.g. after submission."""
+        self._stop_timer()
+        self._pending = None
+        self._store.clear()
+
+    def _stop_timer(self) -> None:
+        if self._timer is not None:
+            self._timer.stop()
+            self._timer = None
@@ -29,7 +29,9 @@ class AppLifecycle:
     def mount(self) -> None:
         """Initialize app on mount."""
         self._init_theme()
+        self._init_code_wrap_mode()
         self._init_session_metadata()
+        self._init_session_temp_dir()
 
         if self._app._show_setup:
             self._push_setup_screen()
@@ -39,8 +41,16 @@ class AppLifecycle:
 
     async def unmount(self) -> None:
         """Save session and cleanup app resources before exit."""
+        from tunacode.core.agents.agent_components.http_pool import close_provider_clients
+        from tunacode.core.session.temp_dir import close_session_temp_dir
+
         self._stop_slopgotchi_timer()
+        if self._app._draft_autosave is not None:
+            self._app._draft_autosave.flush()
         await self._state_manager.save_session()
+        await close_provider_clients()
+        if self._state_manager.session.temp_dir:
+            close_session_temp_dir(Path(self._state_manager.session.temp_dir))
 
     def _init_theme(self) -> None:
         """Load and apply a supported theme from user settings."""
@@ -53,6 +63,26 @@ class AppLifecycle:
             return
         self._app.theme = saved_theme
 
+    def _init_code_wrap_mode(self) -> None:
+        """Apply the configured code block wrap mode."""
+        from tunacode.constants import CodeWrapMode
+
+        from tunacode.ui.renderers.code_wrap import set_code_wrap_mode
+
+        user_config = self._state_manager.session.user_config
+        set_code_wrap_mode(CodeWrapMode(user_config["settings"]["code_wrap_mode"]))
+
+    def _init_session_temp_dir(self) -> None:
+        """Create this session's scratch directory after sweeping ones left by dead processes."""
+        from tunacode.core.session.temp_dir import (
+            open_session_temp_dir,
+            sweep_stale_session_temp_dirs,
+        )
+
+        sweep_stale_session_temp_dirs()
+        session = self._state_manager.session
+        session.temp_dir = str(open_session_temp_dir(session.session_id))
+
     def _init_session_metadata(self) -> None:
         """Initialize persisted session metadata for this app launch."""
         from tunacode.configuration.paths import get_project_id
@@ -87,6 +117,7 @@ class AppLifecycle:
 
         app = self._app
         app.set_focus(app.editor)
+        self._init_draft_autosave()
         app.run_worker(app._request_worker, exclusive=False)
         if not self._is_tmux_test_mode():
             self._start_slopgotchi_timer()
@@ -94,9 +125,46 @@ class AppLifecycle:
 
         from tunacode.ui.welcome import show_welcome
 
-        show_welcome(app.chat_container)
+        settings = self._state_manager.session.user_config["settings"]
+        show_welcome(app.chat_container, safe_mode=settings["safe_mode"])
         app.call_after_refresh(self._emit_ready_file_if_configured)
 
+    def _init_draft_autosave(self) -> None:
+        """Start saving the composer draft and offer to recover the last unsent one."""
+        from tunacode.configuration.paths import get_tunacode_home
+        from tunacode.constants import DRAFTS_SUBDIR
+
+        from tunacode.ui.drafts import DraftAutosave, DraftStore, prune_expired_drafts
+        from tunacode.ui.screens import DraftRecoveryScreen
+
+        session = self._state_manager.session
+        settings = session.user_config["settings"]["draft_autosave"]
+        if not settings["enabled"]:
+            return
+
+        home = get_tunacode_home()
+        max_age_hours = settings["max_age_hours"]
+        prune_expired_drafts(home / DRAFTS_SUBDIR, max_age_hours=max_age_hours)
+        store = DraftStore.for_project(home, session.project_id, max_age_hours=max_age_hours)
+        app = self._app
+        app._draft_autosave = DraftAutosave(
+            store,
+            debounce_seconds=settings["debounce_ms"] / 1000,
+            set_timer=app.set_timer,
+        )
+
+        draft = store.load()
+        if draft is None:
+            return
+
+        def on_recovery_choice(recover: bool | None) -> None:
+            if recover:
+                app.editor.restore_draft(draft.text)
+                return
+            store.clear()
+
+        app.push_screen(DraftRecoveryScreen(draft), on_recovery_choice)
+
     def _is_tmux_test_mode(self) -> bool:
         """Return True when startup is running under tmux E2E test orchestration."""
         return bool(os.environ.get(TEST_READY_FILE_ENV_VAR))
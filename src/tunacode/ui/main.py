@@ -16,6 +16,8 @@ from tunacode.ui.repl_support import run_textual_repl
 
 DEFAULT_TIMEOUT_SECONDS = 600
 BASE_URL_HELP_TEXT = "API base URL (e.g., https://openrouter.ai/api/v1)"
+SAFE_MODE_HELP_TEXT = "Read-only session: only tools and commands classified read-only may run"
+STAGED_HELP_TEXT = "Review only the changes staged for commit (git diff --cached)"
 
 app_settings = ApplicationSettings()
 app = typer.Typer(help="TunaCode - OS AI-powered development assistant")
@@ -65,7 +67,64 @@ def _apply_base_url_override(state_manager: StateManager, base_url: str | None)
     state_manager.session.user_config["env"][ENV_OPENAI_BASE_URL] = base_url
 
 
-async def _run_textual_app(*, model: str | None, baseurl: str | None, show_setup: bool) -> None:
+def _resolve_cli_model(state_manager: StateManager, model: str | None) -> str:
+    """Return the --model value (or current model) with a provider detected from the base URL."""
+    from tunacode.configuration.models import qualify_model_string
+
+    session = state_manager.session
+    base_url = session.user_config["env"].get(ENV_OPENAI_BASE_URL)
+    return qualify_model_string(model or session.current_model, base_url)
+
+
+async def _check_base_url(state_manager: StateManager, base_url: str | None) -> bool:
+    """Probe a --baseurl endpoint before the UI starts; False when it is unreachable."""
+    if not base_url:
+        return True
+
+    from tunacode.core.agents.agent_components.endpoint_probe import probe_session_base_url
+
+    try:
+        probe = await probe_session_base_url(state_manager, base_url)
+    except ConfigurationError as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        return False
+    if probe is None or not probe.lists_models:
+        return True
+
+    current_model = state_manager.session.current_model
+    model_id = current_model.split(":", 1)[-1]
+    if model_id not in probe.model_ids:
+        served = ", ".join(probe.model_ids)
+        print(f"Warning: {base_url} does not list model '{model_id}'. Served: {served}")
+    return True
+
+
+def _warn_missing_shell(state_manager: StateManager) -> None:
+    """Warn when the shell that runs bash and ! commands is not installed."""
+    from tunacode.utils.system.shell_program import resolve_shell
+
+    shell = state_manager.session.user_config["settings"]["shell"]
+    invocation = resolve_shell(shell["program"], shell["args"])
+    if not invocation.is_available():
+        print(
+            f"Warning: shell '{invocation.program}' (settings.shell.program) was not found; "
+            "shell commands will fail until it is installed or the setting is changed."
+        )
+
+
+def _apply_safe_mode_override(state_manager: StateManager, safe_mode: bool) -> None:
+    """Apply --safe-mode CLI flag; it can only turn safe mode on."""
+    if safe_mode:
+        state_manager.session.user_config["settings"]["safe_mode"] = True
+
+
+async def _run_textual_app(
+    *,
+    model: str | None,
+    baseurl: str | None,
+    show_setup: bool,
+    safe_mode: bool = False,
+) -> None:
     try:
         try:
             sm = _get_state_manager()
@@ -74,12 +133,16 @@ async def _run_textual_app(*, model: str | None, baseurl: str | None, show_setup
             return
 
         _apply_base_url_override(sm, baseurl)
+        _apply_safe_mode_override(sm, safe_mode)
 
         update_task = asyncio.create_task(asyncio.to_thread(check_for_updates), name="update_check")
         update_task.add_done_callback(_handle_background_task_error)
 
-        if model:
-            sm.session.current_model = model
+        sm.session.current_model = _resolve_cli_model(sm, model)
+        if not await _check_base_url(sm, baseurl):
+            update_task.cancel()
+            return
+        _warn_missing_shell(sm)
 
         try:
             await run_textual_repl(sm, show_setup=show_setup)
@@ -108,8 +171,16 @@ async def _run_textual_app(*, model: str | None, baseurl: str | None, show_setup
         _reset_state_manager()
 
 
-def _run_textual_cli(*, model: str | None, baseurl: str | None, show_setup: bool) -> None:
-    asyncio.run(_run_textual_app(model=model, baseurl=baseurl, show_setup=show_setup))
+def _run_textual_cli(
+    *,
+    model: str | None,
+    baseurl: str | None,
+    show_setup: bool,
+    safe_mode: bool = False,
+) -> None:
+    asyncio.run(
+        _run_textual_app(model=model, baseurl=baseurl, show_setup=show_setup, safe_mode=safe_mode)
+    )
 
 
 @app.callback(invoke_without_command=True)
@@ -125,6 +196,7 @@ def _default_command(
     _context: int = typer.Option(  # noqa: ARG001 - reserved for future use
         None, "--context", help="Maximum context window size for custom models"
     ),
+    safe_mode: bool = typer.Option(False, "--safe-mode", help=SAFE_MODE_HELP_TEXT),
 ) -> None:
     if version:
         _print_version()
@@ -134,7 +206,12 @@ def _default_command(
         if setup:
             raise typer.BadParameter("Use `tunacode --setup` without a subcommand.")
         return
-    _run_textual_cli(model=model, baseurl=baseurl, show_setup=setup or not _config_exists())
+    _run_textual_cli(
+        model=model,
+        baseurl=baseurl,
+        show_setup=setup or not _config_exists(),
+        safe_mode=safe_mode,
+    )
 
 
 @app.command(hidden=True)
@@ -149,13 +226,51 @@ def main(
         None, "--context", help="Maximum context window size for custom models"
     ),
     setup: bool = typer.Option(False, "--setup", help="Run setup wizard"),
+    safe_mode: bool = typer.Option(False, "--safe-mode", help=SAFE_MODE_HELP_TEXT),
 ) -> None:
     """Deprecated alias for `tunacode`."""
     if version:
         _print_version()
         raise typer.Exit(code=0)
 
-    _run_textual_cli(model=model, baseurl=baseurl, show_setup=setup or not _config_exists())
+    _run_textual_cli(
+        model=model,
+        baseurl=baseurl,
+        show_setup=setup or not _config_exists(),
+        safe_mode=safe_mode,
+    )
+
+
+async def _run_review(*, model: str | None, baseurl: str | None) -> str:
+    from tunacode.types import ModelName
+
+    from tunacode.core.review.staged import run_staged_review
+
+    sm = _get_state_manager()
+    try:
+        _apply_base_url_override(sm, baseurl)
+        return await run_staged_review(sm, ModelName(_resolve_cli_model(sm, model)))
+    finally:
+        _reset_state_manager()
+
+
+@app.command()
+def review(
+    staged: bool = typer.Option(False, "--staged", help=STAGED_HELP_TEXT),
+    baseurl: str | None = typer.Option(None, "--baseurl", help=BASE_URL_HELP_TEXT),
+    model: str | None = typer.Option(
+        None, "--model", help="Model to use (e.g., openai/gpt-4o, openrouter:openai/gpt-4.1)"
+    ),
+) -> None:
+    """Review git changes with the agent and print the findings."""
+    if not staged:
+        raise typer.BadParameter("Only staged reviews are supported: `tunacode review --staged`.")
+    try:
+        output = asyncio.run(_run_review(model=model, baseurl=baseurl))
+    except (ConfigurationError, RuntimeError) as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        raise typer.Exit(code=1) from exc
+    print(output)
 
 
 if __name__ == "__main__":
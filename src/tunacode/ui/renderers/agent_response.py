@@ -14,11 +14,11 @@ from __future__ import annotations
 from datetime import datetime
 
 from rich.console import Group, RenderableType
-from rich.markdown import Markdown
 from rich.text import Text
 
 from tunacode.constants import BOX_HORIZONTAL, SEPARATOR_WIDTH, UI_COLORS
 
+from tunacode.ui.renderers.code_wrap import WrappedMarkdown
 from tunacode.ui.widgets.chat import PanelMeta
 
 # Threshold for k-suffix (tokens) and ms->s conversion (duration)
@@ -77,7 +77,7 @@ def render_agent_streaming(
     muted_color = UI_COLORS["muted"]
 
     # Viewport (markdown content)
-    viewport = Markdown(content) if content else Text("...", style="dim italic")
+    viewport = WrappedMarkdown(content) if content else Text("...", style="dim italic")
 
     # Status bar - streaming indicator
     status_parts: list[str] = ["streaming"]
@@ -130,7 +130,7 @@ def render_agent_response(
     timestamp = datetime.now().strftime("%I:%M %p").lstrip("0")
 
     # Viewport (markdown content)
-    viewport = Markdown(content)
+    viewport = WrappedMarkdown(content)
 
     # Status bar
     status_parts: list[str] = []
@@ -0,0 +1,62 @@
+"""Markdown whose code blocks follow the active code wrap mode.
+
+The mode comes from ``settings.code_wrap_mode`` at startup and can be changed
+at runtime with ``/wrap``; every markdown render after the change uses it.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import ClassVar
+
+from rich.console import Console, ConsoleOptions, RenderResult
+from rich.markdown import CodeBlock, Markdown, MarkdownElement
+from rich.padding import Padding
+from rich.syntax import Syntax
+
+from tunacode.constants import CodeWrapMode
+
+from tunacode.ui.renderers.tools.syntax_utils import fit_code_lines
+
+CODE_BLOCK_PADDING = 1
+
+
+@dataclass(slots=True)
+class _CodeWrapState:
+    mode: CodeWrapMode = CodeWrapMode.WRAP
+
+
+# Module-level singleton: the UI runs one app per process.
+_state = _CodeWrapState()
+
+
+def get_code_wrap_mode() -> CodeWrapMode:
+    return _state.mode
+
+
+def set_code_wrap_mode(mode: CodeWrapMode) -> None:
+    _state.mode = mode
+
+
+class _WrapAwareCodeBlock(CodeBlock):
+    def __rich_console__(self, console: Console, options: ConsoleOptions) -> RenderResult:
+        code = str(self.text).rstrip()
+        highlighted = Syntax(code, self.lexer_name, theme=self.theme).highlight(code)
+        highlighted.rstrip()
+        width = max(1, options.max_width - 2 * CODE_BLOCK_PADDING)
+        background = Syntax.get_theme(self.theme).get_background_style()
+        yield Padding(
+            fit_code_lines(highlighted, width, _state.mode),
+            CODE_BLOCK_PADDING,
+            style=background,
+        )
+
+
+class WrappedMarkdown(Markdown):
+    """Rich ``Markdown`` with code blocks fitted by the active wrap mode."""
+
+    elements: ClassVar[dict[str, type[MarkdownElement]]] = {
+        **Markdown.elements,
+        "fence": _WrapAwareCodeBlock,
+        "code_block": _WrapAwareCodeBlock,
+    }
@@ -20,6 +20,7 @@ ERROR_SEVERITY_MAP: dict[str, str] = {
     "FileOperationError": "error",
     "AgentError": "error",
     "GlobalRequestTimeoutError": "error",
+    "TurnDeadlineExceededError": "error",
     "ContextOverflowError": "error",
     "ConfigurationError": "warning",
     "ValidationError": "warning",
@@ -56,6 +57,10 @@ DEFAULT_RECOVERY_COMMANDS: dict[str, list[str]] = {
         "Check network connectivity",
         "Increase timeout in tunacode.json",
     ],
+    "TurnDeadlineExceededError": [
+        "Increase settings.turn_deadline in tunacode.json",
+        "Split the task into smaller requests",
+    ],
 }
 
 
@@ -0,0 +1,109 @@
+"""Incremental markdown rendering for streaming assistant text.
+
+Streamed text is split at the last blank line outside a code fence: blocks
+before it are settled and their ``Markdown`` renderable is cached, so only the
+growing tail is re-parsed on each update. The tail is patched before parsing
+so half-written markdown still renders sensibly:
+
+- an unclosed code fence is closed, so it renders as code in progress;
+- an unclosed inline code span on the last line is closed;
+- a half-written table row is held back until its line ends.
+"""
+
+from __future__ import annotations
+
+import re
+
+from rich.console import Group, RenderableType
+from rich.markdown import Markdown
+
+from tunacode.ui.renderers.code_wrap import WrappedMarkdown
+
+FENCE_PATTERN = re.compile(r"^ {0,3}(`{3,}|~{3,})")
+TABLE_ROW_PREFIX = "|"
+INLINE_CODE_MARK = "`"
+
+
+def _next_fence_state(line: str, open_marker: str | None) -> str | None:
+    """Return the open fence marker after ``line``, or None outside a fence."""
+    match = FENCE_PATTERN.match(line)
+    if match is None:
+        return open_marker
+    marker = match.group(1)
+    if open_marker is None:
+        return marker
+    is_closing = not line[match.end() :].strip()
+    if is_closing and marker[0] == open_marker[0] and len(marker) >= len(open_marker):
+        return None
+    return open_marker
+
+
+def _open_fence(lines: list[str]) -> str | None:
+    """Return the marker of the fence left open at the end of ``lines``."""
+    open_marker: str | None = None
+    for line in lines:
+        open_marker = _next_fence_state(line, open_marker)
+    return open_marker
+
+
+def close_partial_markdown(text: str) -> str:
+    """Patch streamed markdown so an incomplete tail renders without breaking layout."""
+    lines = text.split("\n")
+    fence = _open_fence(lines)
+    if fence is not None:
+        return f"{text}\n{fence}"
+
+    last_line = lines[-1]
+    if last_line.lstrip().startswith(TABLE_ROW_PREFIX) and not last_line.rstrip().endswith(
+        TABLE_ROW_PREFIX
+    ):
+        return "\n".join(lines[:-1])
+    if last_line.count(INLINE_CODE_MARK) % 2 == 1:
+        return f"{text}{INLINE_CODE_MARK}"
+    return text
+
+
+def _settled_boundary(text: str) -> int:
+    """Index just past the last blank line that is not inside a code fence."""
+    boundary = 0
+    offset = 0
+    open_marker: str | None = None
+    for line in text.split("\n")[:-1]:
+        offset += len(line) + 1
+        open_marker = _next_fence_state(line, open_marker)
+        if open_marker is None and not line.strip():
+            boundary = offset
+    return boundary
+
+
+class StreamingMarkdown:
+    """Accumulate streamed text and produce a stable markdown renderable."""
+
+    def __init__(self) -> None:
+        self._text = ""
+        self._settled_text = ""
+        self._settled: Markdown | None = None
+
+    @property
+    def text(self) -> str:
+        return self._text
+
+    def append(self, chunk: str) -> None:
+        self._text += chunk
+
+    def reset(self) -> None:
+        self._text = ""
+        self._settled_text = ""
+        self._settled = None
+
+    def renderable(self) -> RenderableType:
+        boundary = _settled_boundary(self._text)
+        settled_text = self._text[:boundary]
+        if settled_text != self._settled_text:
+            self._settled_text = settled_text
+            self._settled = WrappedMarkdown(settled_text) if settled_text.strip() else None
+
+        tail = WrappedMarkdown(close_partial_markdown(self._text[boundary:]))
+        if self._settled is None:
+            return tail
+        return Group(self._settled, tail)
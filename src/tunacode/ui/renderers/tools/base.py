@@ -30,6 +30,7 @@ from tunacode.constants import (
     UI_COLORS,
 )
 
+from tunacode.ui.terminal import truncate_cells
 from tunacode.ui.widgets.chat import PanelMeta
 
 
@@ -41,11 +42,10 @@ def truncate_line(line: str, max_width: int) -> str:
         max_width: Maximum allowed width
 
     Returns:
-        Original line if within limit, otherwise truncated with '...'
+        Original line if within limit, otherwise truncated with '...'.
+        Width is measured in terminal cells, so wide characters count twice.
     """
-    if len(line) > max_width:
-        return line[: max_width - 3] + "..."
-    return line
+    return truncate_cells(line, max_width)
 
 
 def truncate_content(
@@ -53,6 +53,11 @@ class ReadFileRenderer(BaseToolRenderer[ReadFileData]):
             total = int(match.group(1)) if match else 0
             return line, total, True
 
+        if line.startswith("(File windowed"):
+            match = re.search(r"of (\d+) lines", line)
+            total = int(match.group(1)) if match else 0
+            return line, total, True
+
         if line.startswith("(End of file"):
             match = re.search(r"total (\d+) lines", line)
             total = int(match.group(1)) if match else 0
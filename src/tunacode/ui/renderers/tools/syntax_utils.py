@@ -9,15 +9,22 @@ from __future__ import annotations
 from pathlib import Path
 from typing import TYPE_CHECKING
 
+from rich.cells import cell_len
 from rich.syntax import Syntax
 from rich.text import Text
 
+from tunacode.constants import CodeWrapMode
+
 if TYPE_CHECKING:
     from rich.console import RenderableType
 
 # NeXTSTEP-consistent theme used across all renderers
 SYNTAX_THEME = "monokai"
 
+CONTINUATION_INDICATOR = "↪ "
+CONTINUATION_STYLE = "dim"
+TRUNCATION_OVERFLOW = "ellipsis"
+
 # Map file extensions to pygments lexer names
 EXTENSION_LEXERS: dict[str, str] = {
     # Python
@@ -198,6 +205,55 @@ def syntax_or_text(
     return Text(content)
 
 
+def _split_by_cells(line: Text, first_width: int, rest_width: int) -> list[Text]:
+    """Split ``line`` into chunks that fit the given cell widths, keeping styles."""
+    offsets: list[int] = []
+    limit = first_width
+    used = 0
+    for index, char in enumerate(line.plain):
+        char_width = cell_len(char)
+        if used and used + char_width > limit:
+            offsets.append(index)
+            limit = rest_width
+            used = 0
+        used += char_width
+    return list(line.divide(offsets))
+
+
+def fit_code_lines(code: Text, width: int, mode: CodeWrapMode) -> Text:
+    """Fit highlighted ``code`` into ``width`` cells according to ``mode``.
+
+    ``wrap`` soft-wraps long lines and marks continuations with an indicator,
+    ``truncate`` cuts them with an ellipsis, and ``scroll`` leaves them whole.
+    Widths are measured in terminal cells, so wide and emoji characters count
+    double. Highlight styles carry over to every wrapped chunk.
+    """
+    if mode is CodeWrapMode.SCROLL:
+        code.no_wrap = True
+        code.overflow = "ignore"
+        return code
+
+    indicator_width = cell_len(CONTINUATION_INDICATOR)
+    rest_width = max(1, width - indicator_width)
+    fitted: list[Text] = []
+    for line in code.split("\n", allow_blank=True):
+        if line.cell_len <= width:
+            fitted.append(line)
+            continue
+        if mode is CodeWrapMode.TRUNCATE:
+            line.truncate(width, overflow=TRUNCATION_OVERFLOW)
+            fitted.append(line)
+            continue
+        first, *continuations = _split_by_cells(line, width, rest_width)
+        fitted.append(first)
+        for chunk in continuations:
+            fitted.append(Text(CONTINUATION_INDICATOR, style=CONTINUATION_STYLE) + chunk)
+
+    result = Text("\n").join(fitted)
+    result.no_wrap = True
+    return result
+
+
 # Shebang keyword -> lexer mapping (checked with `in` against the shebang line)
 _SHEBANG_LEXERS: tuple[tuple[str, str], ...] = (
     ("python", "python"),
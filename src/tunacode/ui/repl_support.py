@@ -14,7 +14,7 @@ from rich.console import Console
 from rich.text import Text
 from tinyagent.agent_types import TextContent
 
-from tunacode.constants import MAX_CALLBACK_CONTENT
+from tunacode.constants import FULL_OUTPUT_DETAIL_KEY, MAX_CALLBACK_CONTENT
 from tunacode.types import (
     StreamResultProtocol,
     ToolArgs,
@@ -157,6 +157,10 @@ def _extract_tool_result_text(result: ToolResult | None) -> str | None:
     if result is None:
         return None
 
+    full_output = (result.details or {}).get(FULL_OUTPUT_DETAIL_KEY)
+    if isinstance(full_output, str):
+        return full_output
+
     parts: list[str] = []
     for item in result.content:
         if not isinstance(item, TextContent):
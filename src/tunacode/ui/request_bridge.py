@@ -1,13 +1,31 @@
-"""Thread-safe request-to-UI callback bridge."""
+"""Thread-safe request-to-UI callback bridge.
+
+Streamed deltas wait in queues until the UI flush timer drains them. With
+``settings.stream_buffer_max_chars`` above zero the queues are bounded: once
+that many characters are waiting, the request thread's delta callbacks await
+until the UI catches up, so a fast provider cannot grow memory without limit
+on a multi-megabyte response. A single delta larger than the bound is still
+accepted when the queues are empty, and ``close()`` releases any waiting
+producer and drops later deltas, so a stopped UI never deadlocks the request.
+"""
 
 from __future__ import annotations
 
+import asyncio
+import threading
 import time
 from queue import Empty, SimpleQueue
 from typing import TYPE_CHECKING
 
+from tunacode.types import ToolProgress, UsageUpdate
+
 from tunacode.ui.request_debug import BridgeDrainBatch
-from tunacode.ui.widgets import CompactionStatusChanged, SystemNoticeDisplay
+from tunacode.ui.widgets import (
+    CompactionStatusChanged,
+    SystemNoticeDisplay,
+    ToolProgressChanged,
+    UsageChanged,
+)
 
 if TYPE_CHECKING:
     from tunacode.ui.app import TextualReplApp
@@ -20,16 +38,43 @@ class RequestUiBridge:
     and UI mutation happens later when the app flushes queued state.
     """
 
-    def __init__(self, app: TextualReplApp) -> None:
+    BACKPRESSURE_POLL_SECONDS = 0.01
+
+    def __init__(self, app: TextualReplApp, *, max_buffered_chars: int = 0) -> None:
         self._app = app
         self._streaming_deltas: SimpleQueue[tuple[str, float]] = SimpleQueue()
         self._thinking_deltas: SimpleQueue[tuple[str, float]] = SimpleQueue()
+        self._max_buffered_chars = max_buffered_chars
+        self._buffered_chars = 0
+        self._buffer_lock = threading.Lock()
+        self._closed = threading.Event()
+
+    @property
+    def buffered_chars(self) -> int:
+        with self._buffer_lock:
+            return self._buffered_chars
+
+    def close(self) -> None:
+        """Stop accepting deltas and release producers waiting for buffer space."""
+        self._closed.set()
 
     async def streaming_callback(self, delta: str) -> None:
-        self._streaming_deltas.put((delta, time.monotonic()))
+        await self._enqueue(self._streaming_deltas, delta)
 
     async def thinking_callback(self, delta: str) -> None:
-        self._thinking_deltas.put((delta, time.monotonic()))
+        await self._enqueue(self._thinking_deltas, delta)
+
+    def _is_full(self) -> bool:
+        return 0 < self._max_buffered_chars <= self.buffered_chars
+
+    async def _enqueue(self, queue: SimpleQueue[tuple[str, float]], delta: str) -> None:
+        while self._is_full() and not self._closed.is_set():
+            await asyncio.sleep(self.BACKPRESSURE_POLL_SECONDS)
+        if self._closed.is_set():
+            return
+        with self._buffer_lock:
+            self._buffered_chars += len(delta)
+        queue.put((delta, time.monotonic()))
 
     def notice_callback(self, notice: str) -> None:
         self._app.post_message(SystemNoticeDisplay(notice=notice))
@@ -37,6 +82,12 @@ class RequestUiBridge:
     def compaction_status_callback(self, active: bool) -> None:
         self._app.post_message(CompactionStatusChanged(active=active))
 
+    def tool_progress_callback(self, progress: ToolProgress) -> None:
+        self._app.post_message(ToolProgressChanged(progress=progress))
+
+    def usage_callback(self, update: UsageUpdate) -> None:
+        self._app.post_message(UsageChanged(update=update))
+
     def drain_streaming(self) -> BridgeDrainBatch:
         return self._drain_queue(self._streaming_deltas)
 
@@ -54,6 +105,8 @@ class RequestUiBridge:
             except Empty:
                 if chunk_count == 0:
                     return BridgeDrainBatch()
+                with self._buffer_lock:
+                    self._buffered_chars -= char_count
                 oldest_age_ms = (time.monotonic() - oldest_enqueued_at) * 1000.0
                 return BridgeDrainBatch(
                     text="".join(chunks),
@@ -1,5 +1,6 @@
 """Textual screens for TunaCode REPL."""
 
+from tunacode.ui.screens.draft_recovery import DraftRecoveryScreen  # noqa: F401
 from tunacode.ui.screens.model_picker import ModelPickerScreen  # noqa: F401
 from tunacode.ui.screens.session_picker import SessionPickerScreen  # noqa: F401
 from tunacode.ui.screens.setup import SetupScreen  # noqa: F401
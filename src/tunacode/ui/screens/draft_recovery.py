@@ -0,0 +1,77 @@
+"""Draft recovery confirmation screen for TunaCode."""
+
+from __future__ import annotations
+
+from datetime import datetime
+
+from textual.app import ComposeResult
+from textual.containers import Vertical
+from textual.screen import Screen
+from textual.widgets import Static
+
+from tunacode.ui.drafts import Draft
+
+# Modal layout constants
+MODAL_WIDTH = 60
+MODAL_PADDING_VERTICAL = 1
+MODAL_PADDING_HORIZONTAL = 2
+SECTION_MARGIN_BOTTOM = 1
+PREVIEW_MAX_LINES = 5
+
+
+class DraftRecoveryScreen(Screen[bool]):
+    """Modal screen offering to restore an unsent composer draft."""
+
+    CSS = f"""
+    DraftRecoveryScreen {{
+        align: center middle;
+    }}
+
+    #draft-container {{
+        width: {MODAL_WIDTH};
+        height: auto;
+        border: solid $primary;
+        background: $surface;
+        padding: {MODAL_PADDING_VERTICAL} {MODAL_PADDING_HORIZONTAL};
+    }}
+
+    #draft-title {{
+        text-style: bold;
+        color: $accent;
+        text-align: center;
+        margin-bottom: {SECTION_MARGIN_BOTTOM};
+    }}
+
+    #draft-preview {{
+        color: $text-muted;
+        margin-bottom: {SECTION_MARGIN_BOTTOM};
+    }}
+    """
+
+    BINDINGS = [
+        ("escape", "discard", "Discard"),
+        ("y", "recover", "Yes"),
+        ("n", "discard", "No"),
+    ]
+
+    def __init__(self, draft: Draft) -> None:
+        super().__init__()
+        self._draft = draft
+
+    def compose(self) -> ComposeResult:
+        saved_at = datetime.fromtimestamp(self._draft.saved_at).strftime("%Y-%m-%d %H:%M")
+        lines = self._draft.text.splitlines()
+        preview = "\n".join(lines[:PREVIEW_MAX_LINES])
+        if len(lines) > PREVIEW_MAX_LINES:
+            preview += f"\n... ({len(lines) - PREVIEW_MAX_LINES} more lines)"
+        with Vertical(id="draft-container"):
+            yield Static("Unsent Draft", id="draft-title")
+            yield Static(f"Saved {saved_at}", id="draft-info")
+            yield Static(preview, id="draft-preview", markup=False)
+            yield Static("Recover draft? (y/n)")
+
+    def action_recover(self) -> None:
+        self.dismiss(True)
+
+    def action_discard(self) -> None:
+        self.dismiss(False)
@@ -87,6 +87,9 @@ class SessionPickerScreen(Screen[str | None]):
             current_marker = " (current)" if is_current else ""
 
             label = f"{short_id}{current_marker} | {msg_count} msgs | {model} | {last_mod}"
+            tags = session.get("tags") or []
+            if tags:
+                label += " | " + " ".join(f"#{tag}" for tag in tags)
             options.append(Option(label, id=session_id))
 
             if is_current:
@@ -15,6 +15,13 @@ from rich.console import RenderableType
 from rich.text import Text
 from textual.notifications import SeverityLevel
 
+from tunacode.utils.system.process_group import (
+    kill_process_group,
+    new_process_group_kwargs,
+    signal_process_group,
+)
+from tunacode.utils.system.shell_program import ShellInvocation
+
 SHELL_COMMAND_TIMEOUT_SECONDS: float = 120.0
 SHELL_COMMAND_CANCEL_GRACE_SECONDS: float = 0.5
 SHELL_COMMAND_USAGE_TEXT = "Usage: !<command>"
@@ -82,6 +89,8 @@ class ShellRunnerHost(Protocol):
 
     def tool_panel_max_width(self) -> int: ...
 
+    def shell_invocation(self) -> ShellInvocation: ...
+
 
 @dataclass
 class ShellRunner:
@@ -121,7 +130,7 @@ class ShellRunner:
             return
 
         try:
-            process.send_signal(SHELL_CANCEL_SIGNAL)
+            signal_process_group(process, SHELL_CANCEL_SIGNAL)
         except ProcessLookupError:
             assert self._task is not None
             self._task.cancel()
@@ -218,7 +227,7 @@ STDERR:
         try:
             await asyncio.wait_for(process.wait(), timeout=SHELL_COMMAND_CANCEL_GRACE_SECONDS)
         except TimeoutError:
-            process.kill()
+            kill_process_group(process)
             await process.wait()
 
     async def _run(self, cmd: str) -> None:
@@ -229,11 +238,12 @@ STDERR:
 
         self.host.notify(f"Running: {cmd}")
 
-        process = await asyncio.create_subprocess_shell(
-            cmd,
+        process = await asyncio.create_subprocess_exec(
+            *self.host.shell_invocation().argv(cmd),
             stdout=asyncio.subprocess.PIPE,
             stderr=asyncio.subprocess.PIPE,
             stdin=subprocess.DEVNULL,
+            **new_process_group_kwargs(),
         )
         self._process = process
 
@@ -243,7 +253,7 @@ STDERR:
                 timeout=SHELL_COMMAND_TIMEOUT_SECONDS,
             )
         except TimeoutError:
-            process.kill()
+            kill_process_group(process)
             await process.wait()
             raise  # Let _on_done handle uniformly
         except asyncio.CancelledError:
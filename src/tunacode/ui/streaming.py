@@ -5,6 +5,8 @@ from __future__ import annotations
 import time
 from typing import TYPE_CHECKING
 
+from tunacode.ui.renderers.streaming_markdown import StreamingMarkdown
+
 if TYPE_CHECKING:
     from textual.widgets import Static
 
@@ -13,11 +15,11 @@ class StreamingHandler:
     def __init__(self, output_widget: Static, throttle_ms: float) -> None:
         self._output = output_widget
         self._throttle_ms = throttle_ms
-        self._text: str = ""
+        self._markdown = StreamingMarkdown()
         self._last_update: float = 0.0
 
     async def callback(self, chunk: str) -> None:
-        self._text += chunk
+        self._markdown.append(chunk)
         is_first_chunk = not self._output.has_class("active")
         if is_first_chunk:
             self._output.add_class("active")
@@ -25,10 +27,10 @@ class StreamingHandler:
         elapsed_ms = (now - self._last_update) * 1000.0
         if elapsed_ms >= self._throttle_ms or is_first_chunk:
             self._last_update = now
-            self._output.update(self._text)
+            self._output.update(self._markdown.renderable())
 
     def reset(self) -> None:
         self._output.update("")
         self._output.remove_class("active")
-        self._text = ""
+        self._markdown.reset()
         self._last_update = 0.0
@@ -0,0 +1,176 @@
+"""Terminal capability detection and the degraded-rendering overrides.
+
+Capabilities are detected once at startup from the environment: ``NO_COLOR``
+(any non-empty value) or ``TERM=dumb`` turn color off entirely, ``COLORTERM``
+of ``truecolor``/``24bit`` (or Windows Terminal) means 24-bit color, a
+``TERM`` ending in ``256color`` means 256 colors, and anything else gets the
+16 standard colors. Unicode needs a UTF-8 locale or output encoding.
+``settings.terminal.color`` and ``settings.terminal.unicode`` override the
+detection (``auto`` keeps it).
+
+The app is built inside ``textual_environment()`` so Textual's Rich console
+sees the resolved color depth: highlight colors are downgraded by Rich to the
+nearest 256 or 16 color, and ``none`` renders without any color codes.
+Renderers read ``get_terminal_capabilities()``; unicode glyphs get ASCII
+fallbacks when unicode is off. Widths are measured in terminal cells, so
+wide (CJK) characters count twice.
+"""
+
+from __future__ import annotations
+
+import os
+import shutil
+import sys
+from collections.abc import Iterator, Mapping
+from contextlib import contextmanager
+from dataclasses import dataclass
+
+from rich.cells import cell_len, set_cell_size
+
+from tunacode.constants import TerminalColorMode, TerminalUnicodeMode
+from tunacode.types import TerminalSettings
+
+TRUECOLOR_COLORTERM_VALUES = frozenset({"truecolor", "24bit"})
+DUMB_TERM = "dumb"
+UTF8_MARKERS = ("utf-8", "utf8")
+DEFAULT_TERMINAL_WIDTH = 80
+ELLIPSIS_UNICODE = "…"
+ELLIPSIS_ASCII = "..."
+
+# Environment steering Rich's color detection for each depth; None removes the key.
+_COLOR_ENVIRONMENT: dict[TerminalColorMode, dict[str, str | None]] = {
+    TerminalColorMode.TRUECOLOR: {"NO_COLOR": None, "COLORTERM": "truecolor"},
+    TerminalColorMode.COLOR_256: {
+        "NO_COLOR": None,
+        "COLORTERM": None,
+        "TERM": "xterm-256color",
+    },
+    TerminalColorMode.COLOR_16: {"NO_COLOR": None, "COLORTERM": None, "TERM": "xterm"},
+    TerminalColorMode.NONE: {"NO_COLOR": "1"},
+}
+
+
+@dataclass(frozen=True, slots=True)
+class TerminalCapabilities:
+    color: TerminalColorMode
+    unicode: bool
+    width: int
+
+    @property
+    def has_color(self) -> bool:
+        return self.color is not TerminalColorMode.NONE
+
+    @property
+    def ellipsis(self) -> str:
+        return ELLIPSIS_UNICODE if self.unicode else ELLIPSIS_ASCII
+
+
+def detect_color_mode(environ: Mapping[str, str]) -> TerminalColorMode:
+    term = environ.get("TERM", "").lower()
+    if environ.get("NO_COLOR", "") != "" or term == DUMB_TERM:
+        return TerminalColorMode.NONE
+    if environ.get("COLORTERM", "").lower() in TRUECOLOR_COLORTERM_VALUES:
+        return TerminalColorMode.TRUECOLOR
+    if "WT_SESSION" in environ:
+        return TerminalColorMode.TRUECOLOR
+    if term.endswith("256color"):
+        return TerminalColorMode.COLOR_256
+    return TerminalColorMode.COLOR_16
+
+
+def detect_unicode(environ: Mapping[str, str], encoding: str | None) -> bool:
+    if encoding and encoding.lower().replace("_", "-") in UTF8_MARKERS:
+        return True
+    locale = environ.get("LC_ALL") or environ.get("LC_CTYPE") or environ.get("LANG", "")
+    return any(marker in locale.lower() for marker in UTF8_MARKERS)
+
+
+def detect_terminal_capabilities(
+    environ: Mapping[str, str],
+    *,
+    encoding: str | None,
+    width: int,
+) -> TerminalCapabilities:
+    return TerminalCapabilities(
+        color=detect_color_mode(environ),
+        unicode=detect_unicode(environ, encoding),
+        width=width,
+    )
+
+
+def resolve_terminal_capabilities(
+    settings: TerminalSettings,
+    environ: Mapping[str, str] | None = None,
+) -> TerminalCapabilities:
+    """Detect capabilities for this process and apply the configured overrides."""
+    env = os.environ if environ is None else environ
+    stdout = sys.stdout
+    detected = detect_terminal_capabilities(
+        env,
+        encoding=None if stdout is None else stdout.encoding,
+        width=shutil.get_terminal_size((DEFAULT_TERMINAL_WIDTH, 24)).columns,
+    )
+    color_mode = TerminalColorMode(settings["color"])
+    unicode_mode = TerminalUnicodeMode(settings["unicode"])
+    return TerminalCapabilities(
+        color=detected.color if color_mode is TerminalColorMode.AUTO else color_mode,
+        unicode=(
+            detected.unicode
+            if unicode_mode is TerminalUnicodeMode.AUTO
+            else unicode_mode is TerminalUnicodeMode.ON
+        ),
+        width=detected.width,
+    )
+
+
+@contextmanager
+def textual_environment(capabilities: TerminalCapabilities) -> Iterator[None]:
+    """Expose the resolved color depth to Rich while the Textual app is constructed.
+
+    The environment is restored afterwards, so shell commands run by the agent
+    still inherit the user's own ``TERM`` and ``COLORTERM``.
+    """
+    overrides = _COLOR_ENVIRONMENT[capabilities.color]
+    saved = {key: os.environ.get(key) for key in overrides}
+    _apply_environment(overrides)
+    try:
+        yield
+    finally:
+        _apply_environment(saved)
+
+
+def _apply_environment(values: Mapping[str, str | None]) -> None:
+    for key, value in values.items():
+        if value is None:
+            os.environ.pop(key, None)
+        else:
+            os.environ[key] = value
+
+
+def truncate_cells(text: str, max_width: int, *, ellipsis: str = ELLIPSIS_ASCII) -> str:
+    """Cut ``text`` to ``max_width`` terminal cells, ending with ``ellipsis`` when cut."""
+    if cell_len(text) <= max_width:
+        return text
+    keep = max(0, max_width - cell_len(ellipsis))
+    return set_cell_size(text, keep).rstrip() + ellipsis
+
+
+@dataclass(slots=True)
+class _TerminalState:
+    capabilities: TerminalCapabilities = TerminalCapabilities(
+        color=TerminalColorMode.TRUECOLOR,
+        unicode=True,
+        width=DEFAULT_TERMINAL_WIDTH,
+    )
+
+
+# Module-level singleton: the UI runs one app per process.
+_state = _TerminalState()
+
+
+def get_terminal_capabilities() -> TerminalCapabilities:
+    return _state.capabilities
+
+
+def set_terminal_capabilities(capabilities: TerminalCapabilities) -> None:
+    _state.capabilities = capabilities
@@ -0,0 +1,82 @@
+"""Watch-mode driver: polls a FileWatcher and submits its prompts as turns.
+
+Each tick scans the watched files in a worker thread so large trees never
+block the event loop; a tick that finds the previous scan still running is
+skipped.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import time
+from typing import TYPE_CHECKING
+
+from tunacode.core.watch.watcher import FileWatcher
+
+from tunacode.ui.widgets.messages import EditorSubmitRequested
+
+if TYPE_CHECKING:
+    from textual.timer import Timer
+
+    from tunacode.ui.app import TextualReplApp
+
+WATCH_POLL_INTERVAL_SECONDS = 0.5
+
+
+class WatchMode:
+    """Own the watch timer and keep the agent's own edits from re-triggering it."""
+
+    def __init__(self, app: TextualReplApp) -> None:
+        self._app = app
+        self._watcher: FileWatcher | None = None
+        self._timer: Timer | None = None
+        self._was_busy = False
+        self._scanning = False
+
+    @property
+    def watcher(self) -> FileWatcher | None:
+        return self._watcher
+
+    def start(self, watcher: FileWatcher) -> None:
+        self.stop()
+        self._watcher = watcher
+        self._was_busy = False
+        self._timer = self._app.set_interval(WATCH_POLL_INTERVAL_SECONDS, self._tick)
+
+    def stop(self) -> None:
+        if self._timer is not None:
+            self._timer.stop()
+        self._timer = None
+        self._watcher = None
+
+    def _is_busy(self) -> bool:
+        app = self._app
+        return app.request_in_flight or not app.request_queue.empty()
+
+    async def _tick(self) -> None:
+        watcher = self._watcher
+        if watcher is None or self._scanning:
+            return
+
+        self._scanning = True
+        try:
+            snapshot = await asyncio.to_thread(watcher.scan)
+        finally:
+            self._scanning = False
+        if self._watcher is not watcher:
+            return
+
+        # Changes made while a turn runs (or just before it ended) are the agent's own.
+        if self._is_busy() or self._was_busy:
+            watcher.rebaseline(snapshot)
+            self._was_busy = self._is_busy()
+            return
+
+        prompt = watcher.poll(time.monotonic(), snapshot)
+        if prompt is not None:
+            self._was_busy = True
+            self._app.post_message(EditorSubmitRequested(text=prompt, raw_text=prompt))
+            return
+        if watcher.exhausted:
+            self.stop()
+            self._app.notify(f"Watch mode stopped after {watcher.runs} run(s)")
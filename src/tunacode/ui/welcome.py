@@ -29,6 +29,7 @@ class WriteableLog(Protocol):
 
 
 WELCOME_TITLE_FORMAT = ">>> {name} v{version}"
+SAFE_MODE_BANNER = "Safe mode: only read-only tools and commands will run."
 SECTION_DIVIDER = "   ──────────────────────────────────────────────\n\n"
 
 
@@ -38,7 +39,7 @@ def generate_logo() -> Text:
     return Text.from_ansi(logo_ansi)
 
 
-def show_welcome(log: WriteableLog) -> None:
+def show_welcome(log: WriteableLog, *, safe_mode: bool = False) -> None:
     """Display welcome message with logo to the given log widget."""
     try:
         logo = generate_logo()
@@ -64,6 +65,8 @@ def show_welcome(log: WriteableLog) -> None:
     welcome.append("\n")
     welcome.append(f"{welcome_title}\n", style=STYLE_PRIMARY)
     welcome.append("AI coding assistant in your terminal.\n\n", style=STYLE_MUTED)
+    if safe_mode:
+        welcome.append(f"{SAFE_MODE_BANNER}\n\n", style=STYLE_WARNING)
 
     # Group 1: Core navigation
     welcome.append("   /help", style=STYLE_PRIMARY)
@@ -12,11 +12,14 @@ _WIDGET_EXPORTS = {
     "Editor": ".editor",
     "FileAutoComplete": ".file_autocomplete",
     "EditorCompletionsAvailable": ".messages",
+    "EditorDraftChanged": ".messages",
     "EditorSubmitRequested": ".messages",
     "ToolResultDisplay": ".messages",
     "TuiLogDisplay": ".messages",
     "SystemNoticeDisplay": ".messages",
     "CompactionStatusChanged": ".messages",
+    "ToolProgressChanged": ".messages",
+    "UsageChanged": ".messages",
     "ResourceBar": ".resource_bar",
     "SkillsAutoComplete": ".skills_autocomplete",
 }
@@ -5,15 +5,29 @@ from __future__ import annotations
 from textual.widgets import Input
 from textual_autocomplete import AutoComplete, DropdownItem, TargetState
 
+from tunacode.prompts.custom_prompts import CustomPromptError, list_custom_prompts
+
 from tunacode.ui.command_registry import COMMAND_DESCRIPTIONS
 from tunacode.ui.widgets.autocomplete_positioning import align_autocomplete_above_target
 
 COMMAND_PREFIX = "/"
 COMMAND_ARGUMENT_SEPARATOR = " "
 COMMAND_DESCRIPTION_SEPARATOR = " - "
+CUSTOM_PROMPT_DESCRIPTION = "Custom prompt"
+
 
-# Pre-compute sorted command items at module load.
-_COMMAND_ITEMS: list[tuple[str, str]] = sorted(COMMAND_DESCRIPTIONS.items())
+def _command_items() -> list[tuple[str, str]]:
+    """Return built-in commands plus custom prompts, read fresh so new files show up."""
+    items = dict(COMMAND_DESCRIPTIONS)
+    try:
+        prompts = list_custom_prompts()
+    except CustomPromptError:
+        prompts = []
+    for prompt in prompts:
+        description = prompt.schema.description or CUSTOM_PROMPT_DESCRIPTION
+        usage = prompt.schema.usage(prompt.name)
+        items.setdefault(prompt.name.casefold(), f"{description} ({usage})")
+    return sorted(items.items())
 
 
 def _get_command_search_prefix(text: str, cursor_position: int) -> str | None:
@@ -33,7 +47,7 @@ def _is_exact_command_match(command_prefix: str) -> bool:
     if not command_prefix:
         return False
 
-    return command_prefix.lower() in COMMAND_DESCRIPTIONS
+    return command_prefix.lower() in dict(_command_items())
 
 
 class CommandAutoComplete(AutoComplete):
@@ -85,7 +99,7 @@ class CommandAutoComplete(AutoComplete):
         search = command_prefix.lower()
         return [
             DropdownItem(main=f"{COMMAND_PREFIX}{name}{COMMAND_DESCRIPTION_SEPARATOR}{desc}")
-            for name, desc in _COMMAND_ITEMS
+            for name, desc in _command_items()
             if name.startswith(search)
         ]
 
@@ -14,9 +14,11 @@ from textual.binding import Binding
 from textual.expand_tabs import expand_tabs_inline
 from textual.geometry import Offset, Region, Size
 from textual.strip import Strip
+from textual.timer import Timer
 from textual.widgets import Input
 
-from .messages import EditorSubmitRequested
+from .messages import EditorDraftChanged, EditorSubmitRequested
+from .paste_burst import PASTE_BURST_SETTLE_SECONDS, PasteBurst, normalize_newlines
 
 if TYPE_CHECKING:
     from tunacode.ui.app import TextualReplApp
@@ -65,6 +67,8 @@ class Editor(Input):
         self._was_pasted: bool = False
         self._pasted_content: str = ""
         self._paste_after_typed_text: bool = False
+        self._paste_burst = PasteBurst()
+        self._paste_burst_timer: Timer | None = None
         self._wrap_cache: _WrappedEditorState | None = None
         self._wrap_cache_key: tuple[object, ...] | None = None
 
@@ -90,6 +94,9 @@ class Editor(Input):
             app._last_editor_keypress_at = time.monotonic()
             app._request_debug.note_editor_keypress(key_label=event.character or event.key)
 
+        if self._handle_paste_burst_key(event):
+            return
+
         if self.has_paste_buffer and not self.value and event.key == "backspace":
             event.prevent_default()
             self._clear_paste_buffer()
@@ -119,12 +126,68 @@ class Editor(Input):
             self.value = f"! {event.character}"
             self.cursor_position = len(self.value)
 
+    def _handle_paste_burst_key(self, event: events.Key) -> bool:
+        """Keep an unbracketed paste's Enters from submitting; True when consumed."""
+        now = time.monotonic()
+        if event.key == "enter" and self._paste_burst.follows_keystroke(now):
+            event.prevent_default()
+            event.stop()
+            self._paste_burst.push_line(self.value, now)
+            self.value = ""
+            self._schedule_paste_burst_flush()
+            return True
+        if event.character:
+            self._paste_burst.note_keystroke(now)
+            if self._paste_burst.active:
+                self._schedule_paste_burst_flush()
+        return False
+
+    def _schedule_paste_burst_flush(self) -> None:
+        if self._paste_burst_timer is not None:
+            self._paste_burst_timer.stop()
+        self._paste_burst_timer = self.set_timer(
+            PASTE_BURST_SETTLE_SECONDS, self._flush_paste_burst
+        )
+
+    def _flush_paste_burst(self) -> None:
+        if self._paste_burst_timer is not None:
+            self._paste_burst_timer.stop()
+            self._paste_burst_timer = None
+        if not self._paste_burst.active:
+            return
+        text = self._paste_burst.take(self.value)
+        self.value = ""
+        self._buffer_paste(text)
+
+    @property
+    def draft_text(self) -> str:
+        """The text a submission would send now, or "" when there is nothing to send."""
+        submission = self._build_submission()
+        return submission[1] if submission is not None else ""
+
+    def restore_draft(self, text: str) -> None:
+        """Put recovered draft text back; multi-line drafts return to the paste buffer."""
+        if "\n" in text or len(text) >= self.PASTE_BUFFER_LONG_LINE_THRESHOLD:
+            self.value = ""
+            self._buffer_paste(text)
+            return
+        self.value = text
+        self.cursor_position = len(text)
+
+    def _notify_draft_changed(self) -> None:
+        if self.is_mounted:
+            self.post_message(EditorDraftChanged(text=self.draft_text))
+
     def clear_input(self) -> None:
         self.value = ""
         self._clear_paste_buffer()
         self.scroll_to(x=0, y=0, animate=False, immediate=True)
 
     async def action_submit(self) -> None:
+        if self._paste_burst.active:
+            # The paste is still settling; the user confirms it with another Enter.
+            self._flush_paste_burst()
+            return
         submission = self._build_submission()
         if submission is None:
             return
@@ -140,22 +203,27 @@ class Editor(Input):
 
     def _on_paste(self, event: events.Paste) -> None:
         """Capture full paste content before Input truncates to first line."""
-        line_count = max(1, len(event.text.splitlines()))
+        text = normalize_newlines(event.text)
+        line_count = max(1, len(text.splitlines()))
         is_multiline = line_count > 1
-        is_long_single_line = len(event.text) >= self.PASTE_BUFFER_LONG_LINE_THRESHOLD
+        is_long_single_line = len(text) >= self.PASTE_BUFFER_LONG_LINE_THRESHOLD
 
         if not is_multiline and not is_long_single_line:
             return
 
+        self._buffer_paste(text)
+        event.prevent_default()
+        event.stop()
+
+    def _buffer_paste(self, text: str) -> None:
+        """Hold pasted text as one atomic insert, shown as a summary until submitted."""
         self._was_pasted = True
-        self._pasted_content = event.text
+        self._pasted_content = text
         self._paste_after_typed_text = bool(self.value.strip())
 
         if paste_summary := self.paste_summary:
             self.placeholder = paste_summary
-
-        event.prevent_default()
-        event.stop()
+        self._notify_draft_changed()
 
     def watch_value(self, value: str) -> None:
         """React to value changes."""
@@ -188,6 +256,8 @@ class Editor(Input):
             self.placeholder = ""
 
         self._update_bash_mode(self.value)
+        if previous_summary:
+            self._notify_draft_changed()
 
     def _invalidate_wrap_cache(self) -> None:
         self._wrap_cache = None
@@ -196,6 +266,7 @@ class Editor(Input):
     def _watch_value(self, value: str) -> None:
         super()._watch_value(value)
         self._invalidate_wrap_cache()
+        self._notify_draft_changed()
 
     def _watch__suggestion(self, value: str) -> None:  # noqa: ARG002
         del value
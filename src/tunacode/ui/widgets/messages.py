@@ -7,7 +7,7 @@ from collections.abc import Iterable
 from rich.console import RenderableType
 from textual.message import Message
 
-from tunacode.types import ToolArgs, ToolName, ToolResult
+from tunacode.types import ToolArgs, ToolName, ToolProgress, ToolResult, UsageUpdate
 
 
 class EditorCompletionsAvailable(Message):
@@ -28,6 +28,14 @@ class EditorSubmitRequested(Message):
         self.was_pasted = was_pasted
 
 
+class EditorDraftChanged(Message):
+    """The editor's unsent content changed (typed text or paste buffer)."""
+
+    def __init__(self, *, text: str) -> None:
+        super().__init__()
+        self.text = text
+
+
 class ToolResultDisplay(Message):
     """Request to display a tool result panel in the RichLog."""
 
@@ -72,3 +80,19 @@ class CompactionStatusChanged(Message):
     def __init__(self, *, active: bool) -> None:
         super().__init__()
         self.active = active
+
+
+class ToolProgressChanged(Message):
+    """Request to update one live progress step on the UI thread."""
+
+    def __init__(self, *, progress: ToolProgress) -> None:
+        super().__init__()
+        self.progress = progress
+
+
+class UsageChanged(Message):
+    """Request to show live token usage and cost on the UI thread."""
+
+    def __init__(self, *, update: UsageUpdate) -> None:
+        super().__init__()
+        self.update = update
@@ -0,0 +1,52 @@
+"""Paste handling for terminals that split a paste into keystrokes.
+
+Textual's drivers turn on bracketed paste mode, so most terminals deliver a
+paste as one ``events.Paste``. Terminals (and multiplexers) without it send the
+pasted text as ordinary key presses, with an Enter for every newline, which
+would submit each line on its own. ``PasteBurst`` recognizes those bursts: an
+Enter that arrives within ``PASTE_BURST_WINDOW_SECONDS`` of the previous key
+cannot have been typed, so it becomes a line break in the burst instead of a
+submission.
+"""
+
+from __future__ import annotations
+
+PASTE_BURST_WINDOW_SECONDS: float = 0.01
+PASTE_BURST_SETTLE_SECONDS: float = 0.05
+
+
+def normalize_newlines(text: str) -> str:
+    """Turn CRLF and lone CR line endings (common in pasted text) into LF."""
+    return text.replace("\r\n", "\n").replace("\r", "\n")
+
+
+class PasteBurst:
+    """Lines of a keystroke-delivered paste collected until the burst settles."""
+
+    def __init__(self, *, window_seconds: float = PASTE_BURST_WINDOW_SECONDS) -> None:
+        self._window_seconds = window_seconds
+        self._last_keystroke_at: float | None = None
+        self._lines: list[str] = []
+
+    @property
+    def active(self) -> bool:
+        return bool(self._lines)
+
+    def note_keystroke(self, now: float) -> None:
+        self._last_keystroke_at = now
+
+    def follows_keystroke(self, now: float) -> bool:
+        """True when a key at ``now`` is too close to the previous one to be typed."""
+        last = self._last_keystroke_at
+        return last is not None and now - last <= self._window_seconds
+
+    def push_line(self, line: str, now: float) -> None:
+        self._lines.append(line)
+        self._last_keystroke_at = now
+
+    def take(self, tail: str) -> str:
+        """Return the burst joined with ``tail`` (text after the last Enter) and reset."""
+        text = "\n".join([*self._lines, tail])
+        self._lines = []
+        self._last_keystroke_at = None
+        return text
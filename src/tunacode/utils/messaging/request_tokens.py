@@ -0,0 +1,101 @@
+"""Token estimate of a whole request before it is sent.
+
+``estimate_request_tokens()`` counts what a request carries -- system prompt,
+history, tool definitions and the next user message -- plus the formatting
+overhead the wire API adds around them. The overhead table follows OpenAI's
+published chat accounting: every message costs 3 tokens for its role and
+separators, the reply is primed with 3 more, and each tool definition adds a
+few tokens of function framing around its JSON schema. APIs without an entry
+use the chat-completions values.
+
+Counting goes through ``count_tokens``, ``estimate_tokens()`` by default, so a
+caller with a real tokenizer (``tiktoken`` or a provider endpoint) passes its
+own counter and gets estimates as close as that tokenizer.
+"""
+
+from __future__ import annotations
+
+import json
+from collections.abc import Callable, Sequence
+from dataclasses import dataclass
+
+from tinyagent.agent_types import AgentMessage, AgentTool, AssistantMessage, ToolCallContent
+
+from tunacode.utils.messaging.adapter import get_content
+from tunacode.utils.messaging.token_counter import estimate_tokens
+
+TokenCounter = Callable[[str], int]
+
+DEFAULT_WIRE_API = "openai-completions"
+
+
+@dataclass(frozen=True, slots=True)
+class WireOverhead:
+    per_message: int
+    reply_priming: int
+    per_tool: int
+    tools_base: int
+
+
+CHAT_COMPLETIONS_OVERHEAD = WireOverhead(per_message=3, reply_priming=3, per_tool=7, tools_base=12)
+
+# Formatting tokens each wire API adds, keyed by the model's alchemy API name.
+WIRE_OVERHEADS: dict[str, WireOverhead] = {
+    DEFAULT_WIRE_API: CHAT_COMPLETIONS_OVERHEAD,
+    "minimax-completions": CHAT_COMPLETIONS_OVERHEAD,
+    "openai-responses": WireOverhead(per_message=4, reply_priming=3, per_tool=7, tools_base=12),
+    # Ollama's /api/chat renders the model's chat template: header and end-of-turn markers.
+    "ollama-chat": WireOverhead(per_message=4, reply_priming=4, per_tool=7, tools_base=12),
+}
+
+
+@dataclass(frozen=True, slots=True)
+class RequestTokenEstimate:
+    system: int
+    history: int
+    tools: int
+    next_message: int
+    overhead: int
+
+    @property
+    def total(self) -> int:
+        return self.system + self.history + self.tools + self.next_message + self.overhead
+
+
+def estimate_request_tokens(
+    *,
+    system_prompt: str,
+    messages: Sequence[AgentMessage],
+    tools: Sequence[AgentTool] = (),
+    next_message: str = "",
+    wire_api: str = DEFAULT_WIRE_API,
+    count_tokens: TokenCounter = estimate_tokens,
+) -> RequestTokenEstimate:
+    """Estimate the input tokens of a request without sending it."""
+    overhead = WIRE_OVERHEADS.get(wire_api, CHAT_COMPLETIONS_OVERHEAD)
+    message_count = len(messages) + (1 if system_prompt else 0) + (1 if next_message else 0)
+    framing = message_count * overhead.per_message + overhead.reply_priming
+    if tools:
+        framing += overhead.tools_base + len(tools) * overhead.per_tool
+    return RequestTokenEstimate(
+        system=count_tokens(system_prompt),
+        history=sum(_message_tokens(message, count_tokens) for message in messages),
+        tools=sum(count_tokens(_tool_definition_text(tool)) for tool in tools),
+        next_message=count_tokens(next_message),
+        overhead=framing,
+    )
+
+
+def _message_tokens(message: AgentMessage, count_tokens: TokenCounter) -> int:
+    tokens = count_tokens(get_content(message))
+    if not isinstance(message, AssistantMessage):
+        return tokens
+    for item in message.content:
+        if isinstance(item, ToolCallContent):
+            tokens += count_tokens(f"{item.name}{json.dumps(item.arguments or {})}")
+    return tokens
+
+
+def _tool_definition_text(tool: AgentTool) -> str:
+    parameters = json.dumps(tool.parameters or {}, separators=(",", ":"))
+    return f"{tool.name}{tool.description or ''}{parameters}"
@@ -1,4 +1,4 @@
-"""System utilities: paths, sessions, and file listing."""
+"""System utilities: paths, sessions, file listing, and git diffs."""
 
 from tunacode.configuration.paths import (  # noqa: F401
     check_for_updates,
@@ -6,6 +6,12 @@ from tunacode.configuration.paths import (  # noqa: F401
     get_session_dir,
     get_tunacode_home,
 )
+from tunacode.utils.system.git_diff import (  # noqa: F401
+    FileDiff,
+    format_numbered_diff,
+    parse_git_diff,
+    read_git_diff,
+)
 from tunacode.utils.system.gitignore import (  # noqa: F401
     DEFAULT_IGNORE_PATTERNS,
     list_cwd,
@@ -0,0 +1,318 @@
+"""Parse unified git diffs into files, hunks and numbered lines.
+
+``parse_git_diff()`` accepts ``git diff`` output (or any unified diff) and
+returns one ``FileDiff`` per file. Every added line carries its line number in
+the new file and every removed line its number in the old file, counted from
+each hunk's ``@@ -a,b +c,d @@`` header, so a review can point at "line 42 of
+src/foo.py" exactly. Hunk bodies are consumed by their declared line counts,
+which keeps content lines such as ``--- x`` from being mistaken for headers.
+
+Extended headers are understood: renames and copies (with similarity), mode
+changes, new and deleted files, and binary markers (``Binary files ... differ``
+and ``GIT binary patch``). ``read_git_diff()`` runs ``git diff`` for callers
+that want the working tree or the staging area.
+"""
+
+from __future__ import annotations
+
+import re
+import subprocess
+from dataclasses import dataclass, field
+from pathlib import Path
+
+LINE_CONTEXT = "context"
+LINE_ADDED = "added"
+LINE_REMOVED = "removed"
+
+STATUS_MODIFIED = "modified"
+STATUS_ADDED = "added"
+STATUS_DELETED = "deleted"
+STATUS_RENAMED = "renamed"
+STATUS_COPIED = "copied"
+
+DEV_NULL = "/dev/null"
+GIT_DIFF_TIMEOUT_SECONDS = 10
+
+_DIFF_GIT_PREFIX = "diff --git "
+_HUNK_HEADER = re.compile(r"^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@ ?(.*)$")
+_BINARY_FILES = re.compile(r"^Binary files (.+) and (.+) differ$")
+_C_ESCAPES = {"t": "\t", "n": "\n", '"': '"', "\\": "\\", "a": "\a", "b": "\b", "r": "\r"}
+
+
+@dataclass(frozen=True, slots=True)
+class DiffLine:
+    kind: str
+    text: str
+    old_lineno: int | None
+    new_lineno: int | None
+
+
+@dataclass(slots=True)
+class DiffHunk:
+    old_start: int
+    old_count: int
+    new_start: int
+    new_count: int
+    section: str
+    lines: list[DiffLine] = field(default_factory=list)
+
+
+@dataclass(slots=True)
+class FileDiff:
+    old_path: str | None
+    new_path: str | None
+    status: str = STATUS_MODIFIED
+    old_mode: str | None = None
+    new_mode: str | None = None
+    similarity: int | None = None
+    is_binary: bool = False
+    hunks: list[DiffHunk] = field(default_factory=list)
+
+    @property
+    def path(self) -> str:
+        """The path a reviewer refers to: the new path, or the old one for deletions."""
+        return self.new_path or self.old_path or ""
+
+    def added_lines(self) -> list[DiffLine]:
+        return [line for hunk in self.hunks for line in hunk.lines if line.kind == LINE_ADDED]
+
+    def removed_lines(self) -> list[DiffLine]:
+        return [line for hunk in self.hunks for line in hunk.lines if line.kind == LINE_REMOVED]
+
+
+def _unquote_path(raw: str) -> str:
+    if not (len(raw) >= 2 and raw.startswith('"') and raw.endswith('"')):
+        return raw
+    body, out, index = raw[1:-1], bytearray(), 0
+    while index < len(body):
+        char = body[index]
+        if char != "\\" or index + 1 >= len(body):
+            out.extend(char.encode("utf-8"))
+            index += 1
+            continue
+        escape = body[index + 1]
+        octal = body[index + 1 : index + 4]
+        if len(octal) == 3 and all(digit in "01234567" for digit in octal):
+            out.append(int(octal, 8))
+            index += 4
+            continue
+        out.extend(_C_ESCAPES.get(escape, escape).encode("utf-8"))
+        index += 2
+    return out.decode("utf-8", errors="replace")
+
+
+def _strip_prefix(path: str) -> str | None:
+    path = _unquote_path(path.split("\t", 1)[0])
+    if path == DEV_NULL:
+        return None
+    if path[:2] in ("a/", "b/"):
+        return path[2:]
+    return path
+
+
+def _split_diff_git_paths(rest: str) -> tuple[str | None, str | None]:
+    if rest.startswith('"'):
+        closing = rest.find('" ', 1)
+        if closing != -1:
+            return _strip_prefix(rest[: closing + 1]), _strip_prefix(rest[closing + 2 :])
+    # Same path on both sides ("a/x b/x") is the common case; spaces are legal in x.
+    middle = len(rest) // 2
+    if rest[middle : middle + 1] == " " and rest[2:middle] == rest[middle + 3 :]:
+        return _strip_prefix(rest[:middle]), _strip_prefix(rest[middle + 1 :])
+    old, separator, new = rest.rpartition(" b/")
+    if separator:
+        return _strip_prefix(old), new
+    return None, None
+
+
+def _apply_mode_header(file_diff: FileDiff, line: str) -> bool:
+    for prefix, attribute in (("old mode ", "old_mode"), ("new mode ", "new_mode")):
+        if line.startswith(prefix):
+            setattr(file_diff, attribute, line[len(prefix) :].strip())
+            return True
+    if line.startswith("new file mode "):
+        file_diff.status, file_diff.old_path = STATUS_ADDED, None
+        file_diff.new_mode = line[len("new file mode ") :].strip()
+        return True
+    if line.startswith("deleted file mode "):
+        file_diff.status, file_diff.new_path = STATUS_DELETED, None
+        file_diff.old_mode = line[len("deleted file mode ") :].strip()
+        return True
+    return False
+
+
+def _apply_extended_header(file_diff: FileDiff, line: str) -> None:
+    if _apply_mode_header(file_diff, line):
+        return
+    for verb, status in (("rename", STATUS_RENAMED), ("copy", STATUS_COPIED)):
+        if line.startswith(f"{verb} from "):
+            file_diff.status = status
+            file_diff.old_path = _unquote_path(line[len(verb) + 6 :])
+            return
+        if line.startswith(f"{verb} to "):
+            file_diff.status = status
+            file_diff.new_path = _unquote_path(line[len(verb) + 4 :])
+            return
+    if line.startswith(("similarity index ", "dissimilarity index ")):
+        file_diff.similarity = int(line.rsplit(" ", 1)[-1].rstrip("%"))
+        return
+    if line.startswith("GIT binary patch"):
+        file_diff.is_binary = True
+        return
+    binary = _BINARY_FILES.match(line)
+    if binary:
+        file_diff.is_binary = True
+        file_diff.old_path = _strip_prefix(binary.group(1))
+        file_diff.new_path = _strip_prefix(binary.group(2))
+
+
+def _parse_hunk_header(line: str) -> DiffHunk | None:
+    match = _HUNK_HEADER.match(line)
+    if match is None:
+        return None
+    old_count = match.group(2)
+    new_count = match.group(4)
+    return DiffHunk(
+        old_start=int(match.group(1)),
+        old_count=1 if old_count is None else int(old_count),
+        new_start=int(match.group(3)),
+        new_count=1 if new_count is None else int(new_count),
+        section=match.group(5),
+    )
+
+
+class _DiffParser:
+    def __init__(self) -> None:
+        self.files: list[FileDiff] = []
+        self._file: FileDiff | None = None
+        self._hunk: DiffHunk | None = None
+        self._old_lineno = 0
+        self._new_lineno = 0
+        self._old_left = 0
+        self._new_left = 0
+
+    def feed(self, line: str) -> None:
+        if self._hunk is not None and (self._old_left > 0 or self._new_left > 0):
+            if self._feed_hunk_line(line):
+                return
+        self._hunk = None
+        if line.startswith(_DIFF_GIT_PREFIX):
+            old_path, new_path = _split_diff_git_paths(line[len(_DIFF_GIT_PREFIX) :])
+            self._start_file(FileDiff(old_path=old_path, new_path=new_path))
+            return
+        if line.startswith("--- "):
+            old_path = _strip_prefix(line[4:])
+            if self._file is None or self._file.hunks:
+                self._start_file(FileDiff(old_path=old_path, new_path=old_path))
+            self._set_side(old=True, path=old_path)
+            return
+        if line.startswith("+++ ") and self._file is not None:
+            self._set_side(old=False, path=_strip_prefix(line[4:]))
+            return
+        hunk = _parse_hunk_header(line)
+        if hunk is not None and self._file is not None:
+            self._file.hunks.append(hunk)
+            self._hunk = hunk
+            self._old_lineno, self._new_lineno = hunk.old_start, hunk.new_start
+            self._old_left, self._new_left = hunk.old_count, hunk.new_count
+            return
+        if self._file is not None:
+            _apply_extended_header(self._file, line)
+
+    def _start_file(self, file_diff: FileDiff) -> None:
+        self.files.append(file_diff)
+        self._file = file_diff
+
+    def _set_side(self, *, old: bool, path: str | None) -> None:
+        file_diff = self._file
+        if file_diff is None:
+            return
+        if path is None:
+            file_diff.status = STATUS_ADDED if old else STATUS_DELETED
+        if old:
+            file_diff.old_path = path
+        else:
+            file_diff.new_path = path
+
+    def _feed_hunk_line(self, line: str) -> bool:
+        hunk = self._hunk
+        if hunk is None:
+            return False
+        if line.startswith("\\"):
+            return True
+        marker, text = line[:1], line[1:]
+        if marker == "+":
+            hunk.lines.append(DiffLine(LINE_ADDED, text, None, self._new_lineno))
+            self._new_lineno += 1
+            self._new_left -= 1
+            return True
+        if marker == "-":
+            hunk.lines.append(DiffLine(LINE_REMOVED, text, self._old_lineno, None))
+            self._old_lineno += 1
+            self._old_left -= 1
+            return True
+        if marker == " " or line == "":
+            hunk.lines.append(DiffLine(LINE_CONTEXT, text, self._old_lineno, self._new_lineno))
+            self._old_lineno += 1
+            self._new_lineno += 1
+            self._old_left -= 1
+            self._new_left -= 1
+            return True
+        return False
+
+
+def parse_git_diff(diff_text: str) -> list[FileDiff]:
+    """Parse unified diff text into per-file hunks with old/new line numbers."""
+    parser = _DiffParser()
+    for line in diff_text.splitlines():
+        parser.feed(line)
+    return parser.files
+
+
+def read_git_diff(*, staged: bool = False, cwd: Path | None = None) -> str:
+    """Return ``git diff`` output for the working tree, or the index with ``staged``.
+
+    Raises ``RuntimeError`` when git is missing or the directory is not a repository.
+    """
+    command = ["git", "diff", "--no-color", "--no-ext-diff"]
+    if staged:
+        command.append("--cached")
+    try:
+        result = subprocess.run(
+            command,
+            capture_output=True,
+            text=True,
+            cwd=cwd,
+            timeout=GIT_DIFF_TIMEOUT_SECONDS,
+        )
+    except (OSError, subprocess.TimeoutExpired) as exc:
+        raise RuntimeError(f"git diff failed: {exc}") from exc
+    if result.returncode != 0:
+        raise RuntimeError(f"git diff failed: {result.stderr.strip()}")
+    return result.stdout
+
+
+def format_numbered_diff(files: list[FileDiff]) -> str:
+    """Render parsed files with explicit line numbers for review prompts.
+
+    Added and context lines show their new-file number, removed lines their
+    old-file number, e.g. ``src/foo.py:42 + value = 1``.
+    """
+    out: list[str] = []
+    for file_diff in files:
+        header = f"{file_diff.path} ({file_diff.status})"
+        if file_diff.status in (STATUS_RENAMED, STATUS_COPIED):
+            header = f"{file_diff.old_path} -> {file_diff.new_path} ({file_diff.status})"
+        if file_diff.is_binary:
+            header += " [binary]"
+        out.append(header)
+        for hunk in file_diff.hunks:
+            old_range = f"{hunk.old_start},{hunk.old_count}"
+            out.append(f"@@ -{old_range} +{hunk.new_start},{hunk.new_count} @@")
+            for line in hunk.lines:
+                if line.kind == LINE_REMOVED:
+                    out.append(f"{file_diff.old_path}:{line.old_lineno} - {line.text}")
+                    continue
+                marker = "+" if line.kind == LINE_ADDED else " "
+                out.append(f"{file_diff.path}:{line.new_lineno} {marker} {line.text}")
+    return "\n".join(out)
@@ -0,0 +1,92 @@
+"""Run shell commands in their own process group so the whole tree can be killed.
+
+Killing only the shell leaves its descendants running: ``bash -c "server &"``
+or a pipeline keeps going after the command that started it is gone. Commands
+are therefore started as the leader of a new process group (a new session on
+POSIX, ``CREATE_NEW_PROCESS_GROUP`` on Windows), and shutdown signals the
+group. On Windows the tree is ended with ``taskkill /T``; Job Objects are not
+used.
+
+``AGENT_TURN_SCOPE`` holds the groups started during the current agent turn.
+The request orchestrator kills whatever is still alive when the turn ends, so
+a backgrounded process may serve later tool calls in the same turn but never
+outlives it.
+"""
+
+from __future__ import annotations
+
+import os
+import signal
+import subprocess
+import sys
+from asyncio.subprocess import Process
+from typing import Any
+
+IS_WINDOWS = sys.platform == "win32"
+
+
+def new_process_group_kwargs() -> dict[str, Any]:
+    """Keyword arguments for ``asyncio.create_subprocess_*`` that start a new group."""
+    if IS_WINDOWS:
+        return {"creationflags": subprocess.CREATE_NEW_PROCESS_GROUP}  # type: ignore[attr-defined]
+    return {"start_new_session": True}
+
+
+def signal_process_group(process: Process, sig: int) -> None:
+    """Send ``sig`` to every process in ``process``'s group; a gone group is ignored."""
+    if IS_WINDOWS:
+        if sig == signal.SIGINT:
+            process.send_signal(signal.CTRL_BREAK_EVENT)  # type: ignore[attr-defined]
+            return
+        subprocess.run(
+            ["taskkill", "/T", "/F", "/PID", str(process.pid)],
+            capture_output=True,
+            check=False,
+        )
+        return
+    try:
+        os.killpg(process.pid, sig)
+    except (ProcessLookupError, PermissionError):
+        return
+
+
+def kill_process_group(process: Process) -> None:
+    """Kill ``process`` and all of its descendants that share its group."""
+    signal_process_group(process, signal.SIGKILL if not IS_WINDOWS else signal.SIGTERM)
+
+
+class ProcessScope:
+    """Process groups that must not outlive one lifetime, such as an agent turn."""
+
+    def __init__(self) -> None:
+        self._processes: list[Process] = []
+
+    def register(self, process: Process) -> None:
+        self._processes.append(process)
+
+    def __len__(self) -> int:
+        return len(self._processes)
+
+    def kill_all(self) -> int:
+        """Kill every registered group and forget it; returns how many were registered."""
+        processes, self._processes = self._processes, []
+        for process in processes:
+            kill_process_group(process)
+        return len(processes)
+
+
+AGENT_TURN_SCOPE = ProcessScope()
+
+
+def pid_is_running(pid: int) -> bool:
+    """Whether a process with ``pid`` exists; always True on Windows."""
+    if IS_WINDOWS:
+        # os.kill(pid, 0) terminates the process on Windows; assume it runs.
+        return True
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    return True
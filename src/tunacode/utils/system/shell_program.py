@@ -0,0 +1,72 @@
+"""Which shell interprets commands, and how it is invoked.
+
+``settings.shell.program`` names the shell (``bash``, ``zsh``, ``fish``,
+``pwsh``...) and ``settings.shell.args`` the flags placed before the command.
+An empty program picks the platform default: ``bash`` on POSIX (``/bin/sh``
+when bash is not installed) and ``pwsh`` on Windows (``COMSPEC``, usually
+``cmd.exe``, when PowerShell 7 is not installed). Empty args pick the flags
+the named shell needs to run one command string.
+"""
+
+from __future__ import annotations
+
+import os
+import shutil
+import sys
+from dataclasses import dataclass
+from pathlib import PurePath
+
+IS_WINDOWS = sys.platform == "win32"
+
+POSIX_DEFAULT_SHELL = "bash"
+POSIX_FALLBACK_SHELL = "/bin/sh"
+WINDOWS_DEFAULT_SHELL = "pwsh"
+WINDOWS_FALLBACK_SHELL = "cmd.exe"
+
+POSIX_COMMAND_FLAGS: tuple[str, ...] = ("-c",)
+POWERSHELL_COMMAND_FLAGS: tuple[str, ...] = ("-NoProfile", "-NonInteractive", "-Command")
+CMD_COMMAND_FLAGS: tuple[str, ...] = ("/d", "/s", "/c")
+
+SHELL_COMMAND_FLAGS: dict[str, tuple[str, ...]] = {
+    "pwsh": POWERSHELL_COMMAND_FLAGS,
+    "powershell": POWERSHELL_COMMAND_FLAGS,
+    "cmd": CMD_COMMAND_FLAGS,
+}
+
+
+@dataclass(frozen=True, slots=True)
+class ShellInvocation:
+    """A shell program and the flags that make it run one command string."""
+
+    program: str
+    args: tuple[str, ...]
+
+    def argv(self, command: str) -> list[str]:
+        return [self.program, *self.args, command]
+
+    def is_available(self) -> bool:
+        return shutil.which(self.program) is not None
+
+
+def default_shell_program() -> str:
+    """The platform's preferred shell, or its always-present fallback."""
+    if IS_WINDOWS:
+        if shutil.which(WINDOWS_DEFAULT_SHELL) is not None:
+            return WINDOWS_DEFAULT_SHELL
+        return os.environ.get("COMSPEC", WINDOWS_FALLBACK_SHELL)
+    if shutil.which(POSIX_DEFAULT_SHELL) is not None:
+        return POSIX_DEFAULT_SHELL
+    return POSIX_FALLBACK_SHELL
+
+
+def shell_command_flags(program: str) -> tuple[str, ...]:
+    """Flags ``program`` needs to run a command string; ``-c`` for POSIX-style shells."""
+    name = PurePath(program.replace("\\", "/")).name.lower().removesuffix(".exe")
+    return SHELL_COMMAND_FLAGS.get(name, POSIX_COMMAND_FLAGS)
+
+
+def resolve_shell(program: str, args: list[str]) -> ShellInvocation:
+    """Fill an empty configured program or argument list with the defaults."""
+    resolved_program = program or default_shell_program()
+    resolved_args = tuple(args) if args else shell_command_flags(resolved_program)
+    return ShellInvocation(program=resolved_program, args=resolved_args)
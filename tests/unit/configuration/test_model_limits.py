@@ -0,0 +1,67 @@
+"""Tests for per-model context window and max token overrides."""
+
+from __future__ import annotations
+
+import copy
+import re
+
+import pytest
+
+from tunacode.configuration import limits
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.configuration.models import get_model_context_window
+from tunacode.configuration.user_config import validate_user_config
+
+from tunacode.infrastructure.cache.caches import limits_settings as limits_settings_cache
+
+LOCAL_MODEL = "lmstudio:qwen2.5-coder-7b"
+
+
+def _config_with(**settings: object) -> dict[str, object]:
+    config = copy.deepcopy(DEFAULT_USER_CONFIG)
+    config["settings"].update(settings)  # type: ignore[typeddict-item]
+    return config  # type: ignore[return-value]
+
+
+def test_model_limits_override_registry_and_global_values() -> None:
+    config = validate_user_config(
+        _config_with(
+            max_tokens=2048,
+            model_limits={LOCAL_MODEL: {"context_window": 32_768, "max_tokens": 4096}},
+        )
+    )
+    limits_settings_cache.set_settings(config["settings"])
+    try:
+        assert get_model_context_window(LOCAL_MODEL) == 32_768
+        assert limits.get_max_tokens(LOCAL_MODEL) == 4096
+        assert limits.get_max_tokens("openai:gpt-4.1") == 2048
+        assert limits.get_max_tokens() == 2048
+        assert limits.get_context_window_override("openai:gpt-4.1") is None
+    finally:
+        limits.clear_cache()
+
+
+@pytest.mark.parametrize(
+    ("settings", "message"),
+    [
+        (
+            {"model_limits": {LOCAL_MODEL: {"context_window": 8192, "max_tokens": 8192}}},
+            "max_tokens (8192) must be less than context_window (8192)",
+        ),
+        (
+            {"max_tokens": 16_384, "model_limits": {LOCAL_MODEL: {"context_window": 8192}}},
+            "max_tokens (16384) must be less than context_window (8192)",
+        ),
+        (
+            {"model_limits": {"qwen2.5-coder-7b": {"context_window": 8192}}},
+            "keys must be 'provider:model'",
+        ),
+        (
+            {"model_limits": {LOCAL_MODEL: {"context_window": 0}}},
+            "context_window must be > 0, got 0",
+        ),
+    ],
+)
+def test_invalid_model_limits_are_rejected(settings: dict[str, object], message: str) -> None:
+    with pytest.raises(ValueError, match=re.escape(message)):
+        validate_user_config(_config_with(**settings))
@@ -0,0 +1,38 @@
+"""Tests for inferring the provider of a bare model id from its base URL."""
+
+from __future__ import annotations
+
+import pytest
+
+from tunacode.configuration.models import detect_provider_from_base_url, qualify_model_string
+
+
+@pytest.mark.parametrize(
+    ("base_url", "expected"),
+    [
+        ("https://api.openai.com/v1", "openai"),
+        ("https://API.Anthropic.com/v1/", "anthropic"),
+        ("https://openrouter.ai/api/v1", "openrouter"),
+        ("https://eu.api.groq.com/openai/v1", "groq"),
+        ("http://localhost:11434/v1", "ollama"),
+        ("http://127.0.0.1:1234/v1", "lmstudio"),
+        ("http://localhost:8000/v1", None),
+        ("https://notapi.openai.com.example.net/v1", None),
+        ("https://gateway.internal/v1", None),
+        ("http://localhost:notaport/v1", None),
+        ("", None),
+        (None, None),
+    ],
+)
+def test_detect_provider_from_base_url_host(base_url: str | None, expected: str | None) -> None:
+    assert detect_provider_from_base_url(base_url) == expected
+
+
+def test_explicit_provider_overrides_detection() -> None:
+    base_url = "https://api.openai.com/v1"
+
+    assert qualify_model_string("gpt-4.1", base_url) == "openai:gpt-4.1"
+    assert qualify_model_string("openrouter:openai/gpt-4.1", base_url) == (
+        "openrouter:openai/gpt-4.1"
+    )
+    assert qualify_model_string("gpt-4.1", "https://gateway.internal/v1") == "gpt-4.1"
@@ -109,7 +109,7 @@ def test_get_or_create_agent_does_not_reuse_module_cached_agent_across_sessions(
     )
     monkeypatch.setattr(agent_config, "load_tunacode_context", lambda: "CONTEXT")
     monkeypatch.setattr(agent_config, "load_models_registry", lambda: None)
-    monkeypatch.setattr(agent_config, "get_max_tokens", lambda: 4096)
+    monkeypatch.setattr(agent_config, "get_max_tokens", lambda _model=None: 4096)
     monkeypatch.setattr(agent_config, "_build_tools", lambda **kwargs: [])
     monkeypatch.setattr(agent_config, "_build_tinyagent_model", lambda model, config: object())
 
@@ -36,7 +36,7 @@ def test_get_or_create_agent_emits_init_phase_lifecycle_logs(
     monkeypatch.setattr(agent_config, "resolve_selected_skills", lambda _names: [])
     monkeypatch.setattr(agent_config, "load_system_prompt", lambda _base_path, model=None: "SYS")
     monkeypatch.setattr(agent_config, "load_tunacode_context", lambda: "CTX")
-    monkeypatch.setattr(agent_config, "get_max_tokens", lambda: 4096)
+    monkeypatch.setattr(agent_config, "get_max_tokens", lambda _model=None: 4096)
     monkeypatch.setattr(agent_config, "_build_tools", lambda **kwargs: [])
     monkeypatch.setattr(agent_config, "_build_tinyagent_model", lambda model, config: object())
 
@@ -2,7 +2,13 @@ from __future__ import annotations
 
 from dataclasses import dataclass, field
 
-from tinyagent.agent_types import AssistantMessage, AssistantMessageEvent, SimpleStreamOptions
+from tinyagent.agent_types import (
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    SimpleStreamOptions,
+)
+from tinyagent.alchemy_provider import OpenAICompatModel
 
 from tunacode.core.agents.agent_components import agent_config
 
@@ -63,7 +69,11 @@ async def test_build_stream_fn_wraps_provider_response_with_debug_tracing(
     monkeypatch.setattr(agent_config.time, "perf_counter", lambda: next(perf_counter_values))
 
     stream_fn = agent_config._build_stream_fn(request_delay=0.0, max_tokens=None, max_retries=1)
-    response = await stream_fn(model=object(), context=object(), options=SimpleStreamOptions())
+    response = await stream_fn(
+        model=OpenAICompatModel(provider="openrouter", id="openai/gpt-4.1"),
+        context=Context(system_prompt="SYS", messages=[], tools=None),
+        options=SimpleStreamOptions(),
+    )
 
     first_event = await response.__anext__()
     second_event = await response.__anext__()
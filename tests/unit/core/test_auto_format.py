@@ -0,0 +1,117 @@
+"""Tests for formatting the files a turn edited."""
+
+from __future__ import annotations
+
+import shlex
+import sys
+from pathlib import Path
+
+from tunacode.types import AutoFormatSettings
+from tunacode.utils.system.process_group import AGENT_TURN_SCOPE
+
+from tunacode.tools import edit_journal, line_cache
+from tunacode.tools.hashline import HashedLine
+from tunacode.tools.utils.file_transaction import FileTransaction
+
+from tunacode.core.agents.agent_components.auto_format import (
+    format_turn_edits,
+    select_formatter_files,
+)
+
+UPPERCASE_SCRIPT = (
+    "import sys, pathlib\n"
+    "for name in sys.argv[1:]:\n"
+    "    path = pathlib.Path(name)\n"
+    "    path.write_text(path.read_text().upper())\n"
+)
+
+
+def _command(script: str) -> str:
+    return f"{shlex.quote(sys.executable)} -c {shlex.quote(script)}"
+
+
+def _settings(formatters: dict[str, str], timeout: float = 10.0) -> AutoFormatSettings:
+    return AutoFormatSettings(enabled=True, formatters=formatters, timeout=timeout)
+
+
+def _edit(path: Path, content: str) -> None:
+    transaction = FileTransaction()
+    transaction.stage(str(path), content)
+    transaction.commit()
+
+
+async def test_only_files_the_turn_touched_are_formatted_and_journaled(tmp_path: Path) -> None:
+    edit_journal.clear()
+    untouched = tmp_path / "untouched.py"
+    untouched.write_text("keep me\n")
+    edited = tmp_path / "pkg" / "edited.py"
+    edited.parent.mkdir()
+    edited.write_text("original\n")
+    edit_journal.begin_turn()
+    _edit(edited, "changed\n")
+    line_cache.store(str(edited), [HashedLine(line_number=1, hash="ab", content="changed")])
+
+    report = await format_turn_edits(
+        _settings({"*.py": _command(UPPERCASE_SCRIPT)}), root=tmp_path.resolve()
+    )
+
+    assert report.warnings == []
+    assert report.formatted == [edited.resolve()]
+    assert edited.read_text() == "CHANGED\n"
+    assert untouched.read_text() == "keep me\n"
+    assert line_cache.get(str(edited)) is None
+    assert len(AGENT_TURN_SCOPE) == 1
+    AGENT_TURN_SCOPE.kill_all()
+
+    edit_journal.undo_last_turn()
+    assert edited.read_text() == "original\n"
+
+
+async def test_failing_formatter_warns_without_raising(tmp_path: Path) -> None:
+    edit_journal.clear()
+    target = tmp_path / "main.rs"
+    edit_journal.begin_turn()
+    _edit(target, "fn main() {}\n")
+    _edit(tmp_path / "README.md", "# hi\n")
+    failing = _command("import sys; sys.stderr.write('syntax error'); sys.exit(2)")
+
+    report = await format_turn_edits(
+        _settings({"*.rs": failing, "*.md": "no-such-formatter-binary --write"}),
+        root=tmp_path.resolve(),
+    )
+    AGENT_TURN_SCOPE.kill_all()
+
+    assert report.formatted == []
+    assert report.warnings[0].endswith("exited with 2: syntax error")
+    assert report.warnings[1].startswith("Auto-format: `no-such-formatter-binary --write` could")
+    assert target.read_text() == "fn main() {}\n"
+
+
+async def test_slow_formatter_is_killed_at_the_timeout(tmp_path: Path) -> None:
+    edit_journal.clear()
+    target = tmp_path / "slow.py"
+    edit_journal.begin_turn()
+    _edit(target, "x = 1\n")
+
+    report = await format_turn_edits(
+        _settings({"*.py": _command("import time; time.sleep(30)")}, timeout=0.2),
+        root=tmp_path.resolve(),
+    )
+    AGENT_TURN_SCOPE.kill_all()
+
+    assert report.warnings[0].endswith("timed out after 0.2s")
+
+
+def test_first_matching_pattern_wins_and_outside_paths_are_skipped(tmp_path: Path) -> None:
+    inside = tmp_path / "web" / "app.ts"
+    inside.parent.mkdir()
+    inside.write_text("")
+    outside = tmp_path.parent / "elsewhere.ts"
+
+    selected = select_formatter_files(
+        [inside, outside, tmp_path / "deleted.ts"],
+        {"web/*.ts": "prettier --write", "*.ts": "other"},
+        root=tmp_path,
+    )
+
+    assert selected == {"prettier --write": [inside]}
@@ -0,0 +1,236 @@
+"""Tests for resumable background responses and their normal-streaming fallback."""
+
+from __future__ import annotations
+
+import json
+from collections.abc import AsyncIterator
+
+import httpx
+import pytest
+from tinyagent.agent_types import (
+    AssistantMessage,
+    Context,
+    SimpleStreamOptions,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.core.agents.agent_components import background_responses
+from tunacode.core.agents.agent_components.background_responses import (
+    BackgroundResponseError,
+    build_request_input,
+    open_background_response,
+    with_background_responses,
+)
+
+OPENAI_MODEL = OpenAICompatModel(
+    provider="openai", id="gpt-5", base_url="https://api.openai.com/v1/chat/completions"
+)
+LOCAL_MODEL = OpenAICompatModel(
+    provider="ollama", id="qwen", base_url="http://localhost:11434/v1/chat/completions"
+)
+USAGE = {"input_tokens": 12, "output_tokens": 3, "total_tokens": 15}
+
+
+def _event(sequence: int, event_type: str, **fields: object) -> dict[str, object]:
+    return {"type": event_type, "sequence_number": sequence, **fields}
+
+
+def _sse(events: list[dict[str, object]]) -> bytes:
+    return "".join(f"data: {json.dumps(event)}\n\n" for event in events).encode()
+
+
+class _DroppedStream(httpx.AsyncByteStream):
+    """Serves ``body`` and then fails the way a reset connection does."""
+
+    def __init__(self, body: bytes) -> None:
+        self._body = body
+
+    async def __aiter__(self) -> AsyncIterator[bytes]:
+        yield self._body
+        raise httpx.ReadError("connection reset by peer")
+
+
+def _transport(
+    requests: list[httpx.Request], responses: list[httpx.Response]
+) -> httpx.MockTransport:
+    def _handle(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return responses.pop(0)
+
+    return httpx.MockTransport(_handle)
+
+
+def _isolate(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(background_responses, "_unsupported_base_urls", set())
+    monkeypatch.setattr(background_responses, "RECONNECT_DELAY_SECONDS", 0.0)
+
+
+def _context() -> Context:
+    return Context(
+        system_prompt="Be brief.",
+        messages=[UserMessage(content=[TextContent(text="hi")], timestamp=None)],
+    )
+
+
+async def test_dropped_stream_resumes_after_last_sequence_without_duplicates(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    requests: list[httpx.Request] = []
+    created = {"id": "resp_1", "status": "queued"}
+    first = _sse(
+        [
+            _event(0, "response.created", response=created),
+            _event(1, "response.output_text.delta", delta="Hel"),
+            _event(2, "response.output_text.delta", delta="lo"),
+        ]
+    )
+    resumed = _sse(
+        [
+            _event(2, "response.output_text.delta", delta="lo"),
+            _event(3, "response.output_text.delta", delta=" world"),
+            _event(4, "response.completed", response={"id": "resp_1", "usage": USAGE}),
+        ]
+    )
+    transport = _transport(
+        requests,
+        [httpx.Response(200, stream=_DroppedStream(first)), httpx.Response(200, content=resumed)],
+    )
+
+    stream = await open_background_response(
+        OPENAI_MODEL,
+        _context(),
+        SimpleStreamOptions(api_key="sk-test"),
+        max_reconnects=2,
+        transport=transport,
+    )
+    assert stream is not None
+    deltas = [event.delta async for event in stream if event.type == "text_delta"]
+    message = await stream.result()
+
+    assert deltas == ["Hel", "lo", " world"]
+    assert message.content[0].text == "Hello world"
+    assert message.stop_reason == "stop"
+    assert message.usage["input"] == 12
+    assert stream.reconnects == 1
+    body = json.loads(requests[0].content)
+    assert (body["background"], body["stream"], body["instructions"]) == (True, True, "Be brief.")
+    assert requests[1].method == "GET"
+    assert requests[1].url.path == "/v1/responses/resp_1"
+    assert requests[1].url.params["starting_after"] == "2"
+
+
+async def test_stream_gives_up_after_max_reconnects(monkeypatch: pytest.MonkeyPatch) -> None:
+    _isolate(monkeypatch)
+    started = _sse([_event(0, "response.created", response={"id": "resp_2"})])
+    transport = _transport(
+        [],
+        [
+            httpx.Response(200, stream=_DroppedStream(started)),
+            httpx.Response(200, stream=_DroppedStream(b"")),
+        ],
+    )
+
+    stream = await open_background_response(
+        OPENAI_MODEL, _context(), SimpleStreamOptions(), max_reconnects=1, transport=transport
+    )
+    assert stream is not None
+
+    with pytest.raises(BackgroundResponseError, match=r"resp_2 stream lost after 1 reconnect"):
+        await stream.result()
+
+
+async def test_function_calls_end_the_response_as_tool_calls() -> None:
+    call = {"type": "function_call", "call_id": "call_1", "name": "grep", "arguments": '{"p": 1}'}
+    body = _sse(
+        [
+            _event(0, "response.created", response={"id": "resp_3"}),
+            _event(1, "response.output_item.done", item=call),
+            _event(2, "response.completed", response={"id": "resp_3", "usage": USAGE}),
+        ]
+    )
+    stream = await open_background_response(
+        OPENAI_MODEL,
+        _context(),
+        SimpleStreamOptions(),
+        max_reconnects=0,
+        transport=_transport([], [httpx.Response(200, content=body)]),
+    )
+    assert stream is not None
+
+    message = await stream.result()
+
+    assert message.stop_reason == "tool_calls"
+    assert message.content == [ToolCallContent(id="call_1", name="grep", arguments={"p": 1})]
+
+
+async def test_failed_response_raises_with_provider_message() -> None:
+    failed = {"id": "resp_4", "error": {"message": "server overloaded"}}
+    body = _sse([_event(0, "response.failed", response=failed)])
+    stream = await open_background_response(
+        OPENAI_MODEL,
+        _context(),
+        SimpleStreamOptions(),
+        max_reconnects=0,
+        transport=_transport([], [httpx.Response(200, content=body)]),
+    )
+    assert stream is not None
+
+    with pytest.raises(BackgroundResponseError, match="server overloaded"):
+        await stream.result()
+
+
+async def test_rejected_background_request_falls_back_and_is_remembered(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    requests: list[httpx.Request] = []
+    fallback_calls: list[str] = []
+
+    async def _normal_stream(model, context, options):
+        fallback_calls.append(model.id)
+        return "normal-stream"
+
+    stream_fn = with_background_responses(
+        _normal_stream,
+        max_reconnects=1,
+        transport=_transport(requests, [httpx.Response(400, json={"error": "no background"})]),
+    )
+
+    first = await stream_fn(OPENAI_MODEL, _context(), SimpleStreamOptions())
+    second = await stream_fn(OPENAI_MODEL, _context(), SimpleStreamOptions())
+    local = await stream_fn(LOCAL_MODEL, _context(), SimpleStreamOptions())
+
+    assert (first, second, local) == ("normal-stream", "normal-stream", "normal-stream")
+    assert len(requests) == 1
+    assert fallback_calls == ["gpt-5", "gpt-5", "qwen"]
+
+
+def test_request_input_replays_tool_calls_and_results() -> None:
+    context = Context(
+        messages=[
+            UserMessage(content=[TextContent(text="find it")], timestamp=None),
+            AssistantMessage(
+                content=[ToolCallContent(id="c1", name="grep", arguments={"pattern": "x"})],
+                stop_reason="tool_calls",
+                timestamp=None,
+            ),
+            ToolResultMessage(
+                tool_call_id="c1",
+                tool_name="grep",
+                content=[TextContent(text="a.py:1")],
+                is_error=False,
+                timestamp=None,
+            ),
+        ]
+    )
+
+    assert build_request_input(context) == [
+        {"role": "user", "content": [{"type": "input_text", "text": "find it"}]},
+        {"type": "function_call", "call_id": "c1", "name": "grep", "arguments": '{"pattern": "x"}'},
+        {"type": "function_call_output", "call_id": "c1", "output": "a.py:1"},
+    ]
@@ -28,3 +28,17 @@ def test_is_context_overflow_error_accepts_supported_error_forms(error_text: str
 )
 def test_is_context_overflow_error_rejects_non_overflow_errors(error_text: str) -> None:
     assert not is_context_overflow_error(error_text)
+
+
+@pytest.mark.parametrize(
+    ("provider", "error_text"),
+    [
+        ("anthropic", "prompt is too long: 210511 tokens > 200000 maximum"),
+        ("gemini", "The input token count (1200000) exceeds the maximum number of tokens allowed"),
+        ("mistral", "Prompt contains 40000 tokens, too large for model with 32768 maximum"),
+        ("ollama", "the request exceeds the available context size, try increasing it"),
+    ],
+)
+def test_is_context_overflow_error_matches_provider_wording(provider: str, error_text: str) -> None:
+    assert is_context_overflow_error(error_text, provider)
+    assert not is_context_overflow_error(error_text, "openai")
@@ -0,0 +1,107 @@
+"""Tests for compacting and retrying a turn the provider rejected as too long."""
+
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+from tinyagent.agent_types import AgentMessage, TextContent, UserMessage
+
+from tunacode.exceptions import ContextOverflowError
+
+from tunacode.core.agents.helpers import (
+    CONTEXT_OVERFLOW_AUTO_COMPACT_OFF_NOTICE,
+    CONTEXT_OVERFLOW_RETRY_NOTICE,
+)
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.compaction.types import KEY_OVERFLOW_RETRY_COUNT, CompactionRecord
+from tunacode.core.session import StateManager
+
+OVERFLOW_ERROR = "prompt is too long: 210511 tokens > 200000 maximum"
+
+
+def _orchestrator(*, auto_compact: bool, notices: list[str]) -> RequestOrchestrator:
+    state_manager = StateManager()
+    state_manager.session.user_config["settings"]["auto_compact"] = auto_compact
+    return RequestOrchestrator(
+        message="test",
+        model="anthropic:claude-sonnet-4",
+        state_manager=state_manager,
+        streaming_callback=None,
+        notice_callback=notices.append,
+    )
+
+
+def _history(count: int) -> list[AgentMessage]:
+    return [
+        UserMessage(content=[TextContent(text=f"m{index}")], timestamp=None)
+        for index in range(count)
+    ]
+
+
+def _record() -> CompactionRecord:
+    return CompactionRecord(
+        summary="earlier work",
+        compacted_message_count=3,
+        tokens_before=900,
+        tokens_after=100,
+        compaction_count=1,
+        previous_summary=None,
+        last_compacted_at="2026-01-01T00:00:00+00:00",
+    )
+
+
+async def test_overflow_compacts_retries_once_and_records_it(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    notices: list[str] = []
+    orchestrator = _orchestrator(auto_compact=True, notices=notices)
+    session = orchestrator.state_manager.session
+    replaced: list[list[AgentMessage]] = []
+    agent = SimpleNamespace(
+        state=SimpleNamespace(error=OVERFLOW_ERROR), replace_messages=replaced.append
+    )
+
+    async def _compact(history: list[AgentMessage]) -> list[AgentMessage]:
+        session.compaction = _record()
+        return history[-1:]
+
+    async def _retry(*, agent: SimpleNamespace, baseline_message_count: int) -> None:
+        _ = baseline_message_count
+        agent.state.error = None
+
+    monkeypatch.setattr(orchestrator, "_force_compact_history", _compact)
+    monkeypatch.setattr(orchestrator, "_run_stream", _retry)
+
+    await orchestrator._retry_after_context_overflow_if_needed(
+        agent=agent, pre_request_history=_history(4)
+    )
+
+    assert notices == [CONTEXT_OVERFLOW_RETRY_NOTICE]
+    assert [len(messages) for messages in replaced] == [1]
+    assert session.compaction is not None
+    assert session.compaction.overflow_retry_count == 1
+    persisted = session.compaction.to_dict()
+    assert CompactionRecord.from_dict(persisted).overflow_retry_count == 1
+    del persisted[KEY_OVERFLOW_RETRY_COUNT]
+    assert CompactionRecord.from_dict(persisted).overflow_retry_count == 0
+
+
+async def test_overflow_without_auto_compact_fails_without_retrying(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    notices: list[str] = []
+    orchestrator = _orchestrator(auto_compact=False, notices=notices)
+    agent = SimpleNamespace(state=SimpleNamespace(error=OVERFLOW_ERROR))
+
+    async def _unexpected(*args: object, **kwargs: object) -> None:
+        raise AssertionError("must not compact or retry")
+
+    monkeypatch.setattr(orchestrator, "_force_compact_history", _unexpected)
+    monkeypatch.setattr(orchestrator, "_run_stream", _unexpected)
+
+    with pytest.raises(ContextOverflowError):
+        await orchestrator._retry_after_context_overflow_if_needed(
+            agent=agent, pre_request_history=_history(2)
+        )
+    assert notices == [CONTEXT_OVERFLOW_AUTO_COMPACT_OFF_NOTICE]
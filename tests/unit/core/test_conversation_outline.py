@@ -0,0 +1,125 @@
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.core.session.outline import (
+    ConversationOutline,
+    request_snippet,
+    summarize_turns,
+)
+
+
+def _user(text: str) -> UserMessage:
+    return UserMessage(content=[TextContent(text=text)], timestamp=None)
+
+
+def _call(call_id: str, name: str, arguments: dict[str, object]) -> AssistantMessage:
+    return AssistantMessage(
+        content=[ToolCallContent(id=call_id, name=name, arguments=arguments)],
+        stop_reason="tool_calls",
+        timestamp=None,
+    )
+
+
+def _result(call_id: str, name: str, *, is_error: bool = False) -> ToolResultMessage:
+    return ToolResultMessage(
+        tool_call_id=call_id,
+        tool_name=name,
+        content=[TextContent(text="ok")],
+        is_error=is_error,
+        timestamp=None,
+    )
+
+
+def _answer(text: str, stop_reason: str = "stop") -> AssistantMessage:
+    return AssistantMessage(
+        content=[TextContent(text=text)], stop_reason=stop_reason, timestamp=None
+    )
+
+
+def _edit_turn() -> list[AgentMessage]:
+    return [
+        _user("fix the failing test\nmore detail"),
+        _call("c1", "read_file", {"filepath": "a.py"}),
+        _result("c1", "read_file"),
+        _call("c2", "hashline_edit", {"filepath": "a.py"}),
+        _result("c2", "hashline_edit"),
+        _call("c3", "write_file", {"filepath": "b.py"}),
+        _result("c3", "write_file", is_error=True),
+        _answer("Fixed."),
+    ]
+
+
+def test_summarize_turns_lists_request_tools_files_and_outcome() -> None:
+    messages = [*_edit_turn(), _user("thanks"), _answer("", stop_reason="error")]
+
+    first, second = summarize_turns(messages)
+
+    assert first.turn_id == 0
+    assert (first.first_message, first.end_message) == (0, 8)
+    assert first.request == "fix the failing test"
+    assert first.tools == ("read_file", "hashline_edit", "write_file")
+    assert first.files_changed == ("a.py",)
+    assert first.tool_errors == 1
+    assert first.outcome == "answered"
+    assert second.turn_id == 1
+    assert second.first_message == 8
+    assert second.outcome == "error"
+
+
+def test_turn_waiting_on_tools_is_pending() -> None:
+    messages = [_user("look around"), _call("c1", "grep", {"pattern": "x"})]
+
+    (turn,) = summarize_turns(messages)
+
+    assert turn.outcome == "pending"
+
+
+def test_render_line_is_one_line() -> None:
+    (turn,) = summarize_turns(_edit_turn())
+
+    line = turn.render_line()
+
+    assert "\n" not in line
+    assert line.startswith("#1 fix the failing test")
+    assert "files: a.py" in line
+    assert line.endswith("answered")
+
+
+def test_request_snippet_truncates_long_requests() -> None:
+    snippet = request_snippet("x" * 200, max_chars=20)
+
+    assert len(snippet) == 20
+    assert snippet.endswith("...")
+
+
+def test_outline_update_only_resummarizes_the_last_turn() -> None:
+    messages: list[AgentMessage] = [_user("look around"), _call("c1", "grep", {"pattern": "x"})]
+    outline = ConversationOutline()
+
+    (pending,) = outline.update(messages)
+    messages.extend([_result("c1", "grep"), _answer("Found it."), _user("next")])
+    finished, started = outline.update(messages)
+
+    assert pending.outcome == "pending"
+    assert finished.outcome == "answered"
+    assert finished.end_message == 4
+    assert started.turn_id == 1
+    assert started.outcome == "pending"
+
+
+def test_outline_rebuilds_after_history_is_rewritten() -> None:
+    outline = ConversationOutline()
+    outline.update([*_edit_turn(), _user("second")])
+
+    turns = outline.update([_user("after clear")])
+
+    assert [turn.request for turn in turns] == ["after clear"]
+    assert turns[0].turn_id == 0
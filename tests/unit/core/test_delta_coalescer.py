@@ -0,0 +1,100 @@
+from __future__ import annotations
+
+from tinyagent.agent_types import AssistantMessageEvent, MessageUpdateEvent
+
+from tunacode.core.agents.agent_components.agent_streaming import THINKING_SEGMENT_SEPARATOR
+from tunacode.core.agents.agent_components.delta_coalescer import (
+    TextDeltaCoalescer,
+    build_text_coalescer,
+)
+from tunacode.core.agents.helpers import _TinyAgentStreamState
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.session import StateManager
+
+
+class _Clock:
+    def __init__(self) -> None:
+        self.now = 0.0
+
+    def __call__(self) -> float:
+        return self.now
+
+
+def _update(event_type: str, delta: str) -> MessageUpdateEvent:
+    return MessageUpdateEvent(
+        assistant_message_event=AssistantMessageEvent(type=event_type, delta=delta)
+    )
+
+
+async def test_batches_by_char_count_and_window_without_changing_text() -> None:
+    emitted: list[str] = []
+
+    async def _emit(text: str) -> None:
+        emitted.append(text)
+
+    clock = _Clock()
+    coalescer = TextDeltaCoalescer(_emit, window_ms=50, max_chars=6, clock=clock)
+    deltas = ["He", "llo", " w", "or", "ld", "!"]
+
+    for delta in deltas[:3]:
+        await coalescer.push(delta)
+    clock.now = 0.01
+    await coalescer.push(deltas[3])
+    clock.now = 0.07
+    await coalescer.push(deltas[4])
+    await coalescer.push(deltas[5])
+    await coalescer.flush()
+
+    assert emitted == ["Hello w", "orld", "!"]
+    assert "".join(emitted) == "".join(deltas)
+
+
+def test_coalescing_is_off_by_default() -> None:
+    async def _emit(text: str) -> None:
+        _ = text
+
+    assert build_text_coalescer(_emit, window_ms=0, max_chars=0) is None
+    assert build_text_coalescer(None, window_ms=50, max_chars=0) is None
+
+
+async def test_thinking_delta_flushes_pending_text_first() -> None:
+    rendered: list[tuple[str, str]] = []
+
+    async def _text(chunk: str) -> None:
+        rendered.append(("text", chunk))
+
+    async def _thinking(chunk: str) -> None:
+        rendered.append(("thinking", chunk))
+
+    state_manager = StateManager()
+    orchestrator = RequestOrchestrator(
+        message="test",
+        model="openai/gpt-4o",
+        state_manager=state_manager,
+        streaming_callback=_text,
+        thinking_callback=_thinking,
+    )
+    state = _TinyAgentStreamState(
+        runtime=state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+        text_coalescer=build_text_coalescer(_text, window_ms=0, max_chars=1_000),
+    )
+
+    for event in [_update("text_delta", "Let me "), _update("text_delta", "check. ")]:
+        await orchestrator._dispatch_stream_event(
+            event=event, agent=None, state=state, baseline_message_count=0
+        )
+    assert rendered == []
+
+    await orchestrator._dispatch_stream_event(
+        event=_update("thinking_delta", "hmm"), agent=None, state=state, baseline_message_count=0
+    )
+
+    assert rendered == [
+        ("text", "Let me check. "),
+        ("thinking", f"{THINKING_SEGMENT_SEPARATOR}hmm"),
+    ]
+    assert state_manager.session._debug_raw_stream_accum == "Let me check. "
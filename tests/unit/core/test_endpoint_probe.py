@@ -0,0 +1,74 @@
+"""Tests for probing a user-supplied base URL before the first turn."""
+
+from __future__ import annotations
+
+import httpx
+import pytest
+
+from tunacode.exceptions import ConfigurationError
+
+from tunacode.core.agents.agent_components.endpoint_probe import probe_base_url
+
+
+def _transport(requests: list[httpx.Request], response: httpx.Response) -> httpx.MockTransport:
+    def _handle(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return response
+
+    return httpx.MockTransport(_handle)
+
+
+async def test_probe_lists_served_models_and_detects_provider() -> None:
+    requests: list[httpx.Request] = []
+    body = {"data": [{"id": "qwen2.5-coder"}, {"id": "llama3.1"}]}
+
+    probe = await probe_base_url(
+        "http://localhost:11434/v1/chat/completions",
+        probe_path="/models",
+        api_key="sk-local",
+        transport=_transport(requests, httpx.Response(200, json=body)),
+    )
+
+    assert str(requests[0].url) == "http://localhost:11434/v1/models"
+    assert requests[0].headers["Authorization"] == "Bearer sk-local"
+    assert probe.provider_id == "ollama"
+    assert probe.model_ids == ("qwen2.5-coder", "llama3.1")
+    assert probe.lists_models is True
+
+
+async def test_probe_reads_ollama_tags_from_a_custom_path() -> None:
+    requests: list[httpx.Request] = []
+    body = {"models": [{"name": "llama3.1:8b"}]}
+
+    probe = await probe_base_url(
+        "http://localhost:11434",
+        probe_path="/api/tags",
+        transport=_transport(requests, httpx.Response(200, json=body)),
+    )
+
+    assert str(requests[0].url) == "http://localhost:11434/api/tags"
+    assert "Authorization" not in requests[0].headers
+    assert probe.model_ids == ("llama3.1:8b",)
+
+
+async def test_probe_treats_any_http_response_as_reachable() -> None:
+    probe = await probe_base_url(
+        "https://gateway.internal/v1",
+        probe_path="/models",
+        transport=_transport([], httpx.Response(404, text="not found")),
+    )
+
+    assert (probe.status_code, probe.model_ids, probe.lists_models) == (404, (), False)
+    assert probe.provider_id is None
+
+
+async def test_probe_raises_configuration_error_when_unreachable() -> None:
+    def _refuse(request: httpx.Request) -> httpx.Response:
+        raise httpx.ConnectError("Connection refused", request=request)
+
+    with pytest.raises(ConfigurationError, match="Cannot reach base URL http://localhost:9"):
+        await probe_base_url(
+            "http://localhost:9/v1",
+            probe_path="/models",
+            transport=httpx.MockTransport(_refuse),
+        )
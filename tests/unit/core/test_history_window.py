@@ -0,0 +1,83 @@
+"""Tests for sliding-window history as an alternative to compaction."""
+
+from __future__ import annotations
+
+import pytest
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.core.agents.agent_components.agent_config import _build_transform_context
+from tunacode.core.compaction.controller import CompactionController
+from tunacode.core.compaction.history_window import window_messages
+from tunacode.core.compaction.types import CompactionRecord
+from tunacode.core.session import StateManager
+
+
+def _turn(index: int) -> list[AgentMessage]:
+    call_id = f"call{index}"
+    return [
+        UserMessage(content=[TextContent(text=f"question {index}")], timestamp=None),
+        AssistantMessage(
+            content=[ToolCallContent(id=call_id, name="read_file", arguments={})],
+            stop_reason="tool_calls",
+            timestamp=None,
+        ),
+        ToolResultMessage(
+            tool_call_id=call_id,
+            tool_name="read_file",
+            content=[TextContent(text="contents")],
+            timestamp=None,
+        ),
+        AssistantMessage(
+            content=[TextContent(text=f"answer {index}")], stop_reason="stop", timestamp=None
+        ),
+    ]
+
+
+def _history(turns: int) -> list[AgentMessage]:
+    return [message for index in range(turns) for message in _turn(index)]
+
+
+def test_window_keeps_whole_turns_from_the_last_user_messages() -> None:
+    history = _history(4)
+
+    windowed = window_messages(history, 2)
+
+    assert windowed == history[8:]
+    assert window_messages(history, 10) == history
+
+
+async def test_window_mode_skips_compaction_and_pins_the_summary(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    session = state_manager.session
+    session.user_config["settings"]["history"] = {"mode": "window", "window_turns": 1}
+    session.conversation.messages = _history(3)
+    session.compaction = CompactionRecord(
+        summary="earlier work",
+        compacted_message_count=4,
+        tokens_before=500,
+        tokens_after=100,
+        compaction_count=1,
+        previous_summary=None,
+        last_compacted_at="2026-01-01T00:00:00+00:00",
+    )
+
+    async def _no_compaction(self: CompactionController, *args: object, **kwargs: object) -> None:
+        raise AssertionError("window mode must not summarize")
+
+    monkeypatch.setattr(CompactionController, "check_and_compact", _no_compaction)
+    transform = _build_transform_context(state_manager)
+
+    request = await transform(session.conversation.messages, None)
+
+    assert "earlier work" in request[0].content[0].text
+    assert request[1:] == _history(3)[8:]
+    assert len(session.conversation.messages) == 12
@@ -0,0 +1,123 @@
+"""Tests for continuing background responses with ``previous_response_id``."""
+
+from __future__ import annotations
+
+import json
+
+import httpx
+import pytest
+from tinyagent.agent_types import (
+    AgentMessage,
+    Context,
+    SimpleStreamOptions,
+    TextContent,
+    UserMessage,
+)
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.core.agents.agent_components import background_responses
+from tunacode.core.agents.agent_components.background_responses import with_background_responses
+
+OPENAI_MODEL = OpenAICompatModel(
+    provider="openai", id="gpt-5", base_url="https://api.openai.com/v1/chat/completions"
+)
+
+
+def _user(text: str) -> UserMessage:
+    return UserMessage(content=[TextContent(text=text)], timestamp=None)
+
+
+def _completed(response_id: str, text: str) -> httpx.Response:
+    events = [
+        {"type": "response.created", "sequence_number": 0, "response": {"id": response_id}},
+        {"type": "response.output_text.delta", "sequence_number": 1, "delta": text},
+        {"type": "response.completed", "sequence_number": 2, "response": {"id": response_id}},
+    ]
+    body = "".join(f"data: {json.dumps(event)}\n\n" for event in events)
+    return httpx.Response(200, content=body.encode())
+
+
+class _Provider:
+    """Answers each background request with the next queued response."""
+
+    def __init__(self, responses: list[httpx.Response]) -> None:
+        self.bodies: list[dict[str, object]] = []
+        self._responses = responses
+
+    def handle(self, request: httpx.Request) -> httpx.Response:
+        self.bodies.append(json.loads(request.content))
+        return self._responses.pop(0)
+
+
+async def _normal_stream(model, context, options):
+    raise AssertionError("the chat-completions stream must not be used")
+
+
+async def _turn(stream_fn, history: list[AgentMessage]) -> None:
+    context = Context(system_prompt="SYS", messages=history)
+    response = await stream_fn(OPENAI_MODEL, context, SimpleStreamOptions(api_key="sk-test"))
+    history.append(await response.result())
+
+
+def _stream_fn(provider: _Provider, monkeypatch: pytest.MonkeyPatch):
+    monkeypatch.setattr(background_responses, "_unsupported_base_urls", set())
+    return with_background_responses(
+        _normal_stream, max_reconnects=0, transport=httpx.MockTransport(provider.handle)
+    )
+
+
+async def test_next_turn_sends_only_new_messages_after_the_stored_response(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    provider = _Provider([_completed("resp_1", "a1"), _completed("resp_2", "a2")])
+    stream_fn = _stream_fn(provider, monkeypatch)
+    history: list[AgentMessage] = [_user("one")]
+
+    await _turn(stream_fn, history)
+    history.append(_user("two"))
+    await _turn(stream_fn, history)
+
+    first, second = provider.bodies
+    assert "previous_response_id" not in first
+    assert len(first["input"]) == 1
+    assert second["previous_response_id"] == "resp_1"
+    assert second["input"] == [{"role": "user", "content": [{"type": "input_text", "text": "two"}]}]
+    assert second["instructions"] == "SYS"
+
+
+async def test_rewritten_history_forces_a_full_resend(monkeypatch: pytest.MonkeyPatch) -> None:
+    provider = _Provider([_completed("resp_1", "a1"), _completed("resp_2", "a2")])
+    stream_fn = _stream_fn(provider, monkeypatch)
+    history: list[AgentMessage] = [_user("one")]
+    await _turn(stream_fn, history)
+
+    compacted: list[AgentMessage] = [_user("summary"), _user("kept"), _user("two")]
+    await _turn(stream_fn, compacted)
+
+    resent = provider.bodies[1]
+    assert "previous_response_id" not in resent
+    assert len(resent["input"]) == 3
+
+
+async def test_rejected_continuation_resends_the_full_history(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    provider = _Provider(
+        [
+            _completed("resp_1", "a1"),
+            httpx.Response(400, json={"error": {"message": "previous response not found"}}),
+            _completed("resp_2", "a2"),
+        ]
+    )
+    stream_fn = _stream_fn(provider, monkeypatch)
+    history: list[AgentMessage] = [_user("one")]
+    await _turn(stream_fn, history)
+    history.append(_user("two"))
+
+    await _turn(stream_fn, history)
+
+    rejected, resent = provider.bodies[1:]
+    assert rejected["previous_response_id"] == "resp_1"
+    assert "previous_response_id" not in resent
+    assert len(resent["input"]) == 3
+    assert background_responses._unsupported_base_urls == set()
@@ -0,0 +1,202 @@
+from __future__ import annotations
+
+import asyncio
+from types import SimpleNamespace
+
+import pytest
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    AssistantMessage,
+    JsonObject,
+    MessageEndEvent,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    TurnEndEvent,
+    UserMessage,
+)
+
+from tunacode.types import ModelName, UsageMetrics
+
+from tunacode.core.agents.agent_components import turn_resume
+from tunacode.core.agents.agent_components.turn_resume import (
+    RESUME_CONTINUE_PROMPT,
+    prepare_turn_resume,
+)
+from tunacode.core.agents.helpers import (
+    _TinyAgentStreamState,
+    apply_user_message_affixes,
+    strip_user_message_affixes,
+)
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.agents.resume import InterruptedTurnKind, detect_interrupted_turn
+from tunacode.core.session import StateManager
+
+
+def _user(text: str) -> UserMessage:
+    return UserMessage(content=[TextContent(text=text)], timestamp=None)
+
+
+def _tool_turn(*call_ids: str, stop_reason: str = "tool_calls") -> AssistantMessage:
+    return AssistantMessage(
+        content=[
+            ToolCallContent(id=call_id, name="read_file", arguments={"filepath": f"{call_id}.py"})
+            for call_id in call_ids
+        ],
+        stop_reason=stop_reason,
+        timestamp=None,
+    )
+
+
+def _result(call_id: str) -> ToolResultMessage:
+    return ToolResultMessage(
+        tool_call_id=call_id,
+        tool_name="read_file",
+        content=[TextContent(text="done")],
+        timestamp=None,
+    )
+
+
+def test_detects_a_prompt_without_response() -> None:
+    turn = detect_interrupted_turn([_user("fix the bug")])
+
+    assert turn is not None
+    assert turn.kind is InterruptedTurnKind.NO_RESPONSE
+    assert turn.prompt == "fix the bug"
+
+
+def test_pending_tool_calls_list_only_calls_without_results() -> None:
+    turn = detect_interrupted_turn([_user("go"), _tool_turn("a", "b", "c"), _result("a")])
+
+    assert turn is not None
+    assert turn.kind is InterruptedTurnKind.PENDING_TOOL_CALLS
+    assert [call.id for call in turn.missing_tool_calls] == ["b", "c"]
+    assert turn.completed_tool_call_ids == ("a",)
+    assert turn.describe() == "2 of 3 tool call(s) never finished"
+
+    answered = detect_interrupted_turn([_user("go"), _tool_turn("a"), _result("a")])
+    assert answered is not None
+    assert answered.kind is InterruptedTurnKind.UNANSWERED_TOOL_RESULTS
+
+
+def test_completed_and_aborted_turns_are_not_reported() -> None:
+    reply = AssistantMessage(content=[TextContent(text="done")], stop_reason="stop", timestamp=None)
+
+    assert detect_interrupted_turn([]) is None
+    assert detect_interrupted_turn([_user("hi"), reply]) is None
+    assert detect_interrupted_turn([_user("hi"), _tool_turn("a", stop_reason="aborted")]) is None
+
+
+def test_strip_user_message_affixes_undoes_apply() -> None:
+    wrapped = apply_user_message_affixes("fix it", prefix="Be brief.", suffix="Thanks.")
+
+    assert strip_user_message_affixes(wrapped, prefix="Be brief.", suffix="Thanks.") == "fix it"
+    assert strip_user_message_affixes("fix it", prefix="", suffix="") == "fix it"
+
+
+@pytest.mark.asyncio
+async def test_prepare_turn_resume_runs_only_the_missing_tool_calls(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    executed: list[str] = []
+
+    async def _read(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        _ = (args, signal, on_update)
+        executed.append(tool_call_id)
+        return AgentToolResult(content=[TextContent(text="read")], details={})
+
+    tools = [AgentTool(name="read_file", label="read_file", execute=_read)]
+    agent = SimpleNamespace(state=SimpleNamespace(tools=tools))
+    monkeypatch.setattr(turn_resume, "get_or_create_agent", lambda _model, _sm: agent)
+    state_manager = StateManager()
+    conversation = state_manager.session.conversation
+    conversation.messages = [_user("go"), _tool_turn("a", "b"), _result("a")]
+
+    prompt = await prepare_turn_resume(ModelName("openai/gpt-4o"), state_manager)
+
+    assert prompt == RESUME_CONTINUE_PROMPT
+    assert executed == ["b"]
+    assert [
+        message.tool_call_id
+        for message in conversation.messages
+        if isinstance(message, ToolResultMessage)
+    ] == ["a", "b"]
+    resumed = detect_interrupted_turn(conversation.messages)
+    assert resumed is not None
+    assert resumed.kind is InterruptedTurnKind.UNANSWERED_TOOL_RESULTS
+
+
+@pytest.mark.asyncio
+async def test_prepare_turn_resume_resends_an_unanswered_prompt() -> None:
+    state_manager = StateManager()
+    settings = state_manager.session.user_config["settings"]
+    settings["user_message_prefix"] = "Be brief."
+    conversation = state_manager.session.conversation
+    wrapped = apply_user_message_affixes("fix it", prefix="Be brief.", suffix="")
+    conversation.messages = [_user(wrapped)]
+
+    assert await prepare_turn_resume(ModelName("openai/gpt-4o"), state_manager) == "fix it"
+    assert conversation.messages == []
+
+
+@pytest.mark.asyncio
+async def test_a_turn_is_checkpointed_per_assistant_message_and_tool_batch(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    state_manager.session.project_id = "project"
+    saved: list[int] = []
+
+    async def _save_session(messages: list[object]) -> bool:
+        saved.append(len(messages))
+        return True
+
+    monkeypatch.setattr(state_manager, "save_session", _save_session)
+    orchestrator = RequestOrchestrator(
+        message="go",
+        model="openai/gpt-4o",
+        state_manager=state_manager,
+        streaming_callback=None,
+    )
+    state = _TinyAgentStreamState(
+        runtime=state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+    )
+    history: list[object] = []
+    agent = SimpleNamespace(state=SimpleNamespace(messages=history))
+
+    async def _message_end(message: object) -> None:
+        history.append(message)
+        await orchestrator._handle_stream_message_end(
+            MessageEndEvent(message=message), agent=agent, state=state, baseline_message_count=0
+        )
+
+    async def _turn_end() -> None:
+        await orchestrator._handle_stream_turn_end(
+            TurnEndEvent(), agent=agent, state=state, baseline_message_count=0
+        )
+
+    usage = UsageMetrics().to_dict()
+    tool_turn = _tool_turn("a", "b")
+    tool_turn.usage = usage
+    answer = AssistantMessage(content=[TextContent(text="done")], usage=usage, timestamp=None)
+
+    await _message_end(_user("go"))
+    await _message_end(tool_turn)
+    await _message_end(_result("a"))
+    await _message_end(_result("b"))
+    await _turn_end()
+    await _message_end(answer)
+    await _turn_end()
+
+    assert saved == [2, 4, 5]
@@ -0,0 +1,100 @@
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AgentContext,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+)
+
+from tunacode.core.agents.agent_components.agent_turn_control import build_should_stop_after_turn
+from tunacode.core.agents.agent_components.loop_detection import LoopDetector, turn_signature
+from tunacode.core.session import StateManager
+
+
+def _turn(
+    name: str,
+    arguments: dict[str, object],
+    output: str,
+) -> tuple[AssistantMessage, list[ToolResultMessage]]:
+    message = AssistantMessage(
+        content=[ToolCallContent(id="call-1", name=name, arguments=arguments)],
+        stop_reason="tool_calls",
+        timestamp=None,
+    )
+    result = ToolResultMessage(
+        tool_call_id="call-1",
+        tool_name=name,
+        content=[TextContent(text=output)],
+        timestamp=None,
+    )
+    return message, [result]
+
+
+def test_turn_signature_ignores_argument_order_and_includes_result() -> None:
+    first = turn_signature(*_turn("grep", {"pattern": "x", "path": "."}, "a.py:1"))
+    reordered = turn_signature(*_turn("grep", {"path": ".", "pattern": "x"}, "a.py:1"))
+    changed = turn_signature(*_turn("grep", {"pattern": "x", "path": "."}, "a.py:2"))
+
+    assert first == reordered
+    assert first != changed
+
+
+def test_detector_trips_on_repeated_identical_calls() -> None:
+    detector = LoopDetector(threshold=3)
+    turn = _turn("read_file", {"filepath": "a.py"}, "same")
+
+    assert detector.observe(*turn) is None
+    assert detector.observe(*turn) is None
+    assert detector.observe(*turn) is not None
+    assert detector.observe(*turn) is None
+
+
+def test_detector_ignores_repeated_reads_of_a_changing_file() -> None:
+    detector = LoopDetector(threshold=3)
+
+    for version in range(6):
+        assert detector.observe(*_turn("read_file", {"filepath": "a.py"}, f"v{version}")) is None
+
+
+def test_detector_trips_on_two_state_cycle() -> None:
+    detector = LoopDetector(threshold=2)
+    turn_a = _turn("read_file", {"filepath": "a.py"}, "a")
+    turn_b = _turn("read_file", {"filepath": "b.py"}, "b")
+
+    assert detector.observe(*turn_a) is None
+    assert detector.observe(*turn_b) is None
+    assert detector.observe(*turn_a) is None
+    description = detector.observe(*turn_b)
+
+    assert description is not None
+    assert "alternated" in description
+
+
+def test_detector_is_disabled_with_zero_threshold() -> None:
+    detector = LoopDetector(threshold=0)
+    turn = _turn("bash", {"command": "ls"}, "out")
+
+    assert all(detector.observe(*turn) is None for _ in range(5))
+
+
+def test_turn_hook_nudges_or_halts_on_loop() -> None:
+    state_manager = StateManager()
+    settings = state_manager.session.user_config["settings"]
+    settings["loop_detection"] = {"threshold": 2, "action": "nudge"}
+    should_stop = build_should_stop_after_turn(state_manager.session)
+    message, results = _turn("bash", {"command": "ls"}, "out")
+
+    assert should_stop(message, results, AgentContext(), []) is False
+    assert should_stop(message, results, AgentContext(), []) is False
+    assert "[loop detector]" in results[-1].content[-1].text
+    assert state_manager.session.runtime.loop_detection
+
+    settings["loop_detection"] = {"threshold": 2, "action": "halt"}
+    state_manager.session.runtime.iteration_count = 0
+    should_stop = build_should_stop_after_turn(state_manager.session)
+    message, results = _turn("bash", {"command": "ls"}, "out")
+
+    assert should_stop(message, results, AgentContext(), []) is False
+    assert should_stop(message, results, AgentContext(), []) is True
@@ -0,0 +1,185 @@
+"""Tests for streaming through Ollama's native chat API."""
+
+from __future__ import annotations
+
+import copy
+import json
+
+import httpx
+import pytest
+from tinyagent.agent_types import (
+    AgentTool,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    TextContent,
+    UserMessage,
+)
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.configuration.user_config import validate_user_config
+
+from tunacode.core.agents.agent_components import ollama_native
+from tunacode.core.agents.agent_components.ollama_native import (
+    OllamaConfig,
+    OllamaModelNotPulledError,
+    open_ollama_chat,
+    with_ollama_native,
+)
+from tunacode.core.agents.agent_components.provider_error_hints import (
+    ProviderErrorKind,
+    match_provider_error,
+)
+
+OLLAMA_MODEL = OpenAICompatModel(
+    provider="ollama", id="qwen3", base_url="http://localhost:11434/v1/chat/completions"
+)
+CONFIG = OllamaConfig(keep_alive="30m", options=(("num_ctx", 8192), ("temperature", 0.2)))
+READ_TOOL = AgentTool(
+    name="read_file",
+    description="Read a file",
+    parameters={"type": "object", "properties": {"filepath": {"type": "string"}}},
+)
+
+
+def _ndjson(chunks: list[dict[str, object]]) -> bytes:
+    return "".join(f"{json.dumps(chunk)}\n" for chunk in chunks).encode()
+
+
+def _transport(
+    requests: list[httpx.Request], show: httpx.Response, chat: httpx.Response | None = None
+) -> httpx.MockTransport:
+    def _handle(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        if request.url.path == "/api/show":
+            return show
+        assert chat is not None
+        return chat
+
+    return httpx.MockTransport(_handle)
+
+
+def _context() -> Context:
+    return Context(
+        system_prompt="Be brief.",
+        messages=[UserMessage(content=[TextContent(text="read a.txt")], timestamp=None)],
+        tools=[READ_TOOL],
+    )
+
+
+def _isolate(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(ollama_native, "_capabilities", {})
+
+
+async def test_tool_calling_turn_streams_through_api_chat(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    requests: list[httpx.Request] = []
+    chat = _ndjson(
+        [
+            {"message": {"role": "assistant", "content": "Let me "}, "done": False},
+            {"message": {"role": "assistant", "content": "look."}, "done": False},
+            {
+                "message": {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [
+                        {"function": {"name": "read_file", "arguments": {"filepath": "a.txt"}}}
+                    ],
+                },
+                "done": False,
+            },
+            {"done": True, "done_reason": "stop", "prompt_eval_count": 40, "eval_count": 7},
+        ]
+    )
+    transport = _transport(
+        requests,
+        show=httpx.Response(200, json={"capabilities": ["completion", "tools"]}),
+        chat=httpx.Response(200, content=chat),
+    )
+
+    stream = await open_ollama_chat(
+        OLLAMA_MODEL, _context(), SimpleStreamOptions(), config=CONFIG, transport=transport
+    )
+    deltas = [event.delta async for event in stream if event.type == "text_delta"]
+    message = await stream.result()
+
+    assert [str(request.url) for request in requests] == [
+        "http://localhost:11434/api/show",
+        "http://localhost:11434/api/chat",
+    ]
+    body = json.loads(requests[1].content)
+    assert body["options"] == {"num_ctx": 8192, "temperature": 0.2}
+    assert body["keep_alive"] == "30m"
+    assert body["messages"][0] == {"role": "system", "content": "Be brief."}
+    assert body["tools"][0]["function"]["name"] == "read_file"
+    assert deltas == ["Let me ", "look."]
+    assert message.stop_reason == "tool_calls"
+    assert message.content[-1].name == "read_file"
+    assert message.content[-1].arguments == {"filepath": "a.txt"}
+    assert message.usage["input"] == 40 and message.usage["output"] == 7
+
+
+async def test_missing_model_is_reported_as_needing_a_pull(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    transport = _transport([], show=httpx.Response(404, json={"error": "model 'qwen3' not found"}))
+
+    with pytest.raises(OllamaModelNotPulledError, match="run `ollama pull qwen3`") as raised:
+        await open_ollama_chat(
+            OLLAMA_MODEL, _context(), SimpleStreamOptions(), config=CONFIG, transport=transport
+        )
+
+    hint = match_provider_error(str(raised.value), "ollama")
+    assert hint is not None and hint.kind is ProviderErrorKind.MODEL_NOT_PULLED
+
+
+async def test_tools_are_withheld_from_models_without_tool_support(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    requests: list[httpx.Request] = []
+    transport = _transport(
+        requests,
+        show=httpx.Response(200, json={"capabilities": ["completion"]}),
+        chat=httpx.Response(200, content=_ndjson([{"done": True, "eval_count": 1}])),
+    )
+    other_models: list[str] = []
+
+    async def _compat_stream(
+        model: Model, context: Context, options: SimpleStreamOptions
+    ) -> object:
+        _ = (context, options)
+        other_models.append(model.provider)
+        return "compat"
+
+    stream_fn = with_ollama_native(_compat_stream, CONFIG, transport=transport)
+    stream = await stream_fn(OLLAMA_MODEL, _context(), SimpleStreamOptions())
+    await stream.result()
+    other = OpenAICompatModel(
+        provider="openai", id="gpt-5", base_url="https://api.openai.com/v1/chat/completions"
+    )
+
+    assert "tools" not in json.loads(requests[1].content)
+    assert await stream_fn(other, _context(), SimpleStreamOptions()) == "compat"
+    assert other_models == ["openai"]
+
+
+def test_settings_keep_native_api_off_by_default_and_check_options() -> None:
+    config = copy.deepcopy(DEFAULT_USER_CONFIG)
+    assert OllamaConfig.from_settings(validate_user_config(config)["settings"]["ollama"]) is None
+
+    config["settings"]["ollama"].update(
+        native_api=True, keep_alive="30m", options={"num_ctx": 8192}
+    )
+    settings = validate_user_config(config)["settings"]["ollama"]
+    assert OllamaConfig.from_settings(settings) == OllamaConfig(
+        keep_alive="30m", options=(("num_ctx", 8192),)
+    )
+
+    config["settings"]["ollama"]["options"] = {"stop": ["\n"]}
+    with pytest.raises(TypeError, match="settings.ollama.options.stop"):
+        validate_user_config(config)
@@ -0,0 +1,110 @@
+from __future__ import annotations
+
+import asyncio
+
+import pytest
+from tinyagent.agent_types import (
+    AgentTool,
+    AgentToolResult,
+    AgentToolUpdateCallback,
+    AssistantMessage,
+    JsonObject,
+    TextContent,
+    ToolCallContent,
+    UserMessage,
+)
+
+from tunacode.core.agents.agent_components.partial_recovery import (
+    SALVAGED_STOP_REASON,
+    execute_salvaged_tool_calls,
+    is_complete_tool_call,
+    salvage_tool_calls,
+    trailing_error_message,
+)
+
+
+def _tool_call(call_id: str, *, partial_json: str = "") -> ToolCallContent:
+    return ToolCallContent(
+        id=call_id,
+        name="read_file",
+        arguments={"filepath": "a.py"},
+        partial_json=partial_json,
+    )
+
+
+def _errored_message(*content: object) -> AssistantMessage:
+    return AssistantMessage(content=list(content), stop_reason="error", timestamp=None)
+
+
+def test_is_complete_tool_call_rejects_unparseable_partial_json() -> None:
+    assert is_complete_tool_call(_tool_call("call-1"))
+    assert is_complete_tool_call(_tool_call("call-2", partial_json='{"filepath": "a.py"}'))
+    assert not is_complete_tool_call(_tool_call("call-3", partial_json='{"filepath": "a.'))
+    assert not is_complete_tool_call(TextContent(text="hello"))
+
+
+def test_salvage_tool_calls_drops_partial_calls_and_keeps_text() -> None:
+    message = _errored_message(
+        TextContent(text="Reading files"),
+        _tool_call("call-1"),
+        _tool_call("call-2", partial_json='{"file'),
+    )
+
+    salvaged = salvage_tool_calls(message)
+
+    assert salvaged is not None
+    assert salvaged.stop_reason == SALVAGED_STOP_REASON
+    tool_call_ids = [item.id for item in salvaged.content if isinstance(item, ToolCallContent)]
+    assert tool_call_ids == ["call-1"]
+    assert isinstance(salvaged.content[0], TextContent)
+    assert message.stop_reason == "error"
+
+
+def test_salvage_tool_calls_returns_none_without_complete_calls() -> None:
+    message = _errored_message(TextContent(text="partial"), _tool_call("c", partial_json="{"))
+
+    assert salvage_tool_calls(message) is None
+
+
+def test_trailing_error_message_only_matches_last_errored_assistant() -> None:
+    errored = _errored_message(_tool_call("call-1"))
+    completed = AssistantMessage(content=[], stop_reason="complete", timestamp=None)
+    user = UserMessage(content=[TextContent(text="hi")], timestamp=None)
+
+    assert trailing_error_message([user, errored]) is errored
+    assert trailing_error_message([errored, user]) is None
+    assert trailing_error_message([completed]) is None
+    assert trailing_error_message([]) is None
+
+
+@pytest.mark.asyncio
+async def test_execute_salvaged_tool_calls_reports_missing_and_failing_tools() -> None:
+    async def _read(
+        tool_call_id: str,
+        args: JsonObject,
+        signal: asyncio.Event | None,
+        on_update: AgentToolUpdateCallback,
+    ) -> AgentToolResult:
+        _ = (signal, on_update)
+        if tool_call_id == "call-2":
+            raise RuntimeError("disk on fire")
+        return AgentToolResult(content=[TextContent(text=f"read {args['filepath']}")], details={})
+
+    tools = [AgentTool(name="read_file", label="read_file", execute=_read)]
+    message = AssistantMessage(
+        content=[
+            _tool_call("call-1"),
+            _tool_call("call-2"),
+            ToolCallContent(id="call-3", name="missing", arguments={}),
+        ],
+        stop_reason=SALVAGED_STOP_REASON,
+        timestamp=None,
+    )
+
+    results = await execute_salvaged_tool_calls(message, tools)
+
+    assert [result.tool_call_id for result in results] == ["call-1", "call-2", "call-3"]
+    assert [result.is_error for result in results] == [False, True, True]
+    assert results[0].content[0].text == "read a.py"
+    assert "disk on fire" in results[1].content[0].text
+    assert "Tool not found: missing" in results[2].content[0].text
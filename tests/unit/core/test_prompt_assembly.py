@@ -0,0 +1,84 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+
+from tunacode.core.agents.agent_components.agent_session_config import SkillsPromptState
+from tunacode.core.agents.agent_components.prompt_assembly import (
+    TRUNCATED_SECTION_MARKER,
+    FunctionContextProvider,
+    PromptContext,
+    assemble_prompt,
+    register_context_provider,
+    registered_context_providers,
+    unregister_context_provider,
+)
+
+
+def _context() -> PromptContext:
+    skills_state = SkillsPromptState(
+        available_block="",
+        selected_block="",
+        fingerprint="",
+        selected_skills=[],
+    )
+    return PromptContext(model="openai:gpt-4o", base_path=Path("."), skills_state=skills_state)
+
+
+def _provider(label: str, text: str, priority: int) -> FunctionContextProvider:
+    return FunctionContextProvider(label=label, render_fn=lambda _c: text, priority=priority)
+
+
+def test_sections_are_concatenated_in_provider_order_and_empty_ones_skipped() -> None:
+    providers = [
+        _provider("base", "BASE\n", 100),
+        _provider("empty", "", 50),
+        _provider("extra", "EXTRA\n", 10),
+    ]
+
+    prompt = assemble_prompt(providers, _context())
+
+    assert prompt.text == "BASE\nEXTRA\n"
+    assert [section.label for section in prompt.sections] == ["base", "extra"]
+    assert prompt.dropped == []
+
+
+def test_budget_drops_lowest_priority_sections_first() -> None:
+    providers = [
+        _provider("base", "b" * 400, 100),
+        _provider("skills", "s" * 4_000, 20),
+        _provider("guide", "g" * 400, 50),
+    ]
+
+    prompt = assemble_prompt(providers, _context(), budget_tokens=200)
+
+    assert prompt.dropped == ["skills"]
+    assert prompt.text == "b" * 400 + "g" * 400
+
+
+def test_budget_truncates_a_section_when_that_is_enough() -> None:
+    providers = [_provider("base", "b" * 400, 100), _provider("guide", "g" * 2_000, 50)]
+
+    prompt = assemble_prompt(providers, _context(), budget_tokens=300)
+
+    guide = prompt.sections[1]
+    assert guide.truncated
+    assert guide.text.endswith(TRUNCATED_SECTION_MARKER)
+    assert prompt.sections[0].text == "b" * 400
+    assert len(prompt.text) <= 300 * 4
+
+
+@pytest.fixture
+def registered_provider():
+    provider = _provider("team_rules", "RULES\n", 60)
+    register_context_provider(provider)
+    yield provider
+    unregister_context_provider(provider.label)
+
+
+def test_registering_a_label_twice_replaces_the_provider(registered_provider) -> None:
+    replacement = _provider(registered_provider.label, "NEW RULES\n", 60)
+    register_context_provider(replacement)
+
+    assert registered_context_providers() == [replacement]
@@ -0,0 +1,80 @@
+"""Tests for prompt caching breakpoint hints."""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import Context, TextContent, UserMessage
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.core.agents.agent_components.prompt_caching import (
+    CACHE_CONTROL_KEY,
+    PROMPT_CACHE_MODE_AUTOMATIC,
+    PROMPT_CACHE_MODE_EXPLICIT,
+    PROMPT_CACHE_MODE_NONE,
+    apply_prompt_cache_hints,
+    resolve_prompt_cache_mode,
+)
+
+
+def _user(text: str) -> UserMessage:
+    return UserMessage(content=[TextContent(text=text)], timestamp=None)
+
+
+def _build_context() -> Context:
+    messages = [_user("first"), _user("middle"), _user("latest")]
+    return Context(system_prompt="SYS", messages=messages, tools=None)
+
+
+def _cache_control(message: UserMessage) -> object:
+    return getattr(message.content[0], CACHE_CONTROL_KEY, None)
+
+
+def test_resolve_prompt_cache_mode_uses_registry_capabilities() -> None:
+    assert resolve_prompt_cache_mode("anthropic", "claude-opus-4-5-20251101") == (
+        PROMPT_CACHE_MODE_EXPLICIT
+    )
+    assert resolve_prompt_cache_mode("openrouter", "anthropic/claude-sonnet-4.6") == (
+        PROMPT_CACHE_MODE_EXPLICIT
+    )
+    assert resolve_prompt_cache_mode("openrouter", "openai/gpt-4.1") == (
+        PROMPT_CACHE_MODE_AUTOMATIC
+    )
+    assert resolve_prompt_cache_mode("openrouter", "prime-intellect/intellect-3") == (
+        PROMPT_CACHE_MODE_NONE
+    )
+
+
+def test_apply_prompt_cache_hints_marks_first_and_last_messages() -> None:
+    model = OpenAICompatModel(provider="anthropic", id="claude-opus-4-5-20251101")
+    context = _build_context()
+
+    hinted = apply_prompt_cache_hints(model, context)
+
+    assert hinted.system_prompt == "SYS"
+    assert _cache_control(hinted.messages[0]) == {"type": "ephemeral"}
+    assert _cache_control(hinted.messages[1]) is None
+    assert _cache_control(hinted.messages[2]) == {"type": "ephemeral"}
+    assert _cache_control(context.messages[0]) is None
+
+
+def test_apply_prompt_cache_hints_ignores_automatic_and_uncached_models() -> None:
+    context = _build_context()
+
+    for model in (
+        OpenAICompatModel(provider="openrouter", id="openai/gpt-4.1"),
+        OpenAICompatModel(provider="openrouter", id="prime-intellect/intellect-3"),
+    ):
+        assert apply_prompt_cache_hints(model, context) is context
+
+
+def test_cache_breakpoints_survive_request_serialization() -> None:
+    model = OpenAICompatModel(provider="anthropic", id="claude-opus-4-5-20251101")
+
+    hinted = apply_prompt_cache_hints(model, _build_context())
+    payload = hinted.model_dump(mode="json", exclude_none=True)
+
+    first_block, middle_block, last_block = (
+        message["content"][0] for message in payload["messages"]
+    )
+    assert first_block[CACHE_CONTROL_KEY] == {"type": "ephemeral"}
+    assert CACHE_CONTROL_KEY not in middle_block
+    assert last_block[CACHE_CONTROL_KEY] == {"type": "ephemeral"}
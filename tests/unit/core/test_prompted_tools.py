@@ -0,0 +1,153 @@
+"""Tests for prompted tool calling on models without native function calling."""
+
+from __future__ import annotations
+
+import pytest
+from tinyagent.agent_types import (
+    AgentTool,
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.core.agents.agent_components.prompted_tools import (
+    JSON_TOOL_CALL_FORMAT,
+    XML_TOOL_CALL_FORMAT,
+    PromptedToolsConfig,
+    build_prompted_context,
+    with_prompted_tools,
+)
+
+READ_CALL = '{"name": "read_file", "arguments": {"filepath": "a.py"}}'
+
+
+class _ScriptedStream:
+    def __init__(self, chunks: list[str]) -> None:
+        self._chunks = chunks
+        self._events = iter(
+            [
+                *(AssistantMessageEvent(type="text_delta", delta=chunk) for chunk in chunks),
+                AssistantMessageEvent(type="done"),
+            ]
+        )
+
+    def __aiter__(self) -> _ScriptedStream:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        try:
+            return next(self._events)
+        except StopIteration:
+            raise StopAsyncIteration from None
+
+    async def result(self) -> AssistantMessage:
+        return AssistantMessage(
+            content=[TextContent(text="".join(self._chunks))], stop_reason="stop", timestamp=None
+        )
+
+
+def test_parser_holds_back_split_markers_and_hides_blocks() -> None:
+    parser = XML_TOOL_CALL_FORMAT.new_parser()
+    chunks = [
+        "Let me look.<to",
+        "ol_call>\n" + READ_CALL[:20],
+        READ_CALL[20:] + "\n</tool",
+        "_call> ok",
+    ]
+
+    steps = [parser.feed(chunk) for chunk in chunks]
+    final = parser.finish()
+
+    assert [step.text for step in steps] == ["Let me look.", "", "", " ok"]
+    assert [call for step in steps for call in step.calls] == [
+        ("read_file", {"filepath": "a.py"})
+    ]
+    assert final.text == ""
+    assert final.calls == []
+
+
+def test_parser_uses_an_unclosed_block_only_when_its_json_is_complete() -> None:
+    complete = JSON_TOOL_CALL_FORMAT.new_parser()
+    complete.feed(f"```tool_call\n{READ_CALL}\n")
+    assert complete.finish().calls == [("read_file", {"filepath": "a.py"})]
+
+    truncated = JSON_TOOL_CALL_FORMAT.new_parser()
+    truncated.feed('```tool_call\n{"name": "read_')
+    finished = truncated.finish()
+    assert finished.calls == []
+    assert finished.text == '```tool_call\n{"name": "read_'
+
+
+def test_prompted_context_moves_tools_into_the_prompt_and_rewrites_tool_turns() -> None:
+    tool = AgentTool(name="read_file", label="read_file", description="Read a file.")
+    context = Context(
+        system_prompt="SYS",
+        messages=[
+            UserMessage(content=[TextContent(text="open a.py")], timestamp=None),
+            AssistantMessage(
+                content=[ToolCallContent(id="c1", name="read_file", arguments={"filepath": "a"})],
+                stop_reason="tool_calls",
+                timestamp=None,
+            ),
+            ToolResultMessage(
+                tool_call_id="c1",
+                tool_name="read_file",
+                content=[TextContent(text="print(1)")],
+                timestamp=None,
+            ),
+        ],
+        tools=[tool],
+    )
+
+    prompted = build_prompted_context(context, XML_TOOL_CALL_FORMAT)
+
+    assert prompted.tools is None
+    assert prompted.system_prompt.startswith("SYS\n\n# Tools")
+    assert "## read_file\nRead a file." in prompted.system_prompt
+    assistant, result = prompted.messages[1], prompted.messages[2]
+    assert assistant.content[0].text == (
+        '<tool_call>\n{"name": "read_file", "arguments": {"filepath": "a"}}\n</tool_call>'
+    )
+    assert isinstance(result, UserMessage)
+    assert result.content[0].text == '<tool_result name="read_file">\nprint(1)\n</tool_result>'
+
+
+async def test_stream_maps_blocks_to_tool_calls_for_matching_models_only() -> None:
+    chunks = ["Reading.\n<tool_", f"call>{READ_CALL}</tool_call>"]
+    contexts: list[Context] = []
+
+    async def _stream(model: Model, context: Context, options: SimpleStreamOptions) -> object:
+        _ = (model, options)
+        contexts.append(context)
+        return _ScriptedStream(chunks)
+
+    config = PromptedToolsConfig.from_settings({"models": ["ollama:hermes*"], "format": "xml"})
+    assert config is not None
+    stream_fn = with_prompted_tools(_stream, config)
+
+    options = SimpleStreamOptions()
+    stream = await stream_fn(Model(provider="ollama", id="hermes3"), Context(), options)
+    deltas = [event.delta async for event in stream if event.type == "text_delta"]
+    message = await stream.result()
+
+    assert deltas == ["Reading.\n"]
+    assert message.stop_reason == "tool_calls"
+    assert message.content[0].text == "Reading.\n"
+    call = message.content[1]
+    assert isinstance(call, ToolCallContent)
+    assert (call.name, call.arguments) == ("read_file", {"filepath": "a.py"})
+
+    native = await stream_fn(Model(provider="openai", id="gpt-4o"), Context(), options)
+    assert isinstance(native, _ScriptedStream)
+
+
+def test_unknown_format_is_rejected() -> None:
+    assert PromptedToolsConfig.from_settings({"models": [], "format": "yaml"}) is None
+    with pytest.raises(ValueError, match="Unknown settings.prompted_tools.format 'yaml'"):
+        PromptedToolsConfig.from_settings({"models": ["*"], "format": "yaml"})
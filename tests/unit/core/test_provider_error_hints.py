@@ -0,0 +1,74 @@
+from __future__ import annotations
+
+from tunacode.exceptions import AgentError
+
+from tunacode.core.agents.agent_components.provider_error_hints import (
+    HINT_SUGGESTED_FIXES,
+    ProviderErrorKind,
+    build_agent_error,
+    match_provider_error,
+    provider_from_model,
+)
+
+
+def test_anthropic_max_tokens_error_maps_to_reduce_max_tokens() -> None:
+    raw = "max_tokens: 64000 > 32000, which is the maximum allowed number of output tokens"
+
+    hint = match_provider_error(raw, "anthropic")
+
+    assert hint is not None
+    assert hint.kind is ProviderErrorKind.MAX_TOKENS_TOO_LARGE
+    assert hint.provider == "anthropic"
+    assert hint.raw_message == raw
+    assert "settings.max_tokens" in hint.suggested_fix
+
+
+def test_openrouter_tool_endpoint_error_maps_to_tools_unsupported() -> None:
+    raw = "No endpoints found that support tool use. To learn more about provider routing..."
+
+    hint = match_provider_error(raw, "openrouter")
+
+    assert hint is not None
+    assert hint.kind is ProviderErrorKind.TOOLS_UNSUPPORTED
+
+
+def test_common_patterns_apply_to_any_provider() -> None:
+    hint = match_provider_error("registry.ollama.ai/library/gemma does not support tools", "ollama")
+
+    assert hint is not None
+    assert hint.kind is ProviderErrorKind.TOOLS_UNSUPPORTED
+    assert hint.suggested_fix == HINT_SUGGESTED_FIXES[ProviderErrorKind.TOOLS_UNSUPPORTED]
+
+
+def test_provider_patterns_are_not_applied_to_other_providers() -> None:
+    assert match_provider_error("No endpoints found that support tool use", "openai") is None
+
+
+def test_unrecognized_errors_have_no_hint() -> None:
+    assert match_provider_error("invalid api key", "openai") is None
+    assert match_provider_error("", "openai") is None
+
+
+def test_build_agent_error_keeps_raw_message_and_kind() -> None:
+    raw = "Unsupported parameter: 'max_tokens' is not supported with this model."
+
+    error = build_agent_error(raw, "openai")
+
+    assert isinstance(error, AgentError)
+    assert error.raw_message == raw
+    assert error.kind == ProviderErrorKind.PARAMETER_UNSUPPORTED
+    assert error.suggested_fix == HINT_SUGGESTED_FIXES[ProviderErrorKind.PARAMETER_UNSUPPORTED]
+    assert raw in str(error)
+
+
+def test_build_agent_error_without_match_is_plain() -> None:
+    error = build_agent_error("invalid api key", "openai")
+
+    assert error.raw_message == "invalid api key"
+    assert error.kind is None
+    assert error.suggested_fix is None
+
+
+def test_provider_from_model() -> None:
+    assert provider_from_model("openrouter:openai/gpt-4.1") == "openrouter"
+    assert provider_from_model("gpt-4.1") == ""
@@ -42,6 +42,7 @@ def test_error_severity_map_contains_only_active_taxonomy() -> None:
         "FileOperationError",
         "AgentError",
         "GlobalRequestTimeoutError",
+        "TurnDeadlineExceededError",
         "ContextOverflowError",
         "ConfigurationError",
         "ValidationError",
@@ -0,0 +1,161 @@
+from __future__ import annotations
+
+import httpx
+import pytest
+from tinyagent.agent_types import Context, Model, SimpleStreamOptions
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.core.agents.agent_components import agent_config
+from tunacode.core.agents.agent_components.agent_session_config import AgentSettings, SessionConfig
+from tunacode.core.agents.agent_components.backoff import BackoffPolicy
+from tunacode.core.agents.agent_components.provider_fallback import (
+    MODEL_FALLBACK_LABEL,
+    is_model_not_found_error,
+    is_retryable_stream_error,
+    with_provider_fallback,
+)
+
+PRIMARY = OpenAICompatModel(provider="openrouter", id="openai/gpt-4.1")
+LOCAL = OpenAICompatModel(provider="ollama", id="qwen2.5-coder", base_url="http://localhost:11434")
+
+
+MODEL_NOT_FOUND_BODY = {
+    "error": {"code": "model_not_found", "message": "The model `gpt-4.1-typo` does not exist"}
+}
+
+
+def _status_error(status_code: int, body: object | None = None) -> httpx.HTTPStatusError:
+    request = httpx.Request("POST", "https://example.test/v1/chat/completions")
+    response = httpx.Response(status_code=status_code, request=request, json=body)
+    return httpx.HTTPStatusError("provider error", request=request, response=response)
+
+
+def _fake_stream(failures: dict[str, Exception], calls: list[tuple[str, str | None]]):
+    async def _stream(model: Model, context: Context, options: SimpleStreamOptions) -> object:
+        _ = context
+        calls.append((model.provider, options.api_key))
+        failure = failures.get(model.provider)
+        if failure is not None:
+            raise failure
+        return {"served_by": model.provider}
+
+    return _stream
+
+
+def _with_local_fallback(stream_fn):
+    return with_provider_fallback(
+        stream_fn,
+        [LOCAL],
+        resolve_api_key=lambda provider: f"key-{provider}",
+        should_fail_over=is_retryable_stream_error,
+    )
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize(
+    "failure",
+    [_status_error(503), _status_error(429), httpx.ConnectError("down")],
+)
+async def test_retryable_primary_failure_fails_over_with_fallback_api_key(
+    failure: Exception,
+) -> None:
+    calls: list[tuple[str, str | None]] = []
+    stream_fn = _with_local_fallback(_fake_stream({"openrouter": failure}, calls))
+
+    result = await stream_fn(PRIMARY, Context(), SimpleStreamOptions(api_key="key-primary"))
+
+    assert result == {"served_by": "ollama"}
+    assert calls == [("openrouter", "key-primary"), ("ollama", "key-ollama")]
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("status_code", [400, 401])
+async def test_non_retryable_primary_failure_does_not_fail_over(status_code: int) -> None:
+    calls: list[tuple[str, str | None]] = []
+    failures = {"openrouter": _status_error(status_code)}
+    stream_fn = _with_local_fallback(_fake_stream(failures, calls))
+
+    with pytest.raises(httpx.HTTPStatusError):
+        await stream_fn(PRIMARY, Context(), SimpleStreamOptions(api_key="key-primary"))
+
+    assert calls == [("openrouter", "key-primary")]
+
+
+@pytest.mark.parametrize(
+    ("status_code", "body", "expected"),
+    [
+        (404, MODEL_NOT_FOUND_BODY, True),
+        (400, {"error": {"message": "Unknown model: gpt-4.1-typo"}}, True),
+        (400, {"error": {"message": "max_tokens is too large"}}, False),
+        (503, MODEL_NOT_FOUND_BODY, False),
+    ],
+)
+def test_model_not_found_is_detected_from_status_and_error_body(
+    status_code: int, body: object, expected: bool
+) -> None:
+    assert is_model_not_found_error(_status_error(status_code, body)) is expected
+
+
+@pytest.mark.asyncio
+async def test_model_not_found_retries_once_with_the_fallback_model() -> None:
+    stable = OpenAICompatModel(provider="openrouter", id="openai/gpt-4.1-mini")
+    served: list[str] = []
+    retired = {PRIMARY.id}
+
+    async def _stream(model: Model, context: Context, options: SimpleStreamOptions) -> object:
+        _ = (context, options)
+        served.append(model.id)
+        if model.id in retired:
+            raise _status_error(404, MODEL_NOT_FOUND_BODY)
+        return {"served_by": model.id}
+
+    stream_fn = with_provider_fallback(
+        _stream,
+        [stable],
+        resolve_api_key=lambda provider: f"key-{provider}",
+        should_fail_over=is_model_not_found_error,
+        label=MODEL_FALLBACK_LABEL,
+    )
+
+    assert await stream_fn(PRIMARY, Context(), SimpleStreamOptions()) == {"served_by": stable.id}
+    assert served == [PRIMARY.id, stable.id]
+
+    retired.add(stable.id)
+    served.clear()
+    with pytest.raises(httpx.HTTPStatusError):
+        await stream_fn(stable, Context(), SimpleStreamOptions())
+    assert served == [stable.id]
+
+
+def test_fallback_model_uses_its_own_base_url(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(agent_config, "get_provider_alchemy_api", lambda _provider: None)
+    config = SessionConfig(
+        settings=AgentSettings(
+            request_delay=0.0,
+            global_request_timeout=None,
+            turn_deadline=None,
+            max_retries=1,
+            tool_strict_validation=False,
+            max_iterations=40,
+            recover_partial_tool_calls=False,
+            safe_mode=False,
+            loop_detection_threshold=3,
+            loop_detection_action="nudge",
+            system_prompt_max_tokens=None,
+            fallback_providers=(("ollama:qwen2.5-coder", "http://localhost:11434/v1"),),
+            fallback_model=None,
+            stream_coalesce_window_ms=0,
+            stream_coalesce_max_chars=0,
+            thinking_budget=None,
+            task_decomposition=False,
+            retain_raw_responses=False,
+            background_max_reconnects=None,
+            retry_backoff=BackoffPolicy(),
+        ),
+        env={"OPENAI_BASE_URL": "https://primary.example/v1"},
+    )
+
+    [fallback] = agent_config._build_fallback_models(config)
+
+    assert (fallback.provider, fallback.id) == ("ollama", "qwen2.5-coder")
+    assert fallback.base_url == "http://localhost:11434/v1/chat/completions"
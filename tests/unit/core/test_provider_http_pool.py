@@ -0,0 +1,107 @@
+"""Tests for the per-provider pooled HTTP clients."""
+
+from __future__ import annotations
+
+import copy
+import json
+
+import httpx
+import pytest
+from tinyagent.agent_types import Context, SimpleStreamOptions, TextContent, UserMessage
+from tinyagent.alchemy_provider import OpenAICompatModel
+
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.configuration.user_config import validate_user_config
+
+from tunacode.core.agents.agent_components import background_responses, http_pool
+from tunacode.core.agents.agent_components.background_responses import open_background_response
+from tunacode.core.agents.agent_components.http_pool import (
+    ProviderHttpPolicy,
+    close_provider_clients,
+    provider_client,
+    provider_http_policies,
+)
+
+OPENAI_MODEL = OpenAICompatModel(
+    provider="openai", id="gpt-5", base_url="https://api.openai.com/v1/chat/completions"
+)
+TUNED = ProviderHttpPolicy(max_connections=32, max_keepalive_connections=16)
+
+
+def _isolate(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(http_pool, "_pool", http_pool._ClientPool())
+    monkeypatch.setattr(background_responses, "_unsupported_base_urls", set())
+
+
+def _completed_response() -> httpx.Response:
+    events = [
+        {"type": "response.created", "sequence_number": 0, "response": {"id": "resp_1"}},
+        {"type": "response.output_text.delta", "sequence_number": 1, "delta": "ok"},
+        {"type": "response.completed", "sequence_number": 2, "response": {"id": "resp_1"}},
+    ]
+    body = "".join(f"data: {json.dumps(event)}\n\n" for event in events)
+    return httpx.Response(200, content=body.encode())
+
+
+def _context() -> Context:
+    return Context(messages=[UserMessage(content=[TextContent(text="hi")], timestamp=None)])
+
+
+def test_settings_fill_defaults_per_provider_and_reject_bad_pools() -> None:
+    config = copy.deepcopy(DEFAULT_USER_CONFIG)
+    config["settings"]["provider_http"] = {
+        "openai": {"max_connections": 32, "max_keepalive_connections": 16},
+        "vllm": {},
+    }
+    settings = validate_user_config(config)["settings"]["provider_http"]
+
+    assert provider_http_policies(settings) == (("openai", TUNED), ("vllm", ProviderHttpPolicy()))
+
+    config["settings"]["provider_http"] = {"vllm": {"max_keepalive_connections": 11}}
+    with pytest.raises(ValueError, match="must be <= max_connections"):
+        validate_user_config(config)
+    config["settings"]["provider_http"] = {"vllm": {"http2": "yes"}}
+    with pytest.raises(TypeError, match="settings.provider_http.vllm.http2"):
+        validate_user_config(config)
+
+
+async def test_background_requests_reuse_the_pooled_client_across_turns(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    requests: list[httpx.Request] = []
+
+    def _handle(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return _completed_response()
+
+    pooled = httpx.AsyncClient(transport=httpx.MockTransport(_handle))
+    http_pool._pool.clients["openai"] = (TUNED, pooled)
+
+    for _turn in range(2):
+        stream = await open_background_response(
+            OPENAI_MODEL, _context(), SimpleStreamOptions(), max_reconnects=0, http_policy=TUNED
+        )
+        assert stream is not None
+        message = await stream.result()
+        assert message.content[0].text == "ok"
+
+    assert len(requests) == 2
+    assert pooled.is_closed is False
+
+
+async def test_changed_policy_replaces_the_client_and_exit_closes_all(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    first = provider_client("openai", ProviderHttpPolicy())
+
+    assert provider_client("openai", ProviderHttpPolicy()) is first
+    replacement = provider_client("openai", TUNED)
+    assert replacement is not first
+    assert first.is_closed is False
+
+    await close_provider_clients()
+
+    assert first.is_closed and replacement.is_closed
+    assert http_pool._pool.clients == {}
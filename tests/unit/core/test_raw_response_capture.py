@@ -0,0 +1,75 @@
+from __future__ import annotations
+
+import json
+
+import pytest
+from tinyagent.agent_types import (
+    AssistantMessage,
+    AssistantMessageEvent,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    TextContent,
+)
+
+from tunacode.core.agents.agent_components.stream_debug import with_raw_response_capture
+from tunacode.core.debug.raw_responses import RawResponseLog
+
+MODEL = Model(provider="openai", id="gpt-4.1", api="openai-completions")
+SECRET = "sk-test-secret"
+
+
+class _FakeResponse:
+    def __init__(self, events: list[AssistantMessageEvent], answer: AssistantMessage) -> None:
+        self._events = iter(events)
+        self._answer = answer
+
+    def __aiter__(self) -> _FakeResponse:
+        return self
+
+    async def __anext__(self) -> AssistantMessageEvent:
+        try:
+            return next(self._events)
+        except StopIteration:
+            raise StopAsyncIteration from None
+
+    async def result(self) -> AssistantMessage:
+        return self._answer
+
+
+async def _provider(
+    model: Model, context: Context, options: SimpleStreamOptions
+) -> _FakeResponse:
+    _ = (model, context, options)
+    answer = AssistantMessage(content=[TextContent(text="hello")])
+    event = AssistantMessageEvent(type="text_delta", delta="hello", partial=answer)
+    return _FakeResponse([event], answer)
+
+
+@pytest.mark.asyncio
+async def test_capture_records_events_and_result_but_not_request_options() -> None:
+    log = RawResponseLog()
+    stream_fn = with_raw_response_capture(_provider, lambda: log, request_id_fn=lambda: "req-1")
+    options = SimpleStreamOptions(api_key=SECRET)
+
+    response = await stream_fn(MODEL, Context(system_prompt="SYS", messages=[]), options)
+    seen = [event async for event in response]
+    await response.result()
+
+    record = log.get()
+    assert record is not None
+    assert len(seen) == 1
+    assert (record.index, record.request_id, record.model) == (1, "req-1", "openai:gpt-4.1")
+    assert record.events == [{"type": "text_delta", "delta": "hello"}]
+    assert record.response["content"][0]["text"] == "hello"
+    assert SECRET not in json.dumps(record.to_dict())
+
+
+def test_log_keeps_only_the_newest_records() -> None:
+    log = RawResponseLog(limit=2)
+    for request_id in ("a", "b", "c"):
+        log.record(request_id=request_id, model="m", events=[], response={})
+
+    assert [entry.index for entry in log.entries()] == [2, 3]
+    assert log.get(1) is None
+    assert log.get(3) is not None and log.get(3).request_id == "c"
@@ -0,0 +1,64 @@
+from __future__ import annotations
+
+import pytest
+from tinyagent.agent_types import (
+    AssistantMessage,
+    Context,
+    Model,
+    SimpleStreamOptions,
+    ThinkingContent,
+)
+
+from tunacode.core.agents.agent_components import reasoning_budget
+from tunacode.core.agents.agent_components.reasoning_budget import (
+    effort_for_budget,
+    reasoning_tokens_used,
+    with_reasoning_budget,
+)
+
+CLAUDE = Model(provider="anthropic", id="claude-sonnet-4")
+O_SERIES = Model(provider="openai", id="o4-mini")
+PLAIN = Model(provider="openai", id="gpt-4.1")
+REASONING_MODELS = {"anthropic:claude-sonnet-4", "openai:o4-mini"}
+
+
+@pytest.mark.parametrize(
+    ("budget", "effort"),
+    [(1_024, "minimal"), (4_000, "minimal"), (4_096, "low"), (20_000, "medium"), (64_000, "high")],
+)
+def test_budget_maps_to_largest_tier_that_fits(budget: int, effort: str) -> None:
+    assert effort_for_budget(budget) == effort
+
+
+@pytest.mark.asyncio
+async def test_budget_is_sent_in_the_form_each_provider_accepts(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    monkeypatch.setattr(
+        reasoning_budget, "model_supports_reasoning", lambda model: model in REASONING_MODELS
+    )
+    sent: dict[str, SimpleStreamOptions] = {}
+
+    async def _stream(model: Model, context: Context, options: SimpleStreamOptions) -> object:
+        _ = context
+        sent[model.id] = options
+        return None
+
+    stream_fn = with_reasoning_budget(_stream, 8_000)
+    for model in (CLAUDE, O_SERIES, PLAIN):
+        await stream_fn(model, Context(), SimpleStreamOptions())
+
+    claude_options = sent["claude-sonnet-4"].model_dump()
+    assert claude_options["thinking"] == {"type": "enabled", "budget_tokens": 8_000}
+    assert sent["o4-mini"].model_dump()["reasoning_effort"] == "low"
+    assert "reasoning_effort" not in sent["gpt-4.1"].model_dump()
+    assert "thinking" not in sent["gpt-4.1"].model_dump()
+
+
+def test_reasoning_tokens_prefer_reported_usage_then_estimate() -> None:
+    thinking = ThinkingContent(thinking="x" * 400)
+    reported = AssistantMessage(content=[thinking], usage={"reasoning": 42})
+    estimated = AssistantMessage(content=[thinking], usage={})
+
+    assert reasoning_tokens_used(reported) == 42
+    assert reasoning_tokens_used(estimated) == 100
@@ -0,0 +1,36 @@
+"""Tests for estimating the next request from the live session."""
+
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+from tinyagent.agent_types import AgentTool, TextContent, UserMessage
+
+from tunacode.core.agents.agent_components import request_estimate
+from tunacode.core.session import StateManager
+
+
+def test_estimate_counts_the_session_prompt_tools_history_and_affixed_message(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    session = state_manager.session
+    session.system_prompt = "x" * 40
+    settings = session.user_config["settings"]
+    settings["user_message_prefix"] = "[ctx]"
+    session.conversation.messages = [
+        UserMessage(content=[TextContent(text="y" * 80)], timestamp=None)
+    ]
+    tool = AgentTool(name="bash", label="bash", description="Run.", parameters={})
+    agent = SimpleNamespace(state=SimpleNamespace(tools=[tool]))
+    monkeypatch.setattr(request_estimate, "get_or_create_agent", lambda model, sm: agent)
+
+    estimate = request_estimate.estimate_next_request(
+        "openai:gpt-4o", state_manager, "question", count_tokens=len
+    )
+
+    assert estimate.system == 40
+    assert estimate.history == 80
+    assert estimate.tools == len("bashRun.{}")
+    assert estimate.next_message == len("[ctx]\n\nquestion")
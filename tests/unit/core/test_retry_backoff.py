@@ -0,0 +1,100 @@
+"""Tests for the configurable retry backoff strategies."""
+
+from __future__ import annotations
+
+import copy
+import random
+
+import httpx
+import pytest
+from tinyagent.agent_types import Context, Model, SimpleStreamOptions
+
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.configuration.user_config import validate_user_config
+from tunacode.constants import BackoffStrategy
+
+from tunacode.core.agents.agent_components import backoff as backoff_module
+from tunacode.core.agents.agent_components.backoff import BackoffPolicy
+from tunacode.core.agents.agent_components.provider_fallback import (
+    is_retryable_stream_error,
+    with_provider_fallback,
+)
+
+CEILINGS = [0.5, 1.0, 2.0, 4.0, 8.0, 8.0]
+
+
+class _LifecycleLog:
+    def __init__(self) -> None:
+        self.lines: list[str] = []
+
+    def lifecycle(self, message: str) -> None:
+        self.lines.append(message)
+
+
+def _delays(strategy: BackoffStrategy, count: int = len(CEILINGS)) -> list[float]:
+    schedule = BackoffPolicy(strategy=strategy).schedule("test", rng=random.Random(7))
+    return [schedule.next_delay() for _ in range(count)]
+
+
+def test_none_waits_the_capped_exponential_delay() -> None:
+    assert _delays(BackoffStrategy.NONE) == CEILINGS
+
+
+def test_jittered_delays_stay_inside_their_bounds() -> None:
+    full = _delays(BackoffStrategy.FULL_JITTER)
+    equal = _delays(BackoffStrategy.EQUAL_JITTER)
+    decorrelated = _delays(BackoffStrategy.DECORRELATED, count=20)
+
+    assert all(0.0 <= delay <= ceiling for delay, ceiling in zip(full, CEILINGS, strict=True))
+    assert full != CEILINGS
+    assert all(
+        ceiling / 2 <= delay <= ceiling for delay, ceiling in zip(equal, CEILINGS, strict=True)
+    )
+    previous = 0.5
+    for delay in decorrelated:
+        assert 0.5 <= delay <= min(8.0, previous * 3)
+        previous = delay
+
+
+async def test_failover_waits_a_logged_backoff_delay(monkeypatch: pytest.MonkeyPatch) -> None:
+    log = _LifecycleLog()
+    monkeypatch.setattr(backoff_module, "get_logger", lambda: log)
+    request = httpx.Request("POST", "https://example.test/v1/chat/completions")
+    overloaded = httpx.HTTPStatusError(
+        "overloaded", request=request, response=httpx.Response(503, request=request)
+    )
+    served: list[str] = []
+
+    async def _stream(model: Model, context: Context, options: SimpleStreamOptions) -> object:
+        _ = (context, options)
+        served.append(model.provider)
+        if model.provider == "primary":
+            raise overloaded
+        return {"served_by": model.provider}
+
+    stream_fn = with_provider_fallback(
+        _stream,
+        [Model(provider="secondary", id="m")],
+        resolve_api_key=lambda _provider: None,
+        should_fail_over=is_retryable_stream_error,
+        backoff=BackoffPolicy(strategy=BackoffStrategy.NONE, base_delay=0.01, max_delay=0.01),
+    )
+
+    result = await stream_fn(Model(provider="primary", id="m"), Context(), SimpleStreamOptions())
+
+    assert result == {"served_by": "secondary"}
+    assert log.lines == ["Backoff: provider_failover retry=1 strategy=none delay=0.010s"]
+
+
+def test_settings_default_to_full_jitter_and_reject_bad_values() -> None:
+    config = copy.deepcopy(DEFAULT_USER_CONFIG)
+    policy = BackoffPolicy.from_settings(validate_user_config(config)["settings"]["retry_backoff"])
+    assert policy == BackoffPolicy(strategy=BackoffStrategy.FULL_JITTER)
+
+    config["settings"]["retry_backoff"]["strategy"] = "random"
+    with pytest.raises(ValueError, match="settings.retry_backoff.strategy"):
+        validate_user_config(config)
+
+    config["settings"]["retry_backoff"].update(strategy="none", base_delay=2.0, max_delay=1.0)
+    with pytest.raises(ValueError, match="max_delay must be >= base_delay"):
+        validate_user_config(config)
@@ -0,0 +1,50 @@
+from __future__ import annotations
+
+import pytest
+
+from tunacode.constants import CommandRisk
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.core.agents.agent_components import agent_tools
+
+
+def _tools_by_name(*, safe_mode: bool) -> dict[str, object]:
+    return {tool.name: tool for tool in agent_tools._build_tools(safe_mode=safe_mode)}
+
+
+def test_safe_mode_offers_only_read_only_capable_tools() -> None:
+    tools = _tools_by_name(safe_mode=True)
+
+    assert set(tools) == {"bash", "discover", "grep", "list_directory", "read_file"}
+
+
+@pytest.mark.asyncio
+async def test_safe_mode_blocks_mutating_bash_even_when_policy_allows() -> None:
+    bash = _tools_by_name(safe_mode=True)["bash"]
+
+    with pytest.raises(ToolRetryError, match=r"Safe mode: bash call blocked \(destructive\)"):
+        await bash.execute("call-1", {"command": "rm -rf build"}, None, lambda _update: None)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize(
+    ("command", "risk"),
+    [
+        ("rg --pre 'sh -c \"rm -rf ~\"' x .", CommandRisk.DESTRUCTIVE),
+        ("git diff --output=~/.bashrc", CommandRisk.WRITE),
+    ],
+)
+async def test_safe_mode_blocks_read_only_programs_with_writing_options(
+    command: str, risk: CommandRisk
+) -> None:
+    bash = _tools_by_name(safe_mode=True)["bash"]
+
+    with pytest.raises(ToolRetryError, match=rf"Safe mode: bash call blocked \({risk.value}\)"):
+        await bash.execute("call-1", {"command": command}, None, lambda _update: None)
+
+
+def test_safe_mode_wraps_copies_and_leaves_shared_tools_unguarded() -> None:
+    agent_tools._build_tools(safe_mode=True)
+
+    assert agent_tools.bash.execute.__name__ != "_execute_read_only"
+    assert "write_file" in _tools_by_name(safe_mode=False)
@@ -0,0 +1,111 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import Any
+
+from tunacode.core.session.diff import diff_session_data, diff_session_files, session_events
+
+
+def _user(text: str) -> dict[str, Any]:
+    return {"role": "user", "content": [{"type": "text", "text": text}], "timestamp": 1}
+
+
+def _tool_call(call_id: str, name: str, arguments: dict[str, Any]) -> dict[str, Any]:
+    return {
+        "role": "assistant",
+        "content": [{"type": "tool_call", "id": call_id, "name": name, "arguments": arguments}],
+        "usage": {"input": 10},
+        "timestamp": 2,
+    }
+
+
+def _tool_result(call_id: str, name: str, text: str) -> dict[str, Any]:
+    return {
+        "role": "tool_result",
+        "tool_call_id": call_id,
+        "tool_name": name,
+        "content": [{"type": "text", "text": text}],
+        "is_error": False,
+        "timestamp": 3,
+    }
+
+
+def _answer(text: str) -> dict[str, Any]:
+    return {"role": "assistant", "content": [{"type": "text", "text": text}], "timestamp": 4}
+
+
+def _session(*messages: dict[str, Any]) -> dict[str, Any]:
+    return {"version": 2, "messages": list(messages)}
+
+
+def test_diff_ignores_timestamps_usage_and_tool_call_ids() -> None:
+    run_a = _session(
+        _user("fix it"),
+        _tool_call("call-a", "read_file", {"filepath": "a.py", "offset": 0}),
+        _tool_result("call-a", "read_file", "print(1)"),
+        _answer("Done"),
+    )
+    run_b = json.loads(json.dumps(run_a))
+    run_b["messages"][1]["content"][0]["id"] = "call-b"
+    run_b["messages"][1]["content"][0]["arguments"] = {"offset": 0, "filepath": "a.py"}
+    run_b["messages"][1]["usage"] = {"input": 99}
+    for message in run_b["messages"]:
+        message["timestamp"] = 1000
+
+    diff = diff_session_data(run_a, run_b)
+
+    assert diff.first_divergence is None
+    assert [event.kind for event in diff.events_a] == ["user", "tool_call", "tool_result", "answer"]
+
+
+def test_diff_reports_first_divergence_and_final_answers() -> None:
+    shared = [_user("fix it"), _tool_call("1", "grep", {"pattern": "bug"})]
+    run_a = _session(
+        *shared,
+        _tool_result("1", "grep", "a.py:3"),
+        _tool_call("2", "hashline_edit", {"filepath": "a.py"}),
+        _tool_result("2", "hashline_edit", "ok"),
+        _answer("Fixed a.py"),
+    )
+    run_b = _session(
+        *shared,
+        _tool_result("1", "grep", "a.py:3"),
+        _tool_call("2", "bash", {"command": "pytest"}),
+        _tool_result("2", "bash", "1 failed"),
+        _answer("Tests fail"),
+    )
+
+    diff = diff_session_data(run_a, run_b)
+
+    first = diff.first_divergence
+    assert first is not None
+    assert first.index_a == first.index_b == 3
+    assert first.events_a[0].summary == 'hashline_edit({"filepath": "a.py"})'
+    assert first.events_b[0].summary == 'bash({"command": "pytest"})'
+    assert diff.final_answer_a == "Fixed a.py"
+    assert diff.final_answer_b == "Tests fail"
+
+
+def test_session_events_marks_tool_errors() -> None:
+    result = _tool_result("1", "bash", "boom")
+    result["is_error"] = True
+
+    events = session_events(_session(result))
+
+    assert events[0].summary == "bash [error] boom"
+
+
+def test_diff_session_files_migrates_legacy_files(tmp_path: Path) -> None:
+    legacy = {"messages": [_user("hi"), {"thought": "hmm"}, _answer("hello")]}
+    current = _session(_user("hi"), _answer("hello there"))
+    file_a = tmp_path / "project_a.json"
+    file_b = tmp_path / "project_b.json"
+    file_a.write_text(json.dumps(legacy))
+    file_b.write_text(json.dumps(current))
+
+    diff = diff_session_files(file_a, file_b)
+
+    assert len(diff.differences) == 1
+    assert diff.differences[0].tag == "replace"
+    assert diff.final_answer_a == "hello"
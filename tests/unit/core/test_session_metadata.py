@@ -0,0 +1,106 @@
+"""Tests for session metadata, tags, and filtered session listing."""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import pytest
+
+from tunacode.configuration.paths import get_session_storage_dir
+from tunacode.types import UsageMetrics
+
+from tunacode.core.session import StateManager
+from tunacode.core.session.metadata import (
+    SessionMetadata,
+    SessionMetadataError,
+    parse_session_filter,
+)
+
+PROJECT_ID = "project-meta"
+
+
+def _state_manager(session_id: str) -> StateManager:
+    state_manager = StateManager()
+    state_manager.session.project_id = PROJECT_ID
+    state_manager.session.session_id = session_id
+    return state_manager
+
+
+def test_update_sets_removes_and_tags_without_duplicates() -> None:
+    metadata = SessionMetadata(values={"project": "foo", "stale": "x"}, tags=["wip"])
+
+    metadata.update({"ticket": "JIRA-123", "stale": None}, add_tags=["review", "wip", "review"])
+    assert metadata.values == {"project": "foo", "ticket": "JIRA-123"}
+    assert metadata.tags == ["wip", "review"]
+
+    metadata.update(remove_tags=["wip", "absent"])
+    assert metadata.tags == ["review"]
+
+
+def test_rejected_update_changes_nothing() -> None:
+    metadata = SessionMetadata(values={"project": "foo"})
+
+    with pytest.raises(SessionMetadataError):
+        metadata.update({"owner": "me"}, add_tags=["has space"])
+
+    assert metadata == SessionMetadata(values={"project": "foo"})
+
+
+async def test_metadata_round_trips_and_filters_the_listing(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("XDG_DATA_HOME", str(tmp_path))
+    tagged = _state_manager("session-tagged")
+    tagged.session.metadata.update({"project": "foo", "ticket": "JIRA-123"}, add_tags=["bug"])
+    assert await tagged.save_session()
+    assert await _state_manager("session-plain").save_session()
+
+    listed = {entry["session_id"]: entry for entry in tagged.list_sessions()}
+    matching = tagged.list_sessions(parse_session_filter(["tag:bug", "project=foo"]))
+    by_key = tagged.list_sessions(parse_session_filter(["has:ticket"]))
+    no_match = tagged.list_sessions(parse_session_filter(["project=bar"]))
+
+    assert listed["session-tagged"]["tags"] == ["bug"]
+    assert listed["session-plain"]["metadata"] == {}
+    assert [entry["session_id"] for entry in matching] == ["session-tagged"]
+    assert [entry["session_id"] for entry in by_key] == ["session-tagged"]
+    assert no_match == []
+
+    reloaded = _state_manager("other")
+    assert await reloaded.load_session("session-tagged")
+    assert reloaded.session.metadata == SessionMetadata(
+        values={"project": "foo", "ticket": "JIRA-123"}, tags=["bug"]
+    )
+
+
+async def test_old_session_without_metadata_lists_and_loads_empty(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("XDG_DATA_HOME", str(tmp_path))
+    storage_dir = get_session_storage_dir()
+    storage_dir.mkdir(parents=True, exist_ok=True)
+    (storage_dir / f"{PROJECT_ID}_legacy.json").write_text(
+        json.dumps(
+            {
+                "session_id": "legacy",
+                "project_id": PROJECT_ID,
+                "session_total_usage": UsageMetrics().to_dict(),
+                "messages": [],
+            }
+        ),
+        encoding="utf-8",
+    )
+    state_manager = _state_manager("current")
+
+    [entry] = state_manager.list_sessions()
+
+    assert (entry["metadata"], entry["tags"]) == ({}, [])
+    assert state_manager.list_sessions(parse_session_filter(["tag:bug"])) == []
+    assert await state_manager.load_session("legacy")
+    assert state_manager.session.metadata.is_empty()
+
+
+def test_unknown_filter_term_is_rejected() -> None:
+    with pytest.raises(SessionMetadataError, match="Unknown session filter"):
+        parse_session_filter(["bug"])
@@ -0,0 +1,76 @@
+"""Tests for session file schema migrations."""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import SessionMigrationError
+
+from tunacode.core.session.migrations import (
+    CURRENT_SESSION_VERSION,
+    migrate_session_data,
+    migrate_session_file,
+)
+
+
+def _legacy_session() -> dict[str, object]:
+    return {
+        "session_id": "abc",
+        "thoughts": ["kept"],
+        "messages": [
+            {"role": "user", "content": [{"type": "text", "text": "hi"}]},
+            {"thought": "legacy thought"},
+        ],
+    }
+
+
+def test_chained_migration_upgrades_unversioned_session() -> None:
+    legacy = _legacy_session()
+
+    migrated = migrate_session_data(legacy)
+
+    assert migrated["version"] == CURRENT_SESSION_VERSION
+    assert migrated["thoughts"] == ["kept", "legacy thought"]
+    assert migrated["messages"] == [legacy["messages"][0]]
+    assert "version" not in legacy
+    assert migrate_session_data(migrated) == migrated
+
+
+@pytest.fixture
+def sessions_dir(tmp_path: Path) -> Path:
+    directory = tmp_path / "sessions"
+    directory.mkdir()
+    return directory
+
+
+def test_migrate_session_file_writes_backup_and_is_idempotent(sessions_dir: Path) -> None:
+    session_file = sessions_dir / "project_abc.json"
+    original = json.dumps(_legacy_session())
+    session_file.write_text(original, encoding="utf-8")
+
+    migrate_session_file(session_file)
+    upgraded = session_file.read_text(encoding="utf-8")
+    migrate_session_file(session_file)
+
+    assert json.loads(upgraded)["version"] == CURRENT_SESSION_VERSION
+    assert session_file.read_text(encoding="utf-8") == upgraded
+    assert (sessions_dir / "project_abc.json.v0.bak").read_text(encoding="utf-8") == original
+    assert sorted(path.name for path in sessions_dir.iterdir()) == [
+        "project_abc.json",
+        "project_abc.json.v0.bak",
+    ]
+
+
+def test_failed_migration_leaves_file_untouched(sessions_dir: Path) -> None:
+    session_file = sessions_dir / "project_bad.json"
+    original = json.dumps({"version": 1, "messages": "not-a-list"})
+    session_file.write_text(original, encoding="utf-8")
+
+    with pytest.raises(SessionMigrationError, match="from version 1"):
+        migrate_session_file(session_file)
+
+    assert session_file.read_text(encoding="utf-8") == original
+    assert [path.name for path in sessions_dir.iterdir()] == ["project_bad.json"]
@@ -0,0 +1,60 @@
+"""Tests for the per-session scratch directory."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+from pathlib import Path
+
+import pytest
+
+from tunacode.core.session.temp_dir import (
+    OWNER_PID_FILE,
+    SESSION_TEMP_ENV_VAR,
+    close_session_temp_dir,
+    open_session_temp_dir,
+    session_temp_path,
+    sweep_stale_session_temp_dirs,
+)
+
+
+def test_session_dir_is_stable_exported_and_replaced_on_switch(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.delenv(SESSION_TEMP_ENV_VAR, raising=False)
+
+    first = open_session_temp_dir("abc", root=tmp_path)
+    assert first == session_temp_path("abc", root=tmp_path) == tmp_path / "tunacode-abc"
+    assert open_session_temp_dir("abc", root=tmp_path) == first
+    (first / "scratch.txt").write_text("intermediate")
+    assert os.environ[SESSION_TEMP_ENV_VAR] == str(first)
+
+    second = open_session_temp_dir("def", root=tmp_path)
+    assert not first.exists()
+    assert os.environ[SESSION_TEMP_ENV_VAR] == str(second)
+
+    close_session_temp_dir(second)
+    assert not second.exists()
+    assert SESSION_TEMP_ENV_VAR not in os.environ
+
+
+def test_sweep_removes_only_directories_of_dead_owners(tmp_path: Path) -> None:
+    finished = subprocess.run(
+        [sys.executable, "-c", "import os; print(os.getpid())"],
+        capture_output=True,
+        text=True,
+        check=True,
+    )
+    dead_pid = int(finished.stdout)
+    stale = tmp_path / "tunacode-stale"
+    live = tmp_path / "tunacode-live"
+    foreign = tmp_path / "tunacode-foreign"
+    for path in (stale, live, foreign):
+        path.mkdir()
+    (stale / OWNER_PID_FILE).write_text(str(dead_pid))
+    (live / OWNER_PID_FILE).write_text(str(os.getpid()))
+
+    assert sweep_stale_session_temp_dirs(root=tmp_path) == [stale]
+    assert live.exists()
+    assert foreign.exists()
@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+import pytest
+from tinyagent.agent_types import AssistantMessage, TextContent
+
+from tunacode.types import ModelName
+
+from tunacode.core.review import staged
+from tunacode.core.session import StateManager
+
+STAGED_DIFF = """\
+diff --git a/src/app.py b/src/app.py
+index 1111111..2222222 100644
+--- a/src/app.py
++++ b/src/app.py
+@@ -10,2 +10,3 @@ def run():
+     start()
++    retry = True
+     stop()
+"""
+
+
+@pytest.mark.asyncio
+async def test_empty_staging_area_returns_message_without_calling_agent(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    calls: list[str] = []
+    monkeypatch.setattr(staged, "read_git_diff", lambda *, staged, cwd: "")
+
+    async def _fake_process_request(message: str, model: ModelName, state_manager) -> None:
+        calls.append(message)
+
+    monkeypatch.setattr(staged, "process_request", _fake_process_request)
+
+    result = await staged.run_staged_review(StateManager(), ModelName("openai:gpt-4.1"))
+
+    assert result == staged.EMPTY_STAGING_MESSAGE
+    assert calls == []
+
+
+@pytest.mark.asyncio
+async def test_staged_diff_is_reviewed_read_only_with_line_numbers(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    prompts: list[str] = []
+
+    def _read_git_diff(*, staged: bool, cwd: object) -> str:
+        assert staged is True
+        return STAGED_DIFF
+
+    async def _fake_process_request(message: str, model: ModelName, state_manager) -> None:
+        prompts.append(message)
+        answer = AssistantMessage(content=[TextContent(text="src/app.py:11 low: name it.")])
+        state_manager.session.conversation.messages.append(answer)
+
+    monkeypatch.setattr(staged, "read_git_diff", _read_git_diff)
+    monkeypatch.setattr(staged, "process_request", _fake_process_request)
+
+    result = await staged.run_staged_review(state_manager, ModelName("openai:gpt-4.1"))
+
+    assert prompts[0].startswith(staged.REVIEW_PROMPT)
+    assert "src/app.py:11 +     retry = True" in prompts[0]
+    assert state_manager.session.user_config["settings"]["safe_mode"] is True
+    assert result.splitlines()[:2] == [
+        "Review of 1 staged file(s):",
+        "- src/app.py (modified, +1/-0)",
+    ]
+    assert result.endswith("src/app.py:11 low: name it.")
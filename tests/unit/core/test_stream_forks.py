@@ -0,0 +1,96 @@
+"""Tests for forking one agent event stream into reasoning, answer and tool calls."""
+
+from __future__ import annotations
+
+import asyncio
+from collections.abc import AsyncIterator
+
+import pytest
+from tinyagent.agent_types import (
+    AgentEvent,
+    AssistantMessageEvent,
+    MessageUpdateEvent,
+    ToolExecutionStartEvent,
+)
+
+from tunacode.core.agents.agent_components.stream_forks import fork_response_stream
+
+
+def _delta(kind: str, text: str) -> MessageUpdateEvent:
+    return MessageUpdateEvent(assistant_message_event=AssistantMessageEvent(type=kind, delta=text))
+
+
+async def _events(
+    items: list[AgentEvent], *, error: Exception | None = None
+) -> AsyncIterator[AgentEvent]:
+    for item in items:
+        await asyncio.sleep(0)
+        yield item
+    if error is not None:
+        raise error
+
+
+async def _collect(fork: AsyncIterator[object]) -> list[object]:
+    return [item async for item in fork]
+
+
+async def test_forks_split_reasoning_answer_and_tool_calls() -> None:
+    tool_start = ToolExecutionStartEvent(tool_call_id="t1", tool_name="bash", args={})
+    forks = fork_response_stream(
+        _events(
+            [
+                _delta("thinking_delta", "plan "),
+                _delta("text_delta", "Hello"),
+                tool_start,
+                _delta("thinking_delta", "more"),
+                _delta("text_delta", " world"),
+            ]
+        )
+    )
+
+    reasoning, answer, tool_calls = await asyncio.gather(
+        _collect(forks.reasoning), _collect(forks.answer), _collect(forks.tool_calls)
+    )
+
+    assert reasoning == ["plan ", "more"]
+    assert answer == ["Hello", " world"]
+    assert tool_calls == [tool_start]
+    assert all(fork.finished for fork in forks.forks)
+
+
+async def test_closing_one_fork_does_not_stall_the_others() -> None:
+    forks = fork_response_stream(
+        _events([_delta("thinking_delta", "x") for _ in range(50)] + [_delta("text_delta", "done")])
+    )
+    forks.reasoning.close()
+
+    assert await _collect(forks.answer) == ["done"]
+    assert await _collect(forks.reasoning) == []
+
+
+async def test_source_error_reaches_every_open_fork_after_its_buffer() -> None:
+    forks = fork_response_stream(
+        _events([_delta("text_delta", "partial")], error=RuntimeError("stream broke"))
+    )
+
+    answer: list[str] = []
+    with pytest.raises(RuntimeError, match="stream broke"):
+        async for chunk in forks.answer:
+            answer.append(chunk)
+    with pytest.raises(RuntimeError, match="stream broke"):
+        await _collect(forks.reasoning)
+
+    assert answer == ["partial"]
+
+
+async def test_aclose_ends_a_waiting_consumer() -> None:
+    async def _never_ending() -> AsyncIterator[AgentEvent]:
+        await asyncio.Event().wait()
+        yield _delta("text_delta", "unreachable")
+
+    forks = fork_response_stream(_never_ending())
+    waiter = asyncio.create_task(_collect(forks.answer))
+    await asyncio.sleep(0)
+    await forks.aclose()
+
+    assert await waiter == []
@@ -0,0 +1,99 @@
+"""Tests for live token-usage updates during streaming."""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AssistantMessage,
+    AssistantMessageEvent,
+    MessageEndEvent,
+    MessageUpdateEvent,
+    TextContent,
+)
+
+from tunacode.types import UsageMetrics, UsageUpdate
+
+from tunacode.core.agents.helpers import _TinyAgentStreamState
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.session import StateManager
+
+
+def _usage(input_tokens: int, output_tokens: int, cost: float = 0.0) -> dict[str, object]:
+    usage = UsageMetrics(
+        input=input_tokens, output=output_tokens, total_tokens=input_tokens + output_tokens
+    )
+    usage.cost.total = cost
+    return usage.to_dict()
+
+
+def _setup() -> tuple[RequestOrchestrator, _TinyAgentStreamState, list[UsageUpdate]]:
+    state_manager = StateManager()
+    updates: list[UsageUpdate] = []
+    orchestrator = RequestOrchestrator(
+        message="test",
+        model="openai/gpt-4o",
+        state_manager=state_manager,
+        streaming_callback=None,
+        usage_callback=updates.append,
+    )
+    state = _TinyAgentStreamState(
+        runtime=state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+    )
+    return orchestrator, state, updates
+
+
+async def _update(
+    orchestrator: RequestOrchestrator, state: _TinyAgentStreamState, usage: object
+) -> None:
+    partial = AssistantMessage(content=[TextContent(text="x")], usage=usage)
+    event = MessageUpdateEvent(
+        assistant_message_event=AssistantMessageEvent(type="text_delta", delta="x", partial=partial)
+    )
+    await orchestrator._handle_stream_message_update(
+        event, agent=None, state=state, baseline_message_count=0
+    )
+
+
+async def _end(
+    orchestrator: RequestOrchestrator, state: _TinyAgentStreamState, usage: object
+) -> None:
+    message = AssistantMessage(content=[TextContent(text="done")], usage=usage)
+    await orchestrator._handle_stream_message_end(
+        MessageEndEvent(message=message), agent=None, state=state, baseline_message_count=0
+    )
+
+
+async def test_running_usage_is_reported_then_the_final_usage() -> None:
+    orchestrator, state, updates = _setup()
+    session = orchestrator.state_manager.session
+    session.usage.session_total_usage = UsageMetrics.from_dict(_usage(100, 10, cost=1.0))
+
+    await _update(orchestrator, state, _usage(50, 5, cost=0.5))
+    await _update(orchestrator, state, _usage(50, 5, cost=0.5))
+    await _update(orchestrator, state, _usage(50, 3, cost=0.4))
+    await _update(orchestrator, state, _usage(50, 9, cost=0.6))
+    await _end(orchestrator, state, _usage(50, 12, cost=0.7))
+
+    assert [update.call_usage.output for update in updates] == [5, 9, 12]
+    assert [update.final for update in updates] == [False, False, True]
+    assert [update.session_total.output for update in updates] == [15, 19, 22]
+    assert session.usage.session_total_usage.output == 22
+    assert session.usage.session_total_usage.cost.total == 1.7
+    assert updates[-1].session_total is not session.usage.session_total_usage
+    assert state.running_usage is None
+
+
+async def test_provider_without_running_usage_gets_only_the_final_event() -> None:
+    orchestrator, state, updates = _setup()
+
+    await _update(orchestrator, state, None)
+    await _update(orchestrator, state, _usage(0, 0))
+    await _update(orchestrator, state, {"output": 3})
+    await _end(orchestrator, state, _usage(20, 4))
+
+    assert len(updates) == 1
+    assert updates[0].final is True
+    assert updates[0].call_usage.total_tokens == 24
@@ -0,0 +1,87 @@
+"""Tests for the settings.system_prompt override and prefix/suffix layers."""
+
+from __future__ import annotations
+
+import copy
+from types import SimpleNamespace
+from unittest.mock import MagicMock
+
+import pytest
+
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.configuration.user_config import validate_user_config
+
+from tunacode.core.agents.agent_components import agent_config
+from tunacode.core.agents.agent_components import system_prompt as system_prompt_module
+from tunacode.core.session import StateManager
+
+
+class _FakeAgent:
+    def __init__(self, options: object) -> None:
+        self.options = options
+        self._state = SimpleNamespace(system_prompt=None)
+
+    def set_system_prompt(self, system_prompt: str) -> None:
+        self._state.system_prompt = system_prompt
+
+    def set_model(self, model: object) -> None:
+        self.model = model
+
+    def set_tools(self, tools: list[object]) -> None:
+        self.tools = tools
+
+
+def _build_prompt(monkeypatch: pytest.MonkeyPatch, **layers: str) -> tuple[StateManager, MagicMock]:
+    logger = MagicMock()
+    tools = [SimpleNamespace(name="read_file"), SimpleNamespace(name="hashline_edit")]
+    monkeypatch.setattr(agent_config, "get_logger", lambda: logger)
+    monkeypatch.setattr(system_prompt_module, "get_logger", lambda: logger)
+    monkeypatch.setattr(agent_config, "Agent", _FakeAgent)
+    monkeypatch.setattr(agent_config, "get_cached_models_registry", lambda: None)
+    monkeypatch.setattr(agent_config, "load_models_registry", lambda: {})
+    monkeypatch.setattr(agent_config, "list_skill_summaries", lambda: [])
+    monkeypatch.setattr(agent_config, "resolve_selected_skills", lambda _names: [])
+    monkeypatch.setattr(agent_config, "load_system_prompt", lambda _base_path, model=None: "SYS")
+    monkeypatch.setattr(agent_config, "load_tunacode_context", lambda: "CTX")
+    monkeypatch.setattr(agent_config, "get_max_tokens", lambda _model=None: 4096)
+    monkeypatch.setattr(agent_config, "_build_tools", lambda **kwargs: tools)
+    monkeypatch.setattr(agent_config, "_build_tinyagent_model", lambda model, config: object())
+
+    state_manager = StateManager()
+    state_manager.session.selected_skill_names = []
+    state_manager.session.user_config["settings"]["system_prompt"].update(layers)
+    agent_config.get_or_create_agent(state_manager.session.current_model, state_manager)
+    return state_manager, logger
+
+
+def test_override_replaces_the_base_prompt_inside_prefix_and_suffix(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager, logger = _build_prompt(
+        monkeypatch, override="Review code with read_file only.", prefix="PRE", suffix="POST"
+    )
+
+    system_prompt = state_manager.session.system_prompt
+    assert system_prompt.startswith("PRE\n\nReview code with read_file only.CTX")
+    assert system_prompt.endswith("\n\nPOST\n")
+    assert "SYS" not in system_prompt
+    warnings = [call.args[0] for call in logger.warning.call_args_list]
+    assert warnings == [
+        "settings.system_prompt.override replaces the built-in tool-usage instructions "
+        "and never mentions: hashline_edit"
+    ]
+
+
+def test_empty_layers_keep_the_built_in_prompt(monkeypatch: pytest.MonkeyPatch) -> None:
+    state_manager, logger = _build_prompt(monkeypatch)
+
+    assert state_manager.session.system_prompt.startswith("SYSCTX")
+    logger.warning.assert_not_called()
+
+
+def test_system_prompt_settings_must_be_strings() -> None:
+    config = copy.deepcopy(DEFAULT_USER_CONFIG)
+    config["settings"]["system_prompt"]["prefix"] = 3  # type: ignore[typeddict-item]
+
+    with pytest.raises(TypeError, match="settings.system_prompt.prefix must be a string"):
+        validate_user_config(config)
@@ -0,0 +1,85 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.core.agents.agent_components.task_tool import (
+    TASK_DECOMPOSITION_LABEL,
+    build_task_tool,
+    task_decomposition_providers,
+)
+from tunacode.core.session import StateManager
+from tunacode.core.session.tasks import (
+    TASK_DONE,
+    TASK_IN_PROGRESS,
+    TASK_PENDING,
+    TaskStore,
+    TaskTransitionError,
+)
+
+
+def test_status_transitions_are_validated() -> None:
+    store = TaskStore()
+    task = store.create("write parser")
+
+    with pytest.raises(TaskTransitionError, match="pending to done"):
+        store.update(task.task_id, status=TASK_DONE)
+    store.update(task.task_id, status=TASK_IN_PROGRESS)
+    store.update(task.task_id, status=TASK_DONE)
+    with pytest.raises(TaskTransitionError, match="done to pending"):
+        store.update(task.task_id, status=TASK_PENDING)
+    with pytest.raises(TaskTransitionError, match="Unknown task id"):
+        store.update("99", status=TASK_IN_PROGRESS)
+
+
+@pytest.mark.asyncio
+async def test_tasks_tool_creates_updates_and_lists() -> None:
+    store = TaskStore()
+    tool = build_task_tool(lambda: store)
+
+    async def _call(args: dict[str, object]) -> dict[str, object]:
+        result = await tool.execute("c1", args, None, lambda _update: None)
+        return json.loads(result.content[0].text)
+
+    await _call({"action": "create", "titles": ["read code", "fix bug"]})
+    await _call({"action": "update", "id": "1", "status": TASK_IN_PROGRESS})
+    listed = await _call({"action": "list", "status": TASK_IN_PROGRESS})
+
+    assert [task["title"] for task in listed["tasks"]] == ["read code"]
+    with pytest.raises(ToolRetryError, match="cannot move"):
+        await _call({"action": "update", "id": "2", "status": TASK_DONE})
+
+
+@pytest.mark.asyncio
+async def test_tasks_survive_session_save_and_resume(
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    monkeypatch.setenv("XDG_DATA_HOME", str(tmp_path))
+    state_manager = StateManager()
+    session = state_manager.session
+    session.project_id = "project-test"
+    first = session.tasks.create("read code")
+    session.tasks.create("fix bug")
+    session.tasks.update(first.task_id, status=TASK_IN_PROGRESS)
+    session.tasks.update(first.task_id, status=TASK_DONE)
+    assert await state_manager.save_session() is True
+
+    resumed = StateManager()
+    assert await resumed.load_session(session.session_id) is True
+
+    statuses = [(task.title, task.status) for task in resumed.session.tasks.list()]
+    assert statuses == [("read code", TASK_DONE), ("fix bug", TASK_PENDING)]
+    assert resumed.session.tasks.create("ship").task_id == "3"
+
+
+def test_decomposition_prompt_is_only_added_when_enabled() -> None:
+    assert task_decomposition_providers(False) == []
+    (provider,) = task_decomposition_providers(True)
+
+    assert provider.label == TASK_DECOMPOSITION_LABEL
+    assert "tasks" in provider.render(None)  # type: ignore[arg-type]
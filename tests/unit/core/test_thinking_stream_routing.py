@@ -2,8 +2,15 @@
 
 from __future__ import annotations
 
+import copy
+
+import pytest
 from tinyagent.agent_types import AssistantMessageEvent, MessageUpdateEvent
 
+from tunacode.configuration import user_config
+
+from tunacode.core.agents.agent_components.agent_streaming import THINKING_SEGMENT_SEPARATOR
+from tunacode.core.agents.helpers import _TinyAgentStreamState
 from tunacode.core.agents.main import RequestOrchestrator
 from tunacode.core.session import StateManager
 
@@ -106,3 +113,71 @@ async def test_non_delta_or_invalid_events_are_ignored() -> None:
     assert streamed == []
     assert thought_chunks == []
     assert state_manager.session._debug_raw_stream_accum == ""
+
+
+async def test_thinking_resumed_after_answer_text_starts_a_new_segment() -> None:
+    streamed: list[str] = []
+    thought_chunks: list[str] = []
+    orchestrator, _ = _build_orchestrator(streaming_chunks=streamed, thinking_chunks=thought_chunks)
+    state = _TinyAgentStreamState(
+        runtime=orchestrator.state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+    )
+
+    for event_type, delta in [
+        ("thinking_delta", "plan "),
+        ("thinking_delta", "first"),
+        ("text_delta", "Step one."),
+        ("thinking_delta", "now verify"),
+    ]:
+        event = MessageUpdateEvent(
+            assistant_message_event=AssistantMessageEvent(type=event_type, delta=delta)
+        )
+        await orchestrator._handle_message_update(event, state=state)
+
+    assert thought_chunks == ["plan ", "first", "\n\nnow verify"]
+    assert streamed == ["Step one."]
+
+
+async def test_segment_separator_prefixes_only_the_first_delta_after_text() -> None:
+    thought_chunks: list[str] = []
+    orchestrator, _ = _build_orchestrator(streaming_chunks=[], thinking_chunks=thought_chunks)
+    state = _TinyAgentStreamState(
+        runtime=orchestrator.state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+    )
+
+    for event_type, delta in [
+        ("text_delta", "Intro."),
+        ("thinking_delta", "a"),
+        ("thinking_delta", "b"),
+        ("text_delta", "More."),
+        ("thinking_delta", "c"),
+    ]:
+        event = MessageUpdateEvent(
+            assistant_message_event=AssistantMessageEvent(type=event_type, delta=delta)
+        )
+        await orchestrator._handle_message_update(event, state=state)
+
+    assert thought_chunks == [
+        f"{THINKING_SEGMENT_SEPARATOR}a",
+        "b",
+        f"{THINKING_SEGMENT_SEPARATOR}c",
+    ]
+
+
+def test_show_thoughts_setting_seeds_the_session_toggle(monkeypatch: pytest.MonkeyPatch) -> None:
+    def _load_hidden(defaults: dict) -> dict:
+        config = copy.deepcopy(defaults)
+        config["settings"]["show_thoughts"] = False
+        return config
+
+    monkeypatch.setattr(user_config, "load_config_with_defaults", _load_hidden)
+
+    assert StateManager().session.show_thoughts is False
@@ -7,9 +7,10 @@ import httpx
 import pytest
 from tinyagent.agent_types import Context, Model, SimpleStreamOptions
 
-from tunacode.constants import ENV_OPENAI_BASE_URL
+from tunacode.constants import ENV_OPENAI_BASE_URL, BackoffStrategy
 
 from tunacode.core.agents.agent_components import agent_config
+from tunacode.core.agents.agent_components.backoff import BackoffPolicy
 from tunacode.core.compaction import controller as compaction_controller
 from tunacode.core.compaction.controller import (
     CompactionController,
@@ -211,7 +212,12 @@ async def test_build_stream_fn_retries_transient_http_errors(
     monkeypatch.setattr(agent_config, "stream_alchemy_openai_completions", _fake_stream)
     monkeypatch.setattr(agent_config, "_sleep_with_delay", _fake_sleep)
 
-    stream_fn = agent_config._build_stream_fn(request_delay=0.0, max_tokens=None, max_retries=3)
+    stream_fn = agent_config._build_stream_fn(
+        request_delay=0.0,
+        max_tokens=None,
+        max_retries=3,
+        backoff=BackoffPolicy(strategy=BackoffStrategy.NONE),
+    )
 
     result = await stream_fn(Model(), Context(), SimpleStreamOptions(api_key="sk-test"))
 
@@ -343,7 +349,7 @@ async def test_compaction_generate_summary_uses_alchemy_stream(
         return _FakeResponse()
 
     monkeypatch.setattr(compaction_controller, "stream_alchemy_openai_completions", _fake_stream)
-    monkeypatch.setattr(compaction_controller, "get_max_tokens", lambda: 321)
+    monkeypatch.setattr(compaction_controller, "get_max_tokens", lambda _model=None: 321)
 
     summary = await controller._generate_summary("Summarize this", None)
 
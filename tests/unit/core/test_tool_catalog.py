@@ -0,0 +1,53 @@
+from __future__ import annotations
+
+from tinyagent.agent_types import AgentTool
+
+from tunacode.core.agents.tool_catalog import (
+    BUILTIN_TOOL_SOURCE,
+    list_tools,
+    merge_tool_sources,
+)
+
+
+def _tool(name: str, description: str = "") -> AgentTool:
+    return AgentTool(
+        name=name,
+        label=name,
+        description=description,
+        parameters={"type": "object", "properties": {"path": {"type": "string"}}},
+    )
+
+
+def test_list_tools_reports_builtin_tools_with_schemas() -> None:
+    tools = list_tools()
+    names = [tool.name for tool in tools]
+
+    assert "bash" in names
+    assert "read_file" in names
+    assert len(names) == len(set(names))
+    for tool in tools:
+        assert tool.source == BUILTIN_TOOL_SOURCE
+        assert not tool.namespaced
+        assert tool.description
+        assert tool.parameters.get("type") == "object"
+
+
+def test_merge_tool_sources_prefixes_colliding_non_builtin_tools() -> None:
+    merged = merge_tool_sources(
+        {
+            BUILTIN_TOOL_SOURCE: [_tool("read_file")],
+            "docs": [_tool("read_file"), _tool("search")],
+            "web": [_tool("search"), _tool("fetch")],
+        }
+    )
+
+    assert [(tool.source, tool.name) for tool in merged] == [
+        (BUILTIN_TOOL_SOURCE, "read_file"),
+        ("docs", "docs__read_file"),
+        ("docs", "docs__search"),
+        ("web", "web__search"),
+        ("web", "fetch"),
+    ]
+    assert merged[2].tool_name == "search"
+    assert merged[2].namespaced
+    assert not merged[4].namespaced
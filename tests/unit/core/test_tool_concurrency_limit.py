@@ -84,7 +84,9 @@ def test_should_stop_after_turn_updates_runtime_and_stops_after_limit() -> None:
     assert should_stop(message, [], AgentContext(), []) is False
     assert state_manager.session.runtime.iteration_count == 1
     assert state_manager.session.runtime.current_iteration == 1
+    assert state_manager.session.runtime.step_limit_reached is False
 
     assert should_stop(message, [], AgentContext(), []) is True
     assert state_manager.session.runtime.iteration_count == 2
     assert state_manager.session.runtime.current_iteration == 2
+    assert state_manager.session.runtime.step_limit_reached is True
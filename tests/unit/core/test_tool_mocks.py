@@ -0,0 +1,81 @@
+"""Tests for the test-only tool mock registry."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+from tinyagent.agent_types import AgentTool, JsonObject
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.core.agents.agent_components import agent_tools, tool_mocks
+from tunacode.core.agents.agent_components.tool_mocks import (
+    TOOL_MOCKS_ENV_VAR,
+    register_tool_mock,
+)
+
+
+def _isolate(monkeypatch: pytest.MonkeyPatch, *, enabled: bool = True) -> None:
+    monkeypatch.setattr(tool_mocks, "_registry", tool_mocks._MockRegistry())
+    if enabled:
+        monkeypatch.setenv(TOOL_MOCKS_ENV_VAR, "1")
+    else:
+        monkeypatch.delenv(TOOL_MOCKS_ENV_VAR, raising=False)
+
+
+def _tools() -> dict[str, AgentTool]:
+    return {tool.name: tool for tool in agent_tools._build_tools()}
+
+
+async def _call(tool: AgentTool, args: JsonObject) -> str:
+    result = await tool.execute("call-1", args, None, lambda _update: None)
+    return result.content[0].text
+
+
+async def test_matching_call_plays_the_script_then_falls_through(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _isolate(monkeypatch)
+    target = tmp_path / "real.txt"
+    target.write_text("real contents\n", encoding="utf-8")
+    mock = register_tool_mock(
+        "read_file", ["first canned", "second canned"], args={"filepath": str(target)}
+    )
+    read_file = _tools()["read_file"]
+
+    assert await _call(read_file, {"filepath": str(target)}) == "first canned"
+    assert await _call(read_file, {"filepath": str(target), "offset": 1}) == "second canned"
+    assert "real contents" in await _call(read_file, {"filepath": str(target)})
+    assert mock.calls == [{"filepath": str(target)}, {"filepath": str(target), "offset": 1}]
+
+
+async def test_args_filter_and_scripted_errors(monkeypatch: pytest.MonkeyPatch) -> None:
+    _isolate(monkeypatch)
+    register_tool_mock("bash", "all tests passed")
+    failing = register_tool_mock(
+        "bash", ToolRetryError("3 failed, 12 passed"), args={"command": "pytest"}
+    )
+    bash = _tools()["bash"]
+
+    with pytest.raises(ToolRetryError, match="3 failed"):
+        await _call(bash, {"command": "pytest"})
+    assert await _call(bash, {"command": "pytest"}) == "all tests passed"
+    assert len(failing.calls) == 1
+
+
+async def test_mocks_are_ignored_unless_enabled(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _isolate(monkeypatch, enabled=False)
+    target = tmp_path / "real.txt"
+    target.write_text("real contents\n", encoding="utf-8")
+    mock = register_tool_mock("read_file", "canned")
+
+    assert "real contents" in await _call(_tools()["read_file"], {"filepath": str(target)})
+    assert mock.calls == []
+
+
+def test_empty_script_is_rejected() -> None:
+    with pytest.raises(ValueError, match="at least one result"):
+        register_tool_mock("bash", [])
@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AgentMessage,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    ToolResultMessage,
+    UserMessage,
+)
+
+from tunacode.utils.messaging import estimate_messages_tokens, get_content
+
+from tunacode.core.compaction.tool_output_budget import (
+    MIN_TOOL_RESULT_CHARS,
+    fit_tool_results_to_budget,
+    max_input_tokens,
+)
+
+
+def _history(*tool_outputs: str) -> list[AgentMessage]:
+    messages: list[AgentMessage] = [
+        UserMessage(content=[TextContent(text="run the build")], timestamp=None)
+    ]
+    for index, output in enumerate(tool_outputs):
+        call_id = f"call-{index}"
+        messages.append(
+            AssistantMessage(
+                content=[ToolCallContent(id=call_id, name="bash", arguments={"command": "make"})],
+                stop_reason="tool_calls",
+                timestamp=None,
+            )
+        )
+        messages.append(
+            ToolResultMessage(
+                tool_call_id=call_id,
+                tool_name="bash",
+                content=[TextContent(text=output)],
+                timestamp=None,
+            )
+        )
+    return messages
+
+
+def test_max_input_tokens_subtracts_reserve_and_response_limit() -> None:
+    assert max_input_tokens(100_000, reserve_fraction=0.1, max_output_tokens=8_000) == 82_000
+    assert max_input_tokens(100_000, reserve_fraction=0.0, max_output_tokens=None) == 100_000
+    assert max_input_tokens(1_000, reserve_fraction=0.5, max_output_tokens=4_000) == 0
+
+
+def test_huge_tool_output_is_trimmed_to_leave_room_for_the_response() -> None:
+    context_window = 32_000
+    budget = max_input_tokens(context_window, reserve_fraction=0.25, max_output_tokens=4_000)
+    history = _history("ok", "x" * 400_000)
+
+    fitted = fit_tool_results_to_budget(history, budget)
+
+    assert estimate_messages_tokens(fitted) <= budget
+    assert get_content(fitted[2]) == "ok"
+    trimmed = get_content(fitted[4])
+    assert trimmed.startswith("x" * MIN_TOOL_RESULT_CHARS)
+    assert "characters trimmed to fit the context window" in trimmed
+    assert get_content(history[4]) == "x" * 400_000
+
+
+def test_history_within_budget_is_returned_unchanged() -> None:
+    history = _history("small output")
+
+    assert fit_tool_results_to_budget(history, 10_000) is history
@@ -0,0 +1,147 @@
+"""Tests for runtime-registered tool plugins."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+from tinyagent.agent_types import AgentTool, JsonObject
+
+from tunacode.constants import CommandRisk
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools.utils import command_risk
+from tunacode.tools.utils.command_risk import classify_tool_call
+
+from tunacode.core.agents.agent_components import agent_tools, tool_plugins
+from tunacode.core.agents.agent_components.tool_plugins import (
+    register_tool_plugin,
+    registered_tool_plugins,
+    unregister_tool_plugin,
+)
+from tunacode.core.agents.tool_catalog import list_tools
+
+
+class _EchoPlugin:
+    def __init__(self, name: str = "echo") -> None:
+        self._name = name
+        self.calls: list[JsonObject] = []
+
+    def name(self) -> str:
+        return self._name
+
+    def schema(self) -> JsonObject:
+        return {
+            "type": "object",
+            "description": "Echo the text back.",
+            "additionalProperties": False,
+            "properties": {"text": {"type": "string"}, "path": {"type": "string"}},
+            "required": ["text"],
+        }
+
+    async def invoke(self, args: JsonObject) -> str:
+        self.calls.append(args)
+        return f"echo: {args['text']}"
+
+
+def _isolate(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(tool_plugins, "_registry", tool_plugins._PluginRegistry())
+    monkeypatch.setattr(command_risk, "_extension_tool_risks", {})
+
+
+def _tools(*, safe_mode: bool = False) -> dict[str, AgentTool]:
+    return {tool.name: tool for tool in agent_tools._build_tools(safe_mode=safe_mode)}
+
+
+async def _call(tool: AgentTool, args: JsonObject) -> str:
+    result = await tool.execute("call-1", args, None, lambda _update: None)
+    return result.content[0].text
+
+
+async def test_registered_plugin_is_offered_and_invoked(monkeypatch: pytest.MonkeyPatch) -> None:
+    _isolate(monkeypatch)
+    plugin = _EchoPlugin()
+    register_tool_plugin(plugin)
+
+    tool = _tools()["echo"]
+
+    assert tool.description == "Echo the text back."
+    assert await _call(tool, {"text": "hi"}) == "echo: hi"
+    assert [(info.name, info.source) for info in list_tools()][-1] == ("echo", "plugin")
+    assert unregister_tool_plugin("echo") is True
+    assert "echo" not in _tools()
+
+
+async def test_invalid_arguments_are_refused_before_invoke(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    plugin = _EchoPlugin()
+    register_tool_plugin(plugin)
+    tool = _tools()["echo"]
+
+    with pytest.raises(ToolRetryError, match=r"'text' is required; 'extra' is not a known"):
+        await _call(tool, {"extra": 1})
+    with pytest.raises(ToolRetryError, match="'text' must be of type string"):
+        await _call(tool, {"text": 3})
+    assert plugin.calls == []
+
+
+async def test_path_arguments_are_jailed_to_the_workspace(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _isolate(monkeypatch)
+    monkeypatch.chdir(tmp_path)
+    register_tool_plugin(_EchoPlugin(), path_arguments=["path"])
+    tool = _tools()["echo"]
+
+    assert await _call(tool, {"text": "ok", "path": "notes/new.md"}) == "echo: ok"
+    with pytest.raises(ToolRetryError, match="outside the working directory"):
+        await _call(tool, {"text": "no", "path": "../secrets.txt"})
+
+
+def test_colliding_plugin_names_are_namespaced_and_builtins_kept(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _isolate(monkeypatch)
+    register_tool_plugin(_EchoPlugin("read_file"), risk=CommandRisk.READ_ONLY)
+    register_tool_plugin(_EchoPlugin("echo"))
+    register_tool_plugin(_EchoPlugin("echo"), source="docs")
+
+    tools = _tools()
+
+    assert tools["read_file"].description != "Echo the text back."
+    assert {"plugin__read_file", "plugin__echo", "docs__echo"} <= set(tools)
+    assert "echo" not in tools
+    assert classify_tool_call("plugin__read_file", {}) is CommandRisk.READ_ONLY
+    assert classify_tool_call("docs__echo", {}) is CommandRisk.WRITE
+
+
+async def test_safe_mode_offers_only_read_only_plugins(monkeypatch: pytest.MonkeyPatch) -> None:
+    _isolate(monkeypatch)
+    register_tool_plugin(_EchoPlugin("lookup"), risk=CommandRisk.READ_ONLY)
+    register_tool_plugin(_EchoPlugin("deploy"), risk=CommandRisk.DESTRUCTIVE)
+
+    tools = _tools(safe_mode=True)
+
+    assert "deploy" not in tools
+    assert await _call(tools["lookup"], {"text": "x"}) == "echo: x"
+
+
+@pytest.mark.parametrize(
+    ("kwargs", "message"),
+    [
+        ({"source": "builtin"}, "is reserved"),
+        ({"source": "bad source"}, "source must match"),
+    ],
+)
+def test_registration_rejects_reserved_or_invalid_sources(
+    monkeypatch: pytest.MonkeyPatch, kwargs: dict[str, str], message: str
+) -> None:
+    _isolate(monkeypatch)
+
+    with pytest.raises(ValueError, match=message):
+        register_tool_plugin(_EchoPlugin(), **kwargs)
+    with pytest.raises(ValueError, match="name must match"):
+        register_tool_plugin(_EchoPlugin("has space"))
+    assert registered_tool_plugins() == []
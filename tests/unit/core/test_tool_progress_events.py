@@ -0,0 +1,58 @@
+"""Tests for live tool progress steps emitted from the tool loop."""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import ToolExecutionEndEvent, ToolExecutionStartEvent
+
+from tunacode.types import ToolProgress
+from tunacode.types.callbacks import STEP_COMPLETED, STEP_FAILED, STEP_STARTED
+
+from tunacode.core.agents.helpers import _TinyAgentStreamState
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.session import StateManager
+
+
+async def test_tool_loop_reports_each_step_under_a_stable_id() -> None:
+    steps: list[ToolProgress] = []
+    orchestrator = RequestOrchestrator(
+        message="test",
+        model="openai/gpt-4o",
+        state_manager=StateManager(),
+        streaming_callback=None,
+        tool_progress_callback=steps.append,
+    )
+    state = _TinyAgentStreamState(
+        runtime=orchestrator.state_manager.session.runtime,
+        baseline_message_count=0,
+        tool_start_times={},
+        active_tool_call_ids=set(),
+        batch_tool_call_ids=set(),
+    )
+    handler_kwargs = {"agent": None, "state": state, "baseline_message_count": 0}
+
+    await orchestrator._handle_stream_tool_execution_start(
+        ToolExecutionStartEvent(
+            tool_call_id="c1", tool_name="bash", args={"command": "uv run   pytest -q"}
+        ),
+        **handler_kwargs,
+    )
+    await orchestrator._handle_stream_tool_execution_start(
+        ToolExecutionStartEvent(tool_call_id="c2", tool_name="read_file", args={}),
+        **handler_kwargs,
+    )
+    await orchestrator._handle_stream_tool_execution_end(
+        ToolExecutionEndEvent(tool_call_id="c2", tool_name="read_file", is_error=True),
+        **handler_kwargs,
+    )
+    await orchestrator._handle_stream_tool_execution_end(
+        ToolExecutionEndEvent(tool_call_id="c1", tool_name="bash", is_error=False),
+        **handler_kwargs,
+    )
+
+    assert steps == [
+        ToolProgress("c1", "bash: uv run pytest -q", STEP_STARTED),
+        ToolProgress("c2", "read_file", STEP_STARTED),
+        ToolProgress("c2", "read_file", STEP_FAILED),
+        ToolProgress("c1", "bash: uv run pytest -q", STEP_COMPLETED),
+    ]
+    assert state.tool_progress_labels == {}
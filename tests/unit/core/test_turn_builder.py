@@ -0,0 +1,64 @@
+"""Tests for building and submitting a user turn fluently."""
+
+from __future__ import annotations
+
+from typing import Any
+
+import pytest
+
+from tunacode.exceptions import ModelConfigurationError, ValidationError
+from tunacode.types import ModelName
+
+from tunacode.core.agents import turn_builder
+from tunacode.core.agents.turn_builder import TurnBuilder
+from tunacode.core.session import StateManager
+
+DEFAULT_MODEL = ModelName("openrouter:openai/gpt-4.1")
+CHEAP_MODEL = ModelName("openrouter:openai/gpt-4.1-mini")
+
+
+async def _noop_stream(_chunk: str) -> None:
+    return None
+
+
+async def test_submit_passes_the_assembled_turn_to_process_request(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    state_manager.session.user_config["recent_models"] = [CHEAP_MODEL]
+    calls: list[tuple[tuple[Any, ...], dict[str, Any]]] = []
+
+    async def _fake_process_request(*args: Any, **kwargs: Any) -> str:
+        calls.append((args, kwargs))
+        return "agent"
+
+    monkeypatch.setattr(turn_builder, "process_request", _fake_process_request)
+
+    result = await (
+        TurnBuilder(state_manager, DEFAULT_MODEL)
+        .text("first line")
+        .text("second line")
+        .model_override(CHEAP_MODEL)
+        .on_text(_noop_stream)
+        .raw()
+        .submit()
+    )
+
+    assert result == "agent"
+    args, kwargs = calls[0]
+    assert args == ("first line\nsecond line", DEFAULT_MODEL, state_manager)
+    assert kwargs["model_override"] == CHEAP_MODEL
+    assert kwargs["streaming_callback"] is _noop_stream
+    assert kwargs["apply_affixes"] is False
+
+
+def test_build_rejects_an_empty_message() -> None:
+    with pytest.raises(ValidationError, match="A turn needs message text"):
+        TurnBuilder(StateManager(), DEFAULT_MODEL).text("   ").build()
+
+
+def test_build_rejects_an_unknown_model_override() -> None:
+    builder = TurnBuilder(StateManager(), DEFAULT_MODEL).text("hi")
+
+    with pytest.raises(ModelConfigurationError, match="per-turn model override"):
+        builder.model_override(ModelName("nope:model")).build()
@@ -0,0 +1,115 @@
+from __future__ import annotations
+
+import asyncio
+import sys
+
+import pytest
+from tinyagent.agent_types import ToolExecutionStartEvent
+
+from tunacode.exceptions import TurnDeadlineExceededError
+from tunacode.utils.system.process_group import AGENT_TURN_SCOPE, new_process_group_kwargs
+
+from tunacode.core.agents.agent_components.turn_deadline import TurnDeadline
+from tunacode.core.agents.helpers import _TinyAgentStreamState
+from tunacode.core.agents.main import RequestOrchestrator
+from tunacode.core.session import StateManager
+
+
+def _orchestrator(deadline: float, notices: list[str]) -> RequestOrchestrator:
+    state_manager = StateManager()
+    settings = state_manager.session.user_config["settings"]
+    settings["turn_deadline"] = deadline
+    settings["global_request_timeout"] = 0.0
+    return RequestOrchestrator(
+        message="test",
+        model="openai/gpt-4o",
+        state_manager=state_manager,
+        streaming_callback=None,
+        notice_callback=notices.append,
+    )
+
+
+async def test_deadline_cancels_running_tool_and_reaps_its_process(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    notices: list[str] = []
+    orchestrator = _orchestrator(0.2, notices)
+    spawned: list[asyncio.subprocess.Process] = []
+
+    async def _stuck_tool_turn() -> None:
+        state = _TinyAgentStreamState(
+            runtime=orchestrator.state_manager.session.runtime,
+            baseline_message_count=0,
+            tool_start_times={},
+            active_tool_call_ids=set(),
+            batch_tool_call_ids=set(),
+        )
+        await orchestrator._handle_stream_tool_execution_start(
+            ToolExecutionStartEvent(
+                tool_call_id="c1", tool_name="bash", args={"command": "sleep 60"}
+            ),
+            agent=None,
+            state=state,
+            baseline_message_count=0,
+        )
+        process = await asyncio.create_subprocess_exec(
+            sys.executable, "-c", "import time; time.sleep(60)", **new_process_group_kwargs()
+        )
+        AGENT_TURN_SCOPE.register(process)
+        spawned.append(process)
+        await process.wait()
+
+    monkeypatch.setattr(orchestrator, "_run_impl", _stuck_tool_turn)
+
+    with pytest.raises(TurnDeadlineExceededError) as exc_info:
+        await orchestrator.run()
+
+    assert exc_info.value.running == ["bash: sleep 60"]
+    assert notices == ["Turn deadline exceeded after 0.2s while running: bash: sleep 60."]
+    assert await asyncio.wait_for(spawned[0].wait(), timeout=5) != 0
+    assert len(AGENT_TURN_SCOPE) == 0
+
+
+async def test_deadline_names_the_model_request_when_no_tool_runs(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    orchestrator = _orchestrator(0.05, [])
+
+    async def _slow_model() -> None:
+        await asyncio.sleep(60)
+
+    monkeypatch.setattr(orchestrator, "_run_impl", _slow_model)
+
+    with pytest.raises(TurnDeadlineExceededError, match="while running: model request"):
+        await orchestrator.run()
+
+
+async def test_unrelated_timeout_inside_the_turn_is_not_a_deadline() -> None:
+    deadline = TurnDeadline(60.0)
+
+    with pytest.raises(TimeoutError):
+        async with deadline.scope():
+            raise TimeoutError("tool timed out")
+
+    assert deadline.expired is False
+
+
+async def test_reset_rearms_the_deadline_mid_turn() -> None:
+    deadline = TurnDeadline(0.2)
+
+    async with deadline.scope():
+        await asyncio.sleep(0.15)
+        deadline.reset()
+        await asyncio.sleep(0.15)
+
+    assert deadline.expired is False
+
+
+async def test_no_deadline_when_disabled() -> None:
+    deadline = TurnDeadline(None)
+
+    async with deadline.scope():
+        deadline.reset()
+        await asyncio.sleep(0)
+
+    assert deadline.running() == ["model request"]
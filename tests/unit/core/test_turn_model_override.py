@@ -0,0 +1,50 @@
+from __future__ import annotations
+
+import pytest
+
+from tunacode.exceptions import ModelConfigurationError
+from tunacode.types import ModelName
+
+from tunacode.core.agents import main as agent_main
+from tunacode.core.session import StateManager
+
+DEFAULT_MODEL = ModelName("openrouter:openai/gpt-4.1")
+CHEAP_MODEL = ModelName("openrouter:openai/gpt-4.1-mini")
+
+
+def test_override_must_be_configured_or_in_registry(monkeypatch: pytest.MonkeyPatch) -> None:
+    state_manager = StateManager()
+    state_manager.session.user_config["recent_models"] = [CHEAP_MODEL]
+    monkeypatch.setattr(agent_main, "is_known_model", lambda model: model == "openai:gpt-4o")
+
+    assert agent_main.resolve_turn_model(DEFAULT_MODEL, None, state_manager) == DEFAULT_MODEL
+    assert agent_main.resolve_turn_model(DEFAULT_MODEL, CHEAP_MODEL, state_manager) == CHEAP_MODEL
+    assert agent_main.resolve_turn_model(DEFAULT_MODEL, "openai:gpt-4o", state_manager) == (
+        "openai:gpt-4o"
+    )
+    with pytest.raises(ModelConfigurationError, match="per-turn model override"):
+        agent_main.resolve_turn_model(DEFAULT_MODEL, ModelName("nope:model"), state_manager)
+
+
+@pytest.mark.asyncio
+async def test_override_applies_to_one_request_and_keeps_session_default(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    state_manager = StateManager()
+    state_manager.session.current_model = DEFAULT_MODEL
+    state_manager.session.user_config["recent_models"] = [CHEAP_MODEL]
+    served: list[str] = []
+
+    async def _fake_run(self: agent_main.RequestOrchestrator) -> object:
+        served.append(self.model)
+        return object()
+
+    monkeypatch.setattr(agent_main.RequestOrchestrator, "run", _fake_run)
+
+    await agent_main.process_request(
+        "quick", DEFAULT_MODEL, state_manager, model_override=CHEAP_MODEL
+    )
+    await agent_main.process_request("hard", DEFAULT_MODEL, state_manager)
+
+    assert served == [CHEAP_MODEL, DEFAULT_MODEL]
+    assert state_manager.session.current_model == DEFAULT_MODEL
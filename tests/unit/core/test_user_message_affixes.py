@@ -0,0 +1,43 @@
+from __future__ import annotations
+
+import pytest
+
+from tunacode.types import ModelName
+
+from tunacode.core.agents import main
+from tunacode.core.agents.helpers import apply_user_message_affixes
+from tunacode.core.session import StateManager
+
+
+def test_affixes_wrap_the_message_in_separate_paragraphs() -> None:
+    wrapped = apply_user_message_affixes(
+        "fix the bug", prefix="Context: repo X.", suffix="Be brief."
+    )
+
+    assert wrapped == "Context: repo X.\n\nfix the bug\n\nBe brief."
+    assert apply_user_message_affixes("fix the bug", prefix="", suffix="  ") == "fix the bug"
+
+
+@pytest.mark.asyncio
+async def test_process_request_applies_affixes_unless_skipped(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    sent: list[str] = []
+
+    class _FakeOrchestrator:
+        def __init__(self, message: str, *args: object) -> None:
+            sent.append(message)
+
+        async def run(self) -> None:
+            return None
+
+    monkeypatch.setattr(main, "RequestOrchestrator", _FakeOrchestrator)
+    monkeypatch.setattr(main, "resolve_turn_model", lambda model, override, state: model)
+    state_manager = StateManager()
+    state_manager.session.user_config["settings"]["user_message_suffix"] = "Be brief."
+    model = ModelName("openai:gpt-4.1")
+
+    await main.process_request("hello", model, state_manager)
+    await main.process_request("hello", model, state_manager, apply_affixes=False)
+
+    assert sent == ["hello\n\nBe brief.", "hello"]
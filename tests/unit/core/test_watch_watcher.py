@@ -0,0 +1,100 @@
+"""Tests for the watch-mode file watcher."""
+
+from __future__ import annotations
+
+import os
+from pathlib import Path
+
+from tunacode.core.watch.watcher import FileWatcher
+
+
+def _touch(path: Path, content: str, mtime_ns: int) -> None:
+    path.write_text(content, encoding="utf-8")
+    os.utime(path, ns=(mtime_ns, mtime_ns))
+
+
+def test_watcher_debounces_and_renders_changed_files(tmp_path: Path) -> None:
+    test_file = tmp_path / "test_app.py"
+    _touch(test_file, "a", 1_000)
+    (tmp_path / "notes.md").write_text("ignored", encoding="utf-8")
+    watcher = FileWatcher(root=tmp_path, globs=["*.py"], prompt_template="Fix {files}")
+
+    _touch(test_file, "ab", 2_000)
+    assert watcher.poll(now=10.0) is None
+    assert watcher.poll(now=10.5) is None
+    (tmp_path / "notes.md").write_text("still ignored", encoding="utf-8")
+
+    assert watcher.poll(now=12.0) == "Fix test_app.py"
+    assert watcher.poll(now=20.0) is None
+
+
+def test_watcher_applies_cooldown_and_max_runs(tmp_path: Path) -> None:
+    test_file = tmp_path / "test_app.py"
+    _touch(test_file, "v0", 1_000)
+    watcher = FileWatcher(root=tmp_path, globs=["*.py"], cooldown_seconds=30.0, max_runs=2)
+
+    _touch(test_file, "v1", 2_000)
+    watcher.poll(now=0.0)
+    assert watcher.poll(now=5.0) is not None
+
+    _touch(test_file, "v2", 3_000)
+    watcher.poll(now=6.0)
+    assert watcher.poll(now=10.0) is None
+    assert watcher.poll(now=40.0) is not None
+    assert watcher.exhausted
+
+    _touch(test_file, "v3", 4_000)
+    assert watcher.poll(now=100.0) is None
+    assert watcher.poll(now=200.0) is None
+
+
+def test_rebaseline_ignores_changes_made_during_a_turn(tmp_path: Path) -> None:
+    test_file = tmp_path / "test_app.py"
+    _touch(test_file, "v0", 1_000)
+    watcher = FileWatcher(root=tmp_path, globs=["*.py"])
+
+    _touch(test_file, "edited by the agent", 2_000)
+    watcher.rebaseline()
+
+    assert watcher.poll(now=0.0) is None
+    assert watcher.poll(now=100.0) is None
+
+
+def test_scan_matches_globs_and_skips_ignored_paths(tmp_path: Path) -> None:
+    root = tmp_path / "ws"
+    for relative in (
+        "app.py",
+        "src/pkg/core.py",
+        "src/pkg/notes.md",
+        "node_modules/dep/index.py",
+        ".venv/lib/site.py",
+        "build/generated.py",
+    ):
+        path = root / relative
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text("x", encoding="utf-8")
+    (root / ".gitignore").write_text("build/\n", encoding="utf-8")
+
+    top_level = FileWatcher(root=root, globs=["*.py"])
+    recursive = FileWatcher(root=root, globs=["**/*.py"])
+    scoped = FileWatcher(root=root, globs=["src/**/*.md"])
+
+    assert sorted(top_level.scan()) == ["app.py"]
+    assert sorted(recursive.scan()) == ["app.py", "src/pkg/core.py"]
+    assert sorted(scoped.scan()) == ["src/pkg/notes.md"]
+
+
+def test_poll_and_rebaseline_accept_a_precomputed_snapshot(tmp_path: Path) -> None:
+    root = tmp_path / "ws"
+    root.mkdir()
+    test_file = root / "test_app.py"
+    _touch(test_file, "v0", 1_000)
+    watcher = FileWatcher(root=root, globs=["*.py"], prompt_template="Fix {files}")
+
+    _touch(test_file, "v1", 2_000)
+    watcher.rebaseline(watcher.scan())
+    assert watcher.poll(now=0.0) is None
+
+    _touch(test_file, "v2", 3_000)
+    assert watcher.poll(now=1.0, snapshot=watcher.scan()) is None
+    assert watcher.poll(now=5.0, snapshot=watcher.scan()) == "Fix test_app.py"
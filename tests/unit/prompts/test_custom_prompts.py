@@ -0,0 +1,172 @@
+from __future__ import annotations
+
+from pathlib import Path
+from types import SimpleNamespace
+from typing import cast
+
+import pytest
+
+from tunacode.configuration.defaults import DEFAULT_USER_CONFIG
+from tunacode.prompts.custom_prompts import (
+    CustomPromptError,
+    find_custom_prompt,
+    list_custom_prompts,
+    render_custom_prompt,
+    resolve_prompt_roots,
+)
+from tunacode.prompts.prompt_schema import PromptArgumentSpec
+
+FIX_PROMPT = """---
+description: Fix a failing test
+arg.test: required; The failing test id
+arg.file: The file to look at first
+---
+Fix {{test}}. Start with {{file}}.
+"""
+
+from tunacode.ui.app import TextualReplApp
+from tunacode.ui.commands import handle_command
+
+
+def _write_prompt(root: Path, name: str, body: str) -> None:
+    prompts_dir = root / ".tunacode" / "prompts"
+    prompts_dir.mkdir(parents=True, exist_ok=True)
+    (prompts_dir / f"{name}.md").write_text(body, encoding="utf-8")
+
+
+class _FakeApp:
+    def __init__(self, *, unknown_slash_commands: str = "error") -> None:
+        settings = {**DEFAULT_USER_CONFIG["settings"]}
+        settings["unknown_slash_commands"] = unknown_slash_commands
+        session = SimpleNamespace(user_config={"settings": settings})
+        self.state_manager = SimpleNamespace(session=session)
+        self.submitted: list[str] = []
+        self.notices: list[str] = []
+
+    def submit_user_message(self, text: str) -> None:
+        self.submitted.append(text)
+
+    def notify(self, message: str, **kwargs: object) -> None:
+        self.notices.append(message)
+
+
+def test_project_prompts_shadow_global_ones(tmp_path: Path) -> None:
+    project, home = tmp_path / "project", tmp_path / "home"
+    _write_prompt(home, "review", "global review")
+    _write_prompt(home, "explain", "explain $1")
+    _write_prompt(project, "review", "project review")
+    roots = resolve_prompt_roots(project_root=project, home_directory=home)
+
+    prompt = find_custom_prompt("Review", roots=roots)
+
+    assert prompt is not None and prompt.body == "project review"
+    assert [item.name for item in list_custom_prompts(roots=roots)] == ["explain", "review"]
+
+
+def test_positional_all_and_named_arguments_are_substituted(tmp_path: Path) -> None:
+    _write_prompt(tmp_path, "fix", "Fix $1 in {{ file }} ($@). Missing: [$3]")
+    roots = resolve_prompt_roots(project_root=tmp_path, home_directory=tmp_path / "none")
+    prompt = find_custom_prompt("fix", roots=roots)
+    assert prompt is not None
+
+    rendered = render_custom_prompt(prompt, "'the crash' file=app.py fast")
+
+    assert rendered == "Fix the crash in app.py (the crash fast). Missing: []"
+
+
+@pytest.mark.asyncio
+async def test_slash_name_submits_the_expanded_prompt(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.chdir(tmp_path)
+    _write_prompt(tmp_path, "explain", "Explain $1 simply.")
+    app = _FakeApp()
+
+    assert await handle_command(cast(TextualReplApp, app), "/explain closures") is True
+    assert await handle_command(cast(TextualReplApp, app), "/nope") is True
+
+    assert app.submitted == ["Explain closures simply."]
+    assert app.notices == ["Unknown command: /nope"]
+
+
+@pytest.mark.asyncio
+async def test_unknown_names_pass_through_when_configured(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.chdir(tmp_path)
+    app = _FakeApp(unknown_slash_commands="pass_through")
+
+    assert await handle_command(cast(TextualReplApp, app), "/usr/bin/env is odd") is False
+    assert app.notices == []
+
+
+def test_frontmatter_declares_the_argument_schema(tmp_path: Path) -> None:
+    _write_prompt(tmp_path, "fix", FIX_PROMPT)
+    roots = resolve_prompt_roots(project_root=tmp_path, home_directory=tmp_path / "none")
+    prompt = find_custom_prompt("fix", roots=roots)
+    assert prompt is not None
+
+    assert prompt.schema.description == "Fix a failing test"
+    assert prompt.schema.arguments == (
+        PromptArgumentSpec("test", required=True, description="The failing test id"),
+        PromptArgumentSpec("file", description="The file to look at first"),
+    )
+    assert prompt.schema.usage("fix") == "/fix <test> [file]"
+    assert render_custom_prompt(prompt, "test_login file=auth.py") == (
+        "Fix test_login. Start with auth.py."
+    )
+
+
+@pytest.mark.asyncio
+async def test_missing_required_argument_is_reported_before_submission(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.chdir(tmp_path)
+    _write_prompt(tmp_path, "fix", FIX_PROMPT)
+    app = _FakeApp()
+
+    assert await handle_command(cast(TextualReplApp, app), "/fix file=auth.py") is True
+    assert await handle_command(cast(TextualReplApp, app), "/fix t1 colour=red") is True
+
+    assert app.submitted == []
+    assert app.notices[0].startswith("Missing required argument(s) for /fix: test.")
+    assert app.notices[1].startswith("Unknown argument(s) for /fix: colour.")
+
+
+def test_templates_support_conditionals_includes_and_escapes(tmp_path: Path) -> None:
+    _write_prompt(tmp_path, "header", "Repo rules for $1.")
+    _write_prompt(
+        tmp_path,
+        "ship",
+        "{{> header}}\n{{#if draft}}Open a draft PR.{{else}}Open a PR.{{/if}}"
+        "{{#if 2}} Also $2.{{/if}} Literal \\{{draft}} costs \\$5.",
+    )
+    roots = resolve_prompt_roots(project_root=tmp_path, home_directory=tmp_path / "none")
+    prompt = find_custom_prompt("ship", roots=roots)
+    assert prompt is not None
+
+    assert render_custom_prompt(prompt, "api draft=yes", roots=roots) == (
+        "Repo rules for api.\nOpen a draft PR. Literal {{draft}} costs $5."
+    )
+    assert render_custom_prompt(prompt, "api docs", roots=roots) == (
+        "Repo rules for api.\nOpen a PR. Also docs. Literal {{draft}} costs $5."
+    )
+
+
+def test_include_cycles_and_unbalanced_blocks_are_rejected(tmp_path: Path) -> None:
+    _write_prompt(tmp_path, "a", "A {{> b}}")
+    _write_prompt(tmp_path, "b", "B {{> a}}")
+    _write_prompt(tmp_path, "open", "{{#if x}}never closed")
+    _write_prompt(tmp_path, "plain", "Use {{ mustache }} {{#each}} as-is: $0 $x")
+    roots = resolve_prompt_roots(project_root=tmp_path, home_directory=tmp_path / "none")
+
+    def _render(name: str) -> str:
+        prompt = find_custom_prompt(name, roots=roots)
+        assert prompt is not None
+        return render_custom_prompt(prompt, "", roots=roots)
+
+    with pytest.raises(CustomPromptError, match="Include cycle: a -> b -> a"):
+        _render("a")
+    with pytest.raises(CustomPromptError, match="never closed"):
+        _render("open")
+    assert _render("plain") == "Use  {{#each}} as-is: $0 $x"
@@ -0,0 +1,138 @@
+"""Tests for the structured details the bash tool returns alongside its text."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+from tinyagent.agent_types import ToolResultMessage
+
+from tunacode.constants import FULL_OUTPUT_DETAIL_KEY, CommandPolicy
+from tunacode.exceptions import ToolRetryError
+from tunacode.utils.messaging import estimate_message_tokens, estimate_tokens
+
+from tunacode.tools import bash as bash_module
+
+
+def _allow_all_commands(
+    monkeypatch: pytest.MonkeyPatch,
+    *,
+    limit: int = 10_000,
+    history_limit: int = 50_000,
+    capture_bytes: int = 1_048_576,
+    stream_output: bool = False,
+    shell: tuple[str, list[str]] = ("", []),
+) -> None:
+    monkeypatch.setattr(bash_module, "get_command_policy", lambda _risk: CommandPolicy.ALLOW)
+    monkeypatch.setattr(bash_module, "get_command_limit", lambda: limit)
+    monkeypatch.setattr(bash_module, "get_command_history_limit", lambda: history_limit)
+    monkeypatch.setattr(bash_module, "get_command_capture_bytes", lambda: capture_bytes)
+    monkeypatch.setattr(bash_module, "get_command_stream_output", lambda: stream_output)
+    monkeypatch.setattr(bash_module, "get_command_shell", lambda: shell)
+
+
+async def test_bash_result_details_carry_exit_code_timing_and_cwd(
+    monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+) -> None:
+    _allow_all_commands(monkeypatch)
+
+    result = await bash_module.bash.execute(
+        "call-1", {"command": "echo out; exit 3", "cwd": str(tmp_path)}, None, None
+    )
+
+    assert "Exit Code: 3" in result.content[0].text
+    assert result.details["exit_code"] == 3
+    assert result.details["cwd"] == str(tmp_path)
+    assert result.details["truncated"] is False
+    assert isinstance(result.details["duration_ms"], float)
+
+
+async def test_bash_result_details_flag_truncated_output(monkeypatch: pytest.MonkeyPatch) -> None:
+    _allow_all_commands(monkeypatch, limit=100)
+
+    result = await bash_module.bash.execute(
+        "call-2", {"command": "printf 'x%.0s' $(seq 1 5000)"}, None, None
+    )
+
+    assert result.details["exit_code"] == 0
+    assert result.details["truncated"] is True
+
+
+async def test_bash_keeps_fuller_output_in_details_than_the_model_sees(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _allow_all_commands(monkeypatch, limit=100, history_limit=8_000)
+
+    result = await bash_module.bash.execute(
+        "call-5", {"command": "printf 'x%.0s' $(seq 1 5000)"}, None, None
+    )
+
+    model_text = result.content[0].text
+    history_text = result.details[FULL_OUTPUT_DETAIL_KEY]
+    assert bash_module.CMD_OUTPUT_TRUNCATED in model_text
+    assert bash_module.CMD_OUTPUT_TRUNCATED not in history_text
+    assert "x" * 5000 in history_text
+    message = ToolResultMessage(
+        tool_call_id="call-5",
+        tool_name="bash",
+        content=result.content,
+        details=result.details,
+        timestamp=None,
+    )
+    assert estimate_message_tokens(message) == estimate_tokens(model_text)
+
+    _allow_all_commands(monkeypatch, limit=100, history_limit=1_000)
+    result = await bash_module.bash.execute(
+        "call-6", {"command": "printf 'x%.0s' $(seq 1 5000)"}, None, None
+    )
+    history_text = result.details[FULL_OUTPUT_DETAIL_KEY]
+    assert len(history_text) == 1_000 + len(bash_module.CMD_OUTPUT_TRUNCATED)
+
+
+async def test_bash_keeps_head_and_tail_of_output_beyond_capture_limit(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _allow_all_commands(monkeypatch, capture_bytes=64)
+
+    result = await bash_module.bash.execute(
+        "call-3", {"command": "echo start; seq 1 500; echo end"}, None, None
+    )
+
+    text = result.content[0].text
+    assert "start" in text
+    assert "end" in text
+    assert "[output truncated (64 of " in text
+    assert result.details["truncated"] is True
+    assert result.details["stdout_bytes"] > 64
+
+
+async def test_bash_streams_partial_output_when_enabled(monkeypatch: pytest.MonkeyPatch) -> None:
+    _allow_all_commands(monkeypatch, stream_output=True)
+    updates: list[str] = []
+
+    result = await bash_module.bash.execute(
+        "call-4",
+        {"command": "echo first"},
+        None,
+        lambda partial: updates.append(partial.content[0].text),
+    )
+
+    assert updates == ["first\n"]
+    assert result.details["stdout_bytes"] == len("first\n")
+
+
+async def test_bash_runs_commands_with_the_configured_shell(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    _allow_all_commands(monkeypatch, shell=("sh", ["-c"]))
+
+    result = await bash_module.bash.execute("call-5", {"command": "echo $0"}, None, None)
+
+    assert "STDOUT:\nsh" in result.content[0].text
+
+
+async def test_bash_names_a_missing_shell(monkeypatch: pytest.MonkeyPatch) -> None:
+    _allow_all_commands(monkeypatch, shell=("no-such-shell-xyz", []))
+
+    with pytest.raises(ToolRetryError, match="Shell 'no-such-shell-xyz' not found"):
+        await bash_module.bash.execute("call-6", {"command": "echo hi"}, None, None)
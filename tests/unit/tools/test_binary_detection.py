@@ -0,0 +1,48 @@
+"""Tests for the binary/text sniffing shared by read_file and grep."""
+
+from __future__ import annotations
+
+import codecs
+
+import pytest
+
+from tunacode.tools.utils.binary_detection import GENERIC_BINARY_TYPE, sniff_bytes
+
+
+@pytest.mark.parametrize(
+    ("sample", "detected_type"),
+    [
+        (b"\x89PNG\r\n\x1a\n\x00\x00", "PNG image"),
+        (b"%PDF-1.7\n%\xe2\xe3", "PDF document"),
+        (b"\x7fELF\x02\x01\x01\x00", "ELF executable"),
+        (b"PK\x03\x04\x14\x00", "ZIP archive"),
+        (b"plain\x00text\x01with\x02nuls", GENERIC_BINARY_TYPE),
+        (bytes(range(1, 32)) * 4, GENERIC_BINARY_TYPE),
+    ],
+)
+def test_binary_samples_are_detected_with_type(sample: bytes, detected_type: str) -> None:
+    sniff = sniff_bytes(sample)
+
+    assert sniff.is_binary
+    assert sniff.detected_type == detected_type
+
+
+@pytest.mark.parametrize(
+    ("sample", "encoding"),
+    [
+        ("plain café text\n".encode(), "utf-8"),
+        (b"", "utf-8"),
+        (codecs.BOM_UTF8 + b"bom text", "utf-8-sig"),
+        ("wide text".encode("utf-16"), "utf-16"),
+        ("wide text".encode("utf-32"), "utf-32"),
+        ("no bom here".encode("utf-16-le"), "utf-16-le"),
+        ("no bom here".encode("utf-16-be"), "utf-16-be"),
+        (b"\x1b[31mcolored\x1b[0m\tlog\r\n", "utf-8"),
+    ],
+)
+def test_text_samples_report_their_encoding(sample: bytes, encoding: str) -> None:
+    sniff = sniff_bytes(sample)
+
+    assert not sniff.is_binary
+    assert sniff.encoding == encoding
+    assert isinstance(sample.decode(sniff.encoding), str)
@@ -0,0 +1,200 @@
+"""Tests for bash command risk classification and per-tier policy."""
+
+from __future__ import annotations
+
+import pytest
+
+from tunacode.constants import CommandPolicy, CommandRisk
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools import bash as bash_module
+from tunacode.tools.utils.command_risk import (
+    classify_command,
+    classify_tool_call,
+    explain_command,
+)
+
+
+@pytest.mark.parametrize(
+    ("command", "expected"),
+    [
+        ("ls -la", CommandRisk.READ_ONLY),
+        ("git log --oneline | head -5", CommandRisk.READ_ONLY),
+        ("python -m pytest", CommandRisk.WRITE),
+        ("echo hi > notes.txt", CommandRisk.WRITE),
+        ("curl https://example.com", CommandRisk.NETWORK),
+        ("ls && git push origin main", CommandRisk.NETWORK),
+        ("echo $(wget -qO- example.com)", CommandRisk.NETWORK),
+        ("FOO=1 sudo rm -rf build", CommandRisk.DESTRUCTIVE),
+    ],
+)
+def test_classify_command_picks_riskiest_segment(command: str, expected: CommandRisk) -> None:
+    assert classify_command(command) is expected
+
+
+@pytest.mark.parametrize(
+    ("command", "rule", "matched", "risky_args"),
+    [
+        ("ls && sudo rm -rf build", "destructive program", "rm", ("rm",)),
+        ("git log && git push origin", "network subcommand", "git push", ("git", "push")),
+        ("cat a > out.txt", "output redirect", ">", ("out.txt",)),
+        ("make test", "program not known to be read-only", "make", ("make",)),
+        ("git status", "read_only subcommand", "git status", ("git", "status")),
+    ],
+)
+def test_explain_command_names_the_deciding_rule(
+    command: str, rule: str, matched: str, risky_args: tuple[str, ...]
+) -> None:
+    explanation = explain_command(command)
+
+    assert explanation.risk is classify_command(command)
+    assert (explanation.rule, explanation.matched, explanation.risky_args) == (
+        rule,
+        matched,
+        risky_args,
+    )
+    assert explanation.segment in command
+    assert explanation.reason.endswith(f"-> {explanation.risk.value}")
+
+
+@pytest.mark.parametrize(
+    ("command", "expected", "matched"),
+    [
+        ("FOO=bar BAZ=1 rm -rf build", CommandRisk.DESTRUCTIVE, "rm"),
+        ("env FOO=bar rm -rf build", CommandRisk.DESTRUCTIVE, "rm"),
+        ("env -i -u HOME FOO=bar rm -rf build", CommandRisk.DESTRUCTIVE, "rm"),
+        ("sudo -u root -- rm -rf /", CommandRisk.DESTRUCTIVE, "rm"),
+        ("FOO=bar env cat notes.txt", CommandRisk.READ_ONLY, "cat"),
+        ("FOO=bar", CommandRisk.READ_ONLY, ""),
+        ("sudo -s", CommandRisk.WRITE, "sudo"),
+        ("LD_PRELOAD=/tmp/evil.so ls", CommandRisk.DESTRUCTIVE, "LD_PRELOAD"),
+        ("env DYLD_INSERT_LIBRARIES=x cat a", CommandRisk.DESTRUCTIVE, "DYLD_INSERT_LIBRARIES"),
+        ("export PATH=/tmp/bin; ls", CommandRisk.DESTRUCTIVE, "PATH"),
+    ],
+)
+def test_leading_assignments_and_wrappers_are_stripped_and_checked(
+    command: str, expected: CommandRisk, matched: str
+) -> None:
+    explanation = explain_command(command)
+
+    assert (explanation.risk, explanation.matched) == (expected, matched)
+    if expected is CommandRisk.DESTRUCTIVE and matched.isupper():
+        assert explanation.rule == "dangerous environment assignment"
+
+
+@pytest.mark.parametrize(
+    ("command", "expected", "matched"),
+    [
+        ("cd foo && rm -rf bar", CommandRisk.DESTRUCTIVE, "rm"),
+        ("for f in *; do rm $f; done", CommandRisk.DESTRUCTIVE, "rm"),
+        ("for f in *.py; do wc -l $f; done", CommandRisk.READ_ONLY, "for"),
+        ("if grep -q x a; then curl example.com; fi", CommandRisk.NETWORK, "curl"),
+        ("while true; do ! ls; done", CommandRisk.READ_ONLY, "true"),
+        ("case $x in a) ls;; b) reboot;; esac", CommandRisk.DESTRUCTIVE, "reboot"),
+        ("(cd build && rm -rf out)", CommandRisk.DESTRUCTIVE, "rm"),
+        ("cleanup() { rm -rf tmp; }; cleanup", CommandRisk.DESTRUCTIVE, "rm"),
+        ("diff <(ls a) <(ls b)", CommandRisk.READ_ONLY, "diff"),
+        ("bash -c 'cat a && rm b'", CommandRisk.DESTRUCTIVE, "rm"),
+        ("sh -ec 'git status'", CommandRisk.READ_ONLY, "git status"),
+        ("eval \"wget example.com\"", CommandRisk.NETWORK, "wget"),
+        ("find . -name '*.pyc' | xargs -n 10 rm", CommandRisk.DESTRUCTIVE, "rm"),
+        ("find . -name '*.py' -exec grep -l TODO {} +", CommandRisk.READ_ONLY, "find"),
+        ("find . -name '*.tmp' -exec rm {} \\;", CommandRisk.DESTRUCTIVE, "rm"),
+        ("find . -name '*.tmp' -delete", CommandRisk.DESTRUCTIVE, "-delete"),
+        ("$CLEANER --all", CommandRisk.DESTRUCTIVE, "$CLEANER"),
+        ("bash -c \"bash -c 'bash -c \\\"bash -c ls\\\"'\"", CommandRisk.DESTRUCTIVE, "bash"),
+    ],
+)
+def test_compound_and_nested_commands_take_their_riskiest_part(
+    command: str, expected: CommandRisk, matched: str
+) -> None:
+    explanation = explain_command(command)
+
+    assert (explanation.risk, explanation.matched) == (expected, matched)
+
+
+@pytest.mark.parametrize(
+    ("command", "expected", "matched"),
+    [
+        ("rg --pre 'rm' x", CommandRisk.DESTRUCTIVE, "--pre"),
+        ("rg --pre-glob '*.pdf' --pre cat x", CommandRisk.DESTRUCTIVE, "--pre-glob"),
+        ("less +'!rm x' f", CommandRisk.DESTRUCTIVE, "+!rm x"),
+        ("sort --compress-program=sh in", CommandRisk.DESTRUCTIVE, "--compress-program=sh"),
+        ("sort -o out in", CommandRisk.WRITE, "-o"),
+        ("sort --out=out in", CommandRisk.WRITE, "--out=out"),
+        ("tree -o out", CommandRisk.WRITE, "-o"),
+        ("find . -fprint out", CommandRisk.WRITE, "-fprint"),
+        ("uniq a b", CommandRisk.WRITE, "b"),
+        ("git log --output=x", CommandRisk.WRITE, "--output=x"),
+        ("git diff --output=x", CommandRisk.WRITE, "--output=x"),
+        ("date -s '2020-01-01'", CommandRisk.WRITE, "-s"),
+        ("date 01010000", CommandRisk.WRITE, "01010000"),
+        ("find . -name '*.py' -exec sort -o out {} \\;", CommandRisk.WRITE, "-o"),
+    ],
+)
+def test_read_only_programs_are_raised_by_unsafe_options(
+    command: str, expected: CommandRisk, matched: str
+) -> None:
+    explanation = explain_command(command)
+
+    assert (explanation.risk, explanation.matched) == (expected, matched)
+
+
+@pytest.mark.parametrize(
+    "command",
+    [
+        "rg -n --hidden -g '*.py' -A3 foo src",
+        "grep -rn -e TODO src",
+        "git log --oneline -5 -- src",
+        "git diff --stat -M50% HEAD~1",
+        "find . -name '*.py' -mtime -1 -type f -print",
+        "sort -k2 -n -t, data.csv",
+        "uniq -c -f 1 data.txt",
+        "head -20 notes.txt",
+        "tail -n +5 notes.txt",
+        "date -u +%Y-%m-%d",
+        "less -R +G notes.txt",
+    ],
+)
+def test_read_only_programs_stay_read_only_with_known_safe_options(command: str) -> None:
+    assert classify_command(command) is CommandRisk.READ_ONLY
+
+
+def test_nested_commands_name_the_program_that_ran_them() -> None:
+    explanation = explain_command("sudo bash -c 'rm -rf /'")
+
+    assert explanation.rule == "destructive program via bash"
+    assert explanation.segment == "rm -rf /"
+
+
+async def test_bash_blocks_denied_tier_and_runs_others(monkeypatch: pytest.MonkeyPatch) -> None:
+    def policy(risk: CommandRisk) -> CommandPolicy:
+        return CommandPolicy.DENY if risk is CommandRisk.NETWORK else CommandPolicy.ALLOW
+
+    monkeypatch.setattr(bash_module, "get_command_policy", policy)
+    monkeypatch.setattr(bash_module, "get_command_limit", lambda: 10_000)
+
+    with pytest.raises(ToolRetryError, match="settings.command_policy.network") as blocked:
+        await bash_module.bash.execute("call-1", {"command": "curl example.com"}, None, None)
+    assert "Matched rule: network program 'curl'" in str(blocked.value)
+
+    result = await bash_module.bash.execute("call-2", {"command": "ls"}, None, None)
+    assert "Exit Code: 0" in result.content[0].text
+
+
+@pytest.mark.parametrize(
+    ("tool_name", "arguments", "expected"),
+    [
+        ("read_file", {"filepath": "a.py"}, CommandRisk.READ_ONLY),
+        ("write_file", {"filepath": "a.py"}, CommandRisk.WRITE),
+        ("web_fetch", {"url": "https://example.com"}, CommandRisk.NETWORK),
+        ("bash", {"command": "git status"}, CommandRisk.READ_ONLY),
+        ("bash", {"command": "git push"}, CommandRisk.NETWORK),
+        ("bash", {}, CommandRisk.WRITE),
+        ("unknown_tool", {}, CommandRisk.WRITE),
+    ],
+)
+def test_classify_tool_call_uses_tool_tier_or_bash_command(
+    tool_name: str, arguments: dict[str, object], expected: CommandRisk
+) -> None:
+    assert classify_tool_call(tool_name, arguments) is expected
@@ -0,0 +1,150 @@
+"""Tests for loading and merging command risk rules."""
+
+from __future__ import annotations
+
+import os
+from pathlib import Path
+
+import pytest
+
+from tunacode.constants import CommandRisk
+
+from tunacode.tools.cache_accessors.command_rules_cache import (
+    clear_command_rules_cache,
+    get_command_rules,
+)
+from tunacode.tools.utils.command_risk import classify_command, explain_command
+from tunacode.tools.utils.command_rules import (
+    BUILTIN_COMMAND_RULES,
+    CommandRuleError,
+    build_rule_set,
+    parse_command_rules,
+)
+
+
+def _write(path: Path, text: str) -> Path:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(text, encoding="utf-8")
+    return path
+
+
+def test_user_rules_override_builtins_with_a_warning(tmp_path: Path) -> None:
+    user_file = _write(
+        tmp_path / "user_rules",
+        "# local tools\nread_only make\nread_only   git push  # mirror only\nnetwork terraform\n",
+    )
+
+    rules = build_rule_set(user_file=user_file, project_file=None)
+    builtin_push = next(
+        rule
+        for rule in BUILTIN_COMMAND_RULES
+        if rule.matcher is not None and rule.key[0] == "git" and rule.matcher.matches("push")
+    )
+
+    assert classify_command("make test", rules=rules) is CommandRisk.READ_ONLY
+    assert classify_command("git push origin", rules=rules) is CommandRisk.READ_ONLY
+    assert classify_command("terraform plan", rules=rules) is CommandRisk.NETWORK
+    assert rules.warnings == [
+        f"{user_file}:3: 'read_only git push' overrides '{builtin_push.text}' "
+        f"from {builtin_push.location}"
+    ]
+    assert explain_command("make", rules=rules).rule == f"read_only program rule {user_file}:2"
+
+
+def test_subcommand_rules_take_precedence_over_program_rules(tmp_path: Path) -> None:
+    user_file = _write(tmp_path / "user_rules", "read_only make\ndestructive make clean\n")
+
+    rules = build_rule_set(user_file=user_file, project_file=None)
+
+    assert classify_command("make all", rules=rules) is CommandRisk.READ_ONLY
+    assert classify_command("make clean", rules=rules) is CommandRisk.DESTRUCTIVE
+
+
+def test_set_glob_and_regex_subcommands_match_and_name_misses(tmp_path: Path) -> None:
+    user_file = _write(
+        tmp_path / "user_rules",
+        "read_only gh {status,browse}\nwrite gh run-*\nnetwork gh /(pr|issue)-[a-z]+/\n",
+    )
+
+    rules = build_rule_set(user_file=user_file, project_file=None)
+    missed = explain_command("gh repo delete", rules=rules)
+
+    assert classify_command("gh status", rules=rules) is CommandRisk.READ_ONLY
+    assert classify_command("gh run-tests", rules=rules) is CommandRisk.WRITE
+    assert classify_command("gh pr-list", rules=rules) is CommandRisk.NETWORK
+    assert classify_command("gh pr", rules=rules) is CommandRisk.WRITE
+    assert (missed.risk, missed.matched, missed.risky_args) == (
+        CommandRisk.WRITE,
+        "repo",
+        ("gh", "repo"),
+    )
+    assert missed.rule == "gh subcommand not matched by {status,browse} run-* /(pr|issue)-[a-z]+/"
+
+
+def test_project_patterns_cannot_shadow_riskier_rules(tmp_path: Path) -> None:
+    project_file = _write(tmp_path / "project_rules", "read_only git *\nread_only git {st,log}\n")
+
+    rules = build_rule_set(user_file=None, project_file=project_file)
+
+    assert classify_command("git push", rules=rules) is CommandRisk.NETWORK
+    assert classify_command("git st", rules=rules) is CommandRisk.WRITE
+    assert classify_command("git log", rules=rules) is CommandRisk.READ_ONLY
+    assert [warning.split(":")[1] for warning in rules.warnings] == ["1", "2"]
+
+
+def test_project_rules_may_only_raise_a_tier(tmp_path: Path) -> None:
+    project_file = _write(tmp_path / "project_rules", "read_only rm\nnetwork deploy.sh\n")
+
+    rules = build_rule_set(user_file=None, project_file=project_file)
+
+    assert classify_command("rm -rf build", rules=rules) is CommandRisk.DESTRUCTIVE
+    assert classify_command("deploy.sh staging", rules=rules) is CommandRisk.NETWORK
+    assert rules.warnings == [
+        f"{project_file}:1: ignored 'read_only rm'; project rules cannot lower "
+        "a command below destructive"
+    ]
+
+
+@pytest.mark.parametrize(
+    ("text", "line", "message"),
+    [
+        (
+            "read_only ls\nreadonly cat\n",
+            2,
+            "unknown tier 'readonly' (expected one of read_only, write, network, destructive)",
+        ),
+        ("\n\nnetwork\n", 3, "expected '<tier> <program> [<subcommand>]'"),
+        ("write git commit --amend\n", 1, "expected '<tier> <program> [<subcommand>]'"),
+        ("write ./build.sh\n", 1, "invalid program './build.sh'"),
+        ("read_only git {status,}\n", 1, "invalid subcommand '' in {status,}"),
+        (
+            "network gh /pr-(/\n",
+            1,
+            "invalid regex /pr-(/: missing ), unterminated subpattern at position 3",
+        ),
+    ],
+)
+def test_rule_syntax_errors_name_the_line(text: str, line: int, message: str) -> None:
+    with pytest.raises(CommandRuleError) as exc_info:
+        parse_command_rules(text, source="rules")
+
+    assert exc_info.value.line == line
+    assert str(exc_info.value) == f"rules:{line}: {message}"
+
+
+def test_cached_rules_reload_when_a_rule_file_changes(tmp_path: Path) -> None:
+    clear_command_rules_cache()
+    project_root = tmp_path / "project"
+    rule_file = _write(tmp_path / "home" / ".tunacode" / "command_rules", "read_only make\n")
+
+    def _classify_make() -> CommandRisk:
+        rules = get_command_rules(project_root=project_root, home_directory=tmp_path / "home")
+        return classify_command("make", rules=rules)
+
+    assert _classify_make() is CommandRisk.READ_ONLY
+    rule_file.write_text("destructive make\n", encoding="utf-8")
+    later = rule_file.stat().st_mtime_ns + 1_000_000_000
+    os.utime(rule_file, ns=(later, later))
+
+    assert _classify_make() is CommandRisk.DESTRUCTIVE
+    clear_command_rules_cache()
@@ -0,0 +1,121 @@
+"""Tests for the per-turn edit journal and undo."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+from collections.abc import Iterator
+from pathlib import Path
+
+import pytest
+
+from tunacode.configuration.paths import get_tunacode_home
+from tunacode.exceptions import UndoConflictError
+
+from tunacode.tools import edit_journal
+from tunacode.tools.utils.file_transaction import FileTransaction
+
+
+@pytest.fixture(autouse=True)
+def fresh_journal() -> Iterator[None]:
+    edit_journal.clear()
+    yield
+    edit_journal.clear()
+
+
+def _write(path: Path, content: str) -> None:
+    transaction = FileTransaction()
+    transaction.stage(str(path), content)
+    transaction.commit()
+
+
+def test_undo_restores_original_content_and_removes_created_files(tmp_path: Path) -> None:
+    existing = tmp_path / "existing.txt"
+    existing.write_text("original\n", encoding="utf-8")
+    created = tmp_path / "created.txt"
+
+    edit_journal.begin_turn()
+    _write(existing, "first\n")
+    _write(existing, "second\n")
+    _write(created, "new\n")
+
+    restored = edit_journal.undo_last_turn()
+
+    assert restored == sorted([existing.resolve(), created.resolve()])
+    assert existing.read_text(encoding="utf-8") == "original\n"
+    assert not created.exists()
+    assert edit_journal.undo_last_turn() == []
+
+
+def test_undo_walks_back_one_turn_at_a_time(tmp_path: Path) -> None:
+    target = tmp_path / "target.txt"
+    target.write_text("v0\n", encoding="utf-8")
+
+    edit_journal.begin_turn()
+    _write(target, "v1\n")
+    edit_journal.begin_turn()
+    _write(target, "v2\n")
+    edit_journal.begin_turn()
+
+    edit_journal.undo_last_turn()
+    assert target.read_text(encoding="utf-8") == "v1\n"
+    edit_journal.undo_last_turn()
+    assert target.read_text(encoding="utf-8") == "v0\n"
+
+
+def test_undo_refuses_when_file_changed_externally(tmp_path: Path) -> None:
+    target = tmp_path / "target.txt"
+    target.write_text("v0\n", encoding="utf-8")
+
+    edit_journal.begin_turn()
+    _write(target, "v1\n")
+    target.write_text("edited by hand\n", encoding="utf-8")
+
+    with pytest.raises(UndoConflictError, match="target.txt"):
+        edit_journal.undo_last_turn()
+    assert target.read_text(encoding="utf-8") == "edited by hand\n"
+
+
+def _blob_dir() -> Path:
+    return get_tunacode_home() / edit_journal.JOURNAL_SUBDIR / str(os.getpid())
+
+
+def test_blobs_are_bounded_by_the_retained_turns(tmp_path: Path) -> None:
+    target = tmp_path / "target.txt"
+    target.write_text("v0\n", encoding="utf-8")
+
+    for version in range(1, edit_journal.MAX_JOURNAL_TURNS + 10):
+        edit_journal.begin_turn()
+        _write(target, f"draft {version}\n")
+        _write(target, f"v{version}\n")
+    edit_journal.begin_turn()
+
+    # The journal keeps MAX_JOURNAL_TURNS - 1 edited turns plus the empty current one;
+    # consecutive turns share a version, and overwritten drafts are dropped.
+    retained_turns = edit_journal.MAX_JOURNAL_TURNS - 1
+    assert len(list(_blob_dir().iterdir())) == retained_turns + 1
+
+    edit_journal.undo_last_turn()
+    assert len(list(_blob_dir().iterdir())) == retained_turns
+
+    edit_journal.clear()
+    assert not _blob_dir().exists()
+
+
+def test_blob_dirs_of_dead_processes_are_swept(tmp_path: Path) -> None:
+    finished = subprocess.run(
+        [sys.executable, "-c", "import os; print(os.getpid())"],
+        capture_output=True,
+        text=True,
+        check=True,
+    )
+    stale = get_tunacode_home() / edit_journal.JOURNAL_SUBDIR / finished.stdout.strip()
+    stale.mkdir(parents=True)
+    (stale / "blob").write_bytes(b"old")
+
+    edit_journal.begin_turn()
+    _write(tmp_path / "target.txt", "new\n")
+
+    assert not stale.exists()
+    assert _blob_dir().is_dir()
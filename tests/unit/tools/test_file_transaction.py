@@ -0,0 +1,125 @@
+"""Tests for all-or-nothing multi-file writes."""
+
+from __future__ import annotations
+
+import os
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import FileOperationError
+
+from tunacode.tools.utils import file_transaction
+from tunacode.tools.utils.file_transaction import NOTHING_APPLIED_MESSAGE, FileTransaction
+
+
+@pytest.fixture
+def work_dir(tmp_path: Path) -> Path:
+    work = tmp_path / "work"
+    work.mkdir()
+    return work
+
+
+def test_commit_writes_every_staged_file(work_dir: Path) -> None:
+    existing = work_dir / "existing.txt"
+    existing.write_text("old\n", encoding="utf-8")
+    created = work_dir / "created.txt"
+
+    transaction = FileTransaction()
+    transaction.stage(str(existing), "new\n")
+    transaction.stage(str(created), "fresh\n")
+    transaction.commit()
+
+    assert existing.read_text(encoding="utf-8") == "new\n"
+    assert created.read_text(encoding="utf-8") == "fresh\n"
+    assert sorted(path.name for path in work_dir.iterdir()) == ["created.txt", "existing.txt"]
+
+
+def test_staging_failure_leaves_targets_untouched(work_dir: Path) -> None:
+    first = work_dir / "first.txt"
+    first.write_text("original\n", encoding="utf-8")
+
+    transaction = FileTransaction()
+    transaction.stage(str(first), "changed\n")
+    transaction.stage(str(work_dir / "missing-dir" / "second.txt"), "x")
+
+    with pytest.raises(FileOperationError, match=NOTHING_APPLIED_MESSAGE):
+        transaction.commit()
+
+    assert first.read_text(encoding="utf-8") == "original\n"
+    assert sorted(path.name for path in work_dir.iterdir()) == ["first.txt"]
+
+
+def test_swap_failure_rolls_back_files_already_replaced(
+    work_dir: Path,
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    first = work_dir / "first.txt"
+    second = work_dir / "second.txt"
+    third = work_dir / "third.txt"
+    first.write_text("one\n", encoding="utf-8")
+    second.write_text("two\n", encoding="utf-8")
+
+    real_replace = os.replace
+    calls: list[str] = []
+
+    def _flaky_replace(source: Path, target: Path) -> None:
+        calls.append(Path(target).name)
+        if Path(target).name == "third.txt":
+            raise PermissionError("denied")
+        real_replace(source, target)
+
+    monkeypatch.setattr(file_transaction.os, "replace", _flaky_replace)
+
+    transaction = FileTransaction()
+    transaction.stage(str(first), "ONE\n")
+    transaction.stage(str(second), "TWO\n")
+    transaction.stage(str(third), "THREE\n")
+
+    with pytest.raises(FileOperationError, match=NOTHING_APPLIED_MESSAGE):
+        transaction.commit()
+
+    assert calls == ["first.txt", "second.txt", "third.txt"]
+    assert first.read_text(encoding="utf-8") == "one\n"
+    assert second.read_text(encoding="utf-8") == "two\n"
+    assert not third.exists()
+    assert sorted(path.name for path in work_dir.iterdir()) == ["first.txt", "second.txt"]
+
+
+def test_commit_writes_through_symlinks_and_hard_links(work_dir: Path) -> None:
+    real = work_dir / "real.txt"
+    real.write_text("old\n", encoding="utf-8")
+    link = work_dir / "link.txt"
+    link.symlink_to(real)
+    other = work_dir / "other.txt"
+    other.write_text("old\n", encoding="utf-8")
+    hard_link = work_dir / "hard.txt"
+    os.link(other, hard_link)
+
+    transaction = FileTransaction()
+    transaction.stage(str(link), "via symlink\n")
+    transaction.stage(str(hard_link), "via hard link\n")
+    transaction.commit()
+
+    assert link.is_symlink()
+    assert real.read_text(encoding="utf-8") == "via symlink\n"
+    assert other.read_text(encoding="utf-8") == "via hard link\n"
+    assert os.path.samefile(other, hard_link)
+
+
+def test_staging_the_same_file_by_different_paths_keeps_one_entry(
+    work_dir: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    real = work_dir / "real.txt"
+    real.write_text("old\n", encoding="utf-8")
+    link = work_dir / "link.txt"
+    link.symlink_to(real)
+    monkeypatch.chdir(work_dir)
+
+    transaction = FileTransaction()
+    transaction.stage_delete(str(link))
+    transaction.stage("real.txt", "kept\n")
+    transaction.commit()
+
+    assert link.is_symlink()
+    assert real.read_text(encoding="utf-8") == "kept\n"
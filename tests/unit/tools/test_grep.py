@@ -0,0 +1,106 @@
+"""Tests for the grep tool's in-process search."""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools import grep as grep_module
+from tunacode.tools.grep import grep
+from tunacode.tools.utils import ripgrep_search
+
+
+async def _grep(args: dict[str, object]) -> dict[str, object]:
+    result = await grep.execute("call-1", args, None, lambda _update: None)
+    return json.loads(result.content[0].text)
+
+
+@pytest.fixture
+def workspace(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> Path:
+    monkeypatch.setattr(ripgrep_search, "get_ripgrep_binary_path", lambda: None)
+    monkeypatch.chdir(tmp_path)
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "app.py").write_text("import os\n\ndef main():\n    return 1\n")
+    (tmp_path / "notes.md").write_text("def in markdown\ncafé main\n", encoding="utf-8")
+    (tmp_path / "blob.bin").write_bytes(b"def main\x00\x01\x02")
+    return tmp_path
+
+
+async def test_grep_returns_structured_matches_with_context(workspace: Path) -> None:
+    payload = await _grep({"pattern": r"def main", "include": ["*.py"], "context_lines": 1})
+
+    assert payload["matches"] == [
+        {
+            "file": "src/app.py",
+            "line": 3,
+            "text": "def main():",
+            "before": [""],
+            "after": ["    return 1"],
+        }
+    ]
+
+
+async def test_grep_skips_binary_files_and_decodes_utf8(workspace: Path) -> None:
+    payload = await _grep({"pattern": "main"})
+
+    files = {match["file"] for match in payload["matches"]}
+    assert files == {"src/app.py", "notes.md"}
+    assert payload["binary_files_skipped"] == 1
+    assert any(match["text"] == "café main" for match in payload["matches"])
+
+
+async def test_grep_searches_utf16_files_as_text(workspace: Path) -> None:
+    (workspace / "wide.txt").write_text("def main in utf-16\n", encoding="utf-16")
+
+    payload = await _grep({"pattern": "main", "include": ["*.txt"]})
+
+    assert payload["binary_files_skipped"] == 0
+    assert [match["text"] for match in payload["matches"]] == ["def main in utf-16"]
+
+
+async def test_grep_caps_matches_and_respects_gitignore(workspace: Path) -> None:
+    (workspace / ".gitignore").write_text("notes.md\n", encoding="utf-8")
+
+    payload = await _grep({"pattern": "def", "max_matches": 1})
+
+    assert payload["truncated"] is False
+    assert [match["file"] for match in payload["matches"]] == ["src/app.py"]
+
+    (workspace / "src" / "more.py").write_text("def a():\n    pass\n")
+    capped = await _grep({"pattern": "def", "max_matches": 1})
+    assert capped["truncated"] is True
+    assert len(capped["matches"]) == 1
+
+
+async def test_grep_rejects_invalid_regex(workspace: Path) -> None:
+    with pytest.raises(ToolRetryError, match="Invalid regular expression"):
+        await _grep({"pattern": "("})
+
+
+async def test_grep_stops_walking_once_the_match_cap_is_hit(workspace: Path) -> None:
+    for index in range(100):
+        (workspace / "src" / f"mod{index:03}.py").write_text("def f():\n")
+
+    payload = await _grep({"pattern": "def", "include": ["mod*.py"], "max_matches": 1})
+
+    assert payload["truncated"] is True
+    assert payload["files_searched"] <= grep_module.MAX_PENDING_SEARCHES + 1
+
+
+async def test_grep_defaults_max_matches_to_ripgrep_max_results(
+    workspace: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    settings = {"timeout": 10, "max_results": 1, "enable_metrics": True}
+    monkeypatch.setattr(grep_module, "get_ripgrep_settings", lambda: settings)
+    searches_before = grep_module.metrics.search_count
+
+    payload = await _grep({"pattern": "main"})
+
+    assert len(payload["matches"]) == 1
+    assert payload["truncated"] is True
+    assert grep_module.metrics.search_count == searches_before + 1
+    assert grep_module.metrics.fallback_count >= 1
@@ -0,0 +1,62 @@
+"""Tests for the current-region hint attached to hashline_edit failures."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+from tinyagent.agent_types import AgentTool
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools import line_cache
+from tunacode.tools.hashline import content_hash
+from tunacode.tools.hashline_edit import hashline_edit
+from tunacode.tools.read_file import read_file
+
+
+@pytest.fixture(autouse=True)
+def clear_line_cache() -> None:
+    line_cache.clear()
+    yield
+    line_cache.clear()
+
+
+async def _execute(tool: AgentTool, args: dict[str, object]) -> str:
+    result = await tool.execute("call-1", args, None, lambda _update: None)
+    return result.content[0].text
+
+
+async def test_stale_ref_failure_includes_on_disk_region_and_refreshes_cache(
+    tmp_path: Path,
+) -> None:
+    target = tmp_path / "module.py"
+    target.write_text("".join(f"line {number}\n" for number in range(1, 41)), encoding="utf-8")
+    await _execute(read_file, {"filepath": str(target)})
+
+    target.write_text(
+        "".join(f"changed {number}\n" for number in range(1, 41)),
+        encoding="utf-8",
+    )
+    stale_ref = f"20:{content_hash('line 20')}"
+    line_cache.update_lines(str(target), {20: "cached but stale"})
+
+    with pytest.raises(ToolRetryError) as exc_info:
+        await _execute(
+            hashline_edit,
+            {"filepath": str(target), "operation": "replace", "line": stale_ref, "new": "x"},
+        )
+
+    message = str(exc_info.value)
+    assert "(lines 10-30 of 40)" in message
+    assert f"20:{content_hash('changed 20')}|changed 20" in message
+    region_lines = message.split("<file>\n", 1)[1].splitlines()
+    assert region_lines[0].startswith("10:")
+    assert region_lines[-2].startswith("30:")
+
+    fresh_ref = f"20:{content_hash('changed 20')}"
+    await _execute(
+        hashline_edit,
+        {"filepath": str(target), "operation": "replace", "line": fresh_ref, "new": "edited"},
+    )
+    assert target.read_text(encoding="utf-8").splitlines()[19] == "edited"
@@ -0,0 +1,113 @@
+"""Tests for the structured EditError raised by refused hashline_edit calls."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+
+from tunacode.constants import EditFailureKind
+from tunacode.exceptions import EditError, ToolRetryError
+
+from tunacode.tools import hashline_edit as hashline_edit_module
+from tunacode.tools import line_cache
+from tunacode.tools.hashline import content_hash
+from tunacode.tools.hashline_edit import hashline_edit
+from tunacode.tools.read_file import read_file
+
+
+async def _execute(args: dict[str, object]) -> None:
+    await hashline_edit.execute("call-1", args, None, lambda _update: None)
+
+
+async def _read(target: Path) -> None:
+    line_cache.clear()
+    await read_file.execute("call-0", {"filepath": str(target)}, None, lambda _update: None)
+
+
+async def test_context_mismatch_reports_expected_and_actual(tmp_path: Path) -> None:
+    target = tmp_path / "module.py"
+    target.write_text("alpha\nbeta\n", encoding="utf-8")
+    await _read(target)
+    target.write_text("alpha\nBETA\n", encoding="utf-8")
+    line_cache.update_lines(str(target), {2: "cached beta"})
+
+    with pytest.raises(EditError) as exc_info:
+        await _execute(
+            {"filepath": str(target), "operation": "replace", "line": f"2:{content_hash('beta')}"}
+        )
+
+    error = exc_info.value
+    assert isinstance(error, ToolRetryError)
+    assert str(error).startswith("hashline_edit failed (context_mismatch): File has changed")
+    assert error.diagnostic() == {
+        "kind": "context_mismatch",
+        "filepath": str(target),
+        "line": 2,
+        "expected": content_hash("beta"),
+        "actual": content_hash("cached beta"),
+        "actual_line": "BETA",
+    }
+
+
+async def test_missing_file_and_bad_ref_have_their_own_kinds(tmp_path: Path) -> None:
+    missing = tmp_path / "missing.py"
+    with pytest.raises(EditError) as exc_info:
+        await _execute({"filepath": str(missing), "operation": "replace", "line": "1:ab"})
+    assert exc_info.value.diagnostic() == {"kind": "file_not_found", "filepath": str(missing)}
+
+    target = tmp_path / "present.py"
+    target.write_text("one\n", encoding="utf-8")
+    with pytest.raises(EditError) as exc_info:
+        await _execute({"filepath": str(target), "operation": "replace", "line": "one"})
+    assert exc_info.value.kind is EditFailureKind.INVALID_REF
+    assert exc_info.value.line is None
+
+
+async def test_out_of_range_and_unread_lines_carry_the_line(tmp_path: Path) -> None:
+    target = tmp_path / "short.py"
+    target.write_text("one\ntwo\n", encoding="utf-8")
+    line_cache.clear()
+
+    with pytest.raises(EditError) as exc_info:
+        await _execute({"filepath": str(target), "operation": "replace", "line": "2:ab"})
+    assert (exc_info.value.kind, exc_info.value.line) == (EditFailureKind.NOT_READ, 2)
+
+    await _read(target)
+    line_cache.update_lines(str(target), {5: "phantom"})
+    with pytest.raises(EditError) as exc_info:
+        await _execute(
+            {
+                "filepath": str(target),
+                "operation": "replace",
+                "line": f"5:{content_hash('phantom')}",
+                "new": "x",
+            }
+        )
+    assert exc_info.value.kind is EditFailureKind.OUT_OF_RANGE
+    assert "out of range (file has 2 lines)" in str(exc_info.value)
+
+
+async def test_permission_error_is_reported_as_final(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    target = tmp_path / "locked.py"
+    target.write_text("one\n", encoding="utf-8")
+    await _read(target)
+
+    def _denied(*_args: object) -> None:
+        raise PermissionError(13, "Permission denied")
+
+    monkeypatch.setattr(hashline_edit_module, "_write_file_lines", _denied)
+
+    with pytest.raises(EditError, match="Do not retry this edit") as exc_info:
+        await _execute(
+            {
+                "filepath": str(target),
+                "operation": "replace",
+                "line": f"1:{content_hash('one')}",
+                "new": "two",
+            }
+        )
+    assert exc_info.value.kind is EditFailureKind.PERMISSION_DENIED
+    assert target.read_text(encoding="utf-8") == "one\n"
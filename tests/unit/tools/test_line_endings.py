@@ -0,0 +1,65 @@
+"""Tests for line-ending preservation in hashline_edit."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+from tunacode.tools import line_cache
+from tunacode.tools.hashline import content_hash
+from tunacode.tools.hashline_edit import hashline_edit
+from tunacode.tools.read_file import read_file
+from tunacode.tools.utils.line_endings import carry_line_endings, dominant_ending, split_lines
+
+
+async def _edit(target: Path, args: dict[str, object]) -> None:
+    line_cache.clear()
+    await read_file.execute("call-0", {"filepath": str(target)}, None, lambda _update: None)
+    await hashline_edit.execute(
+        "call-1", {"filepath": str(target), **args}, None, lambda _update: None
+    )
+
+
+async def test_crlf_file_matches_lf_refs_and_stays_crlf(tmp_path: Path) -> None:
+    target = tmp_path / "windows.txt"
+    target.write_bytes(b"one\r\ntwo\r\nthree\r\n")
+
+    ref = f"2:{content_hash('two')}"
+    await _edit(
+        target,
+        {"operation": "replace_range", "start": ref, "end": ref, "new": "TWO\ntwo and a half\n"},
+    )
+
+    assert target.read_bytes() == b"one\r\nTWO\r\ntwo and a half\r\nthree\r\n"
+
+
+async def test_mixed_endings_are_kept_on_unchanged_lines(tmp_path: Path) -> None:
+    target = tmp_path / "mixed.txt"
+    target.write_bytes(b"a\r\nb\nc\r\nd\n")
+
+    await _edit(target, {"operation": "replace", "line": f"3:{content_hash('c')}", "new": "C"})
+
+    assert target.read_bytes() == b"a\r\nb\nC\r\nd\n"
+
+
+async def test_insert_after_a_last_line_without_newline(tmp_path: Path) -> None:
+    target = tmp_path / "no_eol.txt"
+    target.write_bytes(b"first\r\nlast")
+
+    await _edit(
+        target, {"operation": "insert_after", "after": f"2:{content_hash('last')}", "new": "tail"}
+    )
+
+    assert target.read_bytes() == b"first\r\nlast\r\ntail"
+
+
+def test_split_lines_and_dominant_ending() -> None:
+    assert split_lines("a\r\nb\rc\nd") == (["a", "b", "c", "d"], ["\r\n", "\r", "\n", ""])
+    assert split_lines("") == ([], [])
+    assert dominant_ending(["\r\n", "\n", "\r\n", ""]) == "\r\n"
+    assert dominant_ending([""]) == "\n"
+
+
+def test_carry_line_endings_for_removed_lines() -> None:
+    endings = carry_line_endings(["a", "b", "c"], ["\n", "\r\n", ""], ["a", "c"])
+
+    assert endings == ["\n", ""]
@@ -0,0 +1,69 @@
+"""Tests for the list_directory tool."""
+
+from __future__ import annotations
+
+import json
+import os
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools.list_directory import list_directory
+
+
+async def _list(args: dict[str, object]) -> dict[str, object]:
+    result = await list_directory.execute("call-1", args, None, lambda _update: None)
+    return json.loads(result.content[0].text)
+
+
+@pytest.fixture
+def workspace(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> Path:
+    root = tmp_path / "ws"
+    root.mkdir()
+    monkeypatch.chdir(root)
+    (root / "b.txt").write_text("bee", encoding="utf-8")
+    (root / "a.txt").write_text("a", encoding="utf-8")
+    (root / "sub").mkdir()
+    os.symlink(root / "sub", root / "link")
+    return root
+
+
+async def test_list_directory_returns_sorted_structured_entries(workspace: Path) -> None:
+    payload = await _list({})
+
+    entries = payload["entries"]
+    assert [entry["name"] for entry in entries] == ["a.txt", "b.txt", "link", "sub"]
+    assert entries[1]["type"] == "file"
+    assert entries[1]["size"] == 3
+    assert entries[2]["type"] == "symlink"
+    assert entries[2]["is_symlink"] is True
+    assert entries[2]["symlink_target"] == str(workspace / "sub")
+    assert entries[3]["type"] == "directory"
+    assert payload["next_offset"] is None
+
+
+async def test_list_directory_paginates(workspace: Path) -> None:
+    first_page = await _list({"limit": 3})
+    second_page = await _list({"offset": first_page["next_offset"], "limit": 3})
+
+    assert first_page["total"] == 4
+    assert first_page["next_offset"] == 3
+    assert [entry["name"] for entry in second_page["entries"]] == ["sub"]
+    assert second_page["next_offset"] is None
+
+
+async def test_list_directory_respects_gitignore_by_default(workspace: Path) -> None:
+    (workspace / ".gitignore").write_text("b.txt\n", encoding="utf-8")
+
+    filtered = await _list({})
+    unfiltered = await _list({"respect_gitignore": False})
+
+    assert "b.txt" not in [entry["name"] for entry in filtered["entries"]]
+    assert "b.txt" in [entry["name"] for entry in unfiltered["entries"]]
+
+
+async def test_list_directory_rejects_paths_outside_working_directory(workspace: Path) -> None:
+    with pytest.raises(ToolRetryError, match="outside the working directory"):
+        await _list({"path": str(workspace.parent)})
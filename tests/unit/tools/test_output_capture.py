@@ -0,0 +1,52 @@
+"""Tests for bounded head-and-tail capture of command output."""
+
+from __future__ import annotations
+
+from tunacode.tools.utils.output_capture import BoundedCapture
+
+
+def test_capture_within_limit_keeps_everything() -> None:
+    capture = BoundedCapture(16)
+    capture.feed(b"hello ")
+    capture.feed(b"world")
+
+    assert capture.text() == "hello world"
+    assert capture.total_bytes == 11
+    assert capture.truncated is False
+
+
+def test_capture_keeps_head_and_tail_and_counts_dropped_bytes() -> None:
+    capture = BoundedCapture(8)
+    for chunk in (b"AAAA", b"-" * 100, b"ZZZZ"):
+        capture.feed(chunk)
+
+    assert capture.total_bytes == 108
+    assert capture.truncated is True
+    assert capture.text() == "AAAA\n...\n[output truncated (8 of 108 bytes)]\n...\nZZZZ"
+
+
+def test_capture_never_splits_a_multibyte_character() -> None:
+    capture = BoundedCapture(6)
+    capture.feed("ééééé".encode())
+
+    text = capture.text()
+    assert "�" not in text
+    assert text.startswith("é")
+    assert text.endswith("é")
+    assert "(4 of 10 bytes)" in text
+
+
+def test_zero_limit_disables_truncation() -> None:
+    capture = BoundedCapture(0)
+    capture.feed(b"x" * 10_000)
+
+    assert capture.truncated is False
+    assert len(capture.text()) == 10_000
+
+
+def test_tail_text_starts_on_a_character_boundary() -> None:
+    capture = BoundedCapture(0)
+    capture.feed("aé".encode())
+
+    assert capture.tail_text(1) == ""
+    assert capture.tail_text(2) == "é"
@@ -0,0 +1,179 @@
+"""Tests for read_file windowing, line ranges, byte limits and binary files."""
+
+from __future__ import annotations
+
+import base64
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools import line_cache
+from tunacode.tools import read_file as read_file_module
+from tunacode.tools.read_file import parse_read_range, read_file
+
+
+def _limits(monkeypatch: pytest.MonkeyPatch, *, max_bytes: int, head: int, tail: int) -> None:
+    monkeypatch.setattr(read_file_module, "get_read_max_bytes", lambda: max_bytes)
+    monkeypatch.setattr(read_file_module, "get_read_window_lines", lambda: (head, tail))
+
+
+def _numbered_file(tmp_path: Path, count: int) -> Path:
+    target = tmp_path / "big.log"
+    target.write_text("".join(f"line {number}\n" for number in range(1, count + 1)))
+    return target
+
+
+async def _read(args: dict[str, object]) -> tuple[str, dict[str, object]]:
+    result = await read_file.execute("call-1", args, None, lambda _update: None)
+    return result.content[0].text, result.details
+
+
+async def test_large_file_is_windowed_with_metadata(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    line_cache.clear()
+    _limits(monkeypatch, max_bytes=100, head=3, tail=2)
+    target = _numbered_file(tmp_path, 50)
+
+    text, details = await _read({"filepath": str(target)})
+
+    body = text.splitlines()
+    assert body[1].endswith("|line 1")
+    assert body[3].endswith("|line 3")
+    assert body[4] == "... (lines 4-48 omitted) ..."
+    assert body[6].endswith("|line 50")
+    assert "(File windowed:" in text
+    assert "Use 'start_line'/'end_line' to read lines 4-48" in text
+    assert details == {
+        "total_bytes": target.stat().st_size,
+        "total_lines": 50,
+        "ranges": [[1, 3], [49, 50]],
+        "windowed": True,
+        "encoding": "utf-8",
+        "ends_with_newline": True,
+    }
+    assert sorted(line_cache.get(str(target)) or {}) == [1, 2, 3, 49, 50]
+
+
+async def test_line_range_pages_through_a_large_file(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=100, head=3, tail=2)
+    target = _numbered_file(tmp_path, 50)
+
+    text, details = await _read({"filepath": str(target), "start_line": 10, "end_line": 12})
+
+    assert [line.split("|", 1)[1] for line in text.splitlines()[1:4]] == [
+        "line 10",
+        "line 11",
+        "line 12",
+    ]
+    assert "beyond line 12" in text
+    assert details["ranges"] == [[10, 12]]
+    assert details["windowed"] is False
+
+
+async def test_max_bytes_stops_the_read_at_a_line_boundary(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = tmp_path / "unicode.txt"
+    target.write_text("é" * 10 + "\n" + "ü" * 10 + "\n" + "end\n", encoding="utf-8")
+
+    text, details = await _read({"filepath": str(target), "offset": 0, "max_bytes": 30})
+
+    assert "é" * 10 in text
+    assert "ü" not in text
+    assert details["ranges"] == [[1, 1]]
+    assert "beyond line 1" in text
+
+
+async def test_small_file_reads_whole_with_total_lines(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = _numbered_file(tmp_path, 5)
+
+    text, details = await _read({"filepath": str(target)})
+
+    assert "(End of file - total 5 lines)" in text
+    assert details["total_lines"] == 5
+    assert details["ranges"] == [[1, 5]]
+
+
+async def test_range_past_end_of_file_is_rejected(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = _numbered_file(tmp_path, 5)
+
+    with pytest.raises(ToolRetryError, match=r"past the end .* \(5 lines\)"):
+        await _read({"filepath": str(target), "start_line": 6})
+
+
+@pytest.mark.parametrize(
+    ("args", "message"),
+    [
+        ({"start_line": 0}, "'start_line' must be >= 1"),
+        ({"start_line": 5, "end_line": 4}, r"end_line \(4\) is before start_line \(5\)"),
+        ({"offset": 2, "end_line": 4}, "not both"),
+        ({"limit": 0}, "'limit' must be >= 1"),
+    ],
+)
+def test_parse_read_range_checks_bounds(args: dict[str, object], message: str) -> None:
+    with pytest.raises(ToolRetryError, match=message):
+        parse_read_range(args)
+
+
+def test_parse_read_range_converts_lines_to_offset() -> None:
+    read_range = parse_read_range({"start_line": 3, "end_line": 7})
+
+    assert (read_range.offset, read_range.limit, read_range.explicit) == (2, 5, True)
+    assert parse_read_range({}).explicit is False
+
+
+PNG_HEADER = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR"
+
+
+async def test_binary_file_is_summarized_not_dumped(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = tmp_path / "logo.png"
+    target.write_bytes(PNG_HEADER + bytes(range(256)))
+
+    text, details = await _read({"filepath": str(target)})
+
+    assert text.startswith(f"Binary file '{target}': {target.stat().st_size} bytes")
+    assert "detected type: PNG image" in text
+    assert details["binary"] is True
+    assert details["detected_type"] == "PNG image"
+
+
+async def test_binary_file_as_base64_respects_max_bytes(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = tmp_path / "logo.png"
+    target.write_bytes(PNG_HEADER + bytes(range(256)))
+
+    text, details = await _read({"filepath": str(target), "as_base64": True, "max_bytes": 8})
+
+    assert base64.b64decode(text.splitlines()[1]) == PNG_HEADER[:8]
+    assert details["returned_bytes"] == 8
+    assert details["encoding"] == "base64"
+
+
+async def test_utf16_file_with_bom_reads_as_text(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    _limits(monkeypatch, max_bytes=10_000, head=3, tail=2)
+    target = tmp_path / "wide.txt"
+    target.write_text("first\nsecond\n", encoding="utf-16")
+
+    text, details = await _read({"filepath": str(target)})
+
+    assert [line.split("|", 1)[1] for line in text.splitlines()[1:3]] == ["first", "second"]
+    assert details["encoding"] == "utf-16"
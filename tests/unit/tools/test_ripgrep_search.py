@@ -0,0 +1,104 @@
+"""Tests for structured search through the ripgrep binary."""
+
+from __future__ import annotations
+
+import shutil
+from pathlib import Path
+
+import pytest
+
+from tunacode.tools.utils import ripgrep_search
+from tunacode.tools.utils.ripgrep_search import (
+    RipgrepEventCollector,
+    SearchMatch,
+    SearchQuery,
+    build_ripgrep_command,
+    search_with_ripgrep,
+)
+
+
+def _line(kind: str, path: str, number: int, text: str) -> dict[str, object]:
+    return {
+        "type": kind,
+        "data": {"path": {"text": path}, "lines": {"text": text}, "line_number": number},
+    }
+
+
+def _file_events(
+    path: str, *lines: dict[str, object], binary_offset: int | None = None
+) -> list[dict[str, object]]:
+    return [
+        {"type": "begin", "data": {"path": {"text": path}}},
+        *lines,
+        {"type": "end", "data": {"path": {"text": path}, "binary_offset": binary_offset}},
+    ]
+
+
+def test_command_puts_ignore_defaults_after_includes_and_ends_with_the_path() -> None:
+    query = SearchQuery(pattern="-x", path="src", include=("*.py",), context_lines=2)
+
+    command = build_ripgrep_command(Path("rg"), query)
+
+    assert command[-4:] == ["--regexp", "-x", "--", "src"]
+    assert command.index("*.py") < command.index("!.git/")
+    assert ["--context", "2"] == command[command.index("--context") :][:2]
+    assert "--no-require-git" in command
+    assert build_ripgrep_command(Path("rg"), SearchQuery(pattern="x", path="."))[-1] == "--"
+
+
+def test_collector_builds_context_skips_binary_files_and_caps_matches() -> None:
+    collector = RipgrepEventCollector(context_lines=1, max_matches=2)
+    events = [
+        *_file_events(
+            "./src/app.py",
+            _line("context", "./src/app.py", 2, "\n"),
+            _line("match", "./src/app.py", 3, "def main():\n"),
+            _line("context", "./src/app.py", 4, "    return 1\r\n"),
+        ),
+        *_file_events(
+            "blob.bin", _line("match", "blob.bin", 1, "def main\n"), binary_offset=9
+        ),
+        *_file_events("b.py", _line("match", "b.py", 1, "def b\n")),
+        *_file_events("c.py", _line("match", "c.py", 1, "def c\n")),
+    ]
+
+    accepted = [collector.feed(event) for event in events]
+    outcome = collector.finish()
+
+    assert accepted[-1] is False
+    assert outcome.matches == [
+        SearchMatch("src/app.py", 3, "def main():", before=("",), after=("    return 1",)),
+        SearchMatch("b.py", 1, "def b"),
+    ]
+    assert outcome.binary_files_skipped == 1
+    assert outcome.truncated is True
+    assert outcome.files_searched == 4
+
+
+async def test_search_without_binary_requests_the_fallback(
+    monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+) -> None:
+    monkeypatch.setattr(ripgrep_search, "get_ripgrep_binary_path", lambda: None)
+
+    assert await search_with_ripgrep(SearchQuery(pattern="x", path=""), tmp_path) is None
+
+
+@pytest.mark.skipif(shutil.which("rg") is None, reason="ripgrep is not installed")
+async def test_search_with_real_ripgrep_matches_the_in_process_results(
+    monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+) -> None:
+    rg = Path(shutil.which("rg") or "rg")
+    monkeypatch.setattr(ripgrep_search, "get_ripgrep_binary_path", lambda: rg)
+    root = tmp_path / "ws"
+    (root / "src").mkdir(parents=True)
+    (root / "src" / "app.py").write_text("import os\n\ndef main():\n    return 1\n")
+    (root / "ignored.py").write_text("def main\n")
+    (root / ".gitignore").write_text("ignored.py\n")
+    query = SearchQuery(pattern="def main", path="", include=("*.py",), context_lines=1)
+
+    outcome = await search_with_ripgrep(query, root)
+
+    assert outcome is not None
+    assert outcome.matches == [
+        SearchMatch("src/app.py", 3, "def main():", before=("",), after=("    return 1",))
+    ]
@@ -0,0 +1,118 @@
+"""Tests for trailing-newline detection in read_file and preservation in hashline_edit."""
+
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+
+from tunacode.exceptions import ToolRetryError
+
+from tunacode.tools import line_cache
+from tunacode.tools.hashline import content_hash
+from tunacode.tools.hashline_edit import hashline_edit
+from tunacode.tools.read_file import read_file
+
+
+async def _read(target: Path) -> tuple[str, dict[str, object]]:
+    line_cache.clear()
+    result = await read_file.execute(
+        "call-0", {"filepath": str(target)}, None, lambda _update: None
+    )
+    return result.content[0].text, result.details
+
+
+async def _edit(target: Path, args: dict[str, object]) -> str:
+    await _read(target)
+    result = await hashline_edit.execute(
+        "call-1", {"filepath": str(target), **args}, None, lambda _update: None
+    )
+    return result.content[0].text
+
+
+async def test_read_file_reports_a_missing_final_newline(tmp_path: Path) -> None:
+    target = tmp_path / "no_eol.txt"
+    target.write_text("one\ntwo", encoding="utf-8")
+
+    text, details = await _read(target)
+
+    assert "(End of file - total 2 lines, no newline at end)" in text
+    assert details["ends_with_newline"] is False
+
+    target.write_text("one\ntwo\n", encoding="utf-8")
+    text, details = await _read(target)
+    assert "(End of file - total 2 lines)" in text
+    assert details["ends_with_newline"] is True
+
+
+@pytest.mark.parametrize(
+    "args",
+    [
+        {"operation": "replace", "line": f"2:{content_hash('two')}", "new": "TWO"},
+        {"operation": "replace", "line": f"2:{content_hash('two')}", "new": "TWO\n"},
+        {
+            "operation": "replace_range",
+            "start": f"1:{content_hash('one')}",
+            "end": f"2:{content_hash('two')}",
+            "new": "ONE\nTWO\n",
+        },
+        {"operation": "insert_after", "after": f"1:{content_hash('one')}", "new": "between"},
+    ],
+)
+async def test_edits_do_not_add_a_final_newline(tmp_path: Path, args: dict[str, object]) -> None:
+    target = tmp_path / "no_eol.txt"
+    target.write_text("one\ntwo", encoding="utf-8")
+
+    await _edit(target, args)
+
+    assert not target.read_bytes().endswith(b"\n")
+
+
+async def test_edits_do_not_strip_a_final_newline(tmp_path: Path) -> None:
+    target = tmp_path / "eol.txt"
+    target.write_text("one\ntwo\n", encoding="utf-8")
+
+    await _edit(target, {"operation": "replace", "line": f"2:{content_hash('two')}", "new": "2"})
+
+    assert target.read_text(encoding="utf-8") == "one\n2\n"
+
+
+async def test_trailing_newline_can_be_changed_explicitly(tmp_path: Path) -> None:
+    target = tmp_path / "crlf.txt"
+    target.write_bytes(b"one\r\ntwo")
+
+    output = await _edit(
+        target,
+        {
+            "operation": "replace",
+            "line": f"2:{content_hash('two')}",
+            "new": "two",
+            "trailing_newline": True,
+        },
+    )
+
+    assert target.read_bytes() == b"one\r\ntwo\r\n"
+    assert "Added a newline at end of file." in output
+
+    output = await _edit(
+        target,
+        {
+            "operation": "replace",
+            "line": f"1:{content_hash('one')}",
+            "new": "one",
+            "trailing_newline": False,
+        },
+    )
+    assert target.read_bytes() == b"one\r\ntwo"
+    assert "Removed the newline at end of file." in output
+
+
+async def test_trailing_newline_must_be_a_boolean(tmp_path: Path) -> None:
+    target = tmp_path / "file.txt"
+    target.write_text("one\n", encoding="utf-8")
+
+    with pytest.raises(ToolRetryError, match="'trailing_newline' must be a boolean"):
+        await _edit(
+            target,
+            {"operation": "replace", "line": f"1:{content_hash('one')}", "trailing_newline": "no"},
+        )
@@ -0,0 +1,47 @@
+"""Tests for code block wrap modes."""
+
+from __future__ import annotations
+
+from rich.cells import cell_len
+from rich.text import Text
+
+from tunacode.constants import CodeWrapMode
+
+from tunacode.ui.renderers.tools.syntax_utils import CONTINUATION_INDICATOR, fit_code_lines
+
+
+def _styled_line() -> Text:
+    line = Text("x = ")
+    line.append("'abcdefghij'", style="green")
+    return line
+
+
+def test_wrap_mode_marks_continuations_and_keeps_highlighting() -> None:
+    fitted = fit_code_lines(_styled_line(), 8, CodeWrapMode.WRAP)
+
+    lines = fitted.plain.split("\n")
+    assert lines == ["x = 'abc", f"{CONTINUATION_INDICATOR}defghi", f"{CONTINUATION_INDICATOR}j'"]
+    assert all(cell_len(line) <= 8 for line in lines)
+    green_text = "".join(
+        fitted.plain[span.start : span.end] for span in fitted.spans if span.style == "green"
+    )
+    assert green_text == "'abcdefghij'"
+
+
+def test_wrap_mode_measures_wide_characters_in_cells() -> None:
+    fitted = fit_code_lines(Text("日本語テキスト"), 6, CodeWrapMode.WRAP)
+
+    lines = fitted.plain.split("\n")
+    assert lines[0] == "日本語"
+    assert all(cell_len(line) <= 6 for line in lines)
+
+
+def test_truncate_mode_adds_ellipsis_and_scroll_leaves_lines_whole() -> None:
+    code = Text("short\n" + "y" * 20)
+
+    truncated = fit_code_lines(code.copy(), 10, CodeWrapMode.TRUNCATE)
+    scrolled = fit_code_lines(code.copy(), 10, CodeWrapMode.SCROLL)
+
+    assert truncated.plain == "short\n" + "y" * 9 + "…"
+    assert scrolled.plain == code.plain
+    assert scrolled.no_wrap is True
@@ -0,0 +1,38 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+
+from tunacode.core.session import StateManager
+
+from tunacode.ui.commands import compact
+from tunacode.ui.commands.compact import COMPACT_BUSY_NOTICE, CompactCommand
+
+
+@pytest.mark.asyncio
+async def test_compact_is_rejected_while_a_request_is_running(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    notices: list[str] = []
+    monkeypatch.setattr(
+        compact,
+        "get_or_create_compaction_controller",
+        lambda _state_manager: pytest.fail("compaction must not start mid-turn"),
+    )
+    app = SimpleNamespace(
+        state_manager=StateManager(),
+        request_in_flight=True,
+        notify=lambda message, severity="information": notices.append(message),
+    )
+
+    await CompactCommand().execute(app, "")  # type: ignore[arg-type]
+
+    assert notices == [COMPACT_BUSY_NOTICE]
+
+
+def test_summary_preview_is_bounded() -> None:
+    preview = compact._summary_preview("x" * 5_000)
+
+    assert preview.startswith("Summary:\n")
+    assert len(preview) <= len("Summary:\n") + compact.COMPACT_SUMMARY_PREVIEW_CHARS
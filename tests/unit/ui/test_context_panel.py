@@ -5,7 +5,10 @@ from typing import cast
 
 from textual.dom import DOMNode
 
-from tunacode.ui.context_panel import is_widget_within_field
+from tunacode.types import ToolProgress
+from tunacode.types.callbacks import STEP_COMPLETED, STEP_FAILED, STEP_STARTED
+
+from tunacode.ui.context_panel import build_progress_field, is_widget_within_field
 
 
 @dataclass
@@ -36,3 +39,21 @@ def test_is_widget_within_field_stops_at_root_boundary() -> None:
         cast(DOMNode, sibling),
         field_id="root",
     )
+
+
+def test_build_progress_field_counts_finished_steps() -> None:
+    steps = [
+        ToolProgress("c1", "bash: pytest", STEP_COMPLETED),
+        ToolProgress("c2", "read_file: a.py", STEP_FAILED),
+        ToolProgress("c3", "grep: TODO", STEP_STARTED),
+    ]
+
+    title, content = build_progress_field(steps)
+
+    assert title == "Progress [2/3]"
+    assert content.plain.splitlines() == [
+        "✓ bash: pytest",
+        "✗ read_file: a.py",
+        "◌ grep: TODO",
+    ]
+    assert build_progress_field([])[0] == "Progress"
@@ -0,0 +1,86 @@
+"""Tests for composer draft persistence, expiry and debouncing."""
+
+from __future__ import annotations
+
+import os
+import stat
+from collections.abc import Callable
+from pathlib import Path
+
+from tunacode.ui.drafts import DraftAutosave, DraftStore, prune_expired_drafts
+
+
+def _store(tmp_path: Path, *, max_age_hours: int = 24) -> DraftStore:
+    return DraftStore.for_project(tmp_path, "project-a", max_age_hours=max_age_hours)
+
+
+def test_saved_draft_round_trips_with_owner_only_permissions(tmp_path: Path) -> None:
+    store = _store(tmp_path)
+    store.save("half-written\nprompt", now=1_000.0)
+
+    draft = store.load(now=1_060.0)
+
+    assert draft is not None
+    assert draft.text == "half-written\nprompt"
+    assert store.path == tmp_path / "drafts" / "project-a.json"
+    assert stat.S_IMODE(store.path.stat().st_mode) == 0o600
+
+
+def test_expired_draft_is_deleted_instead_of_offered(tmp_path: Path) -> None:
+    store = _store(tmp_path, max_age_hours=1)
+    store.save("old", now=0.0)
+
+    assert store.load(now=3_601.0) is None
+    assert not store.path.exists()
+
+
+def test_saving_empty_text_removes_the_draft(tmp_path: Path) -> None:
+    store = _store(tmp_path)
+    store.save("something", now=0.0)
+    store.save("   ", now=1.0)
+
+    assert not store.path.exists()
+
+
+def test_prune_removes_only_expired_drafts_of_any_project(tmp_path: Path) -> None:
+    drafts_dir = tmp_path / "drafts"
+    drafts_dir.mkdir()
+    (drafts_dir / "old.json").write_text("{}")
+    (drafts_dir / "new.json").write_text("{}")
+    os.utime(drafts_dir / "old.json", (0, 0))
+
+    removed = prune_expired_drafts(drafts_dir, max_age_hours=24, now=100_000.0)
+
+    assert removed == 1
+    assert [path.name for path in drafts_dir.iterdir()] == ["new.json"]
+
+
+class _FakeTimer:
+    def __init__(self, callback: Callable[[], None]) -> None:
+        self.callback = callback
+        self.stopped = False
+
+    def stop(self) -> None:
+        self.stopped = True
+
+
+def test_autosave_debounces_and_discard_clears(tmp_path: Path) -> None:
+    store = _store(tmp_path)
+    timers: list[_FakeTimer] = []
+
+    def set_timer(_delay: float, callback: Callable[[], None]) -> _FakeTimer:
+        timers.append(_FakeTimer(callback))
+        return timers[-1]
+
+    autosave = DraftAutosave(store, debounce_seconds=1.0, set_timer=set_timer)
+    autosave.schedule("dra")
+    autosave.schedule("draft")
+
+    assert timers[0].stopped is True
+    assert not store.path.exists()
+    timers[-1].callback()
+    loaded = store.load()
+    assert loaded is not None and loaded.text == "draft"
+
+    autosave.discard()
+    assert not store.path.exists()
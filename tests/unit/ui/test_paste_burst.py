@@ -0,0 +1,34 @@
+"""Tests for reassembling a paste that arrived as keystrokes."""
+
+from __future__ import annotations
+
+from tunacode.ui.widgets.paste_burst import PasteBurst, normalize_newlines
+
+
+def test_enter_right_after_a_keystroke_is_part_of_a_paste() -> None:
+    burst = PasteBurst(window_seconds=0.01)
+    burst.note_keystroke(1.000)
+
+    assert burst.follows_keystroke(1.005) is True
+    assert burst.follows_keystroke(1.200) is False
+
+
+def test_enter_with_no_prior_keystroke_submits() -> None:
+    assert PasteBurst().follows_keystroke(5.0) is False
+
+
+def test_take_joins_lines_with_the_trailing_text_and_resets() -> None:
+    burst = PasteBurst(window_seconds=0.01)
+    burst.note_keystroke(1.000)
+    burst.push_line("first", 1.001)
+    burst.push_line("", 1.002)
+
+    assert burst.active is True
+    assert burst.follows_keystroke(1.003) is True
+    assert burst.take("last") == "first\n\nlast"
+    assert burst.active is False
+    assert burst.follows_keystroke(1.004) is False
+
+
+def test_normalize_newlines_converts_crlf_and_cr() -> None:
+    assert normalize_newlines("a\r\nb\rc\n") == "a\nb\nc\n"
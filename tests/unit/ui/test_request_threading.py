@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import asyncio
 import inspect
 from unittest.mock import AsyncMock, patch
 
@@ -189,6 +190,34 @@ async def test_request_ui_bridge_drains_all_chunks_in_order() -> None:
     assert bridge.drain_thinking().has_data is False
 
 
+async def test_request_ui_bridge_applies_backpressure_until_drained() -> None:
+    bridge = RequestUiBridge(_FakeBridgeApp(), max_buffered_chars=4)
+
+    await bridge.streaming_callback("hello")
+    blocked = asyncio.ensure_future(bridge.thinking_callback("next"))
+    await asyncio.sleep(bridge.BACKPRESSURE_POLL_SECONDS * 3)
+    assert blocked.done() is False
+    assert bridge.buffered_chars == 5
+
+    assert bridge.drain_streaming().text == "hello"
+    await asyncio.wait_for(blocked, timeout=1.0)
+    assert bridge.drain_thinking().text == "next"
+    assert bridge.buffered_chars == 0
+
+
+async def test_request_ui_bridge_close_releases_waiting_producer() -> None:
+    bridge = RequestUiBridge(_FakeBridgeApp(), max_buffered_chars=1)
+    await bridge.streaming_callback("full")
+    blocked = asyncio.ensure_future(bridge.streaming_callback("dropped"))
+    await asyncio.sleep(bridge.BACKPRESSURE_POLL_SECONDS * 3)
+
+    bridge.close()
+    await asyncio.wait_for(blocked, timeout=1.0)
+    await bridge.streaming_callback("after close")
+
+    assert bridge.drain_streaming().text == "full"
+
+
 async def test_flush_timer_applies_queued_deltas_to_streaming_handler() -> None:
     app = TextualReplApp(state_manager=StateManager())
     app._request_bridge = RequestUiBridge(_FakeBridgeApp())
@@ -0,0 +1,47 @@
+"""Tests for incremental markdown rendering of streamed assistant text."""
+
+from __future__ import annotations
+
+from rich.console import Console, Group
+from rich.markdown import Markdown
+
+from tunacode.ui.renderers.streaming_markdown import StreamingMarkdown, close_partial_markdown
+
+
+def _render(renderable: object, width: int = 40) -> str:
+    console = Console(width=width, file=None, record=True, color_system=None)
+    console.print(renderable)
+    return console.export_text()
+
+
+def test_unclosed_code_fence_is_closed_as_code_in_progress() -> None:
+    assert close_partial_markdown("intro\n```python\nx = 1") == "intro\n```python\nx = 1\n```"
+    assert close_partial_markdown("```\ncode\n```\n") == "```\ncode\n```\n"
+
+
+def test_partial_inline_code_and_table_rows() -> None:
+    assert close_partial_markdown("run `pytest -") == "run `pytest -`"
+    assert close_partial_markdown("| a | b |\n| --- | --- |\n| 1 | 2") == "| a | b |\n| --- | --- |"
+
+
+def test_settled_blocks_are_cached_and_only_the_tail_changes() -> None:
+    stream = StreamingMarkdown()
+    stream.append("First paragraph.\n\nSecond")
+    first = stream.renderable()
+    stream.append(" paragraph grows")
+    second = stream.renderable()
+
+    assert isinstance(first, Group) and isinstance(second, Group)
+    assert first.renderables[0] is second.renderables[0]
+    assert "Second paragraph grows" in _render(second)
+
+
+def test_blank_lines_inside_open_fence_do_not_settle() -> None:
+    stream = StreamingMarkdown()
+    stream.append("```\nline one\n\nline two")
+
+    renderable = stream.renderable()
+
+    assert isinstance(renderable, Markdown)
+    output = _render(renderable)
+    assert "line one" in output and "line two" in output
@@ -0,0 +1,69 @@
+"""Tests for terminal capability detection and its config overrides."""
+
+from __future__ import annotations
+
+import os
+
+import pytest
+
+from tunacode.constants import TerminalColorMode
+
+from tunacode.ui.terminal import (
+    TerminalCapabilities,
+    detect_color_mode,
+    detect_unicode,
+    resolve_terminal_capabilities,
+    textual_environment,
+    truncate_cells,
+)
+
+
+@pytest.mark.parametrize(
+    ("environ", "expected"),
+    [
+        ({"NO_COLOR": "1", "COLORTERM": "truecolor"}, TerminalColorMode.NONE),
+        ({"NO_COLOR": "", "COLORTERM": "truecolor"}, TerminalColorMode.TRUECOLOR),
+        ({"TERM": "dumb"}, TerminalColorMode.NONE),
+        ({"TERM": "xterm-256color"}, TerminalColorMode.COLOR_256),
+        ({"TERM": "xterm-256color", "COLORTERM": "24bit"}, TerminalColorMode.TRUECOLOR),
+        ({"TERM": "xterm"}, TerminalColorMode.COLOR_16),
+    ],
+)
+def test_color_mode_follows_the_environment(
+    environ: dict[str, str], expected: TerminalColorMode
+) -> None:
+    assert detect_color_mode(environ) is expected
+
+
+def test_unicode_needs_a_utf8_encoding_or_locale() -> None:
+    assert detect_unicode({}, "UTF-8") is True
+    assert detect_unicode({"LANG": "en_US.UTF-8"}, "ascii") is True
+    assert detect_unicode({"LANG": "C"}, "ascii") is False
+
+
+def test_settings_override_detection() -> None:
+    capabilities = resolve_terminal_capabilities(
+        {"color": "256", "unicode": "off"},
+        environ={"COLORTERM": "truecolor", "LANG": "en_US.UTF-8"},
+    )
+
+    assert capabilities.color is TerminalColorMode.COLOR_256
+    assert capabilities.unicode is False
+    assert capabilities.ellipsis == "..."
+
+
+def test_textual_environment_sets_no_color_and_restores_it(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    monkeypatch.delenv("NO_COLOR", raising=False)
+    capabilities = TerminalCapabilities(color=TerminalColorMode.NONE, unicode=True, width=80)
+
+    with textual_environment(capabilities):
+        assert os.environ["NO_COLOR"] == "1"
+
+    assert "NO_COLOR" not in os.environ
+
+
+def test_truncate_cells_counts_wide_characters_twice() -> None:
+    assert truncate_cells("日本語のテキスト", 10) == "日本語..."
+    assert truncate_cells("short", 10) == "short"
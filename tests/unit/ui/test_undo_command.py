@@ -0,0 +1,26 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+
+from tunacode.ui.commands import undo
+from tunacode.ui.commands.undo import UNDO_BUSY_NOTICE, UndoCommand
+
+
+@pytest.mark.asyncio
+async def test_undo_is_rejected_while_a_request_is_running(
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    notices: list[str] = []
+    monkeypatch.setattr(
+        undo, "undo_last_turn", lambda: pytest.fail("undo must not run mid-turn")
+    )
+    app = SimpleNamespace(
+        request_in_flight=True,
+        notify=lambda message, severity="information": notices.append(message),
+    )
+
+    await UndoCommand().execute(app, "")  # type: ignore[arg-type]
+
+    assert notices == [UNDO_BUSY_NOTICE]
@@ -0,0 +1,41 @@
+from __future__ import annotations
+
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tunacode.core.watch.watcher import FileWatcher
+
+from tunacode.ui.commands.watch import WatchCommand
+
+
+@pytest.mark.asyncio
+async def test_watch_uses_the_configured_limits(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    root = tmp_path / "ws"
+    root.mkdir()
+    source = root / "app.py"
+    source.write_text("v0", encoding="utf-8")
+    monkeypatch.chdir(root)
+    started: list[FileWatcher] = []
+    notices: list[str] = []
+    settings = {"watch": {"debounce_seconds": 0.0, "cooldown_seconds": 0.0, "max_runs": 2}}
+    app = SimpleNamespace(
+        watch_mode=SimpleNamespace(watcher=None, start=started.append),
+        state_manager=SimpleNamespace(session=SimpleNamespace(user_config={"settings": settings})),
+        notify=notices.append,
+    )
+
+    await WatchCommand().execute(app, "*.py")  # type: ignore[arg-type]
+
+    [watcher] = started
+    assert notices == ["Watching *.py (max 2 runs)"]
+    # No debounce or cooldown: each batch runs on the next poll, until max_runs is used up.
+    prompts: list[str | None] = []
+    for content in ("v1", "v22", "v333"):
+        source.write_text(content, encoding="utf-8")
+        assert watcher.poll(now=1.0) is None
+        prompts.append(watcher.poll(now=1.0))
+    assert [prompt is not None for prompt in prompts] == [True, True, False]
@@ -0,0 +1,58 @@
+from __future__ import annotations
+
+import asyncio
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tunacode.core.watch.watcher import FileWatcher
+
+from tunacode.ui.watch_mode import WatchMode
+
+
+def _fake_app(*, busy: bool) -> SimpleNamespace:
+    posted: list[object] = []
+    return SimpleNamespace(
+        request_in_flight=busy,
+        request_queue=asyncio.Queue(),
+        post_message=posted.append,
+        notify=lambda _message: None,
+        posted=posted,
+    )
+
+
+@pytest.mark.asyncio
+async def test_tick_rebaselines_while_a_request_is_in_flight(tmp_path: Path) -> None:
+    root = tmp_path / "ws"
+    root.mkdir()
+    test_file = root / "test_app.py"
+    test_file.write_text("v0", encoding="utf-8")
+    watcher = FileWatcher(root=root, globs=["*.py"], debounce_seconds=0.0)
+    app = _fake_app(busy=True)
+    watch_mode = WatchMode(app)  # type: ignore[arg-type]
+    watch_mode._watcher = watcher
+
+    test_file.write_text("edited by the agent", encoding="utf-8")
+    await watch_mode._tick()
+    app.request_in_flight = False
+    await watch_mode._tick()
+    await watch_mode._tick()
+
+    assert app.posted == []
+    assert watcher.runs == 0
+
+
+@pytest.mark.asyncio
+async def test_tick_skips_while_the_previous_scan_is_running(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    watcher = FileWatcher(root=tmp_path, globs=["*.py"])
+    watch_mode = WatchMode(_fake_app(busy=False))  # type: ignore[arg-type]
+    watch_mode._watcher = watcher
+    watch_mode._scanning = True
+    monkeypatch.setattr(
+        FileWatcher, "scan", lambda _self: pytest.fail("a second scan must not start")
+    )
+
+    await watch_mode._tick()
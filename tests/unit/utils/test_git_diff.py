@@ -0,0 +1,93 @@
+from __future__ import annotations
+
+from tunacode.utils.system.git_diff import (
+    STATUS_ADDED,
+    STATUS_DELETED,
+    STATUS_MODIFIED,
+    STATUS_RENAMED,
+    format_numbered_diff,
+    parse_git_diff,
+)
+
+MULTI_HUNK_DIFF = """\
+diff --git a/src/foo.py b/src/foo.py
+index 1111111..2222222 100644
+--- a/src/foo.py
++++ b/src/foo.py
+@@ -1,4 +1,5 @@ import os
+ import sys
++import json
+ 
+ def main():
+-    return 1
++    return 2
+@@ -20,3 +21,4 @@ def helper():
+     a = 1
+--- not a header
++    b = 2
++    c = 3
+     return a
+\\ No newline at end of file
+"""
+
+EXTENDED_HEADERS_DIFF = """\
+diff --git a/old name.py b/new name.py
+old mode 100644
+new mode 100755
+similarity index 92%
+rename from old name.py
+rename to new name.py
+diff --git a/assets/logo.png b/assets/logo.png
+index 3333333..4444444 100644
+Binary files a/assets/logo.png and b/assets/logo.png differ
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+index 5555555..0000000
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/new.txt b/new.txt
+new file mode 100644
+index 0000000..6666666
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++hello
+"""
+
+
+def test_line_numbers_track_across_hunks() -> None:
+    [file_diff] = parse_git_diff(MULTI_HUNK_DIFF)
+
+    assert file_diff.path == "src/foo.py"
+    assert file_diff.status == STATUS_MODIFIED
+    assert [(line.new_lineno, line.text) for line in file_diff.added_lines()] == [
+        (2, "import json"),
+        (5, "    return 2"),
+        (22, "    b = 2"),
+        (23, "    c = 3"),
+    ]
+    assert [(line.old_lineno, line.text) for line in file_diff.removed_lines()] == [
+        (4, "    return 1"),
+        (21, "-- not a header"),
+    ]
+    assert file_diff.hunks[1].section == "def helper():"
+    assert "src/foo.py:22 +     b = 2" in format_numbered_diff([file_diff])
+
+
+def test_extended_headers_renames_modes_binary_and_add_delete() -> None:
+    renamed, binary, deleted, added = parse_git_diff(EXTENDED_HEADERS_DIFF)
+
+    assert (renamed.status, renamed.old_path, renamed.new_path) == (
+        STATUS_RENAMED,
+        "old name.py",
+        "new name.py",
+    )
+    assert (renamed.old_mode, renamed.new_mode, renamed.similarity) == ("100644", "100755", 92)
+    assert renamed.hunks == []
+    assert binary.is_binary and binary.path == "assets/logo.png"
+    assert (deleted.status, deleted.path, deleted.new_path) == (STATUS_DELETED, "gone.txt", None)
+    assert deleted.removed_lines()[0].old_lineno == 1
+    assert (added.status, added.old_path) == (STATUS_ADDED, None)
+    assert added.added_lines()[0].new_lineno == 1
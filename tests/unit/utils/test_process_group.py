@@ -0,0 +1,60 @@
+"""Tests for killing a shell command's whole process group at scope end."""
+
+from __future__ import annotations
+
+import asyncio
+import os
+import sys
+from pathlib import Path
+
+import pytest
+
+from tunacode.utils.system.process_group import ProcessScope, new_process_group_kwargs
+
+pytestmark = pytest.mark.skipif(sys.platform == "win32", reason="POSIX process groups")
+
+
+def _is_alive(pid: int) -> bool:
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    stat_path = Path(f"/proc/{pid}/stat")
+    if stat_path.exists():
+        return stat_path.read_text().split(") ", 1)[1][0] != "Z"
+    return True
+
+
+async def _wait_until_dead(pid: int) -> bool:
+    for _ in range(50):
+        if not _is_alive(pid):
+            return True
+        await asyncio.sleep(0.05)
+    return False
+
+
+async def test_scope_kills_backgrounded_descendants_after_the_shell_exits() -> None:
+    process = await asyncio.create_subprocess_shell(
+        "sleep 30 >/dev/null 2>&1 & echo $!",
+        stdout=asyncio.subprocess.PIPE,
+        **new_process_group_kwargs(),
+    )
+    scope = ProcessScope()
+    scope.register(process)
+    stdout, _ = await process.communicate()
+    background_pid = int(stdout.decode().strip())
+
+    assert _is_alive(background_pid)
+    assert scope.kill_all() == 1
+    assert await _wait_until_dead(background_pid)
+    assert len(scope) == 0
+
+
+async def test_kill_all_ignores_groups_that_already_exited() -> None:
+    process = await asyncio.create_subprocess_shell("true", **new_process_group_kwargs())
+    await process.wait()
+    scope = ProcessScope()
+    scope.register(process)
+
+    assert scope.kill_all() == 1
+    assert scope.kill_all() == 0
@@ -0,0 +1,62 @@
+"""Tests for the pre-flight request token estimate."""
+
+from __future__ import annotations
+
+from tinyagent.agent_types import (
+    AgentTool,
+    AssistantMessage,
+    TextContent,
+    ToolCallContent,
+    UserMessage,
+)
+
+from tunacode.utils.messaging.request_tokens import (
+    CHAT_COMPLETIONS_OVERHEAD,
+    WIRE_OVERHEADS,
+    estimate_request_tokens,
+)
+
+
+def _count_chars(text: str) -> int:
+    return len(text)
+
+
+def test_estimate_splits_the_request_into_its_parts() -> None:
+    history = [
+        UserMessage(content=[TextContent(text="hello")], timestamp=None),
+        AssistantMessage(
+            content=[ToolCallContent(id="c1", name="grep", arguments={"q": 1})],
+            stop_reason="tool_calls",
+            timestamp=None,
+        ),
+    ]
+    tool = AgentTool(name="grep", label="grep", description="Search.", parameters={})
+
+    estimate = estimate_request_tokens(
+        system_prompt="system!",
+        messages=history,
+        tools=[tool],
+        next_message="next",
+        count_tokens=_count_chars,
+    )
+
+    overhead = CHAT_COMPLETIONS_OVERHEAD
+    assert estimate.system == 7
+    assert estimate.history == len("hello") + len('grep{"q": 1}')
+    assert estimate.tools == len("grepSearch.{}")
+    assert estimate.next_message == 4
+    assert estimate.overhead == (
+        4 * overhead.per_message + overhead.reply_priming + overhead.tools_base + overhead.per_tool
+    )
+    assert estimate.total == 7 + 17 + 13 + 4 + estimate.overhead
+
+
+def test_overhead_follows_the_wire_api_and_falls_back_for_unknown_ones() -> None:
+    def _overhead(wire_api: str) -> int:
+        return estimate_request_tokens(
+            system_prompt="", messages=[], next_message="hi", wire_api=wire_api
+        ).overhead
+
+    native = WIRE_OVERHEADS["ollama-chat"]
+    assert _overhead("ollama-chat") == native.per_message + native.reply_priming
+    assert _overhead("unknown-api") == _overhead("openai-completions")
@@ -0,0 +1,45 @@
+"""Tests for resolving the configured shell program and its flags."""
+
+from __future__ import annotations
+
+import pytest
+
+from tunacode.utils.system import shell_program
+from tunacode.utils.system.shell_program import resolve_shell, shell_command_flags
+
+
+@pytest.mark.parametrize(
+    ("program", "flags"),
+    [
+        ("zsh", ("-c",)),
+        ("/usr/bin/fish", ("-c",)),
+        ("pwsh", ("-NoProfile", "-NonInteractive", "-Command")),
+        ("C:\\Windows\\powershell.exe", ("-NoProfile", "-NonInteractive", "-Command")),
+        ("cmd.exe", ("/d", "/s", "/c")),
+    ],
+)
+def test_flags_follow_the_shell_name(program: str, flags: tuple[str, ...]) -> None:
+    assert shell_command_flags(program) == flags
+
+
+def test_configured_args_replace_the_default_flags() -> None:
+    invocation = resolve_shell("bash", ["-lc"])
+
+    assert invocation.argv("ls") == ["bash", "-lc", "ls"]
+
+
+def test_posix_default_falls_back_to_sh_without_bash(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(shell_program, "IS_WINDOWS", False)
+    monkeypatch.setattr(shell_program.shutil, "which", lambda _name: None)
+
+    invocation = resolve_shell("", [])
+
+    assert invocation.argv("ls") == ["/bin/sh", "-c", "ls"]
+    assert invocation.is_available() is False
+
+
+def test_windows_default_prefers_pwsh(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setattr(shell_program, "IS_WINDOWS", True)
+    monkeypatch.setattr(shell_program.shutil, "which", lambda name: f"C:\\bin\\{name}.exe")
+
+    assert resolve_shell("", []).program == "pwsh"